@@ -4,6 +4,7 @@ use camino::{Utf8Path, Utf8PathBuf};
 use directories_next::ProjectDirs;
 use rover_print::print::{Print, PrintExt};
 use rover_std::Fs;
+use rover_storage::secret::SecretBackend;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -11,6 +12,9 @@ use crate::{
     HoustonProblem,
 };
 
+/// The name of the profile used when no `--profile` is given.
+const DEFAULT_PROFILE: &str = "default";
+
 /// Config allows end users to override default settings
 /// usually determined by Houston. They are intended to
 /// give library consumers a way to support environment variable
@@ -120,6 +124,178 @@ impl Config {
     fn get_elv2_toml_path(&self) -> Utf8PathBuf {
         self.home.join("elv2_license.toml")
     }
+
+    /// Makes `name` the profile that the `default` profile resolves to, so
+    /// commands run without `--profile` use it. Switching back to `default`
+    /// itself removes the override.
+    pub fn set_default_profile(&self, name: &str) -> Result<(), HoustonProblem> {
+        let toml_path = self.get_default_profile_toml_path();
+        if name == DEFAULT_PROFILE {
+            if toml_path.exists() {
+                std::fs::remove_file(toml_path.as_std_path())?;
+            }
+            return Ok(());
+        }
+        let default_profile_toml = DefaultProfileToml {
+            name: name.to_string(),
+        };
+        let contents = toml::to_string(&default_profile_toml)?;
+        Fs::write_file(toml_path, contents)?;
+        Ok(())
+    }
+
+    /// Retrieves the profile set by [`Config::set_default_profile`], if any.
+    pub fn default_profile(&self) -> Option<String> {
+        let toml_path = self.get_default_profile_toml_path();
+        let contents = Fs::read_file(toml_path).ok()?;
+        toml::from_str::<DefaultProfileToml>(&contents)
+            .ok()
+            .map(|default_profile_toml| default_profile_toml.name)
+    }
+
+    /// Resolves a profile name to the profile it refers to: `default` points
+    /// at whichever profile was last switched to, every other name is itself.
+    pub fn resolve_profile(&self, name: &str) -> String {
+        if name == DEFAULT_PROFILE {
+            if let Some(default_profile) = self.default_profile() {
+                return default_profile;
+            }
+        }
+        name.to_string()
+    }
+
+    fn get_default_profile_toml_path(&self) -> Utf8PathBuf {
+        self.home.join("default_profile.toml")
+    }
+
+    /// Retrieves the secret backend configured in
+    /// self.home.join("secret_backend.toml"), defaulting to the platform's
+    /// keyring when there's no such file. For example, to keep credentials in
+    /// a secret manager through a helper command:
+    ///
+    /// ```toml
+    /// backend = "helper"
+    /// command = ["rover-vault-helper", "--vault", "dev"]
+    /// ```
+    pub fn secret_backend(&self) -> Result<SecretBackend, HoustonProblem> {
+        let toml_path = self.get_secret_backend_toml_path();
+        if !toml_path.exists() {
+            return Ok(SecretBackend::default());
+        }
+        let contents = Fs::read_file(toml_path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    fn get_secret_backend_toml_path(&self) -> Utf8PathBuf {
+        self.home.join("secret_backend.toml")
+    }
+
+    /// Retrieves the network settings configured in
+    /// self.home.join("network.toml"), which are all unset when there's no
+    /// such file. For example, to reach Studio through a corporate proxy that
+    /// re-signs traffic with a private root CA:
+    ///
+    /// ```toml
+    /// cacert = "/etc/ssl/corp-root.pem"
+    /// proxy = "http://proxy.corp.example.com:3128"
+    /// no_proxy = "localhost,.corp.example.com"
+    /// ```
+    pub fn network_settings(&self) -> Result<NetworkSettings, HoustonProblem> {
+        let toml_path = self.get_network_toml_path();
+        if !toml_path.exists() {
+            return Ok(NetworkSettings::default());
+        }
+        let contents = Fs::read_file(toml_path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    fn get_network_toml_path(&self) -> Utf8PathBuf {
+        self.home.join("network.toml")
+    }
+
+    /// Retrieves the retry settings configured in
+    /// self.home.join("retry.toml"), which are all unset when there's no such
+    /// file. Each kind of request has its own table. For example, to ride out
+    /// Studio's rate limits during a large publish without retrying mutations
+    /// that may already have gone through:
+    ///
+    /// ```toml
+    /// [studio]
+    /// attempts = 8
+    /// max_delay = "2m"
+    /// max_elapsed = "10m"
+    /// retry_mutations = false
+    ///
+    /// [downloads]
+    /// attempts = 3
+    /// ```
+    pub fn retry_settings(&self) -> Result<RetrySettings, HoustonProblem> {
+        let toml_path = self.get_retry_toml_path();
+        if !toml_path.exists() {
+            return Ok(RetrySettings::default());
+        }
+        let contents = Fs::read_file(toml_path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    fn get_retry_toml_path(&self) -> Utf8PathBuf {
+        self.home.join("retry.toml")
+    }
+}
+
+/// How Rover reaches servers on private networks, as configured in
+/// `network.toml`. Each setting can be overridden by its command-line flag.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkSettings {
+    /// A PEM bundle of root certificates to trust alongside the platform's.
+    pub cacert: Option<Utf8PathBuf>,
+    /// A PEM client certificate to present to servers that require mutual TLS.
+    pub client_cert: Option<Utf8PathBuf>,
+    /// The PEM private key for `client_cert`, if it isn't in the same file.
+    pub client_key: Option<Utf8PathBuf>,
+    /// The proxy to send every request through.
+    pub proxy: Option<String>,
+    /// Hosts to reach directly rather than through `proxy`; defaults to the
+    /// `NO_PROXY` environment variable.
+    pub no_proxy: Option<String>,
+}
+
+/// How Rover retries failed requests, as configured in `retry.toml`, with a
+/// separate budget for each kind of request. The command-line flags override
+/// a setting for all of them at once.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetrySettings {
+    /// Requests to Apollo Studio
+    #[serde(default)]
+    pub studio: RetryBudget,
+    /// Introspection requests to subgraphs and graphs
+    #[serde(default)]
+    pub introspection: RetryBudget,
+    /// Plugin downloads
+    #[serde(default)]
+    pub downloads: RetryBudget,
+}
+
+/// How one kind of request is retried. Anything unset keeps Rover's default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetryBudget {
+    /// The most attempts to make, counting the first.
+    pub attempts: Option<u32>,
+    /// The delay before the first retry, e.g. `"500ms"`.
+    pub min_delay: Option<String>,
+    /// The longest delay between two attempts, e.g. `"1m"`.
+    pub max_delay: Option<String>,
+    /// Whether delays are randomly stretched so that clients don't retry in
+    /// lockstep.
+    pub jitter: Option<bool>,
+    /// How long after the first attempt to keep retrying, e.g. `"5m"`.
+    pub max_elapsed: Option<String>,
+    /// Whether to retry GraphQL mutations after failures that may have
+    /// reached the server.
+    pub retry_mutations: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -127,6 +303,11 @@ struct Elv2Toml {
     did_accept: bool,
 }
 
+#[derive(Serialize, Deserialize)]
+struct DefaultProfileToml {
+    name: String,
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryFrom;
@@ -134,9 +315,10 @@ mod tests {
     use assert_fs::TempDir;
     use camino::Utf8PathBuf;
     use rover_print::print::testing::TerminalCapture;
+    use rover_storage::secret::SecretBackend;
     use serial_test::serial;
 
-    use super::Config;
+    use super::{Config, NetworkSettings, RetryBudget, RetrySettings};
     use crate::profile::Profile;
 
     // `#[serial]`: these tests exercise the real OS credential store (not a
@@ -183,4 +365,95 @@ mod tests {
             .iter()
             .any(|line| line.contains(profile) && line.contains("secret store")));
     }
+
+    #[test]
+    fn secret_backend_defaults_to_the_keyring_and_reads_the_configured_one() {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        let config = Config::new(Some(&tmp_path), None).unwrap();
+
+        assert_eq!(config.secret_backend().unwrap(), SecretBackend::Keyring);
+
+        std::fs::write(
+            config.home.join("secret_backend.toml"),
+            "backend = \"env-file\"\npath = \"/run/secrets/rover.env\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.secret_backend().unwrap(),
+            SecretBackend::EnvFile {
+                path: "/run/secrets/rover.env".into()
+            }
+        );
+    }
+
+    #[test]
+    fn network_settings_are_unset_until_configured() {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        let config = Config::new(Some(&tmp_path), None).unwrap();
+
+        assert_eq!(
+            config.network_settings().unwrap(),
+            NetworkSettings::default()
+        );
+
+        std::fs::write(
+            config.home.join("network.toml"),
+            "cacert = \"/etc/ssl/corp-root.pem\"\nproxy = \"http://proxy.corp:3128\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.network_settings().unwrap(),
+            NetworkSettings {
+                cacert: Some("/etc/ssl/corp-root.pem".into()),
+                proxy: Some("http://proxy.corp:3128".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn retry_settings_are_unset_until_configured() {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        let config = Config::new(Some(&tmp_path), None).unwrap();
+
+        assert_eq!(config.retry_settings().unwrap(), RetrySettings::default());
+
+        std::fs::write(
+            config.home.join("retry.toml"),
+            "[studio]\nattempts = 8\nmax_delay = \"2m\"\nretry_mutations = false\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.retry_settings().unwrap(),
+            RetrySettings {
+                studio: RetryBudget {
+                    attempts: Some(8),
+                    max_delay: Some("2m".to_string()),
+                    retry_mutations: Some(false),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn default_resolves_to_itself_until_switched() {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        let config = Config::new(Some(&tmp_path), None).unwrap();
+
+        assert_eq!(config.resolve_profile("default"), "default");
+
+        config.set_default_profile("work").unwrap();
+        assert_eq!(config.resolve_profile("default"), "work");
+        assert_eq!(config.resolve_profile("personal"), "personal");
+
+        config.set_default_profile("default").unwrap();
+        assert_eq!(config.default_profile(), None);
+        assert_eq!(config.resolve_profile("default"), "default");
+    }
 }
@@ -6,8 +6,10 @@ mod config;
 mod error;
 mod profile;
 
-pub use config::Config;
+pub use config::{Config, NetworkSettings, RetryBudget, RetrySettings};
 pub use error::HoustonProblem;
 pub use profile::mask_key;
 /// Utilities for saving, loading, and deleting configuration profiles.
-pub use profile::{Credential, CredentialOrigin, LoadOpts, OAuthSession, Profile};
+pub use profile::{
+    Credential, CredentialOrigin, CredentialSource, LoadOpts, OAuthSession, Profile,
+};
@@ -1,9 +1,14 @@
 mod sensitive;
 
-use std::fmt;
+use std::{
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use camino::Utf8PathBuf as PathBuf;
 use rover_std::Fs;
+use rover_storage::secret::SecretBackend;
 use sensitive::Sensitive;
 use serde::{Deserialize, Serialize};
 
@@ -42,6 +47,87 @@ pub struct Credential {
     pub expires_at: Option<i64>,
 }
 
+/// Where a long-lived Studio client gets the credential for each request.
+///
+/// A profile's OAuth session can be refreshed in the background while a
+/// command like `rover dev` runs, so a source loaded from a profile reads the
+/// profile's credential again once it's within [`RELOAD_MARGIN_SECS`] of
+/// expiring, until it finds the refreshed one. Credentials that don't expire
+/// are read once.
+#[derive(Clone, Debug)]
+pub enum CredentialSource {
+    /// The same credential for every request.
+    Fixed(Credential),
+    /// The credential stored for a profile.
+    Profile {
+        /// The profile's name, which may be `default`.
+        name: String,
+        /// The config the profile is stored in.
+        config: Config,
+        /// The credential as last loaded, shared by every clone of the source.
+        /// Also used if reading it again fails.
+        cached: Arc<Mutex<Credential>>,
+    },
+}
+
+/// How close to expiring a cached credential is before [`CredentialSource`]
+/// reads the profile again. The same margin `rover auth`'s background refresh
+/// replaces an expiring session with, so a refreshed token is picked up as
+/// soon as it's saved.
+const RELOAD_MARGIN_SECS: i64 = 5 * 60;
+
+impl CredentialSource {
+    /// Loads the credential of the profile called `name`, see [`Profile::get_credential`].
+    pub fn profile(name: &str, config: &Config) -> Result<CredentialSource, HoustonProblem> {
+        let loaded = Profile::get_credential(name, config)?;
+        Ok(CredentialSource::Profile {
+            name: name.to_string(),
+            config: config.clone(),
+            cached: Arc::new(Mutex::new(loaded)),
+        })
+    }
+
+    /// The credential to send with the next request.
+    pub fn credential(&self) -> Credential {
+        match self {
+            CredentialSource::Fixed(credential) => credential.clone(),
+            CredentialSource::Profile {
+                name,
+                config,
+                cached,
+            } => {
+                let mut cached = cached.lock().unwrap_or_else(PoisonError::into_inner);
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs() as i64;
+                if cached
+                    .expires_at
+                    .is_none_or(|expires_at| expires_at - now > RELOAD_MARGIN_SECS)
+                {
+                    return cached.clone();
+                }
+                match Profile::get_credential(name, config) {
+                    Ok(credential) => {
+                        *cached = credential.clone();
+                        credential
+                    }
+                    Err(error) => {
+                        tracing::warn!(profile = name, "failed to reload the credential: {error}");
+                        cached.clone()
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl From<Credential> for CredentialSource {
+    fn from(credential: Credential) -> Self {
+        CredentialSource::Fixed(credential)
+    }
+}
+
 /// A profile's stored OAuth session (both tokens), as opposed to
 /// [`Credential`] which only carries the token used to authenticate.
 #[derive(Clone, Debug)]
@@ -50,6 +136,10 @@ pub struct OAuthSession {
     pub access_token: String,
     /// A refresh token, if the authorization server issued one.
     pub refresh_token: Option<String>,
+    /// Unix timestamp at which `access_token` expires, if known.
+    pub expires_at: Option<i64>,
+    /// The scopes the authorization server granted, if it reported any.
+    pub scopes: Vec<String>,
 }
 
 /// Info about where the API key was retrieved
@@ -81,6 +171,9 @@ impl Profile {
     }
 
     /// Writes an api_key to the filesystem (`$APOLLO_CONFIG_HOME/profiles/<profile_name>/.sensitive`).
+    ///
+    /// Like every write, this acts on the profile named `name` itself, even
+    /// `default` when `rover auth switch` has pointed it elsewhere.
     pub fn set_api_key(name: &str, config: &Config, api_key: &str) -> Result<(), HoustonProblem> {
         let data = ProfileData {
             api_key: Some(api_key.to_string()),
@@ -97,27 +190,62 @@ impl Profile {
         access_token: String,
         refresh_token: Option<String>,
         expires_at: Option<i64>,
+    ) -> Result<(), HoustonProblem> {
+        Profile::set_oauth_session(
+            name,
+            config,
+            OAuthSession {
+                access_token,
+                refresh_token,
+                expires_at,
+                scopes: Vec::new(),
+            },
+        )
+    }
+
+    /// Writes a whole OAuth session, including its granted scopes, to the
+    /// secret store. Used both by `rover auth login` and when a session is
+    /// refreshed in the background, so a refresh never drops the scopes
+    /// recorded at login.
+    pub fn set_oauth_session(
+        name: &str,
+        config: &Config,
+        session: OAuthSession,
     ) -> Result<(), HoustonProblem> {
         Sensitive::OAuth {
-            access_token,
-            refresh_token,
-            expires_at,
+            access_token: session.access_token,
+            refresh_token: session.refresh_token,
+            expires_at: session.expires_at,
+            scopes: session.scopes,
         }
         .save(name, config)
     }
 
     /// Returns the profile's stored OAuth session, or `None` if the profile's
     /// stored credential is a legacy API key (from `rover config auth`)
-    /// rather than an OAuth session (from `rover auth login`). Used by
-    /// `rover auth logout` to revoke both tokens before deleting local storage.
+    /// rather than an OAuth session (from `rover auth login`).
     ///
     /// Unlike [`Profile::get_credential`], this does not consult
-    /// `config.override_api_key` (the `APOLLO_KEY` env var) — logout always
-    /// acts on the profile's own stored credential, regardless of any
+    /// `config.override_api_key` (the `APOLLO_KEY` env var), so it always
+    /// returns the profile's own stored credential, regardless of any
     /// runtime override.
+    ///
+    /// `default` resolves to the profile selected by `rover auth switch`, if
+    /// any; see [`Profile::get_stored_oauth_session`] to skip that.
     pub fn get_oauth_session(
         name: &str,
         config: &Config,
+    ) -> Result<Option<OAuthSession>, HoustonProblem> {
+        Profile::get_stored_oauth_session(&config.resolve_profile(name), config)
+    }
+
+    /// Returns the OAuth session stored for the profile named `name` itself,
+    /// without following `rover auth switch`. Used by `rover auth logout`,
+    /// which revokes the session of the profile it's about to delete, and by
+    /// `rover auth status`, which reports each profile under its own name.
+    pub fn get_stored_oauth_session(
+        name: &str,
+        config: &Config,
     ) -> Result<Option<OAuthSession>, HoustonProblem> {
         let opts = LoadOpts { sensitive: true };
         let profile = Profile::load(name, config, opts)?;
@@ -125,10 +253,13 @@ impl Profile {
             Sensitive::OAuth {
                 access_token,
                 refresh_token,
-                ..
+                expires_at,
+                scopes,
             } => Some(OAuthSession {
                 access_token,
                 refresh_token,
+                expires_at,
+                scopes,
             }),
             Sensitive::ApiKey { .. } => None,
         })
@@ -143,7 +274,8 @@ impl Profile {
     /// login`, or a legacy pasted-in API key from `rover config auth` — whichever
     /// is currently stored for the profile.
     ///
-    /// Takes an optional `profile` argument. Defaults to `"default"`.
+    /// Takes an optional `profile` argument. Defaults to `"default"`, which
+    /// resolves to the profile selected by `rover auth switch`, if any.
     pub fn get_credential(name: &str, config: &Config) -> Result<Credential, HoustonProblem> {
         let credential = match (
             &config.override_api_key,
@@ -160,6 +292,7 @@ impl Profile {
                 expires_at: None,
             },
             (None, None) => {
+                let name = &config.resolve_profile(name);
                 let opts = LoadOpts { sensitive: true };
                 let profile = Profile::load(name, config, opts)?;
                 match profile.sensitive {
@@ -186,6 +319,35 @@ impl Profile {
         Ok(credential)
     }
 
+    /// Points a profile at a graph registry other than Apollo Studio, such as a
+    /// local `file://` registry, by writing it to
+    /// `$APOLLO_CONFIG_HOME/profiles/<profile_name>/registry`. `None` points it
+    /// back at Studio.
+    pub fn set_registry_url(
+        name: &str,
+        config: &Config,
+        registry_url: Option<&str>,
+    ) -> Result<(), HoustonProblem> {
+        let path = Profile::dir(name, config).join("registry");
+        match registry_url {
+            Some(registry_url) => Fs::write_file(path, format!("{registry_url}\n"))?,
+            None if path.exists() => std::fs::remove_file(path)?,
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the graph registry a profile was pointed at with
+    /// [`Profile::set_registry_url`], if any.
+    pub fn get_registry_url(name: &str, config: &Config) -> Result<Option<String>, HoustonProblem> {
+        let path = Profile::dir(&config.resolve_profile(name), config).join("registry");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let registry_url = Fs::read_file(path)?.trim().to_string();
+        Ok((!registry_url.is_empty()).then_some(registry_url))
+    }
+
     /// Saves configuration options for a specific profile to the file system,
     /// splitting sensitive information into a separate file.
     pub fn save(name: &str, config: &Config, data: ProfileData) -> Result<(), HoustonProblem> {
@@ -212,6 +374,13 @@ impl Profile {
                 profile_name.to_string(),
             ))
         } else {
+            // a helper command or env file is provisioned outside of Rover,
+            // so its credentials count even for profiles Rover never saved.
+            if opts.sensitive && config.secret_backend()? != SecretBackend::Keyring {
+                if let Some(sensitive) = Sensitive::load_from_store(profile_name, config)? {
+                    return Ok(Profile { sensitive });
+                }
+            }
             let profiles_base_dir = Profile::base_dir(config);
             let mut base_dir_contents = Fs::get_dir_entries(profiles_base_dir)
                 .map_err(|_| HoustonProblem::NoConfigProfiles)?;
@@ -223,7 +392,8 @@ impl Profile {
     }
 
     /// Deletes profile data from the file system and removes its credential
-    /// from the secret store.
+    /// from the secret store. Deleting `default` deletes the profile named
+    /// `default`, not the one `rover auth switch` selected.
     pub fn delete(name: &str, config: &Config) -> Result<(), HoustonProblem> {
         // delete the credential before the index directory: if this fails, the
         // profile stays visible in `list` (and deletable again) instead of
@@ -232,6 +402,10 @@ impl Profile {
         let dir = Profile::dir(name, config);
         tracing::debug!(dir = ?dir);
         Fs::remove_dir_all(dir)?;
+        // don't leave `default` pointing at a profile that no longer exists.
+        if config.default_profile().as_deref() == Some(name) {
+            config.set_default_profile("default")?;
+        }
         Ok(())
     }
 
@@ -497,6 +671,82 @@ mod tests {
         ));
     }
 
+    // A session's expiry and granted scopes must survive a save/load round trip,
+    // so `rover auth status` can report them and a refresh can preserve them.
+    #[rstest]
+    #[serial]
+    fn get_oauth_session_returns_the_stored_expiry_and_scopes(test_config: (Config, TempDir)) {
+        let (config, _tmp_home) = test_config;
+        let profile = "session-roundtrip";
+        Profile::set_oauth_session(
+            profile,
+            &config,
+            OAuthSession {
+                access_token: "access-token".to_string(),
+                refresh_token: Some("refresh-token".to_string()),
+                expires_at: Some(1_700_000_000),
+                scopes: vec!["rover:cli".to_string(), "openid".to_string()],
+            },
+        )
+        .unwrap();
+
+        let session = Profile::get_oauth_session(profile, &config).unwrap();
+
+        assert_that!(session).is_some().matches(|session| {
+            session.expires_at == Some(1_700_000_000)
+                && session.scopes == vec!["rover:cli".to_string(), "openid".to_string()]
+        });
+    }
+
+    // A profile's credential is only read again once it's close to expiring,
+    // rather than for every request.
+    #[rstest]
+    #[serial]
+    fn credential_source_reads_the_profile_again_near_expiry(test_config: (Config, TempDir)) {
+        let (config, _tmp_home) = test_config;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let profile = "credential-source-cache";
+        Profile::set_oauth_tokens(
+            profile,
+            &config,
+            "first".to_string(),
+            None,
+            Some(now + 3600),
+        )
+        .unwrap();
+        let source = CredentialSource::profile(profile, &config).unwrap();
+
+        Profile::set_oauth_tokens(profile, &config, "second".to_string(), None, Some(now + 60))
+            .unwrap();
+        assert_that!(source.credential().api_key).is_equal_to("first".to_string());
+
+        let source = CredentialSource::profile(profile, &config).unwrap();
+        Profile::set_oauth_tokens(
+            profile,
+            &config,
+            "third".to_string(),
+            None,
+            Some(now + 3600),
+        )
+        .unwrap();
+        assert_that!(source.credential().api_key).is_equal_to("third".to_string());
+        // the refreshed credential is cached in turn.
+        Profile::set_oauth_tokens(
+            profile,
+            &config,
+            "fourth".to_string(),
+            None,
+            Some(now + 3600),
+        )
+        .unwrap();
+        assert_that!(source.credential().api_key).is_equal_to("third".to_string());
+
+        Profile::delete(profile, &config).unwrap();
+    }
+
     // `set_api_key` must replace a previously stored OAuth token, not coexist with it.
     #[rstest]
     #[serial]
@@ -520,4 +770,73 @@ mod tests {
             .is_equal_to(CredentialOrigin::ConfigFile(profile.to_string()));
         assert_that!(credential.expires_at).is_none();
     }
+
+    // Writing `default` while `rover auth switch` points it at another profile
+    // must write the profile named `default`, leaving the switched-to one alone.
+    #[rstest]
+    #[serial]
+    fn login_while_switched_writes_the_default_profile_itself(test_config: (Config, TempDir)) {
+        let (config, _tmp_home) = test_config;
+        Profile::set_api_key("switched-login-work", &config, "work-key").unwrap();
+        config.set_default_profile("switched-login-work").unwrap();
+
+        Profile::set_oauth_tokens("default", &config, "default-token".to_string(), None, None)
+            .unwrap();
+
+        let work = Profile::get_credential("switched-login-work", &config).unwrap();
+        assert_that!(&work.api_key).is_equal_to("work-key".to_string());
+        let default = Profile::get_stored_oauth_session("default", &config)
+            .unwrap()
+            .unwrap();
+        assert_that!(&default.access_token).is_equal_to("default-token".to_string());
+        // reads still follow the switch.
+        let credential = Profile::get_credential("default", &config).unwrap();
+        assert_that!(&credential.api_key).is_equal_to("work-key".to_string());
+
+        Profile::delete("default", &config).unwrap();
+        Profile::delete("switched-login-work", &config).unwrap();
+    }
+
+    // Deleting `default` while switched must delete the profile named
+    // `default`, never the switched-to profile or its secret.
+    #[rstest]
+    #[serial]
+    fn delete_while_switched_deletes_the_default_profile_itself(test_config: (Config, TempDir)) {
+        let (config, _tmp_home) = test_config;
+        Profile::set_api_key("default", &config, "default-key").unwrap();
+        Profile::set_api_key("switched-delete-work", &config, "work-key").unwrap();
+        config.set_default_profile("switched-delete-work").unwrap();
+
+        Profile::delete("default", &config).unwrap();
+
+        assert_that!(Profile::list(&config).unwrap())
+            .is_equal_to(vec!["switched-delete-work".to_string()]);
+        let work = Profile::get_credential("switched-delete-work", &config).unwrap();
+        assert_that!(&work.api_key).is_equal_to("work-key".to_string());
+        assert_that!(config.default_profile())
+            .is_equal_to(Some("switched-delete-work".to_string()));
+
+        Profile::delete("switched-delete-work", &config).unwrap();
+        assert_that!(config.default_profile()).is_none();
+    }
+
+    #[rstest]
+    fn a_profile_can_be_pointed_at_a_registry_and_back(test_config: (Config, TempDir)) {
+        let (config, _tmp_home) = test_config;
+        let profile = "local-registry";
+        assert_that!(Profile::get_registry_url(profile, &config))
+            .is_ok()
+            .is_none();
+
+        Profile::set_registry_url(profile, &config, Some("file:///tmp/registry")).unwrap();
+        assert_that!(Profile::get_registry_url(profile, &config))
+            .is_ok()
+            .is_some()
+            .is_equal_to("file:///tmp/registry".to_string());
+
+        Profile::set_registry_url(profile, &config, None).unwrap();
+        assert_that!(Profile::get_registry_url(profile, &config))
+            .is_ok()
+            .is_none();
+    }
 }
@@ -38,6 +38,12 @@ pub enum Sensitive {
         refresh_token: Option<String>,
         /// Unix timestamp at which `access_token` expires, if known.
         expires_at: Option<i64>,
+        /// The scopes the authorization server granted, if it reported any.
+        ///
+        /// Defaulted so sessions saved before scopes were recorded keep
+        /// deserializing; omitted when empty to keep those payloads unchanged.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        scopes: Vec<String>,
     },
 }
 
@@ -54,9 +60,10 @@ impl Sensitive {
     }
 
     fn store(config: &Config) -> Result<RoverSecretStore, HoustonProblem> {
-        Ok(RoverSecretStore::new(
+        Ok(RoverSecretStore::with_backend(
             SECRET_STORE_SERVICE.to_string(),
             config.home.clone().into_std_path_buf(),
+            &config.secret_backend()?,
         )?)
     }
 
@@ -73,6 +80,19 @@ impl Sensitive {
         Ok(())
     }
 
+    /// Loads a credential for a profile from the secret store alone, without
+    /// looking for a legacy `.sensitive` file.
+    pub fn load_from_store(
+        profile_name: &str,
+        config: &Config,
+    ) -> Result<Option<Sensitive>, HoustonProblem> {
+        let store = Sensitive::store(config)?;
+        store
+            .read::<Sensitive>(&Sensitive::key(profile_name))?
+            .map(|sensitive| Sensitive::validate(sensitive, profile_name))
+            .transpose()
+    }
+
     /// Loads a credential for a profile from the OS keychain (or its secure
     /// file-based fallback). Falls back to, and transparently migrates, a legacy
     /// plaintext `.sensitive` file left over from older versions of Rover.
@@ -296,6 +316,7 @@ mod tests {
             access_token: "user:gh.foo:djru4788dhsg3657fhLOLO".to_string(),
             refresh_token: Some("should-never-appear-in-output".to_string()),
             expires_at: Some(1_700_000_000),
+            scopes: vec!["rover:cli".to_string()],
         },
         "user**************************LOLO"
     )]
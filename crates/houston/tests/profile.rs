@@ -59,8 +59,60 @@ fn it_lists_no_profiles_when_none_exist() {
     assert_that!(profiles).is_empty();
 }
 
+#[test]
+fn it_resolves_the_default_profile_to_the_switched_profile() {
+    let config = get_config(None);
+    config::Profile::set_api_key("default", &config, "defaultpassword")
+        .expect("setting api key failed");
+    config::Profile::set_api_key("work", &config, "workpassword").expect("setting api key failed");
+
+    config
+        .set_default_profile("work")
+        .expect("switching the default profile failed");
+
+    let credential =
+        config::Profile::get_credential("default", &config).expect("getting credential failed");
+    assert_eq!(credential.api_key, "workpassword");
+    assert_eq!(
+        credential.origin,
+        config::CredentialOrigin::ConfigFile("work".to_string())
+    );
+
+    // deleting the switched-to profile must not leave `default` dangling.
+    config::Profile::delete("work", &config).expect("deleting profile failed");
+    assert_that!(config.default_profile()).is_none();
+    assert_eq!(
+        config::Profile::get_credential("default", &config)
+            .expect("getting credential failed")
+            .api_key,
+        "defaultpassword"
+    );
+}
+
 fn get_config(override_api_key: Option<String>) -> Config {
     let tmp_home = TempDir::new().unwrap();
     let tmp_home_path = Utf8Path::from_path(tmp_home.path()).unwrap().to_owned();
     Config::new(Some(&tmp_home_path), override_api_key).unwrap()
 }
+
+#[test]
+fn it_reads_credentials_from_a_configured_env_file() {
+    let config = get_config(None);
+    std::fs::create_dir_all(&config.home).unwrap();
+    let env_file = config.home.join("rover.env");
+    std::fs::write(
+        &env_file,
+        "ROVER_PROFILE_PROVISIONED='{\"api_key\":\"provisioned-key\"}'\n",
+    )
+    .unwrap();
+    std::fs::write(
+        config.home.join("secret_backend.toml"),
+        format!("backend = \"env-file\"\npath = {:?}\n", env_file.as_str()),
+    )
+    .unwrap();
+
+    let credential = config::Profile::get_credential("provisioned", &config)
+        .expect("getting provisioned credential failed");
+
+    assert_eq!(credential.api_key, "provisioned-key");
+}
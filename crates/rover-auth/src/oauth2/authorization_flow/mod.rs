@@ -31,12 +31,15 @@ pub struct AuthorizationFlowResponse {
     pub refresh_token: Option<RefreshToken>,
     /// Lifetime of the access token.
     pub expires_in: Option<std::time::Duration>,
+    /// The scopes granted, if the server reported them.
+    pub scopes: Vec<Scope>,
 }
 
 impl PartialEq for AuthorizationFlowResponse {
     fn eq(&self, other: &Self) -> bool {
         self.access_token.secret() == other.access_token.secret()
             && self.expires_in == other.expires_in
+            && self.scopes == other.scopes
             && self
                 .refresh_token
                 .as_ref()
@@ -188,10 +191,12 @@ impl AuthorizationFlow<state::AuthorizationFlowWithCode> {
         let access_token = resp.access_token().clone();
         let refresh_token = resp.refresh_token().cloned();
         let expires_in = resp.expires_in();
+        let scopes = resp.scopes().cloned().unwrap_or_default();
         Ok(AuthorizationFlowResponse {
             access_token,
             refresh_token,
             expires_in,
+            scopes,
         })
     }
 }
@@ -328,6 +333,7 @@ mod tests {
                 access_token,
                 refresh_token: Some(refresh_token),
                 expires_in: None,
+                scopes: Vec::new(),
             })
     }
 
@@ -0,0 +1,466 @@
+use std::{fmt::Debug, future::Future, time::Duration};
+
+use oauth2::{
+    AccessToken, ClientId, DeviceAuthorizationUrl, DeviceCodeErrorResponseType, EndpointNotSet,
+    EndpointSet, RefreshToken, RequestTokenError, Scope, StandardDeviceAuthorizationResponse,
+    TokenResponse, TokenUrl, basic::BasicClient,
+};
+use rover_http::Body;
+use rover_print::{
+    print::Print,
+    style::{Style, StyledText},
+};
+use tower::Service;
+use url::Url;
+
+use crate::OauthHttpClient;
+
+type DeviceFlowClient =
+    BasicClient<EndpointNotSet, EndpointSet, EndpointNotSet, EndpointNotSet, EndpointSet>;
+
+/// Tokens returned once the user approves a device authorization request.
+#[derive(Debug)]
+pub struct DeviceFlowResponse {
+    /// The issued access token.
+    pub access_token: AccessToken,
+    /// A refresh token, if the server issued one.
+    pub refresh_token: Option<RefreshToken>,
+    /// Lifetime of the access token.
+    pub expires_in: Option<Duration>,
+    /// The scopes granted, if the server reported them.
+    pub scopes: Vec<Scope>,
+}
+
+mod state {
+    use oauth2::StandardDeviceAuthorizationResponse;
+    use url::Url;
+
+    use super::DeviceFlowClient;
+
+    #[derive(Debug)]
+    pub struct DeviceFlowInit {
+        pub client_id: String,
+        pub device_authorization_url: Url,
+        pub token_url: Url,
+    }
+
+    #[derive(Debug)]
+    pub struct DeviceFlowWithCode {
+        pub details: StandardDeviceAuthorizationResponse,
+        pub client: DeviceFlowClient,
+    }
+}
+
+/// Errors from the device authorization flow.
+#[derive(thiserror::Error, Debug)]
+pub enum DeviceFlowError {
+    /// The device authorization endpoint didn't issue a device code.
+    #[error("Failed to request a device code: {}", .0)]
+    DeviceAuthorization(Box<dyn std::error::Error>),
+    /// The user declined the authorization request.
+    #[error("The authorization request was denied.")]
+    AccessDenied,
+    /// The user didn't approve the request before the device code expired.
+    #[error("The device code expired before the authorization request was approved.")]
+    ExpiredToken,
+    /// The token endpoint failed for any other reason.
+    #[error("Failed to exchange the device code: {}", .0)]
+    TokenExchange(Box<dyn std::error::Error>),
+}
+
+/// State machine for the OAuth2 Device Authorization Grant (RFC 8628), for
+/// machines without a browser that can reach a local redirect server: the
+/// user approves the request on any other device, while Rover polls the
+/// token endpoint until they do.
+#[derive(Debug)]
+pub struct DeviceFlow<T>
+where
+    T: Debug,
+{
+    state: T,
+}
+
+#[bon::bon]
+impl DeviceFlow<state::DeviceFlowInit> {
+    #[builder]
+    /// Creates a new [`DeviceFlow`] in its initial state.
+    pub const fn new(
+        client_id: String,
+        device_authorization_url: Url,
+        token_url: Url,
+    ) -> DeviceFlow<state::DeviceFlowInit> {
+        DeviceFlow {
+            state: state::DeviceFlowInit {
+                client_id,
+                device_authorization_url,
+                token_url,
+            },
+        }
+    }
+
+    /// Requests a device code, then prints the user code and the URL to
+    /// enter it at, returning the flow ready to poll for a token.
+    pub async fn request_device_code<S, B, P>(
+        &self,
+        scopes: Vec<Scope>,
+        http_service: S,
+        stderr: &P,
+    ) -> Result<DeviceFlow<state::DeviceFlowWithCode>, DeviceFlowError>
+    where
+        S: Service<http::Request<B>, Response = http::Response<B>> + Send + 'static,
+        S::Error: std::error::Error + From<B::Error> + 'static,
+        S::Future: Send,
+        B: From<Vec<u8>> + Body + Unpin + Send,
+        B::Data: Send,
+        P: Print,
+    {
+        let client = BasicClient::new(ClientId::new(self.state.client_id.clone()))
+            .set_device_authorization_url(DeviceAuthorizationUrl::from_url(
+                self.state.device_authorization_url.clone(),
+            ))
+            .set_token_uri(TokenUrl::from_url(self.state.token_url.clone()));
+        let http_client = OauthHttpClient::new(http_service);
+        let details: StandardDeviceAuthorizationResponse = client
+            .exchange_device_code()
+            .add_scopes(scopes)
+            .request_async(&http_client)
+            .await
+            .map_err(|err| DeviceFlowError::DeviceAuthorization(Box::new(err)))?;
+
+        let instructions = match details.verification_uri_complete() {
+            Some(complete) => format!(
+                "To authenticate, visit {} and confirm the code {}, or visit {} and enter it.",
+                complete.secret(),
+                details.user_code().secret(),
+                details.verification_uri().as_str(),
+            ),
+            None => format!(
+                "To authenticate, visit {} and enter the code {}",
+                details.verification_uri().as_str(),
+                details.user_code().secret(),
+            ),
+        };
+        stderr.print(&StyledText::new(Style::Info, instructions));
+
+        Ok(DeviceFlow {
+            state: state::DeviceFlowWithCode { details, client },
+        })
+    }
+}
+
+impl DeviceFlow<state::DeviceFlowWithCode> {
+    /// The code the user enters to approve this request.
+    pub fn user_code(&self) -> &str {
+        self.state.details.user_code().secret()
+    }
+
+    /// The URL the user enters [`DeviceFlow::user_code`] at.
+    pub fn verification_uri(&self) -> &str {
+        self.state.details.verification_uri().as_str()
+    }
+
+    /// Polls the token endpoint until the user approves (or denies) the
+    /// request, or the device code expires. Waits between polls using
+    /// `sleep`, at the interval the server asked for: unchanged while
+    /// authorization is pending, and five seconds longer each time the
+    /// server asks Rover to slow down.
+    pub async fn poll_for_token<S, B, SF, F>(
+        self,
+        http_service: S,
+        sleep: SF,
+    ) -> Result<DeviceFlowResponse, DeviceFlowError>
+    where
+        S: Service<http::Request<B>, Response = http::Response<B>> + Send + 'static,
+        S::Error: std::error::Error + From<B::Error> + 'static,
+        S::Future: Send,
+        B: From<Vec<u8>> + Body + Unpin + Send,
+        B::Data: Send,
+        SF: Fn(Duration) -> F,
+        F: Future<Output = ()>,
+    {
+        let http_client = OauthHttpClient::new(http_service);
+        let resp = self
+            .state
+            .client
+            .exchange_device_access_token(&self.state.details)
+            .request_async(&http_client, sleep, None)
+            .await
+            .map_err(|err| match err {
+                RequestTokenError::ServerResponse(resp) => match resp.error() {
+                    DeviceCodeErrorResponseType::AccessDenied => DeviceFlowError::AccessDenied,
+                    DeviceCodeErrorResponseType::ExpiredToken => DeviceFlowError::ExpiredToken,
+                    _ => DeviceFlowError::TokenExchange(Box::new(
+                        RequestTokenError::<S::Error, _>::ServerResponse(resp),
+                    )),
+                },
+                other => DeviceFlowError::TokenExchange(Box::new(other)),
+            })?;
+
+        Ok(DeviceFlowResponse {
+            access_token: resp.access_token().clone(),
+            refresh_token: resp.refresh_token().cloned(),
+            expires_in: resp.expires_in(),
+            scopes: resp.scopes().cloned().unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use bytes::Bytes;
+    use http::{Method, Uri};
+    use oauth2::Scope;
+    use rover_http::{Full, test::MockHttpService};
+    use rover_print::{print::MockPrint, style::Style};
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+    use url::Url;
+
+    use crate::oauth2::device_flow::{DeviceFlow, DeviceFlowError, state::DeviceFlowWithCode};
+
+    #[fixture]
+    fn device_authorization_url() -> Url {
+        Url::parse("https://example.com/device_authorization").unwrap()
+    }
+
+    #[fixture]
+    fn token_url() -> Url {
+        Url::parse("https://example.com/token").unwrap()
+    }
+
+    #[fixture]
+    fn client_id() -> String {
+        "client_id".to_string()
+    }
+
+    fn json_response(status: u16, body: serde_json::Value) -> http::Response<Full<Bytes>> {
+        http::Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(Full::new(Bytes::from(serde_json::to_vec(&body).unwrap())))
+            .unwrap()
+    }
+
+    fn token_error(error: &str) -> http::Response<Full<Bytes>> {
+        json_response(400, serde_json::json!({ "error": error }))
+    }
+
+    /// Runs the device authorization request against a mock that issues a
+    /// device code, returning the flow ready to poll.
+    async fn with_device_code(
+        client_id: String,
+        device_authorization_url: Url,
+        token_url: Url,
+    ) -> DeviceFlow<DeviceFlowWithCode> {
+        let mut http_service = MockHttpService::new();
+        http_service.expect_call().times(1).returning(|_| {
+            futures::future::ready(Ok(json_response(
+                200,
+                serde_json::json!({
+                    "device_code": "device_code",
+                    "user_code": "WDJB-MJHT",
+                    "verification_uri": "https://example.com/device",
+                    "expires_in": 600,
+                    "interval": 5
+                }),
+            )))
+        });
+        let mut mock_print = MockPrint::new();
+        mock_print.expect_print().times(1).returning(|_| ());
+        DeviceFlow::builder()
+            .client_id(client_id)
+            .device_authorization_url(device_authorization_url)
+            .token_url(token_url)
+            .build()
+            .request_device_code(Vec::new(), http_service, &mock_print)
+            .await
+            .unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    #[timeout(Duration::from_secs(5))]
+    async fn test_device_flow_prints_the_user_code_and_verification_uri(
+        client_id: String,
+        device_authorization_url: Url,
+        token_url: Url,
+    ) {
+        let mut http_service = MockHttpService::new();
+        let expected_url = device_authorization_url.clone();
+        http_service
+            .expect_call()
+            .times(1)
+            .withf(move |req| {
+                req.method() == Method::POST
+                    && req.uri() == &Uri::try_from(expected_url.as_str()).unwrap()
+            })
+            .returning(|_| {
+                futures::future::ready(Ok(json_response(
+                    200,
+                    serde_json::json!({
+                        "device_code": "device_code",
+                        "user_code": "WDJB-MJHT",
+                        "verification_uri": "https://example.com/device",
+                        "verification_uri_complete": "https://example.com/device?user_code=WDJB-MJHT",
+                        "expires_in": 600,
+                        "interval": 5
+                    }),
+                )))
+            });
+        let mut mock_print = MockPrint::new();
+        mock_print
+            .expect_print()
+            .times(1)
+            .withf(|message| {
+                message.style() == &Style::Info
+                    && message
+                        .text()
+                        .contains("https://example.com/device?user_code=WDJB-MJHT")
+                    && message.text().contains("WDJB-MJHT")
+            })
+            .returning(|_| ());
+
+        let result = DeviceFlow::builder()
+            .client_id(client_id)
+            .device_authorization_url(device_authorization_url)
+            .token_url(token_url)
+            .build()
+            .request_device_code(
+                vec![Scope::new("rover:cli".to_string())],
+                http_service,
+                &mock_print,
+            )
+            .await;
+
+        assert_that!(result).is_ok();
+        let with_code = result.unwrap();
+        assert_that!(with_code.user_code()).is_equal_to("WDJB-MJHT");
+        assert_that!(with_code.verification_uri()).is_equal_to("https://example.com/device");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    #[timeout(Duration::from_secs(5))]
+    async fn test_device_flow_keeps_polling_while_pending_and_slows_down_when_asked(
+        client_id: String,
+        device_authorization_url: Url,
+        token_url: Url,
+    ) {
+        let with_code = with_device_code(client_id, device_authorization_url, token_url).await;
+        let mut http_service = MockHttpService::new();
+        let mut seq = mockall::Sequence::new();
+        for error in [
+            "authorization_pending",
+            "slow_down",
+            "authorization_pending",
+        ] {
+            http_service
+                .expect_call()
+                .times(1)
+                .in_sequence(&mut seq)
+                .returning(move |_| futures::future::ready(Ok(token_error(error))));
+        }
+        http_service
+            .expect_call()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| {
+                futures::future::ready(Ok(json_response(
+                    200,
+                    serde_json::json!({
+                        "access_token": "access_token",
+                        "refresh_token": "refresh_token",
+                        "token_type": "Bearer",
+                        "expires_in": 3600,
+                        "scope": "rover:cli"
+                    }),
+                )))
+            });
+
+        let sleeps = Arc::new(Mutex::new(Vec::new()));
+        let result = with_code
+            .poll_for_token(http_service, {
+                let sleeps = sleeps.clone();
+                move |interval| {
+                    sleeps.lock().unwrap().push(interval);
+                    futures::future::ready(())
+                }
+            })
+            .await;
+
+        assert_that!(result).is_ok();
+        let response = result.unwrap();
+        assert_that!(response.access_token.secret()).is_equal_to(&"access_token".to_string());
+        assert_that!(response.refresh_token.map(|t| t.secret().to_string()))
+            .is_equal_to(Some("refresh_token".to_string()));
+        assert_that!(response.expires_in).is_equal_to(Some(Duration::from_secs(3600)));
+        assert_that!(response.scopes).is_equal_to(vec![Scope::new("rover:cli".to_string())]);
+        // authorization_pending keeps the interval, slow_down adds 5s to it
+        assert_that!(*sleeps.lock().unwrap()).is_equal_to(vec![
+            Duration::from_secs(5),
+            Duration::from_secs(10),
+            Duration::from_secs(10),
+        ]);
+    }
+
+    #[rstest]
+    #[case::access_denied("access_denied")]
+    #[case::expired_token("expired_token")]
+    #[tokio::test]
+    #[timeout(Duration::from_secs(5))]
+    async fn test_device_flow_stops_polling_when_denied_or_expired(
+        #[case] error: &'static str,
+        client_id: String,
+        device_authorization_url: Url,
+        token_url: Url,
+    ) {
+        let with_code = with_device_code(client_id, device_authorization_url, token_url).await;
+        let mut http_service = MockHttpService::new();
+        http_service
+            .expect_call()
+            .times(1)
+            .returning(move |_| futures::future::ready(Ok(token_error(error))));
+
+        let result = with_code
+            .poll_for_token(http_service, |_| futures::future::ready(()))
+            .await;
+
+        assert_that!(result).is_err().matches(|err| match error {
+            "access_denied" => matches!(err, DeviceFlowError::AccessDenied),
+            _ => matches!(err, DeviceFlowError::ExpiredToken),
+        });
+    }
+
+    #[rstest]
+    #[tokio::test]
+    #[timeout(Duration::from_secs(5))]
+    async fn test_device_flow_device_code_request_failure(
+        client_id: String,
+        device_authorization_url: Url,
+        token_url: Url,
+    ) {
+        let mut http_service = MockHttpService::new();
+        http_service
+            .expect_call()
+            .times(1)
+            .returning(|_| futures::future::ready(Ok(token_error("invalid_client"))));
+        let mock_print = MockPrint::new();
+
+        let result = DeviceFlow::builder()
+            .client_id(client_id)
+            .device_authorization_url(device_authorization_url)
+            .token_url(token_url)
+            .build()
+            .request_device_code(Vec::new(), http_service, &mock_print)
+            .await;
+
+        assert_that!(result)
+            .is_err()
+            .matches(|err| matches!(err, DeviceFlowError::DeviceAuthorization(_)));
+    }
+}
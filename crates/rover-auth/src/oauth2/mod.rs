@@ -4,6 +4,8 @@ use serde::Serialize;
 pub mod authorization_flow;
 /// Client credentials grant service.
 pub mod client_credentials;
+/// OAuth 2.0 device authorization grant.
+pub mod device_flow;
 /// Token refresh service.
 pub mod refresh_token;
 /// Dynamic client registration service.
@@ -2,8 +2,8 @@ use std::{marker::PhantomData, time::Duration};
 
 use http::{Request, Response};
 use oauth2::{
-    AccessToken, ClientId, RefreshToken as OauthRefreshToken, RequestTokenError, TokenResponse,
-    TokenUrl, basic::BasicClient,
+    AccessToken, ClientId, RefreshToken as OauthRefreshToken, RequestTokenError, Scope,
+    TokenResponse, TokenUrl, basic::BasicClient,
 };
 use rover_http::Body;
 use rover_tower::{ResponseFuture, service::replace_ready_service};
@@ -49,6 +49,8 @@ pub struct RefreshTokenResponse {
     pub refresh_token: Option<OauthRefreshToken>,
     /// Lifetime of the new access token.
     pub expires_in: Option<Duration>,
+    /// The scopes granted to the new access token, if the server reported them.
+    pub scopes: Vec<Scope>,
 }
 
 /// Tower service that exchanges a refresh token for a new access token.
@@ -106,6 +108,7 @@ where
                 access_token: resp.access_token().clone(),
                 refresh_token: resp.refresh_token().cloned(),
                 expires_in: resp.expires_in(),
+                scopes: resp.scopes().cloned().unwrap_or_default(),
             })
         };
         Box::pin(fut)
@@ -118,6 +121,7 @@ mod tests {
 
     use bytes::Bytes;
     use http::{Method, Uri};
+    use oauth2::Scope;
     use rover_http::{Full, HttpServiceError, test::MockHttpService};
     use rover_tower::{expect_poll_ready, test::MockCloneService};
     use rstest::{fixture, rstest};
@@ -146,7 +150,8 @@ mod tests {
         let body = serde_json::json!({
             "access_token": "new_access_token",
             "refresh_token": "new_refresh_token",
-            "token_type": "Bearer"
+            "token_type": "Bearer",
+            "scope": "rover:cli openid"
         });
         http::Response::builder()
             .body(Full::new(Bytes::from(serde_json::to_vec(&body).unwrap())))
@@ -190,6 +195,10 @@ mod tests {
         assert_that!(resp.access_token.secret()).is_equal_to(&"new_access_token".to_string());
         assert_that!(resp.refresh_token.as_ref().unwrap().secret())
             .is_equal_to(&"new_refresh_token".to_string());
+        assert_that!(resp.scopes).is_equal_to(vec![
+            Scope::new("rover:cli".to_string()),
+            Scope::new("openid".to_string()),
+        ]);
     }
 
     #[rstest]
@@ -0,0 +1,134 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use httpmock::prelude::*;
+use rover_auth::oauth2::{
+    Scope,
+    device_flow::{DeviceFlow, DeviceFlowError},
+};
+use rover_http::{Full, ReqwestService};
+use rover_print::print::MockPrint;
+use rstest::rstest;
+use speculoos::prelude::*;
+use url::Url;
+
+fn http_service() -> ReqwestService {
+    ReqwestService::builder()
+        .client(reqwest::Client::default())
+        .build()
+        .unwrap()
+}
+
+#[rstest]
+#[tokio::test]
+#[timeout(Duration::from_secs(10))]
+async fn test_full_device_flow_polls_until_approved() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/device_authorization")
+            .body_includes("client_id=test-client-id");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"device_code":"test_device_code","user_code":"WDJB-MJHT","verification_uri":"https://example.com/device","expires_in":600,"interval":1}"#);
+    });
+    // until the user approves the request, the token endpoint reports it as
+    // pending; the stub "approves" it the first time the flow backs off.
+    let pending = server.mock(|when, then| {
+        when.method(POST)
+            .path("/token")
+            .body_includes("device_code=test_device_code");
+        then.status(400)
+            .header("content-type", "application/json")
+            .body(r#"{"error":"authorization_pending"}"#);
+    });
+    let pending = Arc::new(Mutex::new(Some(pending)));
+
+    let flow = DeviceFlow::builder()
+        .client_id("test-client-id".to_string())
+        .device_authorization_url(Url::parse(&server.url("/device_authorization")).unwrap())
+        .token_url(Url::parse(&server.url("/token")).unwrap())
+        .build();
+
+    let mut mock_print = MockPrint::new();
+    mock_print
+        .expect_print()
+        .times(1)
+        .withf(|message| message.text().contains("WDJB-MJHT"))
+        .returning(|_| ());
+
+    let with_code = flow
+        .request_device_code::<_, Full<bytes::Bytes>, _>(
+            vec![Scope::new("rover:cli".to_string())],
+            http_service(),
+            &mock_print,
+        )
+        .await;
+    assert_that!(with_code).is_ok();
+    let with_code = with_code.unwrap();
+
+    let result = with_code
+        .poll_for_token::<_, Full<bytes::Bytes>, _, _>(http_service(), |_| {
+            if let Some(mut pending) = pending.lock().unwrap().take() {
+                pending.assert_calls(1);
+                pending.delete();
+                server.mock(|when, then| {
+                    when.method(POST).path("/token");
+                    then.status(200)
+                        .header("content-type", "application/json")
+                        .body(r#"{"access_token":"test_access_token","token_type":"Bearer","refresh_token":"test_refresh_token"}"#);
+                });
+            }
+            futures::future::ready(())
+        })
+        .await;
+    let response = assert_that!(result).is_ok().subject;
+
+    assert_that!(response.access_token.secret()).is_equal_to(&"test_access_token".to_string());
+    assert_that!(response.refresh_token.as_ref().unwrap().secret())
+        .is_equal_to(&"test_refresh_token".to_string());
+}
+
+#[rstest]
+#[tokio::test]
+#[timeout(Duration::from_secs(10))]
+async fn test_device_flow_reports_a_denied_request() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/device_authorization");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"device_code":"test_device_code","user_code":"WDJB-MJHT","verification_uri":"https://example.com/device","expires_in":600}"#);
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/token");
+        then.status(400)
+            .header("content-type", "application/json")
+            .body(r#"{"error":"access_denied"}"#);
+    });
+
+    let flow = DeviceFlow::builder()
+        .client_id("test-client-id".to_string())
+        .device_authorization_url(Url::parse(&server.url("/device_authorization")).unwrap())
+        .token_url(Url::parse(&server.url("/token")).unwrap())
+        .build();
+
+    let mut mock_print = MockPrint::new();
+    mock_print.expect_print().times(1).returning(|_| ());
+
+    let with_code = flow
+        .request_device_code::<_, Full<bytes::Bytes>, _>(Vec::new(), http_service(), &mock_print)
+        .await
+        .unwrap();
+    let result = with_code
+        .poll_for_token::<_, Full<bytes::Bytes>, _, _>(http_service(), |_| {
+            futures::future::ready(())
+        })
+        .await;
+
+    assert_that!(result)
+        .is_err()
+        .matches(|err| matches!(err, DeviceFlowError::AccessDenied));
+}
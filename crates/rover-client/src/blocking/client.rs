@@ -1,11 +1,22 @@
 use std::time::{Duration, Instant};
 
 use backon::Retryable;
+use bytes::Bytes;
 use graphql_client::{Error as GraphQLError, GraphQLQuery, Response as GraphQLResponse};
+use http_body_util::Full;
 use reqwest::{
     header::{HeaderMap, HeaderValue},
     Client as ReqwestClient, Response, StatusCode,
 };
+use rover_graphql::is_mutation;
+use rover_http::{
+    cassette::Cassette,
+    retry::{retry_after, RetryConfig},
+    trace_context::inject_trace_context,
+    HttpServiceError, ReqwestService,
+};
+use tower::ServiceExt;
+use tracing::Instrument;
 
 use crate::error::{EndpointKind, RoverClientError};
 
@@ -15,7 +26,7 @@ pub(crate) const JSON_CONTENT_TYPE: &str = "application/json";
 pub struct GraphQLClient {
     graphql_endpoint: String,
     client: ReqwestClient,
-    retry_period: Duration,
+    retry: RetryConfig,
 }
 
 impl GraphQLClient {
@@ -29,10 +40,17 @@ impl GraphQLClient {
         GraphQLClient {
             graphql_endpoint: graphql_endpoint.to_string(),
             client,
-            retry_period,
+            retry: RetryConfig::new(retry_period),
         }
     }
 
+    /// Retries requests as `retry` describes, rather than with the default
+    /// delays for up to the `retry_period` given to [`GraphQLClient::new`].
+    pub const fn with_retry_config(mut self, retry: RetryConfig) -> GraphQLClient {
+        self.retry = retry;
+        self
+    }
+
     /// Client method for making a GraphQL request.
     ///
     /// Takes one argument, `variables`. Returns an optional response.
@@ -46,12 +64,23 @@ impl GraphQLClient {
     where
         Q: GraphQLQuery,
     {
-        let request_body = self.get_request_body::<Q>(variables)?;
+        let (request_body, operation_name, non_idempotent) =
+            self.get_request_body::<Q>(variables)?;
         header_map.append("Content-Type", HeaderValue::from_str(JSON_CONTENT_TYPE)?);
-        let response = self
-            .execute(request_body, header_map, true, endpoint_kind)
-            .await;
-        GraphQLClient::handle_response::<Q>(response?, endpoint_kind).await
+        async {
+            let response = self
+                .execute(
+                    request_body,
+                    header_map,
+                    true,
+                    non_idempotent,
+                    endpoint_kind,
+                )
+                .await;
+            GraphQLClient::handle_response::<Q>(response?, endpoint_kind).await
+        }
+        .instrument(operation_span(operation_name))
+        .await
     }
 
     /// Client method for making a GraphQL request.
@@ -67,48 +96,83 @@ impl GraphQLClient {
     where
         Q: GraphQLQuery,
     {
-        let request_body = self.get_request_body::<Q>(variables)?;
+        let (request_body, operation_name, non_idempotent) =
+            self.get_request_body::<Q>(variables)?;
         header_map.append("Content-Type", HeaderValue::from_str(JSON_CONTENT_TYPE)?);
-        let response = self
-            .execute(request_body, header_map, false, endpoint_kind)
-            .await;
-        GraphQLClient::handle_response::<Q>(response?, endpoint_kind).await
+        async {
+            let response = self
+                .execute(
+                    request_body,
+                    header_map,
+                    false,
+                    non_idempotent,
+                    endpoint_kind,
+                )
+                .await;
+            GraphQLClient::handle_response::<Q>(response?, endpoint_kind).await
+        }
+        .instrument(operation_span(operation_name))
+        .await
     }
 
+    /// Returns the serialized request body, along with the name of the
+    /// operation it's for and whether that operation is a mutation.
     fn get_request_body<Q: GraphQLQuery>(
         &self,
         variables: Q::Variables,
-    ) -> Result<String, RoverClientError> {
+    ) -> Result<(String, &'static str, bool), RoverClientError> {
         let body = Q::build_query(variables);
-        Ok(serde_json::to_string(&body)?)
+        let non_idempotent = is_mutation(body.query, Some(body.operation_name));
+        Ok((
+            serde_json::to_string(&body)?,
+            body.operation_name,
+            non_idempotent,
+        ))
     }
 
+    /// Sends the request, retrying failures that are likely to be transient.
+    /// Mutations (`non_idempotent`) are only retried when the server can't
+    /// have acted on them, unless the [`RetryConfig`] allows otherwise.
     async fn execute(
         &self,
         request_body: String,
         header_map: &HeaderMap,
         should_retry: bool,
+        non_idempotent: bool,
         endpoint_kind: EndpointKind,
     ) -> Result<Response, RoverClientError> {
         enum BackoffError<E> {
             Permanent(E),
             Transient(E),
+            /// The server turned the request away and asked to be retried
+            /// after the given delay, if any
+            Rejected(E, Option<Duration>),
         }
 
         tracing::trace!(request_headers = ?header_map);
         tracing::debug!("Request Body: {}", request_body);
+        if Cassette::installed().is_some() {
+            return self
+                .execute_through_service(request_body, header_map, endpoint_kind)
+                .await;
+        }
+        // a request that may have reached the server is only sent again if
+        // doing so twice is harmless
+        let may_repeat = *self.retry.retry_mutations() || !non_idempotent;
         let graphql_operation = || async {
+            let mut headers = header_map.clone();
+            inject_trace_context(&mut headers);
             let response = self
                 .client
                 .post(&self.graphql_endpoint)
-                .headers(header_map.clone())
+                .headers(headers)
                 .body(request_body.clone())
                 .send()
                 .await;
 
             match response {
                 Err(client_error) => {
-                    if client_error.is_timeout() || client_error.is_connect() {
+                    if client_error.is_connect() || (client_error.is_timeout() && may_repeat) {
                         Err(BackoffError::Transient(client_error))
                     } else if client_error.is_body()
                         || client_error.is_decode()
@@ -116,7 +180,7 @@ impl GraphQLClient {
                         || client_error.is_redirect()
                     {
                         Err(BackoffError::Permanent(client_error))
-                    } else if client_error.is_request() {
+                    } else if client_error.is_request() && may_repeat {
                         if let Some(hyper_error) =
                             get_source_error_type::<hyper::Error>(&client_error)
                         {
@@ -135,7 +199,15 @@ impl GraphQLClient {
                 Ok(success) => {
                     if let Err(status_error) = success.error_for_status_ref() {
                         if let Some(response_status) = status_error.status() {
-                            if response_status.is_server_error()
+                            if matches!(
+                                response_status,
+                                StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+                            ) {
+                                let delay = retry_after(success.headers());
+                                Err(BackoffError::Rejected(status_error, delay))
+                            } else if !may_repeat {
+                                Err(BackoffError::Permanent(status_error))
+                            } else if response_status.is_server_error()
                                 || response_status.is_client_error()
                                 || response_status.is_redirection()
                             {
@@ -161,33 +233,89 @@ impl GraphQLClient {
         };
 
         if should_retry {
-            let backoff_strategy = backon::ExponentialBuilder::default()
-                .with_min_delay(Duration::from_millis(500))
-                .with_max_delay(Duration::from_secs(60))
+            let mut backoff_strategy = backon::ExponentialBuilder::default()
+                .with_min_delay(*self.retry.min_delay())
+                .with_max_delay(*self.retry.max_delay())
                 .with_factor(1.5)
-                .without_max_times()
-                .with_jitter();
+                .without_max_times();
+            if let Some(max_attempts) = self.retry.max_attempts() {
+                backoff_strategy =
+                    backoff_strategy.with_max_times(max_attempts.saturating_sub(1) as usize);
+            }
+            if *self.retry.jitter() {
+                backoff_strategy = backoff_strategy.with_jitter();
+            }
 
             let start = Instant::now();
+            let max_elapsed_time = *self.retry.max_elapsed_time();
             graphql_operation
                 .retry(backoff_strategy)
-                .when(|e| {
-                    matches!(e, BackoffError::Transient(_)) && start.elapsed() < self.retry_period
+                .when(|e| match e {
+                    BackoffError::Permanent(_) => false,
+                    BackoffError::Transient(_) | BackoffError::Rejected(_, None) => {
+                        start.elapsed() < max_elapsed_time
+                    }
+                    // waiting longer than the budget allows would only delay the failure
+                    BackoffError::Rejected(_, Some(delay)) => {
+                        start.elapsed() + *delay < max_elapsed_time
+                    }
+                })
+                .adjust(|e, backoff| match e {
+                    // `backoff` is `None` once the attempts run out
+                    BackoffError::Rejected(_, Some(delay)) => backoff.map(|_| *delay),
+                    _ => backoff,
                 })
                 .await
         } else {
             graphql_operation().await
         }
         .map_err(|e| match e {
-            BackoffError::Permanent(reqwest_error) | BackoffError::Transient(reqwest_error) => {
-                RoverClientError::SendRequest {
-                    source: reqwest_error,
-                    endpoint_kind,
-                }
-            }
+            BackoffError::Permanent(reqwest_error)
+            | BackoffError::Transient(reqwest_error)
+            | BackoffError::Rejected(reqwest_error, _) => RoverClientError::SendRequest {
+                source: reqwest_error,
+                endpoint_kind,
+            },
         })
     }
 
+    /// Sends the request through a [`ReqwestService`] rather than the client
+    /// directly, so that it's recorded to (or replayed from) the installed
+    /// [`Cassette`] like every other request. Replayed requests are never
+    /// retried, as their outcome is already decided.
+    async fn execute_through_service(
+        &self,
+        request_body: String,
+        header_map: &HeaderMap,
+        endpoint_kind: EndpointKind,
+    ) -> Result<Response, RoverClientError> {
+        let mut request = http::Request::post(&self.graphql_endpoint)
+            .body(Full::new(Bytes::from(request_body)))
+            .map_err(|e| RoverClientError::Service {
+                source: Box::new(e),
+                endpoint_kind,
+            })?;
+        request.headers_mut().extend(header_map.clone());
+        let service_error = |e: HttpServiceError| RoverClientError::Service {
+            source: Box::new(e),
+            endpoint_kind,
+        };
+        let response = ReqwestService::builder()
+            .client(self.client.clone())
+            .build()
+            .map_err(service_error)?
+            .oneshot(request)
+            .await
+            .map_err(service_error)?;
+        let response = Response::from(response.map(reqwest::Body::wrap));
+        response
+            .error_for_status()
+            .map_err(|source| RoverClientError::SendRequest {
+                source,
+                endpoint_kind,
+            })
+    }
+
     /// To be used internally or by other implementations of a GraphQL client.
     ///
     /// This fn tries to parse the JSON response from a GraphQL server. It will
@@ -236,6 +364,16 @@ impl GraphQLClient {
     }
 }
 
+/// The span a GraphQL operation is sent in, matching the one `rover-graphql`
+/// sends its operations in.
+fn operation_span(operation_name: &'static str) -> tracing::Span {
+    tracing::info_span!(
+        "GraphQL operation",
+        otel.kind = "client",
+        graphql.operation.name = operation_name,
+    )
+}
+
 fn handle_graphql_body_errors(errors: Vec<GraphQLError>) -> Result<(), RoverClientError> {
     if errors.is_empty() {
         Ok(())
@@ -340,6 +478,7 @@ mod tests {
                 "{}".to_string(),
                 &HeaderMap::new(),
                 true,
+                false,
                 EndpointKind::ApolloStudio,
             )
             .await;
@@ -371,6 +510,7 @@ mod tests {
                 "{}".to_string(),
                 &HeaderMap::new(),
                 true,
+                false,
                 EndpointKind::ApolloStudio,
             )
             .await;
@@ -399,6 +539,7 @@ mod tests {
                 "{}".to_string(),
                 &HeaderMap::new(),
                 true,
+                false,
                 EndpointKind::ApolloStudio,
             )
             .await;
@@ -434,6 +575,7 @@ mod tests {
                 "{}".to_string(),
                 &HeaderMap::new(),
                 true,
+                false,
                 EndpointKind::ApolloStudio,
             )
             .await;
@@ -1,13 +1,16 @@
 use std::{str::FromStr, time::Duration};
 
 use graphql_client::GraphQLQuery;
-use houston::{Credential, CredentialOrigin};
+use houston::{Credential, CredentialOrigin, CredentialSource};
 use reqwest::{
     header::{HeaderMap, HeaderValue},
     Client as ReqwestClient,
 };
 use rover_graphql::{GraphQLLayer, GraphQLService};
-use rover_http::{retry::RetryPolicy, HttpService, ReqwestService};
+use rover_http::{
+    retry::{RetryConfig, RetryPolicy},
+    HttpService, HttpServiceError, ReqwestService,
+};
 use rover_studio::service::{HttpStudioServiceError, HttpStudioServiceLayer};
 use tower::{retry::RetryLayer, util::BoxCloneServiceLayer, ServiceBuilder, ServiceExt};
 use url::Url;
@@ -15,6 +18,7 @@ use url::Url;
 use crate::{
     blocking::{GraphQLClient, CLIENT_NAME},
     error::EndpointKind,
+    local_registry::LocalRegistry,
     RoverClientError,
 };
 
@@ -25,31 +29,42 @@ pub enum InitStudioServiceError {
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
     #[error(transparent)]
+    Http(#[from] Box<HttpServiceError>),
+    #[error(transparent)]
     StudioService(#[from] HttpStudioServiceError),
+    #[error("the local registry at {} has no Studio service", .0.display())]
+    LocalRegistry(std::path::PathBuf),
 }
 
 impl From<InitStudioServiceError> for RoverClientError {
     fn from(value: InitStudioServiceError) -> Self {
-        RoverClientError::ServiceReady(Box::new(value))
+        match value {
+            InitStudioServiceError::LocalRegistry(root) => {
+                RoverClientError::UnsupportedByLocalRegistry { root }
+            }
+            value => RoverClientError::ServiceReady(Box::new(value)),
+        }
     }
 }
 
 /// Represents a client for making GraphQL requests to Apollo Studio.
 pub struct StudioClient {
-    pub credential: Credential,
+    credential: CredentialSource,
     graphql_endpoint: String,
     client: GraphQLClient,
     reqwest_client: ReqwestClient,
     version: String,
     is_sudo: bool,
-    retry_period: Duration,
+    retry: RetryConfig,
+    local_registry: Option<LocalRegistry>,
 }
 
 impl StudioClient {
     /// Construct a new [StudioClient] from an `api_key`, a `uri`, and a `version`.
-    /// For use in Rover, the `uri` is usually going to be to Apollo Studio
+    /// For use in Rover, the `uri` is usually going to be to Apollo Studio.
+    /// The credential is read from `credential` for every request.
     pub fn new(
-        credential: Credential,
+        credential: impl Into<CredentialSource>,
         graphql_endpoint: &str,
         version: &str,
         is_sudo: bool,
@@ -57,16 +72,54 @@ impl StudioClient {
         retry_period: Duration,
     ) -> StudioClient {
         StudioClient {
-            credential,
+            credential: credential.into(),
             graphql_endpoint: graphql_endpoint.to_string(),
             reqwest_client: client.clone(),
             client: GraphQLClient::new(graphql_endpoint, client, retry_period),
             version: version.to_string(),
             is_sudo,
-            retry_period,
+            retry: RetryConfig::new(retry_period),
+            local_registry: None,
+        }
+    }
+
+    /// Construct a [StudioClient] that reads and writes graphs in a [LocalRegistry]
+    /// rather than Apollo Studio. Operations the registry doesn't support fail
+    /// without sending anything, so no credential is needed.
+    pub fn local(registry: LocalRegistry, version: &str, client: ReqwestClient) -> StudioClient {
+        let credential = Credential {
+            api_key: String::new(),
+            origin: CredentialOrigin::EnvVar,
+            expires_at: None,
+        };
+        StudioClient {
+            local_registry: Some(registry),
+            ..StudioClient::new(credential, "", version, false, client, Duration::ZERO)
         }
     }
 
+    /// The local registry this client works against, if it isn't talking to Apollo Studio
+    pub const fn local_registry(&self) -> Option<&LocalRegistry> {
+        self.local_registry.as_ref()
+    }
+
+    fn refuse_local_registry(&self) -> Result<(), RoverClientError> {
+        match &self.local_registry {
+            Some(registry) => Err(RoverClientError::UnsupportedByLocalRegistry {
+                root: registry.root().to_path_buf(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Retries requests to Studio as `retry` describes, rather than with the
+    /// default delays for up to the `retry_period` given to [`StudioClient::new`].
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> StudioClient {
+        self.client = self.client.with_retry_config(retry.clone());
+        self.retry = retry;
+        self
+    }
+
     /// Client method for making a GraphQL request to Apollo Studio.
     ///
     /// Takes one argument, `variables`. Returns a Response or a RoverClientError.
@@ -75,6 +128,7 @@ impl StudioClient {
         &self,
         variables: Q::Variables,
     ) -> Result<Q::ResponseData, RoverClientError> {
+        self.refuse_local_registry()?;
         let mut header_map = self.build_studio_headers()?;
         self.client
             .post::<Q>(variables, &mut header_map, EndpointKind::ApolloStudio)
@@ -89,6 +143,7 @@ impl StudioClient {
         &self,
         variables: Q::Variables,
     ) -> Result<Q::ResponseData, RoverClientError> {
+        self.refuse_local_registry()?;
         let mut header_map = self.build_studio_headers()?;
         self.client
             .post_no_retry::<Q>(variables, &mut header_map, EndpointKind::ApolloStudio)
@@ -117,16 +172,16 @@ impl StudioClient {
         let client_version = HeaderValue::from_str(&self.version)?;
         headers.insert("apollographql-client-version", client_version);
 
-        match &self.credential.origin {
+        let credential = self.credential.credential();
+        match &credential.origin {
             CredentialOrigin::OauthAuthorizationPkce(_)
             | CredentialOrigin::OauthClientCredentials => {
-                let mut auth =
-                    HeaderValue::from_str(&format!("Bearer {}", self.credential.api_key))?;
+                let mut auth = HeaderValue::from_str(&format!("Bearer {}", credential.api_key))?;
                 auth.set_sensitive(true);
                 headers.insert(reqwest::header::AUTHORIZATION, auth);
             }
             CredentialOrigin::EnvVar | CredentialOrigin::ConfigFile(_) => {
-                let mut api_key = HeaderValue::from_str(&self.credential.api_key)?;
+                let mut api_key = HeaderValue::from_str(&credential.api_key)?;
                 api_key.set_sensitive(true);
                 headers.insert("x-api-key", api_key);
             }
@@ -140,12 +195,17 @@ impl StudioClient {
     }
 
     pub fn get_credential_origin(&self) -> CredentialOrigin {
-        self.credential.origin.clone()
+        self.credential.credential().origin
     }
 
     pub fn studio_graphql_service(
         &self,
     ) -> Result<GraphQLService<HttpService>, InitStudioServiceError> {
+        if let Some(registry) = &self.local_registry {
+            return Err(InitStudioServiceError::LocalRegistry(
+                registry.root().to_path_buf(),
+            ));
+        }
         let service = ServiceBuilder::new()
             .layer(GraphQLLayer::default())
             .layer(BoxCloneServiceLayer::new(HttpStudioServiceLayer::new(
@@ -154,11 +214,14 @@ impl StudioClient {
                 self.version.to_string(),
                 self.is_sudo,
             )?))
-            .layer(RetryLayer::new(RetryPolicy::new(self.retry_period)))
+            .layer(RetryLayer::new(RetryPolicy::from_config(
+                self.retry.clone(),
+            )))
             .service(
                 ReqwestService::builder()
                     .client(self.reqwest_client.clone())
-                    .build()?,
+                    .build()
+                    .map_err(Box::new)?,
             );
 
         Ok(service)
@@ -115,6 +115,15 @@ pub enum RoverClientError {
     #[error("Could not find graph with ID '{graph_id}'")]
     GraphIdNotFound { graph_id: String },
 
+    /// when someone provides a proposal ID that doesn't exist, or that they
+    /// can't see.
+    #[error("Could not find proposal with ID '{proposal_id}'")]
+    ProposalNotFound { proposal_id: String },
+
+    /// when someone provides an operation collection ID that doesn't exist.
+    #[error("Could not find operation collection with ID '{collection_id}'")]
+    OperationCollectionNotFound { collection_id: String },
+
     /// The requested graph artifact could not be found.
     #[error("Could not find the graph artifact: {msg}")]
     GraphArtifactNotFound { msg: String },
@@ -274,6 +283,12 @@ pub enum RoverClientError {
     #[error("The check workflow took too long to run.")]
     ChecksTimeoutError { url: Option<String> },
 
+    #[error("Launch {launch_id} of {graph_ref} took too long to finish.")]
+    LaunchTimeoutError {
+        graph_ref: GraphRef,
+        launch_id: String,
+    },
+
     #[error("The schema check finished, but Rover could not retrieve its full result.")]
     CheckWorkflowResultUnavailable {
         url: Option<String>,
@@ -316,6 +331,9 @@ pub enum RoverClientError {
     #[error("Something went wrong on our end. This isn't your fault! Please try again.")]
     GraphProjectInitError,
 
+    #[error("This command needs Apollo GraphOS and isn't supported by the local registry at {}.", .root.display())]
+    UnsupportedByLocalRegistry { root: std::path::PathBuf },
+
     #[error("Service failed to become ready")]
     ServiceReady(Box<dyn std::error::Error + Send + Sync>),
 
@@ -15,6 +15,9 @@ pub use error::{EndpointKind, RoverClientError};
 /// Module for actually querying studio
 pub mod operations;
 
+/// Module for the file-backed graph registry
+pub mod local_registry;
+
 /// Module for getting release info
 pub mod releases;
 
@@ -0,0 +1,792 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use apollo_federation_types::rover::BuildErrors;
+use chrono::{DateTime, Local, Utc};
+use futures::future::BoxFuture;
+use rover_studio::types::GraphRef;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    operations::{
+        subgraph::{
+            delete::{SubgraphDeleteInput, SubgraphDeleteResponse},
+            fetch::SubgraphFetchInput,
+            fetch_all::{Subgraph, SubgraphFetchAllInput, SubgraphFetchAllResponse},
+            list::{SubgraphInfo, SubgraphListInput, SubgraphListResponse, SubgraphUpdatedAt},
+            publish::{SubgraphPublishInput, SubgraphPublishResponse},
+            routing_url::SubgraphRoutingUrlInput,
+        },
+        supergraph::fetch::SupergraphFetchInput,
+    },
+    shared::{FetchResponse, Sdl, SdlType},
+    RoverClientError,
+};
+
+const SUBGRAPHS_DIR: &str = "subgraphs";
+const SUPERGRAPH_FILE: &str = "supergraph.graphql";
+const BUILD_ERRORS_FILE: &str = "build-errors.json";
+
+/// Composes a variant's subgraphs into a supergraph whenever a [`LocalRegistry`] changes.
+///
+/// Returns the supergraph SDL, or the build errors if the subgraphs don't compose.
+/// An `Err` means composition couldn't be run at all.
+pub trait Compose: Send + Sync {
+    fn compose(
+        &self,
+        subgraphs: Vec<Subgraph>,
+    ) -> BoxFuture<'_, Result<Result<String, BuildErrors>, RoverClientError>>;
+}
+
+/// A graph registry kept in a local directory, for working without Apollo Studio.
+///
+/// Each variant lives in `<root>/<graph id>/<variant>/`, with one JSON file per subgraph in
+/// `subgraphs/` and the most recent successful composition in `supergraph.graphql`.
+#[derive(Clone)]
+pub struct LocalRegistry {
+    root: PathBuf,
+    composer: Arc<dyn Compose>,
+}
+
+/// A subgraph as it is stored on disk
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct StoredSubgraph {
+    routing_url: Option<String>,
+    sdl: String,
+    updated_at: DateTime<Utc>,
+}
+
+impl LocalRegistry {
+    /// Construct a [LocalRegistry] rooted at `root` that runs `composer` on every change.
+    pub fn new(root: impl Into<PathBuf>, composer: impl Compose + 'static) -> LocalRegistry {
+        LocalRegistry {
+            root: root.into(),
+            composer: Arc::new(composer),
+        }
+    }
+
+    /// The directory this registry is kept in
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Stores a subgraph, creating its graph and variant if they don't exist yet,
+    /// and recomposes the variant's supergraph.
+    pub async fn publish_subgraph(
+        &self,
+        input: SubgraphPublishInput,
+    ) -> Result<SubgraphPublishResponse, RoverClientError> {
+        let subgraph_path = self.subgraph_path(&input.graph_ref, &input.subgraph)?;
+        let existing = read_json::<StoredSubgraph>(&subgraph_path)?;
+        let routing_url = input
+            .url
+            .or_else(|| existing.as_ref().and_then(|s| s.routing_url.clone()));
+        if existing.is_none() && routing_url.is_none() {
+            return Err(RoverClientError::MissingRoutingUrlError {
+                subgraph_name: input.subgraph,
+                graph_ref: input.graph_ref,
+            });
+        }
+
+        let unchanged = existing
+            .as_ref()
+            .is_some_and(|s| s.sdl == input.schema && s.routing_url == routing_url);
+        if unchanged {
+            return Ok(SubgraphPublishResponse {
+                api_schema_hash: None,
+                supergraph_was_updated: false,
+                subgraph_was_created: false,
+                subgraph_was_updated: false,
+                build_errors: BuildErrors::new(),
+                launch_id: None,
+                launch_url: None,
+                launch_cli_copy: None,
+            });
+        }
+
+        let subgraph = StoredSubgraph {
+            routing_url,
+            sdl: input.schema,
+            updated_at: Utc::now(),
+        };
+        let mut subgraphs = if self.variant_dir(&input.graph_ref).is_dir() {
+            self.subgraphs(&input.graph_ref)?
+        } else {
+            Vec::new()
+        };
+        subgraphs.retain(|(name, _)| name != &input.subgraph);
+        subgraphs.push((input.subgraph.clone(), subgraph.clone()));
+        subgraphs.sort_by(|a, b| a.0.cmp(&b.0));
+        let composition = self.compose(subgraphs).await?;
+
+        write_json(&subgraph_path, &subgraph)?;
+        let build_errors = self.save_composition(&input.graph_ref, composition)?;
+
+        Ok(SubgraphPublishResponse {
+            api_schema_hash: None,
+            supergraph_was_updated: build_errors.is_empty(),
+            subgraph_was_created: existing.is_none(),
+            subgraph_was_updated: existing.is_some(),
+            build_errors,
+            launch_id: None,
+            launch_url: None,
+            launch_cli_copy: None,
+        })
+    }
+
+    /// Removes a subgraph and recomposes the variant's supergraph without it.
+    /// With `dry_run`, only reports whether the remaining subgraphs would compose.
+    pub async fn delete_subgraph(
+        &self,
+        input: SubgraphDeleteInput,
+    ) -> Result<SubgraphDeleteResponse, RoverClientError> {
+        let subgraph_path = self.subgraph_path(&input.graph_ref, &input.subgraph)?;
+        self.read_subgraph(&input.graph_ref, &input.subgraph)?;
+
+        let remaining = self
+            .subgraphs(&input.graph_ref)?
+            .into_iter()
+            .filter(|(name, _)| name != &input.subgraph)
+            .collect();
+        let composition = self.compose(remaining).await?;
+
+        let build_errors = if input.dry_run {
+            match composition {
+                Some(Err(build_errors)) => build_errors,
+                _ => BuildErrors::new(),
+            }
+        } else {
+            remove_file(&subgraph_path)?;
+            self.save_composition(&input.graph_ref, composition)?
+        };
+
+        Ok(SubgraphDeleteResponse {
+            supergraph_was_updated: !input.dry_run && build_errors.is_empty(),
+            build_errors,
+        })
+    }
+
+    /// Returns a subgraph's SDL and routing URL
+    pub fn fetch_subgraph(
+        &self,
+        input: SubgraphFetchInput,
+    ) -> Result<FetchResponse, RoverClientError> {
+        let subgraph = self.read_subgraph(&input.graph_ref, &input.subgraph_name)?;
+        Ok(FetchResponse {
+            sdl: Sdl {
+                contents: subgraph.sdl,
+                r#type: SdlType::Subgraph {
+                    routing_url: subgraph.routing_url,
+                },
+            },
+        })
+    }
+
+    /// Returns every subgraph in a variant
+    pub fn fetch_all_subgraphs(
+        &self,
+        input: SubgraphFetchAllInput,
+    ) -> Result<SubgraphFetchAllResponse, RoverClientError> {
+        let subgraphs = self.subgraphs(&input.graph_ref)?;
+        if subgraphs.is_empty() {
+            return Err(RoverClientError::ExpectedFederatedGraph {
+                graph_ref: input.graph_ref,
+                can_operation_convert: false,
+            });
+        }
+        Ok(SubgraphFetchAllResponse {
+            subgraphs: to_subgraphs(subgraphs),
+            federation_version: None,
+        })
+    }
+
+    /// Lists a variant's subgraphs, most recently updated first
+    pub fn list_subgraphs(
+        &self,
+        input: SubgraphListInput,
+    ) -> Result<SubgraphListResponse, RoverClientError> {
+        let mut subgraphs: Vec<SubgraphInfo> = self
+            .subgraphs(&input.graph_ref)?
+            .into_iter()
+            .map(|(name, subgraph)| SubgraphInfo {
+                name,
+                url: subgraph.routing_url,
+                updated_at: SubgraphUpdatedAt {
+                    local: Some(subgraph.updated_at.with_timezone(&Local)),
+                    utc: Some(subgraph.updated_at),
+                },
+            })
+            .collect();
+        subgraphs.sort_by(|a, b| a.updated_at.utc.cmp(&b.updated_at.utc).reverse());
+        Ok(SubgraphListResponse {
+            subgraphs,
+            root_url: String::new(),
+            graph_ref: input.graph_ref,
+        })
+    }
+
+    /// Returns a subgraph's routing URL
+    pub fn routing_url(&self, input: SubgraphRoutingUrlInput) -> Result<String, RoverClientError> {
+        self.read_subgraph(&input.graph_ref, &input.subgraph_name)?
+            .routing_url
+            .ok_or(RoverClientError::MissingRoutingUrlError {
+                subgraph_name: input.subgraph_name,
+                graph_ref: input.graph_ref,
+            })
+    }
+
+    /// Returns the variant's most recently composed supergraph
+    pub fn fetch_supergraph(
+        &self,
+        input: SupergraphFetchInput,
+    ) -> Result<FetchResponse, RoverClientError> {
+        let variant_dir = self.existing_variant_dir(&input.graph_ref)?;
+        match read_file(&variant_dir.join(SUPERGRAPH_FILE))? {
+            Some(contents) => Ok(FetchResponse {
+                sdl: Sdl {
+                    contents,
+                    r#type: SdlType::Supergraph,
+                },
+            }),
+            None => Err(RoverClientError::NoSupergraphBuilds {
+                source: read_json(&variant_dir.join(BUILD_ERRORS_FILE))?.unwrap_or_default(),
+                graph_ref: input.graph_ref,
+            }),
+        }
+    }
+
+    /// Composes a variant's subgraphs as they will be after a change. Nothing is
+    /// written yet, so the change can be abandoned if composition can't run at all.
+    /// A variant without subgraphs has nothing to compose.
+    async fn compose(
+        &self,
+        subgraphs: Vec<(String, StoredSubgraph)>,
+    ) -> Result<Option<Result<String, BuildErrors>>, RoverClientError> {
+        if subgraphs.is_empty() {
+            return Ok(None);
+        }
+        self.composer
+            .compose(to_subgraphs(subgraphs))
+            .await
+            .map(Some)
+    }
+
+    /// Keeps the supergraph if the variant composed and the build errors if it
+    /// didn't, and returns those build errors.
+    fn save_composition(
+        &self,
+        graph_ref: &GraphRef,
+        composition: Option<Result<String, BuildErrors>>,
+    ) -> Result<BuildErrors, RoverClientError> {
+        let variant_dir = self.variant_dir(graph_ref);
+        match composition {
+            Some(Ok(supergraph_sdl)) => {
+                write_file(&variant_dir.join(SUPERGRAPH_FILE), &supergraph_sdl)?;
+                remove_file(&variant_dir.join(BUILD_ERRORS_FILE))?;
+                Ok(BuildErrors::new())
+            }
+            Some(Err(build_errors)) => {
+                write_json(&variant_dir.join(BUILD_ERRORS_FILE), &build_errors)?;
+                Ok(build_errors)
+            }
+            None => {
+                remove_file(&variant_dir.join(SUPERGRAPH_FILE))?;
+                remove_file(&variant_dir.join(BUILD_ERRORS_FILE))?;
+                Ok(BuildErrors::new())
+            }
+        }
+    }
+
+    fn variant_dir(&self, graph_ref: &GraphRef) -> PathBuf {
+        self.root
+            .join(graph_ref.graph_id())
+            .join(graph_ref.variant())
+    }
+
+    fn existing_variant_dir(&self, graph_ref: &GraphRef) -> Result<PathBuf, RoverClientError> {
+        let variant_dir = self.variant_dir(graph_ref);
+        if variant_dir.is_dir() {
+            Ok(variant_dir)
+        } else {
+            Err(RoverClientError::GraphNotFound {
+                graph_ref: graph_ref.clone(),
+            })
+        }
+    }
+
+    fn subgraph_path(&self, graph_ref: &GraphRef, name: &str) -> Result<PathBuf, RoverClientError> {
+        let is_valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_valid {
+            return Err(RoverClientError::AdhocError {
+                msg: format!(
+                    "'{name}' is not a valid subgraph name. Subgraph names may only contain letters, numbers, '-' and '_'."
+                ),
+            });
+        }
+        Ok(self
+            .variant_dir(graph_ref)
+            .join(SUBGRAPHS_DIR)
+            .join(format!("{name}.json")))
+    }
+
+    fn read_subgraph(
+        &self,
+        graph_ref: &GraphRef,
+        name: &str,
+    ) -> Result<StoredSubgraph, RoverClientError> {
+        let subgraphs = self.subgraphs(graph_ref)?;
+        let valid_subgraphs = subgraphs.iter().map(|(name, _)| name.clone()).collect();
+        subgraphs
+            .into_iter()
+            .find_map(|(n, subgraph)| (n == name).then_some(subgraph))
+            .ok_or_else(|| RoverClientError::NoSubgraphInGraph {
+                invalid_subgraph: name.to_string(),
+                valid_subgraphs,
+            })
+    }
+
+    /// Reads every subgraph in a variant, sorted by name
+    fn subgraphs(
+        &self,
+        graph_ref: &GraphRef,
+    ) -> Result<Vec<(String, StoredSubgraph)>, RoverClientError> {
+        let subgraphs_dir = self.existing_variant_dir(graph_ref)?.join(SUBGRAPHS_DIR);
+        let entries = match fs::read_dir(&subgraphs_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(io_error(&subgraphs_dir, err)),
+        };
+
+        let mut subgraphs = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|err| io_error(&subgraphs_dir, err))?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if let Some(subgraph) = read_json(&path)? {
+                subgraphs.push((name.to_string(), subgraph));
+            }
+        }
+        subgraphs.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(subgraphs)
+    }
+}
+
+impl fmt::Debug for LocalRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalRegistry")
+            .field("root", &self.root)
+            .finish_non_exhaustive()
+    }
+}
+
+fn to_subgraphs(subgraphs: Vec<(String, StoredSubgraph)>) -> Vec<Subgraph> {
+    subgraphs
+        .into_iter()
+        .map(|(name, subgraph)| {
+            Subgraph::builder()
+                .name(name)
+                .and_url(subgraph.routing_url)
+                .sdl(subgraph.sdl)
+                .build()
+        })
+        .collect()
+}
+
+fn io_error(path: &Path, err: io::Error) -> RoverClientError {
+    RoverClientError::AdhocError {
+        msg: format!(
+            "Could not access the local registry at {}: {err}",
+            path.display()
+        ),
+    }
+}
+
+fn read_file(path: &Path) -> Result<Option<String>, RoverClientError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(io_error(path, err)),
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>, RoverClientError> {
+    read_file(path)?
+        .map(|contents| serde_json::from_str(&contents))
+        .transpose()
+        .map_err(RoverClientError::from)
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), RoverClientError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| io_error(parent, err))?;
+    }
+    fs::write(path, contents).map_err(|err| io_error(path, err))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), RoverClientError> {
+    write_file(path, &serde_json::to_string_pretty(value)?)
+}
+
+fn remove_file(path: &Path) -> Result<(), RoverClientError> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(io_error(path, err)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        sync::{Arc, Mutex},
+    };
+
+    use apollo_federation_types::rover::BuildError;
+    use pretty_assertions::assert_eq;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::shared::GitContext;
+
+    /// Composes by joining the subgraphs' SDL. It fails to compose if any of them
+    /// contains "broken", and fails to run at all if any contains "unrunnable".
+    #[derive(Clone, Default)]
+    struct FakeComposer {
+        calls: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    impl Compose for FakeComposer {
+        fn compose(
+            &self,
+            subgraphs: Vec<Subgraph>,
+        ) -> BoxFuture<'_, Result<Result<String, BuildErrors>, RoverClientError>> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(subgraphs.iter().map(|s| s.name().clone()).collect());
+            if subgraphs.iter().any(|s| s.sdl().contains("unrunnable")) {
+                return Box::pin(async {
+                    Err(RoverClientError::AdhocError {
+                        msg: "no supergraph plugin".to_string(),
+                    })
+                });
+            }
+            let result = if subgraphs.iter().any(|s| s.sdl().contains("broken")) {
+                Err(BuildErrors::from(vec![BuildError::composition_error(
+                    None,
+                    Some("it's broken".to_string()),
+                    None,
+                    None,
+                )]))
+            } else {
+                Ok(subgraphs
+                    .iter()
+                    .map(|s| s.sdl().as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            };
+            Box::pin(async move { Ok(result) })
+        }
+    }
+
+    fn graph_ref() -> GraphRef {
+        GraphRef::from_str("my-graph@current").unwrap()
+    }
+
+    fn publish_input(subgraph: &str, url: Option<&str>, schema: &str) -> SubgraphPublishInput {
+        SubgraphPublishInput {
+            graph_ref: graph_ref(),
+            subgraph: subgraph.to_string(),
+            url: url.map(str::to_string),
+            schema: schema.to_string(),
+            git_context: GitContext {
+                branch: None,
+                author: None,
+                commit: None,
+                remote_url: None,
+            },
+            convert_to_federated_graph: false,
+            changelog_message: None,
+        }
+    }
+
+    fn registry() -> (LocalRegistry, FakeComposer, assert_fs::TempDir) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let composer = FakeComposer::default();
+        (
+            LocalRegistry::new(dir.path(), composer.clone()),
+            composer,
+            dir,
+        )
+    }
+
+    #[tokio::test]
+    async fn publishing_a_new_subgraph_composes_the_variant() {
+        let (registry, composer, _dir) = registry();
+
+        let response = registry
+            .publish_subgraph(publish_input(
+                "products",
+                Some("http://products"),
+                "type Query { a: Int }",
+            ))
+            .await
+            .unwrap();
+
+        assert_that!(response.subgraph_was_created).is_true();
+        assert_that!(response.supergraph_was_updated).is_true();
+        assert_eq!(*composer.calls.lock().unwrap(), vec![vec!["products"]]);
+        let supergraph = registry
+            .fetch_supergraph(SupergraphFetchInput {
+                graph_ref: graph_ref(),
+            })
+            .unwrap();
+        assert_eq!(supergraph.sdl.contents, "type Query { a: Int }");
+    }
+
+    #[tokio::test]
+    async fn publishing_an_unchanged_subgraph_does_not_recompose() {
+        let (registry, composer, _dir) = registry();
+        let input = publish_input("products", Some("http://products"), "type Query { a: Int }");
+        registry.publish_subgraph(input.clone()).await.unwrap();
+
+        let response = registry
+            .publish_subgraph(SubgraphPublishInput { url: None, ..input })
+            .await
+            .unwrap();
+
+        assert_that!(response.subgraph_was_created).is_false();
+        assert_that!(response.subgraph_was_updated).is_false();
+        assert_that!(*composer.calls.lock().unwrap()).has_length(1);
+    }
+
+    #[tokio::test]
+    async fn a_new_subgraph_needs_a_routing_url() {
+        let (registry, _composer, _dir) = registry();
+
+        let result = registry
+            .publish_subgraph(publish_input("products", None, "type Query { a: Int }"))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(RoverClientError::MissingRoutingUrlError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn build_errors_keep_the_last_good_supergraph() {
+        let (registry, _composer, _dir) = registry();
+        registry
+            .publish_subgraph(publish_input(
+                "products",
+                Some("http://products"),
+                "type Query { a: Int }",
+            ))
+            .await
+            .unwrap();
+
+        let response = registry
+            .publish_subgraph(publish_input("reviews", Some("http://reviews"), "broken"))
+            .await
+            .unwrap();
+
+        assert_that!(response.subgraph_was_created).is_true();
+        assert_that!(response.supergraph_was_updated).is_false();
+        assert_that!(response.build_errors.len()).is_equal_to(1);
+        let supergraph = registry
+            .fetch_supergraph(SupergraphFetchInput {
+                graph_ref: graph_ref(),
+            })
+            .unwrap();
+        assert_eq!(supergraph.sdl.contents, "type Query { a: Int }");
+    }
+
+    #[tokio::test]
+    async fn nothing_is_stored_when_composition_cannot_run() {
+        let (registry, _composer, dir) = registry();
+
+        let result = registry
+            .publish_subgraph(publish_input(
+                "products",
+                Some("http://products"),
+                "unrunnable",
+            ))
+            .await;
+
+        assert!(matches!(result, Err(RoverClientError::AdhocError { .. })));
+        assert_that!(dir.path().join("my-graph").exists()).is_false();
+    }
+
+    #[tokio::test]
+    async fn a_variant_that_never_composed_has_no_supergraph() {
+        let (registry, _composer, _dir) = registry();
+        registry
+            .publish_subgraph(publish_input("reviews", Some("http://reviews"), "broken"))
+            .await
+            .unwrap();
+
+        let result = registry.fetch_supergraph(SupergraphFetchInput {
+            graph_ref: graph_ref(),
+        });
+
+        assert!(matches!(
+            result,
+            Err(RoverClientError::NoSupergraphBuilds { source, .. }) if source.len() == 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn subgraphs_can_be_fetched_and_listed() {
+        let (registry, _composer, _dir) = registry();
+        registry
+            .publish_subgraph(publish_input(
+                "products",
+                Some("http://products"),
+                "type Query { a: Int }",
+            ))
+            .await
+            .unwrap();
+        registry
+            .publish_subgraph(publish_input(
+                "reviews",
+                Some("http://reviews"),
+                "type Query { b: Int }",
+            ))
+            .await
+            .unwrap();
+
+        let fetched = registry
+            .fetch_subgraph(SubgraphFetchInput {
+                graph_ref: graph_ref(),
+                subgraph_name: "reviews".to_string(),
+            })
+            .unwrap();
+        assert_eq!(
+            fetched.sdl.r#type,
+            SdlType::Subgraph {
+                routing_url: Some("http://reviews".to_string())
+            }
+        );
+        assert_eq!(fetched.sdl.contents, "type Query { b: Int }");
+
+        let all = registry
+            .fetch_all_subgraphs(SubgraphFetchAllInput {
+                graph_ref: graph_ref(),
+            })
+            .unwrap();
+        let names: Vec<_> = all.subgraphs.iter().map(|s| s.name().as_str()).collect();
+        assert_eq!(names, vec!["products", "reviews"]);
+
+        let listed = registry
+            .list_subgraphs(SubgraphListInput {
+                graph_ref: graph_ref(),
+            })
+            .unwrap();
+        assert_that!(listed.subgraphs).has_length(2);
+
+        let routing_url = registry
+            .routing_url(SubgraphRoutingUrlInput {
+                graph_ref: graph_ref(),
+                subgraph_name: "products".to_string(),
+            })
+            .unwrap();
+        assert_eq!(routing_url, "http://products");
+    }
+
+    #[tokio::test]
+    async fn deleting_a_subgraph_recomposes_without_it() {
+        let (registry, composer, _dir) = registry();
+        registry
+            .publish_subgraph(publish_input(
+                "products",
+                Some("http://products"),
+                "type Query { a: Int }",
+            ))
+            .await
+            .unwrap();
+        registry
+            .publish_subgraph(publish_input(
+                "reviews",
+                Some("http://reviews"),
+                "type Query { b: Int }",
+            ))
+            .await
+            .unwrap();
+
+        let dry_run = registry
+            .delete_subgraph(SubgraphDeleteInput {
+                graph_ref: graph_ref(),
+                subgraph: "reviews".to_string(),
+                dry_run: true,
+            })
+            .await
+            .unwrap();
+        assert_that!(dry_run.supergraph_was_updated).is_false();
+        assert_that!(registry.fetch_subgraph(SubgraphFetchInput {
+            graph_ref: graph_ref(),
+            subgraph_name: "reviews".to_string(),
+        }))
+        .is_ok();
+
+        let deleted = registry
+            .delete_subgraph(SubgraphDeleteInput {
+                graph_ref: graph_ref(),
+                subgraph: "reviews".to_string(),
+                dry_run: false,
+            })
+            .await
+            .unwrap();
+        assert_that!(deleted.supergraph_was_updated).is_true();
+        assert_eq!(
+            composer.calls.lock().unwrap().last().unwrap(),
+            &vec!["products".to_string()]
+        );
+        assert!(matches!(
+            registry.fetch_subgraph(SubgraphFetchInput {
+                graph_ref: graph_ref(),
+                subgraph_name: "reviews".to_string(),
+            }),
+            Err(RoverClientError::NoSubgraphInGraph { valid_subgraphs, .. }) if valid_subgraphs == vec!["products"]
+        ));
+    }
+
+    #[test]
+    fn an_unknown_variant_is_not_found() {
+        let (registry, _composer, _dir) = registry();
+
+        let result = registry.list_subgraphs(SubgraphListInput {
+            graph_ref: graph_ref(),
+        });
+
+        assert!(matches!(
+            result,
+            Err(RoverClientError::GraphNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn subgraph_names_cannot_escape_the_registry() {
+        let (registry, _composer, _dir) = registry();
+
+        let result = registry
+            .publish_subgraph(publish_input(
+                "../escape",
+                Some("http://x"),
+                "type Query { a: Int }",
+            ))
+            .await;
+
+        assert!(matches!(result, Err(RoverClientError::AdhocError { .. })));
+    }
+}
@@ -11,7 +11,7 @@ pub async fn run(client: &StudioClient) -> Result<RegistryIdentity, RoverClientE
     let mut service = WhoAmI::new(
         client
             .studio_graphql_service()
-            .map_err(|err| RoverClientError::ServiceReady(Box::new(err)))?,
+            .map_err(RoverClientError::from)?,
     );
     let service = service.ready().await?;
     let identity = service
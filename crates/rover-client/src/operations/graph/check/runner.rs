@@ -13,7 +13,7 @@ pub async fn run(
     let mut service = GraphCheck::new(
         client
             .studio_graphql_service()
-            .map_err(|err| RoverClientError::ServiceReady(Box::new(err)))?,
+            .map_err(RoverClientError::from)?,
     );
     let service = service.ready().await?;
     service.call(input).await
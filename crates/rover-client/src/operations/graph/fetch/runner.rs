@@ -19,7 +19,7 @@ pub async fn run(
     let mut service = GraphFetch::new(
         client
             .studio_graphql_service()
-            .map_err(|err| RoverClientError::ServiceReady(Box::new(err)))?,
+            .map_err(RoverClientError::from)?,
     );
     let service = service.ready().await?;
     service.call(GraphFetchRequest::new(input)).await
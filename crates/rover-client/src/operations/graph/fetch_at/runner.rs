@@ -0,0 +1,175 @@
+use graphql_client::*;
+use rover_studio::types::GraphRef;
+
+use super::types::*;
+use crate::{
+    blocking::StudioClient,
+    operations::graph::history::{resolve_launch, HistoryPoint},
+    shared::{graphql_enum_value, FetchResponse, Sdl, SdlType},
+    RoverClientError,
+};
+
+type GraphQLDocument = String;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/graph/fetch_at/fetch_at_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. graph_fetch_at_query
+pub(crate) struct GraphFetchAtQuery;
+
+/// Fetch the SDL a graph variant published in a past launch, given either the
+/// launch's ID or a time to find the launch that was live then
+pub async fn run(
+    input: GraphFetchAtInput,
+    client: &StudioClient,
+) -> Result<FetchResponse, RoverClientError> {
+    let launch_id = match &input.at {
+        HistoryPoint::Launch(id) => id.clone(),
+        point @ HistoryPoint::Time(_) => {
+            resolve_launch(&input.graph_ref, point, client)
+                .await?
+                .launch_id
+        }
+    };
+    let data = client
+        .post::<GraphFetchAtQuery>(graph_fetch_at_query::Variables {
+            graph_id: input.graph_ref.graph_id().clone(),
+            variant: input.graph_ref.variant().clone(),
+            launch_id: launch_id.clone(),
+        })
+        .await?;
+    build_response(data, &input.graph_ref, &launch_id)
+}
+
+fn build_response(
+    data: graph_fetch_at_query::ResponseData,
+    graph_ref: &GraphRef,
+    launch_id: &str,
+) -> Result<FetchResponse, RoverClientError> {
+    let launch = data
+        .graph
+        .and_then(|graph| graph.variant)
+        .ok_or_else(|| RoverClientError::GraphNotFound {
+            graph_ref: graph_ref.clone(),
+        })?
+        .launch
+        .ok_or_else(|| RoverClientError::AdhocError {
+            msg: format!("Could not find launch '{launch_id}' in the history of {graph_ref}"),
+        })?;
+    match launch.publication {
+        Some(publication) => Ok(FetchResponse {
+            sdl: Sdl {
+                contents: publication.schema.document,
+                r#type: SdlType::Graph,
+            },
+        }),
+        None => Err(RoverClientError::AdhocError {
+            msg: format!(
+                "Launch '{launch_id}' of {graph_ref} didn't publish a schema (its status is {})",
+                graphql_enum_value(launch.status)
+            ),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use serde_json::json;
+
+    use super::*;
+
+    fn graph_ref() -> GraphRef {
+        GraphRef::new("mygraph", Some("current")).unwrap()
+    }
+
+    #[test]
+    fn failed_launches_have_no_schema() {
+        let data = serde_json::from_value(json!({ "graph": { "variant": { "launch": {
+            "status": "LAUNCH_FAILED",
+            "publication": null
+        } } } }))
+        .unwrap();
+        assert!(matches!(
+            build_response(data, &graph_ref(), "l-1"),
+            Err(RoverClientError::AdhocError { msg }) if msg.contains("LAUNCH_FAILED")
+        ));
+    }
+
+    #[tokio::test]
+    async fn run_fetches_the_schema_live_at_a_time() {
+        let server = MockServer::start_async().await;
+        let history = server.mock(|when, then| {
+            when.method(POST).body_includes("GraphHistoryQuery");
+            then.status(200)
+                .json_body(json!({ "data": { "graph": { "variant": {
+                "launchSummaries": [
+                    {
+                        "id": "l-2",
+                        "createdAt": "2026-01-02T00:00:00Z",
+                        "completedAt": null,
+                        "status": "LAUNCH_COMPLETED",
+                        "subgraphChanges": null,
+                        "buildInput": { "__typename": "FilterBuildInput" }
+                    },
+                    {
+                        "id": "l-1",
+                        "createdAt": "2026-01-01T00:00:00Z",
+                        "completedAt": null,
+                        "status": "LAUNCH_COMPLETED",
+                        "subgraphChanges": null,
+                        "buildInput": { "__typename": "FilterBuildInput" }
+                    }
+                ]
+            } } } }));
+        });
+        let fetch = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("GraphFetchAtQuery")
+                .body_includes("\"launchId\":\"l-1\"");
+            then.status(200)
+                .json_body(json!({ "data": { "graph": { "variant": { "launch": {
+                "status": "LAUNCH_COMPLETED",
+                "publication": { "schema": { "document": "type Query { old: String }" } }
+            } } } } }));
+        });
+
+        let client = StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        );
+        let response = run(
+            GraphFetchAtInput {
+                graph_ref: graph_ref(),
+                at: "2026-01-01T12:00:00Z".parse().unwrap(),
+            },
+            &client,
+        )
+        .await
+        .unwrap();
+
+        history.assert();
+        fetch.assert();
+        assert_eq!(response.sdl.contents, "type Query { old: String }");
+    }
+}
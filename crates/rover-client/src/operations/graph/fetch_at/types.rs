@@ -0,0 +1,10 @@
+use rover_studio::types::GraphRef;
+
+use crate::operations::graph::history::HistoryPoint;
+
+/// Input for fetching the SDL a graph variant had at a point in its history
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GraphFetchAtInput {
+    pub graph_ref: GraphRef,
+    pub at: HistoryPoint,
+}
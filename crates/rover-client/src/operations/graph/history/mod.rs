@@ -0,0 +1,9 @@
+mod runner;
+mod types;
+
+pub use runner::run;
+pub(crate) use runner::{launches, resolve_launch, MAX_CONCURRENT_QUERIES, MAX_PAGES, PAGE_SIZE};
+pub use types::{
+    ChangeCounts, GraphHistoryInput, GraphHistoryResponse, GraphPublish, HistoryPoint,
+    LaunchSubgraph, LaunchSummary, SubgraphChange,
+};
@@ -0,0 +1,449 @@
+use chrono::DateTime;
+use futures::{StreamExt, TryStreamExt};
+use graphql_client::*;
+use rover_studio::types::GraphRef;
+
+use self::graph_history_query::GraphHistoryQueryGraphVariantLaunchSummariesBuildInput as BuildInput;
+use super::types::*;
+use crate::{blocking::StudioClient, shared::graphql_enum_value, RoverClientError};
+
+type Timestamp = String;
+
+/// How many launches to ask for at a time when searching a variant's history
+pub(crate) const PAGE_SIZE: i64 = 100;
+/// How far back to search a variant's history, in pages
+pub(crate) const MAX_PAGES: i64 = 20;
+/// How many per-launch queries to have in flight at once
+pub(crate) const MAX_CONCURRENT_QUERIES: usize = 10;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/graph/history/history_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. graph_history_query
+pub(crate) struct GraphHistoryQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/graph/history/launch_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// Snake case of this name is the mod name. i.e. graph_history_launch_query
+pub(crate) struct GraphHistoryLaunchQuery;
+
+/// Lists a variant's most recent publishes, newest first, with how much each
+/// one changed the API schema
+pub async fn run(
+    input: GraphHistoryInput,
+    client: &StudioClient,
+) -> Result<GraphHistoryResponse, RoverClientError> {
+    let launches = launches(&input.graph_ref, input.limit, 0, client).await?;
+    let publishes = futures::stream::iter(launches)
+        .map(|launch| async {
+            let data = client
+                .post::<GraphHistoryLaunchQuery>(graph_history_launch_query::Variables {
+                    graph_id: input.graph_ref.graph_id().clone(),
+                    variant: input.graph_ref.variant().clone(),
+                    launch_id: launch.launch_id.clone(),
+                })
+                .await?;
+            Ok::<_, RoverClientError>(build_publish(data, launch))
+        })
+        .buffered(MAX_CONCURRENT_QUERIES)
+        .try_collect()
+        .await?;
+    Ok(GraphHistoryResponse {
+        graph_ref: input.graph_ref,
+        publishes,
+    })
+}
+
+/// A page of a variant's launches, newest first
+pub(crate) async fn launches(
+    graph_ref: &GraphRef,
+    limit: i64,
+    offset: i64,
+    client: &StudioClient,
+) -> Result<Vec<LaunchSummary>, RoverClientError> {
+    let data = client
+        .post::<GraphHistoryQuery>(history_variables(graph_ref, limit, offset))
+        .await?;
+    build_launches(data, graph_ref)
+}
+
+/// Finds the launch a [`HistoryPoint`] refers to, searching back through the
+/// variant's history a page at a time
+pub(crate) async fn resolve_launch(
+    graph_ref: &GraphRef,
+    point: &HistoryPoint,
+    client: &StudioClient,
+) -> Result<LaunchSummary, RoverClientError> {
+    for page in 0..MAX_PAGES {
+        let launches = launches(graph_ref, PAGE_SIZE, page * PAGE_SIZE, client).await?;
+        let page_len = launches.len() as i64;
+        if let Some(launch) = launches
+            .into_iter()
+            .find(|launch| launch_matches(launch, point))
+        {
+            return Ok(launch);
+        }
+        if page_len < PAGE_SIZE {
+            break;
+        }
+    }
+    Err(RoverClientError::AdhocError {
+        msg: match point {
+            HistoryPoint::Launch(id) => {
+                format!("Could not find launch '{id}' in the history of {graph_ref}")
+            }
+            HistoryPoint::Time(_) => {
+                format!("{graph_ref} has no completed launches from before {point}")
+            }
+        },
+    })
+}
+
+fn launch_matches(launch: &LaunchSummary, point: &HistoryPoint) -> bool {
+    match point {
+        HistoryPoint::Launch(id) => &launch.launch_id == id,
+        // launches come newest first, so the first one that had completed by
+        // the point in time is the one that was live then. Failed and
+        // superseded launches never published a schema, so were never live.
+        HistoryPoint::Time(time) => {
+            let went_live = launch.completed_at.as_deref().unwrap_or(&launch.created_at);
+            launch.status == "LAUNCH_COMPLETED"
+                && DateTime::parse_from_rfc3339(went_live).is_ok_and(|went_live| went_live <= *time)
+        }
+    }
+}
+
+fn build_launches(
+    data: graph_history_query::ResponseData,
+    graph_ref: &GraphRef,
+) -> Result<Vec<LaunchSummary>, RoverClientError> {
+    let launches = data
+        .graph
+        .and_then(|graph| graph.variant)
+        .ok_or_else(|| RoverClientError::GraphNotFound {
+            graph_ref: graph_ref.clone(),
+        })?
+        .launch_summaries
+        .unwrap_or_default();
+    Ok(launches
+        .into_iter()
+        .map(|launch| LaunchSummary {
+            launch_id: launch.id,
+            created_at: launch.created_at,
+            completed_at: launch.completed_at,
+            status: graphql_enum_value(launch.status),
+            subgraph_changes: launch
+                .subgraph_changes
+                .unwrap_or_default()
+                .into_iter()
+                .map(|change| SubgraphChange {
+                    name: change.name,
+                    change: graphql_enum_value(change.type_),
+                })
+                .collect(),
+            subgraphs: match launch.build_input {
+                BuildInput::CompositionBuildInput(input) => input
+                    .subgraphs
+                    .into_iter()
+                    .map(|subgraph| LaunchSubgraph {
+                        name: subgraph.name,
+                        schema_hash: subgraph.hash,
+                        routing_url: subgraph.routing_url,
+                    })
+                    .collect(),
+                BuildInput::FilterBuildInput => Vec::new(),
+            },
+        })
+        .collect())
+}
+
+fn build_publish(
+    data: graph_history_launch_query::ResponseData,
+    launch: LaunchSummary,
+) -> GraphPublish {
+    let publication = data
+        .graph
+        .and_then(|graph| graph.variant)
+        .and_then(|variant| variant.launch)
+        .and_then(|launch| launch.publication);
+    match publication {
+        Some(publication) => GraphPublish {
+            launch,
+            published_at: Some(publication.published_at),
+            schema_hash: Some(publication.schema.hash),
+            changes: publication.diff_to_previous.map(|diff| {
+                let total = diff.change_summary.total;
+                ChangeCounts {
+                    additions: total.additions,
+                    removals: total.removals,
+                    edits: total.edits,
+                    deprecations: total.deprecations,
+                }
+            }),
+        },
+        None => GraphPublish {
+            launch,
+            published_at: None,
+            schema_hash: None,
+            changes: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn graph_ref() -> GraphRef {
+        GraphRef::new("mygraph", Some("current")).unwrap()
+    }
+
+    fn mock_client(server: &MockServer) -> StudioClient {
+        StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        )
+    }
+
+    fn launch_json(id: &str, created_at: &str) -> Value {
+        launch_json_with_status(id, created_at, "LAUNCH_COMPLETED")
+    }
+
+    fn launch_json_with_status(id: &str, created_at: &str, status: &str) -> Value {
+        json!({
+            "id": id,
+            "createdAt": created_at,
+            "completedAt": created_at,
+            "status": status,
+            "subgraphChanges": [{ "name": "products", "type": "MODIFICATION" }],
+            "buildInput": {
+                "__typename": "CompositionBuildInput",
+                "subgraphs": [
+                    { "name": "products", "hash": format!("{id}-products"), "routingURL": "http://products" }
+                ]
+            }
+        })
+    }
+
+    fn history_json(launches: Vec<Value>) -> Value {
+        json!({ "data": { "graph": { "variant": { "launchSummaries": launches } } } })
+    }
+
+    #[test]
+    fn launches_carry_their_subgraphs() {
+        let data = serde_json::from_value(
+            history_json(vec![launch_json("l-2", "2026-01-02T00:00:00Z")])["data"].clone(),
+        )
+        .unwrap();
+        let launches = build_launches(data, &graph_ref()).unwrap();
+        assert_eq!(launches.len(), 1);
+        assert_eq!(launches[0].status, "LAUNCH_COMPLETED");
+        assert_eq!(
+            launches[0].subgraph_changes,
+            vec![SubgraphChange {
+                name: "products".to_string(),
+                change: "MODIFICATION".to_string(),
+            }]
+        );
+        assert_eq!(launches[0].subgraphs[0].schema_hash, "l-2-products");
+    }
+
+    #[test]
+    fn missing_variant_is_not_found() {
+        let data = serde_json::from_value(json!({ "graph": { "variant": null } })).unwrap();
+        assert!(matches!(
+            build_launches(data, &graph_ref()),
+            Err(RoverClientError::GraphNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn failed_launches_have_no_publication() {
+        let launch = LaunchSummary {
+            launch_id: "l-1".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            completed_at: None,
+            status: "LAUNCH_FAILED".to_string(),
+            subgraph_changes: Vec::new(),
+            subgraphs: Vec::new(),
+        };
+        let data = serde_json::from_value(
+            json!({ "graph": { "variant": { "launch": { "publication": null } } } }),
+        )
+        .unwrap();
+        let publish = build_publish(data, launch);
+        assert_eq!(publish.published_at, None);
+        assert_eq!(publish.changes, None);
+    }
+
+    #[tokio::test]
+    async fn run_adds_change_counts_to_each_launch() {
+        let server = MockServer::start_async().await;
+        let history = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("GraphHistoryQuery")
+                .body_includes("\"limit\":1");
+            then.status(200).json_body(history_json(vec![launch_json(
+                "l-2",
+                "2026-01-02T00:00:00Z",
+            )]));
+        });
+        let launch = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("GraphHistoryLaunchQuery")
+                .body_includes("\"launchId\":\"l-2\"");
+            then.status(200)
+                .json_body(json!({ "data": { "graph": { "variant": { "launch": {
+                "publication": {
+                    "publishedAt": "2026-01-02T00:00:05Z",
+                    "schema": { "hash": "abc" },
+                    "diffToPrevious": { "changeSummary": { "total": {
+                        "additions": 3, "removals": 1, "edits": 0, "deprecations": 1
+                    } } }
+                }
+            } } } } }));
+        });
+
+        let response = run(
+            GraphHistoryInput {
+                graph_ref: graph_ref(),
+                limit: 1,
+            },
+            &mock_client(&server),
+        )
+        .await
+        .unwrap();
+
+        history.assert();
+        launch.assert();
+        assert_eq!(response.publishes.len(), 1);
+        assert_eq!(response.publishes[0].schema_hash.as_deref(), Some("abc"));
+        assert_eq!(
+            response.publishes[0].changes,
+            Some(ChangeCounts {
+                additions: 3,
+                removals: 1,
+                edits: 0,
+                deprecations: 1,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn run_keeps_launches_newest_first() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.method(POST).body_includes("GraphHistoryQuery");
+            then.status(200).json_body(history_json(vec![
+                launch_json("l-3", "2026-01-03T00:00:00Z"),
+                launch_json("l-2", "2026-01-02T00:00:00Z"),
+                launch_json("l-1", "2026-01-01T00:00:00Z"),
+            ]));
+        });
+        let launch = server.mock(|when, then| {
+            when.method(POST).body_includes("GraphHistoryLaunchQuery");
+            then.status(200).json_body(
+                json!({ "data": { "graph": { "variant": { "launch": { "publication": null } } } } }),
+            );
+        });
+
+        let response = run(
+            GraphHistoryInput {
+                graph_ref: graph_ref(),
+                limit: 3,
+            },
+            &mock_client(&server),
+        )
+        .await
+        .unwrap();
+
+        launch.assert_calls(3);
+        let launch_ids: Vec<&str> = response
+            .publishes
+            .iter()
+            .map(|publish| publish.launch.launch_id.as_str())
+            .collect();
+        assert_eq!(launch_ids, vec!["l-3", "l-2", "l-1"]);
+    }
+
+    #[tokio::test]
+    async fn resolve_launch_finds_the_launch_live_at_a_time() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.method(POST).body_includes("GraphHistoryQuery");
+            then.status(200).json_body(history_json(vec![
+                launch_json("l-3", "2026-01-03T00:00:00Z"),
+                launch_json("l-2", "2026-01-02T00:00:00Z"),
+                launch_json("l-1", "2026-01-01T00:00:00Z"),
+            ]));
+        });
+        let client = mock_client(&server);
+
+        let point = "2026-01-02T12:00:00+00:00".parse().unwrap();
+        let launch = resolve_launch(&graph_ref(), &point, &client).await.unwrap();
+        assert_eq!(launch.launch_id, "l-2");
+
+        let point = HistoryPoint::Launch("l-1".to_string());
+        let launch = resolve_launch(&graph_ref(), &point, &client).await.unwrap();
+        assert_eq!(launch.launch_id, "l-1");
+
+        let point = "2025-12-31T00:00:00Z".parse().unwrap();
+        assert!(resolve_launch(&graph_ref(), &point, &client).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn resolve_launch_skips_launches_that_never_went_live() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.method(POST).body_includes("GraphHistoryQuery");
+            then.status(200).json_body(history_json(vec![
+                launch_json("l-4", "2026-01-04T00:00:00Z"),
+                launch_json_with_status("l-3", "2026-01-03T00:00:00Z", "LAUNCH_INITIATED"),
+                launch_json_with_status("l-2", "2026-01-02T00:00:00Z", "LAUNCH_FAILED"),
+                launch_json("l-1", "2026-01-01T00:00:00Z"),
+            ]));
+        });
+        let client = mock_client(&server);
+
+        let point = "2026-01-03T12:00:00Z".parse().unwrap();
+        let launch = resolve_launch(&graph_ref(), &point, &client).await.unwrap();
+        assert_eq!(launch.launch_id, "l-1");
+
+        let point = "2026-01-04T12:00:00Z".parse().unwrap();
+        let launch = resolve_launch(&graph_ref(), &point, &client).await.unwrap();
+        assert_eq!(launch.launch_id, "l-4");
+
+        // a launch asked for by ID is returned whatever its status
+        let point = HistoryPoint::Launch("l-2".to_string());
+        let launch = resolve_launch(&graph_ref(), &point, &client).await.unwrap();
+        assert_eq!(launch.launch_id, "l-2");
+    }
+}
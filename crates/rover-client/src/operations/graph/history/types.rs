@@ -0,0 +1,160 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, FixedOffset};
+use rover_studio::types::GraphRef;
+use serde::Serialize;
+
+use crate::operations::graph::history::runner::graph_history_query;
+
+type QueryVariables = graph_history_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GraphHistoryInput {
+    pub graph_ref: GraphRef,
+    /// The most publishes to list, newest first
+    pub limit: i64,
+}
+
+/// A page of a variant's launches, newest first
+pub(crate) fn history_variables(graph_ref: &GraphRef, limit: i64, offset: i64) -> QueryVariables {
+    QueryVariables {
+        graph_id: graph_ref.graph_id().clone(),
+        variant: graph_ref.variant().clone(),
+        limit,
+        offset,
+    }
+}
+
+/// A point in a variant's history: a launch ID, or the latest launch at or
+/// before an RFC 3339 timestamp
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum HistoryPoint {
+    Launch(String),
+    Time(DateTime<FixedOffset>),
+}
+
+impl FromStr for HistoryPoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err("expected a launch ID or an RFC 3339 timestamp".to_string());
+        }
+        Ok(match DateTime::parse_from_rfc3339(s) {
+            Ok(time) => HistoryPoint::Time(time),
+            Err(_) => HistoryPoint::Launch(s.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for HistoryPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryPoint::Launch(id) => write!(f, "launch {id}"),
+            HistoryPoint::Time(time) => write!(f, "{}", time.to_rfc3339()),
+        }
+    }
+}
+
+/// A launch of a variant, started by a publish
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct LaunchSummary {
+    pub launch_id: String,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+    /// e.g. `LAUNCH_COMPLETED` or `LAUNCH_FAILED`
+    pub status: String,
+    /// The subgraphs this launch added, changed or removed
+    pub subgraph_changes: Vec<SubgraphChange>,
+    /// The subgraphs the launch composed
+    #[serde(skip)]
+    pub subgraphs: Vec<LaunchSubgraph>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct SubgraphChange {
+    pub name: String,
+    /// `ADDITION`, `MODIFICATION` or `DELETION`
+    pub change: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct LaunchSubgraph {
+    pub name: String,
+    pub schema_hash: String,
+    pub routing_url: String,
+}
+
+/// How many schema changes a publish made, compared to the one before it
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize)]
+pub struct ChangeCounts {
+    pub additions: i64,
+    pub removals: i64,
+    pub edits: i64,
+    pub deprecations: i64,
+}
+
+impl fmt::Display for ChangeCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "+{} -{} ~{}", self.additions, self.removals, self.edits)?;
+        if self.deprecations > 0 {
+            write!(f, " ({} deprecated)", self.deprecations)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct GraphPublish {
+    #[serde(flatten)]
+    pub launch: LaunchSummary,
+    /// When the launch's schema was published. Failed launches don't publish one.
+    pub published_at: Option<String>,
+    pub schema_hash: Option<String>,
+    /// Changes to the API schema, if the launch published one
+    pub changes: Option<ChangeCounts>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct GraphHistoryResponse {
+    pub graph_ref: GraphRef,
+    pub publishes: Vec<GraphPublish>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_are_points_in_time() {
+        assert!(matches!(
+            "2026-01-02T03:04:05Z".parse::<HistoryPoint>(),
+            Ok(HistoryPoint::Time(_))
+        ));
+    }
+
+    #[test]
+    fn anything_else_is_a_launch_id() {
+        assert_eq!(
+            "4f1b2c3d".parse::<HistoryPoint>(),
+            Ok(HistoryPoint::Launch("4f1b2c3d".to_string()))
+        );
+    }
+
+    #[test]
+    fn change_counts_only_mention_deprecations_when_there_are_some() {
+        let counts = ChangeCounts {
+            additions: 2,
+            removals: 1,
+            edits: 0,
+            deprecations: 0,
+        };
+        assert_eq!(counts.to_string(), "+2 -1 ~0");
+        let counts = ChangeCounts {
+            deprecations: 3,
+            ..counts
+        };
+        assert_eq!(counts.to_string(), "+2 -1 ~0 (3 deprecated)");
+    }
+}
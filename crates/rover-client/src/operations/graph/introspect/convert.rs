@@ -0,0 +1,408 @@
+//! Offline conversion between SDL and introspection JSON.
+//!
+//! Unlike the live introspection query, which has to work against older
+//! servers, conversion asks for everything a schema can carry: the schema
+//! description, `specifiedByURL`, repeatable directives and `@oneOf`.
+
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
+
+use apollo_compiler::{
+    ast::{
+        Argument, Definition, Directive, DirectiveDefinition, DirectiveLocation, Document,
+        OperationType, SchemaDefinition, Value as AstValue,
+    },
+    schema::ExtendedType,
+    Name, Node,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::{
+    introspection_json::execute_introspection,
+    schema::{definition_sort_key, Schema as IntrospectionSchema},
+    service::{graph_introspect_query, patch_input_values},
+};
+use crate::RoverClientError;
+
+const CONVERT_QUERY: &str = include_str!("convert_query.graphql");
+const CONVERT_OPERATION: &str = "SchemaConvertQuery";
+const ONE_OF: &str = "oneOf";
+
+/// Convert SDL to introspection JSON (`{ "__schema": ... }`) without a running server.
+///
+/// Input object types marked with `@oneOf` report `isOneOf: true`. The
+/// directive doesn't need to be declared in the SDL.
+pub fn sdl_to_introspection(sdl: &str) -> Result<Value, RoverClientError> {
+    let mut document = Document::parse(sdl, "schema.graphql").map_err(|err| {
+        RoverClientError::IntrospectionError {
+            msg: err.errors.to_string(),
+        }
+    })?;
+    let uses_one_of = document
+        .definitions
+        .iter()
+        .any(|definition| match definition {
+            Definition::InputObjectTypeDefinition(input) => input.directives.has(ONE_OF),
+            Definition::InputObjectTypeExtension(input) => input.directives.has(ONE_OF),
+            _ => false,
+        });
+    if uses_one_of && !defines_directive(&document, ONE_OF) {
+        document.definitions.push(one_of_definition());
+    }
+
+    let schema =
+        document
+            .to_schema_validate()
+            .map_err(|err| RoverClientError::IntrospectionError {
+                msg: err.errors.to_string(),
+            })?;
+    let one_of_types: HashSet<&str> = schema
+        .types
+        .iter()
+        .filter_map(|(name, ty)| match ty {
+            ExtendedType::InputObject(input) if input.directives.has(ONE_OF) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    let mut introspection = execute_introspection(&schema, CONVERT_QUERY, CONVERT_OPERATION)?;
+    // apollo-compiler doesn't know about `isOneOf` yet, so it's filled in here
+    // the way graphql-js reports it: a boolean for input objects, null otherwise.
+    if let Some(types) = introspection
+        .pointer_mut("/__schema/types")
+        .and_then(Value::as_array_mut)
+    {
+        for ty in types {
+            let is_one_of = match (ty["kind"].as_str(), ty["name"].as_str()) {
+                (Some("INPUT_OBJECT"), Some(name)) => Value::Bool(one_of_types.contains(name)),
+                _ => Value::Null,
+            };
+            if let Some(ty) = ty.as_object_mut() {
+                ty.insert("isOneOf".to_string(), is_one_of);
+            }
+        }
+    }
+    Ok(introspection)
+}
+
+/// Convert introspection JSON to SDL without a running server.
+///
+/// Accepts `{ "__schema": ... }` as well as a full response wrapped in `data`.
+/// The resulting SDL is validated before it's returned.
+pub fn introspection_to_sdl(introspection: &Value) -> Result<String, RoverClientError> {
+    let mut schema_json = introspection
+        .get("data")
+        .unwrap_or(introspection)
+        .get("__schema")
+        .cloned()
+        .ok_or_else(|| RoverClientError::IntrospectionError {
+            msg:
+                "expected introspection JSON with a `__schema` object, optionally wrapped in `data`"
+                    .to_string(),
+        })?;
+    patch_input_values(&mut schema_json);
+
+    let extras: SchemaExtras = serde_json::from_value(schema_json.clone()).map_err(|err| {
+        RoverClientError::IntrospectionError {
+            msg: format!("failed to deserialize introspection JSON: {err}"),
+        }
+    })?;
+    let response: graph_introspect_query::ResponseData =
+        serde_json::from_value(json!({ "__schema": schema_json })).map_err(|err| {
+            RoverClientError::IntrospectionError {
+                msg: format!("failed to deserialize introspection JSON: {err}"),
+            }
+        })?;
+    let mut document = IntrospectionSchema::try_from(response)
+        .map_err(|msg| RoverClientError::IntrospectionError {
+            msg: msg.to_string(),
+        })?
+        .into_document();
+    extras.apply(&mut document);
+
+    let sdl = document.to_string();
+    apollo_compiler::Schema::parse_and_validate(&sdl, "schema.graphql").map_err(|err| {
+        RoverClientError::IntrospectionError {
+            msg: format!(
+                "SDL from introspection JSON failed validation: {}",
+                err.errors
+            ),
+        }
+    })?;
+    Ok(sdl)
+}
+
+/// The parts of a `__schema` object that the live introspection query doesn't
+/// ask for, and so that `Schema` doesn't encode.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaExtras {
+    #[serde(default)]
+    description: Option<String>,
+    query_type: Option<NamedType>,
+    #[serde(default)]
+    types: Vec<TypeExtras>,
+    #[serde(default)]
+    directives: Vec<DirectiveExtras>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedType {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeExtras {
+    name: Option<String>,
+    #[serde(default, rename = "specifiedByURL")]
+    specified_by_url: Option<String>,
+    #[serde(default)]
+    is_one_of: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DirectiveExtras {
+    name: String,
+    #[serde(default)]
+    is_repeatable: Option<bool>,
+}
+
+impl SchemaExtras {
+    fn apply(self, document: &mut Document) {
+        let specified_by: HashMap<&str, &str> = self
+            .types
+            .iter()
+            .filter_map(|ty| Some((ty.name.as_deref()?, ty.specified_by_url.as_deref()?)))
+            .collect();
+        let one_of: HashSet<&str> = self
+            .types
+            .iter()
+            .filter(|ty| ty.is_one_of == Some(true))
+            .filter_map(|ty| ty.name.as_deref())
+            .collect();
+        let repeatable: HashSet<&str> = self
+            .directives
+            .iter()
+            .filter(|directive| directive.is_repeatable == Some(true))
+            .map(|directive| directive.name.as_str())
+            .collect();
+
+        let mut has_schema_definition = false;
+        for definition in &mut document.definitions {
+            match definition {
+                Definition::SchemaDefinition(schema) => {
+                    has_schema_definition = true;
+                    schema.make_mut().description = self.description.as_deref().map(Node::new_str);
+                }
+                Definition::ScalarTypeDefinition(scalar) => {
+                    if let Some(url) = specified_by.get(scalar.name.as_str()) {
+                        scalar.make_mut().directives.0.push(Node::new(Directive {
+                            name: Name::new_unchecked("specifiedBy"),
+                            arguments: vec![Node::new(Argument {
+                                name: Name::new_unchecked("url"),
+                                value: Node::new(AstValue::String(url.to_string())),
+                            })],
+                        }));
+                    }
+                }
+                Definition::InputObjectTypeDefinition(input)
+                    if one_of.contains(input.name.as_str()) =>
+                {
+                    input.make_mut().directives.0.push(Node::new(Directive {
+                        name: Name::new_unchecked(ONE_OF),
+                        arguments: Vec::new(),
+                    }));
+                }
+                Definition::DirectiveDefinition(directive)
+                    if repeatable.contains(directive.name.as_str()) =>
+                {
+                    directive.make_mut().repeatable = true;
+                }
+                _ => {}
+            }
+        }
+
+        // A schema description needs a schema definition to hang off, even
+        // when the root types have their default names.
+        if let (Some(description), false, Some(query)) = (
+            self.description.as_deref(),
+            has_schema_definition,
+            self.query_type.and_then(|ty| ty.name),
+        ) {
+            document
+                .definitions
+                .push(Definition::SchemaDefinition(Node::new(SchemaDefinition {
+                    description: Some(Node::new_str(description)),
+                    directives: Default::default(),
+                    root_operations: vec![Node::new((
+                        OperationType::Query,
+                        Name::new_unchecked(&query),
+                    ))],
+                })));
+        }
+        // `@specifiedBy` is built in, and only servers that support it report
+        // it, so it's left out like graphql-js's `printSchema` does. That keeps
+        // converting the SDL back and forth stable.
+        document.definitions.retain(|definition| {
+            !matches!(definition, Definition::DirectiveDefinition(directive) if directive.name == "specifiedBy")
+        });
+        if !one_of.is_empty() && !defines_directive(document, ONE_OF) {
+            document.definitions.push(one_of_definition());
+        }
+        document.definitions.sort_by_key(definition_sort_key);
+    }
+}
+
+fn defines_directive(document: &Document, name: &str) -> bool {
+    document.definitions.iter().any(|definition| {
+        matches!(definition, Definition::DirectiveDefinition(directive) if directive.name == name)
+    })
+}
+
+fn one_of_definition() -> Definition {
+    Definition::DirectiveDefinition(Node::new(DirectiveDefinition {
+        description: None,
+        name: Name::new_unchecked(ONE_OF),
+        arguments: Vec::new(),
+        repeatable: false,
+        locations: vec![DirectiveLocation::InputObject],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::graph::introspect::assert_structural_parity;
+
+    const FEATURES_SDL: &str = r#"
+        "The example schema"
+        schema {
+          query: Query
+        }
+
+        type Query {
+          "Look up a user"
+          user(id: ID!, legacyId: Int @deprecated(reason: "Use `id`")): User
+          search(by: UserBy!): [User!]!
+        }
+
+        type User {
+          id: ID!
+          name: String @deprecated(reason: "Use \"displayName\"")
+          displayName: String
+          joined: DateTime
+          role: Role
+        }
+
+        enum Role {
+          ADMIN
+          GUEST @deprecated
+        }
+
+        scalar DateTime @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
+
+        input UserBy @oneOf {
+          id: ID
+          email: String
+        }
+
+        input UserFilter {
+          name: String
+          nickname: String @deprecated(reason: "Use `name`")
+        }
+
+        directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT
+    "#;
+
+    fn find<'a>(items: &'a Value, name: &str) -> &'a Value {
+        items
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|item| item["name"] == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn sdl_to_introspection_keeps_everything_a_schema_carries() {
+        let introspection = sdl_to_introspection(FEATURES_SDL).unwrap();
+        let schema = &introspection["__schema"];
+        let types = &schema["types"];
+
+        assert_eq!(schema["description"], "The example schema");
+        assert_eq!(
+            find(types, "DateTime")["specifiedByURL"],
+            "https://tools.ietf.org/html/rfc3339"
+        );
+        assert_eq!(find(types, "UserBy")["isOneOf"], true);
+        assert_eq!(find(types, "UserFilter")["isOneOf"], false);
+        assert_eq!(find(types, "User")["isOneOf"], Value::Null);
+        assert_eq!(find(&schema["directives"], "tag")["isRepeatable"], true);
+
+        let user = find(&find(types, "Query")["fields"], "user");
+        assert_eq!(user["description"], "Look up a user");
+        assert_eq!(
+            find(&user["args"], "legacyId")["deprecationReason"],
+            "Use `id`"
+        );
+        let nickname = find(&find(types, "UserFilter")["inputFields"], "nickname");
+        assert_eq!(nickname["isDeprecated"], true);
+    }
+
+    #[test]
+    fn introspection_to_sdl_keeps_everything_a_schema_carries() {
+        let sdl = introspection_to_sdl(&sdl_to_introspection(FEATURES_SDL).unwrap()).unwrap();
+
+        for expected in [
+            "\"\"\"The example schema\"\"\"\nschema {",
+            "scalar DateTime @specifiedBy(url: \"https://tools.ietf.org/html/rfc3339\")",
+            "input UserBy @oneOf {",
+            "directive @oneOf on INPUT_OBJECT",
+            "directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT",
+            "legacyId: Int @deprecated(reason: \"Use `id`\")",
+            "nickname: String @deprecated(reason: \"Use `name`\")",
+            "GUEST @deprecated",
+        ] {
+            assert!(sdl.contains(expected), "missing {expected:?} in:\n{sdl}");
+        }
+
+        // converting the SDL again changes nothing
+        let again = introspection_to_sdl(&sdl_to_introspection(&sdl).unwrap()).unwrap();
+        assert_eq!(again, sdl);
+    }
+
+    #[test]
+    fn introspection_to_sdl_accepts_responses_wrapped_in_data() {
+        let response: Value = serde_json::from_str(include_str!("fixtures/swapi.json")).unwrap();
+        let sdl = introspection_to_sdl(&response).unwrap();
+
+        assert!(sdl.contains("schema {\n  query: Root\n}"));
+        let again = introspection_to_sdl(&sdl_to_introspection(&sdl).unwrap()).unwrap();
+        assert_eq!(again, sdl);
+    }
+
+    #[test]
+    fn swapi_reference_introspection_round_trips() {
+        let reference: Value =
+            serde_json::from_str(include_str!("fixtures/swapi-introspection.json")).unwrap();
+
+        let sdl = introspection_to_sdl(&reference).unwrap();
+        let introspection = sdl_to_introspection(&sdl).unwrap();
+
+        assert_structural_parity(&introspection, &reference);
+    }
+
+    #[test]
+    fn introspection_to_sdl_errs_without_a_schema() {
+        let result = introspection_to_sdl(&json!({ "data": { "user": null } }));
+        assert!(matches!(
+            result,
+            Err(RoverClientError::IntrospectionError { .. })
+        ));
+    }
+}
@@ -2,7 +2,8 @@
 //! matching the legacy `apollo schema:download` / graphql-js `introspectionFromSchema` shape.
 
 use apollo_compiler::{
-    introspection, request::coerce_variable_values, response::JsonMap, ExecutableDocument, Schema,
+    introspection, request::coerce_variable_values, response::JsonMap, validation::Valid,
+    ExecutableDocument, Schema,
 };
 use serde_json::Value;
 
@@ -22,31 +23,37 @@ pub fn sdl_to_introspection_json(sdl: &str) -> Result<Value, RoverClientError> {
         }
     })?;
 
-    let document = ExecutableDocument::parse_and_validate(
-        &schema,
-        INTROSPECTION_QUERY,
-        "introspection_query.graphql",
-    )
-    .map_err(|err| RoverClientError::IntrospectionError {
-        msg: err.errors.to_string(),
-    })?;
+    execute_introspection(&schema, INTROSPECTION_QUERY, GRAPH_INTROSPECT_OPERATION)
+}
+
+/// Run an introspection query against `schema`, returning its `data` as JSON.
+pub(super) fn execute_introspection(
+    schema: &Valid<Schema>,
+    query: &str,
+    operation_name: &str,
+) -> Result<Value, RoverClientError> {
+    let document =
+        ExecutableDocument::parse_and_validate(schema, query, "introspection_query.graphql")
+            .map_err(|err| RoverClientError::IntrospectionError {
+                msg: err.errors.to_string(),
+            })?;
 
     let operation = document
         .operations
-        .get(Some(GRAPH_INTROSPECT_OPERATION))
+        .get(Some(operation_name))
         .map_err(|err| RoverClientError::IntrospectionError {
             msg: err.message().to_string(),
         })?;
 
     let variable_values =
-        coerce_variable_values(&schema, operation, &JsonMap::default()).map_err(|err| {
+        coerce_variable_values(schema, operation, &JsonMap::default()).map_err(|err| {
             RoverClientError::IntrospectionError {
                 msg: err.message().to_string(),
             }
         })?;
 
     let response = introspection::partial_execute(
-        &schema,
+        schema,
         &schema.implementers_map(),
         &document,
         operation,
@@ -1,3 +1,4 @@
+mod convert;
 mod introspection_json;
 #[cfg(any(test, feature = "testing"))]
 mod introspection_parity;
@@ -6,6 +7,7 @@ mod schema;
 mod service;
 mod types;
 
+pub use convert::{introspection_to_sdl, sdl_to_introspection};
 #[cfg(any(test, feature = "testing"))]
 pub use introspection_json::introspection_json_to_validated_sdl;
 pub use introspection_json::sdl_to_introspection_json;
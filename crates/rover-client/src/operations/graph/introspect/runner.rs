@@ -74,7 +74,9 @@ pub async fn run(
 
 fn retry_layer(input: &GraphIntrospectInput) -> Option<RetryLayer<RetryPolicy>> {
     if input.should_retry {
-        Some(RetryLayer::new(RetryPolicy::new(input.retry_period)))
+        Some(RetryLayer::new(RetryPolicy::from_config(
+            input.retry.clone(),
+        )))
     } else {
         None
     }
@@ -132,6 +134,7 @@ mod tests {
     use bytes::Bytes;
     use graphql_client::Error as GraphQLError;
     use httpmock::prelude::*;
+    use rover_http::retry::RetryConfig;
     use serde_json::json;
 
     use super::*;
@@ -216,7 +219,7 @@ mod tests {
             headers: Default::default(),
             endpoint,
             should_retry: false,
-            retry_period: Duration::from_secs(1),
+            retry: RetryConfig::new(Duration::from_secs(1)),
             use_legacy_introspection_query: false,
         }
     }
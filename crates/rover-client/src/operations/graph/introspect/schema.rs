@@ -66,6 +66,11 @@ pub struct Schema {
 impl Schema {
     /// Encode Schema into an SDL.
     pub fn encode(self) -> String {
+        self.into_document().to_string()
+    }
+
+    /// Build the SDL document for this Schema.
+    pub(super) fn into_document(self) -> Document {
         let mut document = Document::new();
 
         // When we have a defined mutation and subscription, we record
@@ -142,7 +147,7 @@ impl Schema {
         // order the introspection server reports types and directives in.
         document.definitions.sort_by_key(definition_sort_key);
 
-        document.to_string()
+        document
     }
 
     fn encode_directive(directive: SchemaDirective, document: &mut Document) {
@@ -431,7 +436,7 @@ impl OfType for graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfTypeOfTypeOfT
 }
 
 /// Stable sort key matching apollo-encoder's by-category emission order
-const fn definition_sort_key(def: &Definition) -> u8 {
+pub(super) const fn definition_sort_key(def: &Definition) -> u8 {
     match def {
         Definition::SchemaDefinition(_) => 0,
         Definition::ScalarTypeDefinition(_) => 1,
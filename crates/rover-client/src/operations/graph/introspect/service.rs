@@ -173,10 +173,14 @@ fn legacy_response_to_query_response(
 }
 
 fn patch_legacy_input_values(value: &mut Value) {
-    let Some(schema) = value.get_mut("schema") else {
-        return;
-    };
+    if let Some(schema) = value.get_mut("schema") {
+        patch_input_values(schema);
+    }
+}
 
+/// Fill in the `__InputValue` deprecation fields that older servers and tools
+/// leave out of a `__schema` object.
+pub(super) fn patch_input_values(schema: &mut Value) {
     for_each_in_array(schema, "types", |typ| {
         for_each_in_array(typ, "fields", |field| {
             for_each_in_array(field, "args", inject_input_value_defaults);
@@ -1,4 +1,6 @@
-use std::{collections::HashMap, time::Duration};
+use std::collections::HashMap;
+
+use rover_http::retry::RetryConfig;
 
 #[cfg(test)]
 pub(crate) type QueryResponseData =
@@ -9,7 +11,7 @@ pub struct GraphIntrospectInput {
     pub headers: HashMap<String, String>,
     pub endpoint: url::Url,
     pub should_retry: bool,
-    pub retry_period: Duration,
+    pub retry: RetryConfig,
     /// Use a pre-October-2021 introspection query that omits
     /// `includeDeprecated` on `args`/`inputFields` and
     /// `isDeprecated`/`deprecationReason` on `__InputValue`, for
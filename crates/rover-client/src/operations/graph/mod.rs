@@ -1,6 +1,12 @@
 /// "graph fetch" command execution
 pub mod fetch;
 
+/// "graph fetch --at" command execution
+pub mod fetch_at;
+
+/// "graph history" command execution
+pub mod history;
+
 /// "graph publish" command execution
 pub mod publish;
 
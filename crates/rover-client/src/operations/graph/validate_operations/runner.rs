@@ -14,7 +14,7 @@ pub async fn run(
     let mut service = ValidateOperations::new(
         client
             .studio_graphql_service()
-            .map_err(|err| RoverClientError::ServiceReady(Box::new(err)))?,
+            .map_err(RoverClientError::from)?,
     );
     let service = service.ready().await?;
     service.call(ValidateOperationsRequest::new(input)).await
@@ -18,7 +18,7 @@ pub async fn run(
     let page_svc = ListTagsByDigestPage::new(
         client
             .studio_graphql_service()
-            .map_err(|err| RoverClientError::ServiceReady(Box::new(err)))?,
+            .map_err(RoverClientError::from)?,
     );
     let mut svc = ListTagsByDigest::new(page_svc);
     let svc = svc.ready().await?;
@@ -17,7 +17,7 @@ pub async fn run(
     let page_svc = ListTagsByGraphPage::new(
         client
             .studio_graphql_service()
-            .map_err(|err| RoverClientError::ServiceReady(Box::new(err)))?,
+            .map_err(RoverClientError::from)?,
     );
     let mut svc = ListTagsByGraph::new(page_svc);
     let svc = svc.ready().await?;
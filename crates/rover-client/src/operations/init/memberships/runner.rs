@@ -12,7 +12,7 @@ pub async fn run(client: &StudioClient) -> Result<InitMembershipsResponse, Rover
     let mut service = Memberships::new(
         client
             .studio_graphql_service()
-            .map_err(|err| RoverClientError::ServiceReady(Box::new(err)))?,
+            .map_err(RoverClientError::from)?,
     );
     let service = service.ready().await?;
     let identity = service
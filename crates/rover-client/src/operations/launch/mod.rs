@@ -0,0 +1,2 @@
+/// "launch status"
+pub mod status;
@@ -0,0 +1,5 @@
+mod runner;
+mod types;
+
+pub use runner::{run, wait};
+pub use types::{DownstreamLaunch, LaunchState, LaunchStatusInput, LaunchStatusResponse};
@@ -0,0 +1,362 @@
+use std::time::{Duration, Instant};
+
+use graphql_client::*;
+use rover_studio::types::GraphRef;
+
+use self::launch_status_query::{
+    LaunchStatus, LaunchStatusQueryGraphVariantLaunchBuildResult as BuildResult,
+    LaunchStatusQueryGraphVariantLaunchLatestSequenceStep as SequenceStep,
+};
+use super::types::*;
+use crate::{blocking::StudioClient, RoverClientError};
+
+type Timestamp = String;
+
+/// How long to wait between polls of a launch's status
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/launch/status/status_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. launch_status_query
+pub(crate) struct LaunchStatusQuery;
+
+/// Fetches where a launch, and any contract variant launches it started, are at
+pub async fn run(
+    input: LaunchStatusInput,
+    client: &StudioClient,
+) -> Result<LaunchStatusResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let launch_id = input.launch_id.clone();
+    let data = client.post::<LaunchStatusQuery>(input.into()).await?;
+    build_response(data, graph_ref, launch_id)
+}
+
+/// Polls a launch until it and any contract variant launches it started have
+/// finished, calling `on_update` with each status fetched along the way.
+///
+/// Like the check workflow poll, transient errors are logged and retried
+/// until `timeout` runs out, and any other error surfaces immediately.
+pub async fn wait(
+    input: LaunchStatusInput,
+    timeout: Duration,
+    on_update: impl FnMut(&LaunchStatusResponse),
+    client: &StudioClient,
+) -> Result<LaunchStatusResponse, RoverClientError> {
+    poll_launch(
+        &input.graph_ref,
+        &input.launch_id,
+        timeout,
+        POLL_INTERVAL,
+        async || run(input.clone(), client).await,
+        on_update,
+    )
+    .await
+}
+
+async fn poll_launch(
+    graph_ref: &GraphRef,
+    launch_id: &str,
+    timeout: Duration,
+    interval: Duration,
+    mut poll_status: impl AsyncFnMut() -> Result<LaunchStatusResponse, RoverClientError>,
+    mut on_update: impl FnMut(&LaunchStatusResponse),
+) -> Result<LaunchStatusResponse, RoverClientError> {
+    let now = Instant::now();
+    loop {
+        match poll_status().await {
+            Ok(status) => {
+                on_update(&status);
+                if status.is_finished() {
+                    return Ok(status);
+                }
+            }
+            Err(e) if e.is_transient() => {
+                eprintln!("error while checking status of launch: {e}\nretrying...");
+            }
+            Err(e) => return Err(e),
+        }
+        if now.elapsed() > timeout {
+            return Err(RoverClientError::LaunchTimeoutError {
+                graph_ref: graph_ref.clone(),
+                launch_id: launch_id.to_string(),
+            });
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+fn launch_state(status: LaunchStatus, superseded: bool) -> LaunchState {
+    match status {
+        LaunchStatus::LAUNCH_COMPLETED => LaunchState::Completed,
+        LaunchStatus::LAUNCH_FAILED => LaunchState::Failed,
+        // a superseded launch stays initiated forever
+        _ if superseded => LaunchState::Superseded,
+        _ => LaunchState::InProgress,
+    }
+}
+
+fn build_response(
+    data: launch_status_query::ResponseData,
+    graph_ref: GraphRef,
+    launch_id: String,
+) -> Result<LaunchStatusResponse, RoverClientError> {
+    let launch = data
+        .graph
+        .and_then(|graph| graph.variant)
+        .ok_or_else(|| RoverClientError::GraphNotFound {
+            graph_ref: graph_ref.clone(),
+        })?
+        .launch
+        .ok_or_else(|| RoverClientError::AdhocError {
+            msg: format!("Could not find launch '{launch_id}' of {graph_ref}"),
+        })?;
+    let superseded_by = launch.superseded_by.map(|launch| launch.id);
+    Ok(LaunchStatusResponse {
+        graph_ref,
+        launch_id: launch.id,
+        state: launch_state(launch.status, superseded_by.is_some()),
+        step: launch.latest_sequence_step.map(|step| {
+            match step {
+                SequenceStep::LaunchSequenceInitiatedStep => "INITIATED",
+                SequenceStep::LaunchSequenceBuildStep => "BUILD",
+                SequenceStep::LaunchSequencePublishStep => "PUBLISH",
+                SequenceStep::LaunchSequenceCompletedStep => "COMPLETED",
+                SequenceStep::LaunchSequenceSupersededStep => "SUPERSEDED",
+            }
+            .to_string()
+        }),
+        completed_at: launch.completed_at,
+        superseded_by,
+        build_errors: match launch.build.and_then(|build| build.result) {
+            Some(BuildResult::BuildFailure(failure)) => failure
+                .error_messages
+                .into_iter()
+                .map(|error| error.message)
+                .collect(),
+            _ => Vec::new(),
+        },
+        downstream_launches: launch
+            .downstream_launches
+            .into_iter()
+            .map(|downstream| DownstreamLaunch {
+                variant: downstream.graph_variant,
+                launch_id: downstream.id,
+                state: launch_state(downstream.status, downstream.superseded_by.is_some()),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn graph_ref() -> GraphRef {
+        GraphRef::new("mygraph", Some("current")).unwrap()
+    }
+
+    fn launch_json(status: &str, step: &str) -> Value {
+        json!({
+            "id": "l-1",
+            "status": status,
+            "completedAt": null,
+            "supersededBy": null,
+            "latestSequenceStep": { "__typename": step },
+            "build": null,
+            "downstreamLaunches": []
+        })
+    }
+
+    fn parse(launch: Value) -> Result<LaunchStatusResponse, RoverClientError> {
+        let data = serde_json::from_value(json!({ "graph": { "variant": { "launch": launch } } }))
+            .unwrap();
+        build_response(data, graph_ref(), "l-1".to_string())
+    }
+
+    fn status(state: LaunchState) -> LaunchStatusResponse {
+        LaunchStatusResponse {
+            graph_ref: graph_ref(),
+            launch_id: "l-1".to_string(),
+            state,
+            step: None,
+            completed_at: None,
+            superseded_by: None,
+            build_errors: Vec::new(),
+            downstream_launches: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn in_progress_launches_report_their_step() {
+        let response = parse(launch_json("LAUNCH_INITIATED", "LaunchSequenceBuildStep")).unwrap();
+        assert_eq!(response.state, LaunchState::InProgress);
+        assert_eq!(response.step.as_deref(), Some("BUILD"));
+        assert!(!response.is_finished());
+    }
+
+    #[test]
+    fn superseded_launches_are_finished() {
+        let mut launch = launch_json("LAUNCH_INITIATED", "LaunchSequenceSupersededStep");
+        launch["supersededBy"] = json!({ "id": "l-2" });
+        let response = parse(launch).unwrap();
+        assert_eq!(response.state, LaunchState::Superseded);
+        assert_eq!(response.superseded_by.as_deref(), Some("l-2"));
+        assert!(response.is_finished());
+        assert!(!response.has_failed());
+    }
+
+    #[test]
+    fn failed_builds_carry_their_errors() {
+        let mut launch = launch_json("LAUNCH_FAILED", "LaunchSequenceCompletedStep");
+        launch["build"] = json!({ "result": {
+            "__typename": "BuildFailure",
+            "errorMessages": [{ "message": "Field Query.a has conflicting types" }]
+        } });
+        let response = parse(launch).unwrap();
+        assert!(response.has_failed());
+        assert_eq!(
+            response.build_errors,
+            vec!["Field Query.a has conflicting types".to_string()]
+        );
+    }
+
+    #[test]
+    fn contract_variant_launches_have_to_finish_too() {
+        let mut launch = launch_json("LAUNCH_COMPLETED", "LaunchSequenceCompletedStep");
+        launch["downstreamLaunches"] = json!([
+            { "id": "l-c", "graphVariant": "public", "status": "LAUNCH_INITIATED", "supersededBy": null }
+        ]);
+        let response = parse(launch.clone()).unwrap();
+        assert!(!response.is_finished());
+
+        launch["downstreamLaunches"][0]["status"] = json!("LAUNCH_FAILED");
+        let response = parse(launch).unwrap();
+        assert!(response.is_finished());
+        assert!(response.has_failed());
+    }
+
+    #[test]
+    fn missing_launches_are_reported() {
+        assert!(matches!(
+            parse(Value::Null),
+            Err(RoverClientError::AdhocError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn polls_until_the_launch_finishes() {
+        let polls = Cell::new(0);
+        let updates = Cell::new(0);
+        let result = poll_launch(
+            &graph_ref(),
+            "l-1",
+            Duration::from_secs(30),
+            Duration::ZERO,
+            async || {
+                polls.set(polls.get() + 1);
+                Ok(status(if polls.get() < 3 {
+                    LaunchState::InProgress
+                } else {
+                    LaunchState::Completed
+                }))
+            },
+            |_| updates.set(updates.get() + 1),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.state, LaunchState::Completed);
+        assert_eq!(polls.get(), 3);
+        assert_eq!(updates.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn times_out_when_the_launch_doesnt_finish() {
+        let result = poll_launch(
+            &graph_ref(),
+            "l-1",
+            Duration::ZERO,
+            Duration::ZERO,
+            async || Ok(status(LaunchState::InProgress)),
+            |_| {},
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(RoverClientError::LaunchTimeoutError { launch_id, .. }) if launch_id == "l-1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn fails_fast_on_non_transient_errors() {
+        let result = poll_launch(
+            &graph_ref(),
+            "l-1",
+            Duration::from_secs(300),
+            Duration::ZERO,
+            async || {
+                Err(RoverClientError::AdhocError {
+                    msg: "boom".to_string(),
+                })
+            },
+            |_| {},
+        )
+        .await;
+        assert!(matches!(result, Err(RoverClientError::AdhocError { .. })));
+    }
+
+    #[tokio::test]
+    async fn run_queries_the_launch_on_the_variant() {
+        let server = MockServer::start_async().await;
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("LaunchStatusQuery")
+                .body_includes("\"variant\":\"current\"")
+                .body_includes("\"launchId\":\"l-1\"");
+            then.status(200)
+                .json_body(json!({ "data": { "graph": { "variant": {
+                "launch": launch_json("LAUNCH_COMPLETED", "LaunchSequenceCompletedStep")
+            } } } }));
+        });
+        let client = StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        );
+
+        let response = run(
+            LaunchStatusInput {
+                graph_ref: graph_ref(),
+                launch_id: "l-1".to_string(),
+            },
+            &client,
+        )
+        .await
+        .unwrap();
+
+        mock.assert();
+        assert_eq!(response.state, LaunchState::Completed);
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+
+use rover_studio::types::GraphRef;
+use serde::Serialize;
+
+use crate::operations::launch::status::runner::launch_status_query;
+
+type QueryVariables = launch_status_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LaunchStatusInput {
+    pub graph_ref: GraphRef,
+    pub launch_id: String,
+}
+
+impl From<LaunchStatusInput> for QueryVariables {
+    fn from(input: LaunchStatusInput) -> Self {
+        let (graph_id, variant) = input.graph_ref.into_parts();
+        Self {
+            graph_id,
+            variant,
+            launch_id: input.launch_id,
+        }
+    }
+}
+
+/// Where a launch is at
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LaunchState {
+    /// Still building or publishing
+    InProgress,
+    /// Published, so routers will pick it up
+    Completed,
+    Failed,
+    /// A later launch of the same variant replaced it before it finished
+    Superseded,
+}
+
+impl LaunchState {
+    /// Whether the launch won't change any more
+    pub const fn is_finished(&self) -> bool {
+        !matches!(self, LaunchState::InProgress)
+    }
+}
+
+impl fmt::Display for LaunchState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self {
+            LaunchState::InProgress => "in progress",
+            LaunchState::Completed => "completed",
+            LaunchState::Failed => "failed",
+            LaunchState::Superseded => "superseded",
+        };
+        write!(f, "{state}")
+    }
+}
+
+/// A launch of a contract variant, started by the launch of its source variant
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct DownstreamLaunch {
+    pub variant: String,
+    pub launch_id: String,
+    pub state: LaunchState,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct LaunchStatusResponse {
+    pub graph_ref: GraphRef,
+    pub launch_id: String,
+    pub state: LaunchState,
+    /// The step of the launch sequence it's on, e.g. `BUILD` or `PUBLISH`
+    pub step: Option<String>,
+    pub completed_at: Option<String>,
+    pub superseded_by: Option<String>,
+    pub build_errors: Vec<String>,
+    /// Launches of contract variants built from this one
+    pub downstream_launches: Vec<DownstreamLaunch>,
+}
+
+impl LaunchStatusResponse {
+    /// Whether the launch and every contract variant launch it started have finished
+    pub fn is_finished(&self) -> bool {
+        self.state.is_finished()
+            && self
+                .downstream_launches
+                .iter()
+                .all(|launch| launch.state.is_finished())
+    }
+
+    /// Whether the launch, or any contract variant launch it started, failed
+    pub fn has_failed(&self) -> bool {
+        self.state == LaunchState::Failed
+            || self
+                .downstream_launches
+                .iter()
+                .any(|launch| launch.state == LaunchState::Failed)
+    }
+}
@@ -4,6 +4,18 @@ pub mod contract;
 /// all rover-client functionality for the "graph" commands in rover
 pub mod graph;
 
+/// all rover-client functionality for the "launch" commands in rover
+pub mod launch;
+
+/// all rover-client functionality for the "variant" commands in rover
+pub mod variant;
+
+/// all rover-client functionality for the "operation-collection" commands in rover
+pub mod operation_collection;
+
+/// all rover-client functionality for the "proposal" commands in rover
+pub mod proposal;
+
 /// all rover-client functionality for the "readme" commands in rover
 pub mod readme;
 
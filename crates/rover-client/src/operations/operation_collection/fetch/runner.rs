@@ -0,0 +1,140 @@
+use graphql_client::*;
+
+use self::operation_collection_fetch_query::OperationCollectionFetchQueryOperationCollection as CollectionResult;
+use super::types::*;
+use crate::{
+    blocking::StudioClient,
+    operations::operation_collection::{OperationCollectionEntry, OperationHeader},
+    RoverClientError,
+};
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/operation_collection/fetch/fetch_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. operation_collection_fetch_query
+pub(crate) struct OperationCollectionFetchQuery;
+
+/// Fetches an operation collection with the latest revision of each of its operations
+pub async fn run(
+    input: OperationCollectionFetchInput,
+    client: &StudioClient,
+) -> Result<OperationCollectionFetchResponse, RoverClientError> {
+    let collection_id = input.collection_id.clone();
+    let data = client
+        .post::<OperationCollectionFetchQuery>(input.into())
+        .await?;
+    build_response(data, collection_id)
+}
+
+fn build_response(
+    data: operation_collection_fetch_query::ResponseData,
+    collection_id: String,
+) -> Result<OperationCollectionFetchResponse, RoverClientError> {
+    match data.operation_collection {
+        CollectionResult::OperationCollection(collection) => Ok(OperationCollectionFetchResponse {
+            id: collection.id,
+            name: collection.name,
+            description: collection.description,
+            operations: collection
+                .operations
+                .into_iter()
+                .map(|operation| {
+                    let revision = operation.current_operation_revision;
+                    OperationCollectionEntry {
+                        id: operation.id,
+                        name: operation.name,
+                        body: revision.body,
+                        variables: revision.variables,
+                        headers: revision
+                            .headers
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|header| OperationHeader {
+                                name: header.name,
+                                value: header.value,
+                            })
+                            .collect(),
+                    }
+                })
+                .collect(),
+        }),
+        CollectionResult::NotFoundError(_) => {
+            Err(RoverClientError::OperationCollectionNotFound { collection_id })
+        }
+        CollectionResult::PermissionError(error) => {
+            Err(RoverClientError::PermissionError { msg: error.message })
+        }
+        CollectionResult::ValidationError(error) => {
+            Err(RoverClientError::AdhocError { msg: error.message })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn build_response_takes_each_operations_latest_revision() {
+        let data = serde_json::from_value(json!({ "operationCollection": {
+            "__typename": "OperationCollection",
+            "id": "c-1",
+            "name": "Examples",
+            "description": null,
+            "operations": [{
+                "id": "o-1",
+                "name": "Get products",
+                "currentOperationRevision": {
+                    "body": "query GetProducts { products { id } }",
+                    "variables": "{\"first\":10}",
+                    "headers": [{ "name": "x-client", "value": "web" }]
+                }
+            }, {
+                "id": "o-2",
+                "name": "Me",
+                "currentOperationRevision": {
+                    "body": "query Me { me { id } }",
+                    "variables": null,
+                    "headers": null
+                }
+            }]
+        } }))
+        .unwrap();
+
+        let response = build_response(data, "c-1".to_string()).unwrap();
+
+        assert_eq!(response.operations.len(), 2);
+        assert_eq!(
+            response.operations[0].headers,
+            vec![OperationHeader {
+                name: "x-client".to_string(),
+                value: "web".to_string(),
+            }]
+        );
+        assert!(response.operations[1].headers.is_empty());
+    }
+
+    #[test]
+    fn build_response_errs_for_missing_collections() {
+        let data = serde_json::from_value(json!({ "operationCollection": {
+            "__typename": "NotFoundError",
+            "message": "not found"
+        } }))
+        .unwrap();
+
+        assert!(matches!(
+            build_response(data, "c-1".to_string()),
+            Err(RoverClientError::OperationCollectionNotFound { collection_id }) if collection_id == "c-1"
+        ));
+    }
+}
@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+use super::runner::operation_collection_fetch_query;
+use crate::operations::operation_collection::OperationCollectionEntry;
+
+pub(crate) type QueryVariables = operation_collection_fetch_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OperationCollectionFetchInput {
+    pub collection_id: String,
+}
+
+impl From<OperationCollectionFetchInput> for QueryVariables {
+    fn from(input: OperationCollectionFetchInput) -> Self {
+        Self {
+            id: input.collection_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct OperationCollectionFetchResponse {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub operations: Vec<OperationCollectionEntry>,
+}
@@ -0,0 +1,9 @@
+/// "operation-collection pull" command execution
+pub mod fetch;
+
+/// "operation-collection push" command execution
+pub mod push;
+
+mod types;
+
+pub use types::{OperationCollectionEntry, OperationCollectionEntryInput, OperationHeader};
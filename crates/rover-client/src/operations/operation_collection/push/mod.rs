@@ -0,0 +1,7 @@
+mod runner;
+mod types;
+
+pub use runner::run;
+pub use types::{
+    OperationCollectionEntryUpdate, OperationCollectionPushInput, OperationCollectionPushResponse,
+};
@@ -0,0 +1,309 @@
+use graphql_client::*;
+
+use self::{
+    operation_collection_add_mutation::OperationCollectionAddMutationOperationCollectionAddOperations as AddResult,
+    operation_collection_update_mutation::{
+        OperationCollectionUpdateMutationOperationCollectionOperation as EntryResult,
+        OperationCollectionUpdateMutationOperationCollectionOperationOnOperationCollectionEntryMutationUpdateName as UpdateNameResult,
+        OperationCollectionUpdateMutationOperationCollectionOperationOnOperationCollectionEntryMutationUpdateValues as UpdateValuesResult,
+    },
+};
+use super::types::*;
+use crate::{
+    blocking::StudioClient,
+    operations::operation_collection::{OperationCollectionEntryInput, OperationHeader},
+    RoverClientError,
+};
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/operation_collection/push/add_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. operation_collection_add_mutation
+pub(crate) struct OperationCollectionAddMutation;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/operation_collection/push/update_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+pub(crate) struct OperationCollectionUpdateMutation;
+
+/// Adds new operations to a collection, then saves new values for existing ones
+pub async fn run(
+    input: OperationCollectionPushInput,
+    client: &StudioClient,
+) -> Result<OperationCollectionPushResponse, RoverClientError> {
+    let collection_id = input.collection_id;
+    let mut added_ids = Vec::new();
+    if !input.added.is_empty() {
+        let data = client
+            .post::<OperationCollectionAddMutation>(operation_collection_add_mutation::Variables {
+                id: collection_id.clone(),
+                operations: input
+                    .added
+                    .into_iter()
+                    .map(
+                        |entry| operation_collection_add_mutation::AddOperationInput {
+                            name: entry.name.clone(),
+                            document: add_state(entry),
+                        },
+                    )
+                    .collect(),
+            })
+            .await?;
+        let result = data
+            .operation_collection
+            .ok_or_else(|| RoverClientError::OperationCollectionNotFound {
+                collection_id: collection_id.clone(),
+            })?
+            .add_operations
+            .ok_or_else(|| RoverClientError::MalformedResponse {
+                null_field: "operationCollection.addOperations".to_string(),
+            })?;
+        match result {
+            AddResult::AddOperationCollectionEntriesSuccess(success) => {
+                added_ids = success
+                    .operation_collection_entries
+                    .into_iter()
+                    .map(|entry| entry.id)
+                    .collect();
+            }
+            AddResult::PermissionError(error) => {
+                return Err(RoverClientError::PermissionError { msg: error.message });
+            }
+            AddResult::ValidationError(error) => {
+                return Err(RoverClientError::AdhocError { msg: error.message });
+            }
+        }
+    }
+
+    let updated_count = input.updated.len();
+    for update in input.updated {
+        let data = client
+            .post::<OperationCollectionUpdateMutation>(
+                operation_collection_update_mutation::Variables {
+                    id: collection_id.clone(),
+                    entry_id: update.id.clone(),
+                    name: update.entry.name.clone(),
+                    operation: update_state(update.entry),
+                },
+            )
+            .await?;
+        let entry = data
+            .operation_collection
+            .ok_or_else(|| RoverClientError::OperationCollectionNotFound {
+                collection_id: collection_id.clone(),
+            })?
+            .operation
+            .ok_or_else(|| RoverClientError::MalformedResponse {
+                null_field: "operationCollection.operation".to_string(),
+            })?;
+        match entry {
+            EntryResult::OperationCollectionEntryMutation(mutation) => {
+                match mutation.update_name {
+                    Some(UpdateNameResult::PermissionError(error)) => {
+                        return Err(RoverClientError::PermissionError { msg: error.message });
+                    }
+                    Some(UpdateNameResult::ValidationError(error)) => {
+                        return Err(RoverClientError::AdhocError { msg: error.message });
+                    }
+                    _ => {}
+                }
+                match mutation.update_values {
+                    Some(UpdateValuesResult::PermissionError(error)) => {
+                        return Err(RoverClientError::PermissionError { msg: error.message });
+                    }
+                    Some(UpdateValuesResult::ValidationError(error)) => {
+                        return Err(RoverClientError::AdhocError { msg: error.message });
+                    }
+                    _ => {}
+                }
+            }
+            EntryResult::NotFoundError(_) => {
+                return Err(RoverClientError::AdhocError {
+                    msg: format!(
+                        "Operation {} is no longer in operation collection '{collection_id}'.",
+                        update.id
+                    ),
+                });
+            }
+            EntryResult::PermissionError(error) => {
+                return Err(RoverClientError::PermissionError { msg: error.message });
+            }
+        }
+    }
+
+    Ok(OperationCollectionPushResponse {
+        added_ids,
+        updated_count,
+    })
+}
+
+fn add_state(
+    entry: OperationCollectionEntryInput,
+) -> operation_collection_add_mutation::OperationCollectionEntryStateInput {
+    operation_collection_add_mutation::OperationCollectionEntryStateInput {
+        body: entry.body,
+        variables: entry.variables,
+        headers: Some(
+            entry
+                .headers
+                .into_iter()
+                .map(|OperationHeader { name, value }| {
+                    operation_collection_add_mutation::OperationHeaderInput { name, value }
+                })
+                .collect(),
+        ),
+    }
+}
+
+fn update_state(
+    entry: OperationCollectionEntryInput,
+) -> operation_collection_update_mutation::OperationCollectionEntryStateInput {
+    operation_collection_update_mutation::OperationCollectionEntryStateInput {
+        body: entry.body,
+        variables: entry.variables,
+        headers: Some(
+            entry
+                .headers
+                .into_iter()
+                .map(|OperationHeader { name, value }| {
+                    operation_collection_update_mutation::OperationHeaderInput { name, value }
+                })
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use serde_json::json;
+
+    use super::*;
+
+    fn client(server: &MockServer) -> StudioClient {
+        StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        )
+    }
+
+    fn entry(name: &str) -> OperationCollectionEntryInput {
+        OperationCollectionEntryInput {
+            name: name.to_string(),
+            body: format!("query {name} {{ me {{ id }} }}"),
+            variables: None,
+            headers: vec![OperationHeader {
+                name: "x-client".to_string(),
+                value: "web".to_string(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn run_adds_new_operations_in_one_batch_and_updates_the_rest() {
+        let server = MockServer::start_async().await;
+        let add = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("OperationCollectionAddMutation")
+                .body_includes("\"name\":\"A\"")
+                .body_includes("\"name\":\"B\"");
+            then.status(200)
+                .json_body(json!({ "data": { "operationCollection": {
+                "addOperations": {
+                    "__typename": "AddOperationCollectionEntriesSuccess",
+                    "operationCollectionEntries": [{ "id": "o-a" }, { "id": "o-b" }]
+                }
+            } } }));
+        });
+        let update = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("OperationCollectionUpdateMutation")
+                .body_includes("\"entryId\":\"o-c\"");
+            then.status(200)
+                .json_body(json!({ "data": { "operationCollection": {
+                "operation": {
+                    "__typename": "OperationCollectionEntryMutation",
+                    "updateName": { "__typename": "OperationCollectionEntry" },
+                    "updateValues": { "__typename": "OperationCollectionEntry" }
+                }
+            } } }));
+        });
+
+        let response = run(
+            OperationCollectionPushInput {
+                collection_id: "c-1".to_string(),
+                added: vec![entry("A"), entry("B")],
+                updated: vec![OperationCollectionEntryUpdate {
+                    id: "o-c".to_string(),
+                    entry: entry("C"),
+                }],
+            },
+            &client(&server),
+        )
+        .await
+        .unwrap();
+
+        add.assert();
+        update.assert();
+        assert_eq!(
+            response.added_ids,
+            vec!["o-a".to_string(), "o-b".to_string()]
+        );
+        assert_eq!(response.updated_count, 1);
+    }
+
+    #[tokio::test]
+    async fn run_surfaces_validation_errors() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("OperationCollectionAddMutation");
+            then.status(200)
+                .json_body(json!({ "data": { "operationCollection": {
+                "addOperations": {
+                    "__typename": "ValidationError",
+                    "message": "Operation body can't be parsed"
+                }
+            } } }));
+        });
+
+        let result = run(
+            OperationCollectionPushInput {
+                collection_id: "c-1".to_string(),
+                added: vec![entry("A")],
+                updated: Vec::new(),
+            },
+            &client(&server),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(RoverClientError::AdhocError { msg }) if msg == "Operation body can't be parsed"
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+use serde::Serialize;
+
+use crate::operations::operation_collection::OperationCollectionEntryInput;
+
+/// New values for an operation already in the collection
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OperationCollectionEntryUpdate {
+    pub id: String,
+    pub entry: OperationCollectionEntryInput,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OperationCollectionPushInput {
+    pub collection_id: String,
+    /// Operations to add to the collection
+    pub added: Vec<OperationCollectionEntryInput>,
+    /// Operations in the collection to save new values for
+    pub updated: Vec<OperationCollectionEntryUpdate>,
+}
+
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct OperationCollectionPushResponse {
+    /// The IDs of the operations added to the collection
+    pub added_ids: Vec<String>,
+    pub updated_count: usize,
+}
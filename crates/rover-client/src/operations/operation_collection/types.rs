@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// A header sent with a saved operation
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct OperationHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// An operation saved in a collection, at its latest revision
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct OperationCollectionEntry {
+    pub id: String,
+    pub name: String,
+    pub body: String,
+    /// The operation's variables, as a JSON string
+    pub variables: Option<String>,
+    pub headers: Vec<OperationHeader>,
+}
+
+/// The values to save for an operation in a collection
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct OperationCollectionEntryInput {
+    pub name: String,
+    pub body: String,
+    /// The operation's variables, as a JSON string
+    pub variables: Option<String>,
+    pub headers: Vec<OperationHeader>,
+}
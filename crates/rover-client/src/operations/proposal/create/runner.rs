@@ -0,0 +1,117 @@
+use graphql_client::*;
+use rover_studio::types::GraphRef;
+
+use self::proposal_create_mutation::ProposalCreateMutationGraphCreateProposal as CreateProposalResult;
+use super::types::*;
+use crate::{blocking::StudioClient, RoverClientError};
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/proposal/create/create_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. proposal_create_mutation
+pub(crate) struct ProposalCreateMutation;
+
+/// Creates a proposal from a variant. The proposal starts out with the
+/// variant's current schema.
+pub async fn run(
+    input: ProposalCreateInput,
+    client: &StudioClient,
+) -> Result<ProposalCreateResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let data = client.post::<ProposalCreateMutation>(input.into()).await?;
+    build_response(data, graph_ref)
+}
+
+fn build_response(
+    data: proposal_create_mutation::ResponseData,
+    graph_ref: GraphRef,
+) -> Result<ProposalCreateResponse, RoverClientError> {
+    let result = data
+        .graph
+        .ok_or(RoverClientError::GraphNotFound { graph_ref })?
+        .create_proposal;
+    match result {
+        CreateProposalResult::GraphVariant(variant) => {
+            let proposal = variant
+                .proposal
+                .ok_or(RoverClientError::MalformedResponse {
+                    null_field: "proposal".to_string(),
+                })?;
+            Ok(ProposalCreateResponse {
+                proposal_id: proposal.id,
+                backing_variant: variant.name,
+            })
+        }
+        CreateProposalResult::PermissionError(error) => {
+            Err(RoverClientError::PermissionError { msg: error.message })
+        }
+        CreateProposalResult::CreateProposalError(error) => {
+            Err(RoverClientError::AdhocError { msg: error.message })
+        }
+        CreateProposalResult::ValidationError(error) => {
+            Err(RoverClientError::AdhocError { msg: error.message })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn mock_graph_ref() -> GraphRef {
+        GraphRef::new("mygraph", Some("current")).unwrap()
+    }
+
+    #[test]
+    fn created_proposal_returns_its_id_and_variant() {
+        let data = serde_json::from_value(json!({ "graph": { "createProposal": {
+            "__typename": "GraphVariant",
+            "name": "p-1-variant",
+            "proposal": { "id": "p-1" }
+        } } }))
+        .unwrap();
+        assert_eq!(
+            build_response(data, mock_graph_ref()).unwrap(),
+            ProposalCreateResponse {
+                proposal_id: "p-1".to_string(),
+                backing_variant: "p-1-variant".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn create_errors_are_surfaced() {
+        let data = serde_json::from_value(json!({ "graph": { "createProposal": {
+            "__typename": "CreateProposalError",
+            "message": "Proposals are not enabled for this graph"
+        } } }))
+        .unwrap();
+        assert!(matches!(
+            build_response(data, mock_graph_ref()),
+            Err(RoverClientError::AdhocError { msg }) if msg == "Proposals are not enabled for this graph"
+        ));
+    }
+
+    #[test]
+    fn permission_errors_are_surfaced() {
+        let data = serde_json::from_value(json!({ "graph": { "createProposal": {
+            "__typename": "PermissionError",
+            "message": "nope"
+        } } }))
+        .unwrap();
+        assert!(matches!(
+            build_response(data, mock_graph_ref()),
+            Err(RoverClientError::PermissionError { .. })
+        ));
+    }
+}
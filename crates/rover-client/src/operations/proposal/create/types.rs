@@ -0,0 +1,36 @@
+use rover_studio::types::GraphRef;
+use serde::Serialize;
+
+use crate::operations::proposal::create::runner::proposal_create_mutation;
+
+type MutationVariables = proposal_create_mutation::Variables;
+type MutationInput = proposal_create_mutation::CreateProposalInput;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProposalCreateInput {
+    /// The variant the proposal starts from
+    pub graph_ref: GraphRef,
+    pub display_name: String,
+    pub description: Option<String>,
+}
+
+impl From<ProposalCreateInput> for MutationVariables {
+    fn from(input: ProposalCreateInput) -> Self {
+        let (graph_id, variant) = input.graph_ref.into_parts();
+        Self {
+            graph_id,
+            input: MutationInput {
+                description: input.description,
+                display_name: input.display_name,
+                source_variant_name: variant,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ProposalCreateResponse {
+    pub proposal_id: String,
+    /// The variant that holds the proposal's schema under the hood
+    pub backing_variant: String,
+}
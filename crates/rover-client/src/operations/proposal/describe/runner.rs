@@ -0,0 +1,196 @@
+use graphql_client::*;
+
+use super::types::*;
+use crate::{
+    blocking::StudioClient, operations::proposal::ProposalStatus, shared::graphql_enum_value,
+    RoverClientError,
+};
+
+type Timestamp = String;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/proposal/describe/describe_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. proposal_describe_query
+pub(crate) struct ProposalDescribeQuery;
+
+/// Fetches a proposal, its latest revision and its reviews
+pub async fn run(
+    input: ProposalDescribeInput,
+    client: &StudioClient,
+) -> Result<ProposalDetails, RoverClientError> {
+    let proposal_id = input.proposal_id.clone();
+    let data = client.post::<ProposalDescribeQuery>(input.into()).await?;
+    build_response(data, proposal_id)
+}
+
+fn build_response(
+    data: proposal_describe_query::ResponseData,
+    proposal_id: String,
+) -> Result<ProposalDetails, RoverClientError> {
+    let proposal = data
+        .proposal
+        .ok_or(RoverClientError::ProposalNotFound { proposal_id })?;
+    let revision = proposal.latest_revision;
+    Ok(ProposalDetails {
+        id: proposal.id,
+        display_name: proposal.display_name,
+        description: proposal.description,
+        status: ProposalStatus::from_graphql(proposal.status),
+        source_variant: proposal.source_variant.id,
+        created_by: proposal.created_by.map(|identity| identity.name),
+        created_at: proposal.created_at,
+        updated_at: proposal.updated_at,
+        graph_id: proposal.backing_variant.graph.id,
+        backing_variant: proposal.backing_variant.name,
+        latest_revision: ProposalRevision {
+            id: revision.id,
+            summary: revision.summary,
+            created_at: revision.created_at,
+            launch_id: revision.launch.as_ref().map(|launch| launch.id.clone()),
+            launch_status: revision
+                .launch
+                .map(|launch| graphql_enum_value(launch.status)),
+            check_workflow_id: revision
+                .check_workflow
+                .as_ref()
+                .map(|workflow| workflow.id.clone()),
+            check_status: revision
+                .check_workflow
+                .map(|workflow| graphql_enum_value(workflow.status)),
+        },
+        revision_count: proposal.revision_history.total_count,
+        reviews: proposal
+            .reviews
+            .into_iter()
+            .map(|review| ProposalReview {
+                reviewer: review.created_by.map(|identity| identity.name),
+                decision: graphql_enum_value(review.decision),
+                is_dismissed: review.is_dismissed,
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn proposal_json() -> Value {
+        json!({
+            "id": "p-1",
+            "displayName": "Add reviews",
+            "description": "Adds a reviews field",
+            "status": "OPEN",
+            "createdAt": "2026-01-02T03:04:05Z",
+            "updatedAt": "2026-01-03T03:04:05Z",
+            "createdBy": { "__typename": "User", "name": "Ada" },
+            "sourceVariant": { "id": "mygraph@current" },
+            "backingVariant": { "name": "p-1-variant", "graph": { "id": "mygraph" } },
+            "latestRevision": {
+                "id": "r-2",
+                "summary": "Second pass",
+                "createdAt": "2026-01-03T03:04:05Z",
+                "launch": { "id": "launch-2", "status": "LAUNCH_COMPLETED" },
+                "checkWorkflow": { "id": "wf-2", "status": "PASSED" }
+            },
+            "revisionHistory": { "totalCount": 2 },
+            "reviews": [
+                { "decision": "APPROVED", "isDismissed": false, "createdBy": { "__typename": "User", "name": "Grace" } }
+            ]
+        })
+    }
+
+    #[test]
+    fn builds_details_from_a_proposal() {
+        let data = serde_json::from_value(json!({ "proposal": proposal_json() })).unwrap();
+        let details = build_response(data, "p-1".to_string()).unwrap();
+
+        assert_eq!(details.status, ProposalStatus::Open);
+        assert_eq!(details.source_variant, "mygraph@current");
+        assert_eq!(details.graph_id, "mygraph");
+        assert_eq!(details.backing_variant, "p-1-variant");
+        assert_eq!(
+            details.latest_revision.launch_id.as_deref(),
+            Some("launch-2")
+        );
+        assert_eq!(
+            details.latest_revision.launch_status.as_deref(),
+            Some("LAUNCH_COMPLETED")
+        );
+        assert_eq!(
+            details.latest_revision.check_status.as_deref(),
+            Some("PASSED")
+        );
+        assert_eq!(details.revision_count, 2);
+        assert_eq!(
+            details.reviews,
+            vec![ProposalReview {
+                reviewer: Some("Grace".to_string()),
+                decision: "APPROVED".to_string(),
+                is_dismissed: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_proposal_is_not_found() {
+        let data = serde_json::from_value(json!({ "proposal": null })).unwrap();
+        let result = build_response(data, "p-404".to_string());
+        assert!(matches!(
+            result,
+            Err(RoverClientError::ProposalNotFound { proposal_id }) if proposal_id == "p-404"
+        ));
+    }
+
+    #[tokio::test]
+    async fn run_fetches_the_proposal_by_id() {
+        let server = MockServer::start_async().await;
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("ProposalDescribeQuery")
+                .body_includes("\"proposalId\":\"p-1\"");
+            then.status(200)
+                .json_body(json!({ "data": { "proposal": proposal_json() } }));
+        });
+
+        let client = StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        );
+        let details = run(
+            ProposalDescribeInput {
+                proposal_id: "p-1".to_string(),
+            },
+            &client,
+        )
+        .await
+        .unwrap();
+
+        mock.assert();
+        assert_eq!(details.display_name, "Add reviews");
+    }
+}
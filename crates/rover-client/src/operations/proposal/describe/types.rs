@@ -0,0 +1,58 @@
+use serde::Serialize;
+
+use crate::operations::proposal::{describe::runner::proposal_describe_query, ProposalStatus};
+
+type QueryVariables = proposal_describe_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProposalDescribeInput {
+    pub proposal_id: String,
+}
+
+impl From<ProposalDescribeInput> for QueryVariables {
+    fn from(input: ProposalDescribeInput) -> Self {
+        Self {
+            proposal_id: input.proposal_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ProposalDetails {
+    pub id: String,
+    pub display_name: String,
+    pub description: String,
+    pub status: ProposalStatus,
+    /// The `graph@variant` the proposal was created from
+    pub source_variant: String,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// The graph the proposal belongs to
+    #[serde(skip)]
+    pub graph_id: String,
+    /// The variant that holds the proposal's schema under the hood
+    #[serde(skip)]
+    pub backing_variant: String,
+    pub latest_revision: ProposalRevision,
+    pub revision_count: i64,
+    pub reviews: Vec<ProposalReview>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ProposalRevision {
+    pub id: String,
+    pub summary: String,
+    pub created_at: String,
+    pub launch_id: Option<String>,
+    pub launch_status: Option<String>,
+    pub check_workflow_id: Option<String>,
+    pub check_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ProposalReview {
+    pub reviewer: Option<String>,
+    pub decision: String,
+    pub is_dismissed: bool,
+}
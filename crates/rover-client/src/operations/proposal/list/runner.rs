@@ -0,0 +1,153 @@
+use graphql_client::*;
+use rover_studio::types::GraphRef;
+
+use super::types::*;
+use crate::{
+    blocking::StudioClient,
+    operations::proposal::{ProposalStatus, ProposalSummary},
+    RoverClientError,
+};
+
+type Timestamp = String;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/proposal/list/list_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. proposal_list_query
+pub(crate) struct ProposalListQuery;
+
+/// Lists the proposals created from a variant, newest first
+pub async fn run(
+    input: ProposalListInput,
+    client: &StudioClient,
+) -> Result<ProposalListResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let data = client.post::<ProposalListQuery>(input.into()).await?;
+    build_response(data, graph_ref)
+}
+
+fn build_response(
+    data: proposal_list_query::ResponseData,
+    graph_ref: GraphRef,
+) -> Result<ProposalListResponse, RoverClientError> {
+    let proposals = data
+        .graph
+        .ok_or(RoverClientError::GraphNotFound {
+            graph_ref: graph_ref.clone(),
+        })?
+        .proposals;
+    Ok(ProposalListResponse {
+        graph_ref,
+        total_count: proposals.total_count,
+        proposals: proposals
+            .proposals
+            .into_iter()
+            .map(|proposal| ProposalSummary {
+                id: proposal.id,
+                display_name: proposal.display_name,
+                status: ProposalStatus::from_graphql(proposal.status),
+                source_variant: proposal.source_variant.id,
+                created_by: proposal.created_by.map(|identity| identity.name),
+                created_at: proposal.created_at,
+                updated_at: proposal.updated_at,
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use serde_json::json;
+
+    use super::*;
+
+    fn mock_graph_ref() -> GraphRef {
+        GraphRef::new("mygraph", Some("current")).unwrap()
+    }
+
+    #[test]
+    fn missing_graph_is_not_found() {
+        let data = serde_json::from_value(json!({ "graph": null })).unwrap();
+        let result = build_response(data, mock_graph_ref());
+        assert!(matches!(
+            result,
+            Err(RoverClientError::GraphNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn run_filters_by_source_variant_and_status() {
+        let server = MockServer::start_async().await;
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("ProposalListQuery")
+                .body_includes("\"sourceVariants\":[\"current\"]")
+                .body_includes("\"status\":[\"OPEN\",\"APPROVED\"]");
+            then.status(200)
+                .json_body(json!({ "data": { "graph": { "proposals": {
+                "totalCount": 3,
+                "proposals": [{
+                    "id": "p-1",
+                    "displayName": "Add reviews",
+                    "status": "OPEN",
+                    "createdAt": "2026-01-02T03:04:05Z",
+                    "updatedAt": "2026-01-03T03:04:05Z",
+                    "createdBy": null,
+                    "sourceVariant": { "id": "mygraph@current" }
+                }]
+            } } } }));
+        });
+
+        let client = StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        );
+        let response = run(
+            ProposalListInput {
+                graph_ref: mock_graph_ref(),
+                statuses: vec![ProposalStatus::Open, ProposalStatus::Approved],
+                subgraphs: Vec::new(),
+                limit: Some(1),
+            },
+            &client,
+        )
+        .await
+        .unwrap();
+
+        mock.assert();
+        assert_eq!(response.total_count, 3);
+        assert_eq!(
+            response.proposals,
+            vec![ProposalSummary {
+                id: "p-1".to_string(),
+                display_name: "Add reviews".to_string(),
+                status: ProposalStatus::Open,
+                source_variant: "mygraph@current".to_string(),
+                created_by: None,
+                created_at: "2026-01-02T03:04:05Z".to_string(),
+                updated_at: "2026-01-03T03:04:05Z".to_string(),
+            }]
+        );
+    }
+}
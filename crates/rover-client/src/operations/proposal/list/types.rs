@@ -0,0 +1,58 @@
+use rover_studio::types::GraphRef;
+use serde::Serialize;
+
+use crate::operations::proposal::{
+    list::runner::proposal_list_query, ProposalStatus, ProposalSummary,
+};
+
+type QueryVariables = proposal_list_query::Variables;
+type QueryFilter = proposal_list_query::ProposalsFilterInput;
+type QueryProposalStatus = proposal_list_query::ProposalStatus;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProposalListInput {
+    /// Only proposals created from this variant are listed
+    pub graph_ref: GraphRef,
+    /// Only proposals in one of these statuses are listed, or all of them if empty
+    pub statuses: Vec<ProposalStatus>,
+    /// Only proposals that change one of these subgraphs are listed, or all of them if empty
+    pub subgraphs: Vec<String>,
+    pub limit: Option<i64>,
+}
+
+impl From<ProposalListInput> for QueryVariables {
+    fn from(input: ProposalListInput) -> Self {
+        let (graph_id, variant) = input.graph_ref.into_parts();
+        let status = (!input.statuses.is_empty()).then(|| {
+            input
+                .statuses
+                .into_iter()
+                .map(|status| match status {
+                    ProposalStatus::Draft => QueryProposalStatus::DRAFT,
+                    ProposalStatus::Open => QueryProposalStatus::OPEN,
+                    ProposalStatus::Approved => QueryProposalStatus::APPROVED,
+                    ProposalStatus::Implemented => QueryProposalStatus::IMPLEMENTED,
+                    ProposalStatus::Closed => QueryProposalStatus::CLOSED,
+                    ProposalStatus::Other(status) => QueryProposalStatus::Other(status),
+                })
+                .collect()
+        });
+        Self {
+            graph_id,
+            filter_by: Some(QueryFilter {
+                source_variants: Some(vec![variant]),
+                status,
+                subgraphs: (!input.subgraphs.is_empty()).then_some(input.subgraphs),
+            }),
+            limit: input.limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ProposalListResponse {
+    pub graph_ref: GraphRef,
+    pub proposals: Vec<ProposalSummary>,
+    /// How many proposals match, including any past `limit`
+    pub total_count: i64,
+}
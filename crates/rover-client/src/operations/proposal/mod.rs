@@ -0,0 +1,18 @@
+/// "proposal create"
+pub mod create;
+
+/// "proposal describe"
+pub mod describe;
+
+/// "proposal list"
+pub mod list;
+
+/// "proposal revise"
+pub mod revise;
+
+/// "proposal status"
+pub mod status;
+
+pub(crate) mod types;
+
+pub use types::{ProposalStatus, ProposalSummary};
@@ -0,0 +1,257 @@
+use graphql_client::*;
+
+use self::proposal_revise_mutation::{
+    ProposalReviseMutationGraphVariantProposal as ProposalMutationResult,
+    ProposalReviseMutationGraphVariantProposalOnProposalMutationPublishSubgraphs as PublishResult,
+};
+use super::types::*;
+use crate::{
+    blocking::StudioClient,
+    operations::proposal::describe::{self, ProposalDescribeInput, ProposalRevision},
+    shared::graphql_enum_value,
+    RoverClientError,
+};
+
+type Timestamp = String;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/proposal/revise/revise_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. proposal_revise_mutation
+pub(crate) struct ProposalReviseMutation;
+
+/// Saves a new revision of a proposal with the given subgraph schemas.
+///
+/// A revision has to name the launch of the revision it replaces, so the
+/// proposal is looked up first.
+pub async fn run(
+    input: ProposalReviseInput,
+    client: &StudioClient,
+) -> Result<ProposalReviseResponse, RoverClientError> {
+    let proposal_id = input.proposal_id.clone();
+    let details = describe::run(
+        ProposalDescribeInput {
+            proposal_id: proposal_id.clone(),
+        },
+        client,
+    )
+    .await?;
+    let previous_launch_id =
+        details
+            .latest_revision
+            .launch_id
+            .ok_or_else(|| RoverClientError::AdhocError {
+                msg: format!(
+                    "The latest revision of proposal '{proposal_id}' hasn't started launching yet. Try again in a few seconds."
+                ),
+            })?;
+    let context = ProposalReviseContext {
+        graph_id: details.graph_id,
+        backing_variant: details.backing_variant,
+        previous_launch_id,
+    };
+    let data = client
+        .post::<ProposalReviseMutation>(mutation_variables(input, context))
+        .await?;
+    build_response(data, proposal_id)
+}
+
+fn build_response(
+    data: proposal_revise_mutation::ResponseData,
+    proposal_id: String,
+) -> Result<ProposalReviseResponse, RoverClientError> {
+    let proposal = data
+        .graph
+        .and_then(|graph| graph.variant)
+        .ok_or_else(|| RoverClientError::ProposalNotFound {
+            proposal_id: proposal_id.clone(),
+        })?
+        .proposal;
+    let published = match proposal {
+        ProposalMutationResult::ProposalMutation(mutation) => mutation.publish_subgraphs,
+        ProposalMutationResult::NotFoundError(_) => {
+            return Err(RoverClientError::ProposalNotFound { proposal_id })
+        }
+        ProposalMutationResult::PermissionError(error) => {
+            return Err(RoverClientError::PermissionError { msg: error.message })
+        }
+        ProposalMutationResult::ValidationError(error) => {
+            return Err(RoverClientError::AdhocError { msg: error.message })
+        }
+    };
+    match published {
+        PublishResult::Proposal(proposal) => {
+            let revision = proposal.latest_revision;
+            Ok(ProposalReviseResponse {
+                proposal_id: proposal.id,
+                revision: ProposalRevision {
+                    id: revision.id,
+                    summary: revision.summary,
+                    created_at: revision.created_at,
+                    launch_id: revision.launch.as_ref().map(|launch| launch.id.clone()),
+                    launch_status: revision
+                        .launch
+                        .map(|launch| graphql_enum_value(launch.status)),
+                    check_workflow_id: None,
+                    check_status: None,
+                },
+            })
+        }
+        PublishResult::NotFoundError(_) => Err(RoverClientError::ProposalNotFound { proposal_id }),
+        PublishResult::PermissionError(error) => {
+            Err(RoverClientError::PermissionError { msg: error.message })
+        }
+        PublishResult::ValidationError(error) => {
+            Err(RoverClientError::AdhocError { msg: error.message })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use serde_json::json;
+
+    use super::*;
+    use crate::shared::GitContext;
+
+    fn mock_client(server: &MockServer) -> StudioClient {
+        StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        )
+    }
+
+    fn mock_input() -> ProposalReviseInput {
+        ProposalReviseInput {
+            proposal_id: "p-1".to_string(),
+            subgraphs: vec![ProposalSubgraphInput {
+                name: "reviews".to_string(),
+                schema: "type Query { reviews: [String] }".to_string(),
+                routing_url: Some("http://reviews".to_string()),
+            }],
+            summary: "Add reviews".to_string(),
+            git_context: GitContext {
+                branch: None,
+                author: None,
+                commit: None,
+                remote_url: None,
+            },
+        }
+    }
+
+    fn describe_response(launch: serde_json::Value) -> serde_json::Value {
+        json!({ "data": { "proposal": {
+            "id": "p-1",
+            "displayName": "Add reviews",
+            "description": "",
+            "status": "DRAFT",
+            "createdAt": "2026-01-02T03:04:05Z",
+            "updatedAt": "2026-01-02T03:04:05Z",
+            "createdBy": null,
+            "sourceVariant": { "id": "mygraph@current" },
+            "backingVariant": { "name": "p-1-variant", "graph": { "id": "mygraph" } },
+            "latestRevision": {
+                "id": "r-1",
+                "summary": "Created",
+                "createdAt": "2026-01-02T03:04:05Z",
+                "launch": launch,
+                "checkWorkflow": null
+            },
+            "revisionHistory": { "totalCount": 1 },
+            "reviews": []
+        } } })
+    }
+
+    #[tokio::test]
+    async fn run_publishes_against_the_previous_launch() {
+        let server = MockServer::start_async().await;
+        let describe = server.mock(|when, then| {
+            when.method(POST).body_includes("ProposalDescribeQuery");
+            then.status(200).json_body(describe_response(
+                json!({ "id": "launch-1", "status": "LAUNCH_COMPLETED" }),
+            ));
+        });
+        let revise = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("ProposalReviseMutation")
+                .body_includes("\"backingVariant\":\"p-1-variant\"")
+                .body_includes("\"previousLaunchId\":\"launch-1\"")
+                .body_includes("\"name\":\"reviews\"");
+            then.status(200)
+                .json_body(json!({ "data": { "graph": { "variant": { "proposal": {
+                "__typename": "ProposalMutation",
+                "publishSubgraphs": {
+                    "__typename": "Proposal",
+                    "id": "p-1",
+                    "latestRevision": {
+                        "id": "r-2",
+                        "summary": "Add reviews",
+                        "createdAt": "2026-01-03T03:04:05Z",
+                        "launch": { "id": "launch-2", "status": "LAUNCH_INITIATED" }
+                    }
+                }
+            } } } } }));
+        });
+
+        let response = run(mock_input(), &mock_client(&server)).await.unwrap();
+
+        describe.assert();
+        revise.assert();
+        assert_eq!(response.revision.id, "r-2");
+        assert_eq!(response.revision.launch_id.as_deref(), Some("launch-2"));
+        assert_eq!(
+            response.revision.launch_status.as_deref(),
+            Some("LAUNCH_INITIATED")
+        );
+    }
+
+    #[tokio::test]
+    async fn run_needs_a_previous_launch() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.method(POST).body_includes("ProposalDescribeQuery");
+            then.status(200).json_body(describe_response(json!(null)));
+        });
+
+        let result = run(mock_input(), &mock_client(&server)).await;
+
+        assert!(matches!(result, Err(RoverClientError::AdhocError { .. })));
+    }
+
+    #[test]
+    fn validation_errors_are_surfaced() {
+        let data = serde_json::from_value(json!({ "graph": { "variant": { "proposal": {
+            "__typename": "ProposalMutation",
+            "publishSubgraphs": {
+                "__typename": "ValidationError",
+                "message": "Closed proposals can't be revised"
+            }
+        } } } }))
+        .unwrap();
+        assert!(matches!(
+            build_response(data, "p-1".to_string()),
+            Err(RoverClientError::AdhocError { msg }) if msg == "Closed proposals can't be revised"
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+use serde::Serialize;
+
+use crate::{
+    operations::proposal::{describe::ProposalRevision, revise::runner::proposal_revise_mutation},
+    shared::GitContext,
+};
+
+type MutationVariables = proposal_revise_mutation::Variables;
+type MutationInput = proposal_revise_mutation::PublishProposalSubgraphsInput;
+type SubgraphInput = proposal_revise_mutation::PublishSubgraphsSubgraphInput;
+type SchemaInput = proposal_revise_mutation::PartialSchemaInput;
+type GitContextInput = proposal_revise_mutation::GitContextInput;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProposalReviseInput {
+    pub proposal_id: String,
+    pub subgraphs: Vec<ProposalSubgraphInput>,
+    pub summary: String,
+    pub git_context: GitContext,
+}
+
+/// A subgraph schema to save in a proposal revision
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProposalSubgraphInput {
+    pub name: String,
+    pub schema: String,
+    pub routing_url: Option<String>,
+}
+
+/// Everything the mutation needs besides the caller's input, looked up from
+/// the proposal itself
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ProposalReviseContext {
+    pub graph_id: String,
+    pub backing_variant: String,
+    pub previous_launch_id: String,
+}
+
+pub(crate) fn mutation_variables(
+    input: ProposalReviseInput,
+    context: ProposalReviseContext,
+) -> MutationVariables {
+    MutationVariables {
+        graph_id: context.graph_id,
+        backing_variant: context.backing_variant,
+        input: MutationInput {
+            git_context: Some(GitContextInput {
+                branch: input.git_context.branch,
+                commit: input.git_context.commit,
+                committer: input.git_context.author,
+                remote_url: input.git_context.remote_url,
+                message: None,
+            }),
+            previous_launch_id: context.previous_launch_id,
+            revision: "".to_string(),
+            subgraph_inputs: input
+                .subgraphs
+                .into_iter()
+                .map(|subgraph| SubgraphInput {
+                    active_partial_schema: SchemaInput {
+                        sdl: Some(subgraph.schema),
+                        hash: None,
+                    },
+                    name: subgraph.name,
+                    url: subgraph.routing_url,
+                })
+                .collect(),
+            summary: input.summary,
+        },
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ProposalReviseResponse {
+    pub proposal_id: String,
+    pub revision: ProposalRevision,
+}
@@ -0,0 +1,189 @@
+use graphql_client::*;
+
+use self::proposal_status_mutation::{
+    ProposalStatusMutationGraphVariantProposal as ProposalMutationResult,
+    ProposalStatusMutationGraphVariantProposalOnProposalMutationUpdateStatus as UpdateResult,
+};
+use super::types::*;
+use crate::{
+    blocking::StudioClient,
+    operations::proposal::{
+        describe::{self, ProposalDescribeInput},
+        ProposalStatus,
+    },
+    RoverClientError,
+};
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/proposal/status/status_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. proposal_status_mutation
+pub(crate) struct ProposalStatusMutation;
+
+/// Moves a proposal to a new status, e.g. from `DRAFT` to `OPEN` once it's
+/// ready for review
+pub async fn run(
+    input: ProposalStatusInput,
+    client: &StudioClient,
+) -> Result<ProposalStatusResponse, RoverClientError> {
+    let proposal_id = input.proposal_id.clone();
+    let details = describe::run(
+        ProposalDescribeInput {
+            proposal_id: proposal_id.clone(),
+        },
+        client,
+    )
+    .await?;
+    let data = client
+        .post::<ProposalStatusMutation>(mutation_variables(
+            input.status,
+            details.graph_id,
+            details.backing_variant,
+        ))
+        .await?;
+    build_response(data, proposal_id, details.status)
+}
+
+fn build_response(
+    data: proposal_status_mutation::ResponseData,
+    proposal_id: String,
+    previous_status: ProposalStatus,
+) -> Result<ProposalStatusResponse, RoverClientError> {
+    let proposal = data
+        .graph
+        .and_then(|graph| graph.variant)
+        .ok_or_else(|| RoverClientError::ProposalNotFound {
+            proposal_id: proposal_id.clone(),
+        })?
+        .proposal;
+    let updated = match proposal {
+        ProposalMutationResult::ProposalMutation(mutation) => mutation.update_status,
+        ProposalMutationResult::NotFoundError(_) => {
+            return Err(RoverClientError::ProposalNotFound { proposal_id })
+        }
+        ProposalMutationResult::PermissionError(error) => {
+            return Err(RoverClientError::PermissionError { msg: error.message })
+        }
+        ProposalMutationResult::ValidationError(error) => {
+            return Err(RoverClientError::AdhocError { msg: error.message })
+        }
+    };
+    match updated {
+        UpdateResult::Proposal(proposal) => Ok(ProposalStatusResponse {
+            proposal_id: proposal.id,
+            previous_status,
+            status: ProposalStatus::from_graphql(proposal.status),
+        }),
+        UpdateResult::PermissionError(error) => {
+            Err(RoverClientError::PermissionError { msg: error.message })
+        }
+        UpdateResult::ValidationError(error) => {
+            Err(RoverClientError::AdhocError { msg: error.message })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn run_updates_the_backing_variants_proposal() {
+        let server = MockServer::start_async().await;
+        let describe = server.mock(|when, then| {
+            when.method(POST).body_includes("ProposalDescribeQuery");
+            then.status(200).json_body(json!({ "data": { "proposal": {
+                "id": "p-1",
+                "displayName": "Add reviews",
+                "description": "",
+                "status": "DRAFT",
+                "createdAt": "2026-01-02T03:04:05Z",
+                "updatedAt": "2026-01-02T03:04:05Z",
+                "createdBy": null,
+                "sourceVariant": { "id": "mygraph@current" },
+                "backingVariant": { "name": "p-1-variant", "graph": { "id": "mygraph" } },
+                "latestRevision": {
+                    "id": "r-1",
+                    "summary": "Created",
+                    "createdAt": "2026-01-02T03:04:05Z",
+                    "launch": null,
+                    "checkWorkflow": null
+                },
+                "revisionHistory": { "totalCount": 1 },
+                "reviews": []
+            } } }));
+        });
+        let update = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("ProposalStatusMutation")
+                .body_includes("\"backingVariant\":\"p-1-variant\"")
+                .body_includes("\"status\":\"OPEN\"");
+            then.status(200)
+                .json_body(json!({ "data": { "graph": { "variant": { "proposal": {
+                "__typename": "ProposalMutation",
+                "updateStatus": { "__typename": "Proposal", "id": "p-1", "status": "OPEN" }
+            } } } } }));
+        });
+
+        let client = StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        );
+        let response = run(
+            ProposalStatusInput {
+                proposal_id: "p-1".to_string(),
+                status: ProposalStatus::Open,
+            },
+            &client,
+        )
+        .await
+        .unwrap();
+
+        describe.assert();
+        update.assert();
+        assert_eq!(
+            response,
+            ProposalStatusResponse {
+                proposal_id: "p-1".to_string(),
+                previous_status: ProposalStatus::Draft,
+                status: ProposalStatus::Open,
+            }
+        );
+    }
+
+    #[test]
+    fn permission_errors_are_surfaced() {
+        let data = serde_json::from_value(json!({ "graph": { "variant": { "proposal": {
+            "__typename": "ProposalMutation",
+            "updateStatus": { "__typename": "PermissionError", "message": "nope" }
+        } } } }))
+        .unwrap();
+        assert!(matches!(
+            build_response(data, "p-1".to_string(), ProposalStatus::Draft),
+            Err(RoverClientError::PermissionError { .. })
+        ));
+    }
+}
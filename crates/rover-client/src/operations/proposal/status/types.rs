@@ -0,0 +1,38 @@
+use serde::Serialize;
+
+use crate::operations::proposal::{status::runner::proposal_status_mutation, ProposalStatus};
+
+type MutationVariables = proposal_status_mutation::Variables;
+type MutationProposalStatus = proposal_status_mutation::ProposalStatus;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProposalStatusInput {
+    pub proposal_id: String,
+    pub status: ProposalStatus,
+}
+
+pub(crate) fn mutation_variables(
+    status: ProposalStatus,
+    graph_id: String,
+    backing_variant: String,
+) -> MutationVariables {
+    MutationVariables {
+        graph_id,
+        backing_variant,
+        status: match status {
+            ProposalStatus::Draft => MutationProposalStatus::DRAFT,
+            ProposalStatus::Open => MutationProposalStatus::OPEN,
+            ProposalStatus::Approved => MutationProposalStatus::APPROVED,
+            ProposalStatus::Implemented => MutationProposalStatus::IMPLEMENTED,
+            ProposalStatus::Closed => MutationProposalStatus::CLOSED,
+            ProposalStatus::Other(status) => MutationProposalStatus::Other(status),
+        },
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ProposalStatusResponse {
+    pub proposal_id: String,
+    pub previous_status: ProposalStatus,
+    pub status: ProposalStatus,
+}
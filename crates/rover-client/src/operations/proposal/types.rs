@@ -0,0 +1,99 @@
+use std::{fmt, str::FromStr};
+
+use serde::Serialize;
+
+use crate::shared::graphql_enum_value;
+
+/// The lifecycle state of a schema proposal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ProposalStatus {
+    Draft,
+    Open,
+    Approved,
+    Implemented,
+    Closed,
+    /// A status this version of Rover doesn't know about yet
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl ProposalStatus {
+    /// Converts the `ProposalStatus` enum generated for a query, which
+    /// serializes to the GraphQL enum value
+    pub(crate) fn from_graphql(status: impl Serialize) -> ProposalStatus {
+        let status = graphql_enum_value(status);
+        status.parse().unwrap_or(ProposalStatus::Other(status))
+    }
+}
+
+impl FromStr for ProposalStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "DRAFT" => Ok(ProposalStatus::Draft),
+            "OPEN" => Ok(ProposalStatus::Open),
+            "APPROVED" => Ok(ProposalStatus::Approved),
+            "IMPLEMENTED" => Ok(ProposalStatus::Implemented),
+            "CLOSED" => Ok(ProposalStatus::Closed),
+            _ => Err(format!(
+                "'{s}' is not a proposal status. Expected one of DRAFT, OPEN, APPROVED, IMPLEMENTED or CLOSED"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ProposalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            ProposalStatus::Draft => "DRAFT",
+            ProposalStatus::Open => "OPEN",
+            ProposalStatus::Approved => "APPROVED",
+            ProposalStatus::Implemented => "IMPLEMENTED",
+            ProposalStatus::Closed => "CLOSED",
+            ProposalStatus::Other(status) => status,
+        };
+        write!(f, "{status}")
+    }
+}
+
+/// The fields of a proposal that `proposal list` shows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProposalSummary {
+    pub id: String,
+    pub display_name: String,
+    pub status: ProposalStatus,
+    pub source_variant: String,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("OPEN", ProposalStatus::Open)]
+    #[case("approved", ProposalStatus::Approved)]
+    #[case("Closed", ProposalStatus::Closed)]
+    fn parses_statuses_case_insensitively(#[case] input: &str, #[case] expected: ProposalStatus) {
+        assert_eq!(input.parse::<ProposalStatus>().unwrap(), expected);
+    }
+
+    #[test]
+    fn keeps_unknown_statuses_from_graphos() {
+        assert_eq!(
+            ProposalStatus::from_graphql("ARCHIVED"),
+            ProposalStatus::Other("ARCHIVED".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(ProposalStatus::from_graphql("ARCHIVED")).unwrap(),
+            "ARCHIVED"
+        );
+        assert_eq!(serde_json::to_value(ProposalStatus::Open).unwrap(), "OPEN");
+    }
+}
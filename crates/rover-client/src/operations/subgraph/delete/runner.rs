@@ -24,6 +24,9 @@ pub async fn run(
     input: SubgraphDeleteInput,
     client: &StudioClient,
 ) -> Result<SubgraphDeleteResponse, RoverClientError> {
+    if let Some(registry) = client.local_registry() {
+        return registry.delete_subgraph(input).await;
+    }
     let graph_ref = input.graph_ref.clone();
     let response_data = client.post::<SubgraphDeleteMutation>(input.into()).await?;
     let data = get_delete_data_from_response(response_data, graph_ref)?;
@@ -11,10 +11,13 @@ pub async fn run(
     input: SubgraphFetchInput,
     client: &StudioClient,
 ) -> Result<FetchResponse, RoverClientError> {
+    if let Some(registry) = client.local_registry() {
+        return registry.fetch_subgraph(input);
+    }
     let mut service = SubgraphFetch::new(
         client
             .studio_graphql_service()
-            .map_err(|err| RoverClientError::ServiceReady(Box::new(err)))?,
+            .map_err(RoverClientError::from)?,
     );
     let service = service.ready().await?;
     let fetch_response = service.call(SubgraphFetchRequest::from(input)).await?;
@@ -11,10 +11,13 @@ pub async fn run(
     input: SubgraphFetchAllInput,
     client: &StudioClient,
 ) -> Result<SubgraphFetchAllResponse, RoverClientError> {
+    if let Some(registry) = client.local_registry() {
+        return registry.fetch_all_subgraphs(input);
+    }
     let mut service = SubgraphFetchAll::new(
         client
             .studio_graphql_service()
-            .map_err(|err| RoverClientError::ServiceReady(Box::new(err)))?,
+            .map_err(RoverClientError::from)?,
     );
     let service = service.ready().await?;
     let subgraphs = service
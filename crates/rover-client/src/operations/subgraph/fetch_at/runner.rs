@@ -0,0 +1,177 @@
+use graphql_client::*;
+
+use super::types::*;
+use crate::{
+    blocking::StudioClient,
+    operations::graph::history::resolve_launch,
+    shared::{FetchResponse, Sdl, SdlType},
+    RoverClientError,
+};
+
+type GraphQLDocument = String;
+type SHA256 = String;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/subgraph/fetch_at/fetch_at_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. subgraph_fetch_at_query
+pub(crate) struct SubgraphFetchAtQuery;
+
+/// Fetch the SDL a subgraph was composed with in a past launch, given either
+/// the launch's ID or a time to find the launch that was live then
+pub async fn run(
+    input: SubgraphFetchAtInput,
+    client: &StudioClient,
+) -> Result<FetchResponse, RoverClientError> {
+    let launch = resolve_launch(&input.graph_ref, &input.at, client).await?;
+    let subgraph = launch
+        .subgraphs
+        .iter()
+        .find(|subgraph| subgraph.name == input.subgraph_name)
+        .ok_or_else(|| RoverClientError::NoSubgraphInGraph {
+            invalid_subgraph: input.subgraph_name.clone(),
+            valid_subgraphs: launch
+                .subgraphs
+                .iter()
+                .map(|subgraph| subgraph.name.clone())
+                .collect(),
+        })?;
+    let data = client
+        .post::<SubgraphFetchAtQuery>(subgraph_fetch_at_query::Variables {
+            graph_id: input.graph_ref.graph_id().clone(),
+            hash: subgraph.schema_hash.clone(),
+        })
+        .await?;
+    let contents = data
+        .graph
+        .ok_or_else(|| RoverClientError::GraphNotFound {
+            graph_ref: input.graph_ref.clone(),
+        })?
+        .doc
+        .ok_or_else(|| RoverClientError::AdhocError {
+            msg: format!(
+                "GraphOS no longer has the schema subgraph '{}' was composed with in launch '{}'",
+                input.subgraph_name, launch.launch_id
+            ),
+        })?
+        .source;
+    Ok(FetchResponse {
+        sdl: Sdl {
+            contents,
+            r#type: SdlType::Subgraph {
+                routing_url: Some(subgraph.routing_url.clone()),
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use rover_studio::types::GraphRef;
+    use serde_json::json;
+
+    use super::*;
+    use crate::operations::graph::history::HistoryPoint;
+
+    fn mock_server_with_launch(server: &MockServer) {
+        server.mock(|when, then| {
+            when.method(POST).body_includes("GraphHistoryQuery");
+            then.status(200).json_body(json!({ "data": { "graph": { "variant": {
+                "launchSummaries": [{
+                    "id": "l-1",
+                    "createdAt": "2026-01-01T00:00:00Z",
+                    "completedAt": null,
+                    "status": "LAUNCH_COMPLETED",
+                    "subgraphChanges": [],
+                    "buildInput": {
+                        "__typename": "CompositionBuildInput",
+                        "subgraphs": [{ "name": "products", "hash": "h-1", "routingURL": "http://products" }]
+                    }
+                }]
+            } } } }));
+        });
+    }
+
+    fn mock_client(server: &MockServer) -> StudioClient {
+        StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        )
+    }
+
+    #[tokio::test]
+    async fn run_fetches_the_schema_by_its_hash_in_the_launch() {
+        let server = MockServer::start_async().await;
+        mock_server_with_launch(&server);
+        let doc = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("SubgraphFetchAtQuery")
+                .body_includes("\"hash\":\"h-1\"");
+            then.status(200).json_body(json!({ "data": { "graph": {
+                "doc": { "source": "type Query { products: [String] }" }
+            } } }));
+        });
+
+        let response = run(
+            SubgraphFetchAtInput {
+                graph_ref: GraphRef::new("mygraph", Some("current")).unwrap(),
+                subgraph_name: "products".to_string(),
+                at: HistoryPoint::Launch("l-1".to_string()),
+            },
+            &mock_client(&server),
+        )
+        .await
+        .unwrap();
+
+        doc.assert();
+        assert_eq!(response.sdl.contents, "type Query { products: [String] }");
+        assert_eq!(
+            response.sdl.r#type,
+            SdlType::Subgraph {
+                routing_url: Some("http://products".to_string())
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn run_errs_for_subgraphs_missing_from_the_launch() {
+        let server = MockServer::start_async().await;
+        mock_server_with_launch(&server);
+
+        let result = run(
+            SubgraphFetchAtInput {
+                graph_ref: GraphRef::new("mygraph", Some("current")).unwrap(),
+                subgraph_name: "reviews".to_string(),
+                at: HistoryPoint::Launch("l-1".to_string()),
+            },
+            &mock_client(&server),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(RoverClientError::NoSubgraphInGraph { valid_subgraphs, .. }) if valid_subgraphs == vec!["products".to_string()]
+        ));
+    }
+}
@@ -0,0 +1,11 @@
+use rover_studio::types::GraphRef;
+
+use crate::operations::graph::history::HistoryPoint;
+
+/// Input for fetching the SDL a subgraph had at a point in its variant's history
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SubgraphFetchAtInput {
+    pub graph_ref: GraphRef,
+    pub subgraph_name: String,
+    pub at: HistoryPoint,
+}
@@ -0,0 +1,276 @@
+use futures::{StreamExt, TryStreamExt};
+use graphql_client::*;
+
+use self::subgraph_history_diff_query::{
+    DiffCounts, SubgraphHistoryDiffQueryGraphFlatDiff as FlatDiffResult,
+};
+use super::types::*;
+use crate::{
+    blocking::StudioClient,
+    operations::graph::history::{
+        launches, ChangeCounts, LaunchSummary, MAX_CONCURRENT_QUERIES, MAX_PAGES, PAGE_SIZE,
+    },
+    RoverClientError,
+};
+
+type SHA256 = String;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/subgraph/history/diff_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. subgraph_history_diff_query
+pub(crate) struct SubgraphHistoryDiffQuery;
+
+/// Lists the launches that added, changed or removed a subgraph, newest
+/// first, with how much each one changed the subgraph's schema
+pub async fn run(
+    input: SubgraphHistoryInput,
+    client: &StudioClient,
+) -> Result<SubgraphHistoryResponse, RoverClientError> {
+    let limit = usize::try_from(input.limit).unwrap_or_default();
+    // one more change than asked for, so the oldest one listed has something
+    // to be diffed against
+    let changes = subgraph_launches(&input, limit + 1, client).await?;
+    let publishes = futures::stream::iter(changes.iter().enumerate().take(limit))
+        .map(|(index, launch)| {
+            let input = &input;
+            let changes = &changes;
+            async move {
+                let subgraph = launch
+                    .subgraphs
+                    .iter()
+                    .find(|subgraph| subgraph.name == input.subgraph_name);
+                let previous_hash = changes.get(index + 1).and_then(|previous| {
+                    previous
+                        .subgraphs
+                        .iter()
+                        .find(|subgraph| subgraph.name == input.subgraph_name)
+                        .map(|subgraph| subgraph.schema_hash.clone())
+                });
+                let change_counts = match subgraph {
+                    Some(subgraph) => {
+                        let data = client
+                            .post::<SubgraphHistoryDiffQuery>(
+                                subgraph_history_diff_query::Variables {
+                                    graph_id: input.graph_ref.graph_id().clone(),
+                                    old_sdl_hash: previous_hash,
+                                    new_sdl_hash: subgraph.schema_hash.clone(),
+                                },
+                            )
+                            .await?;
+                        build_change_counts(data)
+                    }
+                    None => None,
+                };
+                Ok::<_, RoverClientError>(SubgraphPublish {
+                    launch_id: launch.launch_id.clone(),
+                    created_at: launch.created_at.clone(),
+                    status: launch.status.clone(),
+                    change: launch
+                        .subgraph_changes
+                        .iter()
+                        .find(|change| change.name == input.subgraph_name)
+                        .map(|change| change.change.clone())
+                        .unwrap_or_default(),
+                    schema_hash: subgraph.map(|subgraph| subgraph.schema_hash.clone()),
+                    routing_url: subgraph.map(|subgraph| subgraph.routing_url.clone()),
+                    changes: change_counts,
+                })
+            }
+        })
+        .buffered(MAX_CONCURRENT_QUERIES)
+        .try_collect()
+        .await?;
+    Ok(SubgraphHistoryResponse {
+        graph_ref: input.graph_ref,
+        subgraph_name: input.subgraph_name,
+        publishes,
+    })
+}
+
+/// Up to `count` of the variant's launches that changed the subgraph, newest first
+async fn subgraph_launches(
+    input: &SubgraphHistoryInput,
+    count: usize,
+    client: &StudioClient,
+) -> Result<Vec<LaunchSummary>, RoverClientError> {
+    let mut changes = Vec::new();
+    for page in 0..MAX_PAGES {
+        let launches = launches(&input.graph_ref, PAGE_SIZE, page * PAGE_SIZE, client).await?;
+        let page_len = launches.len() as i64;
+        changes.extend(launches.into_iter().filter(|launch| {
+            launch
+                .subgraph_changes
+                .iter()
+                .any(|change| change.name == input.subgraph_name)
+        }));
+        if changes.len() >= count || page_len < PAGE_SIZE {
+            break;
+        }
+    }
+    changes.truncate(count);
+    Ok(changes)
+}
+
+fn build_change_counts(data: subgraph_history_diff_query::ResponseData) -> Option<ChangeCounts> {
+    match data.graph?.flat_diff {
+        FlatDiffResult::FlatDiff(diff) => {
+            let summary = diff.summary;
+            let kinds: [&DiffCounts; 8] = [
+                &summary.directive,
+                &summary.enum_,
+                &summary.input,
+                &summary.interface,
+                &summary.object,
+                &summary.scalar,
+                &summary.schema,
+                &summary.union_,
+            ];
+            Some(
+                kinds
+                    .into_iter()
+                    .fold(ChangeCounts::default(), |counts, kind| ChangeCounts {
+                        additions: counts.additions + kind.add,
+                        removals: counts.removals + kind.remove,
+                        edits: counts.edits + kind.change,
+                        deprecations: 0,
+                    }),
+            )
+        }
+        // the diff isn't available for schemas GraphOS no longer has
+        FlatDiffResult::NotFoundError(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use rover_studio::types::GraphRef;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn launch_json(id: &str, changes: Value, products_hash: Option<&str>) -> Value {
+        let subgraphs = match products_hash {
+            Some(hash) => {
+                json!([{ "name": "products", "hash": hash, "routingURL": "http://products" }])
+            }
+            None => json!([]),
+        };
+        json!({
+            "id": id,
+            "createdAt": "2026-01-01T00:00:00Z",
+            "completedAt": null,
+            "status": "LAUNCH_COMPLETED",
+            "subgraphChanges": changes,
+            "buildInput": { "__typename": "CompositionBuildInput", "subgraphs": subgraphs }
+        })
+    }
+
+    fn counts_json(add: i64, remove: i64, change: i64) -> Value {
+        json!({ "add": add, "remove": remove, "change": change })
+    }
+
+    fn diff_json(object: Value) -> Value {
+        let none = counts_json(0, 0, 0);
+        json!({ "data": { "graph": { "flatDiff": {
+            "__typename": "FlatDiff",
+            "summary": {
+                "directive": none, "enum": none, "input": none, "interface": none,
+                "object": object, "scalar": none, "schema": none, "union": none
+            }
+        } } } })
+    }
+
+    #[test]
+    fn change_counts_add_up_every_kind_of_type() {
+        let mut data = diff_json(counts_json(2, 1, 3));
+        data["data"]["graph"]["flatDiff"]["summary"]["enum"] = counts_json(1, 0, 0);
+        let data = serde_json::from_value(data["data"].clone()).unwrap();
+        assert_eq!(
+            build_change_counts(data),
+            Some(ChangeCounts {
+                additions: 3,
+                removals: 1,
+                edits: 3,
+                deprecations: 0,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn run_lists_only_launches_that_changed_the_subgraph() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.method(POST).body_includes("GraphHistoryQuery");
+            then.status(200).json_body(json!({ "data": { "graph": { "variant": {
+                "launchSummaries": [
+                    launch_json("l-3", json!([{ "name": "products", "type": "MODIFICATION" }]), Some("h-3")),
+                    launch_json("l-2", json!([{ "name": "reviews", "type": "MODIFICATION" }]), Some("h-1")),
+                    launch_json("l-1", json!([{ "name": "products", "type": "ADDITION" }]), Some("h-1")),
+                ]
+            } } } }));
+        });
+        let diff = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("SubgraphHistoryDiffQuery")
+                .body_includes("\"oldSdlHash\":\"h-1\"")
+                .body_includes("\"newSdlHash\":\"h-3\"");
+            then.status(200).json_body(diff_json(counts_json(1, 0, 0)));
+        });
+
+        let client = StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        );
+        let response = run(
+            SubgraphHistoryInput {
+                graph_ref: GraphRef::new("mygraph", Some("current")).unwrap(),
+                subgraph_name: "products".to_string(),
+                limit: 1,
+            },
+            &client,
+        )
+        .await
+        .unwrap();
+
+        diff.assert();
+        assert_eq!(
+            response.publishes,
+            vec![SubgraphPublish {
+                launch_id: "l-3".to_string(),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                status: "LAUNCH_COMPLETED".to_string(),
+                change: "MODIFICATION".to_string(),
+                schema_hash: Some("h-3".to_string()),
+                routing_url: Some("http://products".to_string()),
+                changes: Some(ChangeCounts {
+                    additions: 1,
+                    removals: 0,
+                    edits: 0,
+                    deprecations: 0,
+                }),
+            }]
+        );
+    }
+}
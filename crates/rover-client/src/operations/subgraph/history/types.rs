@@ -0,0 +1,36 @@
+use rover_studio::types::GraphRef;
+use serde::Serialize;
+
+use crate::operations::graph::history::ChangeCounts;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SubgraphHistoryInput {
+    pub graph_ref: GraphRef,
+    pub subgraph_name: String,
+    /// The most publishes to list, newest first
+    pub limit: i64,
+}
+
+/// A launch that added, changed or removed a subgraph
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct SubgraphPublish {
+    pub launch_id: String,
+    pub created_at: String,
+    /// e.g. `LAUNCH_COMPLETED` or `LAUNCH_FAILED`
+    pub status: String,
+    /// `ADDITION`, `MODIFICATION` or `DELETION`
+    pub change: String,
+    /// The hash of the subgraph schema the launch composed. Deletions don't have one.
+    pub schema_hash: Option<String>,
+    pub routing_url: Option<String>,
+    /// Changes to the subgraph schema since its previous publish. Deprecations
+    /// aren't counted separately.
+    pub changes: Option<ChangeCounts>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct SubgraphHistoryResponse {
+    pub graph_ref: GraphRef,
+    pub subgraph_name: String,
+    pub publishes: Vec<SubgraphPublish>,
+}
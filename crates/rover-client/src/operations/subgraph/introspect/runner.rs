@@ -12,7 +12,9 @@ pub async fn run(
     client: &Client,
 ) -> Result<SubgraphIntrospectResponse, RoverClientError> {
     let retry_layer = if input.should_retry {
-        Some(RetryLayer::new(RetryPolicy::new(input.retry_period)))
+        Some(RetryLayer::new(RetryPolicy::from_config(
+            input.retry.clone(),
+        )))
     } else {
         None
     };
@@ -24,6 +24,9 @@ pub async fn run(
     input: SubgraphListInput,
     client: &StudioClient,
 ) -> Result<SubgraphListResponse, RoverClientError> {
+    if let Some(registry) = client.local_registry() {
+        return registry.list_subgraphs(input);
+    }
     let graph_ref = input.graph_ref.clone();
     let response_data = client.post::<SubgraphListQuery>(input.into()).await?;
     let root_url = response_data.frontend_url_root.clone();
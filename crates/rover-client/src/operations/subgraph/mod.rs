@@ -10,6 +10,12 @@ pub mod check;
 /// "subgraph fetch" command execution
 pub mod fetch;
 
+/// "subgraph fetch --at" command execution
+pub mod fetch_at;
+
+/// "subgraph history" command execution
+pub mod history;
+
 /// "subgraph fetch_all" command execution
 pub mod fetch_all;
 
@@ -30,6 +30,9 @@ pub async fn run(
     input: SubgraphPublishInput,
     client: &StudioClient,
 ) -> Result<SubgraphPublishResponse, RoverClientError> {
+    if let Some(registry) = client.local_registry() {
+        return registry.publish_subgraph(input).await;
+    }
     let graph_ref = input.graph_ref.clone();
     let variables: MutationVariables = input.clone().into();
     // We don't want to implicitly convert non-federated graph to supergraphs.
@@ -112,6 +115,7 @@ fn build_response(publish_response: UpdateResponse) -> SubgraphPublishResponse {
         subgraph_was_created: publish_response.service_was_created,
         subgraph_was_updated: publish_response.service_was_updated,
         build_errors,
+        launch_id: publish_response.launch.map(|launch| launch.id),
         launch_cli_copy: publish_response.launch_cli_copy,
         launch_url: publish_response.launch_url,
     }
@@ -166,6 +170,7 @@ mod tests {
                 supergraph_was_updated: false,
                 subgraph_was_created: true,
                 subgraph_was_updated: true,
+                launch_id: None,
                 launch_url: None,
                 launch_cli_copy: None,
             }
@@ -192,6 +197,7 @@ mod tests {
                 supergraph_was_updated: true,
                 subgraph_was_created: true,
                 subgraph_was_updated: true,
+                launch_id: None,
                 launch_url: None,
                 launch_cli_copy: None,
             }
@@ -229,6 +235,7 @@ mod tests {
                 supergraph_was_updated: false,
                 subgraph_was_created: false,
                 subgraph_was_updated: true,
+                launch_id: None,
                 launch_url: None,
                 launch_cli_copy: None,
             }
@@ -243,6 +250,7 @@ mod tests {
             "didUpdateGateway": true,
             "serviceWasCreated": true,
             "serviceWasUpdated": true,
+            "launch": { "id": "l-1" },
             "launchUrl": "test.com/launchurl",
             "launchCliCopy": "You can monitor this launch in Apollo Studio: test.com/launchurl",
         });
@@ -257,6 +265,7 @@ mod tests {
                 supergraph_was_updated: true,
                 subgraph_was_created: true,
                 subgraph_was_updated: true,
+                launch_id: Some("l-1".to_string()),
                 launch_url: Some("test.com/launchurl".to_string()),
                 launch_cli_copy: Some(
                     "You can monitor this launch in Apollo Studio: test.com/launchurl".to_string()
@@ -285,6 +294,7 @@ mod tests {
                 supergraph_was_updated: false,
                 subgraph_was_created: false,
                 subgraph_was_updated: false,
+                launch_id: None,
                 launch_url: None,
                 launch_cli_copy: None,
             }
@@ -38,6 +38,9 @@ pub struct SubgraphPublishResponse {
     #[serde(skip_serializing)]
     pub build_errors: BuildErrors,
 
+    #[serde(skip_serializing)]
+    pub launch_id: Option<String>,
+
     pub launch_url: Option<String>,
 
     pub launch_cli_copy: Option<String>,
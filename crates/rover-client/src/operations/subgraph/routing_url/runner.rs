@@ -23,6 +23,9 @@ pub async fn run(
     input: SubgraphRoutingUrlInput,
     client: &StudioClient,
 ) -> Result<String, RoverClientError> {
+    if let Some(registry) = client.local_registry() {
+        return registry.routing_url(input);
+    }
     let variables = input.clone().into();
     let response_data = client.post::<SubgraphRoutingUrlQuery>(variables).await?;
     get_routing_url_from_response_data(input, response_data)
@@ -33,6 +33,9 @@ pub async fn run(
     input: SupergraphFetchInput,
     client: &StudioClient,
 ) -> Result<FetchResponse, RoverClientError> {
+    if let Some(registry) = client.local_registry() {
+        return registry.fetch_supergraph(input);
+    }
     let graph_ref = input.graph_ref.clone();
     let response_data = client.post::<SupergraphFetchQuery>(input.into()).await?;
     get_supergraph_sdl_from_response_data(response_data, graph_ref)
@@ -0,0 +1,302 @@
+use graphql_client::*;
+use rover_studio::types::GraphRef;
+
+use super::types::*;
+use crate::{
+    blocking::StudioClient,
+    operations::graph::variant::{self, VariantListInput},
+    RoverClientError,
+};
+
+type Long = i64;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/variant/configure/check_configuration_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. variant_check_configuration_query
+pub(crate) struct VariantCheckConfigurationQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/variant/configure/operations_check_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+pub(crate) struct VariantOperationsCheckMutation;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/variant/configure/time_range_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+pub(crate) struct VariantTimeRangeMutation;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/variant/configure/included_variants_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+pub(crate) struct VariantIncludedVariantsMutation;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/variant/configure/downstream_variants_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+pub(crate) struct VariantDownstreamVariantsMutation;
+
+/// Applies the given check configuration to a variant, then fetches the
+/// variant's whole check configuration. With no settings given, this only
+/// fetches it.
+pub async fn run(
+    graph_ref: GraphRef,
+    input: VariantConfigureInput,
+    client: &StudioClient,
+) -> Result<VariantCheckConfiguration, RoverClientError> {
+    // errors with the variants the graph does have when this one doesn't exist
+    variant::run(
+        VariantListInput {
+            graph_ref: graph_ref.clone(),
+        },
+        client,
+    )
+    .await?;
+
+    let graph_id = graph_ref.graph_id().to_string();
+    let variant = graph_ref.variant().to_string();
+    let not_found = || RoverClientError::GraphNotFound {
+        graph_ref: graph_ref.clone(),
+    };
+
+    if let Some(enabled) = input.operations_checks {
+        client
+            .post::<VariantOperationsCheckMutation>(variant_operations_check_mutation::Variables {
+                graph_id: graph_id.clone(),
+                variant: variant.clone(),
+                enabled,
+            })
+            .await?
+            .graph
+            .and_then(|graph| graph.variant)
+            .ok_or_else(not_found)?;
+    }
+    if input.sets_time_range() {
+        client
+            .post::<VariantTimeRangeMutation>(variant_time_range_mutation::Variables {
+                graph_id: graph_id.clone(),
+                variant: variant.clone(),
+                time_range_seconds: input.time_range_seconds,
+                operation_count_threshold: input.operation_count_threshold,
+                operation_count_threshold_percentage: input.operation_count_threshold_percentage,
+            })
+            .await?
+            .graph
+            .and_then(|graph| graph.variant)
+            .ok_or_else(not_found)?;
+    }
+    if let Some(included_variants) = input.included_variants {
+        client
+            .post::<VariantIncludedVariantsMutation>(
+                variant_included_variants_mutation::Variables {
+                    graph_id: graph_id.clone(),
+                    variant: variant.clone(),
+                    included_variants,
+                },
+            )
+            .await?
+            .graph
+            .and_then(|graph| graph.variant)
+            .ok_or_else(not_found)?;
+    }
+    if let Some(blocking_downstream_variants) = input.blocking_downstream_variants {
+        client
+            .post::<VariantDownstreamVariantsMutation>(
+                variant_downstream_variants_mutation::Variables {
+                    graph_id: graph_id.clone(),
+                    variant: variant.clone(),
+                    blocking_downstream_variants,
+                },
+            )
+            .await?
+            .graph
+            .and_then(|graph| graph.variant)
+            .ok_or_else(not_found)?;
+    }
+
+    let data = client
+        .post::<VariantCheckConfigurationQuery>(variant_check_configuration_query::Variables {
+            graph_id,
+            variant,
+        })
+        .await?;
+    build_response(data, graph_ref)
+}
+
+fn build_response(
+    data: variant_check_configuration_query::ResponseData,
+    graph_ref: GraphRef,
+) -> Result<VariantCheckConfiguration, RoverClientError> {
+    let config = data
+        .graph
+        .and_then(|graph| graph.variant)
+        .ok_or_else(|| RoverClientError::GraphNotFound {
+            graph_ref: graph_ref.clone(),
+        })?
+        .check_configuration;
+    Ok(VariantCheckConfiguration {
+        graph_ref,
+        operations_checks_enabled: config.operations_checks_enabled,
+        time_range_seconds: config.time_range_config.time_range_seconds,
+        operation_count_threshold: config.time_range_config.operation_count_threshold,
+        operation_count_threshold_percentage: config
+            .time_range_config
+            .operation_count_threshold_percentage,
+        time_range_uses_graph_settings: config.time_range_config.use_graph_settings,
+        included_variants: config.included_variants_config.included_variants,
+        included_variants_use_graph_settings: config.included_variants_config.use_graph_settings,
+        blocking_downstream_variants: config
+            .downstream_variants_config
+            .blocking_downstream_variants,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use houston::{Credential, CredentialOrigin};
+    use httpmock::prelude::*;
+    use reqwest::Client as ReqwestClient;
+    use serde_json::json;
+
+    use super::*;
+
+    fn client(server: &MockServer) -> StudioClient {
+        StudioClient::new(
+            Credential {
+                api_key: "test".to_string(),
+                origin: CredentialOrigin::EnvVar,
+                expires_at: None,
+            },
+            &server.url("/"),
+            "test-version",
+            false,
+            ReqwestClient::new(),
+            Duration::from_secs(1),
+        )
+    }
+
+    fn mock_variant_and_config(server: &MockServer) {
+        server.mock(|when, then| {
+            when.method(POST).body_includes("VariantListQuery");
+            then.status(200).json_body(json!({ "data": {
+                "frontendUrlRoot": "https://studio.apollographql.com",
+                "graph": { "variants": [{ "name": "current" }] }
+            } }));
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("VariantCheckConfigurationQuery");
+            then.status(200)
+                .json_body(json!({ "data": { "graph": { "variant": {
+                "checkConfiguration": {
+                    "operationsChecksEnabled": true,
+                    "timeRangeConfig": {
+                        "timeRangeSeconds": 86400,
+                        "operationCountThreshold": 5,
+                        "operationCountThresholdPercentage": null,
+                        "useGraphSettings": false
+                    },
+                    "includedVariantsConfig": {
+                        "includedVariants": ["current", "staging"],
+                        "useGraphSettings": false
+                    },
+                    "downstreamVariantsConfig": { "blockingDownstreamVariants": ["public"] }
+                }
+            } } } }));
+        });
+    }
+
+    fn graph_ref() -> GraphRef {
+        GraphRef::new("mygraph", Some("current")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn run_only_sends_the_settings_given() {
+        let server = MockServer::start_async().await;
+        mock_variant_and_config(&server);
+        let time_range = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("VariantTimeRangeMutation")
+                .body_includes("\"time_range_seconds\":86400")
+                .body_includes("\"operation_count_threshold\":null");
+            then.status(200)
+                .json_body(json!({ "data": { "graph": { "variant": {
+                "updateCheckConfigurationTimeRange": { "id": "c-1" }
+            } } } }));
+        });
+        let operations_check = server.mock(|when, then| {
+            when.method(POST)
+                .body_includes("VariantOperationsCheckMutation");
+            then.status(500);
+        });
+
+        let config = run(
+            graph_ref(),
+            VariantConfigureInput {
+                time_range_seconds: Some(86400),
+                ..Default::default()
+            },
+            &client(&server),
+        )
+        .await
+        .unwrap();
+
+        time_range.assert();
+        operations_check.assert_calls(0);
+        assert_eq!(config.time_range_seconds, Some(86400));
+        assert_eq!(
+            config.blocking_downstream_variants,
+            vec!["public".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn run_errs_for_missing_variants() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.method(POST).body_includes("VariantListQuery");
+            then.status(200).json_body(json!({ "data": {
+                "frontendUrlRoot": "https://studio.apollographql.com",
+                "graph": { "variants": [{ "name": "staging" }] }
+            } }));
+        });
+
+        let result = run(
+            graph_ref(),
+            VariantConfigureInput::default(),
+            &client(&server),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(RoverClientError::NoSchemaForVariant { .. })
+        ));
+    }
+}
@@ -0,0 +1,47 @@
+use rover_studio::types::GraphRef;
+use serde::Serialize;
+
+/// Check configuration to set on a variant. Settings left as `None` are unchanged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VariantConfigureInput {
+    pub operations_checks: Option<bool>,
+    /// How far back operation checks look for operations, in seconds
+    pub time_range_seconds: Option<i64>,
+    /// Operations seen fewer times than this in the time range are ignored
+    pub operation_count_threshold: Option<i64>,
+    /// Operations making up less than this percentage of traffic are ignored
+    pub operation_count_threshold_percentage: Option<f64>,
+    /// Variants whose operations are checked against this one
+    pub included_variants: Option<Vec<String>>,
+    /// Contract variants whose checks have to pass for this variant's checks to pass
+    pub blocking_downstream_variants: Option<Vec<String>>,
+}
+
+impl VariantConfigureInput {
+    /// Whether any setting was given
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub(crate) const fn sets_time_range(&self) -> bool {
+        self.time_range_seconds.is_some()
+            || self.operation_count_threshold.is_some()
+            || self.operation_count_threshold_percentage.is_some()
+    }
+}
+
+/// A variant's check configuration, after any changes were made
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct VariantCheckConfiguration {
+    pub graph_ref: GraphRef,
+    pub operations_checks_enabled: bool,
+    pub time_range_seconds: Option<i64>,
+    pub operation_count_threshold: Option<i64>,
+    pub operation_count_threshold_percentage: Option<f64>,
+    /// Whether the time range and thresholds come from the graph's settings
+    pub time_range_uses_graph_settings: bool,
+    pub included_variants: Option<Vec<String>>,
+    /// Whether the included variants come from the graph's settings
+    pub included_variants_use_graph_settings: bool,
+    pub blocking_downstream_variants: Vec<String>,
+}
@@ -0,0 +1,140 @@
+use graphql_client::*;
+
+use super::types::*;
+use crate::{blocking::StudioClient, RoverClientError};
+
+type Timestamp = String;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/variant/list/list_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. variant_details_list_query
+pub(crate) struct VariantDetailsListQuery;
+
+/// Lists the variants of a graph, with their contract and launch details
+pub async fn run(
+    input: VariantListInput,
+    client: &StudioClient,
+) -> Result<VariantListResponse, RoverClientError> {
+    let graph_id = input.graph_id.clone();
+    let include_proposals = input.include_proposals;
+    let data = client.post::<VariantDetailsListQuery>(input.into()).await?;
+    build_response(data, graph_id, include_proposals)
+}
+
+fn build_response(
+    data: variant_details_list_query::ResponseData,
+    graph_id: String,
+    include_proposals: bool,
+) -> Result<VariantListResponse, RoverClientError> {
+    let graph = data
+        .graph
+        .ok_or_else(|| RoverClientError::GraphIdNotFound {
+            graph_id: graph_id.clone(),
+        })?;
+    let variants = graph
+        .variants
+        .into_iter()
+        .map(|variant| VariantDetails {
+            name: variant.name,
+            created_at: variant.created_at,
+            federation_version: variant.federation_version,
+            url: variant.url,
+            source_variant: variant.source_variant.map(|source| source.name),
+            contract_filters: variant
+                .contract_filter_config
+                .map(|filters| ContractFilters {
+                    include: filters.include,
+                    exclude: filters.exclude,
+                }),
+            is_proposal: variant.is_proposal.unwrap_or_default(),
+            last_launched_at: variant.latest_launch.and_then(|launch| launch.completed_at),
+        })
+        .filter(|variant| include_proposals || !variant.is_proposal)
+        .collect();
+    Ok(VariantListResponse { graph_id, variants })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn data() -> variant_details_list_query::ResponseData {
+        serde_json::from_value(json!({ "graph": { "variants": [
+            {
+                "name": "current",
+                "createdAt": "2026-01-02T03:04:05Z",
+                "isProposal": false,
+                "federationVersion": "2.9",
+                "url": "https://example.com/graphql",
+                "sourceVariant": null,
+                "contractFilterConfig": null,
+                "latestLaunch": { "completedAt": "2026-01-03T03:04:05Z" }
+            },
+            {
+                "name": "public",
+                "createdAt": "2026-01-02T03:04:05Z",
+                "isProposal": false,
+                "federationVersion": "2.9",
+                "url": null,
+                "sourceVariant": { "name": "current" },
+                "contractFilterConfig": { "include": ["public"], "exclude": [] },
+                "latestLaunch": null
+            },
+            {
+                "name": "p-1-variant",
+                "createdAt": "2026-01-02T03:04:05Z",
+                "isProposal": true,
+                "federationVersion": null,
+                "url": null,
+                "sourceVariant": null,
+                "contractFilterConfig": null,
+                "latestLaunch": null
+            }
+        ] } }))
+        .unwrap()
+    }
+
+    #[test]
+    fn build_response_skips_proposal_variants() {
+        let response = build_response(data(), "mygraph".to_string(), false).unwrap();
+        let names: Vec<_> = response.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["current", "public"]);
+        assert_eq!(
+            response.variants[1].source_variant.as_deref(),
+            Some("current")
+        );
+        assert_eq!(
+            response.variants[1].contract_filters,
+            Some(ContractFilters {
+                include: vec!["public".to_string()],
+                exclude: Vec::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn build_response_can_include_proposal_variants() {
+        let response = build_response(data(), "mygraph".to_string(), true).unwrap();
+        assert_eq!(response.variants.len(), 3);
+    }
+
+    #[test]
+    fn build_response_errs_for_missing_graphs() {
+        let data = serde_json::from_value(json!({ "graph": null })).unwrap();
+        assert!(matches!(
+            build_response(data, "mygraph".to_string(), false),
+            Err(RoverClientError::GraphIdNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,46 @@
+use serde::Serialize;
+
+use super::runner::variant_details_list_query;
+
+pub(crate) type QueryVariables = variant_details_list_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VariantListInput {
+    pub graph_id: String,
+    /// Whether to list the variants backing schema proposals too
+    pub include_proposals: bool,
+}
+
+impl From<VariantListInput> for QueryVariables {
+    fn from(input: VariantListInput) -> Self {
+        Self {
+            graph_id: input.graph_id,
+        }
+    }
+}
+
+/// The tags a contract variant's schema is filtered by
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct ContractFilters {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct VariantDetails {
+    pub name: String,
+    pub created_at: String,
+    pub federation_version: Option<String>,
+    pub url: Option<String>,
+    /// The variant a contract variant is built from
+    pub source_variant: Option<String>,
+    pub contract_filters: Option<ContractFilters>,
+    pub is_proposal: bool,
+    pub last_launched_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct VariantListResponse {
+    pub graph_id: String,
+    pub variants: Vec<VariantDetails>,
+}
@@ -0,0 +1,5 @@
+/// "variant list" command execution
+pub mod list;
+
+/// "variant configure" command execution
+pub mod configure;
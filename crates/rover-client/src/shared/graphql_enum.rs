@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+/// The GraphQL value of an enum generated for a query, e.g. `LAUNCH_COMPLETED`
+pub(crate) fn graphql_enum_value(value: impl Serialize) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
@@ -3,16 +3,18 @@ mod check_response;
 pub(crate) mod check_workflow_poll;
 mod fetch_response;
 mod git_context;
+mod graphql_enum;
 mod lint_response;
 
 pub(crate) use async_check_response::map_check_submission_error;
 pub use async_check_response::CheckRequestSuccessResult;
 pub use check_response::{
     ChangeSeverity, CheckConfig, CheckTaskStatus, CheckWorkflowResponse, CustomCheckResponse,
-    DownstreamCheckResponse, LintCheckResponse, OperationCheckResponse, ProposalsCheckResponse,
-    ProposalsCheckSeverityLevel, ProposalsCoverage, RelatedProposal, SchemaChange,
-    ValidationPeriod, Violation,
+    DownstreamCheckResponse, LintCheckResponse, OperationCheckResponse, Period,
+    ProposalsCheckResponse, ProposalsCheckSeverityLevel, ProposalsCoverage, RelatedProposal,
+    SchemaChange, ValidationPeriod, Violation,
 };
 pub use fetch_response::{FetchResponse, Sdl, SdlType};
 pub use git_context::GitContext;
+pub(crate) use graphql_enum::graphql_enum_value;
 pub use lint_response::{Diagnostic, LintResponse};
@@ -14,13 +14,15 @@
 
 use std::{convert::Infallible, fmt, future::Future, pin::Pin, str::FromStr};
 
+use apollo_parser::{cst, Parser};
 use bytes::Bytes;
 use graphql_client::GraphQLQuery;
 use http::{uri::InvalidUri, HeaderValue, Method, StatusCode, Uri};
 use http_body_util::Full;
-use rover_http::{BodyExt, HttpRequest, HttpResponse};
+use rover_http::{retry::NonIdempotent, BodyExt, HttpRequest, HttpResponse};
 use serde::{Deserialize, Serialize};
 use tower::{Layer, Service};
+use tracing::Instrument;
 use url::Url;
 
 const JSON_CONTENT_TYPE: &str = "application/json";
@@ -120,6 +122,39 @@ pub enum GraphQLServiceError<T: Send + Sync + fmt::Debug> {
     Infallible(#[from] Infallible),
 }
 
+/// Whether the operation a GraphQL document runs is a mutation, and so may
+/// not be safe to send twice. `operation_name` picks the operation out of a
+/// document that defines several; otherwise the first one is used.
+///
+/// A document that doesn't parse, or doesn't define the operation, is treated
+/// as a mutation so that it's never retried.
+pub fn is_mutation(query: &str, operation_name: Option<&str>) -> bool {
+    let tree = Parser::new(query).parse();
+    if tree.errors().len() > 0 {
+        return true;
+    }
+    let operation = tree
+        .document()
+        .definitions()
+        .filter_map(|definition| match definition {
+            cst::Definition::OperationDefinition(operation) => Some(operation),
+            _ => None,
+        })
+        .find(|operation| match operation_name {
+            Some(operation_name) => operation
+                .name()
+                .is_some_and(|name| name.text() == operation_name),
+            None => true,
+        });
+    match operation {
+        // an operation without a type is a query, e.g. `{ me { id } }`
+        Some(operation) => operation
+            .operation_type()
+            .is_some_and(|operation_type| operation_type.mutation_token().is_some()),
+        None => true,
+    }
+}
+
 /// Wrapper around [`GraphQLQuery::Variables`]
 /// This type requires something more concrete around it to be used appropriately
 pub struct GraphQLRequest<Q: GraphQLQuery> {
@@ -220,9 +255,15 @@ where
         let mut client = std::mem::replace(&mut self.inner, cloned);
 
         let url = self.endpoint.clone();
+        let body = Q::build_query(req.into_inner());
+        let non_idempotent = is_mutation(body.query, Some(body.operation_name));
+        let span = tracing::info_span!(
+            "GraphQL operation",
+            otel.kind = "client",
+            graphql.operation.name = body.operation_name,
+        );
 
         let fut = async move {
-            let body = Q::build_query(req.into_inner());
             let body_bytes =
                 Bytes::from(serde_json::to_vec(&body).map_err(GraphQLServiceError::Serialization)?);
             let req = http::Request::builder();
@@ -231,7 +272,7 @@ where
             } else {
                 req
             };
-            let req = req
+            let mut req = req
                 .method(Method::POST)
                 .header(
                     http::header::CONTENT_TYPE,
@@ -239,6 +280,9 @@ where
                 )
                 .body(Full::new(body_bytes))
                 .map_err(GraphQLServiceError::Http)?;
+            if non_idempotent {
+                req.extensions_mut().insert(NonIdempotent);
+            }
             let resp = client
                 .call(req)
                 .await
@@ -280,7 +324,7 @@ where
                     .ok_or_else(|| GraphQLServiceError::NoData(Vec::default()))
             }
         };
-        Box::pin(fut)
+        Box::pin(fut.instrument(span))
     }
 }
 
@@ -302,7 +346,9 @@ mod tests {
     use tower_test::mock;
     use url::Url;
 
-    use super::{GraphQLLayer, GraphQLRequest, GraphQLServiceError, JSON_CONTENT_TYPE};
+    use super::{
+        is_mutation, GraphQLLayer, GraphQLRequest, GraphQLServiceError, JSON_CONTENT_TYPE,
+    };
 
     struct TestQuery {}
 
@@ -506,4 +552,36 @@ mod tests {
             _ => false,
         });
     }
+
+    #[rstest]
+    #[case::query("query Me { me { id } }", None, false)]
+    #[case::anonymous_query("{ me { id } }", None, false)]
+    #[case::mutation("mutation Publish($sdl: String!) { publish(sdl: $sdl) }", None, true)]
+    #[case::after_a_fragment(
+        "fragment F on Mutation { ok } mutation Publish { ...F }",
+        Some("Publish"),
+        true
+    )]
+    #[case::named_among_several("query Check { ok } mutation Publish { ok }", Some("Check"), false)]
+    #[case::keyword_inside_a_string(
+        "query Search { search(text: \"mutation { x }\") { id } }",
+        None,
+        false
+    )]
+    #[case::keyword_inside_a_comment("# mutation\nquery Me { me { id } }", None, false)]
+    #[case::with_a_directive("mutation @deprecated Publish { ok }", Some("Publish"), true)]
+    #[case::with_a_directive_after_the_name(
+        "query Me @cached { me { id } } mutation Publish @audit { ok }",
+        Some("Me"),
+        false
+    )]
+    #[case::unparseable("query Me { me { id }", None, true)]
+    #[case::unknown_operation_name("query Me { me { id } }", Some("Publish"), true)]
+    fn it_detects_mutations(
+        #[case] query: &str,
+        #[case] operation_name: Option<&str>,
+        #[case] expected: bool,
+    ) {
+        assert_that!(is_mutation(query, operation_name)).is_equal_to(expected);
+    }
 }
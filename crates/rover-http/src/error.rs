@@ -44,6 +44,12 @@ pub enum HttpServiceError {
     /// An unexpected error
     #[error("Unexpected HTTP error. {}", .0)]
     Unexpected(Box<dyn std::error::Error + Send + Sync + 'static>),
+    /// The client's trust, identity or proxy settings couldn't be applied
+    #[error("Invalid network configuration: {}", .0)]
+    Network(#[from] crate::NetworkConfigError),
+    /// A request couldn't be recorded to or replayed from a cassette
+    #[error("HTTP cassette error: {}", .0)]
+    Cassette(#[from] crate::cassette::CassetteError),
     /// An infallible body error — this variant can never be constructed
     #[error("Infallible body error")]
     InfallibleBody(#[from] Infallible),
@@ -12,16 +12,20 @@ pub use http_body_util::{BodyExt, Empty, Full};
 use tower::{timeout::error::Elapsed, util::BoxCloneService};
 
 pub mod body;
+pub mod cassette;
 mod error;
 pub mod error_on_status;
 pub mod extend_headers;
+pub mod network;
 mod reqwest;
 pub mod retry;
 #[cfg(any(test, feature = "test"))]
 pub mod test;
 pub mod timeout;
+pub mod trace_context;
 
 pub use error::HttpServiceError;
+pub use network::{NetworkConfig, NetworkConfigError};
 pub use reqwest::{ReqwestService, ReqwestServiceFactory};
 
 /// Ease-of-use synonym for the request type this crate operates on
@@ -49,6 +53,7 @@ pub struct HttpServiceConfig {
     accept_invalid_certificates: Option<bool>,
     accept_invalid_hostnames: Option<bool>,
     timeout: Option<Duration>,
+    network: Option<NetworkConfig>,
 }
 
 impl From<Box<dyn std::error::Error + Send + Sync>> for HttpServiceError {
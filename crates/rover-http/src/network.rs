@@ -0,0 +1,226 @@
+//! Trust, client identity and proxy settings for the HTTP client
+
+use std::path::{Path, PathBuf};
+
+use buildstructor::Builder;
+use derive_getters::Getters;
+use reqwest::{Certificate, ClientBuilder, Identity, NoProxy, Proxy};
+
+/// Settings for reaching servers on private networks: extra root
+/// certificates to trust, a client certificate to present for mutual TLS,
+/// and an explicit proxy.
+///
+/// Everything here is additive. The platform's root certificates are still
+/// trusted alongside `ca_certificate`, and without an explicit `proxy` the
+/// usual `HTTPS_PROXY`/`HTTP_PROXY`/`NO_PROXY` environment variables apply.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Builder, Getters)]
+pub struct NetworkConfig {
+    /// A PEM file of one or more root certificates to trust, in addition to
+    /// the platform's.
+    ca_certificate: Option<PathBuf>,
+    /// A PEM file containing the client certificate (chain) to present to
+    /// servers that require mutual TLS. It may also contain the private key,
+    /// in which case `client_key` can be left out.
+    client_certificate: Option<PathBuf>,
+    /// A PEM file containing the private key for `client_certificate`.
+    client_key: Option<PathBuf>,
+    /// The proxy to send every request through, e.g. `http://proxy.corp:3128`.
+    proxy: Option<String>,
+    /// A comma-separated list of hosts, domains and IP ranges to reach
+    /// directly rather than through `proxy`. Defaults to the `NO_PROXY`
+    /// environment variable.
+    no_proxy: Option<String>,
+}
+
+/// Errors applying a [`NetworkConfig`] to a client
+#[derive(thiserror::Error, Debug)]
+pub enum NetworkConfigError {
+    /// A certificate or key file couldn't be read
+    #[error("could not read {}", .path.display())]
+    Read {
+        /// The file that couldn't be read
+        path: PathBuf,
+        /// Why it couldn't be read
+        source: std::io::Error,
+    },
+    /// The CA bundle doesn't contain valid PEM certificates
+    #[error("{} is not a valid PEM certificate bundle", .path.display())]
+    CaCertificate {
+        /// The CA bundle
+        path: PathBuf,
+        /// Why it couldn't be parsed
+        source: reqwest::Error,
+    },
+    /// The CA bundle doesn't contain any certificates
+    #[error("{} does not contain any PEM certificates", .path.display())]
+    NoCaCertificates {
+        /// The CA bundle
+        path: PathBuf,
+    },
+    /// The client certificate and key don't make a valid identity
+    #[error("{} is not a valid PEM client certificate and private key", .path.display())]
+    ClientCertificate {
+        /// The client certificate
+        path: PathBuf,
+        /// Why it couldn't be parsed
+        source: reqwest::Error,
+    },
+    /// A client key was given without the certificate it belongs to
+    #[error("a client key was provided without a client certificate")]
+    ClientKeyWithoutCertificate,
+    /// The proxy isn't a valid URL
+    #[error("{url} is not a valid proxy URL")]
+    Proxy {
+        /// The proxy as it was given
+        url: String,
+        /// Why it couldn't be used
+        source: reqwest::Error,
+    },
+}
+
+impl NetworkConfig {
+    /// Applies these settings to a [`reqwest::ClientBuilder`]
+    pub fn apply(&self, mut builder: ClientBuilder) -> Result<ClientBuilder, NetworkConfigError> {
+        if let Some(path) = &self.ca_certificate {
+            let certificates = Certificate::from_pem_bundle(&read(path)?).map_err(|source| {
+                NetworkConfigError::CaCertificate {
+                    path: path.clone(),
+                    source,
+                }
+            })?;
+            if certificates.is_empty() {
+                return Err(NetworkConfigError::NoCaCertificates { path: path.clone() });
+            }
+            builder = builder.tls_certs_merge(certificates);
+        }
+
+        match (&self.client_certificate, &self.client_key) {
+            (Some(certificate), key) => {
+                let mut pem = read(certificate)?;
+                if let Some(key) = key {
+                    pem.push(b'\n');
+                    pem.extend(read(key)?);
+                }
+                let identity = Identity::from_pem(&pem).map_err(|source| {
+                    NetworkConfigError::ClientCertificate {
+                        path: certificate.clone(),
+                        source,
+                    }
+                })?;
+                builder = builder.identity(identity);
+            }
+            (None, Some(_)) => return Err(NetworkConfigError::ClientKeyWithoutCertificate),
+            (None, None) => {}
+        }
+
+        if let Some(url) = &self.proxy {
+            let no_proxy = match &self.no_proxy {
+                Some(no_proxy) => NoProxy::from_string(no_proxy),
+                None => NoProxy::from_env(),
+            };
+            let proxy = Proxy::all(url.as_str())
+                .map_err(|source| NetworkConfigError::Proxy {
+                    url: url.clone(),
+                    source,
+                })?
+                .no_proxy(no_proxy);
+            builder = builder.proxy(proxy);
+        }
+
+        Ok(builder)
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, NetworkConfigError> {
+    std::fs::read(path).map_err(|source| NetworkConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::{NetworkConfig, NetworkConfigError};
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join(name)
+    }
+
+    fn apply(config: NetworkConfig) -> Result<reqwest::Client, NetworkConfigError> {
+        Ok(config.apply(reqwest::Client::builder())?.build().unwrap())
+    }
+
+    #[rstest]
+    #[case::nothing(NetworkConfig::default())]
+    #[case::ca_certificate(NetworkConfig::builder().ca_certificate(fixture("ca.pem")).build())]
+    #[case::client_certificate_and_key(
+        NetworkConfig::builder()
+            .client_certificate(fixture("client.pem"))
+            .client_key(fixture("client.key"))
+            .build()
+    )]
+    #[case::proxy(
+        NetworkConfig::builder()
+            .proxy("http://proxy.example.com:3128")
+            .no_proxy("localhost,.internal")
+            .build()
+    )]
+    fn it_applies_valid_settings(#[case] config: NetworkConfig) {
+        assert_that!(apply(config)).is_ok();
+    }
+
+    #[test]
+    fn it_reports_a_missing_ca_certificate() {
+        let config = NetworkConfig::builder()
+            .ca_certificate(fixture("missing.pem"))
+            .build();
+        assert_that!(apply(config)).is_err().matches(
+            |e| matches!(e, NetworkConfigError::Read { path, .. } if path.ends_with("missing.pem")),
+        );
+    }
+
+    #[test]
+    fn it_reports_a_ca_bundle_without_certificates() {
+        let config = NetworkConfig::builder()
+            .ca_certificate(fixture("client.key"))
+            .build();
+        assert_that!(apply(config))
+            .is_err()
+            .matches(|e| matches!(e, NetworkConfigError::NoCaCertificates { .. }));
+    }
+
+    #[test]
+    fn it_reports_a_client_certificate_without_its_key() {
+        let config = NetworkConfig::builder()
+            .client_certificate(fixture("client.pem"))
+            .build();
+        assert_that!(apply(config))
+            .is_err()
+            .matches(|e| matches!(e, NetworkConfigError::ClientCertificate { .. }));
+    }
+
+    #[test]
+    fn it_reports_a_client_key_without_its_certificate() {
+        let config = NetworkConfig::builder()
+            .client_key(fixture("client.key"))
+            .build();
+        assert_that!(apply(config))
+            .is_err()
+            .matches(|e| matches!(e, NetworkConfigError::ClientKeyWithoutCertificate));
+    }
+
+    #[test]
+    fn it_reports_an_invalid_proxy() {
+        let config = NetworkConfig::builder().proxy("not a url").build();
+        assert_that!(apply(config))
+            .is_err()
+            .matches(|e| matches!(e, NetworkConfigError::Proxy { url, .. } if url == "not a url"));
+    }
+}
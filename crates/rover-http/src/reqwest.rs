@@ -9,10 +9,11 @@ use futures::Future;
 use http_body_util::{BodyExt, Full};
 use reqwest::ClientBuilder;
 use tower::{Service, ServiceExt};
+use tracing::Instrument;
 
 use crate::{
-    body::body_to_bytes, HttpRequest, HttpResponse, HttpService, HttpServiceConfig,
-    HttpServiceError, HttpServiceFactory,
+    body::body_to_bytes, cassette::Cassette, trace_context::inject_trace_context, HttpRequest,
+    HttpResponse, HttpService, HttpServiceConfig, HttpServiceError, HttpServiceFactory,
 };
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(90);
@@ -30,8 +31,7 @@ impl HttpServiceFactory for ReqwestServiceFactory {
         let service = ReqwestService::builder()
             .config(self.config.clone())
             .client(self.client.clone())
-            .build()
-            .map_err(HttpServiceError::from)?;
+            .build()?;
         Ok(service.boxed_clone())
     }
 }
@@ -41,28 +41,42 @@ impl HttpServiceFactory for ReqwestServiceFactory {
 pub struct ReqwestService {
     client: reqwest::Client,
     timeout: Duration,
+    cassette: Option<Cassette>,
 }
 
 #[buildstructor]
 impl ReqwestService {
     /// Constructs a new [`ReqwestService`]
     #[builder]
+    #[allow(clippy::result_large_err)]
     pub fn new(
         config: Option<HttpServiceConfig>,
         client: Option<reqwest::Client>,
-    ) -> Result<ReqwestService, reqwest::Error> {
+    ) -> Result<ReqwestService, HttpServiceError> {
         let config = config.unwrap_or_default();
         let client = match client {
             Some(client) => client,
-            None => ClientBuilder::new()
-                .danger_accept_invalid_certs(config.accept_invalid_certificates.unwrap_or_default())
-                .danger_accept_invalid_hostnames(
-                    config.accept_invalid_hostnames.unwrap_or_default(),
-                )
-                .build()?,
+            None => {
+                let builder = ClientBuilder::new()
+                    .danger_accept_invalid_certs(
+                        config.accept_invalid_certificates.unwrap_or_default(),
+                    )
+                    .danger_accept_invalid_hostnames(
+                        config.accept_invalid_hostnames.unwrap_or_default(),
+                    );
+                match &config.network {
+                    Some(network) => network.apply(builder)?,
+                    None => builder,
+                }
+                .build()?
+            }
         };
         let timeout = config.timeout().unwrap_or(DEFAULT_TIMEOUT);
-        Ok(ReqwestService { client, timeout })
+        Ok(ReqwestService {
+            client,
+            timeout,
+            cassette: Cassette::installed(),
+        })
     }
 }
 
@@ -92,43 +106,68 @@ impl Service<HttpRequest> for ReqwestService {
         Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, req: HttpRequest) -> Self::Future {
+    fn call(&mut self, mut req: HttpRequest) -> Self::Future {
+        // only the host and path are recorded, as the query string or userinfo
+        // can carry credentials.
+        let span = tracing::info_span!(
+            "HTTP request",
+            otel.kind = "client",
+            http.request.method = %req.method(),
+            server.address = req.uri().host().unwrap_or_default(),
+            url.path = req.uri().path(),
+            http.response.status_code = tracing::field::Empty,
+        );
+        span.in_scope(|| inject_trace_context(req.headers_mut()));
         let client = self.client.clone();
         let timeout = self.timeout;
-        let fut = async move {
-            // `Request::try_from` doesn't do any processing that reqwest runs at builder-construction time.
-            // We instead go through its builder interface which retains all that internal logic.
-            let (parts, body) = req.into_parts();
-
-            let bytes = body
-                .collect()
-                .await
-                .map_err(|err| HttpServiceError::Body(Box::new(err)))?
-                .to_bytes();
-
-            let mut builder = client
-                .request(parts.method, parts.uri.to_string())
-                .version(parts.version);
-
-            for (name, value) in parts.headers.iter() {
-                builder = builder.header(name, value);
-            }
+        match self.cassette.clone() {
+            Some(cassette) => Box::pin(
+                async move { cassette.handle(req, |req| send(client, timeout, req)).await }
+                    .instrument(span),
+            ),
+            None => Box::pin(send(client, timeout, req).instrument(span)),
+        }
+    }
+}
 
-            let request = builder.body(reqwest::Body::from(bytes)).build()?;
+/// Sends `req` with `client`, giving up after `timeout`
+#[allow(clippy::result_large_err)]
+async fn send(
+    client: reqwest::Client,
+    timeout: Duration,
+    req: HttpRequest,
+) -> Result<HttpResponse, HttpServiceError> {
+    // `Request::try_from` doesn't do any processing that reqwest runs at builder-construction time.
+    // We instead go through its builder interface which retains all that internal logic.
+    let (parts, body) = req.into_parts();
+
+    let bytes = body
+        .collect()
+        .await
+        .map_err(|err| HttpServiceError::Body(Box::new(err)))?
+        .to_bytes();
+
+    let mut builder = client
+        .request(parts.method, parts.uri.to_string())
+        .version(parts.version);
+
+    for (name, value) in parts.headers.iter() {
+        builder = builder.header(name, value);
+    }
 
-            let response = match tokio::time::timeout(timeout, client.execute(request)).await {
-                Ok(result) => result?,
-                Err(_) => return Err(HttpServiceError::TimedOut),
-            };
+    let request = builder.body(reqwest::Body::from(bytes)).build()?;
 
-            let mut resp = http::Response::from(response);
-            let bytes = body_to_bytes(&mut resp)
-                .await
-                .map_err(|err| HttpServiceError::Body(Box::new(err)))?;
-            Ok(resp.map(|_| Full::new(bytes)))
-        };
-        Box::pin(fut)
-    }
+    let response = match tokio::time::timeout(timeout, client.execute(request)).await {
+        Ok(result) => result?,
+        Err(_) => return Err(HttpServiceError::TimedOut),
+    };
+
+    tracing::Span::current().record("http.response.status_code", response.status().as_u16());
+    let mut resp = http::Response::from(response);
+    let bytes = body_to_bytes(&mut resp)
+        .await
+        .map_err(|err| HttpServiceError::Body(Box::new(err)))?;
+    Ok(resp.map(|_| Full::new(bytes)))
 }
 
 impl From<ReqwestService> for HttpService {
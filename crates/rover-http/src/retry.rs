@@ -2,11 +2,13 @@
 
 use std::{
     cell::OnceCell,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
-use http::StatusCode;
-use tap::TapFallible;
+use buildstructor::buildstructor;
+use derive_getters::Getters;
+use http::{header::RETRY_AFTER, HeaderMap, StatusCode};
+use tokio::time::Sleep;
 use tower::{
     retry::{
         backoff::{Backoff, ExponentialBackoff, ExponentialBackoffMaker, MakeBackoff},
@@ -14,40 +16,191 @@ use tower::{
     },
     util::rng::HasherRng,
 };
+use tracing::{instrument::Instrumented, Instrument};
 
 use super::HttpServiceError;
 use crate::{HttpRequest, HttpResponse};
 
+/// The delay before the first retry, unless configured otherwise
+pub const DEFAULT_MIN_DELAY: Duration = Duration::from_millis(500);
+/// The longest delay between two attempts, unless configured otherwise
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(60);
+/// The ratio of each backoff that may be randomly added to it when jitter is on
+const JITTER: f64 = 0.99;
+
+/// Marks a request that isn't safe to send twice, such as a GraphQL mutation.
+///
+/// Insert it into the request's extensions. Unless the [`RetryConfig`] allows
+/// retrying mutations, such a request is only retried when it's certain the
+/// server didn't act on it: the connection couldn't be made, or the server
+/// turned it away with `429 Too Many Requests` or `503 Service Unavailable`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NonIdempotent;
+
+/// How a [`RetryPolicy`] retries: how many attempts it makes, how long it
+/// waits between them, and how long it keeps trying overall.
+#[derive(Clone, Debug, PartialEq, Eq, Getters)]
+pub struct RetryConfig {
+    /// How long after the first attempt a request may still be retried
+    max_elapsed_time: Duration,
+    /// The most attempts made in total, counting the first; unlimited (within
+    /// `max_elapsed_time`) when unset
+    max_attempts: Option<u32>,
+    /// The delay before the first retry, doubling with each one after it
+    min_delay: Duration,
+    /// The longest delay between two attempts
+    max_delay: Duration,
+    /// Whether each delay is randomly stretched, so that many clients don't
+    /// retry in lockstep
+    jitter: bool,
+    /// Whether [`NonIdempotent`] requests are retried like any other
+    retry_mutations: bool,
+}
+
+/// Errors constructing a [`RetryConfig`]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum RetryConfigError {
+    /// The delays are the wrong way round
+    #[error("the maximum retry delay ({max:?}) must not be less than the minimum ({min:?})")]
+    DelayRange {
+        /// The minimum delay
+        min: Duration,
+        /// The maximum delay
+        max: Duration,
+    },
+    /// A maximum delay of zero leaves no room to back off
+    #[error("the maximum retry delay must be greater than zero")]
+    ZeroMaxDelay,
+    /// Zero attempts would never send the request
+    #[error("at least one attempt must be allowed")]
+    ZeroAttempts,
+}
+
+#[buildstructor]
+impl RetryConfig {
+    /// Constructs a [`RetryConfig`] that retries for up to `max_elapsed_time`
+    /// with the default delays and jitter
+    pub const fn new(max_elapsed_time: Duration) -> RetryConfig {
+        RetryConfig {
+            max_elapsed_time,
+            max_attempts: None,
+            min_delay: DEFAULT_MIN_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            jitter: true,
+            retry_mutations: true,
+        }
+    }
+
+    /// Constructs a [`RetryConfig`], falling back to the defaults for
+    /// anything that isn't set
+    #[builder(entry = "builder", exit = "build")]
+    pub fn with_settings(
+        max_elapsed_time: Duration,
+        max_attempts: Option<u32>,
+        min_delay: Option<Duration>,
+        max_delay: Option<Duration>,
+        jitter: Option<bool>,
+        retry_mutations: Option<bool>,
+    ) -> Result<RetryConfig, RetryConfigError> {
+        let min_delay = min_delay.unwrap_or(DEFAULT_MIN_DELAY);
+        let max_delay = max_delay.unwrap_or_else(|| DEFAULT_MAX_DELAY.max(min_delay));
+        if max_delay.is_zero() {
+            return Err(RetryConfigError::ZeroMaxDelay);
+        }
+        if min_delay > max_delay {
+            return Err(RetryConfigError::DelayRange {
+                min: min_delay,
+                max: max_delay,
+            });
+        }
+        if max_attempts == Some(0) {
+            return Err(RetryConfigError::ZeroAttempts);
+        }
+        Ok(RetryConfig {
+            max_elapsed_time,
+            max_attempts,
+            min_delay,
+            max_delay,
+            jitter: jitter.unwrap_or(true),
+            retry_mutations: retry_mutations.unwrap_or(true),
+        })
+    }
+}
+
+/// Reads how long a `Retry-After` header asks the client to wait, given
+/// either as a number of seconds or as an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            Some(
+                date.duration_since(SystemTime::now())
+                    .unwrap_or(Duration::ZERO),
+            )
+        }
+    }
+}
+
+/// Whether a response with this status code was turned away before the
+/// server acted on the request, making it safe to send again.
+fn is_rejected_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    )
+}
+
 /// [`Policy`] implementation that describes whetheer to retry a request
 #[derive(Clone, Debug)]
 pub struct RetryPolicy {
     start_time: OnceCell<Instant>,
-    max_elapsed_time: Duration,
+    config: RetryConfig,
     backoff: ExponentialBackoff,
+    attempt: u32,
 }
 
 impl RetryPolicy {
     /// Constructs a new [`RetryPolicy`]
     pub fn new(max_elapsed_time: Duration) -> RetryPolicy {
+        RetryPolicy::from_config(RetryConfig::new(max_elapsed_time))
+    }
+
+    /// Constructs a [`RetryPolicy`] that retries as `config` describes
+    pub fn from_config(config: RetryConfig) -> RetryPolicy {
+        let jitter = if config.jitter { JITTER } else { 0.0 };
+        // a `RetryConfig` only ever holds delays that make a valid backoff
         let backoff = ExponentialBackoffMaker::new(
-            Duration::from_millis(500),
-            Duration::from_millis(60000),
-            0.99,
+            config.min_delay,
+            config.max_delay,
+            jitter,
             HasherRng::default(),
         )
-        .tap_err(|err| tracing::error!("{:?}", err))
-        .unwrap()
+        .expect("retry delays are validated by RetryConfig")
         .make_backoff();
         RetryPolicy {
             start_time: OnceCell::new(),
-            max_elapsed_time,
+            config,
             backoff,
+            attempt: 0,
         }
     }
 
-    /// Dictates whether a request can be retried, based on an optional maximum elapsed time
+    /// Dictates whether a request can be retried, based on the maximum elapsed
+    /// time and the maximum number of attempts
     pub fn can_retry(&self) -> bool {
-        self.start_time.get_or_init(Instant::now).elapsed() < self.max_elapsed_time
+        let attempts_left = self
+            .config
+            .max_attempts
+            .is_none_or(|max_attempts| self.attempt + 1 < max_attempts);
+        attempts_left && !self.remaining_time().is_zero()
+    }
+
+    fn remaining_time(&self) -> Duration {
+        self.config
+            .max_elapsed_time
+            .saturating_sub(self.start_time.get_or_init(Instant::now).elapsed())
     }
 }
 
@@ -60,32 +213,82 @@ fn is_retryable_status(status: StatusCode) -> bool {
         )
 }
 
+impl RetryPolicy {
+    /// Waits out the next backoff, or the delay the server asked for in
+    /// `Retry-After`, inside a span that records why the request is being
+    /// retried. Gives up instead if the server asks for a longer wait than
+    /// what's left of the budget.
+    fn next_retry(
+        &mut self,
+        reason: &str,
+        retry_after: Option<Duration>,
+    ) -> Option<Instrumented<Sleep>> {
+        let sleep = match retry_after {
+            Some(delay) if delay > self.remaining_time() => {
+                tracing::debug!(
+                    retry_after_ms = delay.as_millis() as u64,
+                    "not retrying, the server asked to wait longer than the retry budget allows"
+                );
+                return None;
+            }
+            Some(delay) => tokio::time::sleep(delay),
+            None => self.backoff.next_backoff(),
+        };
+        self.attempt += 1;
+        let span = tracing::info_span!(
+            "HTTP retry",
+            attempt = self.attempt,
+            reason,
+            backoff_ms = sleep
+                .deadline()
+                .saturating_duration_since(tokio::time::Instant::now())
+                .as_millis() as u64,
+        );
+        Some(sleep.instrument(span))
+    }
+}
+
 impl Policy<HttpRequest, HttpResponse, HttpServiceError> for RetryPolicy {
-    type Future = tokio::time::Sleep;
+    type Future = Instrumented<Sleep>;
     fn retry(
         &mut self,
-        _: &mut HttpRequest,
+        req: &mut HttpRequest,
         result: &mut Result<HttpResponse, HttpServiceError>,
     ) -> Option<Self::Future> {
-        if self.can_retry() {
-            match result {
-                Err(HttpServiceError::TimedOut)
-                | Err(HttpServiceError::Connect(_))
-                | Err(HttpServiceError::Body(_))
-                | Err(HttpServiceError::Decode(_))
-                | Err(HttpServiceError::Request(_))
-                | Err(HttpServiceError::Closed(_)) => Some(self.backoff.next_backoff()),
-                Err(_) => None,
-                Ok(resp) => {
-                    if is_retryable_status(resp.status()) {
-                        Some(self.backoff.next_backoff())
-                    } else {
-                        None
-                    }
+        if !self.can_retry() {
+            return None;
+        }
+        // a request that may have reached the server is only sent again if
+        // doing so twice is harmless
+        let may_repeat =
+            self.config.retry_mutations || req.extensions().get::<NonIdempotent>().is_none();
+        match result {
+            Err(err @ HttpServiceError::Connect(_)) => {
+                let reason = err.to_string();
+                self.next_retry(&reason, None)
+            }
+            Err(
+                err @ (HttpServiceError::TimedOut
+                | HttpServiceError::Body(_)
+                | HttpServiceError::Decode(_)
+                | HttpServiceError::Request(_)
+                | HttpServiceError::Closed(_)),
+            ) if may_repeat => {
+                let reason = err.to_string();
+                self.next_retry(&reason, None)
+            }
+            Err(_) => None,
+            Ok(resp) => {
+                let status = resp.status();
+                if is_rejected_status(status) {
+                    let retry_after = retry_after(resp.headers());
+                    self.next_retry(&status.to_string(), retry_after)
+                } else if may_repeat && is_retryable_status(status) {
+                    self.next_retry(&status.to_string(), None)
+                } else {
+                    None
                 }
             }
-        } else {
-            None
         }
     }
 
@@ -107,7 +310,7 @@ mod tests {
     use speculoos::prelude::*;
     use tower::{Service, ServiceBuilder, ServiceExt};
 
-    use super::RetryPolicy;
+    use super::{retry_after, NonIdempotent, RetryConfig, RetryConfigError, RetryPolicy};
     use crate::{HttpService, ReqwestService};
 
     #[fixture]
@@ -223,4 +426,180 @@ mod tests {
             .matches(|resp| resp.status() == status);
         Ok(())
     }
+
+    fn retry_service_with(config: RetryConfig, raw_service: HttpService) -> HttpService {
+        ServiceBuilder::new()
+            .retry(RetryPolicy::from_config(config))
+            .service(raw_service)
+            .boxed_clone()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    pub async fn max_attempts_bounds_the_number_of_calls(raw_service: HttpService) -> Result<()> {
+        let config = RetryConfig::builder()
+            .max_elapsed_time(Duration::from_secs(10))
+            .max_attempts(2)
+            .min_delay(Duration::from_millis(10))
+            .build()?;
+        let mut service = retry_service_with(config, raw_service);
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/");
+            then.status(500).body("");
+        });
+
+        let request = http::Request::builder()
+            .uri(format!("http://{}/", server.address()))
+            .method(http::Method::GET)
+            .body(Full::default())?;
+        service.call(request).await?;
+
+        mock.assert_calls(2);
+        Ok(())
+    }
+
+    /// `Retry-After: 1` fits in a 1.5s budget once, but not a second time.
+    #[rstest]
+    #[case::too_many_requests(StatusCode::TOO_MANY_REQUESTS)]
+    #[case::service_unavailable(StatusCode::SERVICE_UNAVAILABLE)]
+    #[tokio::test]
+    pub async fn retry_after_is_honored_within_the_budget(
+        #[case] status: StatusCode,
+        mut retry_service: HttpService,
+    ) -> Result<()> {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/");
+            then.status(status.as_u16())
+                .header("Retry-After", "1")
+                .body("");
+        });
+
+        let request = http::Request::builder()
+            .uri(format!("http://{}/", server.address()))
+            .method(http::Method::GET)
+            .body(Full::default())?;
+        let resp = retry_service.call(request).await;
+
+        mock.assert_calls(2);
+        assert_that!(resp)
+            .is_ok()
+            .matches(|resp| resp.status() == status);
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    pub async fn retry_after_beyond_the_budget_gives_up(
+        mut retry_service: HttpService,
+    ) -> Result<()> {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/");
+            then.status(429).header("Retry-After", "120").body("");
+        });
+
+        let request = http::Request::builder()
+            .uri(format!("http://{}/", server.address()))
+            .method(http::Method::GET)
+            .body(Full::default())?;
+        retry_service.call(request).await?;
+
+        mock.assert_calls(1);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::server_error(StatusCode::INTERNAL_SERVER_ERROR, 1)]
+    #[case::gateway_timeout(StatusCode::GATEWAY_TIMEOUT, 1)]
+    #[case::too_many_requests(StatusCode::TOO_MANY_REQUESTS, 2)]
+    #[case::service_unavailable(StatusCode::SERVICE_UNAVAILABLE, 2)]
+    #[tokio::test]
+    pub async fn non_idempotent_requests_are_only_retried_when_rejected(
+        #[case] status: StatusCode,
+        #[case] expected_calls: usize,
+        raw_service: HttpService,
+    ) -> Result<()> {
+        let config = RetryConfig::builder()
+            .max_elapsed_time(Duration::from_secs(10))
+            .max_attempts(2)
+            .min_delay(Duration::from_millis(10))
+            .retry_mutations(false)
+            .build()?;
+        let mut service = retry_service_with(config, raw_service);
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::POST).path("/");
+            then.status(status.as_u16()).body("");
+        });
+
+        let mut request = http::Request::builder()
+            .uri(format!("http://{}/", server.address()))
+            .method(http::Method::POST)
+            .body(Full::default())?;
+        request.extensions_mut().insert(NonIdempotent);
+        service.call(request).await?;
+
+        mock.assert_calls(expected_calls);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::seconds("3", Some(Duration::from_secs(3)))]
+    #[case::past_date("Wed, 21 Oct 2015 07:28:00 GMT", Some(Duration::ZERO))]
+    #[case::garbage("soon", None)]
+    fn it_parses_retry_after(#[case] value: &str, #[case] expected: Option<Duration>) {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(http::header::RETRY_AFTER, value.parse().unwrap());
+        assert_that!(retry_after(&headers)).is_equal_to(expected);
+    }
+
+    #[test]
+    fn it_parses_a_future_retry_after_date() {
+        let date = std::time::SystemTime::now() + Duration::from_secs(30);
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::RETRY_AFTER,
+            httpdate::fmt_http_date(date).parse().unwrap(),
+        );
+        assert_that!(retry_after(&headers))
+            .is_some()
+            .matches(|delay| *delay > Duration::from_secs(25) && *delay <= Duration::from_secs(30));
+    }
+
+    #[rstest]
+    #[case::reversed_delays(
+        Some(Duration::from_secs(5)),
+        Some(Duration::from_secs(1)),
+        None,
+        RetryConfigError::DelayRange { min: Duration::from_secs(5), max: Duration::from_secs(1) }
+    )]
+    #[case::zero_max_delay(None, Some(Duration::ZERO), None, RetryConfigError::ZeroMaxDelay)]
+    #[case::zero_attempts(None, None, Some(0), RetryConfigError::ZeroAttempts)]
+    fn it_rejects_invalid_configs(
+        #[case] min_delay: Option<Duration>,
+        #[case] max_delay: Option<Duration>,
+        #[case] max_attempts: Option<u32>,
+        #[case] expected: RetryConfigError,
+    ) {
+        let config = RetryConfig::builder()
+            .max_elapsed_time(Duration::from_secs(30))
+            .and_min_delay(min_delay)
+            .and_max_delay(max_delay)
+            .and_max_attempts(max_attempts)
+            .build();
+        assert_that!(config).is_err().is_equal_to(expected);
+    }
+
+    /// a long minimum delay raises the default maximum rather than failing
+    #[test]
+    fn a_long_min_delay_raises_the_default_max_delay() {
+        let config = RetryConfig::builder()
+            .max_elapsed_time(Duration::from_secs(300))
+            .min_delay(Duration::from_secs(90))
+            .build()
+            .unwrap();
+        assert_that!(*config.max_delay()).is_equal_to(Duration::from_secs(90));
+    }
 }
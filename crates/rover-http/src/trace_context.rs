@@ -0,0 +1,63 @@
+//! Propagates the current trace context to outgoing HTTP requests
+
+use http::{HeaderMap, HeaderName, HeaderValue};
+use opentelemetry::{global, propagation::Injector};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Adapts a [`HeaderMap`] so a propagator can write headers into it
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// Adds the current span's trace context (a W3C `traceparent` header, and
+/// `tracestate` if there is any) to `headers`.
+///
+/// This is a no-op unless a propagator has been installed, which only happens
+/// when tracing export has been opted into.
+pub fn inject_trace_context(headers: &mut HeaderMap) {
+    let context = tracing::Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+    use opentelemetry::{global, trace::TracerProvider};
+    use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracerProvider};
+    use speculoos::prelude::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::inject_trace_context;
+
+    #[test]
+    fn it_injects_the_current_span_as_a_traceparent() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        let mut headers = HeaderMap::new();
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("some operation");
+            let _entered = span.enter();
+            inject_trace_context(&mut headers);
+        });
+
+        let traceparent = headers.get("traceparent").map(|v| v.to_str().unwrap());
+        // version-trace_id-parent_id-flags, with a sampled, non-zero trace id
+        assert_that!(traceparent)
+            .is_some()
+            .matches(|v| v.len() == 55 && v.starts_with("00-") && v.ends_with("-01"));
+    }
+}
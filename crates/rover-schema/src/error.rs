@@ -41,6 +41,19 @@ pub enum SchemaError {
         argument: Name,
     },
 
+    /// The schema is not a supergraph, so it has no subgraph ownership to describe.
+    #[error("The schema is not a supergraph, as it has no `join__Graph` enum")]
+    NotASupergraph,
+
+    /// A search query has a filter that isn't recognized or has an invalid value.
+    #[error("Invalid search filter `{filter}`: {reason}")]
+    InvalidSearchFilter {
+        /// The filter as written in the query.
+        filter: String,
+        /// Why the filter is invalid.
+        reason: String,
+    },
+
     /// The schema coordinate kind is not supported by this operation.
     #[error("Unsupported coordinate: {0}")]
     UnsupportedCoordinate(SchemaCoordinate),
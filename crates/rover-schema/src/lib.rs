@@ -6,12 +6,20 @@
 pub mod describe;
 /// Error types for schema operations.
 pub mod error;
+/// Subgraph ownership lookups for supergraph schemas.
+pub mod ownership;
 /// Parsed schema wrapper.
 pub mod parsed_schema;
+/// Per-type reference entries for generating schema documentation.
+pub mod reference;
 /// Root-path traversal for finding how types are reachable.
 pub mod root_paths;
 /// Schema text search.
 pub mod search;
+/// Size and quality statistics for schemas.
+pub mod stats;
+/// Which operations select each type and field of a schema.
+pub mod usage;
 // Re-export main public types
 pub use apollo_compiler::coordinate::SchemaCoordinate;
 pub use describe::{
@@ -20,5 +28,15 @@ pub use describe::{
     ObjectDetail, ScalarDetail, SchemaOverview, TypeDetail, UnionDetail,
 };
 pub use error::SchemaError;
+pub use ownership::{
+    EntityKey, FieldInSubgraph, FieldOwnership, OwnershipOutput, SchemaOwnership, SubgraphInfo,
+    TypeInSubgraph, TypeOwnership,
+};
 pub use parsed_schema::ParsedSchema;
-pub use search::{ElementKind, SearchResult};
+pub use reference::{FieldReference, SchemaReference, TypeReference};
+pub use search::{ElementKind, SearchQuery, SearchResult, search_subgraphs};
+pub use stats::{
+    Coverage, CustomScalarStats, DeprecationCounts, DescriptionCoverage, SchemaStats,
+    SubgraphContribution, TypeCounts, TypeDepth, TypeReferences, TypeWidth,
+};
+pub use usage::{OperationRef, OperationUsage};
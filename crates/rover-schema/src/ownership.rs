@@ -0,0 +1,559 @@
+use apollo_compiler::{
+    Name,
+    ast::{Directive, DirectiveList, Value},
+    coordinate::{SchemaCoordinate, TypeAttributeCoordinate},
+    schema::ExtendedType,
+};
+
+use crate::{ParsedSchema, SchemaError, reference::is_federation_type};
+
+const JOIN_GRAPH: &str = "join__Graph";
+
+/// The result of an `ownership` lookup, which varies by the coordinate provided.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(untagged)]
+pub enum OwnershipOutput {
+    /// Ownership of every type in the supergraph.
+    Schema(SchemaOwnership),
+    /// Ownership of a single type and its fields.
+    Type(TypeOwnership),
+    /// Ownership of a single field.
+    Field(FieldOwnership),
+}
+
+/// Ownership of every type in a supergraph.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SchemaOwnership {
+    /// The SDL source location or identifier that was parsed.
+    pub schema_source: String,
+    /// The subgraphs composed into the supergraph.
+    pub subgraphs: Vec<SubgraphInfo>,
+    /// Every type in the supergraph, leaving out the types federation adds, sorted by name.
+    pub types: Vec<TypeOwnership>,
+}
+
+/// A subgraph composed into a supergraph, from its `join__Graph` value.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SubgraphInfo {
+    /// The subgraph name.
+    pub name: String,
+    /// The routing URL of the subgraph.
+    pub url: Option<String>,
+}
+
+/// The subgraphs that declare a type, with its keys, and its fields' owners.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TypeOwnership {
+    /// The type name.
+    pub name: Name,
+    /// The subgraphs that declare this type, from its `@join__type` directives.
+    pub subgraphs: Vec<TypeInSubgraph>,
+    /// Whether this type is marked `@inaccessible`.
+    pub inaccessible: bool,
+    /// Names from the type's `@tag` directives.
+    pub tags: Vec<String>,
+    /// Ownership of the fields of an object, interface or input type.
+    pub fields: Vec<FieldOwnership>,
+}
+
+/// How a subgraph declares a type.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct TypeInSubgraph {
+    /// The subgraph name.
+    pub subgraph: String,
+    /// The entity keys the subgraph declares for this type.
+    pub keys: Vec<EntityKey>,
+    /// Whether the subgraph declares the type as an extension.
+    pub extension: bool,
+    /// Whether the subgraph declares the type as an `@interfaceObject`.
+    pub interface_object: bool,
+}
+
+/// An entity key declared by a subgraph.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct EntityKey {
+    /// The key's field set, such as `id` or `sku variation { id }`.
+    pub fields: String,
+    /// Whether the subgraph can resolve the entity by this key.
+    pub resolvable: bool,
+}
+
+/// The subgraphs that resolve a field.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FieldOwnership {
+    /// The name of the type that owns the field.
+    pub type_name: Name,
+    /// The field name.
+    pub field_name: Name,
+    /// The subgraphs that resolve this field.
+    pub subgraphs: Vec<FieldInSubgraph>,
+    /// Subgraphs that declare this field as `@external`, without resolving it.
+    pub external_in: Vec<String>,
+    /// Whether this field is marked `@inaccessible`.
+    pub inaccessible: bool,
+    /// Names from the field's `@tag` directives.
+    pub tags: Vec<String>,
+}
+
+/// How a subgraph resolves a field, from the field's `@join__field` directive.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FieldInSubgraph {
+    /// The subgraph name.
+    pub subgraph: String,
+    /// Fields of the parent type the subgraph `@requires` to resolve this field.
+    pub requires: Option<String>,
+    /// Fields of the returned type the subgraph `@provides` along with this field.
+    pub provides: Option<String>,
+    /// The subgraph this field is taken over from with `@override`.
+    pub overrides: Option<String>,
+    /// The progressive override label, such as `percent(25)`, that decides whether this
+    /// subgraph resolves the field.
+    pub override_label: Option<String>,
+}
+
+impl ParsedSchema {
+    /// Returns `true` if the schema is a composed supergraph, recognized by its `join__Graph`
+    /// enum.
+    pub fn is_supergraph(&self) -> bool {
+        matches!(
+            self.inner().types.get(JOIN_GRAPH),
+            Some(ExtendedType::Enum(_))
+        )
+    }
+
+    /// Names of the subgraphs composed into a supergraph, in the order they are declared.
+    /// Returns an empty list for schemas that aren't supergraphs.
+    pub fn subgraph_names(&self) -> Vec<String> {
+        self.subgraphs().into_iter().map(|s| s.name).collect()
+    }
+
+    /// The subgraphs composed into a supergraph, in the order they are declared.
+    pub fn subgraphs(&self) -> Vec<SubgraphInfo> {
+        let Some(ExtendedType::Enum(graphs)) = self.inner().types.get(JOIN_GRAPH) else {
+            return Vec::new();
+        };
+        graphs
+            .values
+            .iter()
+            .map(|(graph, value)| SubgraphInfo {
+                name: self.subgraph_name(graph),
+                url: value
+                    .directives
+                    .get("join__graph")
+                    .and_then(|d| d.specified_argument_by_name("url"))
+                    .and_then(|url| url.as_str())
+                    .map(str::to_string),
+            })
+            .collect()
+    }
+
+    /// Returns the subgraphs that declare the named type in a supergraph, from its
+    /// `@join__type` directives.
+    pub fn type_owners(&self, type_name: &Name) -> Vec<String> {
+        let Some(ty) = self.inner().types.get(type_name) else {
+            return Vec::new();
+        };
+        self.graphs(ty.directives().get_all("join__type").map(|d| &*d.node))
+    }
+
+    /// Returns the subgraphs that resolve a field of an object, interface or input type in a
+    /// supergraph.
+    ///
+    /// These are the subgraphs named by the field's `@join__field` directives, leaving out
+    /// those where the field is `external` or `usedOverridden`. A field without `@join__field`
+    /// is resolved by every subgraph that declares its type.
+    pub fn field_owners(&self, type_name: &Name, field_name: &Name) -> Vec<String> {
+        self.field_directives(type_name, field_name)
+            .map(|directives| {
+                self.field_resolvers(type_name, directives)
+                    .into_iter()
+                    .map(|s| s.subgraph)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Describe which subgraphs own the supergraph, or a type or field within it.
+    ///
+    /// Pass `None` for `coord` to get the ownership of every type. Only type and field
+    /// coordinates are supported.
+    pub fn ownership(
+        &self,
+        coord: Option<&SchemaCoordinate>,
+    ) -> Result<OwnershipOutput, SchemaError> {
+        if !self.is_supergraph() {
+            return Err(SchemaError::NotASupergraph);
+        }
+        match coord {
+            None => Ok(OwnershipOutput::Schema(self.schema_ownership())),
+            Some(SchemaCoordinate::Type(tc)) => {
+                self.type_ownership(&tc.ty).map(OwnershipOutput::Type)
+            }
+            Some(SchemaCoordinate::TypeAttribute(tac)) => {
+                self.field_ownership(tac).map(OwnershipOutput::Field)
+            }
+            Some(other) => Err(SchemaError::UnsupportedCoordinate(other.clone())),
+        }
+    }
+
+    fn schema_ownership(&self) -> SchemaOwnership {
+        let mut types: Vec<_> = self
+            .inner()
+            .types
+            .iter()
+            .filter(|(name, ty)| !ty.is_built_in() && !is_federation_type(name))
+            .filter_map(|(name, _)| self.type_ownership(name).ok())
+            .collect();
+        types.sort_by(|a, b| a.name.cmp(&b.name));
+        SchemaOwnership {
+            schema_source: self
+                .source_path()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            subgraphs: self.subgraphs(),
+            types,
+        }
+    }
+
+    /// Return the ownership of the named type and its fields.
+    pub fn type_ownership(&self, type_name: &Name) -> Result<TypeOwnership, SchemaError> {
+        let ty = self
+            .inner()
+            .types
+            .get(type_name)
+            .ok_or_else(|| SchemaError::TypeNotFound(type_name.clone()))?;
+
+        let mut subgraphs: Vec<TypeInSubgraph> = Vec::new();
+        for directive in ty.directives().get_all("join__type") {
+            let Some(Value::Enum(graph)) =
+                directive.specified_argument_by_name("graph").map(|v| &**v)
+            else {
+                continue;
+            };
+            let subgraph = self.subgraph_name(graph);
+            let index = match subgraphs.iter().position(|s| s.subgraph == subgraph) {
+                Some(index) => index,
+                None => {
+                    subgraphs.push(TypeInSubgraph {
+                        subgraph,
+                        keys: Vec::new(),
+                        extension: false,
+                        interface_object: false,
+                    });
+                    subgraphs.len() - 1
+                }
+            };
+            let entry = &mut subgraphs[index];
+            entry.extension |= is_true(directive, "extension");
+            entry.interface_object |= is_true(directive, "isInterfaceObject");
+            if let Some(fields) = string_arg(directive, "key") {
+                entry.keys.push(EntityKey {
+                    fields,
+                    resolvable: !matches!(
+                        directive
+                            .specified_argument_by_name("resolvable")
+                            .map(|v| &**v),
+                        Some(Value::Boolean(false))
+                    ),
+                });
+            }
+        }
+
+        let field_names: Vec<Name> = match ty {
+            ExtendedType::Object(obj) => obj.fields.keys().cloned().collect(),
+            ExtendedType::Interface(iface) => iface.fields.keys().cloned().collect(),
+            ExtendedType::InputObject(inp) => inp.fields.keys().cloned().collect(),
+            _ => Vec::new(),
+        };
+        let fields = field_names
+            .into_iter()
+            .filter_map(|field_name| {
+                let directives = self.field_directives(type_name, &field_name)?;
+                Some(self.build_field_ownership(type_name, field_name, directives))
+            })
+            .collect();
+
+        let directives: Vec<&Directive> = ty.directives().iter().map(|d| &*d.node).collect();
+        Ok(TypeOwnership {
+            name: type_name.clone(),
+            subgraphs,
+            inaccessible: is_inaccessible(&directives),
+            tags: tags(&directives),
+            fields,
+        })
+    }
+
+    /// Return the ownership of the field identified by `coord`.
+    pub fn field_ownership(
+        &self,
+        coord: &TypeAttributeCoordinate,
+    ) -> Result<FieldOwnership, SchemaError> {
+        if !self.inner().types.contains_key(&coord.ty) {
+            return Err(SchemaError::TypeNotFound(coord.ty.clone()));
+        }
+        let directives = self
+            .field_directives(&coord.ty, &coord.attribute)
+            .ok_or_else(|| SchemaError::FieldNotFound {
+                type_name: coord.ty.clone(),
+                field: coord.attribute.clone(),
+            })?;
+        Ok(self.build_field_ownership(&coord.ty, coord.attribute.clone(), directives))
+    }
+
+    fn build_field_ownership(
+        &self,
+        type_name: &Name,
+        field_name: Name,
+        directives: &DirectiveList,
+    ) -> FieldOwnership {
+        let external_in = self.graphs(
+            directives
+                .get_all("join__field")
+                .filter(|d| is_true(d, "external"))
+                .map(|d| &**d),
+        );
+        let all: Vec<&Directive> = directives.iter().map(|d| &**d).collect();
+        FieldOwnership {
+            type_name: type_name.clone(),
+            field_name,
+            subgraphs: self.field_resolvers(type_name, directives),
+            external_in,
+            inaccessible: is_inaccessible(&all),
+            tags: tags(&all),
+        }
+    }
+
+    /// How each subgraph resolves a field with the given directives. A field without
+    /// `@join__field` is resolved by every subgraph that declares its type.
+    fn field_resolvers(
+        &self,
+        type_name: &Name,
+        directives: &DirectiveList,
+    ) -> Vec<FieldInSubgraph> {
+        let join_fields: Vec<_> = directives.get_all("join__field").collect();
+        if join_fields.is_empty() {
+            return self
+                .type_owners(type_name)
+                .into_iter()
+                .map(|subgraph| FieldInSubgraph {
+                    subgraph,
+                    requires: None,
+                    provides: None,
+                    overrides: None,
+                    override_label: None,
+                })
+                .collect();
+        }
+        let mut resolvers: Vec<FieldInSubgraph> = Vec::new();
+        for directive in join_fields {
+            if is_true(directive, "external") || is_true(directive, "usedOverridden") {
+                continue;
+            }
+            let Some(Value::Enum(graph)) =
+                directive.specified_argument_by_name("graph").map(|v| &**v)
+            else {
+                continue;
+            };
+            let subgraph = self.subgraph_name(graph);
+            if resolvers.iter().any(|r| r.subgraph == subgraph) {
+                continue;
+            }
+            resolvers.push(FieldInSubgraph {
+                subgraph,
+                requires: string_arg(directive, "requires"),
+                provides: string_arg(directive, "provides"),
+                overrides: string_arg(directive, "override"),
+                override_label: string_arg(directive, "overrideLabel"),
+            });
+        }
+        resolvers
+    }
+
+    fn field_directives(&self, type_name: &Name, field_name: &Name) -> Option<&DirectiveList> {
+        match self.inner().types.get(type_name)? {
+            ExtendedType::Object(obj) => obj.fields.get(field_name).map(|f| &f.directives),
+            ExtendedType::Interface(iface) => iface.fields.get(field_name).map(|f| &f.directives),
+            ExtendedType::InputObject(inp) => inp.fields.get(field_name).map(|f| &f.directives),
+            _ => None,
+        }
+    }
+
+    /// Resolves the `graph:` arguments of join directives to subgraph names, without
+    /// duplicates.
+    fn graphs<'a>(&self, directives: impl Iterator<Item = &'a Directive>) -> Vec<String> {
+        let mut names = Vec::new();
+        for directive in directives {
+            if let Some(Value::Enum(graph)) =
+                directive.specified_argument_by_name("graph").map(|v| &**v)
+            {
+                let name = self.subgraph_name(graph);
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// The name a `join__Graph` value was given by `@join__graph(name:)`, falling back to the
+    /// value itself in lowercase.
+    fn subgraph_name(&self, graph: &Name) -> String {
+        let Some(ExtendedType::Enum(graphs)) = self.inner().types.get(JOIN_GRAPH) else {
+            return graph.to_lowercase();
+        };
+        graphs
+            .values
+            .get(graph)
+            .and_then(|value| value.directives.get("join__graph"))
+            .and_then(|d| d.specified_argument_by_name("name"))
+            .and_then(|name| name.as_str())
+            .map_or_else(|| graph.to_lowercase(), str::to_string)
+    }
+}
+
+fn is_true(directive: &Directive, argument: &str) -> bool {
+    matches!(
+        directive.specified_argument_by_name(argument).map(|v| &**v),
+        Some(Value::Boolean(true))
+    )
+}
+
+fn string_arg(directive: &Directive, argument: &str) -> Option<String> {
+    directive
+        .specified_argument_by_name(argument)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+fn is_inaccessible(directives: &[&Directive]) -> bool {
+    directives.iter().any(|d| d.name == "inaccessible")
+}
+
+fn tags(directives: &[&Directive]) -> Vec<String> {
+    directives
+        .iter()
+        .filter(|d| d.name == "tag")
+        .filter_map(|d| string_arg(d, "name"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_compiler::{coord, name};
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::ParsedSchema;
+
+    #[fixture]
+    fn supergraph() -> ParsedSchema {
+        let sdl = include_str!("test_fixtures/supergraph.graphql");
+        ParsedSchema::parse(sdl, "supergraph.graphql")
+    }
+
+    #[rstest]
+    fn lists_subgraphs_of_a_supergraph(supergraph: ParsedSchema) {
+        assert_that!(supergraph.is_supergraph()).is_true();
+        assert_that!(supergraph.subgraph_names())
+            .is_equal_to(vec!["products".to_string(), "reviews".to_string()]);
+        assert_that!(supergraph.subgraphs()[0].url)
+            .is_equal_to(Some("http://localhost:4001".to_string()));
+    }
+
+    #[rstest]
+    fn field_owners_come_from_join_field(supergraph: ParsedSchema) {
+        assert_that!(supergraph.field_owners(&name!("Product"), &name!("reviews")))
+            .is_equal_to(vec!["reviews".to_string()]);
+        assert_that!(supergraph.field_owners(&name!("Product"), &name!("name")))
+            .is_equal_to(vec!["products".to_string()]);
+    }
+
+    #[rstest]
+    fn fields_without_join_field_belong_to_every_declaring_subgraph(supergraph: ParsedSchema) {
+        assert_that!(supergraph.field_owners(&name!("Product"), &name!("id")))
+            .is_equal_to(vec!["products".to_string(), "reviews".to_string()]);
+        assert_that!(supergraph.type_owners(&name!("Review")))
+            .is_equal_to(vec!["reviews".to_string()]);
+    }
+
+    #[rstest]
+    fn other_schemas_have_no_owners() {
+        let schema = ParsedSchema::parse(
+            include_str!("test_fixtures/test_schema.graphql"),
+            "test_schema.graphql",
+        );
+        assert_that!(schema.is_supergraph()).is_false();
+        assert_that!(schema.subgraph_names()).is_empty();
+        assert_that!(schema.field_owners(&name!("User"), &name!("id"))).is_empty();
+        assert_that!(schema.ownership(None))
+            .is_err()
+            .matches(|e| matches!(e, SchemaError::NotASupergraph));
+    }
+
+    #[rstest]
+    fn type_ownership_lists_keys_per_subgraph(supergraph: ParsedSchema) {
+        let product = supergraph.type_ownership(&name!("Product")).unwrap();
+        assert_that!(product.subgraphs[1]).is_equal_to(TypeInSubgraph {
+            subgraph: "reviews".to_string(),
+            keys: vec![
+                EntityKey {
+                    fields: "id".to_string(),
+                    resolvable: true,
+                },
+                EntityKey {
+                    fields: "sku".to_string(),
+                    resolvable: false,
+                },
+            ],
+            extension: false,
+            interface_object: false,
+        });
+        assert_that!(product.tags).is_equal_to(vec!["public".to_string()]);
+        let internal = product
+            .fields
+            .iter()
+            .find(|f| f.field_name == "internalCode")
+            .unwrap();
+        assert_that!(internal.inaccessible).is_true();
+    }
+
+    #[rstest]
+    fn field_ownership_shows_dependencies_and_overrides(supergraph: ParsedSchema) {
+        let reviews = supergraph
+            .field_ownership(&coord!(Product.reviews))
+            .unwrap();
+        assert_that!(reviews.subgraphs[0].requires).is_equal_to(Some("name".to_string()));
+
+        let name = supergraph.field_ownership(&coord!(Product.name)).unwrap();
+        assert_that!(name.external_in).is_equal_to(vec!["reviews".to_string()]);
+
+        let in_stock = supergraph
+            .field_ownership(&coord!(Product.inStock))
+            .unwrap();
+        assert_that!(in_stock.subgraphs).is_equal_to(vec![
+            FieldInSubgraph {
+                subgraph: "products".to_string(),
+                requires: None,
+                provides: None,
+                overrides: None,
+                override_label: Some("percent(25)".to_string()),
+            },
+            FieldInSubgraph {
+                subgraph: "reviews".to_string(),
+                requires: None,
+                provides: None,
+                overrides: Some("products".to_string()),
+                override_label: Some("percent(25)".to_string()),
+            },
+        ]);
+    }
+
+    #[rstest]
+    fn ownership_rejects_directive_coordinates(supergraph: ParsedSchema) {
+        let coord = "@tag".parse::<SchemaCoordinate>().unwrap();
+        assert_that!(supergraph.ownership(Some(&coord)))
+            .is_err()
+            .matches(|e| matches!(e, SchemaError::UnsupportedCoordinate(_)));
+    }
+}
@@ -0,0 +1,213 @@
+use apollo_compiler::{
+    Name,
+    ast::{OperationType, Type as AstType},
+    schema::{ExtendedType, FieldDefinition, InputValueDefinition},
+};
+
+use crate::{
+    ParsedSchema,
+    describe::{FieldArgDetail, TypeDetail, deprecated::IsDeprecated},
+};
+
+/// Everything needed to write a reference for a schema, with one entry per type.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SchemaReference {
+    /// Root operation type names, in query, mutation, subscription order.
+    pub root_types: Vec<Name>,
+    /// Subgraph names, if the schema is a supergraph.
+    pub subgraphs: Vec<String>,
+    /// Every documented type, sorted by name.
+    pub types: Vec<TypeReference>,
+}
+
+/// Reference entry for a single named type.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TypeReference {
+    /// The type's detail, including deprecated fields and values.
+    pub detail: TypeDetail,
+    /// Fields of an object, interface or input type, with their full types and arguments.
+    pub fields: Vec<FieldReference>,
+    /// Subgraphs that declare this type, if the schema is a supergraph.
+    pub owners: Vec<String>,
+}
+
+impl TypeReference {
+    /// Returns the type name.
+    pub const fn name(&self) -> &Name {
+        match &self.detail {
+            TypeDetail::Object(d) => &d.name,
+            TypeDetail::Interface(d) => &d.name,
+            TypeDetail::Input(d) => &d.name,
+            TypeDetail::Enum(d) => &d.name,
+            TypeDetail::Union(d) => &d.name,
+            TypeDetail::Scalar(d) => &d.name,
+        }
+    }
+}
+
+/// Reference entry for a field of an object, interface or input type.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FieldReference {
+    /// The field name.
+    pub name: Name,
+    /// The full field type.
+    pub field_type: AstType,
+    /// Optional description from the schema SDL.
+    pub description: Option<String>,
+    /// The default value of an input field, if one is specified.
+    pub default_value: Option<String>,
+    /// Whether this field is marked `@deprecated`.
+    pub is_deprecated: bool,
+    /// The reason given for deprecation, if any.
+    pub deprecation_reason: Option<String>,
+    /// The field's arguments.
+    pub arguments: Vec<FieldArgDetail>,
+    /// Subgraphs that resolve this field, if the schema is a supergraph.
+    pub owners: Vec<String>,
+}
+
+impl ParsedSchema {
+    /// Build a reference for every type in the schema, leaving out built-in types and the
+    /// types federation adds to supergraphs and subgraphs.
+    pub fn reference(&self) -> SchemaReference {
+        let schema = self.inner();
+        let root_types = [
+            OperationType::Query,
+            OperationType::Mutation,
+            OperationType::Subscription,
+        ]
+        .into_iter()
+        .filter_map(|op| schema.root_operation(op).cloned())
+        .collect();
+        let mut types: Vec<_> = schema
+            .types
+            .iter()
+            .filter(|(name, ty)| !ty.is_built_in() && !is_federation_type(name))
+            .filter_map(|(name, ty)| self.type_reference(name, ty))
+            .collect();
+        types.sort_by(|a, b| a.name().cmp(b.name()));
+        SchemaReference {
+            root_types,
+            subgraphs: self.subgraph_names(),
+            types,
+        }
+    }
+
+    fn type_reference(&self, type_name: &Name, ty: &ExtendedType) -> Option<TypeReference> {
+        let detail = self.type_detail(type_name, true, 0).ok()?;
+        let fields = match ty {
+            ExtendedType::Object(obj) => obj
+                .fields
+                .values()
+                .map(|field| self.output_field_reference(type_name, field))
+                .collect(),
+            ExtendedType::Interface(iface) => iface
+                .fields
+                .values()
+                .map(|field| self.output_field_reference(type_name, field))
+                .collect(),
+            ExtendedType::InputObject(inp) => inp
+                .fields
+                .values()
+                .map(|field| self.input_field_reference(type_name, field))
+                .collect(),
+            _ => Vec::new(),
+        };
+        Some(TypeReference {
+            detail,
+            fields,
+            owners: self.type_owners(type_name),
+        })
+    }
+
+    fn output_field_reference(&self, type_name: &Name, field: &FieldDefinition) -> FieldReference {
+        let arguments = field
+            .arguments
+            .iter()
+            .map(|arg| FieldArgDetail {
+                type_name: type_name.clone(),
+                field_name: field.name.clone(),
+                arg_name: arg.name.clone(),
+                arg_type: (*arg.ty).clone(),
+                description: arg.description.as_ref().map(|d| d.to_string()),
+                default_value: arg.default_value.as_ref().map(|v| v.to_string()),
+            })
+            .collect();
+        FieldReference {
+            name: field.name.clone(),
+            field_type: field.ty.clone(),
+            description: field.description.as_ref().map(|d| d.to_string()),
+            default_value: None,
+            is_deprecated: field.is_deprecated(),
+            deprecation_reason: field.deprecation_reason(),
+            arguments,
+            owners: self.field_owners(type_name, &field.name),
+        }
+    }
+
+    fn input_field_reference(
+        &self,
+        type_name: &Name,
+        field: &InputValueDefinition,
+    ) -> FieldReference {
+        FieldReference {
+            name: field.name.clone(),
+            field_type: (*field.ty).clone(),
+            description: field.description.as_ref().map(|d| d.to_string()),
+            default_value: field.default_value.as_ref().map(|v| v.to_string()),
+            is_deprecated: field.is_deprecated(),
+            deprecation_reason: field.deprecation_reason(),
+            arguments: Vec::new(),
+            owners: self.field_owners(type_name, &field.name),
+        }
+    }
+}
+
+/// Federation's own types, such as `join__Graph`, `link__Import` and `_Service`.
+pub(crate) fn is_federation_type(name: &str) -> bool {
+    name.starts_with('_') || name.contains("__")
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_compiler::name;
+    use speculoos::prelude::*;
+
+    use crate::ParsedSchema;
+
+    #[test]
+    fn supergraph_reference_leaves_out_federation_types() {
+        let schema = ParsedSchema::parse(
+            include_str!("test_fixtures/supergraph.graphql"),
+            "supergraph.graphql",
+        );
+        let reference = schema.reference();
+        let names: Vec<_> = reference.types.iter().map(|t| t.name().as_str()).collect();
+        assert_that!(names).is_equal_to(vec!["Product", "Query", "Review"]);
+        assert_that!(reference.root_types).is_equal_to(vec![name!("Query")]);
+
+        let product = &reference.types[0];
+        assert_that!(product.owners).has_length(2);
+        let reviews = product.fields.iter().find(|f| f.name == "reviews").unwrap();
+        assert_that!(reviews.field_type.to_string()).is_equal_to("[Review!]!".to_string());
+        assert_that!(reviews.owners).is_equal_to(vec!["reviews".to_string()]);
+        assert_that!(reviews.arguments[0].default_value).is_equal_to(Some("5".to_string()));
+    }
+
+    #[test]
+    fn input_fields_keep_their_full_types() {
+        let schema = ParsedSchema::parse(
+            include_str!("test_fixtures/test_schema.graphql"),
+            "test_schema.graphql",
+        );
+        let reference = schema.reference();
+        let input = reference
+            .types
+            .iter()
+            .find(|t| t.name() == "CreatePostInput")
+            .unwrap();
+        let tags = input.fields.iter().find(|f| f.name == "tags").unwrap();
+        assert_that!(tags.field_type.to_string()).is_equal_to("[String!]".to_string());
+        assert_that!(reference.subgraphs).is_empty();
+    }
+}
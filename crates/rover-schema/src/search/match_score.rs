@@ -37,13 +37,13 @@ impl MatchScore {
 
     /// Returns the best tier across multiple OR'd clauses — the strongest
     /// score from any clause that matches, or `None` if no clause does.
-    pub(super) fn best_of_clauses(
+    pub(super) fn best_of_clauses<'a>(
         name: &str,
         description: Option<&str>,
-        clauses: &[Vec<String>],
+        clauses: impl IntoIterator<Item = &'a [String]>,
     ) -> Option<Self> {
         clauses
-            .iter()
+            .into_iter()
             .filter_map(|terms| Self::new(name, description, terms))
             .min()
     }
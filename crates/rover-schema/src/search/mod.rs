@@ -1,6 +1,8 @@
 mod match_score;
+mod query;
 mod result;
 
+pub use query::SearchQuery;
 pub use result::{ElementKind, SearchResult};
 
 use crate::ParsedSchema;
@@ -14,55 +16,86 @@ impl ParsedSchema {
     /// Results are sorted by relevance (name matches rank above description matches)
     /// then alphabetically by coordinate.
     pub fn search(&self, query: &str, limit: usize, include_deprecated: bool) -> Vec<SearchResult> {
-        let clauses = parse_query(query);
-        if clauses.is_empty() {
+        self.search_query(&SearchQuery::terms(query), limit, include_deprecated)
+    }
+
+    /// Like [`ParsedSchema::search`], but for a [`SearchQuery`] parsed ahead of time, whose
+    /// clauses can also filter by kind, type, deprecation and directive.
+    pub fn search_query(
+        &self,
+        query: &SearchQuery,
+        limit: usize,
+        include_deprecated: bool,
+    ) -> Vec<SearchResult> {
+        let mut results = self.matches(query, include_deprecated);
+        rank(&mut results, limit);
+        results
+    }
+
+    fn matches(&self, query: &SearchQuery, include_deprecated: bool) -> Vec<SearchResult> {
+        if query.is_empty() {
             return Vec::new();
         }
-
-        let mut results: Vec<SearchResult> = self
-            .inner()
+        let include_deprecated = include_deprecated || query.selects_deprecated();
+        self.inner()
             .types
             .iter()
             .filter(|(type_name, _)| !type_name.starts_with("__"))
             .flat_map(|(type_name, ty)| {
-                SearchResult::from_extended_type(self, type_name, ty, &clauses, include_deprecated)
+                SearchResult::from_extended_type(self, type_name, ty, query, include_deprecated)
             })
-            .collect();
-
-        results.sort_by(|a, b| {
-            a.score()
-                .cmp(&b.score())
-                .then_with(|| a.coordinate.to_string().cmp(&b.coordinate.to_string()))
-        });
-        results.truncate(limit);
-        results
+            .collect()
     }
 }
 
-/// Parses a query into OR'd clauses of AND'd terms.
-///
-/// `"create post, delete"` → `[["create","post"], ["delete"]]`.
-/// Empty clauses (from `,,` or trailing/leading `,`) are dropped.
-fn parse_query(query: &str) -> Vec<Vec<String>> {
-    query
-        .split(',')
-        .map(|clause| {
-            clause
-                .split_whitespace()
-                .map(|t| t.to_lowercase())
-                .collect::<Vec<_>>()
+/// Search several subgraph schemas at once, labelling each result with the name of the
+/// subgraph it was found in. Results from every subgraph are ranked together, and ties between
+/// the same coordinate in different subgraphs are broken by subgraph name.
+pub fn search_subgraphs<'a>(
+    subgraphs: impl IntoIterator<Item = (&'a str, &'a ParsedSchema)>,
+    query: &SearchQuery,
+    limit: usize,
+    include_deprecated: bool,
+) -> Vec<SearchResult> {
+    let mut results: Vec<SearchResult> = subgraphs
+        .into_iter()
+        .flat_map(|(name, schema)| {
+            schema
+                .matches(query, include_deprecated)
+                .into_iter()
+                .map(move |mut result| {
+                    result.subgraph = Some(name.to_string());
+                    result
+                })
         })
-        .filter(|terms| !terms.is_empty())
-        .collect()
+        .collect();
+    rank(&mut results, limit);
+    results
+}
+
+fn rank(results: &mut Vec<SearchResult>, limit: usize) {
+    results.sort_by(|a, b| {
+        a.score()
+            .cmp(&b.score())
+            .then_with(|| a.coordinate.to_string().cmp(&b.coordinate.to_string()))
+            .then_with(|| a.subgraph.cmp(&b.subgraph))
+    });
+    results.truncate(limit);
 }
 
 #[cfg(test)]
 mod tests {
+    use indoc::indoc;
     use rstest::{fixture, rstest};
     use speculoos::prelude::*;
 
+    use super::{ElementKind, SearchQuery, search_subgraphs};
     use crate::ParsedSchema;
 
+    fn query(query: &str) -> SearchQuery {
+        query.parse().unwrap()
+    }
+
     #[fixture]
     fn schema() -> ParsedSchema {
         let sdl = include_str!("../test_fixtures/test_schema.graphql");
@@ -226,4 +259,61 @@ mod tests {
         let results = schema.search(", email,,", 10, false);
         assert_that!(&results).matching_contains(|r| r.coordinate.to_string() == "User.email");
     }
+
+    #[rstest]
+    fn test_search_filters_by_kind_and_type(schema: ParsedSchema) {
+        let results = schema.search_query(&query("kind:input_field type:ID"), 10, false);
+        assert_that!(
+            results
+                .iter()
+                .map(|r| r.coordinate.to_string())
+                .collect::<Vec<_>>()
+        )
+        .is_equal_to(vec!["CreatePostInput.categoryId".to_string()]);
+    }
+
+    #[rstest]
+    fn test_search_deprecated_filter_includes_deprecated(schema: ParsedSchema) {
+        let results = schema.search_query(&query("deprecated:true"), 10, false);
+        assert_that!(&results).matching_contains(|r| r.coordinate.to_string() == "User.legacyId");
+        assert_that!(results.iter().all(|r| r.kind != ElementKind::Type)).is_true();
+    }
+
+    #[test]
+    fn test_search_subgraphs_labels_results_with_their_subgraph() {
+        let products = ParsedSchema::parse(
+            indoc! {r#"
+                type Product @key(fields: "id") { id: ID! email: String }
+                type Query { product(id: ID!): Product }
+            "#},
+            "products.graphql",
+        );
+        let accounts = ParsedSchema::parse(
+            indoc! {r#"
+                type User @key(fields: "id") { id: ID! email: String! }
+                type Query { me: User }
+            "#},
+            "accounts.graphql",
+        );
+        let subgraphs = [("products", &products), ("accounts", &accounts)];
+
+        let results = search_subgraphs(subgraphs, &query("email"), 10, false);
+        let labelled = results
+            .iter()
+            .map(|r| format!("{} in {}", r.coordinate, r.subgraph.as_deref().unwrap()))
+            .collect::<Vec<_>>();
+        assert_that!(labelled).is_equal_to(vec![
+            "Product.email in products".to_string(),
+            "User.email in accounts".to_string(),
+        ]);
+
+        let results = search_subgraphs(subgraphs, &query("directive:@key"), 10, false);
+        assert_that!(
+            results
+                .iter()
+                .map(|r| r.coordinate.to_string())
+                .collect::<Vec<_>>()
+        )
+        .is_equal_to(vec!["Product".to_string(), "User".to_string()]);
+    }
 }
@@ -0,0 +1,245 @@
+use std::str::FromStr;
+
+use super::{ElementKind, match_score::MatchScore};
+use crate::SchemaError;
+
+/// A parsed search query: comma-separated, OR'd clauses of AND'd terms and filters.
+///
+/// `"email kind:field, phone"` → `[[email, kind:field], [phone]]`. Filters are written
+/// `key:value`:
+///
+/// - `kind:` — `type`, `field`, `input_field` or `enum_value`
+/// - `type:` — the type a field or input field returns, ignoring list and non-null wrappers,
+///   or the enum an enum value belongs to
+/// - `deprecated:` — `true` or `false`
+/// - `directive:` — a directive applied to the element, with or without its `@`
+///
+/// A clause made only of filters matches every element they accept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    clauses: Vec<Clause>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Clause {
+    terms: Vec<String>,
+    filters: Vec<Filter>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Kind(ElementKind),
+    Type(String),
+    Deprecated(bool),
+    Directive(String),
+}
+
+/// What filters can see of an element the query is matched against.
+pub(super) struct Element<'a> {
+    pub(super) name: &'a str,
+    pub(super) description: Option<&'a str>,
+    pub(super) kind: &'a ElementKind,
+    /// The named type a field returns, or the enum an enum value belongs to
+    pub(super) named_type: Option<&'a str>,
+    pub(super) deprecated: bool,
+    pub(super) directives: Vec<&'a str>,
+}
+
+impl SearchQuery {
+    /// A query made only of terms, with no filters: a word like `kind:field` is searched for
+    /// as it's written.
+    ///
+    /// `"create post, delete"` → `[[create, post], [delete]]`.
+    /// Empty clauses (from `,,` or trailing/leading `,`) are dropped.
+    pub fn terms(query: &str) -> Self {
+        let clauses = query
+            .split(',')
+            .map(|clause| Clause {
+                terms: clause
+                    .split_whitespace()
+                    .map(|t| t.to_lowercase())
+                    .collect(),
+                filters: Vec::new(),
+            })
+            .filter(|clause| !clause.terms.is_empty())
+            .collect();
+        Self { clauses }
+    }
+
+    /// Whether the query has no clauses, and so matches nothing.
+    pub const fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// Whether any clause asks for deprecated elements, which are then searched even when
+    /// they would otherwise be left out.
+    pub(super) fn selects_deprecated(&self) -> bool {
+        self.clauses
+            .iter()
+            .any(|clause| clause.filters.contains(&Filter::Deprecated(true)))
+    }
+
+    /// Returns the best tier at which any clause matches `element`, or `None` if none do.
+    pub(super) fn score(&self, element: &Element) -> Option<MatchScore> {
+        let accepted = self
+            .clauses
+            .iter()
+            .filter(|clause| clause.filters.iter().all(|filter| filter.accepts(element)));
+        if accepted.clone().any(|clause| clause.terms.is_empty()) {
+            return Some(MatchScore::Exact);
+        }
+        MatchScore::best_of_clauses(
+            element.name,
+            element.description,
+            accepted.map(|clause| clause.terms.as_slice()),
+        )
+    }
+}
+
+impl FromStr for SearchQuery {
+    type Err = SchemaError;
+
+    /// Empty clauses (from `,,` or trailing/leading `,`) are dropped.
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let mut clauses = Vec::new();
+        for clause in query.split(',') {
+            let mut terms = Vec::new();
+            let mut filters = Vec::new();
+            for word in clause.split_whitespace() {
+                match word.split_once(':') {
+                    Some((key, value)) => filters.push(Filter::parse(key, value)?),
+                    None => terms.push(word.to_lowercase()),
+                }
+            }
+            if !terms.is_empty() || !filters.is_empty() {
+                clauses.push(Clause { terms, filters });
+            }
+        }
+        Ok(Self { clauses })
+    }
+}
+
+impl Filter {
+    fn parse(key: &str, value: &str) -> Result<Self, SchemaError> {
+        let invalid = |reason: &str| SchemaError::InvalidSearchFilter {
+            filter: format!("{key}:{value}"),
+            reason: reason.to_string(),
+        };
+        if value.is_empty() {
+            return Err(invalid("the filter has no value"));
+        }
+        match key.to_lowercase().as_str() {
+            "kind" => match value.to_lowercase().replace('-', "_").as_str() {
+                "type" => Ok(Self::Kind(ElementKind::Type)),
+                "field" => Ok(Self::Kind(ElementKind::Field)),
+                "input_field" => Ok(Self::Kind(ElementKind::InputField)),
+                "enum_value" => Ok(Self::Kind(ElementKind::EnumValue)),
+                _ => Err(invalid(
+                    "kinds are `type`, `field`, `input_field` and `enum_value`",
+                )),
+            },
+            "type" => Ok(Self::Type(
+                value
+                    .trim_matches(|c| matches!(c, '[' | ']' | '!'))
+                    .to_string(),
+            )),
+            "deprecated" => match value.to_lowercase().as_str() {
+                "true" => Ok(Self::Deprecated(true)),
+                "false" => Ok(Self::Deprecated(false)),
+                _ => Err(invalid("`deprecated:` takes `true` or `false`")),
+            },
+            "directive" => Ok(Self::Directive(
+                value.strip_prefix('@').unwrap_or(value).to_string(),
+            )),
+            _ => Err(invalid(
+                "filters are `kind:`, `type:`, `deprecated:` and `directive:`",
+            )),
+        }
+    }
+
+    fn accepts(&self, element: &Element) -> bool {
+        match self {
+            Self::Kind(kind) => element.kind == kind,
+            Self::Type(name) => element
+                .named_type
+                .is_some_and(|named_type| named_type.eq_ignore_ascii_case(name)),
+            Self::Deprecated(deprecated) => element.deprecated == *deprecated,
+            Self::Directive(name) => element
+                .directives
+                .iter()
+                .any(|directive| directive.eq_ignore_ascii_case(name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn element<'a>(kind: &'a ElementKind, directives: Vec<&'a str>) -> Element<'a> {
+        Element {
+            name: "productId",
+            description: None,
+            kind,
+            named_type: Some("ID"),
+            deprecated: false,
+            directives,
+        }
+    }
+
+    #[test]
+    fn parses_terms_and_filters_per_clause() {
+        let query: SearchQuery = "Email kind:field, directive:@key".parse().unwrap();
+        assert_that!(query.clauses).is_equal_to(vec![
+            Clause {
+                terms: vec!["email".to_string()],
+                filters: vec![Filter::Kind(ElementKind::Field)],
+            },
+            Clause {
+                terms: Vec::new(),
+                filters: vec![Filter::Directive("key".to_string())],
+            },
+        ]);
+    }
+
+    #[test]
+    fn terms_never_parses_filters() {
+        let query = SearchQuery::terms("Email kind:field,, phone");
+        assert_that!(query.clauses).is_equal_to(vec![
+            Clause {
+                terms: vec!["email".to_string(), "kind:field".to_string()],
+                filters: Vec::new(),
+            },
+            Clause {
+                terms: vec!["phone".to_string()],
+                filters: Vec::new(),
+            },
+        ]);
+    }
+
+    #[rstest]
+    #[case::unknown_key("owner:products", "filters are")]
+    #[case::unknown_kind("kind:object", "kinds are")]
+    #[case::not_a_boolean("deprecated:yes", "takes `true` or `false`")]
+    #[case::missing_value("type:", "has no value")]
+    fn rejects_invalid_filters(#[case] query: &str, #[case] reason: &str) {
+        let err = query.parse::<SearchQuery>().unwrap_err();
+        assert_that!(err.to_string()).contains(reason);
+    }
+
+    #[test]
+    fn filter_only_clauses_match_everything_they_accept() {
+        let field = ElementKind::Field;
+        let query: SearchQuery = "type:[ID!]! kind:field".parse().unwrap();
+        assert_that!(query.score(&element(&field, Vec::new())))
+            .is_equal_to(Some(MatchScore::Exact));
+
+        let query: SearchQuery = "product directive:key".parse().unwrap();
+        assert_that!(query.score(&element(&field, Vec::new()))).is_none();
+        assert_that!(query.score(&element(&field, vec!["key"])))
+            .is_equal_to(Some(MatchScore::Exact));
+    }
+}
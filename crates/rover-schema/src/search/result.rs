@@ -1,11 +1,17 @@
 use apollo_compiler::{
     Name,
+    ast::DirectiveList,
     coordinate::{SchemaCoordinate, TypeAttributeCoordinate, TypeCoordinate},
     schema::ExtendedType,
 };
 
-use super::match_score::MatchScore;
-use crate::{ParsedSchema, describe::deprecated::IsDeprecated, root_paths::RootPath};
+use super::{
+    match_score::MatchScore,
+    query::{Element, SearchQuery},
+};
+use crate::{
+    ParsedSchema, describe::deprecated::IsDeprecated, root_paths::RootPath, usage::OperationRef,
+};
 
 /// The kind of schema element a search result refers to.
 #[derive(Debug, Clone, serde::Serialize, PartialEq)]
@@ -45,6 +51,13 @@ pub struct SearchResult {
     pub description: Option<String>,
     /// Root paths from Query/Mutation to the containing type.
     pub via: Vec<RootPath>,
+    /// The subgraph the result was found in, when searching several subgraphs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subgraph: Option<String>,
+    /// The operations that select the element, when searching with operations. Only types and
+    /// fields are tracked, so this is always `None` for input fields and enum values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operations: Option<Vec<OperationRef>>,
     #[serde(skip)]
     score: MatchScore,
 }
@@ -66,6 +79,8 @@ impl SearchResult {
             kind: ElementKind::Type,
             description,
             via,
+            subgraph: None,
+            operations: None,
             score,
         }
     }
@@ -89,6 +104,8 @@ impl SearchResult {
             kind,
             description,
             via,
+            subgraph: None,
+            operations: None,
             score,
         }
     }
@@ -100,112 +117,135 @@ impl SearchResult {
     /// Collect all matching results from a single top-level schema type:
     /// the type itself (if it matches) plus any matching fields, input
     /// fields, or enum values it owns.
-    ///
-    /// `clauses` is a list of OR'd term groups — a candidate matches if any
-    /// single clause's terms all match it.
     pub(super) fn from_extended_type(
         schema: &ParsedSchema,
         type_name: &Name,
         ty: &ExtendedType,
-        clauses: &[Vec<String>],
+        query: &SearchQuery,
         include_deprecated: bool,
     ) -> Vec<Self> {
         let mut out = Vec::new();
+        let type_match = |via: Vec<RootPath>| {
+            Self::from_type_match(
+                type_name,
+                ty.description().map(|d| d.to_string()),
+                ty.directives().iter().map(|d| d.name.as_str()).collect(),
+                via,
+                query,
+            )
+        };
         match ty {
             ExtendedType::Object(obj) => {
                 let via = schema.find_root_paths(type_name);
-                let desc = obj.description.as_ref().map(|d| d.to_string());
-                out.extend(Self::from_type_match(type_name, desc, via.clone(), clauses));
+                out.extend(type_match(via.clone()));
                 for (field_name, field) in &obj.fields {
                     if include_deprecated || !field.is_deprecated() {
-                        let fdesc = field.description.as_ref().map(|d| d.to_string());
                         out.extend(Self::from_attribute_match(
                             type_name,
                             field_name,
-                            ElementKind::Field,
-                            fdesc,
+                            AttributeFacts {
+                                kind: ElementKind::Field,
+                                description: field.description.as_ref().map(|d| d.to_string()),
+                                named_type: Some(field.ty.inner_named_type()),
+                                deprecated: field.is_deprecated(),
+                                directives: &field.directives,
+                            },
                             via.clone(),
-                            clauses,
+                            query,
                         ));
                     }
                 }
             }
             ExtendedType::Interface(iface) => {
                 let via = schema.find_root_paths(type_name);
-                let desc = iface.description.as_ref().map(|d| d.to_string());
-                out.extend(Self::from_type_match(type_name, desc, via.clone(), clauses));
+                out.extend(type_match(via.clone()));
                 for (field_name, field) in &iface.fields {
                     if include_deprecated || !field.is_deprecated() {
-                        let fdesc = field.description.as_ref().map(|d| d.to_string());
                         out.extend(Self::from_attribute_match(
                             type_name,
                             field_name,
-                            ElementKind::Field,
-                            fdesc,
+                            AttributeFacts {
+                                kind: ElementKind::Field,
+                                description: field.description.as_ref().map(|d| d.to_string()),
+                                named_type: Some(field.ty.inner_named_type()),
+                                deprecated: field.is_deprecated(),
+                                directives: &field.directives,
+                            },
                             via.clone(),
-                            clauses,
+                            query,
                         ));
                     }
                 }
             }
             ExtendedType::InputObject(inp) => {
-                let desc = inp.description.as_ref().map(|d| d.to_string());
-                out.extend(Self::from_type_match(type_name, desc, Vec::new(), clauses));
+                out.extend(type_match(Vec::new()));
                 for (field_name, field) in &inp.fields {
                     if include_deprecated || !field.is_deprecated() {
-                        let fdesc = field.description.as_ref().map(|d| d.to_string());
                         out.extend(Self::from_attribute_match(
                             type_name,
                             field_name,
-                            ElementKind::InputField,
-                            fdesc,
+                            AttributeFacts {
+                                kind: ElementKind::InputField,
+                                description: field.description.as_ref().map(|d| d.to_string()),
+                                named_type: Some(field.ty.inner_named_type()),
+                                deprecated: field.is_deprecated(),
+                                directives: &field.directives,
+                            },
                             Vec::new(),
-                            clauses,
+                            query,
                         ));
                     }
                 }
             }
             ExtendedType::Enum(e) => {
                 let via = schema.find_root_paths(type_name);
-                let desc = e.description.as_ref().map(|d| d.to_string());
-                out.extend(Self::from_type_match(type_name, desc, via.clone(), clauses));
+                out.extend(type_match(via.clone()));
                 for (val_name, val) in &e.values {
                     if include_deprecated || !val.is_deprecated() {
-                        let vdesc = val.description.as_ref().map(|d| d.to_string());
                         out.extend(Self::from_attribute_match(
                             type_name,
                             val_name,
-                            ElementKind::EnumValue,
-                            vdesc,
+                            AttributeFacts {
+                                kind: ElementKind::EnumValue,
+                                description: val.description.as_ref().map(|d| d.to_string()),
+                                named_type: Some(type_name),
+                                deprecated: val.is_deprecated(),
+                                directives: &val.directives,
+                            },
                             via.clone(),
-                            clauses,
+                            query,
                         ));
                     }
                 }
             }
-            ExtendedType::Union(u) => {
-                let desc = u.description.as_ref().map(|d| d.to_string());
+            ExtendedType::Union(_) => {
                 let via = schema.find_root_paths(type_name);
-                out.extend(Self::from_type_match(type_name, desc, via, clauses));
+                out.extend(type_match(via));
             }
-            ExtendedType::Scalar(s) => {
-                let desc = s.description.as_ref().map(|d| d.to_string());
-                out.extend(Self::from_type_match(type_name, desc, Vec::new(), clauses));
+            ExtendedType::Scalar(_) => {
+                out.extend(type_match(Vec::new()));
             }
         }
         out
     }
 
-    /// Build a result for a top-level type when any clause's terms all match
-    /// the name or description. Returns `None` when no clause matches.
+    /// Build a result for a top-level type when any clause of `query` matches
+    /// it. Returns `None` when no clause matches.
     fn from_type_match(
         type_name: &Name,
         description: Option<String>,
+        directives: Vec<&str>,
         via: Vec<RootPath>,
-        clauses: &[Vec<String>],
+        query: &SearchQuery,
     ) -> Option<Self> {
-        let score =
-            MatchScore::best_of_clauses(type_name.as_str(), description.as_deref(), clauses)?;
+        let score = query.score(&Element {
+            name: type_name.as_str(),
+            description: description.as_deref(),
+            kind: &ElementKind::Type,
+            named_type: None,
+            deprecated: false,
+            directives,
+        })?;
         Some(
             Self::for_type()
                 .type_name(type_name)
@@ -217,23 +257,28 @@ impl SearchResult {
     }
 
     /// Build a result for a single attribute — field, input field, or enum
-    /// value — when any clause's terms all match its name or description.
+    /// value — when any clause of `query` matches it.
     fn from_attribute_match(
         type_name: &Name,
         attribute: &Name,
-        kind: ElementKind,
-        description: Option<String>,
+        facts: AttributeFacts,
         via: Vec<RootPath>,
-        clauses: &[Vec<String>],
+        query: &SearchQuery,
     ) -> Option<Self> {
-        let score =
-            MatchScore::best_of_clauses(attribute.as_str(), description.as_deref(), clauses)?;
+        let score = query.score(&Element {
+            name: attribute.as_str(),
+            description: facts.description.as_deref(),
+            kind: &facts.kind,
+            named_type: facts.named_type.map(Name::as_str),
+            deprecated: facts.deprecated,
+            directives: facts.directives.iter().map(|d| d.name.as_str()).collect(),
+        })?;
         Some(
             Self::for_attribute()
                 .type_name(type_name)
                 .attribute(attribute)
-                .kind(kind)
-                .maybe_description(description)
+                .kind(facts.kind)
+                .maybe_description(facts.description)
                 .via(via)
                 .score(score)
                 .call(),
@@ -241,6 +286,15 @@ impl SearchResult {
     }
 }
 
+/// What a field, input field, or enum value exposes to search filters.
+struct AttributeFacts<'a> {
+    kind: ElementKind,
+    description: Option<String>,
+    named_type: Option<&'a Name>,
+    deprecated: bool,
+    directives: &'a DirectiveList,
+}
+
 fn serialize_coordinate<S: serde::Serializer>(
     coord: &SchemaCoordinate,
     s: S,
@@ -263,8 +317,8 @@ mod tests {
     }
 
     /// Builds a single-clause input from a whitespace-separated string.
-    fn terms(s: &str) -> Vec<Vec<String>> {
-        vec![s.split_whitespace().map(str::to_lowercase).collect()]
+    fn terms(s: &str) -> SearchQuery {
+        SearchQuery::terms(s)
     }
 
     fn ty_of<'a>(schema: &'a ParsedSchema, type_name: &Name) -> &'a ExtendedType {
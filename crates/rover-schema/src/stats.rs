@@ -0,0 +1,649 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use apollo_compiler::{
+    Name,
+    ast::DirectiveList,
+    schema::{ExtendedType, FieldDefinition},
+};
+use serde::Serialize;
+
+use crate::{ParsedSchema, describe::deprecated::IsDeprecated, reference::is_federation_type};
+
+/// Size and quality statistics for a GraphQL schema.
+///
+/// Built-in types are left out, as are the types federation adds to a supergraph.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaStats {
+    /// The SDL source location or identifier that was parsed.
+    pub schema_source: String,
+    /// Number of types of each kind.
+    pub types: TypeCounts,
+    /// Number of fields on object and interface types, including root types.
+    pub fields: usize,
+    /// Number of fields on input object types.
+    pub input_fields: usize,
+    /// Number of arguments on fields.
+    pub arguments: usize,
+    /// Number of enum values.
+    pub enum_values: usize,
+    /// Number of deprecated elements of each kind.
+    pub deprecated: DeprecationCounts,
+    /// How many elements of each kind have a description.
+    pub descriptions: DescriptionCoverage,
+    /// Custom scalars, with how often they're referenced.
+    pub custom_scalars: Vec<CustomScalarStats>,
+    /// The types furthest from a root type, most distant first. Root types are left out.
+    pub deepest_types: Vec<TypeDepth>,
+    /// The types with the most fields, widest first.
+    pub widest_types: Vec<TypeWidth>,
+    /// The types referenced most often by fields, arguments, input fields, union members and
+    /// implemented interfaces, most referenced first.
+    pub most_referenced_types: Vec<TypeReferences>,
+    /// What each subgraph contributes to a supergraph. Empty for other schemas.
+    pub subgraphs: Vec<SubgraphContribution>,
+}
+
+/// Number of types of each kind.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct TypeCounts {
+    /// Every counted type, including root types.
+    pub total: usize,
+    /// Object types, excluding root types.
+    pub objects: usize,
+    /// Interface types.
+    pub interfaces: usize,
+    /// Union types.
+    pub unions: usize,
+    /// Enum types.
+    pub enums: usize,
+    /// Input object types.
+    pub inputs: usize,
+    /// Custom scalar types.
+    pub scalars: usize,
+}
+
+/// Number of deprecated elements of each kind.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct DeprecationCounts {
+    /// Every deprecated element.
+    pub total: usize,
+    /// Deprecated fields on object and interface types.
+    pub fields: usize,
+    /// Deprecated input fields.
+    pub input_fields: usize,
+    /// Deprecated field arguments.
+    pub arguments: usize,
+    /// Deprecated enum values.
+    pub enum_values: usize,
+}
+
+/// How many elements of each kind have a description.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct DescriptionCoverage {
+    /// Every element.
+    pub overall: Coverage,
+    /// Types.
+    pub types: Coverage,
+    /// Fields on object and interface types.
+    pub fields: Coverage,
+    /// Input fields.
+    pub input_fields: Coverage,
+    /// Field arguments.
+    pub arguments: Coverage,
+    /// Enum values.
+    pub enum_values: Coverage,
+}
+
+/// How many of a set of elements have a description.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Coverage {
+    /// Elements with a description.
+    pub described: usize,
+    /// All elements.
+    pub total: usize,
+}
+
+impl Coverage {
+    /// The share of elements with a description, from 0 to 1, or `None` if there are none.
+    pub fn share(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.described as f64 / self.total as f64)
+    }
+
+    fn count(&mut self, description: Option<&impl AsRef<str>>) {
+        self.total += 1;
+        if description.is_some_and(|d| !d.as_ref().trim().is_empty()) {
+            self.described += 1;
+        }
+    }
+
+    const fn add(&mut self, other: Coverage) {
+        self.described += other.described;
+        self.total += other.total;
+    }
+}
+
+/// A custom scalar and how it's used.
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomScalarStats {
+    /// The scalar's name.
+    pub name: Name,
+    /// How many fields, arguments and input fields have the scalar as their type.
+    pub references: usize,
+    /// The URL of the scalar's specification, from `@specifiedBy`.
+    pub specified_by: Option<String>,
+}
+
+/// How far a type is from the root types.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeDepth {
+    /// The type's name.
+    pub name: Name,
+    /// The fewest fields to select from a root type to reach the type.
+    pub depth: usize,
+}
+
+/// How many fields a type has.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeWidth {
+    /// The type's name.
+    pub name: Name,
+    /// Fields of an object or interface type, or input fields of an input object type.
+    pub fields: usize,
+}
+
+/// How often a type is referenced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeReferences {
+    /// The type's name.
+    pub name: Name,
+    /// The number of references.
+    pub references: usize,
+}
+
+/// What a subgraph contributes to a supergraph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SubgraphContribution {
+    /// The subgraph's name.
+    pub name: String,
+    /// Types the subgraph declares.
+    pub types: usize,
+    /// Fields and input fields the subgraph resolves.
+    pub fields: usize,
+    /// Fields and input fields no other subgraph resolves.
+    pub exclusive_fields: usize,
+}
+
+impl ParsedSchema {
+    /// Generate statistics for the schema, listing at most `top` types in each ranking.
+    pub fn stats(&self, top: usize) -> SchemaStats {
+        let schema = self.inner();
+        let overview = self.overview();
+        let counted = |names: &[Name]| {
+            names
+                .iter()
+                .filter(|name| !is_federation_type(name))
+                .count()
+        };
+        let root_types = schema
+            .schema_definition
+            .iter_root_operations()
+            .map(|(_, name)| name.name.clone())
+            .collect::<HashSet<_>>();
+        let mut types = TypeCounts {
+            objects: counted(&overview.objects),
+            interfaces: counted(&overview.interfaces),
+            unions: counted(&overview.unions),
+            enums: counted(&overview.enums),
+            inputs: counted(&overview.inputs),
+            scalars: counted(&overview.scalars),
+            ..Default::default()
+        };
+        types.total = types.objects
+            + types.interfaces
+            + types.unions
+            + types.enums
+            + types.inputs
+            + types.scalars
+            + root_types.len();
+
+        let mut stats = SchemaStats {
+            schema_source: overview.schema_source,
+            types,
+            fields: 0,
+            input_fields: 0,
+            arguments: 0,
+            enum_values: 0,
+            deprecated: DeprecationCounts::default(),
+            descriptions: DescriptionCoverage::default(),
+            custom_scalars: Vec::new(),
+            deepest_types: Vec::new(),
+            widest_types: Vec::new(),
+            most_referenced_types: Vec::new(),
+            subgraphs: Vec::new(),
+        };
+        let mut references: HashMap<&Name, usize> = HashMap::new();
+        let mut reference = |name: &Name| {
+            if let Some(ty) = schema.types.get(name)
+                && !ty.is_built_in()
+                && !is_federation_type(name)
+            {
+                *references.entry(ty.name()).or_default() += 1;
+            }
+        };
+        let mut widths = Vec::new();
+
+        for (name, ty) in self.counted_types() {
+            stats.descriptions.types.count(ty.description());
+            match ty {
+                ExtendedType::Object(object) => {
+                    for interface in &object.implements_interfaces {
+                        reference(&interface.name);
+                    }
+                    stats.count_fields(object.fields.values().map(|f| &*f.node), &mut reference);
+                    widths.push(TypeWidth {
+                        name: name.clone(),
+                        fields: object.fields.len(),
+                    });
+                }
+                ExtendedType::Interface(interface) => {
+                    for implemented in &interface.implements_interfaces {
+                        reference(&implemented.name);
+                    }
+                    stats.count_fields(interface.fields.values().map(|f| &*f.node), &mut reference);
+                    widths.push(TypeWidth {
+                        name: name.clone(),
+                        fields: interface.fields.len(),
+                    });
+                }
+                ExtendedType::InputObject(input) => {
+                    for field in input.fields.values() {
+                        stats.input_fields += 1;
+                        stats
+                            .descriptions
+                            .input_fields
+                            .count(field.description.as_ref());
+                        if field.is_deprecated() {
+                            stats.deprecated.input_fields += 1;
+                        }
+                        reference(field.ty.inner_named_type());
+                    }
+                    widths.push(TypeWidth {
+                        name: name.clone(),
+                        fields: input.fields.len(),
+                    });
+                }
+                ExtendedType::Enum(enum_type) => {
+                    for value in enum_type.values.values() {
+                        stats.enum_values += 1;
+                        stats
+                            .descriptions
+                            .enum_values
+                            .count(value.description.as_ref());
+                        if value.is_deprecated() {
+                            stats.deprecated.enum_values += 1;
+                        }
+                    }
+                }
+                ExtendedType::Union(union) => {
+                    for member in &union.members {
+                        reference(&member.name);
+                    }
+                }
+                ExtendedType::Scalar(_) => {}
+            }
+        }
+
+        let deprecated = &mut stats.deprecated;
+        deprecated.total = deprecated.fields
+            + deprecated.input_fields
+            + deprecated.arguments
+            + deprecated.enum_values;
+        let descriptions = &mut stats.descriptions;
+        for coverage in [
+            descriptions.types,
+            descriptions.fields,
+            descriptions.input_fields,
+            descriptions.arguments,
+            descriptions.enum_values,
+        ] {
+            descriptions.overall.add(coverage);
+        }
+
+        stats.custom_scalars = self
+            .counted_types()
+            .filter_map(|(name, ty)| match ty {
+                ExtendedType::Scalar(scalar) => Some(CustomScalarStats {
+                    name: name.clone(),
+                    references: references.get(name).copied().unwrap_or_default(),
+                    specified_by: scalar
+                        .directives
+                        .get("specifiedBy")
+                        .and_then(|d| d.specified_argument_by_name("url"))
+                        .and_then(|url| url.as_str())
+                        .map(str::to_string),
+                }),
+                _ => None,
+            })
+            .collect();
+        stats.custom_scalars.sort_by(|a, b| {
+            b.references
+                .cmp(&a.references)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let mut depths = self
+            .depths()
+            .into_iter()
+            .filter(|(_, depth)| *depth > 0)
+            .map(|(name, depth)| TypeDepth { name, depth })
+            .collect::<Vec<_>>();
+        depths.sort_by(|a, b| b.depth.cmp(&a.depth).then_with(|| a.name.cmp(&b.name)));
+        depths.truncate(top);
+        stats.deepest_types = depths;
+
+        widths.sort_by(|a, b| b.fields.cmp(&a.fields).then_with(|| a.name.cmp(&b.name)));
+        widths.truncate(top);
+        stats.widest_types = widths;
+
+        let mut most_referenced = references
+            .into_iter()
+            .map(|(name, references)| TypeReferences {
+                name: name.clone(),
+                references,
+            })
+            .collect::<Vec<_>>();
+        most_referenced.sort_by(|a, b| {
+            b.references
+                .cmp(&a.references)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        most_referenced.truncate(top);
+        stats.most_referenced_types = most_referenced;
+
+        stats.subgraphs = self.subgraph_contributions();
+        stats
+    }
+
+    /// Types counted in statistics: those defined by the schema, other than federation's.
+    fn counted_types(&self) -> impl Iterator<Item = (&Name, &ExtendedType)> {
+        self.inner()
+            .types
+            .iter()
+            .filter(|(name, ty)| !ty.is_built_in() && !is_federation_type(name))
+    }
+
+    /// The fewest fields to select from a root type to reach each type that can be reached.
+    fn depths(&self) -> HashMap<Name, usize> {
+        let schema = self.inner();
+        let implementers = schema.implementers_map();
+        let mut depths = HashMap::new();
+        let mut queue = VecDeque::new();
+        for (_, root) in schema.schema_definition.iter_root_operations() {
+            depths.insert(root.name.clone(), 0);
+            queue.push_back(root.name.clone());
+        }
+        while let Some(name) = queue.pop_front() {
+            let depth = depths[&name];
+            let (fields, members): (Vec<&Name>, Vec<&Name>) = match schema.types.get(&name) {
+                Some(ExtendedType::Object(object)) => (
+                    object
+                        .fields
+                        .values()
+                        .map(|f| f.ty.inner_named_type())
+                        .collect(),
+                    Vec::new(),
+                ),
+                Some(ExtendedType::Interface(interface)) => (
+                    interface
+                        .fields
+                        .values()
+                        .map(|f| f.ty.inner_named_type())
+                        .collect(),
+                    implementers
+                        .get(&name)
+                        .map(|implementers| implementers.iter().collect())
+                        .unwrap_or_default(),
+                ),
+                Some(ExtendedType::Union(union)) => (
+                    Vec::new(),
+                    union.members.iter().map(|member| &member.name).collect(),
+                ),
+                _ => continue,
+            };
+            // Selecting a field goes one level deeper, but a union member or implementation
+            // is reached through a fragment at the same depth.
+            let next = fields
+                .into_iter()
+                .map(|name| (name, depth + 1))
+                .chain(members.into_iter().map(|name| (name, depth)));
+            for (next, next_depth) in next {
+                if is_federation_type(next)
+                    || schema.types.get(next).is_none_or(|ty| ty.is_built_in())
+                {
+                    continue;
+                }
+                match depths.get(next) {
+                    Some(&known) if known <= next_depth => {}
+                    _ => {
+                        depths.insert(next.clone(), next_depth);
+                        if next_depth == depth {
+                            queue.push_front(next.clone());
+                        } else {
+                            queue.push_back(next.clone());
+                        }
+                    }
+                }
+            }
+        }
+        depths
+    }
+
+    fn subgraph_contributions(&self) -> Vec<SubgraphContribution> {
+        let mut contributions = self
+            .subgraph_names()
+            .into_iter()
+            .map(|name| SubgraphContribution {
+                name,
+                types: 0,
+                fields: 0,
+                exclusive_fields: 0,
+            })
+            .collect::<Vec<_>>();
+        for (name, ty) in self.counted_types() {
+            for owner in self.type_owners(name) {
+                if let Some(contribution) = contributions.iter_mut().find(|c| c.name == owner) {
+                    contribution.types += 1;
+                }
+            }
+            let field_names: Vec<&Name> = match ty {
+                ExtendedType::Object(object) => object.fields.keys().collect(),
+                ExtendedType::Interface(interface) => interface.fields.keys().collect(),
+                ExtendedType::InputObject(input) => input.fields.keys().collect(),
+                _ => continue,
+            };
+            for field_name in field_names {
+                let owners = self.field_owners(name, field_name);
+                for owner in &owners {
+                    if let Some(contribution) = contributions.iter_mut().find(|c| &c.name == owner)
+                    {
+                        contribution.fields += 1;
+                        if owners.len() == 1 {
+                            contribution.exclusive_fields += 1;
+                        }
+                    }
+                }
+            }
+        }
+        contributions
+    }
+}
+
+impl SchemaStats {
+    fn count_fields<'a>(
+        &mut self,
+        fields: impl Iterator<Item = &'a FieldDefinition>,
+        reference: &mut impl FnMut(&Name),
+    ) {
+        for field in fields {
+            self.fields += 1;
+            self.descriptions.fields.count(field.description.as_ref());
+            if is_deprecated(&field.directives) {
+                self.deprecated.fields += 1;
+            }
+            reference(field.ty.inner_named_type());
+            for argument in &field.arguments {
+                self.arguments += 1;
+                self.descriptions
+                    .arguments
+                    .count(argument.description.as_ref());
+                if is_deprecated(&argument.directives) {
+                    self.deprecated.arguments += 1;
+                }
+                reference(argument.ty.inner_named_type());
+            }
+        }
+    }
+}
+
+fn is_deprecated(directives: &DirectiveList) -> bool {
+    directives.is_deprecated()
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_compiler::name;
+    use indoc::indoc;
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[fixture]
+    fn schema() -> ParsedSchema {
+        ParsedSchema::parse(
+            indoc! {r#"
+                "Entry points"
+                type Query {
+                  "Look up a user"
+                  user(id: ID!, "Include archived users" archived: Boolean @deprecated): User
+                  search(term: String!): [SearchResult!]!
+                }
+
+                type User implements Node {
+                  id: ID!
+                  "When the user signed up"
+                  createdAt: DateTime
+                  homepage: URL @deprecated(reason: "Use links")
+                  posts: [Post!]!
+                }
+
+                type Post implements Node {
+                  id: ID!
+                  publishedAt: DateTime
+                  status: Status
+                }
+
+                interface Node { id: ID! }
+
+                union SearchResult = User | Post
+
+                enum Status { "Visible to everyone" DRAFT PUBLISHED ARCHIVED @deprecated }
+
+                input PostFilter { status: Status, after: DateTime }
+
+                scalar DateTime
+                scalar URL @specifiedBy(url: "https://url.spec.whatwg.org/")
+            "#},
+            "schema.graphql",
+        )
+    }
+
+    #[rstest]
+    fn counts_types_fields_and_deprecations(schema: ParsedSchema) {
+        let stats = schema.stats(3);
+        assert_that!(stats.types.total).is_equal_to(9);
+        assert_that!(stats.types.objects).is_equal_to(2);
+        assert_that!(stats.types.scalars).is_equal_to(2);
+        assert_that!(stats.fields).is_equal_to(10);
+        assert_that!(stats.input_fields).is_equal_to(2);
+        assert_that!(stats.arguments).is_equal_to(3);
+        assert_that!(stats.enum_values).is_equal_to(3);
+        assert_that!(stats.deprecated.total).is_equal_to(3);
+        assert_that!(stats.deprecated.arguments).is_equal_to(1);
+    }
+
+    #[rstest]
+    fn measures_description_coverage(schema: ParsedSchema) {
+        let stats = schema.stats(3);
+        assert_that!(stats.descriptions.types).is_equal_to(Coverage {
+            described: 1,
+            total: 9,
+        });
+        assert_that!(stats.descriptions.fields).is_equal_to(Coverage {
+            described: 2,
+            total: 10,
+        });
+        assert_that!(stats.descriptions.overall).is_equal_to(Coverage {
+            described: 5,
+            total: 27,
+        });
+        assert_that!(Coverage::default().share()).is_none();
+    }
+
+    #[rstest]
+    fn ranks_scalars_depth_width_and_references(schema: ParsedSchema) {
+        let stats = schema.stats(3);
+        let scalars = stats
+            .custom_scalars
+            .iter()
+            .map(|scalar| (scalar.name.as_str(), scalar.references))
+            .collect::<Vec<_>>();
+        assert_that!(scalars).is_equal_to(vec![("DateTime", 3), ("URL", 1)]);
+        assert_that!(stats.custom_scalars[1].specified_by.as_deref())
+            .is_equal_to(Some("https://url.spec.whatwg.org/"));
+
+        assert_that!(stats.deepest_types).is_equal_to(vec![
+            TypeDepth {
+                name: name!("DateTime"),
+                depth: 2,
+            },
+            TypeDepth {
+                name: name!("Status"),
+                depth: 2,
+            },
+            TypeDepth {
+                name: name!("URL"),
+                depth: 2,
+            },
+        ]);
+        assert_that!(stats.widest_types[0]).is_equal_to(TypeWidth {
+            name: name!("User"),
+            fields: 4,
+        });
+        assert_that!(stats.most_referenced_types[0]).is_equal_to(TypeReferences {
+            name: name!("DateTime"),
+            references: 3,
+        });
+    }
+
+    #[test]
+    fn counts_subgraph_contributions_of_a_supergraph() {
+        let sdl = include_str!("test_fixtures/supergraph.graphql");
+        let stats = ParsedSchema::parse(sdl, "supergraph.graphql").stats(5);
+        assert_that!(stats.types.total).is_equal_to(3);
+        assert_that!(stats.subgraphs).is_equal_to(vec![
+            SubgraphContribution {
+                name: "products".to_string(),
+                types: 2,
+                fields: 7,
+                exclusive_fields: 5,
+            },
+            SubgraphContribution {
+                name: "reviews".to_string(),
+                types: 3,
+                fields: 6,
+                exclusive_fields: 4,
+            },
+        ]);
+    }
+}
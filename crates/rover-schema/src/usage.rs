@@ -0,0 +1,253 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use apollo_compiler::{
+    Name,
+    ast::{self, Definition, OperationType, Selection},
+    coordinate::SchemaCoordinate,
+    schema::ExtendedType,
+};
+use serde::Serialize;
+
+use crate::{ElementKind, ParsedSchema, SearchResult};
+
+/// An operation that selects a schema element.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct OperationRef {
+    /// The operation's name, or `None` for an anonymous operation.
+    pub name: Option<String>,
+    /// The file the operation is defined in.
+    pub file: String,
+}
+
+/// Which operations select each type and field, found by following the selections of
+/// operation documents through the types of one or more schemas.
+///
+/// Operations aren't validated, so documents written against a supergraph can be matched
+/// against its subgraphs: each field is looked up in every schema that defines its parent
+/// type. Fragments are followed across documents.
+#[derive(Debug, Default)]
+pub struct OperationUsage {
+    selected_by: HashMap<String, BTreeSet<OperationRef>>,
+}
+
+/// The named type of every field, merged across schemas.
+struct FieldTypes {
+    roots: HashMap<OperationType, Name>,
+    fields: HashMap<Name, HashMap<Name, Name>>,
+}
+
+impl OperationUsage {
+    /// Collects the selections of every operation in `documents`, using the types of
+    /// `schemas` to follow them.
+    pub fn collect<'a>(
+        schemas: impl IntoIterator<Item = &'a ParsedSchema>,
+        documents: &[ast::Document],
+    ) -> Self {
+        let types = FieldTypes::new(schemas);
+        let fragments: HashMap<&Name, &ast::FragmentDefinition> = documents
+            .iter()
+            .flat_map(|document| &document.definitions)
+            .filter_map(|definition| match definition {
+                Definition::FragmentDefinition(fragment) => Some((&fragment.name, &**fragment)),
+                _ => None,
+            })
+            .collect();
+
+        let mut usage = Self::default();
+        for document in documents {
+            let file = document
+                .sources
+                .values()
+                .next()
+                .map(|source| source.path().display().to_string())
+                .unwrap_or_default();
+            for definition in &document.definitions {
+                let Definition::OperationDefinition(operation) = definition else {
+                    continue;
+                };
+                let Some(root) = types.roots.get(&operation.operation_type) else {
+                    continue;
+                };
+                let mut walk = Walk {
+                    types: &types,
+                    fragments: &fragments,
+                    operation: OperationRef {
+                        name: operation.name.as_ref().map(|name| name.to_string()),
+                        file: file.clone(),
+                    },
+                    visited_fragments: HashSet::new(),
+                    usage: &mut usage,
+                };
+                walk.record(root.to_string());
+                walk.selections(root, &operation.selection_set);
+            }
+        }
+        usage
+    }
+
+    /// The operations that select the type or field at `coordinate`, in order of file and name.
+    pub fn operations(&self, coordinate: &SchemaCoordinate) -> Vec<OperationRef> {
+        self.selected_by
+            .get(&coordinate.to_string())
+            .map(|operations| operations.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Sets the operations of every type and field in `results`.
+    pub fn annotate(&self, results: &mut [SearchResult]) {
+        for result in results {
+            if matches!(result.kind, ElementKind::Type | ElementKind::Field) {
+                result.operations = Some(self.operations(&result.coordinate));
+            }
+        }
+    }
+}
+
+impl FieldTypes {
+    fn new<'a>(schemas: impl IntoIterator<Item = &'a ParsedSchema>) -> Self {
+        let mut roots = HashMap::new();
+        let mut fields: HashMap<Name, HashMap<Name, Name>> = HashMap::new();
+        for schema in schemas {
+            let schema = schema.inner();
+            let definition = &schema.schema_definition;
+            for (operation_type, root) in [
+                (OperationType::Query, &definition.query),
+                (OperationType::Mutation, &definition.mutation),
+                (OperationType::Subscription, &definition.subscription),
+            ] {
+                if let Some(root) = root {
+                    roots
+                        .entry(operation_type)
+                        .or_insert_with(|| root.name.clone());
+                }
+            }
+            for (type_name, ty) in &schema.types {
+                let definitions = match ty {
+                    ExtendedType::Object(object) => &object.fields,
+                    ExtendedType::Interface(interface) => &interface.fields,
+                    _ => continue,
+                };
+                let type_fields = fields.entry(type_name.clone()).or_default();
+                for (field_name, field) in definitions {
+                    type_fields
+                        .entry(field_name.clone())
+                        .or_insert_with(|| field.ty.inner_named_type().clone());
+                }
+            }
+        }
+        Self { roots, fields }
+    }
+}
+
+/// Follows the selections of a single operation.
+struct Walk<'a> {
+    types: &'a FieldTypes,
+    fragments: &'a HashMap<&'a Name, &'a ast::FragmentDefinition>,
+    operation: OperationRef,
+    visited_fragments: HashSet<&'a Name>,
+    usage: &'a mut OperationUsage,
+}
+
+impl<'a> Walk<'a> {
+    fn record(&mut self, coordinate: String) {
+        self.usage
+            .selected_by
+            .entry(coordinate)
+            .or_default()
+            .insert(self.operation.clone());
+    }
+
+    fn selections(&mut self, type_name: &Name, selections: &'a [Selection]) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    if field.name.starts_with("__") {
+                        continue;
+                    }
+                    self.record(format!("{type_name}.{}", field.name));
+                    let Some(field_type) = self
+                        .types
+                        .fields
+                        .get(type_name)
+                        .and_then(|fields| fields.get(&field.name))
+                    else {
+                        continue;
+                    };
+                    self.record(field_type.to_string());
+                    self.selections(field_type, &field.selection_set);
+                }
+                Selection::InlineFragment(fragment) => {
+                    let type_name = fragment.type_condition.as_ref().unwrap_or(type_name);
+                    self.record(type_name.to_string());
+                    self.selections(type_name, &fragment.selection_set);
+                }
+                Selection::FragmentSpread(spread) => {
+                    let Some(fragment) = self.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    if self.visited_fragments.insert(&fragment.name) {
+                        self.record(fragment.type_condition.to_string());
+                        self.selections(&fragment.type_condition, &fragment.selection_set);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_compiler::ast;
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn names(usage: &OperationUsage, coordinate: &str) -> Vec<String> {
+        usage
+            .operations(&coordinate.parse().unwrap())
+            .into_iter()
+            .map(|operation| operation.name.unwrap_or_else(|| "<anonymous>".to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn follows_fields_fragments_and_subgraphs() {
+        let products = ParsedSchema::parse(
+            "type Query { product(id: ID!): Product } type Product { id: ID! name: String }",
+            "products.graphql",
+        );
+        let reviews = ParsedSchema::parse(
+            "type Product { id: ID! reviews: [Review!]! } type Review { body: String author: String }",
+            "reviews.graphql",
+        );
+        let documents = [
+            ast::Document::parse(
+                indoc! {r#"
+                    query ProductPage { product(id: "1") { name ...ProductReviews } }
+                    { product(id: "2") { __typename id } }
+                "#},
+                "page.graphql",
+            )
+            .unwrap(),
+            ast::Document::parse(
+                "fragment ProductReviews on Product { reviews { ... on Review { body } } }",
+                "fragments.graphql",
+            )
+            .unwrap(),
+        ];
+        let usage = OperationUsage::collect([&products, &reviews], &documents);
+
+        assert_that!(names(&usage, "Product.name")).is_equal_to(vec!["ProductPage".to_string()]);
+        assert_that!(names(&usage, "Product"))
+            .is_equal_to(vec!["<anonymous>".to_string(), "ProductPage".to_string()]);
+        assert_that!(names(&usage, "Review.body")).is_equal_to(vec!["ProductPage".to_string()]);
+        assert_that!(names(&usage, "Review.author")).is_empty();
+        assert_that!(
+            usage.operations(&"Product.reviews".parse().unwrap())[0]
+                .file
+                .as_str()
+        )
+        .is_equal_to("page.graphql");
+    }
+}
@@ -1,8 +1,9 @@
 use std::collections::BTreeSet;
 
 use bon::Builder;
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use globwalk::GlobWalkerBuilder;
+use ignore::overrides::{Override, OverrideBuilder};
 
 use crate::RoverStdError;
 
@@ -35,6 +36,33 @@ pub struct FileSearch {
 impl FileSearch {
     /// Discovers files with the given extensions under `self.root`.
     pub fn find(&self, extensions: &[&str]) -> Result<Vec<Utf8PathBuf>, RoverStdError> {
+        let root = self.canonical_root()?;
+        let walker = GlobWalkerBuilder::from_patterns(&root, &self.patterns(extensions))
+            .follow_links(false)
+            .build()
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        let results: BTreeSet<Utf8PathBuf> = walker
+            .filter_map(|e| e.ok())
+            .filter_map(|e| Utf8PathBuf::from_path_buf(e.into_path()).ok())
+            .collect();
+
+        Ok(results.into_iter().collect())
+    }
+
+    /// Compiles the patterns [`FileSearch::find`] would use for `extensions`, to check
+    /// individual paths against without walking the filesystem.
+    pub fn matcher(&self, extensions: &[&str]) -> Result<FileMatcher, RoverStdError> {
+        let root = self.canonical_root()?;
+        let mut builder = OverrideBuilder::new(&root);
+        for pattern in self.patterns(extensions) {
+            builder.add(&pattern).map_err(|e| anyhow::anyhow!(e))?;
+        }
+        let patterns = builder.build().map_err(|e| anyhow::anyhow!(e))?;
+        Ok(FileMatcher { root, patterns })
+    }
+
+    fn canonical_root(&self) -> Result<Utf8PathBuf, RoverStdError> {
         // Canonicalize before passing to globwalk. Without this, globwalk's internal
         // strip_prefix panics on macOS where /var and /tmp resolve through /private/var
         // and /private/tmp, causing the walker's base_dir to mismatch entry paths.
@@ -44,9 +72,11 @@ impl FileSearch {
         let root_std = dunce::canonicalize(self.root.as_std_path()).map_err(|e| {
             anyhow::anyhow!("could not resolve root directory '{}': {e}", self.root)
         })?;
-        let root = Utf8PathBuf::from_path_buf(root_std)
-            .map_err(|p| anyhow::anyhow!("canonical path was not UTF-8: {}", p.display()))?;
+        Ok(Utf8PathBuf::from_path_buf(root_std)
+            .map_err(|p| anyhow::anyhow!("canonical path was not UTF-8: {}", p.display()))?)
+    }
 
+    fn patterns(&self, extensions: &[&str]) -> Vec<String> {
         let mut patterns: Vec<String> = if self.includes.is_empty() {
             extensions.iter().map(|ext| format!("**/*.{ext}")).collect()
         } else {
@@ -59,18 +89,31 @@ impl FileSearch {
         for pat in &self.excludes {
             patterns.push(format!("!{pat}"));
         }
+        patterns
+    }
+}
 
-        let walker = GlobWalkerBuilder::from_patterns(&root, &patterns)
-            .follow_links(false)
-            .build()
-            .map_err(|e| anyhow::anyhow!(e))?;
-
-        let results: BTreeSet<Utf8PathBuf> = walker
-            .filter_map(|e| e.ok())
-            .filter_map(|e| Utf8PathBuf::from_path_buf(e.into_path()).ok())
-            .collect();
+/// The compiled patterns of a [`FileSearch`].
+#[derive(Debug, Clone)]
+pub struct FileMatcher {
+    root: Utf8PathBuf,
+    patterns: Override,
+}
 
-        Ok(results.into_iter().collect())
+impl FileMatcher {
+    /// Whether [`FileSearch::find`] would return `path`, which should be canonical. Only the
+    /// path is looked at, so it doesn't need to exist.
+    pub fn is_match(&self, path: &Utf8Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        // the walk doesn't descend into excluded directories
+        let in_excluded_dir = relative
+            .ancestors()
+            .skip(1)
+            .filter(|dir| !dir.as_str().is_empty())
+            .any(|dir| self.patterns.matched(dir, true).is_ignore());
+        !in_excluded_dir && self.patterns.matched(relative, false).is_whitelist()
     }
 }
 
@@ -176,4 +219,47 @@ mod tests {
             .unwrap();
         assert!(files.is_empty());
     }
+
+    /// Verifies that a matcher agrees with what a search finds, including for files that
+    /// don't exist yet.
+    #[test]
+    fn matcher_agrees_with_find() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = Utf8PathBuf::from_path_buf(temp.path().join("root")).unwrap();
+        fs::create_dir_all(root.join("nested")).unwrap();
+        fs::create_dir_all(root.join("other")).unwrap();
+        let root = Utf8PathBuf::from_path_buf(dunce::canonicalize(&root).unwrap()).unwrap();
+
+        let matcher = FileSearch::builder()
+            .root(root.clone())
+            .includes(vec!["nested/**".to_string()])
+            .excludes(vec!["nested/ignore.graphql".to_string()])
+            .build()
+            .matcher(&["graphql"])
+            .unwrap();
+
+        assert!(matcher.is_match(&root.join("nested/b.graphql")));
+        assert!(matcher.is_match(&root.join("nested/deeper/c.graphql")));
+        assert!(!matcher.is_match(&root.join("nested/ignore.graphql")));
+        assert!(!matcher.is_match(&root.join("other/a.graphql")));
+        assert!(!matcher.is_match(&root.join("nested/node_modules/d.graphql")));
+        assert!(!matcher.is_match(Utf8Path::new("/elsewhere/nested/b.graphql")));
+    }
+
+    /// Verifies that a default matcher only matches the requested extensions.
+    #[test]
+    fn default_matcher_matches_extensions() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = Utf8PathBuf::from_path_buf(dunce::canonicalize(temp.path()).unwrap()).unwrap();
+
+        let matcher = FileSearch::builder()
+            .root(root.clone())
+            .build()
+            .matcher(&["graphql", "gql"])
+            .unwrap();
+
+        assert!(matcher.is_match(&root.join("a.graphql")));
+        assert!(matcher.is_match(&root.join("sub/b.gql")));
+        assert!(!matcher.is_match(&root.join("c.txt")));
+    }
 }
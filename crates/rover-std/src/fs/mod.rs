@@ -11,7 +11,7 @@ use std::{
 use anyhow::{anyhow, Context};
 use camino::{ReadDirUtf8, Utf8Path, Utf8PathBuf};
 use encoding_rs::{Encoding, UTF_8};
-pub use file_search::FileSearch;
+pub use file_search::{FileMatcher, FileSearch};
 #[cfg(windows)]
 use notify::event::{DataChange, ModifyKind};
 use notify::{Config, EventKind, PollWatcher, RecursiveMode, Watcher};
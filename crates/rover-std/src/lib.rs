@@ -8,7 +8,7 @@ pub mod url;
 pub mod print;
 pub mod prompt;
 pub use error::RoverStdError;
-pub use fs::{FileSearch, Fs};
+pub use fs::{FileMatcher, FileSearch, Fs};
 pub use hash::sha256_hex;
 pub use spinner::Spinner;
 pub use style::{is_no_color_set, Style};
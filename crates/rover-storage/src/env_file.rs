@@ -0,0 +1,299 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    fmt::{Debug, Formatter},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use keyring_core::{
+    Credential, Entry as KeyringEntry,
+    api::{CredentialApi, CredentialPersistence, CredentialStoreApi},
+};
+use serde_json::Value;
+
+/// A read-only credential store backed by a `.env`-style file, for
+/// environments that provision secrets as files (CI runners, containers with
+/// mounted secrets) rather than through a keyring.
+///
+/// Each credential is a variable named after its service and key, upper-cased
+/// with `:` and `-` replaced by `_` (so the `profile:my-profile` key of the
+/// `rover` service is `ROVER_PROFILE_MY_PROFILE`), whose value is the
+/// credential's JSON. Writes and deletes aren't supported; the file is only
+/// ever changed by whatever provisions it.
+#[derive(Clone, Debug, bon::Builder)]
+pub struct EnvFileStore {
+    #[builder(start_fn)]
+    path: PathBuf,
+}
+
+impl EnvFileStore {
+    fn read_var(&self, name: &str) -> keyring_core::Result<Option<String>> {
+        let vars = dotenvy::from_path_iter(&self.path).map_err(|e| match e {
+            // a file that hasn't been provisioned is treated like a keyring
+            // that isn't available on this platform.
+            dotenvy::Error::Io(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                keyring_core::Error::NoStorageAccess(Box::new(e))
+            }
+            e => keyring_core::Error::PlatformFailure(Box::new(e)),
+        })?;
+        for var in vars {
+            let (key, value) = var.map_err(|e| {
+                keyring_core::Error::BadStoreFormat(format!(
+                    "{} is not a valid .env file: {e}",
+                    self.path.display()
+                ))
+            })?;
+            if key == name {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_only(&self) -> keyring_core::Error {
+        keyring_core::Error::NotSupportedByStore(format!(
+            "{} is a read-only .env credential store",
+            self.path.display()
+        ))
+    }
+}
+
+/// The variable a credential is read from: `service` and `key` joined by `_`,
+/// upper-cased, with `:` and `-` replaced by `_`.
+///
+/// That's only unambiguous for some names (`my-profile` and `my_profile`
+/// would otherwise share a variable, as would `My` and `my`), so `service`
+/// must be lowercase letters and digits, and `key` a `<namespace>:<name>` of
+/// lowercase letters and digits with `-` allowed in `name`. Anything else is
+/// rejected rather than read from a variable another credential might own.
+fn var_name(service: &str, key: &str) -> keyring_core::Result<String> {
+    fn is_plain(s: &str) -> bool {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    }
+    let valid = key
+        .split_once(':')
+        .is_some_and(|(namespace, name)| is_plain(namespace) && name.split('-').all(is_plain));
+    if !is_plain(service) || !valid {
+        return Err(keyring_core::Error::Invalid(
+            key.to_string(),
+            "can't be read from a .env file: only lowercase letters, digits and `-` can be \
+             used, so that every credential has its own variable"
+                .to_string(),
+        ));
+    }
+    Ok(format!("{service}_{key}")
+        .chars()
+        .map(|c| match c {
+            ':' | '-' => '_',
+            c => c.to_ascii_uppercase(),
+        })
+        .collect())
+}
+
+#[derive(Clone, Debug)]
+struct EnvFileCredential {
+    store: EnvFileStore,
+    service: String,
+    user: String,
+}
+
+impl CredentialApi for EnvFileCredential {
+    fn set_secret(&self, _secret: &[u8]) -> keyring_core::Result<()> {
+        Err(self.store.read_only())
+    }
+
+    fn get_secret(&self) -> keyring_core::Result<Vec<u8>> {
+        let name = var_name(&self.service, &self.user)?;
+        let value = self
+            .store
+            .read_var(&name)?
+            .ok_or(keyring_core::Error::NoEntry)?;
+        // round-tripped through `Value` so a malformed credential is
+        // reported against the variable it came from, rather than as an
+        // opaque deserialization error further up.
+        let value: Value = serde_json::from_str(&value).map_err(|e| {
+            keyring_core::Error::BadStoreFormat(format!("{name} is not valid JSON: {e}"))
+        })?;
+        serde_json::to_vec(&value).map_err(|e| {
+            keyring_core::Error::BadStoreFormat(format!(
+                "credential value cannot be re-serialized: {e}"
+            ))
+        })
+    }
+
+    fn delete_credential(&self) -> keyring_core::Result<()> {
+        Err(self.store.read_only())
+    }
+
+    fn get_credential(&self) -> keyring_core::Result<Option<Arc<Credential>>> {
+        self.get_secret().map(|_| None)
+    }
+
+    fn get_specifiers(&self) -> Option<(String, String)> {
+        Some((self.service.clone(), self.user.clone()))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+impl CredentialStoreApi for EnvFileStore {
+    fn vendor(&self) -> String {
+        String::from("rover, https://github.com/apollographql/rover")
+    }
+
+    fn id(&self) -> String {
+        format!("EnvFileStore({})", self.path.display())
+    }
+
+    fn build(
+        &self,
+        service: &str,
+        user: &str,
+        modifiers: Option<&HashMap<&str, &str>>,
+    ) -> keyring_core::Result<KeyringEntry> {
+        if modifiers.is_some_and(|m| !m.is_empty()) {
+            return Err(keyring_core::Error::NotSupportedByStore(
+                "EnvFileStore does not support entry modifiers".to_string(),
+            ));
+        }
+        let cred: Arc<Credential> = Arc::new(EnvFileCredential {
+            store: self.clone(),
+            service: service.to_string(),
+            user: user.to_string(),
+        });
+        Ok(KeyringEntry::new_with_credential(cred))
+    }
+
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use keyring_core::api::CredentialStoreApi;
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+    use tempfile::TempDir;
+
+    use super::{EnvFileStore, var_name};
+
+    /// Pairs the store with the [`TempDir`] holding its `.env` file.
+    struct TestStore {
+        store: EnvFileStore,
+        _temp: TempDir,
+    }
+
+    #[fixture]
+    fn store() -> TestStore {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(".env");
+        std::fs::write(
+            &path,
+            concat!(
+                "# provisioned by CI\n",
+                "ROVER_PROFILE_DEFAULT='{\"api_key\":\"some-key\"}'\n",
+                "ROVER_PROFILE_BROKEN=not-json\n",
+            ),
+        )
+        .unwrap();
+        TestStore {
+            store: EnvFileStore::builder(path).build(),
+            _temp: temp,
+        }
+    }
+
+    #[test]
+    fn var_name_is_the_shouty_service_and_key() {
+        assert_that!(var_name("rover", "profile:my-profile"))
+            .is_ok()
+            .is_equal_to("ROVER_PROFILE_MY_PROFILE".to_string());
+    }
+
+    // Names that would share a variable with another credential's are
+    // rejected, rather than one profile reading the other's secret.
+    #[rstest]
+    #[case::underscore("profile:my_profile")]
+    #[case::uppercase("profile:My-Profile")]
+    #[case::second_colon("profile:my:profile")]
+    #[case::empty_segment("profile:my--profile")]
+    #[case::no_namespace("my-profile")]
+    fn var_name_rejects_ambiguous_keys(#[case] key: &str) {
+        assert_that!(var_name("rover", key))
+            .is_err()
+            .matches(|e| matches!(e, keyring_core::Error::Invalid(..)));
+    }
+
+    // A credential's variable is read back as its JSON.
+    #[rstest]
+    fn get_returns_the_variable_for_the_key(store: TestStore) {
+        let entry = store.store.build("rover", "profile:default", None).unwrap();
+
+        assert_that!(entry.get_secret())
+            .is_ok()
+            .is_equal_to(br#"{"api_key":"some-key"}"#.to_vec());
+    }
+
+    // A key without a variable in the file has no entry.
+    #[rstest]
+    fn get_reports_no_entry_for_a_missing_variable(store: TestStore) {
+        let entry = store.store.build("rover", "profile:missing", None).unwrap();
+
+        assert_that!(entry.get_secret())
+            .is_err()
+            .matches(|e| matches!(e, keyring_core::Error::NoEntry));
+    }
+
+    // A variable that isn't JSON is reported by name, not silently skipped.
+    #[rstest]
+    fn get_reports_a_variable_that_is_not_json(store: TestStore) {
+        let entry = store.store.build("rover", "profile:broken", None).unwrap();
+
+        assert_that!(entry.get_secret()).is_err().matches(|e| {
+            matches!(e, keyring_core::Error::BadStoreFormat(msg) if msg.contains("ROVER_PROFILE_BROKEN"))
+        });
+    }
+
+    // The file is never written to.
+    #[rstest]
+    fn writes_and_deletes_are_not_supported(store: TestStore) {
+        let entry = store.store.build("rover", "profile:default", None).unwrap();
+
+        assert_that!(entry.set_secret(br#"{"api_key":"other-key"}"#))
+            .is_err()
+            .matches(|e| matches!(e, keyring_core::Error::NotSupportedByStore(_)));
+        assert_that!(entry.delete_credential())
+            .is_err()
+            .matches(|e| matches!(e, keyring_core::Error::NotSupportedByStore(_)));
+    }
+
+    // A file that doesn't exist (yet) is treated as an unavailable backend.
+    #[test]
+    fn a_missing_file_reports_no_storage_access() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = EnvFileStore::builder(temp.path().join("missing.env")).build();
+        let entry = store.build("rover", "profile:default", None).unwrap();
+
+        assert_that!(entry.get_secret())
+            .is_err()
+            .matches(|e| matches!(e, keyring_core::Error::NoStorageAccess(_)));
+    }
+}
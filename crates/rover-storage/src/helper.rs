@@ -0,0 +1,359 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    fmt::{Debug, Formatter},
+    io::Write,
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+use keyring_core::{
+    Credential, Entry as KeyringEntry,
+    api::{CredentialApi, CredentialPersistence, CredentialStoreApi},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A credential store backed by an external helper command, in the spirit of
+/// git's credential helpers. Lets teams keep Rover's credentials in a secret
+/// manager (1Password, Vault agent, `pass`, ...) through a small wrapper
+/// script, without Rover knowing anything about the manager itself.
+///
+/// The helper is invoked as `<command...> <action>`, where `action` is one of
+/// `get`, `store` or `erase`, with a [`HelperRequest`] written to its stdin
+/// as JSON. For `get` it prints a [`HelperResponse`] to stdout; an empty
+/// response (or a `null` `secret`) means there is no such credential. A
+/// non-zero exit status is an error, reported with whatever the helper wrote
+/// to stderr.
+#[derive(Clone, Debug, bon::Builder)]
+pub struct HelperCommandStore {
+    /// The program to run, followed by any arguments to pass before the action.
+    #[builder(start_fn)]
+    command: Vec<String>,
+}
+
+/// What Rover sends a helper command on stdin.
+#[derive(Debug, Serialize)]
+struct HelperRequest<'a> {
+    /// The service all of Rover's credentials are stored under.
+    service: &'a str,
+    /// The credential being operated on, unique within `service`.
+    key: &'a str,
+    /// The credential to save; only sent for `store`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret: Option<&'a Value>,
+}
+
+/// What a helper command prints to stdout in response to `get`.
+#[derive(Debug, Deserialize)]
+struct HelperResponse {
+    /// The stored credential, exactly as it was sent with `store`.
+    secret: Option<Value>,
+}
+
+/// A helper command exited unsuccessfully.
+#[derive(thiserror::Error, Debug)]
+#[error("secret helper `{program} {action}` failed ({status}): {stderr}")]
+struct HelperError {
+    program: String,
+    action: &'static str,
+    status: std::process::ExitStatus,
+    stderr: String,
+}
+
+impl HelperCommandStore {
+    /// Runs the helper for `action`, returning what it printed to stdout.
+    fn run(&self, action: &'static str, request: &HelperRequest) -> keyring_core::Result<Vec<u8>> {
+        let Some((program, args)) = self.command.split_first() else {
+            return Err(keyring_core::Error::NoStorageAccess(
+                "no secret helper command configured".into(),
+            ));
+        };
+        let input = serde_json::to_vec(request).map_err(|e| {
+            keyring_core::Error::Invalid("request".to_string(), format!("not valid JSON: {e}"))
+        })?;
+
+        let mut child = Command::new(program)
+            .args(args)
+            .arg(action)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                keyring_core::Error::PlatformFailure(
+                    format!("couldn't run secret helper `{program}`: {e}").into(),
+                )
+            })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            match stdin.write_all(&input) {
+                // a helper that exits without reading its request is judged
+                // by its exit status below, not by the pipe it closed.
+                Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                    return Err(keyring_core::Error::PlatformFailure(Box::new(e)));
+                }
+                _ => {}
+            }
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| keyring_core::Error::PlatformFailure(Box::new(e)))?;
+
+        if !output.status.success() {
+            return Err(keyring_core::Error::PlatformFailure(Box::new(
+                HelperError {
+                    program: program.clone(),
+                    action,
+                    status: output.status,
+                    stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                },
+            )));
+        }
+        Ok(output.stdout)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct HelperCommandCredential {
+    store: HelperCommandStore,
+    service: String,
+    user: String,
+}
+
+impl HelperCommandCredential {
+    fn request<'a>(&'a self, secret: Option<&'a Value>) -> HelperRequest<'a> {
+        HelperRequest {
+            service: &self.service,
+            key: &self.user,
+            secret,
+        }
+    }
+}
+
+impl CredentialApi for HelperCommandCredential {
+    fn set_secret(&self, secret: &[u8]) -> keyring_core::Result<()> {
+        let value: Value = serde_json::from_slice(secret).map_err(|e| {
+            keyring_core::Error::Invalid("secret".to_string(), format!("not valid JSON: {e}"))
+        })?;
+        self.store.run("store", &self.request(Some(&value)))?;
+        Ok(())
+    }
+
+    fn get_secret(&self) -> keyring_core::Result<Vec<u8>> {
+        let stdout = self.store.run("get", &self.request(None))?;
+        if stdout.iter().all(u8::is_ascii_whitespace) {
+            return Err(keyring_core::Error::NoEntry);
+        }
+        let response: HelperResponse = serde_json::from_slice(&stdout).map_err(|e| {
+            keyring_core::Error::BadStoreFormat(format!(
+                "secret helper printed an invalid response: {e}"
+            ))
+        })?;
+        match response.secret {
+            Some(secret) => serde_json::to_vec(&secret).map_err(|e| {
+                keyring_core::Error::BadStoreFormat(format!(
+                    "credential value cannot be re-serialized: {e}"
+                ))
+            }),
+            None => Err(keyring_core::Error::NoEntry),
+        }
+    }
+
+    fn delete_credential(&self) -> keyring_core::Result<()> {
+        self.store.run("erase", &self.request(None))?;
+        Ok(())
+    }
+
+    fn get_credential(&self) -> keyring_core::Result<Option<Arc<Credential>>> {
+        self.get_secret().map(|_| None)
+    }
+
+    fn get_specifiers(&self) -> Option<(String, String)> {
+        Some((self.service.clone(), self.user.clone()))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+impl CredentialStoreApi for HelperCommandStore {
+    fn vendor(&self) -> String {
+        String::from("rover, https://github.com/apollographql/rover")
+    }
+
+    fn id(&self) -> String {
+        format!("HelperCommandStore({})", self.command.join(" "))
+    }
+
+    fn build(
+        &self,
+        service: &str,
+        user: &str,
+        modifiers: Option<&HashMap<&str, &str>>,
+    ) -> keyring_core::Result<KeyringEntry> {
+        if modifiers.is_some_and(|m| !m.is_empty()) {
+            return Err(keyring_core::Error::NotSupportedByStore(
+                "HelperCommandStore does not support entry modifiers".to_string(),
+            ));
+        }
+        let cred: Arc<Credential> = Arc::new(HelperCommandCredential {
+            store: self.clone(),
+            service: service.to_string(),
+            user: user.to_string(),
+        });
+        Ok(KeyringEntry::new_with_credential(cred))
+    }
+
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+// These drive a real helper: a small shell script that keeps each credential
+// in its own file, so they only run where `sh` is available.
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{os::unix::fs::PermissionsExt, path::Path};
+
+    use keyring_core::api::CredentialStoreApi;
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+    use tempfile::TempDir;
+
+    use super::HelperCommandStore;
+
+    const HELPER: &str = r#"#!/bin/sh
+dir="$(dirname "$0")"
+request="$(cat)"
+key="$(printf '%s' "$request" | sed 's/.*"key":"\([^"]*\)".*/\1/' | tr ':' '_')"
+case "$1" in
+  get) [ -f "$dir/$key" ] && printf '{"secret":%s}' "$(cat "$dir/$key")" ;;
+  store) printf '%s' "$request" | sed 's/.*"secret":\(.*\)}$/\1/' > "$dir/$key" ;;
+  erase) rm -f "$dir/$key" ;;
+  *) echo "unknown action $1" >&2; exit 2 ;;
+esac
+exit 0
+"#;
+
+    fn write_script(dir: &Path, name: &str, contents: &str) -> String {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    /// Pairs the store with the [`TempDir`] its helper script (and the
+    /// credentials it stores) live in.
+    struct TestStore {
+        store: HelperCommandStore,
+        _temp: TempDir,
+    }
+
+    #[fixture]
+    fn store() -> TestStore {
+        let temp = tempfile::tempdir().unwrap();
+        let helper = write_script(temp.path(), "helper", HELPER);
+        TestStore {
+            store: HelperCommandStore::builder(vec![helper]).build(),
+            _temp: temp,
+        }
+    }
+
+    // A stored secret round-trips back out of the helper unchanged.
+    #[rstest]
+    fn get_returns_the_stored_secret(store: TestStore) {
+        let entry = store.store.build("rover", "profile:default", None).unwrap();
+
+        entry.set_secret(br#"{"api_key":"some-key"}"#).unwrap();
+
+        assert_that!(entry.get_secret())
+            .is_ok()
+            .is_equal_to(br#"{"api_key":"some-key"}"#.to_vec());
+    }
+
+    // An empty response from `get` means there's no such credential.
+    #[rstest]
+    fn get_reports_no_entry_for_an_unknown_key(store: TestStore) {
+        let entry = store.store.build("rover", "profile:missing", None).unwrap();
+
+        assert_that!(entry.get_secret())
+            .is_err()
+            .matches(|e| matches!(e, keyring_core::Error::NoEntry));
+    }
+
+    // `erase` removes the credential from the helper.
+    #[rstest]
+    fn delete_erases_the_secret(store: TestStore) {
+        let entry = store.store.build("rover", "profile:default", None).unwrap();
+        entry.set_secret(br#"{"api_key":"some-key"}"#).unwrap();
+
+        entry.delete_credential().unwrap();
+
+        assert_that!(entry.get_secret())
+            .is_err()
+            .matches(|e| matches!(e, keyring_core::Error::NoEntry));
+    }
+
+    // A helper that isn't installed is an error naming the helper, not an
+    // unavailable keyring that could be quietly fallen back from.
+    #[test]
+    fn a_missing_helper_reports_a_platform_failure() {
+        let store =
+            HelperCommandStore::builder(vec!["/definitely/not/a/rover/helper".to_string()]).build();
+        let entry = store.build("rover", "profile:default", None).unwrap();
+
+        assert_that!(entry.get_secret()).is_err().matches(|e| {
+            matches!(e, keyring_core::Error::PlatformFailure(_))
+                && e.to_string().contains("/definitely/not/a/rover/helper")
+        });
+    }
+
+    // A helper that exits unsuccessfully surfaces what it wrote to stderr.
+    #[test]
+    fn a_failing_helper_reports_its_stderr() {
+        let temp = tempfile::tempdir().unwrap();
+        let helper = write_script(
+            temp.path(),
+            "helper",
+            "#!/bin/sh\necho 'vault is sealed' >&2\nexit 1\n",
+        );
+        let store = HelperCommandStore::builder(vec![helper]).build();
+        let entry = store.build("rover", "profile:default", None).unwrap();
+
+        assert_that!(entry.get_secret()).is_err().matches(|e| {
+            matches!(e, keyring_core::Error::PlatformFailure(_))
+                && e.to_string().contains("vault is sealed")
+        });
+    }
+
+    // Arguments configured with the helper are passed before the action.
+    #[test]
+    fn configured_arguments_come_before_the_action() {
+        let temp = tempfile::tempdir().unwrap();
+        let helper = write_script(
+            temp.path(),
+            "helper",
+            "#!/bin/sh\ncat > /dev/null\nprintf '{\"secret\":\"%s %s\"}' \"$1\" \"$2\"\n",
+        );
+        let store = HelperCommandStore::builder(vec![helper, "--vault=dev".to_string()]).build();
+        let entry = store.build("rover", "profile:default", None).unwrap();
+
+        assert_that!(entry.get_secret())
+            .is_ok()
+            .is_equal_to(br#""--vault=dev get""#.to_vec());
+    }
+}
@@ -1,4 +1,6 @@
 mod credentials_file;
+mod env_file;
+mod helper;
 pub mod secret;
 
 #[derive(thiserror::Error, Debug)]
@@ -3,7 +3,33 @@ use std::{path::PathBuf, sync::Arc};
 use keyring_core::CredentialStore;
 use serde::{Deserialize, Serialize};
 
-use crate::{StoreError, credentials_file::CredentialsFileStore};
+use crate::{
+    StoreError, credentials_file::CredentialsFileStore, env_file::EnvFileStore,
+    helper::HelperCommandStore,
+};
+
+/// Where a [`RoverSecretStore`] keeps credentials, ahead of its file-store
+/// fallback. Deserializes from configuration tagged by `backend`, e.g.
+/// `backend = "helper"` with `command = ["rover-vault-helper", "--vault", "dev"]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "kebab-case")]
+pub enum SecretBackend {
+    /// The platform's native keyring.
+    #[default]
+    Keyring,
+    /// An external helper command, run as `<command...> get|store|erase`
+    /// with the request as JSON on stdin, like a git credential helper.
+    Helper {
+        /// The program to run, followed by any arguments to pass before the
+        /// action.
+        command: Vec<String>,
+    },
+    /// A read-only `.env`-style file.
+    EnvFile {
+        /// The file to read credentials from.
+        path: PathBuf,
+    },
+}
 
 #[derive(Clone, Debug)]
 pub struct RoverSecretStore {
@@ -19,6 +45,10 @@ pub struct RoverSecretStore {
     /// that unsigned/ad-hoc binaries don't have, which only surfaces once a
     /// secret is actually read or written.
     fallback: Arc<CredentialStore>,
+    /// Whether `fallback` is used at all. Off for a configured helper
+    /// command: whoever set one up expects its failures to be reported, not
+    /// papered over by keeping their credentials in a plaintext file.
+    fall_back: bool,
 }
 
 impl RoverSecretStore {
@@ -26,12 +56,38 @@ impl RoverSecretStore {
     /// back to [`CredentialsFileStore`] in `credentials_dir` when the platform
     /// keyring is unavailable or the target has no native keyring.
     pub fn new(service: String, credentials_dir: PathBuf) -> Result<Self, StoreError> {
+        Self::with_backend(service, credentials_dir, &SecretBackend::Keyring)
+    }
+
+    /// Create a store using the configured `backend` in place of the
+    /// platform's default, with the same [`CredentialsFileStore`] fallback in
+    /// `credentials_dir` (and the same write verification) as [`Self::new`].
+    pub fn with_backend(
+        service: String,
+        credentials_dir: PathBuf,
+        backend: &SecretBackend,
+    ) -> Result<Self, StoreError> {
         let fallback = file_store_backend(credentials_dir.clone())?;
-        let backend = default_backend(credentials_dir)?;
+        let fall_back = !matches!(backend, SecretBackend::Helper { .. });
+        let backend: Arc<CredentialStore> = match backend {
+            SecretBackend::Keyring => default_backend(credentials_dir)?,
+            SecretBackend::Helper { command } if command.is_empty() => {
+                return Err(StoreError::Store(
+                    "the secret helper backend needs a command to run".into(),
+                ));
+            }
+            SecretBackend::Helper { command } => {
+                Arc::new(HelperCommandStore::builder(command.clone()).build())
+            }
+            SecretBackend::EnvFile { path } => {
+                Arc::new(EnvFileStore::builder(path.clone()).build())
+            }
+        };
         Ok(RoverSecretStore {
             service,
             backend,
             fallback,
+            fall_back,
         })
     }
 
@@ -48,6 +104,7 @@ impl RoverSecretStore {
             service,
             backend,
             fallback,
+            fall_back: true,
         }
     }
 
@@ -83,7 +140,10 @@ impl RoverSecretStore {
     fn read_raw(&self, key: &str) -> keyring_core::Result<Vec<u8>> {
         match self.attempt(&self.backend, key, |entry| entry.get_secret()) {
             Ok(data) => Ok(data),
-            Err(e) if matches!(e, keyring_core::Error::NoEntry) || is_unavailable(&e) => {
+            Err(e)
+                if self.fall_back
+                    && (matches!(e, keyring_core::Error::NoEntry) || is_unavailable(&e)) =>
+            {
                 self.attempt(&self.fallback, key, |entry| entry.get_secret())
             }
             Err(e) => Err(e),
@@ -140,9 +200,12 @@ impl RoverSecretStore {
         // propagate rather than being swallowed as a no-op.
         match self.attempt(&self.backend, key, |entry| entry.delete_credential()) {
             Ok(()) | Err(keyring_core::Error::NoEntry) => {}
-            Err(e) if is_unavailable(&e) => {}
+            Err(e) if self.fall_back && is_unavailable(&e) => {}
             Err(e) => return Err(e.into()),
         }
+        if !self.fall_back {
+            return Ok(());
+        }
         match self.attempt(&self.fallback, key, |entry| entry.delete_credential()) {
             Ok(()) | Err(keyring_core::Error::NoEntry) => Ok(()),
             Err(e) => Err(e.into()),
@@ -159,7 +222,9 @@ impl RoverSecretStore {
         op: impl Fn(&keyring_core::Entry) -> keyring_core::Result<T>,
     ) -> keyring_core::Result<T> {
         match self.attempt(&self.backend, key, &op) {
-            Err(e) if is_unavailable(&e) => self.attempt(&self.fallback, key, &op),
+            Err(e) if self.fall_back && is_unavailable(&e) => {
+                self.attempt(&self.fallback, key, &op)
+            }
             result => result,
         }
     }
@@ -690,6 +755,107 @@ mod tests {
         );
     }
 
+    // ==== configured backends ====
+
+    // The env-file backend serves credentials provisioned in the file, and
+    // refuses to overwrite them rather than silently writing elsewhere.
+    #[rstest]
+    fn env_file_backend_reads_from_the_file_and_refuses_writes(temp_dir: TempDir) {
+        let path = temp_dir.path().join(".env");
+        std::fs::write(&path, "ROVER_PROFILE_DEFAULT='{\"data\":\"hello\"}'\n").unwrap();
+        let store = RoverSecretStore::with_backend(
+            "rover".to_string(),
+            temp_dir.path().join("credentials"),
+            &SecretBackend::EnvFile { path },
+        )
+        .unwrap();
+
+        let result: Result<Option<TestValue>, StoreError> = store.read("profile:default");
+        assert_that!(result)
+            .is_ok()
+            .is_some()
+            .is_equal_to(TestValue::new("hello"));
+        assert_that!(store.write("profile:default", TestValue::new("other"))).is_err();
+    }
+
+    // A helper command that isn't installed is reported, rather than the
+    // credential quietly landing in the plaintext file store instead.
+    #[rstest]
+    fn helper_backend_that_is_not_installed_fails_without_touching_the_file_store(
+        temp_dir: TempDir,
+    ) {
+        let credentials_dir = temp_dir.path().join("credentials");
+        let store = RoverSecretStore::with_backend(
+            SERVICE.to_string(),
+            credentials_dir.clone(),
+            &SecretBackend::Helper {
+                command: vec!["definitely-not-a-rover-secret-helper".to_string()],
+            },
+        )
+        .unwrap();
+
+        assert_that!(store.write(KEY, TestValue::new("hello"))).is_err();
+        let result: Result<Option<TestValue>, StoreError> = store.read(KEY);
+        assert_that!(result).is_err();
+        assert_that!(credentials_dir.join("credentials.json").exists()).is_false();
+    }
+
+    // A helper command that fails (say, because its vault is sealed) is
+    // reported too, and nothing is written to the file store.
+    #[cfg(unix)]
+    #[rstest]
+    fn failing_helper_backend_writes_nothing_to_the_file_store(temp_dir: TempDir) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let helper = temp_dir.path().join("helper");
+        std::fs::write(&helper, "#!/bin/sh\necho 'vault is sealed' >&2\nexit 1\n").unwrap();
+        std::fs::set_permissions(&helper, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let credentials_dir = temp_dir.path().join("credentials");
+        let store = RoverSecretStore::with_backend(
+            SERVICE.to_string(),
+            credentials_dir.clone(),
+            &SecretBackend::Helper {
+                command: vec![helper.to_string_lossy().into_owned()],
+            },
+        )
+        .unwrap();
+
+        assert_that!(store.write(KEY, TestValue::new("hello")))
+            .is_err()
+            .matches(|e| e.to_string().contains("vault is sealed"));
+        assert_that!(credentials_dir.join("credentials.json").exists()).is_false();
+    }
+
+    // An empty helper command is a configuration mistake, reported up front.
+    #[rstest]
+    fn helper_backend_without_a_command_is_rejected(temp_dir: TempDir) {
+        let result = RoverSecretStore::with_backend(
+            SERVICE.to_string(),
+            temp_dir.path().to_path_buf(),
+            &SecretBackend::Helper {
+                command: Vec::new(),
+            },
+        );
+
+        assert_that!(result).is_err();
+    }
+
+    #[test]
+    fn secret_backend_deserializes_from_tagged_config() {
+        let backend: SecretBackend = serde_json::from_str(
+            r#"{"backend": "helper", "command": ["rover-vault-helper", "--vault", "dev"]}"#,
+        )
+        .unwrap();
+
+        assert_that!(backend).is_equal_to(SecretBackend::Helper {
+            command: vec![
+                "rover-vault-helper".to_string(),
+                "--vault".to_string(),
+                "dev".to_string(),
+            ],
+        });
+    }
+
     // ==== cross-system consistency scenarios ====
 
     // Backend down for the whole test: a write-then-read round trip on the
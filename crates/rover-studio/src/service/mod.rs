@@ -3,7 +3,7 @@
 use std::str::FromStr;
 
 use buildstructor::buildstructor;
-use houston::{Credential, CredentialOrigin};
+use houston::{Credential, CredentialOrigin, CredentialSource};
 use http::{HeaderMap, HeaderValue, Uri};
 use rover_http::HttpRequest;
 use tower::{Layer, Service};
@@ -25,16 +25,19 @@ pub enum HttpStudioServiceError {
 /// Layer providing middleware that injects studio headers to all requests
 pub struct HttpStudioServiceLayer {
     headers: HeaderMap,
+    credential: CredentialSource,
     uri: Uri,
 }
 
 #[buildstructor]
 impl HttpStudioServiceLayer {
-    /// Constructs a new [`HttpStudioServiceLayer`]
+    /// Constructs a new [`HttpStudioServiceLayer`]. The credential is read from
+    /// `credential` for every request, so a profile's refreshed OAuth session
+    /// reaches services built before the refresh.
     #[builder]
     pub fn new(
         url: Url,
-        credential: Credential,
+        credential: CredentialSource,
         client_version: String,
         is_sudo: bool,
     ) -> Result<HttpStudioServiceLayer, HttpStudioServiceError> {
@@ -53,34 +56,46 @@ impl HttpStudioServiceLayer {
         let client_version = HeaderValue::from_str(&client_version)?;
         headers.insert("apollographql-client-version", client_version);
 
-        match &credential.origin {
-            CredentialOrigin::OauthAuthorizationPkce(_)
-            | CredentialOrigin::OauthClientCredentials => {
-                let mut auth = HeaderValue::from_str(&format!("Bearer {}", credential.api_key))?;
-                auth.set_sensitive(true);
-                headers.insert(http::header::AUTHORIZATION, auth);
-            }
-            CredentialOrigin::EnvVar | CredentialOrigin::ConfigFile(_) => {
-                let mut api_key = HeaderValue::from_str(&credential.api_key)?;
-                api_key.set_sensitive(true);
-                headers.insert("x-api-key", api_key);
-            }
-        }
+        // fail up front on a credential that can't be sent at all
+        auth_header(&credential.credential())?;
 
         if is_sudo {
             headers.insert("apollo-sudo", HeaderValue::from_static("true"));
         }
         let uri = Uri::from_str(url.as_ref())?;
-        Ok(HttpStudioServiceLayer { headers, uri })
+        Ok(HttpStudioServiceLayer {
+            headers,
+            credential,
+            uri,
+        })
     }
 }
 
+/// The header that authenticates `credential`: a bearer token for OAuth, or `x-api-key`.
+fn auth_header(
+    credential: &Credential,
+) -> Result<(http::HeaderName, HeaderValue), http::header::InvalidHeaderValue> {
+    let (name, mut value) = match &credential.origin {
+        CredentialOrigin::OauthAuthorizationPkce(_) | CredentialOrigin::OauthClientCredentials => (
+            http::header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", credential.api_key))?,
+        ),
+        CredentialOrigin::EnvVar | CredentialOrigin::ConfigFile(_) => (
+            http::HeaderName::from_static("x-api-key"),
+            HeaderValue::from_str(&credential.api_key)?,
+        ),
+    };
+    value.set_sensitive(true);
+    Ok((name, value))
+}
+
 impl<S: Clone> Layer<S> for HttpStudioServiceLayer {
     type Service = HttpStudioService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
         HttpStudioService {
             headers: self.headers.clone(),
+            credential: self.credential.clone(),
             uri: self.uri.clone(),
             inner,
         }
@@ -91,6 +106,7 @@ impl<S: Clone> Layer<S> for HttpStudioServiceLayer {
 #[derive(Clone)]
 pub struct HttpStudioService<S: Clone> {
     headers: HeaderMap,
+    credential: CredentialSource,
     uri: Uri,
     inner: S,
 }
@@ -114,6 +130,14 @@ where
         for (name, value) in self.headers.iter() {
             headers.insert(name.clone(), value.clone());
         }
+        match auth_header(&self.credential.credential()) {
+            Ok((name, value)) => {
+                headers.insert(name, value);
+            }
+            Err(error) => {
+                tracing::warn!("not sending a credential that isn't a valid header: {error}")
+            }
+        }
         self.inner.call(req)
     }
 }
@@ -180,9 +204,13 @@ mod tests {
                 .unwrap()))
         });
 
-        let service =
-            HttpStudioServiceLayer::new(studio_endpoint, credential, client_version, is_sudo)?
-                .layer(MockCloneService::new(mock));
+        let service = HttpStudioServiceLayer::new(
+            studio_endpoint,
+            credential.into(),
+            client_version,
+            is_sudo,
+        )?
+        .layer(MockCloneService::new(mock));
 
         let req = http::Request::builder()
             .uri("https://example.com")
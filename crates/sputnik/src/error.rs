@@ -22,6 +22,10 @@ pub enum SputnikError {
     #[error("Could not report anonymous usage data.")]
     HttpError(#[from] reqwest::Error),
 
+    /// HttpServiceError occurs when reporting anonymous usage data through an HTTP cassette fails.
+    #[error("Could not report anonymous usage data.")]
+    HttpServiceError(Box<rover_http::HttpServiceError>),
+
     /// VersionParseError occurs when the version of the tool cannot be determined.
     #[error("Could not parse the version of the tool.")]
     VersionParseError(#[from] semver::Error),
@@ -47,3 +51,9 @@ pub enum SputnikError {
     #[error(transparent)]
     RoverStdError(#[from] RoverStdError),
 }
+
+impl From<rover_http::HttpServiceError> for SputnikError {
+    fn from(error: rover_http::HttpServiceError) -> Self {
+        SputnikError::HttpServiceError(Box::new(error))
+    }
+}
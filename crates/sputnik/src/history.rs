@@ -0,0 +1,161 @@
+use std::io;
+
+use camino::Utf8PathBuf;
+use rover_std::Fs;
+use serde::{Deserialize, Serialize};
+
+use crate::SputnikError;
+
+/// The number of sessions kept in the history file; older ones are dropped.
+const HISTORY_LIMIT: usize = 100;
+
+/// HistoryEntry is one session as recorded in the [`History`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// where the session was reported to, or `None` if it wasn't reported
+    /// at all (e.g. because telemetry is disabled).
+    pub reported_to: Option<String>,
+
+    /// why the session wasn't reported, when it wasn't. Missing from entries
+    /// recorded before the reason was.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_reported: Option<NotReported>,
+
+    /// the session exactly as it was (or would have been) sent.
+    pub payload: serde_json::Value,
+}
+
+/// NotReported is why a session in the [`History`] wasn't reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotReported {
+    /// the user turned telemetry off.
+    TelemetryDisabled,
+    /// debug builds never report to the default HTTP endpoint.
+    DebugBuild,
+}
+
+/// History keeps the most recent sessions in a local JSON Lines file, so
+/// users can inspect exactly what is reported about the commands they run.
+#[derive(Debug, Clone)]
+pub struct History {
+    path: Utf8PathBuf,
+}
+
+impl History {
+    /// creates a History backed by the file at `path`.
+    pub const fn new(path: Utf8PathBuf) -> History {
+        History { path }
+    }
+
+    /// records an entry, dropping the oldest ones beyond the history limit.
+    pub fn record(&self, entry: &HistoryEntry) -> Result<(), SputnikError> {
+        let mut lines = self.read_lines()?;
+        lines.push(serde_json::to_string(entry)?);
+        let skip = lines.len().saturating_sub(HISTORY_LIMIT);
+
+        let mut contents = lines[skip..].join("\n");
+        contents.push('\n');
+        Fs::write_file(&self.path, contents)?;
+        Ok(())
+    }
+
+    /// returns up to `count` of the most recent entries, oldest first.
+    /// lines that can't be parsed (e.g. from an interrupted write) are skipped.
+    pub fn last(&self, count: usize) -> Result<Vec<HistoryEntry>, SputnikError> {
+        let entries: Vec<HistoryEntry> = self
+            .read_lines()?
+            .iter()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let skip = entries.len().saturating_sub(count);
+        Ok(entries.into_iter().skip(skip).collect())
+    }
+
+    fn read_lines(&self) -> Result<Vec<String>, SputnikError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .collect()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use camino::Utf8PathBuf;
+    use serde_json::json;
+    use speculoos::prelude::*;
+
+    use super::{History, HistoryEntry, HISTORY_LIMIT};
+
+    fn entry(n: usize) -> HistoryEntry {
+        HistoryEntry {
+            reported_to: Some("https://example.com/telemetry".to_string()),
+            not_reported: None,
+            payload: json!({ "command": { "name": format!("command-{n}") } }),
+        }
+    }
+
+    fn history(fixture: &assert_fs::TempDir) -> History {
+        let path = fixture.child("history.jsonl");
+        History::new(Utf8PathBuf::try_from(path.path().to_path_buf()).unwrap())
+    }
+
+    /// a history that hasn't been written yet is empty, rather than an error.
+    #[test]
+    fn it_reads_a_missing_history_as_empty() {
+        let fixture = assert_fs::TempDir::new().unwrap();
+        assert_that!(history(&fixture).last(10)).is_ok().is_empty();
+    }
+
+    /// the most recent entries are returned, oldest first.
+    #[test]
+    fn it_returns_the_most_recent_entries() {
+        let fixture = assert_fs::TempDir::new().unwrap();
+        let history = history(&fixture);
+        for n in 0..5 {
+            history.record(&entry(n)).unwrap();
+        }
+
+        assert_that!(history.last(2))
+            .is_ok()
+            .is_equal_to(vec![entry(3), entry(4)]);
+    }
+
+    /// only the last `HISTORY_LIMIT` entries are kept on disk.
+    #[test]
+    fn it_drops_entries_beyond_the_limit() {
+        let fixture = assert_fs::TempDir::new().unwrap();
+        let history = history(&fixture);
+        for n in 0..HISTORY_LIMIT + 3 {
+            history.record(&entry(n)).unwrap();
+        }
+
+        let entries = history.last(usize::MAX).unwrap();
+        assert_that!(entries).has_length(HISTORY_LIMIT);
+        assert_that!(entries[0]).is_equal_to(entry(3));
+    }
+
+    /// a corrupted line doesn't hide the rest of the history.
+    #[test]
+    fn it_skips_lines_it_cannot_parse() {
+        let fixture = assert_fs::TempDir::new().unwrap();
+        let history = history(&fixture);
+        history.record(&entry(0)).unwrap();
+        let path = fixture.child("history.jsonl");
+        let contents = std::fs::read_to_string(path.path()).unwrap();
+        path.write_str(&format!("{contents}{{\"reported_to\":\n"))
+            .unwrap();
+        history.record(&entry(1)).unwrap();
+
+        assert_that!(history.last(10))
+            .is_ok()
+            .is_equal_to(vec![entry(0), entry(1)]);
+    }
+}
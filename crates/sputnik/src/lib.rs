@@ -3,9 +3,13 @@
 //! Utilities for reporting anonymous usage data for the rover CLI tool.
 
 mod error;
+mod history;
 mod report;
 mod session;
+mod sink;
 
 pub use error::SputnikError;
+pub use history::{History, HistoryEntry, NotReported};
 pub use report::Report;
 pub use session::{Command, Session};
+pub use sink::Sink;
@@ -4,7 +4,7 @@ use rover_std::Fs;
 use url::Url;
 use uuid::Uuid;
 
-use crate::{Command, SputnikError};
+use crate::{Command, Sink, SputnikError};
 
 /// Report defines the behavior of how anonymous usage data is reported.
 pub trait Report {
@@ -17,6 +17,12 @@ pub trait Report {
     /// returns the endpoint that the data should be posted to.
     fn endpoint(&self) -> Result<Url, SputnikError>;
 
+    /// returns where the data should be reported to. by default, it is
+    /// POSTed to self.endpoint().
+    fn sink(&self) -> Result<Sink, SputnikError> {
+        Ok(Sink::Http(self.endpoint()?))
+    }
+
     /// returns the name of the tool, this is used to construct
     /// the User-Agent header.
     fn tool_name(&self) -> String;
@@ -35,6 +41,15 @@ pub trait Report {
     /// machine identifier
     fn machine_id_config(&self) -> Result<Utf8PathBuf, SputnikError>;
 
+    /// returns the location of the file that keeps a history of the most
+    /// recent sessions, so users can inspect what has been reported.
+    /// the default implementation keeps it next to self.machine_id_config().
+    fn history_path(&self) -> Result<Utf8PathBuf, SputnikError> {
+        Ok(self
+            .machine_id_config()?
+            .with_file_name("telemetry_history.jsonl"))
+    }
+
     /// returns the globally persistent machine identifier
     /// and writes it if it does not exist
     /// the default implementation uses self.machine_id_config()
@@ -1,16 +1,32 @@
-use std::{collections::HashMap, convert::TryFrom, env, fmt::Debug, time::Duration};
-
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    env,
+    fmt::Debug,
+    fs::OpenOptions,
+    io::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Bytes;
 use camino::Utf8PathBuf;
 use ci_info::types::Vendor as CiVendor;
-use reqwest::{Client, Url};
+use reqwest::{
+    header::{CONTENT_TYPE, USER_AGENT},
+    Client, Response,
+};
 use rover_client::shared::GitContext;
-use rover_std::sha256_hex;
+use rover_http::{cassette::Cassette, Full, HttpServiceConfig, HttpServiceError, ReqwestService};
+use rover_std::{sha256_hex, Fs};
 use semver::Version;
 use serde::Serialize;
+use serde_json::json;
+use tower::ServiceExt;
+use url::Url;
 use uuid::Uuid;
 use wsl::is_wsl;
 
-use crate::{Report, SputnikError};
+use crate::{History, HistoryEntry, NotReported, Report, Sink, SputnikError};
 
 /// Timeout for reporting telemetry. Note that this includes the entire time to make the request
 /// and receive the response, including on the client side. This is not just the server latency.
@@ -80,8 +96,10 @@ pub struct Command {
 #[derive(Debug)]
 struct ReportingInfo {
     is_telemetry_enabled: bool,
-    endpoint: Url,
+    sink: Sink,
+    tool_name: String,
     user_agent: String,
+    history: History,
 }
 
 impl Session {
@@ -93,8 +111,10 @@ impl Session {
         let client = app.client()?;
         let reporting_info = ReportingInfo {
             is_telemetry_enabled: app.is_telemetry_enabled()?,
-            endpoint: app.endpoint()?,
+            sink: app.sink()?,
+            tool_name: app.tool_name(),
             user_agent: app.user_agent(),
+            history: History::new(app.history_path()?),
         };
         let current_dir = Utf8PathBuf::try_from(env::current_dir()?)?;
         let session_id = Uuid::new_v4();
@@ -134,32 +154,154 @@ impl Session {
         })
     }
 
-    /// sends anonymous usage data to the endpoint defined in ReportingInfo.
+    /// returns where this session will be reported to, or `None` if it
+    /// won't be reported at all.
+    pub const fn destination(&self) -> Option<&Sink> {
+        match self.route() {
+            Ok(sink) => Some(sink),
+            Err(_) => None,
+        }
+    }
+
+    /// returns where this session will be reported to, or why it won't be.
+    const fn route(&self) -> Result<&Sink, NotReported> {
+        if !self.reporting_info.is_telemetry_enabled {
+            return Err(NotReported::TelemetryDisabled);
+        }
+        match &self.reporting_info.sink {
+            // TODO: consider whether we want to disable non-production telemetry or at least
+            //  document the reasoning for not using it. sinks other than the default HTTP
+            //  endpoint are opted into explicitly, so they're written to from any build.
+            Sink::Http(_) if cfg!(debug_assertions) => Err(NotReported::DebugBuild),
+            sink => Ok(sink),
+        }
+    }
+
+    /// records this session in the local history, along with where it's
+    /// reported to, or why it isn't.
+    pub fn record(&self) -> Result<(), SputnikError> {
+        let route = self.route();
+        self.reporting_info.history.record(&HistoryEntry {
+            reported_to: route.as_ref().ok().map(|sink| sink.to_string()),
+            not_reported: route.err(),
+            payload: serde_json::to_value(self)?,
+        })
+    }
+
+    /// sends anonymous usage data to the sink defined in ReportingInfo.
     pub async fn report(&self) -> Result<(), SputnikError> {
-        // TODO: consider whether we want to disable non-production telemetry or at least document
-        //  the reasoning for not using it
-        if cfg!(debug_assertions) {
-            tracing::debug!("Skipping telemetry reporting");
-            return Ok(());
+        match self.destination() {
+            None => tracing::debug!("Skipping telemetry reporting"),
+            Some(Sink::Http(endpoint)) => {
+                let body = serde_json::to_string(&self)?;
+                tracing::debug!("POSTing to {}", endpoint);
+                tracing::debug!("{}", body);
+                self.post(endpoint.clone(), body).await?;
+            }
+            Some(Sink::JsonLines(path)) => {
+                let mut line = serde_json::to_string(&self)?;
+                line.push('\n');
+                tracing::debug!("appending to {}", path);
+                if let Some(parent) = path.parent().filter(|p| !p.as_str().is_empty()) {
+                    Fs::create_dir_all(parent)?;
+                }
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)?
+                    .write_all(line.as_bytes())?;
+            }
+            Some(Sink::Otlp(endpoint)) => {
+                let endpoint = Sink::otlp_logs_url(endpoint);
+                let body = serde_json::to_string(&self.otlp_logs_request()?)?;
+                tracing::debug!("exporting to {}", endpoint);
+                tracing::debug!("{}", body);
+                self.post(endpoint, body).await?.error_for_status()?;
+            }
         }
-        if self.reporting_info.is_telemetry_enabled {
-            let body = serde_json::to_string(&self)?;
-            tracing::debug!("POSTing to {}", &self.reporting_info.endpoint);
-            tracing::debug!("{}", body);
-            self.client
-                .post(self.reporting_info.endpoint.clone())
+
+        Ok(())
+    }
+
+    /// POSTs a JSON `body` to `endpoint`. While a [`Cassette`] is installed,
+    /// the request goes through a [`ReqwestService`] so that it's recorded to
+    /// (or replayed from) the cassette like every other request.
+    async fn post(&self, endpoint: Url, body: String) -> Result<Response, SputnikError> {
+        if Cassette::installed().is_none() {
+            return Ok(self
+                .client
+                .post(endpoint)
                 .body(body)
-                .header("User-Agent", &self.reporting_info.user_agent)
-                .header("Content-Type", "application/json")
+                .header(USER_AGENT, &self.reporting_info.user_agent)
+                .header(CONTENT_TYPE, "application/json")
                 .timeout(REPORT_TIMEOUT)
                 .send()
-                .await?;
+                .await?);
         }
+        let request = http::Request::post(endpoint.as_str())
+            .header(USER_AGENT, &self.reporting_info.user_agent)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(body)))
+            .map_err(HttpServiceError::from)?;
+        let response = ReqwestService::builder()
+            .config(HttpServiceConfig::builder().timeout(REPORT_TIMEOUT).build())
+            .client(self.client.clone())
+            .build()?
+            .oneshot(request)
+            .await?;
+        Ok(Response::from(response.map(reqwest::Body::wrap)))
+    }
 
-        Ok(())
+    /// wraps this session in an OTLP/HTTP `ExportLogsServiceRequest`, as a
+    /// single log record whose body is the session's JSON. the fields most
+    /// useful for aggregating in a collector are repeated as attributes.
+    fn otlp_logs_request(&self) -> Result<serde_json::Value, SputnikError> {
+        let time_unix_nano = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+            .to_string();
+        let ci = self
+            .platform
+            .continuous_integration
+            .as_ref()
+            .map(|vendor| format!("{vendor:?}"))
+            .unwrap_or_default();
+        Ok(json!({
+            "resourceLogs": [{
+                "resource": {
+                    "attributes": [
+                        otlp_attribute("service.name", &self.reporting_info.tool_name),
+                        otlp_attribute("service.version", &self.cli_version.to_string()),
+                        otlp_attribute("os.type", &self.platform.os),
+                        otlp_attribute("host.arch", &self.platform.arch),
+                    ]
+                },
+                "scopeLogs": [{
+                    "scope": { "name": "sputnik" },
+                    "logRecords": [{
+                        "timeUnixNano": time_unix_nano,
+                        "severityNumber": 9,
+                        "severityText": "INFO",
+                        "body": { "stringValue": serde_json::to_string(&self)? },
+                        "attributes": [
+                            otlp_attribute("command.name", &self.command.name),
+                            otlp_attribute("machine.id", &self.machine_id.to_string()),
+                            otlp_attribute("session.id", &self.session_id.to_string()),
+                            otlp_attribute("ci.vendor", &ci),
+                        ]
+                    }]
+                }]
+            }]
+        }))
     }
 }
 
+/// an OTLP `KeyValue` attribute with a string value.
+fn otlp_attribute(key: &str, value: &str) -> serde_json::Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
 /// returns sha256 digest of the directory the tool was executed from.
 fn get_cwd_hash(current_dir: &Utf8PathBuf) -> String {
     sha256_hex(current_dir.as_str())
@@ -177,7 +319,8 @@ mod tests {
     use httpmock::{Method::POST, MockServer};
     use reqwest::Client;
     use rstest::*;
-    use speculoos::{assert_that, result::ResultAssertions};
+    use speculoos::{assert_that, result::ResultAssertions, vec::VecAssertions};
+    use url::Url;
 
     use super::*;
 
@@ -213,8 +356,12 @@ mod tests {
             cli_version: Version::parse("0.0.0-test").unwrap(),
             reporting_info: ReportingInfo {
                 is_telemetry_enabled: true,
-                endpoint: Url::parse(format!("http://0.0.0.0/{}", report_path()).as_str()).unwrap(),
+                sink: Sink::Http(
+                    Url::parse(format!("http://0.0.0.0/{}", report_path()).as_str()).unwrap(),
+                ),
+                tool_name: "test-tool".into(),
                 user_agent: user_agent().into(),
+                history: History::new(Utf8PathBuf::from("telemetry_history.jsonl")),
             },
             client: Client::new(),
         }
@@ -248,7 +395,7 @@ mod tests {
         let server = MockServer::start();
         let addr = server.address().to_string();
         let mocked_addr = Url::parse(format!("http://{}/{}", addr, report_path).as_str()).unwrap();
-        session.reporting_info.endpoint = mocked_addr;
+        session.reporting_info.sink = Sink::Http(mocked_addr);
 
         let mocked = server.mock(|when, then| {
             when.method(POST)
@@ -293,4 +440,72 @@ mod tests {
 
         Ok(())
     }
+
+    /// each report is appended to a JSON Lines sink as its own line.
+    #[rstest]
+    #[tokio::test]
+    async fn test_report_appends_to_json_lines(mut session: Session) {
+        let fixture = assert_fs::TempDir::new().unwrap();
+        let path = Utf8PathBuf::try_from(fixture.path().join("usage/rover.jsonl")).unwrap();
+        session.reporting_info.sink = Sink::JsonLines(path.clone());
+
+        assert_that!(session.report().await).is_ok();
+        assert_that!(session.report().await).is_ok();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_that!(lines).has_length(2);
+        assert_that!(lines[0]["command"]["name"]).is_equal_to(json!("test-command"));
+    }
+
+    /// an OTLP sink gets the session as a log record, with the command name
+    /// as an attribute.
+    #[rstest]
+    #[tokio::test]
+    async fn test_report_exports_to_otlp(mut session: Session, user_agent: &'static str) {
+        let server = MockServer::start();
+        let mocked = server.mock(|when, then| {
+            when.method(POST)
+                .path("/v1/logs")
+                .header("User-Agent", user_agent)
+                .header("Content-Type", "application/json")
+                .body_includes(r#""key":"service.name","value":{"stringValue":"test-tool"}"#)
+                .body_includes(r#""key":"command.name","value":{"stringValue":"test-command"}"#);
+            then.status(200);
+        });
+        session.reporting_info.sink = Sink::Otlp(Url::parse(&server.base_url()).unwrap());
+
+        let res = session.report().await;
+
+        mocked.assert();
+        assert_that!(res).is_ok();
+    }
+
+    /// sessions are recorded in the history even when they aren't reported,
+    /// with where they were reported to or why they weren't.
+    #[rstest]
+    #[case::enabled(true, Some("http://0.0.0.0/some/report".to_string()), None)]
+    #[case::disabled(false, None, Some(NotReported::TelemetryDisabled))]
+    fn test_record(
+        #[case] is_telemetry_enabled: bool,
+        #[case] reported_to: Option<String>,
+        #[case] not_reported: Option<NotReported>,
+        mut session: Session,
+    ) {
+        let fixture = assert_fs::TempDir::new().unwrap();
+        let path = Utf8PathBuf::try_from(fixture.path().join("history.jsonl")).unwrap();
+        session.reporting_info.is_telemetry_enabled = is_telemetry_enabled;
+        session.reporting_info.history = History::new(path.clone());
+
+        session.record().unwrap();
+
+        let entries = History::new(path).last(10).unwrap();
+        assert_that!(entries).has_length(1);
+        assert_that!(entries[0].reported_to).is_equal_to(reported_to);
+        assert_that!(entries[0].not_reported).is_equal_to(not_reported);
+        assert_that!(entries[0].payload).is_equal_to(serde_json::to_value(&session).unwrap());
+    }
 }
@@ -0,0 +1,64 @@
+use std::fmt::{self, Display};
+
+use camino::Utf8PathBuf;
+use url::Url;
+
+/// Sink defines where a [`Session`](crate::Session) is reported to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sink {
+    /// POSTs each session as JSON to an HTTP endpoint. This is how usage data
+    /// reaches Apollo by default.
+    Http(Url),
+
+    /// Appends each session as a line of JSON to a local file, for teams that
+    /// collect usage data themselves.
+    JsonLines(Utf8PathBuf),
+
+    /// Exports each session as an OpenTelemetry log record to a collector's
+    /// OTLP/HTTP endpoint. The URL is the collector's base URL; `/v1/logs` is
+    /// appended unless it's already there.
+    Otlp(Url),
+}
+
+impl Sink {
+    /// returns the URL OTLP log records are POSTed to for a collector at `base`.
+    pub(crate) fn otlp_logs_url(base: &Url) -> Url {
+        let mut url = base.clone();
+        if !url.path().trim_end_matches('/').ends_with("/v1/logs") {
+            let path = format!("{}/v1/logs", url.path().trim_end_matches('/'));
+            url.set_path(&path);
+        }
+        url
+    }
+}
+
+impl Display for Sink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sink::Http(url) => write!(f, "{url}"),
+            Sink::JsonLines(path) => write!(f, "{path}"),
+            Sink::Otlp(url) => write!(f, "OTLP collector at {}", Sink::otlp_logs_url(url)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use url::Url;
+
+    use super::Sink;
+
+    #[rstest]
+    #[case::base_url("http://localhost:4318", "http://localhost:4318/v1/logs")]
+    #[case::trailing_slash("http://localhost:4318/", "http://localhost:4318/v1/logs")]
+    #[case::prefixed(
+        "https://otel.example.com/ingest",
+        "https://otel.example.com/ingest/v1/logs"
+    )]
+    #[case::already_logs("http://localhost:4318/v1/logs", "http://localhost:4318/v1/logs")]
+    fn otlp_logs_url_targets_the_logs_signal(#[case] base: &str, #[case] expected: &str) {
+        let actual = Sink::otlp_logs_url(&Url::parse(base).unwrap());
+        assert_eq!(actual.as_str(), expected);
+    }
+}
@@ -6,8 +6,13 @@
 use std::io;
 
 use clap::ValueEnum;
+use opentelemetry::{global, trace::TracerProvider, KeyValue};
+use opentelemetry_otlp::{ExporterBuildError, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracerProvider, Resource};
 pub use tracing_core::Level;
-use tracing_subscriber::fmt;
+use tracing_subscriber::{
+    filter::LevelFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt, Layer,
+};
 
 #[derive(Clone, ValueEnum)]
 /// Enum to describe the log levels that can be utilised by Rover, and by extension the Router
@@ -35,17 +40,131 @@ impl std::fmt::Display for RoverLogLevel {
     }
 }
 
+/// Where to export Rover's spans, for opting into OpenTelemetry tracing.
+#[derive(Clone, Debug)]
+pub struct OtlpConfig {
+    /// The base URL of an OpenTelemetry collector's OTLP/HTTP endpoint (e.g.
+    /// `http://localhost:4318`). `/v1/traces` is appended unless it's already there.
+    pub endpoint: String,
+    /// The `service.name` resource attribute spans are reported with.
+    pub service_name: &'static str,
+    /// The `service.version` resource attribute spans are reported with.
+    pub service_version: &'static str,
+}
+
+impl OtlpConfig {
+    /// returns the URL spans are POSTed to.
+    fn traces_endpoint(&self) -> String {
+        let endpoint = self.endpoint.trim_end_matches('/');
+        if endpoint.ends_with("/v1/traces") {
+            endpoint.to_string()
+        } else {
+            format!("{endpoint}/v1/traces")
+        }
+    }
+
+    fn tracer_provider(&self) -> Result<SdkTracerProvider, ExporterBuildError> {
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(self.traces_endpoint())
+            .build()?;
+        let resource = Resource::builder_empty()
+            .with_service_name(self.service_name)
+            .with_attribute(KeyValue::new("service.version", self.service_version))
+            .build();
+        Ok(SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(resource)
+            .build())
+    }
+}
+
+/// Flushes any spans that haven't been exported yet when dropped, so it
+/// should be kept alive until the application is about to exit.
+#[must_use = "spans are only flushed when the guard is dropped"]
+#[derive(Debug, Default)]
+pub struct TracingGuard {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("failed to export traces: {e}");
+            }
+        }
+    }
+}
+
 /// Initializes a global tracing subscriber that formats
 /// all logs produced by an application that calls init,
 /// and all logs produced by libraries consumed by that application.
-pub fn init(level: Option<Level>) {
+///
+/// If `otlp` is set, spans are also exported to an OpenTelemetry collector,
+/// and the W3C trace context propagator is installed so outgoing requests
+/// can carry a `traceparent` header.
+pub fn init(level: Option<Level>, otlp: Option<OtlpConfig>) -> TracingGuard {
     // by default, no logs are printed.
-    if let Some(level) = level {
-        let format = fmt::format().without_time().pretty();
-        fmt()
-            .with_max_level(level)
-            .event_format(format)
+    let fmt_layer = level.map(|level| {
+        fmt::layer()
+            .event_format(fmt::format().without_time().pretty())
             .with_writer(io::stderr)
+            .with_filter(LevelFilter::from_level(level))
+    });
+
+    let provider = otlp.and_then(|otlp| match otlp.tracer_provider() {
+        Ok(provider) => Some(provider),
+        Err(e) => {
+            eprintln!("failed to set up trace export to {}: {e}", otlp.endpoint);
+            None
+        }
+    });
+    let otel_layer = provider.as_ref().map(|provider| {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        // spans are exported at `info` and above regardless of what's
+        // printed, as that's where each unit of work is instrumented.
+        let filter = LevelFilter::from_level(level.unwrap_or(Level::INFO)).max(LevelFilter::INFO);
+        tracing_opentelemetry::layer()
+            .with_tracer(provider.tracer("rover"))
+            .with_filter(filter)
+    });
+
+    if fmt_layer.is_some() || otel_layer.is_some() {
+        tracing_subscriber::registry()
+            .with(fmt_layer)
+            .with(otel_layer)
             .init();
     }
+
+    TracingGuard { provider }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OtlpConfig;
+
+    fn config(endpoint: &str) -> OtlpConfig {
+        OtlpConfig {
+            endpoint: endpoint.to_string(),
+            service_name: "rover",
+            service_version: "0.0.0",
+        }
+    }
+
+    #[test]
+    fn traces_endpoint_targets_the_traces_signal() {
+        assert_eq!(
+            config("http://localhost:4318").traces_endpoint(),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            config("http://localhost:4318/").traces_endpoint(),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            config("http://localhost:4318/v1/traces").traces_endpoint(),
+            "http://localhost:4318/v1/traces"
+        );
+    }
 }
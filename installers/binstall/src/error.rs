@@ -21,6 +21,14 @@ pub enum InstallerError {
     #[error(transparent)]
     ReqwestError(#[from] reqwest::Error),
 
+    /// Something went wrong while sending an HTTP request through a service
+    #[error(transparent)]
+    HttpServiceError(Box<rover_http::HttpServiceError>),
+
+    /// The network settings couldn't be applied to the HTTP client
+    #[error(transparent)]
+    NetworkConfig(#[from] rover_http::NetworkConfigError),
+
     /// Couldn't find a valid install location on Unix
     #[error("Could not find the home directory of the current user")]
     NoHomeUnix,
@@ -53,3 +61,9 @@ pub enum InstallerError {
     #[error(transparent)]
     WindowsError(#[from] windows_result::Error),
 }
+
+impl From<rover_http::HttpServiceError> for InstallerError {
+    fn from(error: rover_http::HttpServiceError) -> Self {
+        InstallerError::HttpServiceError(Box::new(error))
+    }
+}
@@ -4,8 +4,11 @@ use bon::bon;
 use bytes::Bytes;
 use reqwest::header::{self, HeaderMap, HeaderValue};
 use rover_http::{
-    error_on_status::ErrorOnStatusLayer, extend_headers::ExtendHeadersLayer, retry::RetryPolicy,
-    timeout::TimeoutLayer, Full, HttpRequest, HttpResponse, HttpServiceError,
+    error_on_status::ErrorOnStatusLayer,
+    extend_headers::ExtendHeadersLayer,
+    retry::{RetryConfig, RetryPolicy},
+    timeout::TimeoutLayer,
+    Full, HttpRequest, HttpResponse, HttpServiceError,
 };
 use tower::{retry::RetryLayer, util::BoxService, Service, ServiceBuilder};
 use tower_http::decompression::{DecompressionBody, DecompressionLayer};
@@ -28,25 +31,31 @@ impl FileDownloadService {
         http_service: S1,
         max_elapsed_duration: Option<Duration>,
         timeout_duration: Option<Duration>,
+        // overrides `max_elapsed_duration`, along with the rest of the retry behavior
+        retry_config: Option<RetryConfig>,
     ) -> FileDownloadService
     where
         S1: Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
         S1::Error: Into<HttpServiceError>,
         S1::Future: Send + 'static,
     {
-        let service = ServiceBuilder::new()
-            .boxed()
-            .layer(DecompressionLayer::default()) // explicit stand-in for reqwest's brotli/gzip decompression options
-            .layer(ErrorOnStatusLayer::default()) // short-circuit errors so that we don't attempt to decompress error bodies
-            .layer(file_download_layer())
-            .layer(RetryLayer::new(RetryPolicy::new(
-                max_elapsed_duration
-                    .unwrap_or_else(|| Duration::from_secs(DEFAULT_ELAPSED_DURATION_SECONDS)),
-            )))
-            .layer(TimeoutLayer::new(timeout_duration.unwrap_or_else(|| {
-                Duration::from_secs(DEFAULT_TIMEOUT_DURATION_SECONDS)
-            })))
-            .service(http_service);
+        let service =
+            ServiceBuilder::new()
+                .boxed()
+                .layer(DecompressionLayer::default()) // explicit stand-in for reqwest's brotli/gzip decompression options
+                .layer(ErrorOnStatusLayer::default()) // short-circuit errors so that we don't attempt to decompress error bodies
+                .layer(file_download_layer())
+                .layer(RetryLayer::new(RetryPolicy::from_config(
+                    retry_config.unwrap_or_else(|| {
+                        RetryConfig::new(max_elapsed_duration.unwrap_or_else(|| {
+                            Duration::from_secs(DEFAULT_ELAPSED_DURATION_SECONDS)
+                        }))
+                    }),
+                )))
+                .layer(TimeoutLayer::new(timeout_duration.unwrap_or_else(|| {
+                    Duration::from_secs(DEFAULT_TIMEOUT_DURATION_SECONDS)
+                })))
+                .service(http_service);
         FileDownloadService { inner: service }
     }
 
@@ -7,7 +7,7 @@ use bytes::Bytes;
 use camino::Utf8PathBuf;
 use download::{gz_decode::GzDecodeLayer, FileDownloadService};
 use http::Request;
-use rover_http::Full;
+use rover_http::{Full, HttpServiceError, NetworkConfig, ReqwestService};
 use rover_std::Fs;
 use tower::{Service, ServiceBuilder, ServiceExt};
 use url::Url;
@@ -59,10 +59,11 @@ impl Installer {
         plugin_name: &str,
         plugin_tarball_url: &str,
         file_download_service: FileDownloadService,
+        network: &NetworkConfig,
         is_latest: bool,
     ) -> Result<Option<Utf8PathBuf>, InstallerError> {
         let version = self
-            .get_plugin_version(plugin_tarball_url, is_latest)
+            .get_plugin_version(plugin_tarball_url, network, is_latest)
             .await?;
 
         let bin_dir_path = self.get_bin_dir_path()?;
@@ -92,20 +93,30 @@ impl Installer {
         Ok(Some(plugin_bin_destination))
     }
 
+    /// The version of the plugin at `plugin_tarball_url`. For the latest version, this
+    /// asks the server with a HEAD request sent with the `network` settings.
     pub async fn get_plugin_version(
         &self,
         plugin_tarball_url: &str,
+        network: &NetworkConfig,
         is_latest: bool,
     ) -> Result<String, InstallerError> {
         if is_latest {
-            let no_redirect_client = reqwest::Client::builder()
-                .redirect(reqwest::redirect::Policy::none())
+            let no_redirect_client = network
+                .apply(reqwest::Client::builder().redirect(reqwest::redirect::Policy::none()))?
                 .build()?;
-            let response = no_redirect_client
-                .head(plugin_tarball_url)
-                .send()
-                .await?
-                .error_for_status()?;
+            // sent through a `ReqwestService` so it's recorded to (or replayed
+            // from) an installed cassette like every other request
+            let request = Request::head(plugin_tarball_url)
+                .body(Full::default())
+                .map_err(HttpServiceError::from)?;
+            let response = ReqwestService::builder()
+                .client(no_redirect_client)
+                .build()?
+                .oneshot(request)
+                .await?;
+            let response =
+                reqwest::Response::from(response.map(reqwest::Body::wrap)).error_for_status()?;
 
             if let Some(version) = response.headers().get("x-version") {
                 Ok(version
@@ -317,7 +328,7 @@ mod test {
     use http::{HeaderValue, Response, Uri};
     use httpmock::prelude::*;
     use reqwest::header::{ACCEPT, USER_AGENT};
-    use rover_http::{test::MockHttpService, Full, HttpServiceError};
+    use rover_http::{test::MockHttpService, Full, HttpServiceError, NetworkConfig};
     use rover_tower::{expect_poll_ready, test::MockCloneService};
     use rstest::{fixture, rstest};
     use sealed_test::prelude::*;
@@ -496,7 +507,9 @@ mod test {
             then.status(200).header("x-version", "1.0.0");
         });
         let tarball_url = format!("http://{}/{}", address, binary_name);
-        let result = installer.get_plugin_version(&tarball_url, true).await;
+        let result = installer
+            .get_plugin_version(&tarball_url, &NetworkConfig::default(), true)
+            .await;
         mock.assert_calls(1);
         assert_that!(result)
             .is_ok()
@@ -516,7 +529,9 @@ mod test {
             then.status(200);
         });
         let tarball_url = format!("http://{}/{}", address, binary_name);
-        let result = installer.get_plugin_version(&tarball_url, true).await;
+        let result = installer
+            .get_plugin_version(&tarball_url, &NetworkConfig::default(), true)
+            .await;
         mock.assert_calls(1);
         assert_that!(result)
             .is_err()
@@ -525,6 +540,28 @@ mod test {
             });
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_get_plugin_version_latest_uses_the_network_config(
+        binary_name: &str,
+        installer: Installer,
+    ) {
+        let proxy = MockServer::start();
+        let mock = proxy.mock(|when, then| {
+            when.method(Method::HEAD).path(format!("/{}", binary_name));
+            then.status(200).header("x-version", "1.0.0");
+        });
+        let network = NetworkConfig::builder().proxy(proxy.base_url()).build();
+        let tarball_url = format!("http://rover.invalid/{}", binary_name);
+        let result = installer
+            .get_plugin_version(&tarball_url, &network, true)
+            .await;
+        mock.assert_calls(1);
+        assert_that!(result)
+            .is_ok()
+            .is_equal_to("1.0.0".to_string());
+    }
+
     #[rstest]
     #[case::with_v_prefix("v1.0.0", "v1.0.0")]
     #[case::without_v_prefix("1.0.0", "v1.0.0")]
@@ -536,7 +573,9 @@ mod test {
         #[case] expected_version: &str,
     ) {
         let tarball_url = format!("http://example.com/{}/{}", binary_name, tarball_version_str);
-        let result = installer.get_plugin_version(&tarball_url, false).await;
+        let result = installer
+            .get_plugin_version(&tarball_url, &NetworkConfig::default(), false)
+            .await;
         assert_that!(result)
             .is_ok()
             .is_equal_to(expected_version.to_string());
@@ -4,7 +4,7 @@ use binstall::{download::FileDownloadService, Installer};
 use camino::Utf8PathBuf;
 use httpmock::prelude::*;
 use reqwest::header::{ACCEPT, USER_AGENT};
-use rover_http::ReqwestService;
+use rover_http::{NetworkConfig, ReqwestService};
 use sealed_test::prelude::*;
 use speculoos::prelude::*;
 
@@ -115,7 +115,13 @@ pub async fn test_install_plugin() {
         .build();
 
     let result = installer
-        .install_plugin(plugin_name, &tarball_url, service, true)
+        .install_plugin(
+            plugin_name,
+            &tarball_url,
+            service,
+            &NetworkConfig::default(),
+            true,
+        )
         .await;
 
     assert_that!(result)
@@ -1,4 +1,4 @@
-use std::{fmt::Display, io, process};
+use std::{fmt::Display, io, process, time::Duration};
 
 use camino::Utf8PathBuf;
 use clap::{
@@ -8,24 +8,26 @@ use clap::{
         styling::{AnsiColor, Effects},
     },
 };
-use config::Config;
+use config::{Config, RetryBudget};
 use houston as config;
 use lazycell::{AtomicLazyCell, LazyCell};
 use reqwest::Client;
 use rover_client::shared::GitContext;
+use rover_http::{NetworkConfig, cassette::Cassette, retry::RetryConfig};
 use rover_std::Style;
 use serde::Serialize;
-use sputnik::Session;
+use sputnik::{Report, Session};
 use timber::Level;
+use tracing::Instrument;
 
 #[cfg(feature = "oauth")]
 use crate::options::OauthOpts;
 use crate::{
-    RoverResult,
+    PKG_NAME, PKG_VERSION, RoverResult,
     command::{self, RoverOutput},
     options::OutputOpts,
     utils::{
-        client::{ClientBuilder, ClientTimeout, StudioClientConfig},
+        client::{ClientBuilder, ClientTimeout, DOWNLOAD_RETRY_PERIOD, StudioClientConfig},
         env::{RoverEnv, RoverEnvKey},
         stringify::option_from_display,
         version,
@@ -98,12 +100,101 @@ pub struct Rover {
     #[arg(long = "insecure-accept-invalid-hostnames", global = true)]
     accept_invalid_hostnames: bool,
 
+    /// Trust the root certificates in this PEM file, in addition to the platform's,
+    /// when performing HTTPS requests.
+    ///
+    /// Use this when Studio, your subgraphs or a proxy present certificates issued by a
+    /// private CA. Can also be set as `cacert` in `network.toml` in Rover's config directory.
+    #[arg(long = "cacert", value_name = "PEM", global = true)]
+    #[serde(skip_serializing)]
+    cacert: Option<Utf8PathBuf>,
+
+    /// Present the client certificate in this PEM file to servers that require mutual TLS.
+    ///
+    /// If the file doesn't also contain the private key, pass it with `--client-key`.
+    /// Can also be set as `client_cert` in `network.toml` in Rover's config directory.
+    #[arg(long = "client-cert", value_name = "PEM", global = true)]
+    #[serde(skip_serializing)]
+    client_cert: Option<Utf8PathBuf>,
+
+    /// The PEM private key for `--client-cert`.
+    ///
+    /// Can also be set as `client_key` in `network.toml` in Rover's config directory.
+    #[arg(
+        long = "client-key",
+        value_name = "PEM",
+        global = true,
+        requires = "client_cert"
+    )]
+    #[serde(skip_serializing)]
+    client_key: Option<Utf8PathBuf>,
+
+    /// Send every HTTP(S) request through this proxy, e.g. `http://proxy.example.com:3128`.
+    ///
+    /// Hosts listed in the `NO_PROXY` environment variable (or `no_proxy` in `network.toml`)
+    /// are still reached directly. Without this option, the `HTTPS_PROXY` and `HTTP_PROXY`
+    /// environment variables are honored. Can also be set as `proxy` in `network.toml` in
+    /// Rover's config directory.
+    #[arg(long = "proxy", value_name = "URL", global = true)]
+    #[serde(skip_serializing)]
+    proxy: Option<String>,
+
     /// Configure the timeout length (in seconds) when performing HTTP(S) requests.
     ///
     /// Defaults to 30s for standard operations and 300s for plugin downloads.
     #[arg(long = "client-timeout", global = true)]
     client_timeout: Option<ClientTimeout>,
 
+    /// The most attempts to make at a request that keeps failing with a transient
+    /// error (such as a dropped connection or a rate limit), counting the first.
+    ///
+    /// By default Rover keeps retrying until `--client-timeout` runs out (10 minutes for
+    /// plugin downloads). Can also be set per kind of request as `attempts` in the
+    /// `[studio]`, `[introspection]` and `[downloads]` tables of `retry.toml` in Rover's
+    /// config directory.
+    #[arg(long = "retry-attempts", value_name = "N", global = true)]
+    retry_attempts: Option<u32>,
+
+    /// How long to wait before the first retry, e.g. `500ms` or `2s`.
+    ///
+    /// Each retry after it waits twice as long as the one before, up to `--retry-max-delay`.
+    /// A `Retry-After` sent with a `429` or `503` response takes precedence. Defaults to
+    /// 500ms; can also be set as `min_delay` in `retry.toml`.
+    #[arg(
+        long = "retry-min-delay",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        global = true
+    )]
+    retry_min_delay: Option<Duration>,
+
+    /// The longest to wait between two attempts, e.g. `1m`.
+    ///
+    /// Defaults to 60s; can also be set as `max_delay` in `retry.toml`.
+    #[arg(
+        long = "retry-max-delay",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        global = true
+    )]
+    retry_max_delay: Option<Duration>,
+
+    /// Wait exactly the backoff between attempts, rather than stretching each delay by
+    /// a random amount.
+    ///
+    /// Can also be set as `jitter = false` in `retry.toml`.
+    #[arg(long = "no-retry-jitter", global = true)]
+    no_retry_jitter: bool,
+
+    /// Whether to retry GraphQL mutations, such as publishes, after a failure that may
+    /// have reached the server.
+    ///
+    /// Mutations are always retried when the server turns them away with a `429` or `503`,
+    /// or the connection couldn't be made at all. Defaults to `true`; can also be set as
+    /// `retry_mutations` in `retry.toml`.
+    #[arg(long = "retry-mutations", value_name = "BOOL", global = true)]
+    retry_mutations: Option<bool>,
+
     /// Skip checking for newer versions of rover.
     ///
     /// Set the `APOLLO_ROVER_SKIP_UPDATE` environment variable (to `1` or `true`)
@@ -135,14 +226,38 @@ impl Rover {
     }
 
     pub async fn run(&self) -> RoverResult<()> {
-        timber::init(self.log_level);
+        // spans are only exported to a collector once it's been opted into
+        let otlp = self
+            .get_env_var(RoverEnvKey::TracingOtlpUrl)?
+            .map(|endpoint| timber::OtlpConfig {
+                endpoint,
+                service_name: PKG_NAME,
+                service_version: PKG_VERSION,
+            });
+        let tracing_guard = timber::init(self.log_level, otlp);
         tracing::trace!(command_structure = ?self);
         self.output_opts.set_no_color();
+        self.install_http_cassette()?;
+
+        let command_span = tracing::info_span!(
+            "rover command",
+            rover.command = self
+                .serialize_command()
+                .map(|command| command.name)
+                .unwrap_or_default(),
+            otel.status_code = tracing::field::Empty,
+        );
 
         // attempt to create a new `Session` to capture anonymous usage data
         let rover_output = match Session::new(self) {
             // if successful, report the usage data in the background
             Ok(session) => {
+                // keeps a local copy of what's reported, for `rover telemetry show`.
+                // recorded up front so that it's there for the command itself to read
+                if let Err(history_error) = session.record() {
+                    tracing::debug!(?history_error);
+                }
+
                 // kicks off the reporting on a background thread
                 let report_thread = tokio::task::spawn(async move {
                     // log + ignore errors because it is not in the critical path
@@ -155,7 +270,10 @@ impl Rover {
                 // kicks off the app on the main thread
                 // don't return an error with ? quite yet
                 // since we still want to report the usage data
-                let app_result = self.execute_command().await;
+                let app_result = self
+                    .execute_command()
+                    .instrument(command_span.clone())
+                    .await;
 
                 // makes sure the reporting finishes in the background
                 // before continuing.
@@ -168,9 +286,21 @@ impl Rover {
             }
 
             // otherwise just run the app without reporting
-            Err(_) => self.execute_command().await,
+            Err(_) => {
+                self.execute_command()
+                    .instrument(command_span.clone())
+                    .await
+            }
         };
 
+        if rover_output.is_err() {
+            command_span.record("otel.status_code", "ERROR");
+        }
+        drop(command_span);
+        // `process::exit` skips destructors, so any spans that haven't been
+        // exported yet are flushed before exiting.
+        drop(tracing_guard);
+
         match rover_output {
             Ok(output) => {
                 let exit_code = output.exit_code();
@@ -220,10 +350,15 @@ impl Rover {
             Command::Contract(command) => command.run(self.get_client_config().await?).await,
             Command::Schema(command) => command.run(self.get_client_config().await?).await,
             Command::Dev(command) => {
+                let client_config = self.get_client_config().await?;
+                #[cfg(feature = "oauth")]
+                let _session_refresh = self
+                    .start_session_refresh(&client_config, &command.opts.plugin_opts.profile)
+                    .await?;
                 command
                     .run(
                         self.get_install_override_path()?,
-                        self.get_client_config().await?,
+                        client_config,
                         self.log_level,
                     )
                     .await
@@ -249,6 +384,7 @@ impl Rover {
                     .await
             }
             Command::Template(command) => command.run().await,
+            Command::Telemetry(command) => command.run(self.get_rover_config()?),
             Command::Readme(command) => command.run(self.get_client_config().await?).await,
             Command::Subgraph(command) => {
                 command
@@ -287,7 +423,14 @@ impl Rover {
             }
             Command::License(command) => command.run(self.get_client_config().await?).await,
             #[cfg(feature = "composition-js")]
-            Command::Lsp(command) => command.run(self.get_client_config().await?).await,
+            Command::Lsp(command) => {
+                let client_config = self.get_client_config().await?;
+                #[cfg(feature = "oauth")]
+                let _session_refresh = self
+                    .start_session_refresh(&client_config, &command.opts.plugin_opts.profile)
+                    .await?;
+                command.run(client_config).await
+            }
             Command::ApiKeys(command) => command.run(self.get_client_config().await?).await,
             Command::Client(command) => {
                 command
@@ -295,6 +438,17 @@ impl Rover {
                     .await
             }
             Command::GraphArtifact(command) => command.run(self.get_client_config().await?).await,
+            Command::Proposal(command) => {
+                command
+                    .run(self.get_client_config().await?, self.get_git_context()?)
+                    .await
+            }
+            Command::Launch(command) => command.run(self.get_client_config().await?).await,
+            Command::Variant(command) => command.run(self.get_client_config().await?).await,
+            Command::Operation(command) => command.run(),
+            Command::OperationCollection(command) => {
+                command.run(self.get_client_config().await?).await
+            }
         }
     }
 
@@ -311,12 +465,62 @@ impl Rover {
         command::auth::OauthConfig::builder()
             .authorization_url(self.oauth_opts.authorization_url.clone())
             .token_url(self.oauth_opts.token_url.clone())
+            .device_authorization_url(self.oauth_opts.device_authorization_url.clone())
             .revocation_url(self.oauth_opts.revocation_url.clone())
             .whoami_url(self.oauth_opts.whoami_url.clone())
             .client_id(self.oauth_opts.client_id.clone())
             .build()
     }
 
+    /// Keeps the OAuth session of `profile` (from `rover auth login`) fresh
+    /// for as long as the returned guard lives, so long-running commands like
+    /// `rover dev` and `rover lsp` don't start failing once the access token
+    /// they started with expires. Returns `None` when the command authenticates
+    /// some other way (an API key, `APOLLO_KEY`, or client credentials).
+    #[cfg(feature = "oauth")]
+    async fn start_session_refresh(
+        &self,
+        client_config: &StudioClientConfig,
+        profile: &crate::options::ProfileOpt,
+    ) -> RoverResult<Option<command::auth::SessionRefreshGuard>> {
+        use houston::{CredentialOrigin, Profile};
+        use rover_http::ReqwestService;
+        use rover_print::print::PrintExt;
+
+        let is_oauth_session =
+            Profile::get_credential(&profile.profile_name, &client_config.config).is_ok_and(
+                |credential| {
+                    matches!(
+                        credential.origin,
+                        CredentialOrigin::OauthAuthorizationPkce(_)
+                    )
+                },
+            );
+        if !is_oauth_session {
+            return Ok(None);
+        }
+
+        let http_service = ReqwestService::builder()
+            .client(client_config.get_reqwest_client()?)
+            .build()
+            .map_err(|e| anyhow::anyhow!("failed to build an HTTP client: {e}"))?;
+        let refresher = command::auth::SessionRefresher::new(
+            profile.profile_name.clone(),
+            client_config.config.clone(),
+            self.get_oauth_config(),
+            http_service,
+        );
+        // refresh up front, so the command doesn't start with a token that's
+        // about to expire; failing here isn't fatal, since the token may
+        // still be good for a while and the background task keeps retrying.
+        if let Err(error) = refresher.refresh_if_expiring().await {
+            rover_print::print::stderr::default().warnln(format!(
+                "failed to refresh the OAuth session for profile \"{profile}\": {error}"
+            ));
+        }
+        Ok(Some(refresher.start()))
+    }
+
     pub(crate) async fn get_client_config(&self) -> RoverResult<StudioClientConfig> {
         let override_endpoint = self.get_env_var(RoverEnvKey::RegistryUrl)?;
         let is_sudo = if let Some(fire_flower) = self.get_env_var(RoverEnvKey::FireFlower)? {
@@ -330,12 +534,35 @@ impl Rover {
             config.override_client_credentials_token =
                 self.resolve_client_credentials_token().await?;
         }
+        let retry_settings = config.retry_settings()?;
+        let client_timeout = self.client_timeout.map(|timeout| timeout.get_duration());
+        let default_retry_period = ClientTimeout::default().get_duration();
         let client_config = StudioClientConfig::new(
             override_endpoint,
             config,
             is_sudo,
-            self.get_reqwest_client_builder(),
+            self.get_reqwest_client_builder()?,
             self.client_timeout.unwrap_or_default(),
+        )
+        .with_retry_configs(
+            self.get_retry_config(
+                "studio",
+                &retry_settings.studio,
+                client_timeout,
+                default_retry_period,
+            )?,
+            self.get_retry_config(
+                "introspection",
+                &retry_settings.introspection,
+                client_timeout,
+                default_retry_period,
+            )?,
+            self.get_retry_config(
+                "downloads",
+                &retry_settings.downloads,
+                None,
+                DOWNLOAD_RETRY_PERIOD,
+            )?,
         );
         // Downloads should honor the client timeout if set despite having a different default
         Ok(match self.client_timeout {
@@ -433,6 +660,34 @@ impl Rover {
         Ok(None)
     }
 
+    /// Records every HTTP interaction to the directory in `APOLLO_ROVER_HTTP_RECORD`,
+    /// or answers every request from the one in `APOLLO_ROVER_HTTP_REPLAY`, so a
+    /// run can be reproduced offline.
+    fn install_http_cassette(&self) -> RoverResult<()> {
+        let record = self.get_env_var(RoverEnvKey::RoverHttpRecord)?;
+        let replay = self.get_env_var(RoverEnvKey::RoverHttpReplay)?;
+        let cassette = match (record, replay) {
+            (None, None) => return Ok(()),
+            (Some(dir), None) => Cassette::record(dir)?,
+            (None, Some(dir)) => Cassette::replay(dir)?,
+            (Some(_), Some(_)) => {
+                return Err(anyhow::anyhow!(
+                    "{} and {} can't both be set",
+                    RoverEnvKey::RoverHttpRecord,
+                    RoverEnvKey::RoverHttpReplay
+                )
+                .into());
+            }
+        };
+        tracing::info!(
+            mode = ?cassette.mode(),
+            dir = %cassette.dir().display(),
+            "using an HTTP cassette"
+        );
+        cassette.install();
+        Ok(())
+    }
+
     pub(crate) fn get_install_override_path(&self) -> RoverResult<Option<Utf8PathBuf>> {
         Ok(self
             .get_env_var(RoverEnvKey::Home)?
@@ -459,16 +714,16 @@ impl Rover {
         if let Some(client) = self.client.borrow() {
             Ok(client.clone())
         } else {
-            let client = self.get_reqwest_client_builder().build()?;
+            let client = self.get_reqwest_client_builder()?.build()?;
             let _ = self.client.fill(client);
             self.get_reqwest_client()
         }
     }
 
-    pub(crate) fn get_reqwest_client_builder(&self) -> ClientBuilder {
+    pub(crate) fn get_reqwest_client_builder(&self) -> anyhow::Result<ClientBuilder> {
         // return a copy of the underlying client builder if it's already been populated
         if let Some(client_builder) = self.client_builder.borrow() {
-            *client_builder
+            Ok(client_builder.clone())
         } else {
             // if a request hasn't been made yet, this cell won't be populated yet
             self.client_builder
@@ -476,13 +731,88 @@ impl Rover {
                     ClientBuilder::new()
                         .accept_invalid_certs(self.accept_invalid_certs)
                         .accept_invalid_hostnames(self.accept_invalid_hostnames)
-                        .with_timeout(self.client_timeout.unwrap_or_default().get_duration()),
+                        .with_timeout(self.client_timeout.unwrap_or_default().get_duration())
+                        .with_network(self.get_network_config()?),
                 )
                 .ok();
             self.get_reqwest_client_builder()
         }
     }
 
+    /// The trust, client identity and proxy settings from the global options,
+    /// falling back to `network.toml` in Rover's config directory for any that
+    /// weren't passed.
+    fn get_network_config(&self) -> anyhow::Result<NetworkConfig> {
+        let override_home = self
+            .get_env_var(RoverEnvKey::ConfigHome)?
+            .map(|p| Utf8PathBuf::from(&p));
+        let settings = Config::new(override_home.as_ref(), None)?.network_settings()?;
+
+        // a client key only makes sense alongside the certificate it belongs
+        // to, so the pair is taken from the flags or the file, never mixed.
+        let (client_cert, client_key) = match &self.client_cert {
+            Some(client_cert) => (Some(client_cert.clone()), self.client_key.clone()),
+            None => (settings.client_cert, settings.client_key),
+        };
+        Ok(NetworkConfig::builder()
+            .and_ca_certificate(
+                self.cacert
+                    .clone()
+                    .or(settings.cacert)
+                    .map(Utf8PathBuf::into_std_path_buf),
+            )
+            .and_client_certificate(client_cert.map(Utf8PathBuf::into_std_path_buf))
+            .and_client_key(client_key.map(Utf8PathBuf::into_std_path_buf))
+            .and_proxy(self.proxy.clone().or(settings.proxy))
+            .and_no_proxy(settings.no_proxy)
+            .build())
+    }
+
+    /// How one kind of request is retried: the global `--retry-*` options, falling
+    /// back to the request's table in `retry.toml` in Rover's config directory, and
+    /// then to the defaults. An explicit `max_elapsed` wins over `default_max_elapsed`.
+    fn get_retry_config(
+        &self,
+        table: &str,
+        budget: &RetryBudget,
+        max_elapsed: Option<Duration>,
+        default_max_elapsed: Duration,
+    ) -> anyhow::Result<RetryConfig> {
+        let duration = |setting: &str, value: &Option<String>| {
+            value
+                .as_deref()
+                .map(humantime::parse_duration)
+                .transpose()
+                .map_err(|e| {
+                    anyhow::anyhow!("invalid `{setting}` in the [{table}] table of retry.toml: {e}")
+                })
+        };
+        let max_elapsed = match max_elapsed {
+            Some(max_elapsed) => max_elapsed,
+            None => duration("max_elapsed", &budget.max_elapsed)?.unwrap_or(default_max_elapsed),
+        };
+        let jitter = if self.no_retry_jitter {
+            Some(false)
+        } else {
+            budget.jitter
+        };
+        RetryConfig::builder()
+            .max_elapsed_time(max_elapsed)
+            .and_max_attempts(self.retry_attempts.or(budget.attempts))
+            .and_min_delay(
+                self.retry_min_delay
+                    .or(duration("min_delay", &budget.min_delay)?),
+            )
+            .and_max_delay(
+                self.retry_max_delay
+                    .or(duration("max_delay", &budget.max_delay)?),
+            )
+            .and_jitter(jitter)
+            .and_retry_mutations(self.retry_mutations.or(budget.retry_mutations))
+            .build()
+            .map_err(|e| anyhow::anyhow!("invalid retry settings for {table} requests: {e}"))
+    }
+
     pub(crate) fn get_checks_timeout_seconds(&self) -> RoverResult<u64> {
         if let Some(seconds) = self.get_env_var(RoverEnvKey::ChecksTimeoutSeconds)? {
             Ok(seconds.parse::<u64>()?)
@@ -566,6 +896,9 @@ pub enum Command {
     /// Commands for working with templates
     Template(command::Template),
 
+    /// Inspect the anonymous usage data Rover reports
+    Telemetry(command::Telemetry),
+
     /// Readme commands
     Readme(command::Readme),
 
@@ -605,6 +938,21 @@ pub enum Command {
 
     /// Graph artifact commands
     GraphArtifact(command::GraphArtifact),
+
+    /// Schema proposal commands
+    Proposal(command::Proposal),
+
+    /// Launch commands
+    Launch(command::Launch),
+
+    /// Graph variant commands
+    Variant(command::Variant),
+
+    /// Commands for formatting and normalizing operation files
+    Operation(command::Operation),
+
+    /// Operation collection commands
+    OperationCollection(command::OperationCollection),
 }
 
 #[derive(Default, ValueEnum, Debug, Serialize, Clone, Copy, Eq, PartialEq)]
@@ -1,9 +1,10 @@
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use clap::Parser;
-use houston::{Config, Profile};
-use rover_auth::oauth2::authorization_flow::{
-    AuthorizationFlow, redirect::server::AxumRedirectServer,
+use houston::{OAuthSession, Profile};
+use rover_auth::oauth2::{
+    authorization_flow::{AuthorizationFlow, redirect::server::AxumRedirectServer},
+    device_flow::DeviceFlow,
 };
 use rover_http::ReqwestService;
 use rover_open::{NoopOpenUrl, OpenUrl, SystemOpenUrl};
@@ -11,7 +12,7 @@ use serde::Serialize;
 use url::Url;
 
 use super::OauthConfig;
-use crate::{RoverOutput, RoverResult, options::ProfileOpt};
+use crate::{RoverOutput, RoverResult, options::ProfileOpt, utils::client::StudioClientConfig};
 
 #[derive(Debug, Serialize, Parser)]
 /// Log in via your browser to authenticate `rover` with Apollo
@@ -19,6 +20,10 @@ use crate::{RoverOutput, RoverResult, options::ProfileOpt};
 /// Opens your default browser to complete an OAuth login. Once you
 /// authorize the request, the resulting credential is saved for the
 /// given `--profile` (or "default"), the same way `rover config auth` does.
+///
+/// On machines where no browser can reach this one (over SSH, in a
+/// devcontainer, ...), pass `--device` to approve the login from any other
+/// device instead.
 pub struct Login {
     #[clap(flatten)]
     profile: ProfileOpt,
@@ -27,6 +32,12 @@ pub struct Login {
     /// is always printed, so pass this if you'd rather open it yourself.
     #[arg(long)]
     no_open: bool,
+
+    /// Log in with a code you enter on another device, rather than a browser
+    /// redirect to this machine. Prints a URL and a code to enter there, then
+    /// waits until you approve the login.
+    #[arg(long, conflicts_with = "no_open")]
+    device: bool,
 }
 
 /// Picks which [`OpenUrl`] implementation `authorize()` uses, since it's
@@ -47,12 +58,35 @@ impl OpenUrl for BrowserOpener {
 }
 
 impl Login {
-    pub async fn run(&self, config: Config, oauth_config: OauthConfig) -> RoverResult<RoverOutput> {
+    pub async fn run(
+        &self,
+        client_config: StudioClientConfig,
+        oauth_config: OauthConfig,
+    ) -> RoverResult<RoverOutput> {
         let http_service = ReqwestService::builder()
-            .client(reqwest::Client::new())
+            .client(client_config.get_reqwest_client()?)
             .build()
             .map_err(|e| anyhow::anyhow!("failed to build an HTTP client: {e}"))?;
 
+        let session = if self.device {
+            Self::device_login(oauth_config, http_service).await?
+        } else {
+            self.browser_login(oauth_config, http_service).await?
+        };
+        Profile::set_oauth_session(&self.profile.profile_name, &client_config.config, session)?;
+
+        Ok(RoverOutput::MessageResponse {
+            msg: "Successfully logged in.".to_string(),
+        })
+    }
+
+    /// Runs the PKCE authorization code flow, with the browser redirecting
+    /// back to a server bound on this machine.
+    async fn browser_login(
+        &self,
+        oauth_config: OauthConfig,
+        http_service: ReqwestService,
+    ) -> RoverResult<OAuthSession> {
         let browser_opener = if self.no_open {
             BrowserOpener::Noop(NoopOpenUrl::default())
         } else {
@@ -79,22 +113,46 @@ impl Login {
             .await
             .map_err(|e| anyhow::anyhow!("failed to exchange the authorization code: {e}"))?;
 
-        Profile::set_oauth_tokens(
-            &self.profile.profile_name,
-            &config,
-            tokens.access_token.secret().to_string(),
-            tokens.refresh_token.map(|t| t.secret().to_string()),
-            expires_at(tokens.expires_in),
-        )?;
+        Ok(OAuthSession {
+            access_token: tokens.access_token.secret().to_string(),
+            refresh_token: tokens.refresh_token.map(|t| t.secret().to_string()),
+            expires_at: expires_at(tokens.expires_in),
+            scopes: tokens.scopes.iter().map(|s| s.to_string()).collect(),
+        })
+    }
 
-        Ok(RoverOutput::MessageResponse {
-            msg: "Successfully logged in.".to_string(),
+    /// Runs the device authorization grant: prints a code for the user to
+    /// enter on another device, then polls until they approve it.
+    async fn device_login(
+        oauth_config: OauthConfig,
+        http_service: ReqwestService,
+    ) -> RoverResult<OAuthSession> {
+        let stderr = rover_print::print::stderr::default();
+        let device_flow = DeviceFlow::builder()
+            .client_id(oauth_config.client_id)
+            .device_authorization_url(oauth_config.device_authorization_url)
+            .token_url(oauth_config.token_url)
+            .build();
+        let device_flow = device_flow
+            .request_device_code(Vec::new(), http_service.clone(), &stderr)
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to start the device login: {e}"))?;
+        let tokens = device_flow
+            .poll_for_token(http_service, tokio::time::sleep)
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to complete the device login: {e}"))?;
+
+        Ok(OAuthSession {
+            access_token: tokens.access_token.secret().to_string(),
+            refresh_token: tokens.refresh_token.map(|t| t.secret().to_string()),
+            expires_at: expires_at(tokens.expires_in),
+            scopes: tokens.scopes.iter().map(|s| s.to_string()).collect(),
         })
     }
 }
 
 /// Converts an access token's lifetime into a Unix timestamp of its expiry.
-fn expires_at(expires_in: Option<Duration>) -> Option<i64> {
+pub(super) fn expires_at(expires_in: Option<Duration>) -> Option<i64> {
     expires_in.map(|expires_in| {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
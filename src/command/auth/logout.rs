@@ -50,7 +50,7 @@ impl Logout {
     pub async fn run(&self, config: Config, oauth_config: OauthConfig) -> RoverResult<RoverOutput> {
         let profile_name = &self.profile.profile_name;
 
-        let Some(session) = Profile::get_oauth_session(profile_name, &config)? else {
+        let Some(session) = Profile::get_stored_oauth_session(profile_name, &config)? else {
             return Err(RoverError::new(anyhow::anyhow!(
                 "profile \"{profile_name}\" isn't logged in via `rover auth login`"
             ))
@@ -187,6 +187,8 @@ mod tests {
         let session = OAuthSession {
             access_token: "access-token".to_string(),
             refresh_token: Some("refresh-token".to_string()),
+            expires_at: None,
+            scopes: Vec::new(),
         };
 
         let mut http_service = MockHttpService::new();
@@ -229,6 +231,8 @@ mod tests {
         let session = OAuthSession {
             access_token: "access-token".to_string(),
             refresh_token: Some("refresh-token".to_string()),
+            expires_at: None,
+            scopes: Vec::new(),
         };
 
         let mut http_service = MockHttpService::new();
@@ -291,6 +295,8 @@ mod tests {
         let session = OAuthSession {
             access_token: "access-token".to_string(),
             refresh_token: None,
+            expires_at: None,
+            scopes: Vec::new(),
         };
 
         let tokens = revocable_tokens(&session);
@@ -308,6 +314,8 @@ mod tests {
         let session = OAuthSession {
             access_token: "access-token".to_string(),
             refresh_token: Some("refresh-token".to_string()),
+            expires_at: None,
+            scopes: Vec::new(),
         };
 
         let tokens = revocable_tokens(&session);
@@ -1,12 +1,18 @@
 mod config;
 mod login;
 mod logout;
+mod refresh;
+mod status;
+mod switch;
 mod whoami;
 
 use clap::{Parser, Subcommand};
 use serde::Serialize;
 
-pub use self::config::OauthConfig;
+pub use self::{
+    config::OauthConfig,
+    refresh::{SessionRefreshGuard, SessionRefresher},
+};
 use crate::{RoverResult, utils::client::StudioClientConfig};
 
 #[derive(Debug, Serialize, Parser)]
@@ -21,6 +27,10 @@ pub enum AuthCommand {
     Login(login::Login),
     /// Log out, clearing your stored OAuth session
     Logout(logout::Logout),
+    /// List every profile's session, with its expiry and granted scopes
+    Status(status::Status),
+    /// Change which profile is used when `--profile` isn't given
+    Switch(switch::Switch),
     /// Display the identity of the currently authenticated profile
     Whoami(whoami::WhoAmI),
 }
@@ -32,8 +42,10 @@ impl Auth {
         oauth_config: OauthConfig,
     ) -> RoverResult<crate::RoverOutput> {
         match &self.command {
-            AuthCommand::Login(command) => command.run(client_config.config, oauth_config).await,
+            AuthCommand::Login(command) => command.run(client_config, oauth_config).await,
             AuthCommand::Logout(command) => command.run(client_config.config, oauth_config).await,
+            AuthCommand::Status(command) => command.run(client_config, oauth_config).await,
+            AuthCommand::Switch(command) => command.run(client_config.config),
             AuthCommand::Whoami(command) => command.run(client_config, oauth_config).await,
         }
     }
@@ -0,0 +1,381 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use houston::{Config, OAuthSession, Profile};
+use rover_auth::oauth2::refresh_token::{RefreshToken, RefreshTokenRequest};
+use rover_http::{Full, HttpRequest, HttpResponse, HttpServiceError};
+use tokio::task::JoinHandle;
+use tower::{Service, ServiceExt};
+
+use super::{OauthConfig, login::expires_at};
+use crate::RoverResult;
+
+/// How long before its expiry an access token is refreshed. Generous enough
+/// that a request started just before the refresh still carries a token
+/// that's valid by the time it reaches Studio.
+const REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// How long to wait before retrying a background refresh that failed (e.g.
+/// the OAuth server was briefly unreachable).
+const REFRESH_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Keeps a profile's OAuth session (from `rover auth login`) from expiring
+/// underneath a long-running command like `rover dev` or `rover lsp`, by
+/// exchanging its refresh token for a new access token shortly before the
+/// current one expires and saving the result back to the profile.
+///
+/// Studio clients and services read an expiring credential from the profile
+/// for every request (see [`houston::CredentialSource`]), so saving the
+/// refreshed session is all it takes for services built before the refresh
+/// to send the new access token.
+pub struct SessionRefresher<S> {
+    profile_name: String,
+    config: Config,
+    oauth_config: OauthConfig,
+    http_service: S,
+}
+
+impl<S> SessionRefresher<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse, Error = HttpServiceError>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+{
+    pub const fn new(
+        profile_name: String,
+        config: Config,
+        oauth_config: OauthConfig,
+        http_service: S,
+    ) -> SessionRefresher<S> {
+        SessionRefresher {
+            profile_name,
+            config,
+            oauth_config,
+            http_service,
+        }
+    }
+
+    /// Keeps refreshing the profile's session in the background, each time
+    /// shortly before it expires, until the returned guard is dropped.
+    pub fn start(self) -> SessionRefreshGuard
+    where
+        S: Sync,
+    {
+        let handle = tokio::spawn(async move {
+            loop {
+                let delay = match self.refresh_if_expiring().await {
+                    Ok(Some(expires_at)) => refresh_delay(expires_at, now()),
+                    // nothing that expires (or nothing to refresh it with),
+                    // so there's nothing left to do.
+                    Ok(None) => return,
+                    Err(error) => {
+                        tracing::warn!(
+                            profile = self.profile_name,
+                            "failed to refresh OAuth session: {error}"
+                        );
+                        REFRESH_RETRY_INTERVAL
+                    }
+                };
+                tokio::time::sleep(delay).await;
+            }
+        });
+        SessionRefreshGuard(handle)
+    }
+
+    /// Refreshes the profile's session if its access token expires within
+    /// [`REFRESH_MARGIN`]. Returns the (possibly new) expiry, or `None` when
+    /// the session can't be refreshed: it has no expiry, no refresh token, or
+    /// the profile holds an API key rather than an OAuth session.
+    pub async fn refresh_if_expiring(&self) -> RoverResult<Option<i64>> {
+        // refresh the session that requests use, which for `default` is the
+        // profile `rover auth switch` selected.
+        let profile_name = self.config.resolve_profile(&self.profile_name);
+        let Some(session) = Profile::get_stored_oauth_session(&profile_name, &self.config)? else {
+            return Ok(None);
+        };
+        let (Some(current_expiry), Some(refresh_token)) =
+            (session.expires_at, session.refresh_token.clone())
+        else {
+            return Ok(None);
+        };
+        if !needs_refresh(current_expiry, now()) {
+            return Ok(Some(current_expiry));
+        }
+
+        let request = RefreshTokenRequest::builder()
+            .client_id(self.oauth_config.client_id.clone())
+            .token_url(self.oauth_config.token_url.clone())
+            .refresh_token(refresh_token)
+            .build();
+        let service: RefreshToken<_, Full<Bytes>> = RefreshToken::new(self.http_service.clone());
+        let response = service
+            .oneshot(request)
+            .await
+            .map_err(|e| anyhow!("failed to refresh the OAuth session: {e}"))?;
+
+        let refreshed = OAuthSession {
+            access_token: response.access_token.secret().to_string(),
+            // servers that don't rotate refresh tokens omit a new one, in
+            // which case the current one stays valid.
+            refresh_token: response
+                .refresh_token
+                .map(|t| t.secret().to_string())
+                .or(session.refresh_token),
+            expires_at: expires_at(response.expires_in),
+            scopes: if response.scopes.is_empty() {
+                session.scopes
+            } else {
+                response.scopes.iter().map(|s| s.to_string()).collect()
+            },
+        };
+        let new_expiry = refreshed.expires_at;
+        Profile::set_oauth_session(&profile_name, &self.config, refreshed)?;
+        tracing::debug!(profile = profile_name, "refreshed OAuth session");
+
+        Ok(new_expiry)
+    }
+}
+
+/// Stops the background refresh started by [`SessionRefresher::start`] when
+/// dropped, i.e. when the command it was started for finishes.
+pub struct SessionRefreshGuard(JoinHandle<()>);
+
+impl Drop for SessionRefreshGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Whether an access token expiring at `expires_at` is due for a refresh.
+const fn needs_refresh(expires_at: i64, now: i64) -> bool {
+    expires_at - now <= REFRESH_MARGIN.as_secs() as i64
+}
+
+/// How long to wait until an access token expiring at `expires_at` is due
+/// for a refresh.
+fn refresh_delay(expires_at: i64, now: i64) -> Duration {
+    let due_at = expires_at - REFRESH_MARGIN.as_secs() as i64;
+    Duration::from_secs(u64::try_from(due_at - now).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use assert_fs::TempDir;
+    use camino::Utf8Path;
+    use houston::CredentialSource;
+    use rover_http::test::MockHttpService;
+    use rover_studio::service::HttpStudioServiceLayer;
+    use rover_tower::{expect_poll_ready, test::MockCloneService};
+    use serial_test::serial;
+    use speculoos::prelude::*;
+    use tower::Layer;
+
+    use super::*;
+
+    fn test_config() -> (Config, TempDir) {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_home_path = Utf8Path::from_path(tmp_home.path()).unwrap().to_owned();
+        (Config::new(Some(&tmp_home_path), None).unwrap(), tmp_home)
+    }
+
+    fn token_200() -> http::Response<Full<Bytes>> {
+        let body = serde_json::json!({
+            "access_token": "new-access-token",
+            "token_type": "Bearer",
+            "expires_in": 3600
+        });
+        http::Response::builder()
+            .body(Full::new(Bytes::from(serde_json::to_vec(&body).unwrap())))
+            .unwrap()
+    }
+
+    fn session(expires_at: i64) -> OAuthSession {
+        OAuthSession {
+            access_token: "old-access-token".to_string(),
+            refresh_token: Some("refresh-token".to_string()),
+            expires_at: Some(expires_at),
+            scopes: vec!["rover:cli".to_string()],
+        }
+    }
+
+    #[test]
+    fn needs_refresh_only_within_the_margin() {
+        assert_that!(needs_refresh(1_000 + 3600, 1_000)).is_false();
+        assert_that!(needs_refresh(1_000 + 60, 1_000)).is_true();
+        assert_that!(needs_refresh(1_000 - 60, 1_000)).is_true();
+    }
+
+    #[test]
+    fn refresh_delay_waits_until_the_margin_and_never_goes_negative() {
+        assert_that!(refresh_delay(1_000 + 3600, 1_000))
+            .is_equal_to(Duration::from_secs(3600) - REFRESH_MARGIN);
+        assert_that!(refresh_delay(1_000, 1_000)).is_equal_to(Duration::ZERO);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn refresh_if_expiring_saves_the_refreshed_session() {
+        let (config, _tmp_home) = test_config();
+        let profile_name = "refresh-expiring";
+        Profile::set_oauth_session(profile_name, &config, session(now() + 30)).unwrap();
+
+        let mut http_service = MockHttpService::new();
+        expect_poll_ready!(http_service, 1);
+        http_service
+            .expect_call()
+            .times(1)
+            .returning(|_| futures::future::ready(Ok(token_200())));
+
+        let refresher = SessionRefresher::new(
+            profile_name.to_string(),
+            config.clone(),
+            OauthConfig::default(),
+            MockCloneService::new(http_service),
+        );
+        let result = refresher.refresh_if_expiring().await;
+
+        assert_that!(result)
+            .is_ok()
+            .is_some()
+            .matches(|expires_at| *expires_at > now() + 3000);
+        let stored = Profile::get_oauth_session(profile_name, &config)
+            .unwrap()
+            .unwrap();
+        assert_that!(stored.access_token).is_equal_to("new-access-token".to_string());
+        // the server didn't rotate the refresh token or report scopes, so
+        // both are carried over from the previous session.
+        assert_that!(stored.refresh_token).is_equal_to(Some("refresh-token".to_string()));
+        assert_that!(stored.scopes).is_equal_to(vec!["rover:cli".to_string()]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn refresh_if_expiring_leaves_a_fresh_session_alone() {
+        let (config, _tmp_home) = test_config();
+        let profile_name = "refresh-fresh";
+        let expires_at = now() + 3600;
+        Profile::set_oauth_session(profile_name, &config, session(expires_at)).unwrap();
+
+        // no expectations: any HTTP call fails the test.
+        let refresher = SessionRefresher::new(
+            profile_name.to_string(),
+            config.clone(),
+            OauthConfig::default(),
+            MockCloneService::new(MockHttpService::new()),
+        );
+        let result = refresher.refresh_if_expiring().await;
+
+        assert_that!(result).is_ok().is_equal_to(Some(expires_at));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn refresh_if_expiring_does_nothing_for_an_api_key_profile() {
+        let (config, _tmp_home) = test_config();
+        let profile_name = "refresh-api-key";
+        Profile::set_api_key(profile_name, &config, "some-key").unwrap();
+
+        let refresher = SessionRefresher::new(
+            profile_name.to_string(),
+            config,
+            OauthConfig::default(),
+            MockCloneService::new(MockHttpService::new()),
+        );
+        let result = refresher.refresh_if_expiring().await;
+
+        assert_that!(result).is_ok().is_none();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn a_refreshed_token_reaches_a_service_built_before_the_refresh() {
+        let (config, _tmp_home) = test_config();
+        let profile_name = "refresh-long-lived";
+        Profile::set_oauth_session(profile_name, &config, session(now() + 30)).unwrap();
+
+        // built once, up front, the way `rover dev` builds its Studio services.
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let sent_in_mock = sent.clone();
+        let mut studio = MockHttpService::new();
+        expect_poll_ready!(studio, 2);
+        studio.expect_call().times(2).returning(move |req| {
+            let auth = req.headers()[http::header::AUTHORIZATION].clone();
+            sent_in_mock.lock().unwrap().push(auth);
+            futures::future::ready(Ok(http::Response::new(Full::default())))
+        });
+        let mut studio = HttpStudioServiceLayer::new(
+            "https://studio.example.com".parse().unwrap(),
+            CredentialSource::profile(profile_name, &config).unwrap(),
+            "test-version".to_string(),
+            false,
+        )
+        .unwrap()
+        .layer(MockCloneService::new(studio));
+        let request = || http::Request::new(Full::default());
+        studio.ready().await.unwrap().call(request()).await.unwrap();
+
+        let mut token_endpoint = MockHttpService::new();
+        expect_poll_ready!(token_endpoint, 1);
+        token_endpoint
+            .expect_call()
+            .times(1)
+            .returning(|_| futures::future::ready(Ok(token_200())));
+        SessionRefresher::new(
+            profile_name.to_string(),
+            config.clone(),
+            OauthConfig::default(),
+            MockCloneService::new(token_endpoint),
+        )
+        .refresh_if_expiring()
+        .await
+        .unwrap();
+        studio.ready().await.unwrap().call(request()).await.unwrap();
+
+        assert_that!(*sent.lock().unwrap()).is_equal_to(vec![
+            http::HeaderValue::from_static("Bearer old-access-token"),
+            http::HeaderValue::from_static("Bearer new-access-token"),
+        ]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn refresh_if_expiring_refreshes_the_switched_to_profile() {
+        let (config, _tmp_home) = test_config();
+        let profile_name = "refresh-switched";
+        Profile::set_oauth_session(profile_name, &config, session(now() + 30)).unwrap();
+        config.set_default_profile(profile_name).unwrap();
+
+        let mut http_service = MockHttpService::new();
+        expect_poll_ready!(http_service, 1);
+        http_service
+            .expect_call()
+            .times(1)
+            .returning(|_| futures::future::ready(Ok(token_200())));
+        SessionRefresher::new(
+            "default".to_string(),
+            config.clone(),
+            OauthConfig::default(),
+            MockCloneService::new(http_service),
+        )
+        .refresh_if_expiring()
+        .await
+        .unwrap();
+
+        let stored = Profile::get_stored_oauth_session(profile_name, &config)
+            .unwrap()
+            .unwrap();
+        assert_that!(stored.access_token).is_equal_to("new-access-token".to_string());
+    }
+}
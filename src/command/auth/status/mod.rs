@@ -0,0 +1,243 @@
+mod output;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use chrono::DateTime;
+use clap::Parser;
+use houston::{Config, OAuthSession, Profile};
+use rover_auth::oauth2::{
+    AccessToken,
+    status::{Whoami, WhoamiError, WhoamiRequest},
+};
+use rover_http::{
+    HttpRequest, HttpResponse, HttpServiceError, ReqwestService, timeout::TimeoutLayer,
+};
+use serde::Serialize;
+use tower::{Service, ServiceBuilder};
+
+use self::output::{AuthStatusOutput, ProfileSession, SessionState};
+use super::OauthConfig;
+use crate::{
+    RoverOutput, RoverResult, options::DEFAULT_PROFILE, utils::client::StudioClientConfig,
+};
+
+/// Bounds each profile's identity lookup, so one unreachable session can't
+/// hold up the whole listing. Matches `rover auth whoami`'s per-attempt timeout.
+const STATUS_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Parser)]
+/// List every profile's session, with its expiry and granted scopes
+///
+/// Each OAuth session from `rover auth login` is checked against the OAuth
+/// server, so expired or revoked sessions are reported as such. Profiles
+/// using a Personal API Key are listed too, but never expire.
+pub struct Status {}
+
+impl Status {
+    pub async fn run(
+        &self,
+        client_config: StudioClientConfig,
+        oauth_config: OauthConfig,
+    ) -> RoverResult<RoverOutput> {
+        let http_service = ServiceBuilder::new()
+            .layer(TimeoutLayer::new(STATUS_ATTEMPT_TIMEOUT))
+            .service(
+                ReqwestService::builder()
+                    .client(client_config.get_reqwest_client()?)
+                    .build()
+                    .map_err(|e| anyhow!("failed to build an HTTP client: {e}"))?,
+            );
+
+        let sessions =
+            Self::profile_sessions(&client_config.config, &oauth_config, http_service).await?;
+
+        Ok(RoverOutput::CliOutput(Box::new(AuthStatusOutput {
+            sessions,
+        })))
+    }
+
+    /// Builds a [`ProfileSession`] for every known profile. Generic over the
+    /// HTTP service used for identity lookups so it can be exercised in tests
+    /// against a mock HTTP layer instead of a live server.
+    async fn profile_sessions<S>(
+        config: &Config,
+        oauth_config: &OauthConfig,
+        http_service: S,
+    ) -> RoverResult<Vec<ProfileSession>>
+    where
+        S: Service<HttpRequest, Response = HttpResponse, Error = HttpServiceError>
+            + Clone
+            + Send
+            + 'static,
+        S::Future: Send,
+    {
+        let default_profile = config.resolve_profile(DEFAULT_PROFILE);
+        let mut profiles = Profile::list(config)?;
+        profiles.sort();
+
+        let mut sessions = Vec::with_capacity(profiles.len());
+        for profile in profiles {
+            let is_default = profile == default_profile;
+            let session = match Profile::get_stored_oauth_session(&profile, config)? {
+                Some(session) => {
+                    let (state, email) =
+                        session_state(&session, oauth_config, http_service.clone()).await;
+                    ProfileSession {
+                        profile,
+                        is_default,
+                        state,
+                        email,
+                        expires_at: session
+                            .expires_at
+                            .and_then(|expires_at| DateTime::from_timestamp(expires_at, 0)),
+                        scopes: session.scopes,
+                    }
+                }
+                None => ProfileSession {
+                    profile,
+                    is_default,
+                    state: SessionState::ApiKey,
+                    email: None,
+                    expires_at: None,
+                    scopes: Vec::new(),
+                },
+            };
+            sessions.push(session);
+        }
+        Ok(sessions)
+    }
+}
+
+/// Looks up the identity behind an OAuth session, classifying the session by
+/// whether the OAuth server still accepts its access token.
+async fn session_state<S>(
+    session: &OAuthSession,
+    oauth_config: &OauthConfig,
+    http_service: S,
+) -> (SessionState, Option<String>)
+where
+    S: Service<HttpRequest, Response = HttpResponse, Error = HttpServiceError>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+{
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    if session
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= now)
+    {
+        return (SessionState::Expired, None);
+    }
+
+    let request = WhoamiRequest::new(
+        oauth_config.whoami_url.clone(),
+        AccessToken::new(session.access_token.clone()),
+    );
+    match Whoami::fetch(http_service, request).await {
+        Ok(response) => (SessionState::Valid, Some(response.email)),
+        Err(WhoamiError::NotLoggedIn) => (SessionState::Expired, None),
+        Err(error) => {
+            tracing::debug!("failed to look up OAuth session identity: {error}");
+            (SessionState::Unknown, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use bytes::Bytes;
+    use camino::Utf8Path;
+    use rover_http::{Full, test::MockHttpService};
+    use rover_tower::{expect_poll_ready, test::MockCloneService};
+    use serial_test::serial;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn test_config() -> (Config, TempDir) {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_home_path = Utf8Path::from_path(tmp_home.path()).unwrap().to_owned();
+        (Config::new(Some(&tmp_home_path), None).unwrap(), tmp_home)
+    }
+
+    fn whoami_200() -> http::Response<Full<Bytes>> {
+        let body = serde_json::json!({
+            "sub": "user-123",
+            "email": "grace@apollographql.com",
+            "name": "Grace Hopper",
+        });
+        http::Response::builder()
+            .body(Full::new(Bytes::from(body.to_string())))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn profile_sessions_reports_every_profile_and_the_default() {
+        let (config, _tmp_home) = test_config();
+        Profile::set_api_key("status-ci", &config, "some-key").unwrap();
+        Profile::set_oauth_session(
+            "status-work",
+            &config,
+            OAuthSession {
+                access_token: "access-token".to_string(),
+                refresh_token: None,
+                expires_at: None,
+                scopes: vec!["rover:cli".to_string()],
+            },
+        )
+        .unwrap();
+        config.set_default_profile("status-work").unwrap();
+
+        let mut http_service = MockHttpService::new();
+        expect_poll_ready!(http_service, 1);
+        http_service
+            .expect_call()
+            .times(1)
+            .returning(|_| futures::future::ready(Ok(whoami_200())));
+
+        let sessions = Status::profile_sessions(
+            &config,
+            &OauthConfig::default(),
+            MockCloneService::new(http_service),
+        )
+        .await
+        .unwrap();
+
+        assert_that!(sessions).has_length(2);
+        assert_that!(sessions[0].profile).is_equal_to("status-ci".to_string());
+        assert_that!(sessions[0].state).is_equal_to(SessionState::ApiKey);
+        assert_that!(sessions[0].is_default).is_false();
+        assert_that!(sessions[1].profile).is_equal_to("status-work".to_string());
+        assert_that!(sessions[1].state).is_equal_to(SessionState::Valid);
+        assert_that!(sessions[1].is_default).is_true();
+        assert_that!(sessions[1].email).is_equal_to(Some("grace@apollographql.com".to_string()));
+        assert_that!(sessions[1].scopes).is_equal_to(vec!["rover:cli".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn session_state_skips_the_lookup_for_an_already_expired_session() {
+        let session = OAuthSession {
+            access_token: "access-token".to_string(),
+            refresh_token: None,
+            expires_at: Some(1),
+            scopes: Vec::new(),
+        };
+
+        // no expectations: any HTTP call fails the test.
+        let result = session_state(
+            &session,
+            &OauthConfig::default(),
+            MockCloneService::new(MockHttpService::new()),
+        )
+        .await;
+
+        assert_that!(result).is_equal_to((SessionState::Expired, None));
+    }
+}
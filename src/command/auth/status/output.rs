@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use rover_std::Style;
+use serde::Serialize;
+
+use crate::{command::CliOutput, utils::table};
+
+/// The state of one profile's stored credential, as reported by `rover auth status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum SessionState {
+    /// The OAuth server accepted the access token.
+    Valid,
+    /// The access token was rejected, or its expiry has passed.
+    Expired,
+    /// The identity lookup failed for some other reason (e.g. no network).
+    Unknown,
+    /// The profile holds a Personal API Key, which doesn't expire.
+    ApiKey,
+}
+
+impl SessionState {
+    const fn description(&self) -> &'static str {
+        match self {
+            SessionState::Valid => "valid",
+            SessionState::Expired => "expired",
+            SessionState::Unknown => "unknown",
+            SessionState::ApiKey => "API key",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct ProfileSession {
+    pub(super) profile: String,
+    pub(super) is_default: bool,
+    pub(super) state: SessionState,
+    pub(super) email: Option<String>,
+    pub(super) expires_at: Option<DateTime<Utc>>,
+    pub(super) scopes: Vec<String>,
+}
+
+#[derive(Debug)]
+pub(super) struct AuthStatusOutput {
+    pub(super) sessions: Vec<ProfileSession>,
+}
+
+impl CliOutput for AuthStatusOutput {
+    fn text(&self) -> String {
+        if self.sessions.is_empty() {
+            return "There are no profiles. Run `rover auth login` to create one.".to_string();
+        }
+
+        let mut table = table::get_table();
+        table.set_header(vec![
+            Style::WhoAmIKey.paint("Profile"),
+            Style::WhoAmIKey.paint("Status"),
+            Style::WhoAmIKey.paint("Email"),
+            Style::WhoAmIKey.paint("Expires"),
+            Style::WhoAmIKey.paint("Scopes"),
+        ]);
+        for session in &self.sessions {
+            let profile = if session.is_default {
+                format!("{} (default)", session.profile)
+            } else {
+                session.profile.clone()
+            };
+            table.add_row(vec![
+                profile,
+                session.state.description().to_string(),
+                session.email.clone().unwrap_or_default(),
+                session
+                    .expires_at
+                    .map(|expires_at| expires_at.to_rfc3339())
+                    .unwrap_or_default(),
+                session.scopes.join(" "),
+            ]);
+        }
+
+        table.to_string()
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Ok(serde_json::json!({ "profiles": self.sessions }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[fixture]
+    fn output() -> AuthStatusOutput {
+        AuthStatusOutput {
+            sessions: vec![
+                ProfileSession {
+                    profile: "work".to_string(),
+                    is_default: true,
+                    state: SessionState::Valid,
+                    email: Some("grace@apollographql.com".to_string()),
+                    expires_at: DateTime::from_timestamp(1_700_000_000, 0),
+                    scopes: vec!["rover:cli".to_string(), "openid".to_string()],
+                },
+                ProfileSession {
+                    profile: "ci".to_string(),
+                    is_default: false,
+                    state: SessionState::ApiKey,
+                    email: None,
+                    expires_at: None,
+                    scopes: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    #[rstest]
+    fn text_lists_every_profile(output: AuthStatusOutput) {
+        let text = temp_env::with_var("NO_COLOR", Some("1"), || output.text());
+
+        assert_that!(text).is_equal_to(
+            indoc! {"
+                ┌────────────────┬─────────┬─────────────────────────┬───────────────────────────┬──────────────────┐
+                │ Profile        ┆ Status  ┆ Email                   ┆ Expires                   ┆ Scopes           │
+                ╞════════════════╪═════════╪═════════════════════════╪═══════════════════════════╪══════════════════╡
+                │ work (default) ┆ valid   ┆ grace@apollographql.com ┆ 2023-11-14T22:13:20+00:00 ┆ rover:cli openid │
+                ├╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+                │ ci             ┆ API key ┆                         ┆                           ┆                  │
+                └────────────────┴─────────┴─────────────────────────┴───────────────────────────┴──────────────────┘"}
+            .to_string(),
+        );
+    }
+
+    #[rstest]
+    fn json_matches_expected_shape(output: AuthStatusOutput) {
+        assert_that!(output.json())
+            .is_ok()
+            .is_equal_to(serde_json::json!({
+                "profiles": [
+                    {
+                        "profile": "work",
+                        "is_default": true,
+                        "state": "valid",
+                        "email": "grace@apollographql.com",
+                        "expires_at": "2023-11-14T22:13:20Z",
+                        "scopes": ["rover:cli", "openid"],
+                    },
+                    {
+                        "profile": "ci",
+                        "is_default": false,
+                        "state": "api_key",
+                        "email": null,
+                        "expires_at": null,
+                        "scopes": [],
+                    },
+                ],
+            }));
+    }
+
+    #[test]
+    fn text_explains_how_to_log_in_when_there_are_no_profiles() {
+        let output = AuthStatusOutput {
+            sessions: Vec::new(),
+        };
+
+        assert_that!(output.text()).contains("rover auth login");
+    }
+}
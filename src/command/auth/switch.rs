@@ -0,0 +1,89 @@
+use clap::Parser;
+use houston::{Config, HoustonProblem, Profile};
+use serde::Serialize;
+
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Change which profile is used when `--profile` isn't given
+///
+/// Points the `default` profile at an existing profile, so commands run
+/// without `--profile` (or with `--profile default`) use its credential,
+/// without logging in again. Switch to `default` to undo this.
+///
+/// Only reading a credential follows the switch: logging in, saving an API
+/// key or deleting the `default` profile still acts on the profile named
+/// `default`.
+pub struct Switch {
+    /// The name of the profile to use by default
+    #[arg(value_name = "PROFILE")]
+    profile_name: String,
+}
+
+impl Switch {
+    pub fn run(&self, config: Config) -> RoverResult<RoverOutput> {
+        let profile_name = &self.profile_name;
+        if profile_name != "default" && !Profile::list(&config)?.contains(profile_name) {
+            return Err(
+                RoverError::from(HoustonProblem::ProfileNotFound(profile_name.clone()))
+                    .with_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                        "Run `rover auth login --profile {profile_name}` to create it."
+                    ))),
+            );
+        }
+
+        config.set_default_profile(profile_name)?;
+
+        Ok(RoverOutput::MessageResponse {
+            msg: format!("Switched the default profile to \"{profile_name}\"."),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use camino::Utf8Path;
+    use serial_test::serial;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn test_config() -> (Config, TempDir) {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_home_path = Utf8Path::from_path(tmp_home.path()).unwrap().to_owned();
+        (Config::new(Some(&tmp_home_path), None).unwrap(), tmp_home)
+    }
+
+    fn switch(profile_name: &str) -> Switch {
+        Switch {
+            profile_name: profile_name.to_string(),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn run_points_the_default_profile_at_an_existing_profile() {
+        let (config, _tmp_home) = test_config();
+        Profile::set_api_key("switch-target", &config, "some-key").unwrap();
+
+        let result = switch("switch-target").run(config.clone());
+
+        assert_that!(result).is_ok();
+        assert_that!(config.resolve_profile("default")).is_equal_to("switch-target".to_string());
+    }
+
+    #[test]
+    #[serial]
+    fn run_refuses_to_switch_to_a_profile_that_does_not_exist() {
+        let (config, _tmp_home) = test_config();
+        Profile::set_api_key("switch-target", &config, "some-key").unwrap();
+
+        let error = switch("missing")
+            .run(config.clone())
+            .expect_err("expected switching to a nonexistent profile to fail");
+
+        assert_that!(error.to_string()).contains("There is no profile named");
+        assert_that!(config.default_profile()).is_none();
+    }
+}
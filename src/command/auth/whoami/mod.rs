@@ -21,7 +21,7 @@ use crate::{
 };
 
 /// Bounds a single whoami HTTP attempt. Kept short and independent of
-/// `StudioClientConfig::retry_period` (the overall retry budget, default
+/// `StudioClientConfig::studio_retry` (the overall retry budget, default
 /// 30s) - reusing that same duration here would let one hung attempt consume
 /// the entire retry budget, leaving no room for an actual retry.
 const WHOAMI_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
@@ -91,8 +91,8 @@ impl WhoAmI {
         // this REST call didn't have either before, so a hung connection or a
         // flaky IdP could leave `rover auth whoami` stuck indefinitely.
         let http_service = ServiceBuilder::new()
-            .layer(RetryLayer::new(RetryPolicy::new(
-                client_config.retry_period(),
+            .layer(RetryLayer::new(RetryPolicy::from_config(
+                client_config.studio_retry().clone(),
             )))
             .layer(TimeoutLayer::new(WHOAMI_ATTEMPT_TIMEOUT))
             .service(raw_service);
@@ -155,7 +155,7 @@ impl Check {
     }
 }
 
-fn parse_graphql_files(files: Vec<Utf8PathBuf>) -> RoverResult<Vec<ParsedFile>> {
+pub(crate) fn parse_graphql_files(files: Vec<Utf8PathBuf>) -> RoverResult<Vec<ParsedFile>> {
     let results: Vec<Result<ParsedFile, ClientCheckFailure>> = files
         .into_iter()
         .map(|file| {
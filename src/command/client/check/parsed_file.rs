@@ -15,7 +15,7 @@ pub(super) enum ParsedFileError {
 }
 
 #[derive(Debug, Clone)]
-pub(super) struct ParsedFile {
+pub(crate) struct ParsedFile {
     pub(super) operations: Vec<OperationInput>,
     pub(super) extensions: Vec<ExtensionSnippet>,
     /// Fragment definitions keyed by name, for global deduplication across files.
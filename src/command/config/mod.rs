@@ -2,6 +2,7 @@ mod auth;
 mod clear;
 mod delete;
 mod list;
+mod registry;
 pub(crate) mod whoami;
 
 use clap::Parser;
@@ -29,6 +30,9 @@ pub enum Command {
     /// List all configuration profiles
     List(list::List),
 
+    /// Point a configuration profile at a local graph registry
+    Registry(registry::Registry),
+
     /// View the identity of a user/api key
     Whoami(whoami::WhoAmI),
 }
@@ -40,6 +44,7 @@ impl Config {
                 command.run(client_config.config, &rover_print::print::stderr::default())
             }
             Command::List(command) => command.run(client_config.config),
+            Command::Registry(command) => command.run(client_config.config),
             Command::Delete(command) => command.run(client_config.config),
             Command::Clear(command) => command.run(client_config.config),
             Command::Whoami(command) => {
@@ -0,0 +1,93 @@
+use std::path::Path;
+
+use anyhow::anyhow;
+use clap::Parser;
+use houston as config;
+use serde::Serialize;
+use url::Url;
+
+use crate::{RoverOutput, RoverResult, options::ProfileOpt};
+
+#[derive(Debug, Serialize, Parser)]
+/// Point a configuration profile at a local graph registry
+///
+/// Commands run with the profile keep graphs, variants and subgraphs in a
+/// local directory instead of Apollo GraphOS. `subgraph publish` and
+/// `subgraph delete` recompose the variant with the supergraph plugin, and
+/// `subgraph fetch`, `subgraph list` and `supergraph fetch` read it back.
+/// Setting `APOLLO_REGISTRY_URL` to a `file://` URL does the same for a
+/// single command.
+#[command(after_help = "EXAMPLES:\n    \
+    rover config registry ./registry\n    \
+    rover config registry --profile demo file:///tmp/registry\n    \
+    rover config registry --studio")]
+pub struct Registry {
+    /// The directory, or `file://` URL, to keep the registry in. It's created
+    /// on the first publish.
+    #[arg(required_unless_present = "studio")]
+    #[serde(skip_serializing)]
+    location: Option<String>,
+
+    /// Point the profile back at Apollo GraphOS.
+    #[arg(long, conflicts_with = "location")]
+    studio: bool,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+impl Registry {
+    pub fn run(&self, config: config::Config) -> RoverResult<RoverOutput> {
+        let profile_name = &self.profile.profile_name;
+        match &self.location {
+            Some(location) => {
+                let registry_url = registry_url(location)?;
+                config::Profile::set_registry_url(profile_name, &config, Some(&registry_url))?;
+                eprintln!(
+                    "Profile \"{profile_name}\" now uses the local registry at {registry_url}"
+                );
+            }
+            None => {
+                config::Profile::set_registry_url(profile_name, &config, None)?;
+                eprintln!("Profile \"{profile_name}\" now uses Apollo GraphOS");
+            }
+        }
+        Ok(RoverOutput::EmptySuccess)
+    }
+}
+
+/// Turns a `file://` URL or a (possibly relative) directory into an absolute `file://` URL
+fn registry_url(location: &str) -> RoverResult<String> {
+    if let Ok(url) = Url::parse(location)
+        && url.scheme() == "file"
+    {
+        return Ok(url.to_string());
+    }
+    let path = std::path::absolute(Path::new(location))?;
+    Url::from_directory_path(&path)
+        .map(|url| url.to_string())
+        .map_err(|_| anyhow!("{} is not a valid directory path", path.display()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::registry_url;
+
+    #[test]
+    fn file_urls_are_kept_as_they_are() {
+        assert_that!(registry_url("file:///tmp/registry").unwrap())
+            .is_equal_to("file:///tmp/registry".to_string());
+    }
+
+    #[test]
+    fn relative_directories_become_absolute_file_urls() {
+        let url = registry_url("registry").unwrap();
+        let expected =
+            url::Url::from_directory_path(std::env::current_dir().unwrap().join("registry"))
+                .unwrap()
+                .to_string();
+        assert_that!(url).is_equal_to(expected);
+    }
+}
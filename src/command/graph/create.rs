@@ -0,0 +1,144 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use clap::Parser;
+use rover_client::operations::init::{
+    create_graph::{self, CreateGraphInput},
+    memberships,
+};
+use rover_std::Style;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult,
+    command::{
+        CliOutput,
+        init::graph_id::{
+            GraphId, errors::conversions::validation_error_to_rover_error,
+            generation::generate_graph_id, utils::random::DefaultRandomStringGenerator,
+        },
+    },
+    options::ProfileOpt,
+    utils::client::StudioClientConfig,
+};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover graph create --name \"Products\"\n    \
+    rover graph create products --name \"Products\" --organization my-org")]
+pub struct Create {
+    /// The ID for the new graph. Without it, an ID is generated from --name.
+    #[arg(value_name = "GRAPH_ID")]
+    graph_id: Option<String>,
+
+    /// The graph's display name in Studio
+    #[arg(long = "name")]
+    name: String,
+
+    /// The ID of the organization to create the graph in. Only needed when
+    /// you're a member of more than one organization.
+    #[arg(long = "organization", value_name = "ORGANIZATION_ID")]
+    organization_id: Option<String>,
+
+    /// Hide the graph from organization members who haven't been invited to it
+    #[arg(long)]
+    hidden: bool,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+impl Create {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let graph_id = match &self.graph_id {
+            Some(graph_id) => {
+                GraphId::from_str(graph_id).map_err(validation_error_to_rover_error)?
+            }
+            None => generate_graph_id(&self.name, &mut DefaultRandomStringGenerator, None),
+        };
+        let client = client_config.get_authenticated_client(&self.profile)?;
+
+        let organization_id = match &self.organization_id {
+            Some(organization_id) => organization_id.clone(),
+            None => {
+                let memberships = memberships::run(&client).await?.memberships;
+                match memberships.as_slice() {
+                    [organization] => organization.id.clone(),
+                    [] => {
+                        return Err(RoverError::new(anyhow!(
+                            "These credentials aren't a member of any organization."
+                        ))
+                        .with_suggestion(RoverErrorSuggestion::Adhoc(
+                            "Pass --organization, or use a personal API key from a member of the organization."
+                                .to_string(),
+                        )));
+                    }
+                    organizations => {
+                        return Err(RoverError::new(anyhow!(
+                            "You're a member of more than one organization."
+                        ))
+                        .with_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                            "Pass --organization with one of: {}",
+                            organizations
+                                .iter()
+                                .map(|organization| organization.id.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ))));
+                    }
+                }
+            }
+        };
+
+        eprintln!(
+            "Creating graph {} in organization {} using credentials from the {} profile.",
+            Style::Link.paint(graph_id.to_string()),
+            Style::Link.paint(&organization_id),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+        let response = create_graph::run(
+            CreateGraphInput {
+                hidden_from_uninvited_non_admin: self.hidden,
+                create_graph_id: graph_id.into_string(),
+                title: self.name.clone(),
+                organization_id: organization_id.clone(),
+            },
+            &client,
+        )
+        .await?;
+
+        Ok(RoverOutput::CliOutput(Box::new(GraphCreateOutput {
+            graph_id: response.id,
+            name: self.name.clone(),
+            organization_id,
+        })))
+    }
+}
+
+#[derive(Debug)]
+struct GraphCreateOutput {
+    graph_id: String,
+    name: String,
+    organization_id: String,
+}
+
+impl CliOutput for GraphCreateOutput {
+    fn text(&self) -> String {
+        format!(
+            "Created graph {} ({}) in organization {}.\nPublish a subgraph to create its first variant, e.g. `rover subgraph publish {}@current --name <SUBGRAPH> --schema <SCHEMA>`.",
+            Style::Link.paint(&self.graph_id),
+            self.name,
+            self.organization_id,
+            self.graph_id
+        )
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Ok(json!({
+            "graph_id": self.graph_id,
+            "name": self.name,
+            "organization_id": self.organization_id,
+        }))
+    }
+}
@@ -1,5 +1,9 @@
 use clap::Parser;
-use rover_client::operations::graph::fetch::{self, GraphFetchInput};
+use rover_client::operations::graph::{
+    fetch::{self, GraphFetchInput},
+    fetch_at::{self, GraphFetchAtInput},
+    history::HistoryPoint,
+};
 use rover_std::Style;
 use serde::Serialize;
 
@@ -16,25 +20,51 @@ pub struct Fetch {
 
     #[clap(flatten)]
     profile: ProfileOpt,
+
+    /// Fetch the schema published in a past launch instead of the latest one.
+    /// Takes a launch ID from `rover graph history`, or an RFC 3339 timestamp to
+    /// fetch the schema that was live then.
+    #[arg(long, value_name = "LAUNCH_ID|TIMESTAMP")]
+    #[serde(skip_serializing)]
+    at: Option<HistoryPoint>,
 }
 
 impl Fetch {
     pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
         let graph_ref = self.graph.graph_ref.to_string();
+        let at = self
+            .at
+            .as_ref()
+            .map(|at| format!(" as of {at}"))
+            .unwrap_or_default();
         eprintln!(
-            "Fetching SDL from {} using credentials from the {} profile.",
+            "Fetching SDL from {}{at} using credentials from the {} profile.",
             Style::Link.paint(graph_ref),
             Style::Command.paint(&self.profile.profile_name)
         );
 
-        let fetch_response = fetch::run(
-            GraphFetchInput {
-                graph_ref: self.graph.graph_ref.clone(),
-            },
-            &client,
-        )
-        .await?;
+        let fetch_response = match &self.at {
+            Some(at) => {
+                fetch_at::run(
+                    GraphFetchAtInput {
+                        graph_ref: self.graph.graph_ref.clone(),
+                        at: at.clone(),
+                    },
+                    &client,
+                )
+                .await?
+            }
+            None => {
+                fetch::run(
+                    GraphFetchInput {
+                        graph_ref: self.graph.graph_ref.clone(),
+                    },
+                    &client,
+                )
+                .await?
+            }
+        };
 
         Ok(RoverOutput::FetchResponse(fetch_response))
     }
@@ -0,0 +1,180 @@
+use clap::Parser;
+use rover_client::operations::graph::history::{
+    self, GraphHistoryInput, GraphHistoryResponse, GraphPublish,
+};
+use rover_std::Style;
+use serde::Serialize;
+
+use crate::{
+    RoverOutput, RoverResult,
+    command::CliOutput,
+    options::{GraphRefOpt, ProfileOpt},
+    utils::{client::StudioClientConfig, table},
+};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover graph history my-graph@current\n    \
+    rover graph fetch my-graph@current --at <LAUNCH_ID>")]
+pub struct History {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The most publishes to list, newest first
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(i64).range(1..=100))]
+    limit: i64,
+}
+
+impl History {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        eprintln!(
+            "Fetching publish history for {} using credentials from the {} profile.",
+            Style::Link.paint(self.graph.graph_ref.to_string()),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let response = history::run(
+            GraphHistoryInput {
+                graph_ref: self.graph.graph_ref.clone(),
+                limit: self.limit,
+            },
+            &client,
+        )
+        .await?;
+        Ok(RoverOutput::CliOutput(Box::new(GraphHistoryOutput(
+            response,
+        ))))
+    }
+}
+
+#[derive(Debug)]
+struct GraphHistoryOutput(GraphHistoryResponse);
+
+impl CliOutput for GraphHistoryOutput {
+    fn text(&self) -> String {
+        if self.0.publishes.is_empty() {
+            return format!("No publishes found for {}.", self.0.graph_ref);
+        }
+        let mut table = table::get_table();
+        table.add_row(vec![
+            &Style::Success.paint("Launch ID"),
+            &Style::Success.paint("Created At"),
+            &Style::Success.paint("Status"),
+            &Style::Success.paint("Changes"),
+            &Style::Success.paint("Subgraphs Changed"),
+        ]);
+        for publish in &self.0.publishes {
+            table.add_row(vec![
+                publish.launch.launch_id.clone(),
+                publish.launch.created_at.clone(),
+                publish.launch.status.clone(),
+                changes_text(publish),
+                subgraphs_text(publish),
+            ]);
+        }
+        table.to_string()
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(&self.0)
+    }
+}
+
+fn changes_text(publish: &GraphPublish) -> String {
+    match (&publish.changes, &publish.published_at) {
+        (Some(changes), _) => changes.to_string(),
+        (None, Some(_)) => "N/A".to_string(),
+        (None, None) => "not published".to_string(),
+    }
+}
+
+fn subgraphs_text(publish: &GraphPublish) -> String {
+    if publish.launch.subgraph_changes.is_empty() {
+        return "N/A".to_string();
+    }
+    publish
+        .launch
+        .subgraph_changes
+        .iter()
+        .map(|change| match change.change.as_str() {
+            "ADDITION" => format!("+{}", change.name),
+            "DELETION" => format!("-{}", change.name),
+            _ => change.name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rover_client::operations::graph::history::{ChangeCounts, LaunchSummary, SubgraphChange};
+    use rover_studio::types::GraphRef;
+    use serde_json::json;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn publish(changes: Option<ChangeCounts>) -> GraphPublish {
+        GraphPublish {
+            launch: LaunchSummary {
+                launch_id: "l-2".to_string(),
+                created_at: "2026-01-02T00:00:00Z".to_string(),
+                completed_at: None,
+                status: "LAUNCH_COMPLETED".to_string(),
+                subgraph_changes: vec![
+                    SubgraphChange {
+                        name: "products".to_string(),
+                        change: "MODIFICATION".to_string(),
+                    },
+                    SubgraphChange {
+                        name: "reviews".to_string(),
+                        change: "ADDITION".to_string(),
+                    },
+                ],
+                subgraphs: Vec::new(),
+            },
+            published_at: changes.map(|_| "2026-01-02T00:00:05Z".to_string()),
+            schema_hash: None,
+            changes,
+        }
+    }
+
+    fn output(publishes: Vec<GraphPublish>) -> GraphHistoryOutput {
+        GraphHistoryOutput(GraphHistoryResponse {
+            graph_ref: GraphRef::from_str("mygraph@current").unwrap(),
+            publishes,
+        })
+    }
+
+    #[test]
+    fn text_lists_change_counts_and_changed_subgraphs() {
+        let text = output(vec![publish(Some(ChangeCounts {
+            additions: 2,
+            removals: 0,
+            edits: 1,
+            deprecations: 0,
+        }))])
+        .text();
+        assert_that!(text).contains("l-2");
+        assert_that!(text).contains("+2 -0 ~1");
+        assert_that!(text).contains("products, +reviews");
+    }
+
+    #[test]
+    fn unpublished_launches_say_so() {
+        assert_that!(output(vec![publish(None)]).text()).contains("not published");
+    }
+
+    #[test]
+    fn json_flattens_the_launch_into_each_publish() {
+        let json = output(vec![publish(None)]).json().unwrap();
+        assert_that!(json["publishes"][0]["launch_id"]).is_equal_to(json!("l-2"));
+        assert_that!(json["publishes"][0]["changes"]).is_equal_to(json!(null));
+    }
+}
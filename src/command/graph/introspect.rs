@@ -1,10 +1,11 @@
-use std::{collections::HashMap, time::Duration};
+use std::collections::HashMap;
 
 use clap::Parser;
 use reqwest::Client;
 use rover_client::operations::graph::introspect::{
     self, GraphIntrospectInput, sdl_to_introspection_json,
 };
+use rover_http::retry::RetryConfig;
 use serde::Serialize;
 
 use crate::{
@@ -36,13 +37,12 @@ impl Introspect {
         &self,
         client: Client,
         output_opts: &OutputOpts,
-        retry_period: Duration,
+        retry: &RetryConfig,
     ) -> RoverResult<RoverOutput> {
         if self.opts.watch {
-            self.exec_and_watch(&client, output_opts, retry_period)
-                .await
+            self.exec_and_watch(&client, output_opts, retry).await
         } else {
-            let sdl = self.exec(&client, true, retry_period).await?;
+            let sdl = self.exec(&client, true, retry).await?;
             Self::sdl_to_output(sdl, output_opts.format_kind)
         }
     }
@@ -51,7 +51,7 @@ impl Introspect {
         &self,
         client: &Client,
         should_retry: bool,
-        retry_period: Duration,
+        retry: &RetryConfig,
     ) -> RoverResult<String> {
         // add the flag headers to a hashmap to pass along to rover-client
         let mut headers = HashMap::new();
@@ -66,7 +66,7 @@ impl Introspect {
                 headers,
                 endpoint: self.opts.endpoint.clone(),
                 should_retry,
-                retry_period,
+                retry: retry.clone(),
                 use_legacy_introspection_query: self.legacy_introspection_query,
             },
             client,
@@ -89,11 +89,11 @@ impl Introspect {
         client: &Client,
         output_opts: &OutputOpts,
 
-        retry_period: Duration,
+        retry: &RetryConfig,
     ) -> ! {
         self.opts
             .exec_and_watch(
-                || self.exec(client, false, retry_period),
+                || self.exec(client, false, retry),
                 |sdl| Self::sdl_to_output(sdl, output_opts.format_kind),
                 output_opts,
             )
@@ -0,0 +1,159 @@
+use anyhow::anyhow;
+use clap::Parser;
+use rover_client::operations::init::{
+    list_graphs::{self, ListGraphsInput, OrganizationWithGraphs},
+    memberships,
+};
+use rover_std::Style;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult,
+    command::CliOutput,
+    options::ProfileOpt,
+    utils::{client::StudioClientConfig, table},
+};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover graph list\n    \
+    rover graph list --organization my-org")]
+pub struct List {
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The ID of the organization to list graphs from. Without it, graphs
+    /// from every organization you're a member of are listed.
+    #[arg(long = "organization", value_name = "ORGANIZATION_ID")]
+    organization_id: Option<String>,
+}
+
+impl List {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        eprintln!(
+            "Listing graphs using credentials from the {} profile.",
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let organization_ids = match &self.organization_id {
+            Some(organization_id) => vec![organization_id.clone()],
+            None => memberships::run(&client)
+                .await?
+                .memberships
+                .into_iter()
+                .map(|organization| organization.id)
+                .collect(),
+        };
+        if organization_ids.is_empty() {
+            return Err(RoverError::new(anyhow!(
+                "These credentials aren't a member of any organization."
+            ))
+            .with_suggestion(RoverErrorSuggestion::Adhoc(
+                "Pass --organization, or use a personal API key from a member of the organization."
+                    .to_string(),
+            )));
+        }
+
+        let mut organizations = Vec::with_capacity(organization_ids.len());
+        for organization_id in organization_ids {
+            let response = list_graphs::run(ListGraphsInput { organization_id }, &client).await?;
+            organizations.push(response.organization);
+        }
+        Ok(RoverOutput::CliOutput(Box::new(GraphListOutput(
+            organizations,
+        ))))
+    }
+}
+
+#[derive(Debug)]
+struct GraphListOutput(Vec<OrganizationWithGraphs>);
+
+impl CliOutput for GraphListOutput {
+    fn text(&self) -> String {
+        if self
+            .0
+            .iter()
+            .all(|organization| organization.graphs.is_empty())
+        {
+            return "No graphs found.".to_string();
+        }
+        let mut table = table::get_table();
+        table.add_row(vec![
+            &Style::Success.paint("Organization"),
+            &Style::Success.paint("Graph ID"),
+            &Style::Success.paint("Name"),
+            &Style::Success.paint("Variants"),
+        ]);
+        for organization in &self.0 {
+            for graph in &organization.graphs {
+                table.add_row(vec![
+                    organization.name.clone(),
+                    graph.id.clone(),
+                    graph.name.clone(),
+                    graph
+                        .variants
+                        .iter()
+                        .map(|variant| variant.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ]);
+            }
+        }
+        table.to_string()
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Ok(json!({ "organizations": self.0 }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rover_client::operations::init::list_graphs::{GraphInfo, VariantInfo};
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn organization(graphs: Vec<GraphInfo>) -> OrganizationWithGraphs {
+        OrganizationWithGraphs {
+            id: "my-org".to_string(),
+            name: "My Org".to_string(),
+            graphs,
+        }
+    }
+
+    #[test]
+    fn text_lists_each_graph_with_its_variants() {
+        let output = GraphListOutput(vec![organization(vec![GraphInfo {
+            id: "products-abc123".to_string(),
+            name: "Products".to_string(),
+            variants: vec![
+                VariantInfo {
+                    name: "current".to_string(),
+                },
+                VariantInfo {
+                    name: "staging".to_string(),
+                },
+            ],
+        }])]);
+        let text = output.text();
+        assert_that!(text).contains("products-abc123");
+        assert_that!(text).contains("current, staging");
+    }
+
+    #[test]
+    fn text_says_when_there_are_no_graphs() {
+        let output = GraphListOutput(vec![organization(Vec::new())]);
+        assert_that!(output.text()).is_equal_to("No graphs found.".to_string());
+    }
+
+    #[test]
+    fn json_groups_graphs_by_organization() {
+        let output = GraphListOutput(vec![organization(Vec::new())]);
+        assert_that!(output.json().unwrap()).is_equal_to(json!({
+            "organizations": [{ "id": "my-org", "name": "My Org", "graphs": [] }]
+        }));
+    }
+}
@@ -1,8 +1,11 @@
 mod check;
+mod create;
 mod delete;
 mod fetch;
+mod history;
 mod introspect;
 mod lint;
+mod list;
 mod publish;
 
 use clap::Parser;
@@ -23,15 +26,24 @@ pub enum Command {
     /// against a graph schema in the Apollo graph registry
     Check(check::Check),
 
+    /// Create a graph in an organization
+    Create(create::Create),
+
     /// Delete a graph schema from the Apollo graph registry
     Delete(delete::Delete),
 
     /// Fetch a graph schema from the Apollo graph registry
     Fetch(fetch::Fetch),
 
+    /// List a graph variant's past publishes
+    History(history::History),
+
     /// Lint a graph schema
     Lint(lint::Lint),
 
+    /// List the graphs in your organizations
+    List(list::List),
+
     /// Publish an updated graph schema to the Apollo graph registry
     Publish(publish::Publish),
 
@@ -53,9 +65,12 @@ impl Graph {
                     .run(client_config, git_context, checks_timeout_seconds)
                     .await
             }
+            Command::Create(command) => command.run(client_config).await,
             Command::Delete(command) => command.run(client_config).await,
             Command::Fetch(command) => command.run(client_config).await,
+            Command::History(command) => command.run(client_config).await,
             Command::Lint(command) => command.run(client_config).await,
+            Command::List(command) => command.run(client_config).await,
             Command::Publish(command) => {
                 command
                     .run(client_config, git_context, checks_timeout_seconds)
@@ -66,7 +81,7 @@ impl Graph {
                     .run(
                         client_config.get_reqwest_client()?,
                         output_opts,
-                        client_config.client_timeout().get_duration(),
+                        client_config.introspection_retry(),
                     )
                     .await
             }
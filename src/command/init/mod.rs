@@ -2,8 +2,7 @@
 mod authentication;
 #[cfg(feature = "composition-js")]
 mod config;
-#[cfg(feature = "composition-js")]
-mod graph_id;
+pub(crate) mod graph_id;
 #[cfg(feature = "composition-js")]
 mod helpers;
 #[cfg(feature = "composition-js")]
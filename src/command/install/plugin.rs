@@ -390,7 +390,11 @@ impl PluginInstaller {
     async fn install_latest_major(&self, plugin: &Plugin) -> RoverResult<Option<Utf8PathBuf>> {
         let latest_version = self
             .installer
-            .get_plugin_version(&plugin.get_tarball_url()?, true)
+            .get_plugin_version(
+                &plugin.get_tarball_url()?,
+                self.client_config.client_builder().network(),
+                true,
+            )
             .await?;
 
         if let Ok(Some(exe)) = self.find_existing_exact(plugin, &latest_version)
@@ -428,6 +432,7 @@ impl PluginInstaller {
         self.do_install(plugin, false).await
     }
 
+    #[tracing::instrument(name = "plugin install", skip_all, fields(plugin = %plugin.get_name()))]
     async fn do_install(
         &self,
         plugin: &Plugin,
@@ -446,6 +451,7 @@ impl PluginInstaller {
             // Match the per-attempt timeout to the configured download timeout so it
             // isn't capped by FileDownloadService's shorter default.
             .timeout_duration(*self.client_config.download_timeout())
+            .retry_config(self.client_config.download_retry().clone())
             .build();
         Ok(self
             .installer
@@ -453,6 +459,7 @@ impl PluginInstaller {
                 &plugin_name,
                 &plugin_tarball_url,
                 file_download_service,
+                self.client_config.client_builder().network(),
                 is_latest,
             )
             .await?)
@@ -0,0 +1,29 @@
+use clap::Parser;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult, utils::client::StudioClientConfig};
+
+mod output;
+mod status;
+
+pub(crate) use status::wait_for_launch;
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Launch {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Show where a launch is at, optionally waiting for it to finish
+    Status(status::Status),
+}
+
+impl Launch {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Status(command) => command.run(client_config).await,
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt::{self, Write};
+
+use rover_client::operations::launch::status::{LaunchState, LaunchStatusResponse};
+use rover_std::Style;
+
+use crate::{command::CliOutput, utils::table};
+
+/// The parts of a launch's status worth telling someone about while they wait
+#[derive(Debug, Eq, PartialEq)]
+pub(super) struct LaunchProgress {
+    state: LaunchState,
+    step: Option<String>,
+    downstream: Vec<(String, LaunchState)>,
+}
+
+impl From<&LaunchStatusResponse> for LaunchProgress {
+    fn from(status: &LaunchStatusResponse) -> Self {
+        Self {
+            state: status.state,
+            step: status.step.clone(),
+            downstream: status
+                .downstream_launches
+                .iter()
+                .map(|launch| (launch.variant.clone(), launch.state))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for LaunchProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.step {
+            Some(step) if !self.state.is_finished() => {
+                writeln!(f, "Launch {} ({step})", self.state)?
+            }
+            _ => writeln!(f, "Launch {}", self.state)?,
+        }
+        for (variant, state) in &self.downstream {
+            writeln!(f, "  contract variant {variant}: {state}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct LaunchStatusOutput(pub LaunchStatusResponse);
+
+impl CliOutput for LaunchStatusOutput {
+    fn text(&self) -> String {
+        let status = &self.0;
+        let state = match status.state {
+            LaunchState::Failed => Style::Failure.paint(status.state.to_string()),
+            LaunchState::Completed => Style::Success.paint(status.state.to_string()),
+            _ => status.state.to_string(),
+        };
+        let mut text = format!(
+            "Launch {} of {}: {state}\n",
+            status.launch_id, status.graph_ref
+        );
+        if let Some(step) = &status.step {
+            let _ = writeln!(text, "Step: {step}");
+        }
+        if let Some(completed_at) = &status.completed_at {
+            let _ = writeln!(text, "Completed at: {completed_at}");
+        }
+        if let Some(superseded_by) = &status.superseded_by {
+            let _ = writeln!(text, "Superseded by: {superseded_by}");
+        }
+        if !status.build_errors.is_empty() {
+            let _ = writeln!(text, "\nBuild errors:");
+            for error in &status.build_errors {
+                let _ = writeln!(text, "  - {error}");
+            }
+        }
+        if !status.downstream_launches.is_empty() {
+            let mut table = table::get_table();
+            table.add_row(vec![
+                &Style::Success.paint("Contract Variant"),
+                &Style::Success.paint("Launch"),
+                &Style::Success.paint("Status"),
+            ]);
+            for launch in &status.downstream_launches {
+                table.add_row(vec![
+                    launch.variant.clone(),
+                    launch.launch_id.clone(),
+                    launch.state.to_string(),
+                ]);
+            }
+            let _ = write!(text, "\n{table}");
+        }
+        text
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(&self.0)
+    }
+
+    fn exit_code(&self) -> i32 {
+        if self.0.has_failed() { 1 } else { 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rover_client::operations::launch::status::DownstreamLaunch;
+    use rover_studio::types::GraphRef;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn status(state: LaunchState) -> LaunchStatusResponse {
+        LaunchStatusResponse {
+            graph_ref: GraphRef::new("mygraph", Some("current")).unwrap(),
+            launch_id: "l-1".to_string(),
+            state,
+            step: Some("BUILD".to_string()),
+            completed_at: None,
+            superseded_by: None,
+            build_errors: Vec::new(),
+            downstream_launches: vec![DownstreamLaunch {
+                variant: "public".to_string(),
+                launch_id: "l-c".to_string(),
+                state: LaunchState::InProgress,
+            }],
+        }
+    }
+
+    #[test]
+    fn progress_shows_the_step_and_contract_variants() {
+        let progress = LaunchProgress::from(&status(LaunchState::InProgress));
+        assert_that!(progress.to_string()).is_equal_to(
+            "Launch in progress (BUILD)\n  contract variant public: in progress\n".to_string(),
+        );
+    }
+
+    #[test]
+    fn progress_changes_with_contract_variants() {
+        let before = status(LaunchState::Completed);
+        let mut after = before.clone();
+        after.downstream_launches[0].state = LaunchState::Completed;
+        assert_that!(LaunchProgress::from(&before)).is_not_equal_to(LaunchProgress::from(&after));
+    }
+
+    #[test]
+    fn failed_launches_exit_nonzero() {
+        let mut failed = status(LaunchState::Failed);
+        failed.build_errors = vec!["Field Query.a has conflicting types".to_string()];
+        let output = LaunchStatusOutput(failed);
+        assert_that!(output.exit_code()).is_equal_to(1);
+        assert_that!(output.text()).contains("Field Query.a has conflicting types");
+    }
+
+    #[test]
+    fn failed_contract_variant_launches_exit_nonzero() {
+        let mut launch = status(LaunchState::Completed);
+        launch.downstream_launches[0].state = LaunchState::Failed;
+        assert_that!(LaunchStatusOutput(launch).exit_code()).is_equal_to(1);
+    }
+
+    #[test]
+    fn completed_launches_exit_zero() {
+        let mut launch = status(LaunchState::Completed);
+        launch.downstream_launches[0].state = LaunchState::Completed;
+        assert_that!(LaunchStatusOutput(launch).exit_code()).is_equal_to(0);
+    }
+}
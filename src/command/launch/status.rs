@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use clap::Parser;
+use rover_client::{
+    blocking::StudioClient,
+    operations::launch::status::{self, LaunchStatusInput, LaunchStatusResponse},
+};
+use rover_studio::types::GraphRef;
+use serde::Serialize;
+
+use super::output::{LaunchProgress, LaunchStatusOutput};
+use crate::{
+    RoverOutput, RoverResult,
+    options::{GraphRefOpt, ProfileOpt},
+    utils::client::StudioClientConfig,
+};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover launch status my-graph@current <LAUNCH_ID>\n    \
+    rover launch status my-graph@current <LAUNCH_ID> --wait --timeout 300")]
+pub struct Status {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    /// The ID of the launch, as shown in the launch URL that `rover subgraph publish` prints
+    #[arg(value_name = "LAUNCH_ID")]
+    launch_id: String,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// Keep polling until the launch, and any contract variant launches it
+    /// started, have finished
+    #[arg(long)]
+    wait: bool,
+
+    /// How many seconds to wait with `--wait` before giving up
+    #[arg(long, value_name = "SECONDS", default_value_t = 600, requires = "wait")]
+    timeout: u64,
+}
+
+impl Status {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.graph_ref.clone();
+
+        let response = if self.wait {
+            wait_for_launch(
+                &client,
+                &graph_ref,
+                &self.launch_id,
+                Duration::from_secs(self.timeout),
+            )
+            .await?
+        } else {
+            status::run(
+                LaunchStatusInput {
+                    graph_ref,
+                    launch_id: self.launch_id.clone(),
+                },
+                &client,
+            )
+            .await?
+        };
+
+        Ok(RoverOutput::CliOutput(Box::new(LaunchStatusOutput(
+            response,
+        ))))
+    }
+}
+
+/// Polls a launch until it finishes, printing each change in its progress to stderr
+pub(crate) async fn wait_for_launch(
+    client: &StudioClient,
+    graph_ref: &GraphRef,
+    launch_id: &str,
+    timeout: Duration,
+) -> RoverResult<LaunchStatusResponse> {
+    eprintln!("Waiting for launch {launch_id} of {graph_ref} to finish...");
+    let mut last_progress: Option<LaunchProgress> = None;
+    let response = status::wait(
+        LaunchStatusInput {
+            graph_ref: graph_ref.clone(),
+            launch_id: launch_id.to_string(),
+        },
+        timeout,
+        |status| {
+            let progress = LaunchProgress::from(status);
+            if last_progress.as_ref() != Some(&progress) {
+                eprint!("{progress}");
+                last_progress = Some(progress);
+            }
+        },
+        client,
+    )
+    .await?;
+    Ok(response)
+}
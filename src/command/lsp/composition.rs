@@ -0,0 +1,421 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use apollo_federation_types::{composition::Issue, config::SchemaSource};
+use apollo_language_server::ApolloLanguageServer;
+use camino::Utf8PathBuf;
+use futures::StreamExt;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tower_lsp::lsp_types::{Diagnostic, Range};
+use tracing::{debug, info};
+use url::Url;
+
+use super::{
+    LspOpts, create_composition_runner,
+    errors::{StartCompositionError, StartCompositionError::SupergraphYamlUrlConversionFailed},
+    operations::OperationDocuments,
+};
+use crate::{
+    composition::{
+        CompositionError, CompositionSubgraphAdded, CompositionSubgraphRemoved, CompositionSuccess,
+        events::CompositionEvent,
+        supergraph::{
+            config::{error::ResolveSubgraphError, resolver::ResolveSupergraphConfigError},
+            install::InstallSupergraphError,
+        },
+    },
+    utils::client::StudioClientConfig,
+};
+
+/// How many updates a slow language server may fall behind before it starts missing them.
+const UPDATE_CAPACITY: usize = 100;
+
+/// What a language server needs to be told about a composition event. Unlike the events
+/// themselves, these can be handed to every editor attached to the same supergraph.
+#[derive(Clone, Debug)]
+pub(super) enum LanguageServerUpdate {
+    CompositionStarted,
+    /// `supergraph_sdl` is only present when composition succeeded
+    CompositionFinished {
+        supergraph_sdl: Option<String>,
+        issues: Vec<Issue>,
+    },
+    /// Diagnostics for the supergraph config itself, such as subgraphs that couldn't be resolved
+    SupergraphConfigDiagnostics(Vec<Diagnostic>),
+    SubgraphAdded {
+        name: String,
+        schema_source: SchemaSource,
+    },
+    SubgraphRemoved {
+        name: String,
+    },
+}
+
+/// The composition runners started by this process, one per supergraph config, so that every
+/// connection working on the same supergraph shares its supergraph binary and introspection
+/// polling.
+pub(super) struct Compositions {
+    client_config: StudioClientConfig,
+    lsp_opts: LspOpts,
+    started: tokio::sync::Mutex<HashMap<Utf8PathBuf, Arc<SharedComposition>>>,
+}
+
+impl Compositions {
+    pub(super) fn new(client_config: StudioClientConfig, lsp_opts: LspOpts) -> Self {
+        Self {
+            client_config,
+            lsp_opts,
+            started: Default::default(),
+        }
+    }
+
+    /// Returns the running composition for `supergraph_yaml_path`, starting it on first use.
+    pub(super) async fn get_or_start(
+        &self,
+        supergraph_yaml_path: &Utf8PathBuf,
+    ) -> Result<Arc<SharedComposition>, StartCompositionError> {
+        let mut started = self.started.lock().await;
+        if let Some(composition) = started.get(supergraph_yaml_path) {
+            return Ok(composition.clone());
+        }
+        let composition = SharedComposition::start(
+            supergraph_yaml_path.clone(),
+            self.client_config.clone(),
+            self.lsp_opts.clone(),
+        )
+        .await?;
+        started.insert(supergraph_yaml_path.clone(), composition.clone());
+        Ok(composition)
+    }
+}
+
+/// A composition runner whose events are fanned out to any number of language servers.
+pub(super) struct SharedComposition {
+    supergraph_yaml_path: Utf8PathBuf,
+    supergraph_yaml_url: Url,
+    subgraph_schema_files: HashSet<Utf8PathBuf>,
+    state: Mutex<CompositionState>,
+    updates: broadcast::Sender<LanguageServerUpdate>,
+}
+
+/// Everything a language server attaching late needs to catch up with the others.
+#[derive(Default)]
+struct CompositionState {
+    subgraphs: BTreeMap<String, SchemaSource>,
+    last_finished: Option<LanguageServerUpdate>,
+    supergraph_config_diagnostics: Vec<Diagnostic>,
+    // Resolution errors are kept separately, specific to the subgraphs, so they can be retained
+    // even after a potentially successful composition. They can only be cleared via a
+    // SubgraphAdded event.
+    resolution_errors: HashMap<String, Diagnostic>,
+}
+
+impl SharedComposition {
+    async fn start(
+        supergraph_yaml_path: Utf8PathBuf,
+        client_config: StudioClientConfig,
+        lsp_opts: LspOpts,
+    ) -> Result<Arc<Self>, StartCompositionError> {
+        let supergraph_yaml_url = Url::from_file_path(supergraph_yaml_path.clone())
+            .map_err(|_| SupergraphYamlUrlConversionFailed(supergraph_yaml_path.clone()))?;
+        let runner =
+            create_composition_runner(supergraph_yaml_path.clone(), None, client_config, lsp_opts)
+                .await?;
+        let subgraphs: BTreeMap<String, SchemaSource> = runner
+            .state
+            .initial_supergraph_config
+            .subgraphs()
+            .iter()
+            .map(|(name, subgraph)| (name.clone(), subgraph.schema().clone()))
+            .collect();
+        let subgraph_schema_files = subgraphs
+            .values()
+            .filter_map(|schema_source| match schema_source {
+                SchemaSource::File { file } => {
+                    let path = dunce::canonicalize(file).unwrap_or_else(|_| file.clone());
+                    Utf8PathBuf::from_path_buf(path).ok()
+                }
+                _ => None,
+            })
+            .collect();
+
+        let (updates, _) = broadcast::channel(UPDATE_CAPACITY);
+        let composition = Arc::new(Self {
+            supergraph_yaml_path,
+            supergraph_yaml_url,
+            subgraph_schema_files,
+            state: Mutex::new(CompositionState {
+                subgraphs,
+                ..Default::default()
+            }),
+            updates,
+        });
+
+        let mut stream = runner.run();
+        let pump = composition.clone();
+        tokio::spawn(async move {
+            info!(
+                "Listening for Composition Events for {}",
+                pump.supergraph_yaml_path
+            );
+            while let Some(event) = stream.next().await {
+                // The state is updated and the updates sent while holding the lock, so that
+                // anyone subscribing sees either the state before this event and its updates, or
+                // the state after it and none of them.
+                let mut state = pump.lock();
+                for update in state.apply(event) {
+                    pump.updates.send(update).ok();
+                }
+            }
+        });
+        Ok(composition)
+    }
+
+    pub(super) const fn supergraph_yaml_path(&self) -> &Utf8PathBuf {
+        &self.supergraph_yaml_path
+    }
+
+    pub(super) const fn supergraph_yaml_url(&self) -> &Url {
+        &self.supergraph_yaml_url
+    }
+
+    /// Schema files of subgraphs sourced from disk, which must never be mistaken for operation
+    /// documents.
+    pub(super) fn subgraph_schema_files(&self) -> HashSet<Utf8PathBuf> {
+        self.subgraph_schema_files.clone()
+    }
+
+    /// Returns the subgraphs a new language server should start with, the updates that bring it
+    /// level with the others, and a receiver for everything after that.
+    pub(super) fn subscribe(
+        &self,
+    ) -> (
+        HashMap<String, SchemaSource>,
+        Vec<LanguageServerUpdate>,
+        broadcast::Receiver<LanguageServerUpdate>,
+    ) {
+        let state = self.lock();
+        let subgraphs = state
+            .subgraphs
+            .iter()
+            .map(|(name, schema_source)| (name.clone(), schema_source.clone()))
+            .collect();
+        let mut catch_up = Vec::new();
+        if let Some(last_finished) = &state.last_finished {
+            // Finishing a composition ends the progress report its start began
+            catch_up.push(LanguageServerUpdate::CompositionStarted);
+            catch_up.push(last_finished.clone());
+        }
+        if !state.supergraph_config_diagnostics.is_empty() {
+            catch_up.push(LanguageServerUpdate::SupergraphConfigDiagnostics(
+                state.supergraph_config_diagnostics.clone(),
+            ));
+        }
+        (subgraphs, catch_up, self.updates.subscribe())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CompositionState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl CompositionState {
+    /// Records `event`, returning the updates that language servers need to hear about it.
+    fn apply(&mut self, event: CompositionEvent) -> Vec<LanguageServerUpdate> {
+        let updates = match event {
+            CompositionEvent::Started => vec![LanguageServerUpdate::CompositionStarted],
+            CompositionEvent::Success(CompositionSuccess {
+                supergraph_sdl,
+                federation_version,
+                hints,
+            }) => {
+                debug!("Successfully composed with version {}", federation_version);
+                vec![
+                    // Ensure we keep publishing the resolution errors
+                    self.resolution_error_diagnostics(),
+                    // Publish the results of the composition (new supergraph SDL)
+                    LanguageServerUpdate::CompositionFinished {
+                        supergraph_sdl: Some(supergraph_sdl),
+                        issues: hints.into_iter().map(Into::into).collect(),
+                    },
+                ]
+            }
+            CompositionEvent::Error(CompositionError::Build {
+                source: errors,
+                federation_version,
+            }) => {
+                debug!(
+                    ?errors,
+                    "Composition {federation_version} completed with errors"
+                );
+                vec![
+                    // Ensure we keep publishing the resolution errors
+                    self.resolution_error_diagnostics(),
+                    // Publish the composition errors with more metadata, as this can be
+                    // gleaned from the output of the `supergraph` binary.
+                    LanguageServerUpdate::CompositionFinished {
+                        supergraph_sdl: None,
+                        issues: errors.into_iter().map(Into::into).collect(),
+                    },
+                ]
+            }
+            CompositionEvent::Error(err) => {
+                debug!("Composition failed: {err}");
+                // If we get a composition error back that is not a partial success (as above)
+                // we publish the message from this error and the resolution errors together.
+                let message = match err {
+                    CompositionError::ErrorUpdatingFederationVersion(
+                        InstallSupergraphError::MissingDependency { err },
+                    ) => format!("Supergraph Version could not be updated: {err}"),
+                    CompositionError::ResolvingSubgraphsError(
+                        ResolveSupergraphConfigError::ResolveSubgraphs(errors),
+                    ) => {
+                        self.resolution_errors = errors
+                            .into_iter()
+                            .map(|(name, error)| {
+                                (name.clone(), create_subgraph_resolution_error(&name, error))
+                            })
+                            .collect();
+                        "Composition failed to run due to subgraph resolution issues".to_string()
+                    }
+                    _ => format!("Composition failed to run: {err}"),
+                };
+                let mut diagnostics: Vec<Diagnostic> =
+                    self.resolution_errors.values().cloned().collect();
+                diagnostics.push(Diagnostic::new_simple(Range::default(), message));
+                vec![LanguageServerUpdate::SupergraphConfigDiagnostics(
+                    diagnostics,
+                )]
+            }
+            CompositionEvent::SubgraphAdded(CompositionSubgraphAdded {
+                name,
+                schema_source,
+            }) => {
+                debug!("Subgraph {} added", name);
+                // Adding a subgraph will always remove any resolution errors as it must
+                // resolve correctly to be added.
+                self.resolution_errors.remove(&name);
+                self.subgraphs.insert(name.clone(), schema_source.clone());
+                vec![
+                    LanguageServerUpdate::SubgraphAdded {
+                        name,
+                        schema_source,
+                    },
+                    self.resolution_error_diagnostics(),
+                ]
+            }
+            CompositionEvent::SubgraphRemoved(CompositionSubgraphRemoved {
+                name,
+                resolution_error,
+            }) => {
+                // If we get a resolution error in the event then we can update our internal
+                // structures, otherwise the removal event was not caused by error, so just
+                // remove and then continue on.
+                if let Some(error) = resolution_error {
+                    self.resolution_errors
+                        .insert(name.clone(), create_subgraph_resolution_error(&name, error));
+                }
+                debug!("Subgraph {} removed", name);
+                self.subgraphs.remove(&name);
+                vec![
+                    self.resolution_error_diagnostics(),
+                    LanguageServerUpdate::SubgraphRemoved { name },
+                ]
+            }
+        };
+
+        for update in &updates {
+            match update {
+                LanguageServerUpdate::CompositionFinished { .. } => {
+                    self.last_finished = Some(update.clone())
+                }
+                LanguageServerUpdate::SupergraphConfigDiagnostics(diagnostics) => {
+                    self.supergraph_config_diagnostics = diagnostics.clone()
+                }
+                _ => {}
+            }
+        }
+        updates
+    }
+
+    fn resolution_error_diagnostics(&self) -> LanguageServerUpdate {
+        LanguageServerUpdate::SupergraphConfigDiagnostics(
+            self.resolution_errors.values().cloned().collect(),
+        )
+    }
+}
+
+fn create_subgraph_resolution_error(name: &str, error: ResolveSubgraphError) -> Diagnostic {
+    let message = format!("Subgraph '{name}' could not be resolved: {error}");
+    Diagnostic::new_simple(Range::default(), message)
+}
+
+/// Passes composition updates on to `language_server`, starting with those that bring it level
+/// with the other language servers attached to the same composition.
+pub(super) async fn drive_language_server(
+    language_server: ApolloLanguageServer,
+    supergraph_yaml_url: Url,
+    catch_up: Vec<LanguageServerUpdate>,
+    mut updates: broadcast::Receiver<LanguageServerUpdate>,
+    operations: Option<OperationDocuments>,
+) {
+    for update in catch_up {
+        apply_update(&language_server, &supergraph_yaml_url, update, &operations).await;
+    }
+    loop {
+        match updates.recv().await {
+            Ok(update) => {
+                apply_update(&language_server, &supergraph_yaml_url, update, &operations).await
+            }
+            Err(RecvError::Lagged(missed)) => {
+                debug!("Language server missed {missed} composition updates")
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+async fn apply_update(
+    language_server: &ApolloLanguageServer,
+    supergraph_yaml_url: &Url,
+    update: LanguageServerUpdate,
+    operations: &Option<OperationDocuments>,
+) {
+    match update {
+        LanguageServerUpdate::CompositionStarted => {
+            // Even though it's hidden by library calls, this function emits a WorkDoneProgressBegin event,
+            // which is paired with a WorkDoneProgressEnd event, sent by the `composition_did_update` function.
+            // Any refactoring needs to ensure that we don't break this ordering, otherwise the LSP may well
+            // cease to function in a useful way.
+            language_server.composition_did_start().await;
+        }
+        LanguageServerUpdate::CompositionFinished {
+            supergraph_sdl,
+            issues,
+        } => {
+            // Operations are validated against the API schema of the new supergraph
+            if let (Some(operations), Some(supergraph_sdl)) = (operations, &supergraph_sdl) {
+                operations.schema_did_update(supergraph_sdl).await;
+            }
+            language_server
+                .composition_did_update(supergraph_sdl, issues, None)
+                .await;
+        }
+        LanguageServerUpdate::SupergraphConfigDiagnostics(diagnostics) => {
+            language_server
+                .publish_diagnostics(supergraph_yaml_url.clone(), diagnostics)
+                .await;
+        }
+        LanguageServerUpdate::SubgraphAdded {
+            name,
+            schema_source,
+        } => language_server.add_subgraph(name, schema_source).await,
+        LanguageServerUpdate::SubgraphRemoved { name } => {
+            language_server.remove_subgraph(&name).await
+        }
+    }
+}
@@ -1,46 +1,37 @@
+mod composition;
 mod errors;
+mod operations;
+mod session;
+mod transport;
+mod workspace;
 
-use std::{collections::HashMap, env::temp_dir, fmt::Debug, io::stdin, path::PathBuf};
+use std::{env::temp_dir, fmt::Debug, io::stdin, path::PathBuf, sync::Arc};
 
 use apollo_federation_types::config::FederationVersion;
-use apollo_language_server::{ApolloLanguageServer, Config, MaxSpecVersions};
 use camino::Utf8PathBuf;
 use clap::Parser;
-use futures::{StreamExt, channel::oneshot};
 use serde::Serialize;
+use tokio::io::BufReader;
 use tower::ServiceExt;
-use tower_lsp::{
-    Server,
-    lsp_types::{Diagnostic, Range},
-};
-use tracing::{debug, info};
 use url::Url;
 
+pub use self::transport::ListenAddr;
 use crate::{
     RoverOutput, RoverResult,
-    command::lsp::errors::{
-        StartCompositionError, StartCompositionError::SupergraphYamlUrlConversionFailed,
-    },
+    command::lsp::{composition::Compositions, errors::StartCompositionError},
     composition::{
-        CompositionError, CompositionSubgraphAdded, CompositionSubgraphRemoved, CompositionSuccess,
-        FederationUpdaterConfig,
-        events::CompositionEvent,
-        get_supergraph_binary,
+        FederationUpdaterConfig, get_supergraph_binary,
         pipeline::CompositionPipeline,
         runner::CompositionRunner,
-        supergraph::{
-            config::{
-                error::ResolveSubgraphError,
-                full::introspect::MakeResolveIntrospectSubgraph,
-                resolver::{
-                    ResolveSupergraphConfigError, fetch_remote_subgraph::MakeFetchRemoteSubgraph,
-                    fetch_remote_subgraphs::MakeFetchRemoteSubgraphs,
-                },
+        supergraph::config::{
+            full::introspect::MakeResolveIntrospectSubgraph,
+            resolver::{
+                fetch_remote_subgraph::MakeFetchRemoteSubgraph,
+                fetch_remote_subgraphs::MakeFetchRemoteSubgraphs,
             },
-            install::InstallSupergraphError,
         },
     },
-    options::PluginOpts,
+    options::{FileDiscoveryOpt, PluginOpts},
     utils::{
         client::StudioClientConfig,
         effect::{exec::TokioCommand, write_file::FsWriteFile},
@@ -69,6 +60,20 @@ pub struct LspOpts {
     #[arg(long = "polling-interval", short = 'i', default_value = "5")]
     #[serde(skip_serializing)]
     introspection_polling_interval: u64,
+
+    /// Client operation documents to validate against the composed API schema, selected with
+    /// `--include`/`--exclude`. Operations are only tracked when at least one `--include` is
+    /// given.
+    #[clap(flatten)]
+    #[serde(flatten)]
+    operation_discovery: FileDiscoveryOpt,
+
+    /// Accept editor connections on this address instead of talking over stdin/stdout, so that
+    /// several editors can share one long-lived server. Either a TCP address such as
+    /// `127.0.0.1:9257`, or `unix:<path>` for a Unix domain socket.
+    #[arg(long = "listen", value_name = "ADDR")]
+    #[serde(skip_serializing)]
+    listen: Option<ListenAddr>,
 }
 
 impl Lsp {
@@ -96,94 +101,55 @@ async fn run_lsp(client_config: StudioClientConfig, lsp_opts: LspOpts) -> RoverR
         }
     });
 
-    // Early return if there is no `supergraph.yaml` given as there is no further need to construct
-    // anything
-    let (service, socket) = match supergraph_yaml_path {
+    let server = Arc::new(LspServer {
+        compositions: Compositions::new(client_config.clone(), lsp_opts.clone()),
+        client_config,
+        supergraph_yaml_path,
+        lsp_opts,
+    });
+    // A supergraph config given on the command line must compose before anything is served, so
+    // that problems with it stop the server from starting.
+    if let Some(supergraph_yaml_path) = &server.supergraph_yaml_path {
+        server
+            .compositions
+            .get_or_start(supergraph_yaml_path)
+            .await?;
+    }
+
+    match server.lsp_opts.listen.clone() {
+        Some(addr) => transport::listen(server, &addr).await?,
         None => {
-            let (service, socket, _receiver) = ApolloLanguageServer::build_service(
-                Config {
-                    root_uri: String::default(),
-                    enable_auto_composition: false,
-                    force_federation: false,
-                    disable_telemetry: false,
-                    max_spec_versions: MaxSpecVersions {
-                        connect: None,
-                        federation: None,
-                    },
-                },
-                HashMap::new(),
-                None,
-            );
-            (service, socket)
+            let stdin = BufReader::new(tokio::io::stdin());
+            let stdout = tokio::io::stdout();
+            session::serve(server, stdin, stdout).await;
         }
-        Some(supergraph_yaml_path) => {
-            let supergraph_yaml_url = Url::from_file_path(supergraph_yaml_path.clone())
-                .map_err(|_| SupergraphYamlUrlConversionFailed(supergraph_yaml_path.clone()))?;
+    }
+    Ok(())
+}
 
-            let (spec_lookup_sender, mut spec_lookup_receiver) =
-                futures::channel::mpsc::channel::<(PathBuf, oneshot::Sender<Option<String>>)>(100);
-            let lookup_client_config = client_config.clone();
-            let lookup_supergraph_yaml_path = supergraph_yaml_path.clone();
-            let lookup_plugin_opts = lsp_opts.plugin_opts.clone();
-            tokio::spawn(async move {
-                while let Some((path, response)) = spec_lookup_receiver.next().await {
-                    let spec = load_spec_for_path(
-                        path,
-                        lookup_client_config.clone(),
-                        lookup_supergraph_yaml_path.clone(),
-                        lookup_plugin_opts.clone(),
-                    )
-                    .await;
-                    response.send(spec).ok();
-                }
-            });
-            // Create a composition runner first, so that we can use that to drive the initial
-            // set of subgraphs that get reported to the LSP
-            let composition_runner =
-                create_composition_runner(supergraph_yaml_path, None, client_config, lsp_opts)
-                    .await?;
-            let initial_subgraphs = composition_runner
-                .state
-                .initial_supergraph_config
-                .subgraphs()
-                .iter()
-                .map(|(name, subgraph)| (name.clone(), subgraph.schema().clone()))
-                .collect();
+/// State shared by every editor connected to a `rover lsp` process.
+struct LspServer {
+    client_config: StudioClientConfig,
+    lsp_opts: LspOpts,
+    /// The supergraph config given on the command line, which takes the place of looking for one
+    /// in each workspace folder.
+    supergraph_yaml_path: Option<Utf8PathBuf>,
+    compositions: Compositions,
+}
 
-            // Generate the config needed to spin up the Language Server
-            let (service, socket, _receiver) = ApolloLanguageServer::build_service(
-                Config {
-                    root_uri: String::from(supergraph_yaml_url.clone()),
-                    enable_auto_composition: false,
-                    // Ensure that we force_federation here, otherwise, if we have a broken
-                    // supergraph.yaml and thus end up detecting no subgraphs, the LSP doesn't
-                    // think we're doing this for a monograph.
-                    force_federation: true,
-                    disable_telemetry: false,
-                    max_spec_versions: MaxSpecVersions {
-                        connect: None,
-                        federation: None,
-                    },
-                },
-                initial_subgraphs,
-                Some(spec_lookup_sender),
-            );
-            // Start running composition
-            start_composition(
-                composition_runner,
-                service.inner().to_owned(),
-                supergraph_yaml_url,
-            )
-            .await?;
-            (service, socket)
+impl LspServer {
+    /// The options for finding operation documents in the workspace at `folder`, if any were
+    /// given. Without an explicit `--root-dir`, a workspace folder is scanned from its top.
+    fn operation_discovery(&self, folder: Option<&Url>) -> Option<FileDiscoveryOpt> {
+        let mut discovery = Some(self.lsp_opts.operation_discovery.clone())
+            .filter(|discovery| !discovery.include.is_empty())?;
+        if discovery.root_dir.is_none() {
+            discovery.root_dir = folder
+                .and_then(|folder| folder.to_file_path().ok())
+                .and_then(|path| Utf8PathBuf::from_path_buf(path).ok());
         }
-    };
-
-    let stdin = tokio::io::stdin();
-    let stdout = tokio::io::stdout();
-    let server = Server::new(stdin, stdout, socket);
-    server.serve(service).await;
-    Ok(())
+        Some(discovery)
+    }
 }
 
 async fn load_spec_for_path(
@@ -212,161 +178,6 @@ async fn load_spec_for_path(
     res.ok()
 }
 
-async fn start_composition(
-    runner: CompositionRunner<TokioCommand, FsWriteFile>,
-    language_server: ApolloLanguageServer,
-    supergraph_yaml_url: Url,
-) -> Result<(), StartCompositionError> {
-    let mut stream = runner.run();
-    // Keep a separate data structure to store any resolution errors, specific to the
-    // subgraphs so they can be retained, even after a potentially successful composition.
-    //
-    // These are published each time we publish diagnostics, because they can only be cleared
-    // via a SubgraphAdded event.
-    let mut resolution_errors = HashMap::new();
-
-    // Spawn a separate thread to handle composition and passing that data to the language server
-    tokio::spawn(async move {
-        // This is a slight hack, however, there's no way to tell whether LSP has booted correctly
-        // before we start emitting events to it, due to limitations in `tower_lsp`. As such
-        // we wait in order that early events do not get lost
-        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-        info!("Listening for Composition Events");
-        while let Some(event) = stream.next().await {
-            match event {
-                CompositionEvent::Started => {
-                    // Even though it's hidden by library calls, this function emits a WorkDoneProgressBegin event,
-                    // which is paired with a WorkDoneProgressEnd event, sent by the `composition_did_update` function.
-                    // Any refactoring needs to ensure that we don't break this ordering, otherwise the LSP may well
-                    // cease to function in a useful way.
-                    language_server.composition_did_start().await;
-                }
-                CompositionEvent::Success(CompositionSuccess {
-                    supergraph_sdl,
-                    federation_version,
-                    hints,
-                }) => {
-                    debug!("Successfully composed with version {}", federation_version);
-                    // Ensure we keep publishing the resolution errors
-                    language_server
-                        .publish_diagnostics(
-                            supergraph_yaml_url.clone(),
-                            resolution_errors.values().cloned().collect(),
-                        )
-                        .await;
-                    // Publish the results of the composition (new supergraph SDL)
-                    language_server
-                        .composition_did_update(
-                            Some(supergraph_sdl),
-                            hints.into_iter().map(Into::into).collect(),
-                            None,
-                        )
-                        .await;
-                }
-                CompositionEvent::Error(CompositionError::Build {
-                    source: errors,
-                    federation_version,
-                }) => {
-                    debug!(
-                        ?errors,
-                        "Composition {federation_version} completed with errors"
-                    );
-                    // Ensure we keep publishing the resolution errors
-                    language_server
-                        .publish_diagnostics(
-                            supergraph_yaml_url.clone(),
-                            resolution_errors.values().cloned().collect(),
-                        )
-                        .await;
-                    // Publish the composition errors with more metadata, as this can be
-                    // gleaned from the output of the `supergraph` binary.
-                    language_server
-                        .composition_did_update(
-                            None,
-                            errors.into_iter().map(Into::into).collect(),
-                            None,
-                        )
-                        .await
-                }
-                CompositionEvent::Error(err) => {
-                    debug!("Composition failed: {err}");
-                    // If we get a composition error back that is not a partial success (as above)
-                    // we publish the message from this error and the resolution errors together.
-                    let message = match err {
-                        CompositionError::ErrorUpdatingFederationVersion(
-                            InstallSupergraphError::MissingDependency { err },
-                        ) => format!("Supergraph Version could not be updated: {err}"),
-                        CompositionError::ResolvingSubgraphsError(
-                            ResolveSupergraphConfigError::ResolveSubgraphs(errors),
-                        ) => {
-                            let new_errors = errors
-                                .into_iter()
-                                .map(|(name, error)| {
-                                    (name.clone(), create_subgraph_resolution_error(&name, error))
-                                })
-                                .collect();
-                            resolution_errors = new_errors;
-                            "Composition failed to run due to subgraph resolution issues"
-                                .to_string()
-                        }
-                        _ => format!("Composition failed to run: {err}"),
-                    };
-                    let diagnostic = Diagnostic::new_simple(Range::default(), message);
-                    let mut diagnostics_to_publish: Vec<Diagnostic> =
-                        resolution_errors.values().cloned().collect();
-                    diagnostics_to_publish.push(diagnostic);
-                    language_server
-                        .publish_diagnostics(supergraph_yaml_url.clone(), diagnostics_to_publish)
-                        .await;
-                }
-                CompositionEvent::SubgraphAdded(CompositionSubgraphAdded {
-                    name,
-                    schema_source,
-                }) => {
-                    debug!("Subgraph {} added", name);
-                    // Adding a subgraph will always remove any resolution errors as it must
-                    // resolve correctly to be added.
-                    resolution_errors.remove(&name);
-                    language_server.add_subgraph(name, schema_source).await;
-                    language_server
-                        .publish_diagnostics(
-                            supergraph_yaml_url.clone(),
-                            resolution_errors.values().cloned().collect(),
-                        )
-                        .await;
-                }
-                CompositionEvent::SubgraphRemoved(CompositionSubgraphRemoved {
-                    name,
-                    resolution_error,
-                }) => {
-                    // If we get a resolution error in the event then we can update our internal
-                    // structures, otherwise the removal event was not caused by error, so just
-                    // remove and then continue on.
-                    if let Some(error) = resolution_error {
-                        resolution_errors
-                            .insert(name.clone(), create_subgraph_resolution_error(&name, error));
-                    }
-                    language_server
-                        .publish_diagnostics(
-                            supergraph_yaml_url.clone(),
-                            resolution_errors.values().cloned().collect(),
-                        )
-                        .await;
-                    debug!("Subgraph {} removed", name);
-                    language_server.remove_subgraph(&name).await;
-                }
-            }
-        }
-        Ok::<(), StartCompositionError>(())
-    });
-    Ok(())
-}
-
-fn create_subgraph_resolution_error(name: &str, error: ResolveSubgraphError) -> Diagnostic {
-    let message = format!("Subgraph '{name}' could not be resolved: {error}");
-    Diagnostic::new_simple(Range::default(), message)
-}
-
 async fn create_composition_runner(
     supergraph_config_path: Utf8PathBuf,
     federation_version: Option<FederationVersion>,
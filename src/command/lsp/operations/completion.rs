@@ -0,0 +1,247 @@
+use apollo_compiler::{
+    Schema,
+    ast::OperationType,
+    schema::{ExtendedType, FieldDefinition},
+    validation::Valid,
+};
+use apollo_parser::{Parser, cst, cst::CstNode};
+use tower_lsp::lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionItemTag, Documentation, MarkupContent,
+    MarkupKind, Position,
+};
+
+use super::diagnostics::position_to_offset;
+
+/// Offers the fields that can be selected at `position`, based on the type of the selection set
+/// the cursor sits in.
+pub(super) fn completions(
+    schema: &Valid<Schema>,
+    text: &str,
+    position: Position,
+) -> Vec<CompletionItem> {
+    let offset = position_to_offset(text, position);
+    let Some(type_name) = selection_set_type(schema, text, offset) else {
+        return Vec::new();
+    };
+
+    let mut items: Vec<CompletionItem> = match schema.types.get(type_name.as_str()) {
+        Some(ExtendedType::Object(object)) => object
+            .fields
+            .values()
+            .map(|field| field_item(field))
+            .collect(),
+        Some(ExtendedType::Interface(interface)) => interface
+            .fields
+            .values()
+            .map(|field| field_item(field))
+            .collect(),
+        Some(ExtendedType::Union(union_)) => union_
+            .members
+            .iter()
+            .map(|member| CompletionItem {
+                label: format!("... on {}", member.name),
+                kind: Some(CompletionItemKind::CLASS),
+                ..Default::default()
+            })
+            .collect(),
+        _ => return Vec::new(),
+    };
+    items.push(CompletionItem {
+        label: "__typename".to_string(),
+        kind: Some(CompletionItemKind::FIELD),
+        detail: Some("String!".to_string()),
+        ..Default::default()
+    });
+    items
+}
+
+fn field_item(field: &FieldDefinition) -> CompletionItem {
+    let deprecated = field.directives.has("deprecated");
+    CompletionItem {
+        label: field.name.to_string(),
+        kind: Some(CompletionItemKind::FIELD),
+        detail: Some(field.ty.to_string()),
+        documentation: field.description.as_ref().map(|description| {
+            Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: description.to_string(),
+            })
+        }),
+        tags: deprecated.then(|| vec![CompletionItemTag::DEPRECATED]),
+        ..Default::default()
+    }
+}
+
+/// A step on the way from an operation or fragment definition down to a nested selection set.
+enum Step {
+    Root(String),
+    Field(String),
+    TypeCondition(Option<String>),
+}
+
+/// Resolves the type of the innermost selection set containing `offset`.
+fn selection_set_type(schema: &Valid<Schema>, text: &str, offset: usize) -> Option<String> {
+    let tree = Parser::new(text).parse();
+    let selection_set = tree
+        .document()
+        .syntax()
+        .descendants()
+        .filter_map(cst::SelectionSet::cast)
+        .filter(|selection_set| {
+            let range = selection_set.syntax().text_range();
+            let (start, end) = (usize::from(range.start()), usize::from(range.end()));
+            // While it's being typed, an unclosed selection set also covers the whitespace the
+            // parser leaves after it
+            let unclosed = selection_set.r_curly_token().is_none()
+                && text
+                    .get(end..offset)
+                    .is_some_and(|gap| gap.trim().is_empty());
+            start < offset && (offset < end || unclosed)
+        })
+        .min_by_key(|selection_set| selection_set.syntax().text_range().len())?;
+
+    let mut steps = Vec::new();
+    for ancestor in selection_set.syntax().ancestors() {
+        if let Some(field) = cst::Field::cast(ancestor.clone()) {
+            steps.push(Step::Field(field.name()?.text().to_string()));
+        } else if let Some(inline) = cst::InlineFragment::cast(ancestor.clone()) {
+            steps.push(Step::TypeCondition(
+                inline
+                    .type_condition()
+                    .and_then(|condition| condition.named_type()?.name())
+                    .map(|name| name.text().to_string()),
+            ));
+        } else if let Some(fragment) = cst::FragmentDefinition::cast(ancestor.clone()) {
+            let name = fragment.type_condition()?.named_type()?.name()?;
+            steps.push(Step::Root(name.text().to_string()));
+        } else if let Some(operation) = cst::OperationDefinition::cast(ancestor) {
+            let operation_type = match operation.operation_type() {
+                Some(ty) if ty.mutation_token().is_some() => OperationType::Mutation,
+                Some(ty) if ty.subscription_token().is_some() => OperationType::Subscription,
+                _ => OperationType::Query,
+            };
+            steps.push(Step::Root(
+                schema.root_operation(operation_type)?.to_string(),
+            ));
+        }
+    }
+
+    let mut current: Option<String> = None;
+    for step in steps.into_iter().rev() {
+        current = match step {
+            Step::Root(name) => Some(name),
+            Step::Field(name) => {
+                let parent = current?;
+                let field = schema.type_field(&parent, &name).ok()?;
+                Some(field.ty.inner_named_type().to_string())
+            }
+            Step::TypeCondition(Some(name)) => Some(name),
+            Step::TypeCondition(None) => current,
+        };
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::command::lsp::operations::diagnostics::offset_to_position;
+
+    const SCHEMA: &str = r#"
+        type Query {
+            me: User
+            search: [SearchResult!]!
+        }
+
+        type Mutation {
+            rename(name: String!): User
+        }
+
+        type User {
+            "The user's id"
+            id: ID!
+            name: String
+            username: String @deprecated(reason: "Use `name`.")
+        }
+
+        type Post {
+            title: String!
+        }
+
+        union SearchResult = User | Post
+    "#;
+
+    /// Returns the sorted completion labels at the `|` cursor marker in `text`.
+    fn labels_at(text: &str) -> Vec<String> {
+        let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+        let offset = text.find('|').unwrap();
+        let text = text.replacen('|', "", 1);
+        let position = offset_to_position(&text, offset);
+        let mut labels: Vec<String> = completions(&schema, &text, position)
+            .into_iter()
+            .map(|item| item.label)
+            .collect();
+        labels.sort();
+        labels
+    }
+
+    #[test]
+    fn completes_root_fields() {
+        assert_that!(labels_at("query { | }")).is_equal_to(
+            vec!["__typename", "me", "search"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn completes_nested_fields_in_unfinished_documents() {
+        assert_that!(labels_at("query Me {\n  me {\n    |")).is_equal_to(
+            vec!["__typename", "id", "name", "username"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn completes_mutation_and_fragment_types() {
+        assert_that!(labels_at("mutation { rename(name: \"x\") { | } }"))
+            .contains("name".to_string());
+        assert_that!(labels_at("fragment F on Post { | }"))
+            .is_equal_to(vec!["__typename".to_string(), "title".to_string()]);
+    }
+
+    #[test]
+    fn completes_union_members_and_inline_fragments() {
+        assert_that!(labels_at("{ search { | } }")).is_equal_to(
+            vec!["... on Post", "... on User", "__typename"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>(),
+        );
+        assert_that!(labels_at("{ search { ... on Post { | } } }"))
+            .is_equal_to(vec!["__typename".to_string(), "title".to_string()]);
+    }
+
+    #[test]
+    fn field_items_carry_type_docs_and_deprecation() {
+        let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+        let text = "{ me { } }";
+        let position = offset_to_position(text, text.find("} }").unwrap());
+        let items = completions(&schema, text, position);
+        let id = items.iter().find(|item| item.label == "id").unwrap();
+        assert_that!(id.detail).is_equal_to(Some("ID!".to_string()));
+        assert_that!(id.documentation).is_some();
+        let username = items.iter().find(|item| item.label == "username").unwrap();
+        assert_that!(username.tags).is_equal_to(Some(vec![CompletionItemTag::DEPRECATED]));
+    }
+
+    #[test]
+    fn no_completions_outside_selection_sets() {
+        assert_that!(labels_at("| query { me { id } }")).is_empty();
+    }
+}
@@ -0,0 +1,378 @@
+use std::path::Path;
+
+use apollo_compiler::{
+    ExecutableDocument, Name, Node, Schema,
+    diagnostic::ToCliReport,
+    executable::{Field, Selection, SelectionSet},
+    parser::{FileId, SourceSpan},
+    validation::{DiagnosticList, Valid},
+};
+use apollo_parser::{Parser, cst, cst::CstNode};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, Position, Range};
+
+const DIAGNOSTIC_SOURCE: &str = "rover";
+
+/// Path used for the fragment definitions borrowed from other tracked documents, so their
+/// diagnostics can be told apart from those in the document being validated.
+const SHARED_FRAGMENTS_PATH: &str = "rover-lsp-shared-fragments.graphql";
+
+/// Validates a single operation document against `schema`, returning the diagnostics that
+/// belong to it.
+///
+/// `shared_fragments` holds the names and definitions of the fragments in the other tracked
+/// documents, so that spreading a fragment defined in a different file is not reported as unknown.
+pub(super) fn diagnostics(
+    schema: &Valid<Schema>,
+    path: &str,
+    text: &str,
+    shared_fragments: &[(&str, &str)],
+) -> Vec<Diagnostic> {
+    let local_fragments: Vec<String> = fragment_definitions(text)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    let borrowed = shared_fragments
+        .iter()
+        .filter(|(name, _)| !local_fragments.iter().any(|local| local == name))
+        .map(|(_, definition)| *definition)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut errors = DiagnosticList::new(Default::default());
+    let mut builder = ExecutableDocument::builder(Some(schema), &mut errors).parse(text, path);
+    if !borrowed.is_empty() {
+        builder = builder.parse(borrowed, SHARED_FRAGMENTS_PATH);
+    }
+    let document = builder.build();
+
+    let Some(file_id) = file_id_for_path(&document, path) else {
+        return Vec::new();
+    };
+
+    let mut diagnostics = Vec::new();
+    let validation_errors = match document.clone().validate(schema) {
+        Ok(_) => None,
+        Err(with_errors) => Some(with_errors.errors),
+    };
+    for list in std::iter::once(&errors).chain(validation_errors.as_ref()) {
+        for diagnostic in list.iter() {
+            if diagnostic.error.unstable_error_name() == Some("UnusedFragment") {
+                // A fragment may well be used from a different document
+                continue;
+            }
+            let range = match diagnostic.error.location() {
+                Some(span) if span.file_id() == file_id => span_to_range(text, span),
+                Some(_) => continue,
+                None => Range::default(),
+            };
+            diagnostics.push(Diagnostic {
+                range,
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some(DIAGNOSTIC_SOURCE.to_string()),
+                message: diagnostic.error.to_string(),
+                ..Default::default()
+            });
+        }
+    }
+
+    let mut deprecations = Vec::new();
+    for operation in document.operations.iter() {
+        collect_deprecations(&operation.selection_set, file_id, text, &mut deprecations);
+    }
+    for fragment in document.fragments.values() {
+        collect_deprecations(&fragment.selection_set, file_id, text, &mut deprecations);
+    }
+    diagnostics.extend(deprecations);
+    diagnostics
+}
+
+/// Returns the name and source text of each fragment definition in `text`.
+pub(super) fn fragment_definitions(text: &str) -> Vec<(String, String)> {
+    let tree = Parser::new(text).parse();
+    tree.document()
+        .definitions()
+        .filter_map(|definition| match definition {
+            cst::Definition::FragmentDefinition(fragment) => {
+                let name = fragment.fragment_name()?.name()?.text().to_string();
+                Some((name, fragment.syntax().text().to_string()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn file_id_for_path(document: &ExecutableDocument, path: &str) -> Option<FileId> {
+    document
+        .sources
+        .iter()
+        .find(|(_, source)| source.path() == Path::new(path))
+        .map(|(id, _)| *id)
+}
+
+fn collect_deprecations(
+    selection_set: &SelectionSet,
+    file_id: FileId,
+    text: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                collect_field_deprecations(&selection_set.ty, field, file_id, text, diagnostics);
+                collect_deprecations(&field.selection_set, file_id, text, diagnostics);
+            }
+            Selection::InlineFragment(inline) => {
+                collect_deprecations(&inline.selection_set, file_id, text, diagnostics)
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+fn collect_field_deprecations(
+    parent_type: &Name,
+    field: &Node<Field>,
+    file_id: FileId,
+    text: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some(span) = field.location().filter(|span| span.file_id() == file_id) else {
+        return;
+    };
+    if let Some(reason) = deprecation_reason(&field.definition.directives) {
+        let start = name_offset(text, span, field.alias.is_some(), field.name.as_str());
+        diagnostics.push(deprecation(
+            offsets_to_range(text, start, start + field.name.len()),
+            format!(
+                "The field `{parent_type}.{}` is deprecated. {reason}",
+                field.name
+            ),
+        ));
+    }
+    for argument in &field.arguments {
+        let Some(definition) = field.definition.argument_by_name(&argument.name) else {
+            continue;
+        };
+        let Some(reason) = deprecation_reason(&definition.directives) else {
+            continue;
+        };
+        let Some(span) = argument.location().filter(|span| span.file_id() == file_id) else {
+            continue;
+        };
+        diagnostics.push(deprecation(
+            span_to_range(text, span),
+            format!(
+                "The argument `{}` of `{}` is deprecated. {reason}",
+                argument.name, field.name
+            ),
+        ));
+    }
+}
+
+fn deprecation(range: Range, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message: message.trim_end().to_string(),
+        tags: Some(vec![DiagnosticTag::DEPRECATED]),
+        ..Default::default()
+    }
+}
+
+fn deprecation_reason(directives: &apollo_compiler::ast::DirectiveList) -> Option<String> {
+    let deprecated = directives.get("deprecated")?;
+    Some(
+        deprecated
+            .specified_argument_by_name("reason")
+            .and_then(|reason| reason.as_str())
+            .unwrap_or("No longer supported")
+            .to_string(),
+    )
+}
+
+/// Finds the offset of a field's name within its span, skipping over any alias.
+fn name_offset(text: &str, span: SourceSpan, aliased: bool, name: &str) -> usize {
+    let start = span.offset();
+    if !aliased {
+        return start;
+    }
+    let source = text.get(start..span.end_offset()).unwrap_or_default();
+    source
+        .find(':')
+        .and_then(|colon| {
+            source[colon..]
+                .find(name)
+                .map(|position| start + colon + position)
+        })
+        .unwrap_or(start)
+}
+
+fn span_to_range(text: &str, span: SourceSpan) -> Range {
+    offsets_to_range(text, span.offset(), span.end_offset())
+}
+
+fn offsets_to_range(text: &str, start: usize, end: usize) -> Range {
+    Range::new(
+        offset_to_position(text, start),
+        offset_to_position(text, end),
+    )
+}
+
+/// Converts a byte offset into an LSP position, whose character is counted in UTF-16 code units.
+pub(super) fn offset_to_position(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    let line = before.matches('\n').count() as u32;
+    let character = before[line_start..].encode_utf16().count() as u32;
+    Position::new(line, character)
+}
+
+/// Converts an LSP position into a byte offset, clamping to the end of the line or document.
+pub(super) fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(index) => line_start += index + 1,
+            None => return text.len(),
+        }
+    }
+    let line_end = text[line_start..]
+        .find('\n')
+        .map(|index| line_start + index)
+        .unwrap_or(text.len());
+    let mut units = 0;
+    for (index, ch) in text[line_start..line_end].char_indices() {
+        if units >= position.character as usize {
+            return line_start + index;
+        }
+        units += ch.len_utf16();
+    }
+    line_end
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        type Query {
+            me: User
+            user(id: ID!, legacyId: Int @deprecated(reason: "Use `id`.")): User
+        }
+
+        type User {
+            id: ID!
+            name: String
+            username: String @deprecated(reason: "Use `name`.")
+            friends: [User!]!
+        }
+    "#;
+
+    fn schema() -> Valid<Schema> {
+        Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap()
+    }
+
+    #[test]
+    fn valid_operation_has_no_diagnostics() {
+        let diagnostics = diagnostics(
+            &schema(),
+            "file:///ops/me.graphql",
+            "query Me { me { id name } }",
+            &[],
+        );
+        assert_that!(diagnostics).is_empty();
+    }
+
+    #[test]
+    fn unknown_field_is_reported_in_place() {
+        let text = "query Me {\n  me {\n    id\n    nickname\n  }\n}\n";
+        let diagnostics = diagnostics(&schema(), "file:///ops/me.graphql", text, &[]);
+        assert_that!(diagnostics).has_length(1);
+        let diagnostic = &diagnostics[0];
+        assert_that!(diagnostic.severity).is_equal_to(Some(DiagnosticSeverity::ERROR));
+        assert_that!(diagnostic.message).contains("nickname");
+        assert_that!(diagnostic.range.start).is_equal_to(Position::new(3, 4));
+    }
+
+    #[test]
+    fn argument_type_mismatch_is_reported() {
+        let diagnostics = diagnostics(
+            &schema(),
+            "file:///ops/user.graphql",
+            "query User { user(id: 1.5) { id } }",
+            &[],
+        );
+        assert_that!(diagnostics).has_length(1);
+        assert_that!(diagnostics[0].severity).is_equal_to(Some(DiagnosticSeverity::ERROR));
+    }
+
+    #[test]
+    fn deprecated_field_and_argument_are_warnings() {
+        let text = "query User {\n  user(id: \"1\", legacyId: 1) {\n    handle: username\n  }\n}";
+        let diagnostics = diagnostics(&schema(), "file:///ops/user.graphql", text, &[]);
+        assert_that!(diagnostics).has_length(2);
+        for diagnostic in &diagnostics {
+            assert_that!(diagnostic.severity).is_equal_to(Some(DiagnosticSeverity::WARNING));
+            assert_that!(diagnostic.tags).is_equal_to(Some(vec![DiagnosticTag::DEPRECATED]));
+        }
+        let field = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.message.contains("username"))
+            .unwrap();
+        assert_that!(field.message).contains("Use `name`.");
+        assert_that!(field.range)
+            .is_equal_to(Range::new(Position::new(2, 12), Position::new(2, 20)));
+    }
+
+    #[test]
+    fn fragments_from_other_documents_resolve() {
+        let shared = [("UserFields", "fragment UserFields on User { id name }")];
+        let diagnostics = diagnostics(
+            &schema(),
+            "file:///ops/me.graphql",
+            "query Me { me { ...UserFields } }",
+            &shared,
+        );
+        assert_that!(diagnostics).is_empty();
+    }
+
+    #[test]
+    fn unused_fragments_are_not_reported() {
+        let diagnostics = diagnostics(
+            &schema(),
+            "file:///ops/fragments.graphql",
+            "fragment UserFields on User { id name }",
+            &[],
+        );
+        assert_that!(diagnostics).is_empty();
+    }
+
+    #[test]
+    fn syntax_errors_are_reported() {
+        let diagnostics = diagnostics(
+            &schema(),
+            "file:///ops/me.graphql",
+            "query Me { me { id }",
+            &[],
+        );
+        assert_that!(diagnostics).is_not_empty();
+    }
+
+    #[test]
+    fn positions_round_trip_through_utf16() {
+        let text = "# é😀\nquery";
+        let offset = text.find("query").unwrap();
+        let position = offset_to_position(text, offset);
+        assert_that!(position).is_equal_to(Position::new(1, 0));
+        assert_that!(position_to_offset(text, position)).is_equal_to(offset);
+        let emoji_end = text.find('\n').unwrap();
+        assert_that!(offset_to_position(text, emoji_end)).is_equal_to(Position::new(0, 5));
+    }
+}
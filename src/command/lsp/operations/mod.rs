@@ -0,0 +1,257 @@
+//! Client operation documents tracked by `rover lsp`.
+//!
+//! The Apollo Language Server only knows about the subgraph schemas in `supergraph.yaml`, so
+//! operation documents matched by `--include`/`--exclude` are handled here instead: they are
+//! validated against the API schema of the latest successful composition, and the cursor position
+//! in them is used to offer field completions.
+
+mod completion;
+mod diagnostics;
+mod service;
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use apollo_compiler::{Schema, validation::Valid};
+use apollo_federation::{ApiSchemaOptions, Supergraph};
+use apollo_language_server::ApolloLanguageServer;
+use camino::Utf8PathBuf;
+use rover_std::{FileMatcher, Fs};
+pub(crate) use service::OperationRouter;
+use tower_lsp::lsp_types::{CompletionItem, Diagnostic, Position};
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::{RoverResult, options::FileDiscoveryOpt};
+
+const OPERATION_EXTENSIONS: &[&str] = &["graphql", "gql"];
+
+#[derive(Clone)]
+pub(crate) struct OperationDocuments {
+    language_server: ApolloLanguageServer,
+    file_discovery: FileDiscoveryOpt,
+    /// `file_discovery`'s patterns, to check newly created files against.
+    matcher: Arc<FileMatcher>,
+    /// Schema files belonging to subgraphs, which are never treated as operations even when
+    /// they match the include patterns.
+    subgraph_schemas: Arc<HashSet<Utf8PathBuf>>,
+    state: Arc<Mutex<OperationState>>,
+}
+
+#[derive(Default)]
+struct OperationState {
+    api_schema: Option<Valid<Schema>>,
+    documents: BTreeMap<Url, OperationDocument>,
+}
+
+struct OperationDocument {
+    text: String,
+    version: Option<i32>,
+    /// The name and definition of each fragment `text` defines, which every other document
+    /// can spread. Kept up to date with `text` so other documents' diagnostics don't have to
+    /// re-parse it.
+    fragments: Vec<(String, String)>,
+}
+
+impl OperationDocument {
+    fn new(text: String, version: Option<i32>) -> Self {
+        let fragments = diagnostics::fragment_definitions(&text);
+        Self {
+            text,
+            version,
+            fragments,
+        }
+    }
+}
+
+impl OperationDocuments {
+    /// Loads every operation document matched by `file_discovery` from disk.
+    pub(crate) fn new(
+        language_server: ApolloLanguageServer,
+        file_discovery: FileDiscoveryOpt,
+        subgraph_schemas: HashSet<Utf8PathBuf>,
+    ) -> RoverResult<Self> {
+        let operations = Self {
+            language_server,
+            matcher: Arc::new(file_discovery.matcher(OPERATION_EXTENSIONS)?),
+            file_discovery,
+            subgraph_schemas: Arc::new(subgraph_schemas),
+            state: Arc::default(),
+        };
+        let mut state = operations.lock();
+        for path in operations.discover()? {
+            let Ok(url) = Url::from_file_path(&path) else {
+                continue;
+            };
+            match Fs::read_file(&path) {
+                Ok(text) => {
+                    state
+                        .documents
+                        .insert(url, OperationDocument::new(text, None));
+                }
+                Err(err) => warn!("Could not read operation document {path}: {err}"),
+            }
+        }
+        debug!("Tracking {} operation documents", state.documents.len());
+        drop(state);
+        Ok(operations)
+    }
+
+    /// Whether `uri` is an operation document, rather than something the Apollo Language
+    /// Server should handle.
+    pub(crate) fn is_operation(&self, uri: &Url) -> bool {
+        if self.lock().documents.contains_key(uri) {
+            return true;
+        }
+        if !OPERATION_EXTENSIONS
+            .iter()
+            .any(|extension| uri.path().ends_with(&format!(".{extension}")))
+        {
+            return false;
+        }
+        // Newly created files won't have been picked up at startup, so check them against the
+        // include patterns again.
+        let Some(path) = uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| dunce::canonicalize(path).ok())
+            .and_then(|path| Utf8PathBuf::from_path_buf(path).ok())
+        else {
+            return false;
+        };
+        self.matcher.is_match(&path) && !self.subgraph_schemas.contains(&path)
+    }
+
+    pub(crate) async fn did_open(&self, uri: Url, text: String, version: i32) {
+        self.lock()
+            .documents
+            .insert(uri, OperationDocument::new(text, Some(version)));
+        self.publish_diagnostics().await;
+    }
+
+    pub(crate) async fn did_change(&self, uri: Url, text: String, version: i32) {
+        {
+            let mut state = self.lock();
+            // Changes can be handled out of order, so never let an older one win
+            let current = state
+                .documents
+                .get(&uri)
+                .and_then(|document| document.version);
+            if current.is_some_and(|current| current >= version) {
+                return;
+            }
+            state
+                .documents
+                .insert(uri, OperationDocument::new(text, Some(version)));
+        }
+        self.publish_diagnostics().await;
+    }
+
+    /// Once the editor lets go of a document, its contents on disk become the source of truth.
+    pub(crate) async fn did_close(&self, uri: Url) {
+        let text = uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| Utf8PathBuf::from_path_buf(path).ok())
+            .and_then(|path| Fs::read_file(path).ok());
+        match text {
+            Some(text) => {
+                self.lock()
+                    .documents
+                    .insert(uri, OperationDocument::new(text, None));
+            }
+            None => {
+                self.lock().documents.remove(&uri);
+                self.language_server
+                    .publish_diagnostics(uri, Vec::new())
+                    .await;
+            }
+        }
+        self.publish_diagnostics().await;
+    }
+
+    pub(crate) fn completion(&self, uri: &Url, position: Position) -> Vec<CompletionItem> {
+        let state = self.lock();
+        match (&state.api_schema, state.documents.get(uri)) {
+            (Some(schema), Some(document)) => {
+                completion::completions(schema, &document.text, position)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Called with the supergraph SDL of each successful composition. Operations keep being
+    /// validated against the previous API schema if this one can't be derived.
+    pub(crate) async fn schema_did_update(&self, supergraph_sdl: &str) {
+        let api_schema = Supergraph::new(supergraph_sdl).and_then(|supergraph| {
+            // The router serves `@defer` by default, so clients are free to use it
+            supergraph.to_api_schema(ApiSchemaOptions {
+                include_defer: true,
+                include_stream: false,
+            })
+        });
+        match api_schema {
+            Ok(api_schema) => self.lock().api_schema = Some(api_schema.schema().clone()),
+            Err(err) => {
+                warn!("Could not derive an API schema from the composed supergraph: {err}");
+                return;
+            }
+        }
+        self.publish_diagnostics().await;
+    }
+
+    /// Re-validates every tracked document, as a change to one of them can affect the others
+    /// through shared fragments.
+    async fn publish_diagnostics(&self) {
+        let published: Vec<(Url, Vec<Diagnostic>)> = {
+            let state = self.lock();
+            let Some(schema) = &state.api_schema else {
+                return;
+            };
+            state
+                .documents
+                .iter()
+                .map(|(uri, document)| {
+                    let shared_fragments: Vec<(&str, &str)> = state
+                        .documents
+                        .iter()
+                        .filter(|(other, _)| *other != uri)
+                        .flat_map(|(_, other)| &other.fragments)
+                        .map(|(name, definition)| (name.as_str(), definition.as_str()))
+                        .collect();
+                    (
+                        uri.clone(),
+                        diagnostics::diagnostics(
+                            schema,
+                            uri.as_str(),
+                            &document.text,
+                            &shared_fragments,
+                        ),
+                    )
+                })
+                .collect()
+        };
+        for (uri, diagnostics) in published {
+            self.language_server
+                .publish_diagnostics(uri, diagnostics)
+                .await;
+        }
+    }
+
+    fn discover(&self) -> RoverResult<Vec<Utf8PathBuf>> {
+        Ok(self
+            .file_discovery
+            .find(OPERATION_EXTENSIONS)?
+            .into_iter()
+            .filter(|path| !self.subgraph_schemas.contains(path))
+            .collect())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, OperationState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,129 @@
+use std::task::{Context, Poll};
+
+use futures::{FutureExt, future::BoxFuture};
+use serde::de::DeserializeOwned;
+use tower::Service;
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::{
+        CompletionParams, CompletionResponse, DidChangeTextDocumentParams,
+        DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+        notification::{
+            DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification,
+        },
+        request::{Completion, Request as _},
+    },
+};
+use url::Url;
+
+use super::OperationDocuments;
+
+/// Routes requests about operation documents to [`OperationDocuments`], and everything else to
+/// the Apollo Language Server.
+///
+/// Operation documents must never reach the language server, as it would treat them as subgraph
+/// schemas.
+pub(crate) struct OperationRouter<S> {
+    inner: S,
+    operations: OperationDocuments,
+}
+
+impl<S> OperationRouter<S> {
+    pub(crate) const fn new(inner: S, operations: OperationDocuments) -> Self {
+        Self { inner, operations }
+    }
+}
+
+impl<S> Service<Request> for OperationRouter<S>
+where
+    S: Service<Request, Response = Option<Response>>,
+    S::Future: Send + 'static,
+{
+    type Response = Option<Response>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let Some(operation_request) = OperationRequest::parse(&request)
+            .filter(|operation_request| self.operations.is_operation(operation_request.uri()))
+        else {
+            return self.inner.call(request).boxed();
+        };
+
+        let operations = self.operations.clone();
+        let id = request.id().cloned();
+        async move {
+            match operation_request {
+                OperationRequest::DidOpen(params) => {
+                    let document = params.text_document;
+                    operations
+                        .did_open(document.uri, document.text, document.version)
+                        .await;
+                    Ok(None)
+                }
+                OperationRequest::DidChange(mut params) => {
+                    // Documents are synced in full, so the last change holds the whole text
+                    if let Some(change) = params.content_changes.pop() {
+                        operations
+                            .did_change(
+                                params.text_document.uri,
+                                change.text,
+                                params.text_document.version,
+                            )
+                            .await;
+                    }
+                    Ok(None)
+                }
+                OperationRequest::DidClose(params) => {
+                    operations.did_close(params.text_document.uri).await;
+                    Ok(None)
+                }
+                OperationRequest::Completion(params) => {
+                    let position = params.text_document_position;
+                    let items =
+                        operations.completion(&position.text_document.uri, position.position);
+                    let result =
+                        serde_json::to_value(CompletionResponse::Array(items)).unwrap_or_default();
+                    Ok(id.map(|id| Response::from_ok(id, result)))
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+enum OperationRequest {
+    DidOpen(DidOpenTextDocumentParams),
+    DidChange(DidChangeTextDocumentParams),
+    DidClose(DidCloseTextDocumentParams),
+    Completion(CompletionParams),
+}
+
+impl OperationRequest {
+    fn parse(request: &Request) -> Option<Self> {
+        match request.method() {
+            DidOpenTextDocument::METHOD => params(request).map(Self::DidOpen),
+            DidChangeTextDocument::METHOD => params(request).map(Self::DidChange),
+            DidCloseTextDocument::METHOD => params(request).map(Self::DidClose),
+            Completion::METHOD => params(request).map(Self::Completion),
+            _ => None,
+        }
+    }
+
+    const fn uri(&self) -> &Url {
+        match self {
+            Self::DidOpen(params) => &params.text_document.uri,
+            Self::DidChange(params) => &params.text_document.uri,
+            Self::DidClose(params) => &params.text_document.uri,
+            Self::Completion(params) => &params.text_document_position.text_document.uri,
+        }
+    }
+}
+
+fn params<T: DeserializeOwned>(request: &Request) -> Option<T> {
+    serde_json::from_value(request.params()?.clone()).ok()
+}
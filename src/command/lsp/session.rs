@@ -0,0 +1,585 @@
+//! A single editor connection, multiplexed over one language server per workspace folder.
+
+use std::{
+    collections::HashMap,
+    ops::ControlFlow,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::{SinkExt, future::join_all};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::{
+    io::{AsyncBufRead, AsyncWrite},
+    sync::mpsc,
+};
+use tower_lsp::{
+    jsonrpc::{Error, ErrorCode, Id, Request, Response},
+    lsp_types::{
+        DidChangeWorkspaceFoldersParams, InitializeParams, MessageType, ShowMessageParams, Url,
+        WorkspaceFolder,
+        notification::{DidChangeWorkspaceFolders, Exit, Initialized, Notification, ShowMessage},
+        request::{Initialize, Request as _, Shutdown},
+    },
+};
+use tracing::{debug, warn};
+
+use super::{
+    LspServer,
+    transport::{read_message, write_message},
+    workspace::{Workspace, WorkspaceRoot, workspace_roots},
+};
+
+/// Serves one editor connection until it exits or disconnects.
+pub(super) async fn serve<R, W>(server: Arc<LspServer>, mut reader: R, mut writer: W)
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let (messages, mut outgoing) = mpsc::unbounded_channel::<Value>();
+    let writer_task = tokio::spawn(async move {
+        while let Some(message) = outgoing.recv().await {
+            if let Err(err) = write_message(&mut writer, &message).await {
+                warn!("Could not write to the editor: {err}");
+                break;
+            }
+        }
+    });
+
+    let mut session = Session {
+        server,
+        client: ClientChannel {
+            messages,
+            pending: Arc::default(),
+        },
+        workspaces: Vec::new(),
+        next_workspace_id: 0,
+        initialize_params: None,
+    };
+    loop {
+        match read_message(&mut reader).await {
+            Ok(Some(message)) => {
+                if session.handle(message).await.is_break() {
+                    break;
+                }
+            }
+            Ok(None) => break,
+            Err(err) => {
+                warn!("Could not read from the editor: {err}");
+                break;
+            }
+        }
+    }
+
+    // Stop every language server, then give the last responses a moment to reach the editor
+    drop(session);
+    tokio::time::timeout(Duration::from_secs(1), writer_task)
+        .await
+        .ok();
+}
+
+/// Sends messages to the editor on behalf of every workspace in a session.
+#[derive(Clone)]
+pub(super) struct ClientChannel {
+    messages: mpsc::UnboundedSender<Value>,
+    pending: Arc<Mutex<PendingRequests>>,
+}
+
+/// Requests the language servers made of the editor, by the ID they were given in the session.
+/// Each language server numbers its requests from zero, so they can't be passed on as they are.
+#[derive(Default)]
+struct PendingRequests {
+    next_id: i64,
+    by_session_id: HashMap<i64, (u64, Id)>,
+}
+
+impl ClientChannel {
+    fn send(&self, message: impl Serialize) {
+        match serde_json::to_value(message) {
+            Ok(message) => {
+                self.messages.send(message).ok();
+            }
+            Err(err) => warn!("Could not serialize a message for the editor: {err}"),
+        }
+    }
+
+    /// Passes on a request or notification the language server of `workspace` made of the
+    /// editor.
+    pub(super) fn forward_request(&self, workspace: u64, request: Request) {
+        let (method, id, params) = request.into_parts();
+        let mut builder = Request::build(method);
+        if let Some(id) = id {
+            let mut pending = self
+                .pending
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let session_id = pending.next_id;
+            pending.next_id += 1;
+            pending.by_session_id.insert(session_id, (workspace, id));
+            builder = builder.id(session_id);
+        }
+        if let Some(params) = params {
+            builder = builder.params(params);
+        }
+        self.send(builder.finish());
+    }
+
+    /// Finds the workspace, and its own ID, for a response from the editor.
+    fn take_pending(&self, id: &Id) -> Option<(u64, Id)> {
+        let Id::Number(session_id) = id else {
+            return None;
+        };
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .by_session_id
+            .remove(session_id)
+    }
+
+    fn show_message(&self, typ: MessageType, message: String) {
+        self.send(
+            Request::build(ShowMessage::METHOD)
+                .params(
+                    serde_json::to_value(ShowMessageParams { typ, message }).unwrap_or_default(),
+                )
+                .finish(),
+        );
+    }
+}
+
+struct Session {
+    server: Arc<LspServer>,
+    client: ClientChannel,
+    workspaces: Vec<Workspace>,
+    next_workspace_id: u64,
+    /// Kept to initialize the language servers of workspace folders added later on.
+    initialize_params: Option<InitializeParams>,
+}
+
+impl Session {
+    async fn handle(&mut self, message: Value) -> ControlFlow<()> {
+        if message.get("method").is_none() {
+            // A response to a request one of the language servers made of the editor
+            match serde_json::from_value::<Response>(message) {
+                Ok(response) => self.respond_to_workspace(response).await,
+                Err(err) => warn!("Ignoring malformed message from the editor: {err}"),
+            }
+            return ControlFlow::Continue(());
+        }
+        let request = match serde_json::from_value::<Request>(message) {
+            Ok(request) => request,
+            Err(err) => {
+                warn!("Ignoring malformed message from the editor: {err}");
+                return ControlFlow::Continue(());
+            }
+        };
+
+        match request.method() {
+            Initialize::METHOD => self.initialize(request).await,
+            Shutdown::METHOD => self.shutdown(request).await,
+            Exit::METHOD => {
+                self.broadcast(request).await;
+                return ControlFlow::Break(());
+            }
+            DidChangeWorkspaceFolders::METHOD => self.change_workspace_folders(request).await,
+            _ => match (document_uri(&request), request.id()) {
+                // Notifications that aren't about a single document concern every workspace
+                (None, None) => self.broadcast(request).await,
+                (uri, _) => match self.route(uri.as_ref()) {
+                    Some(index) => self.dispatch(index, request).await,
+                    None => {
+                        if let Some(id) = request.id().cloned() {
+                            self.client
+                                .send(Response::from_error(id, not_initialized()));
+                        }
+                    }
+                },
+            },
+        }
+        ControlFlow::Continue(())
+    }
+
+    async fn initialize(&mut self, request: Request) {
+        let Some(id) = request.id().cloned() else {
+            return;
+        };
+        let params: InitializeParams = match request
+            .params()
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+        {
+            Ok(params) => params.unwrap_or_default(),
+            Err(err) => {
+                self.client.send(Response::from_error(
+                    id,
+                    Error::invalid_params(err.to_string()),
+                ));
+                return;
+            }
+        };
+
+        let folders = workspace_folders(&params);
+        let (roots, errors) = workspace_roots(&self.server, &folders).await;
+        for error in errors {
+            self.client.show_message(MessageType::ERROR, error);
+        }
+        let mut response = None;
+        for root in roots {
+            let Some(index) = self.add_workspace(root) else {
+                continue;
+            };
+            let initialized = self.initialize_workspace(index, &params, id.clone()).await;
+            response = response.or(initialized);
+        }
+        self.initialize_params = Some(params);
+        match response {
+            Some(response) => self.client.send(response),
+            None => self
+                .client
+                .send(Response::from_error(id, Error::internal_error())),
+        }
+    }
+
+    /// Initializes the language server of a workspace with the editor's parameters, narrowed to
+    /// the workspace's own folder, then starts feeding it composition updates.
+    async fn initialize_workspace(
+        &mut self,
+        index: usize,
+        params: &InitializeParams,
+        id: Id,
+    ) -> Option<Response> {
+        let workspace = &mut self.workspaces[index];
+        let mut params = params.clone();
+        if let Some(folder) = &workspace.folder {
+            #[allow(deprecated)]
+            {
+                params.root_uri = Some(folder.clone());
+            }
+            params.workspace_folders = Some(vec![WorkspaceFolder {
+                uri: folder.clone(),
+                name: folder
+                    .path_segments()
+                    .and_then(|mut segments| segments.next_back().map(String::from))
+                    .unwrap_or_default(),
+            }]);
+        }
+        let request = Request::build(Initialize::METHOD)
+            .id(id)
+            .params(serde_json::to_value(params).unwrap_or_default())
+            .finish();
+        let response = workspace.call(request).await?.await.ok().flatten();
+        workspace.start();
+        response
+    }
+
+    fn add_workspace(&mut self, root: WorkspaceRoot) -> Option<usize> {
+        let id = self.next_workspace_id;
+        self.next_workspace_id += 1;
+        match Workspace::new(id, &self.server, root, self.client.clone()) {
+            Ok(workspace) => {
+                self.workspaces.push(workspace);
+                Some(self.workspaces.len() - 1)
+            }
+            Err(err) => {
+                self.client.show_message(
+                    MessageType::ERROR,
+                    format!("Could not start the language server: {err}"),
+                );
+                None
+            }
+        }
+    }
+
+    async fn shutdown(&mut self, request: Request) {
+        let Some(id) = request.id().cloned() else {
+            return;
+        };
+        let mut calls = Vec::new();
+        for workspace in &mut self.workspaces {
+            if let Some(call) = workspace.call(request.clone()).await {
+                calls.push(call);
+            }
+        }
+        join_all(calls).await;
+        self.client.send(Response::from_ok(id, Value::Null));
+    }
+
+    async fn change_workspace_folders(&mut self, request: Request) {
+        let Some(params) = request.params().cloned().and_then(|params| {
+            serde_json::from_value::<DidChangeWorkspaceFoldersParams>(params).ok()
+        }) else {
+            return;
+        };
+        let removed: Vec<Url> = params
+            .event
+            .removed
+            .into_iter()
+            .map(|folder| folder.uri)
+            .collect();
+        self.workspaces.retain(|workspace| {
+            workspace
+                .folder
+                .as_ref()
+                .is_none_or(|folder| !removed.contains(folder))
+        });
+
+        let Some(initialize_params) = self.initialize_params.clone() else {
+            return;
+        };
+        let added: Vec<Url> = params
+            .event
+            .added
+            .into_iter()
+            .map(|folder| folder.uri)
+            .collect();
+        let (roots, errors) = workspace_roots(&self.server, &added).await;
+        for error in errors {
+            self.client.show_message(MessageType::ERROR, error);
+        }
+        for root in roots {
+            // There only ever needs to be one workspace for the documents no other one claims
+            if root.folder.is_none()
+                && self
+                    .workspaces
+                    .iter()
+                    .any(|workspace| workspace.folder.is_none())
+            {
+                continue;
+            }
+            let Some(index) = self.add_workspace(root) else {
+                continue;
+            };
+            // The editor isn't waiting on these, so the IDs just need to be unique within the
+            // workspace's own language server
+            self.initialize_workspace(index, &initialize_params, Id::Number(0))
+                .await;
+            let initialized = Request::build(Initialized::METHOD)
+                .params(serde_json::json!({}))
+                .finish();
+            self.dispatch(index, initialized).await;
+        }
+    }
+
+    /// Picks the workspace for a message about the document at `uri`: the one with the most
+    /// specific folder containing it, or else the one for unclaimed documents. Messages about no
+    /// document in particular go to the first workspace.
+    fn route(&self, uri: Option<&Url>) -> Option<usize> {
+        let claimed = uri.and_then(|uri| {
+            self.workspaces
+                .iter()
+                .enumerate()
+                .filter_map(|(index, workspace)| Some((index, workspace.claims(uri)?)))
+                .max_by_key(|(_, specificity)| *specificity)
+                .map(|(index, _)| index)
+        });
+        claimed
+            .or_else(|| {
+                self.workspaces
+                    .iter()
+                    .position(|workspace| workspace.folder.is_none())
+            })
+            .or_else(|| (!self.workspaces.is_empty()).then_some(0))
+    }
+
+    /// Sends `request` to a workspace without waiting for it to be handled, passing any response
+    /// on to the editor.
+    async fn dispatch(&mut self, index: usize, request: Request) {
+        let Some(call) = self.workspaces[index].call(request).await else {
+            return;
+        };
+        let client = self.client.clone();
+        tokio::spawn(async move {
+            if let Ok(Some(response)) = call.await {
+                client.send(response);
+            }
+        });
+    }
+
+    async fn broadcast(&mut self, request: Request) {
+        for index in 0..self.workspaces.len() {
+            self.dispatch(index, request.clone()).await;
+        }
+    }
+
+    async fn respond_to_workspace(&mut self, response: Response) {
+        let Some((workspace_id, id)) = self.client.take_pending(response.id()) else {
+            debug!("Ignoring a response to an unknown request");
+            return;
+        };
+        let Some(workspace) = self
+            .workspaces
+            .iter_mut()
+            .find(|workspace| workspace.id == workspace_id)
+        else {
+            return;
+        };
+        let (_, body) = response.into_parts();
+        if let Err(err) = workspace
+            .responses
+            .send(Response::from_parts(id, body))
+            .await
+        {
+            debug!("Language server went away before its request was answered: {err}");
+        }
+    }
+}
+
+/// The folders an editor opened, falling back to its root for editors without workspace folders.
+fn workspace_folders(params: &InitializeParams) -> Vec<Url> {
+    match &params.workspace_folders {
+        Some(folders) if !folders.is_empty() => {
+            folders.iter().map(|folder| folder.uri.clone()).collect()
+        }
+        #[allow(deprecated)]
+        _ => params.root_uri.iter().cloned().collect(),
+    }
+}
+
+fn document_uri(request: &Request) -> Option<Url> {
+    let uri = request
+        .params()?
+        .get("textDocument")?
+        .get("uri")?
+        .as_str()?;
+    Url::parse(uri).ok()
+}
+
+fn not_initialized() -> Error {
+    Error {
+        code: ErrorCode::ServerError(-32002),
+        message: "Server not initialized".into(),
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use camino::Utf8PathBuf;
+    use clap::Parser;
+    use houston::Config;
+    use serde_json::json;
+    use speculoos::prelude::*;
+    use tokio::io::{BufReader, duplex, split};
+
+    use super::*;
+    use crate::{
+        command::lsp::{LspOpts, composition::Compositions},
+        utils::client::{ClientBuilder, ClientTimeout, StudioClientConfig},
+    };
+
+    fn server(home: &TempDir) -> Arc<LspServer> {
+        let config = Config {
+            home: Utf8PathBuf::from_path_buf(home.path().to_path_buf()).unwrap(),
+            override_api_key: None,
+            override_client_credentials_token: None,
+        };
+        let client_config = StudioClientConfig::new(
+            None,
+            config,
+            false,
+            ClientBuilder::default(),
+            ClientTimeout::default(),
+        );
+        let lsp_opts = LspOpts::parse_from(["lsp"]);
+        Arc::new(LspServer {
+            compositions: Compositions::new(client_config.clone(), lsp_opts.clone()),
+            client_config,
+            lsp_opts,
+            supergraph_yaml_path: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn serves_a_session_without_a_supergraph_config() {
+        let home = TempDir::new().unwrap();
+        let folder = TempDir::new().unwrap();
+        let folder = Url::from_directory_path(folder.path()).unwrap();
+        let (editor, rover) = duplex(64 * 1024);
+        let (rover_reader, rover_writer) = split(rover);
+        let session = serve(server(&home), BufReader::new(rover_reader), rover_writer);
+
+        let editor = async move {
+            let (editor_reader, mut editor_writer) = split(editor);
+            let mut editor_reader = BufReader::new(editor_reader);
+            let mut next_response = async |id: i64| loop {
+                let message = read_message(&mut editor_reader).await.unwrap().unwrap();
+                if message.get("id") == Some(&json!(id)) {
+                    break message;
+                }
+            };
+
+            // Requests before initialization are rejected, not dropped
+            write_message(
+                &mut editor_writer,
+                &json!({"jsonrpc": "2.0", "id": 0, "method": "textDocument/hover", "params": {
+                    "textDocument": {"uri": folder.join("schema.graphql").unwrap()},
+                    "position": {"line": 0, "character": 0},
+                }}),
+            )
+            .await
+            .unwrap();
+            let response = next_response(0).await;
+            assert_that!(response["error"]["code"]).is_equal_to(json!(-32002));
+
+            write_message(
+                &mut editor_writer,
+                &json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {
+                    "capabilities": {},
+                    "workspaceFolders": [{"uri": folder, "name": "folder"}],
+                }}),
+            )
+            .await
+            .unwrap();
+            let response = next_response(1).await;
+            assert_that!(response["result"]["capabilities"].is_object()).is_true();
+
+            write_message(
+                &mut editor_writer,
+                &json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"}),
+            )
+            .await
+            .unwrap();
+            let response = next_response(2).await;
+            assert_that!(response["result"]).is_equal_to(Value::Null);
+
+            write_message(
+                &mut editor_writer,
+                &json!({"jsonrpc": "2.0", "method": "exit"}),
+            )
+            .await
+            .unwrap();
+        };
+
+        tokio::time::timeout(
+            Duration::from_secs(30),
+            futures::future::join(session, editor),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn workspace_folders_fall_back_to_the_root() {
+        let root = Url::parse("file:///work/graph/").unwrap();
+        #[allow(deprecated)]
+        let params = InitializeParams {
+            root_uri: Some(root.clone()),
+            workspace_folders: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert_that!(workspace_folders(&params)).is_equal_to(vec![root]);
+
+        let folder = Url::parse("file:///work/other/").unwrap();
+        let params = InitializeParams {
+            workspace_folders: Some(vec![WorkspaceFolder {
+                uri: folder.clone(),
+                name: "other".to_string(),
+            }]),
+            ..params
+        };
+        assert_that!(workspace_folders(&params)).is_equal_to(vec![folder]);
+    }
+}
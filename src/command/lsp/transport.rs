@@ -0,0 +1,262 @@
+use std::{fmt, io, net::SocketAddr, str::FromStr, sync::Arc, time::Duration};
+
+#[cfg(unix)]
+use camino::Utf8PathBuf;
+use rover_std::infoln;
+use serde_json::Value;
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    task::LocalSet,
+};
+use tracing::warn;
+
+use super::{LspServer, session};
+
+/// The largest message body an editor may send. Anything bigger ends the session rather than
+/// being buffered.
+const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+/// The longest header line an editor may send.
+const MAX_HEADER_LINE: u64 = 8 * 1024;
+/// How long to wait before accepting again after a connection couldn't be accepted, e.g.
+/// because the process has run out of file descriptors.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Where `rover lsp --listen` accepts connections from editors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ListenAddr {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(Utf8PathBuf),
+}
+
+impl FromStr for ListenAddr {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("unix:") {
+            #[cfg(unix)]
+            return Ok(ListenAddr::Unix(Utf8PathBuf::from(path)));
+            #[cfg(not(unix))]
+            return Err(anyhow::anyhow!(
+                "Unix domain sockets are not supported on this platform: '{path}'"
+            ));
+        }
+        s.parse().map(ListenAddr::Tcp).map_err(|_| {
+            anyhow::anyhow!(
+                "'{s}' is not a valid address. Use a TCP address such as '127.0.0.1:9257', or 'unix:<path>' for a Unix domain socket"
+            )
+        })
+    }
+}
+
+impl fmt::Display for ListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListenAddr::Tcp(addr) => write!(f, "{addr}"),
+            #[cfg(unix)]
+            ListenAddr::Unix(path) => write!(f, "unix:{path}"),
+        }
+    }
+}
+
+/// Accepts connections on `addr` until the process is stopped, serving each one as a separate
+/// session backed by the same `server`.
+pub(super) async fn listen(server: Arc<LspServer>, addr: &ListenAddr) -> io::Result<()> {
+    // Starting a composition isn't `Send`, and any session may need to start one, so sessions
+    // all run on this task rather than being spread across the runtime.
+    LocalSet::new()
+        .run_until(async move {
+            match addr {
+                ListenAddr::Tcp(addr) => {
+                    let listener = tokio::net::TcpListener::bind(addr).await?;
+                    infoln!("Language server listening on {}", listener.local_addr()?);
+                    loop {
+                        let (stream, peer) = match listener.accept().await {
+                            Ok(accepted) => accepted,
+                            Err(err) => {
+                                warn!("Could not accept a language server connection: {err}");
+                                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                                continue;
+                            }
+                        };
+                        tracing::info!("Accepted language server connection from {peer}");
+                        let (reader, writer) = stream.into_split();
+                        tokio::task::spawn_local(session::serve(
+                            server.clone(),
+                            BufReader::new(reader),
+                            writer,
+                        ));
+                    }
+                }
+                #[cfg(unix)]
+                ListenAddr::Unix(path) => {
+                    remove_stale_socket(path)?;
+                    let listener = tokio::net::UnixListener::bind(path)?;
+                    infoln!("Language server listening on unix:{path}");
+                    loop {
+                        let stream = match listener.accept().await {
+                            Ok((stream, _)) => stream,
+                            Err(err) => {
+                                warn!("Could not accept a language server connection: {err}");
+                                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                                continue;
+                            }
+                        };
+                        tracing::info!("Accepted language server connection on unix:{path}");
+                        let (reader, writer) = stream.into_split();
+                        tokio::task::spawn_local(session::serve(
+                            server.clone(),
+                            BufReader::new(reader),
+                            writer,
+                        ));
+                    }
+                }
+            }
+        })
+        .await
+}
+
+/// A socket left behind by a previous `rover lsp --listen` would otherwise make binding fail.
+#[cfg(unix)]
+fn remove_stale_socket(path: &Utf8PathBuf) -> io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+/// Reads the next `Content-Length` framed JSON-RPC message, or `None` once the stream ends.
+///
+/// Badly framed or oversized messages are an error, which ends the session they arrive on.
+pub(super) async fn read_message<R>(reader: &mut R) -> io::Result<Option<Value>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if (&mut *reader)
+            .take(MAX_HEADER_LINE)
+            .read_line(&mut line)
+            .await?
+            == 0
+        {
+            return Ok(None);
+        }
+        if !line.ends_with('\n') && line.len() as u64 == MAX_HEADER_LINE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("header line is longer than {MAX_HEADER_LINE} bytes"),
+            ));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            // Tolerate stray blank lines between messages
+            continue;
+        }
+        match line.split_once(':') {
+            Some((name, value)) if name.eq_ignore_ascii_case("content-length") => {
+                content_length =
+                    Some(value.trim().parse::<usize>().map_err(|err| {
+                        io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+                    })?);
+            }
+            Some(_) => {}
+            None => warn!("Ignoring malformed language server header: {line}"),
+        }
+    }
+
+    let content_length = content_length.unwrap_or_default();
+    if content_length > MAX_MESSAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "message of {content_length} bytes is larger than the {MAX_MESSAGE_SIZE} byte limit"
+            ),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Writes `message` with the `Content-Length` header that the language server protocol expects.
+pub(super) async fn write_message<W>(writer: &mut W, message: &Value) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let body = serde_json::to_vec(message)?;
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(&body).await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case::tcp("127.0.0.1:9257", ListenAddr::Tcp("127.0.0.1:9257".parse().unwrap()))]
+    #[case::ipv6("[::1]:9257", ListenAddr::Tcp("[::1]:9257".parse().unwrap()))]
+    #[cfg_attr(unix, case::unix("unix:/tmp/rover.sock", ListenAddr::Unix("/tmp/rover.sock".into())))]
+    fn parses_listen_addresses(#[case] input: &str, #[case] expected: ListenAddr) {
+        let parsed = ListenAddr::from_str(input).unwrap();
+        assert_that!(parsed).is_equal_to(&expected);
+        assert_that!(parsed.to_string()).is_equal_to(input.to_string());
+    }
+
+    #[test]
+    fn rejects_addresses_without_a_port() {
+        assert_that!(ListenAddr::from_str("localhost")).is_err();
+    }
+
+    #[tokio::test]
+    async fn messages_round_trip_through_framing() {
+        let first = json!({"jsonrpc": "2.0", "method": "initialized", "params": {}});
+        let second = json!({"jsonrpc": "2.0", "id": 1, "result": "é"});
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &first).await.unwrap();
+        write_message(&mut buffer, &second).await.unwrap();
+
+        let mut reader = BufReader::new(buffer.as_slice());
+        assert_that!(read_message(&mut reader).await.unwrap()).is_equal_to(Some(first));
+        assert_that!(read_message(&mut reader).await.unwrap()).is_equal_to(Some(second));
+        assert_that!(read_message(&mut reader).await.unwrap()).is_none();
+    }
+
+    #[tokio::test]
+    async fn oversized_messages_are_rejected_without_being_read() {
+        let input = format!("Content-Length: {}\r\n\r\n{{}}", MAX_MESSAGE_SIZE + 1);
+        let mut reader = BufReader::new(input.as_bytes());
+        let err = read_message(&mut reader).await.unwrap_err();
+        assert_that!(err.kind()).is_equal_to(io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn overlong_header_lines_are_rejected() {
+        let input = "X".repeat(MAX_HEADER_LINE as usize * 2);
+        let mut reader = BufReader::new(input.as_bytes());
+        let err = read_message(&mut reader).await.unwrap_err();
+        assert_that!(err.kind()).is_equal_to(io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn extra_headers_are_ignored() {
+        let input = "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\nContent-Length: 2\r\n\r\n{}";
+        let mut reader = BufReader::new(input.as_bytes());
+        assert_that!(read_message(&mut reader).await.unwrap()).is_equal_to(Some(json!({})));
+    }
+}
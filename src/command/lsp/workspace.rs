@@ -0,0 +1,290 @@
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+use apollo_language_server::{ApolloLanguageServer, Config, MaxSpecVersions};
+use camino::Utf8PathBuf;
+use futures::{FutureExt, StreamExt, channel::oneshot, future::BoxFuture, stream::SplitSink};
+use tokio::task::AbortHandle;
+use tower::{Service, ServiceExt, util::BoxService};
+use tower_lsp::{
+    ClientSocket, ExitedError,
+    jsonrpc::{Request, Response},
+    lsp_types::Url,
+};
+use tracing::warn;
+
+use super::{
+    LspServer,
+    composition::{SharedComposition, drive_language_server},
+    load_spec_for_path,
+    operations::{OperationDocuments, OperationRouter},
+    session::ClientChannel,
+};
+use crate::RoverResult;
+
+/// The names a supergraph config is looked for under, at the top of each workspace folder.
+const SUPERGRAPH_CONFIG_FILE_NAMES: &[&str] = &["supergraph.yaml", "supergraph.yml"];
+
+/// A workspace a session needs a language server for.
+pub(super) struct WorkspaceRoot {
+    /// Documents under this folder are routed to the workspace. `None` for the workspace that
+    /// takes every document no other workspace claims.
+    pub(super) folder: Option<Url>,
+    pub(super) composition: Option<Arc<SharedComposition>>,
+}
+
+/// Works out the workspaces needed for the `folders` an editor opened. Alongside them, returns a
+/// message for every supergraph config that couldn't be started, so it can be shown to the user.
+pub(super) async fn workspace_roots(
+    server: &LspServer,
+    folders: &[Url],
+) -> (Vec<WorkspaceRoot>, Vec<String>) {
+    // A supergraph config given on the command line applies to every document
+    if let Some(supergraph_yaml_path) = &server.supergraph_yaml_path {
+        return match server.compositions.get_or_start(supergraph_yaml_path).await {
+            Ok(composition) => (
+                vec![WorkspaceRoot {
+                    folder: None,
+                    composition: Some(composition),
+                }],
+                Vec::new(),
+            ),
+            Err(err) => (
+                vec![WorkspaceRoot {
+                    folder: None,
+                    composition: None,
+                }],
+                vec![format!("Could not start composition: {err}")],
+            ),
+        };
+    }
+
+    let mut roots = Vec::new();
+    let mut errors = Vec::new();
+    let mut needs_fallback = folders.is_empty();
+    for folder in folders {
+        let Some(supergraph_yaml_path) = find_supergraph_config(folder) else {
+            needs_fallback = true;
+            continue;
+        };
+        match server
+            .compositions
+            .get_or_start(&supergraph_yaml_path)
+            .await
+        {
+            Ok(composition) => roots.push(WorkspaceRoot {
+                folder: Some(folder.clone()),
+                composition: Some(composition),
+            }),
+            Err(err) => {
+                errors.push(format!(
+                    "Could not start composition for {supergraph_yaml_path}: {err}"
+                ));
+                needs_fallback = true;
+            }
+        }
+    }
+    if needs_fallback {
+        roots.push(WorkspaceRoot {
+            folder: None,
+            composition: None,
+        });
+    }
+    (roots, errors)
+}
+
+fn find_supergraph_config(folder: &Url) -> Option<Utf8PathBuf> {
+    let folder = Utf8PathBuf::from_path_buf(folder.to_file_path().ok()?).ok()?;
+    SUPERGRAPH_CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| folder.join(name))
+        .find(|path| path.is_file())
+}
+
+/// One Apollo Language Server within a session.
+pub(super) struct Workspace {
+    pub(super) id: u64,
+    pub(super) folder: Option<Url>,
+    pub(super) service: BoxService<Request, Option<Response>, ExitedError>,
+    /// Where the editor's responses to the language server's own requests are delivered.
+    pub(super) responses: SplitSink<ClientSocket, Response>,
+    /// Feeds composition updates to the language server, once it has been initialized.
+    driver: Option<BoxFuture<'static, ()>>,
+    tasks: Vec<AbortHandle>,
+}
+
+impl Workspace {
+    pub(super) fn new(
+        id: u64,
+        server: &LspServer,
+        root: WorkspaceRoot,
+        client: ClientChannel,
+    ) -> RoverResult<Self> {
+        let mut tasks = Vec::new();
+        let WorkspaceRoot {
+            folder,
+            composition,
+        } = root;
+
+        let config = Config {
+            root_uri: composition
+                .as_ref()
+                .map(|composition| composition.supergraph_yaml_url().clone())
+                .or_else(|| folder.clone())
+                .map(String::from)
+                .unwrap_or_default(),
+            enable_auto_composition: false,
+            // Ensure that we force_federation here, otherwise, if we have a broken
+            // supergraph.yaml and thus end up detecting no subgraphs, the LSP doesn't
+            // think we're doing this for a monograph.
+            force_federation: composition.is_some(),
+            disable_telemetry: false,
+            max_spec_versions: MaxSpecVersions {
+                connect: None,
+                federation: None,
+            },
+        };
+
+        let (service, socket, driver) = match composition {
+            None => {
+                let (service, socket, _receiver) =
+                    ApolloLanguageServer::build_service(config, HashMap::new(), None);
+                (BoxService::new(service), socket, None)
+            }
+            Some(composition) => {
+                let (spec_lookup_sender, mut spec_lookup_receiver) =
+                    futures::channel::mpsc::channel::<(PathBuf, oneshot::Sender<Option<String>>)>(
+                        100,
+                    );
+                let lookup_client_config = server.client_config.clone();
+                let lookup_supergraph_yaml_path = composition.supergraph_yaml_path().clone();
+                let lookup_plugin_opts = server.lsp_opts.plugin_opts.clone();
+                tasks.push(
+                    tokio::spawn(async move {
+                        while let Some((path, response)) = spec_lookup_receiver.next().await {
+                            let spec = load_spec_for_path(
+                                path,
+                                lookup_client_config.clone(),
+                                lookup_supergraph_yaml_path.clone(),
+                                lookup_plugin_opts.clone(),
+                            )
+                            .await;
+                            response.send(spec).ok();
+                        }
+                    })
+                    .abort_handle(),
+                );
+
+                let (initial_subgraphs, catch_up, updates) = composition.subscribe();
+                let (service, socket, _receiver) = ApolloLanguageServer::build_service(
+                    config,
+                    initial_subgraphs,
+                    Some(spec_lookup_sender),
+                );
+                let operations = server
+                    .operation_discovery(folder.as_ref())
+                    .map(|discovery| {
+                        OperationDocuments::new(
+                            service.inner().to_owned(),
+                            discovery,
+                            composition.subgraph_schema_files(),
+                        )
+                    })
+                    .transpose()?;
+                let driver = drive_language_server(
+                    service.inner().to_owned(),
+                    composition.supergraph_yaml_url().clone(),
+                    catch_up,
+                    updates,
+                    operations.clone(),
+                )
+                .boxed();
+                let service = match operations {
+                    Some(operations) => BoxService::new(OperationRouter::new(service, operations)),
+                    None => BoxService::new(service),
+                };
+                (service, socket, Some(driver))
+            }
+        };
+
+        // Anything the language server sends to the editor goes through the session
+        let (responses, mut requests) = StreamExt::split(socket);
+        tasks.push(
+            tokio::spawn(async move {
+                while let Some(request) = requests.next().await {
+                    client.forward_request(id, request);
+                }
+            })
+            .abort_handle(),
+        );
+
+        Ok(Self {
+            id,
+            folder,
+            service,
+            responses,
+            driver,
+            tasks,
+        })
+    }
+
+    /// Starts passing composition updates to the language server. Its client only delivers
+    /// messages once the server has been initialized, so this mustn't happen any earlier.
+    pub(super) fn start(&mut self) {
+        if let Some(driver) = self.driver.take() {
+            self.tasks.push(tokio::spawn(driver).abort_handle());
+        }
+    }
+
+    /// Sends `request` to the language server, returning the future of its response.
+    pub(super) async fn call(
+        &mut self,
+        request: Request,
+    ) -> Option<BoxFuture<'static, Result<Option<Response>, ExitedError>>> {
+        match self.service.ready().await {
+            Ok(service) => Some(service.call(request)),
+            Err(err) => {
+                warn!("Language server is no longer running: {err}");
+                None
+            }
+        }
+    }
+
+    /// Whether this workspace is responsible for the document at `uri`, and if so, how
+    /// specifically: a nested folder wins over the folder containing it.
+    pub(super) fn claims(&self, uri: &Url) -> Option<usize> {
+        let folder = self.folder.as_ref()?;
+        let prefix = folder.as_str().trim_end_matches('/');
+        let rest = uri.as_str().strip_prefix(prefix)?;
+        (rest.is_empty() || rest.starts_with('/')).then_some(prefix.len())
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::{TempDir, prelude::*};
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn finds_supergraph_configs_at_the_top_of_a_folder() {
+        let folder = TempDir::new().unwrap();
+        let url = Url::from_directory_path(folder.path()).unwrap();
+        assert_that!(find_supergraph_config(&url)).is_none();
+
+        folder.child("nested/supergraph.yaml").touch().unwrap();
+        assert_that!(find_supergraph_config(&url)).is_none();
+
+        folder.child("supergraph.yml").touch().unwrap();
+        let found = find_supergraph_config(&url).unwrap();
+        assert_that!(found.file_name()).is_equal_to(Some("supergraph.yml"));
+    }
+}
@@ -15,17 +15,23 @@ mod graph_artifact;
 mod info;
 pub(crate) mod init;
 pub(crate) mod install;
+mod launch;
 mod license;
 #[cfg(feature = "composition-js")]
 mod lsp;
+mod operation;
+mod operation_collection;
 pub(crate) mod output;
 mod persisted_queries;
+mod proposal;
 mod readme;
 mod schema;
 pub(crate) mod subgraph;
 pub(crate) mod supergraph;
+mod telemetry;
 pub(crate) mod template;
 mod update;
+mod variant;
 
 pub use api_key::ApiKeys;
 #[cfg(feature = "oauth")]
@@ -44,14 +50,20 @@ pub use graph_artifact::GraphArtifact;
 pub use info::Info;
 pub use init::Init;
 pub use install::Install;
+pub use launch::Launch;
 pub use license::License;
 #[cfg(feature = "composition-js")]
 pub use lsp::Lsp;
+pub use operation::Operation;
+pub use operation_collection::OperationCollection;
 pub use output::{CliOutput, RoverOutput};
 pub use persisted_queries::PersistedQueries;
+pub use proposal::Proposal;
 pub use readme::Readme;
 pub use schema::Schema;
 pub use subgraph::Subgraph;
 pub use supergraph::Supergraph;
+pub use telemetry::Telemetry;
 pub use template::Template;
 pub use update::Update;
+pub use variant::Variant;
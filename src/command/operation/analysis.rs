@@ -0,0 +1,454 @@
+use apollo_compiler::{
+    ExecutableDocument, Name, Node, Schema, ast,
+    executable::{Field, Operation, OperationType, Selection, SelectionSet},
+    schema::ExtendedType,
+    validation::Valid,
+};
+use camino::Utf8PathBuf;
+use serde::Serialize;
+
+/// The weight of a mutation's root fields, when they have no `@cost` of their own.
+const MUTATION_ROOT_FIELD_COST: u64 = 10;
+
+/// Costs used wherever the schema doesn't say otherwise.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct CostDefaults {
+    /// The size assumed for lists without `@listSize`, or whose size can't be worked out
+    pub(super) list_size: u64,
+    /// The weight of fields returning an object, interface or union without `@cost`. Fields
+    /// returning scalars and enums weigh nothing unless `@cost` says otherwise.
+    pub(super) object_cost: u64,
+}
+
+/// What an operation would cost to execute.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct OperationAnalysis {
+    pub(super) name: String,
+    #[serde(rename = "type")]
+    pub(super) operation_type: &'static str,
+    pub(super) file: Option<Utf8PathBuf>,
+    /// How deeply fields are nested, with root fields at depth 1
+    pub(super) depth: usize,
+    pub(super) aliased_fields: usize,
+    pub(super) estimated_cost: u64,
+    pub(super) list_multipliers: Vec<ListMultiplier>,
+}
+
+/// A field whose cost is multiplied by the size of the list it returns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct ListMultiplier {
+    /// The response keys leading to the field, separated by `.`
+    pub(super) path: String,
+    pub(super) size: u64,
+    pub(super) source: ListSizeSource,
+}
+
+/// Where the size of a list came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "name")]
+pub(super) enum ListSizeSource {
+    /// A slicing argument named by `@listSize`
+    SlicingArgument(String),
+    /// `@listSize(assumedSize:)`
+    AssumedSize,
+    /// `--default-list-size`
+    Default,
+}
+
+/// Analyzes every operation in `document`.
+pub(super) fn analyze(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    defaults: CostDefaults,
+) -> Vec<OperationAnalysis> {
+    document
+        .operations
+        .iter()
+        .map(|operation| {
+            let mut analyzer = Analyzer {
+                schema,
+                document,
+                operation,
+                defaults,
+                aliased_fields: 0,
+                list_multipliers: Vec::new(),
+            };
+            let (estimated_cost, depth) =
+                analyzer.selection_set(&operation.selection_set, "", 0, None, &mut Vec::new());
+            let file = operation
+                .location()
+                .and_then(|location| document.sources.get(&location.file_id()))
+                .and_then(|source| Utf8PathBuf::from_path_buf(source.path().to_path_buf()).ok());
+            OperationAnalysis {
+                name: operation
+                    .name
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_else(|| "<anonymous>".to_string()),
+                operation_type: operation_type_name(operation.operation_type),
+                file,
+                depth,
+                aliased_fields: analyzer.aliased_fields,
+                estimated_cost,
+                list_multipliers: analyzer.list_multipliers,
+            }
+        })
+        .collect()
+}
+
+const fn operation_type_name(operation_type: OperationType) -> &'static str {
+    match operation_type {
+        OperationType::Query => "query",
+        OperationType::Mutation => "mutation",
+        OperationType::Subscription => "subscription",
+    }
+}
+
+/// A list size that `@listSize(sizedFields:)` hands down from a field to some of its children.
+#[derive(Clone)]
+struct SizedFields {
+    names: Vec<String>,
+    size: u64,
+    source: ListSizeSource,
+}
+
+struct Analyzer<'a> {
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    operation: &'a Operation,
+    defaults: CostDefaults,
+    aliased_fields: usize,
+    list_multipliers: Vec<ListMultiplier>,
+}
+
+impl Analyzer<'_> {
+    /// Returns the cost of the selections, and the depth of the deepest field among them.
+    fn selection_set(
+        &mut self,
+        selection_set: &SelectionSet,
+        path: &str,
+        depth: usize,
+        sized_fields: Option<&SizedFields>,
+        fragments: &mut Vec<Name>,
+    ) -> (u64, usize) {
+        let mut cost = 0u64;
+        let mut max_depth = depth;
+        for selection in &selection_set.selections {
+            let (selection_cost, selection_depth) = match selection {
+                Selection::Field(field) => {
+                    self.field(field, path, depth + 1, sized_fields, fragments)
+                }
+                Selection::InlineFragment(inline) => {
+                    self.selection_set(&inline.selection_set, path, depth, sized_fields, fragments)
+                }
+                Selection::FragmentSpread(spread) => {
+                    let Some(fragment) = self.document.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    if fragments.contains(&spread.fragment_name) {
+                        continue;
+                    }
+                    fragments.push(spread.fragment_name.clone());
+                    let result = self.selection_set(
+                        &fragment.selection_set,
+                        path,
+                        depth,
+                        sized_fields,
+                        fragments,
+                    );
+                    fragments.pop();
+                    result
+                }
+            };
+            // Every type condition is counted, so this is an upper bound for abstract types
+            cost = cost.saturating_add(selection_cost);
+            max_depth = max_depth.max(selection_depth);
+        }
+        (cost, max_depth)
+    }
+
+    fn field(
+        &mut self,
+        field: &Field,
+        parent_path: &str,
+        depth: usize,
+        sized_fields: Option<&SizedFields>,
+        fragments: &mut Vec<Name>,
+    ) -> (u64, usize) {
+        if field.alias.is_some() {
+            self.aliased_fields += 1;
+        }
+        let path = if parent_path.is_empty() {
+            field.response_key().to_string()
+        } else {
+            format!("{parent_path}.{}", field.response_key())
+        };
+        let definition = &field.definition;
+        let type_name = definition.ty.inner_named_type();
+        let extended_type = self.schema.types.get(type_name);
+
+        let weight = cost_weight(definition.directives.iter().map(|d| &**d))
+            .or_else(|| {
+                extended_type.and_then(|ty| cost_weight(ty.directives().iter().map(|d| &*d.node)))
+            })
+            .unwrap_or_else(|| {
+                if depth == 1 && self.operation.operation_type == OperationType::Mutation {
+                    MUTATION_ROOT_FIELD_COST
+                } else if extended_type.is_some_and(is_composite) {
+                    self.defaults.object_cost
+                } else {
+                    0
+                }
+            });
+        let arguments_weight = definition
+            .arguments
+            .iter()
+            .filter(|argument| field.specified_argument_by_name(&argument.name).is_some())
+            .filter_map(|argument| cost_weight(argument.directives.iter().map(|d| &**d)))
+            .fold(0u64, u64::saturating_add);
+
+        let list_size = directive(definition.directives.iter().map(|d| &**d), "listSize");
+        let size = list_size.map(|list_size| self.list_size(field, list_size));
+        let handed_down = list_size
+            .and_then(|list_size| string_list_argument(list_size, "sizedFields"))
+            .filter(|names| !names.is_empty());
+        let multiplier = match (&handed_down, sized_fields) {
+            // The size belongs to some of this field's children, rather than to the field
+            (Some(_), _) => None,
+            (None, Some(sized)) if sized.names.iter().any(|name| name == field.name.as_str()) => {
+                Some((sized.size, sized.source.clone()))
+            }
+            (None, _) if definition.ty.is_list() => Some(
+                size.clone()
+                    .unwrap_or((self.defaults.list_size, ListSizeSource::Default)),
+            ),
+            (None, _) => None,
+        };
+        let child_sized_fields = handed_down.map(|names| {
+            let (size, source) = size.unwrap_or((self.defaults.list_size, ListSizeSource::Default));
+            SizedFields {
+                names,
+                size,
+                source,
+            }
+        });
+
+        let instances = match multiplier {
+            Some((size, source)) => {
+                self.list_multipliers.push(ListMultiplier {
+                    path: path.clone(),
+                    size,
+                    source,
+                });
+                size
+            }
+            None => 1,
+        };
+        let (children_cost, children_depth) = self.selection_set(
+            &field.selection_set,
+            &path,
+            depth,
+            child_sized_fields.as_ref(),
+            fragments,
+        );
+        let cost = instances
+            .saturating_mul(weight.saturating_add(children_cost))
+            .saturating_add(arguments_weight);
+        (cost, children_depth)
+    }
+
+    /// Works out a list's size from `@listSize`: the largest slicing argument given, or else the
+    /// assumed size, or else the default.
+    fn list_size(&self, field: &Field, list_size: &ast::Directive) -> (u64, ListSizeSource) {
+        let slicing_arguments = string_list_argument(list_size, "slicingArguments");
+        let sliced = slicing_arguments
+            .iter()
+            .flatten()
+            .filter_map(|name| {
+                let value = field.argument_by_name(name).ok()?;
+                Some((
+                    self.int_value(value)?,
+                    ListSizeSource::SlicingArgument(name.clone()),
+                ))
+            })
+            .max_by_key(|(size, _)| *size);
+        sliced
+            .or_else(|| {
+                list_size
+                    .specified_argument_by_name("assumedSize")
+                    .and_then(|value| value.to_i32())
+                    .and_then(|size| u64::try_from(size).ok())
+                    .map(|size| (size, ListSizeSource::AssumedSize))
+            })
+            .unwrap_or((self.defaults.list_size, ListSizeSource::Default))
+    }
+
+    /// An integer argument's value, looking through variables to their default values.
+    fn int_value(&self, value: &Node<ast::Value>) -> Option<u64> {
+        match value.as_ref() {
+            ast::Value::Variable(name) => self
+                .operation
+                .variables
+                .iter()
+                .find(|variable| &variable.name == name)?
+                .default_value
+                .as_ref()
+                .and_then(|value| self.int_value(value)),
+            value => value.to_i32().and_then(|size| u64::try_from(size).ok()),
+        }
+    }
+}
+
+const fn is_composite(ty: &ExtendedType) -> bool {
+    matches!(
+        ty,
+        ExtendedType::Object(_) | ExtendedType::Interface(_) | ExtendedType::Union(_)
+    )
+}
+
+/// Finds a directive by name, including when it was renamed by the `@link` that imported it,
+/// such as `@federation__cost` in a supergraph.
+fn directive<'a>(
+    directives: impl IntoIterator<Item = &'a ast::Directive>,
+    name: &str,
+) -> Option<&'a ast::Directive> {
+    directives.into_iter().find(|directive| {
+        directive.name == name
+            || directive
+                .name
+                .strip_suffix(name)
+                .is_some_and(|prefix| prefix.ends_with("__"))
+    })
+}
+
+fn cost_weight<'a>(directives: impl IntoIterator<Item = &'a ast::Directive>) -> Option<u64> {
+    directive(directives, "cost")?
+        .specified_argument_by_name("weight")?
+        .to_i32()
+        .and_then(|weight| u64::try_from(weight).ok())
+}
+
+fn string_list_argument(directive: &ast::Directive, name: &str) -> Option<Vec<String>> {
+    match directive.specified_argument_by_name(name)?.as_ref() {
+        ast::Value::List(values) => Some(
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(String::from))
+                .collect(),
+        ),
+        ast::Value::String(value) => Some(vec![value.to_string()]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const SCHEMA: &str = indoc! {r#"
+        directive @cost(weight: Int!) on ARGUMENT_DEFINITION | ENUM | FIELD_DEFINITION | INPUT_FIELD_DEFINITION | OBJECT | SCALAR
+        directive @listSize(assumedSize: Int, slicingArguments: [String!], sizedFields: [String!], requireOneSlicingArgument: Boolean = true) on FIELD_DEFINITION
+
+        type Query {
+          products(first: Int = 5): [Product] @listSize(slicingArguments: ["first"])
+          search(term: String @cost(weight: 3)): SearchConnection @listSize(assumedSize: 20, sizedFields: ["items"])
+          bestSeller: Product
+        }
+
+        type Mutation {
+          addReview(body: String): Review
+        }
+
+        type SearchConnection {
+          items: [Product]
+          total: Int
+        }
+
+        type Product @cost(weight: 2) {
+          id: ID!
+          name: String
+          reviews: [Review]
+          expensive: String @cost(weight: 7)
+        }
+
+        type Review {
+          body: String
+        }
+    "#};
+
+    fn analyze_one(operation: &str) -> OperationAnalysis {
+        let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+        let document =
+            ExecutableDocument::parse_and_validate(&schema, operation, "ops.graphql").unwrap();
+        let defaults = CostDefaults {
+            list_size: 10,
+            object_cost: 1,
+        };
+        analyze(&schema, &document, defaults).remove(0)
+    }
+
+    #[test]
+    fn lists_are_sized_by_slicing_arguments_and_defaults() {
+        let analysis = analyze_one(
+            "query Products($n: Int = 3) { products(first: $n) { name reviews { body } } }",
+        );
+        // 3 products, each weighing 2 plus 10 reviews weighing 1
+        assert_that!(analysis.estimated_cost).is_equal_to(36);
+        assert_that!(analysis.depth).is_equal_to(3);
+        assert_that!(analysis.file).is_equal_to(Some(Utf8PathBuf::from("ops.graphql")));
+        assert_that!(analysis.list_multipliers).is_equal_to(vec![
+            ListMultiplier {
+                path: "products".to_string(),
+                size: 3,
+                source: ListSizeSource::SlicingArgument("first".to_string()),
+            },
+            ListMultiplier {
+                path: "products.reviews".to_string(),
+                size: 10,
+                source: ListSizeSource::Default,
+            },
+        ]);
+
+        // The argument's default in the schema counts when it isn't given
+        let analysis = analyze_one("query Products { products { id } }");
+        assert_that!(analysis.estimated_cost).is_equal_to(10);
+    }
+
+    #[test]
+    fn sized_fields_argument_costs_and_aliases_are_counted() {
+        let analysis = analyze_one(indoc! {r#"
+            query Search {
+              a: search(term: "x") { items { expensive } total }
+              b: bestSeller { id }
+            }
+        "#});
+        // search: 1 + its term argument's 3 + 20 items of 2 + 7; bestSeller: 2
+        assert_that!(analysis.estimated_cost).is_equal_to(186);
+        assert_that!(analysis.aliased_fields).is_equal_to(2);
+        assert_that!(analysis.list_multipliers).is_equal_to(vec![ListMultiplier {
+            path: "a.items".to_string(),
+            size: 20,
+            source: ListSizeSource::AssumedSize,
+        }]);
+    }
+
+    #[test]
+    fn fragments_count_towards_depth_and_cost() {
+        let analysis = analyze_one(indoc! {"
+            query Best { bestSeller { ...ProductFields } }
+            fragment ProductFields on Product { reviews { ... on Review { body } } }
+        "});
+        assert_that!(analysis.depth).is_equal_to(3);
+        assert_that!(analysis.estimated_cost).is_equal_to(12);
+    }
+
+    #[test]
+    fn mutation_root_fields_weigh_more() {
+        let analysis = analyze_one(r#"mutation AddReview { addReview(body: "Great") { body } }"#);
+        assert_that!(analysis.operation_type).is_equal_to("mutation");
+        assert_that!(analysis.estimated_cost).is_equal_to(MUTATION_ROOT_FIELD_COST);
+    }
+}
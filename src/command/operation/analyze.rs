@@ -0,0 +1,166 @@
+use std::io::stdin;
+
+use anyhow::anyhow;
+use apollo_compiler::{
+    ExecutableDocument, Schema, ast,
+    validation::{DiagnosticList, WithErrors},
+};
+use clap::Parser;
+use itertools::Itertools;
+use rover_std::{Fs, warnln};
+use serde::Serialize;
+
+use super::{
+    analysis::{CostDefaults, OperationAnalysis, analyze},
+    output::OperationAnalyzeOutput,
+};
+use crate::{
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult, options::FileDiscoveryOpt,
+    utils::parsers::FileDescriptorType,
+};
+
+#[derive(Debug, Serialize, Parser)]
+/// Report the depth, aliases and estimated cost of operations against a schema
+///
+/// The cost of each field is its `@cost` weight, or the weight of the type it returns. Without
+/// `@cost`, fields returning objects, interfaces or unions weigh `--default-object-cost`, root
+/// mutation fields weigh 10, and other fields weigh nothing. Fields returning lists multiply the
+/// cost of their selections by the list's size. The size comes from `@listSize`, using the
+/// largest of its slicing arguments or else its assumed size, or is `--default-list-size`.
+///
+/// Every fragment on an abstract type is counted, so costs are an upper bound.
+#[command(after_help = "EXAMPLES:\n    \
+    rover operation analyze --schema supergraph.graphql --include 'src/**/*.graphql'\n    \
+    rover operation analyze --schema schema.graphql --max-depth 8 --max-cost 5000")]
+pub struct Analyze {
+    /// The schema to analyze operations against, such as an API or supergraph schema. You can
+    /// pass `-` to use stdin instead of a file.
+    #[arg(long, short = 's', value_name = "SCHEMA_FILE")]
+    #[serde(skip_serializing)]
+    schema: FileDescriptorType,
+
+    #[clap(flatten)]
+    #[serde(flatten)]
+    file_discovery: FileDiscoveryOpt,
+
+    /// The size assumed for lists without `@listSize`
+    #[arg(long, value_name = "SIZE", default_value_t = 10)]
+    default_list_size: u64,
+
+    /// The weight of fields returning an object, interface or union without `@cost`
+    #[arg(long, value_name = "WEIGHT", default_value_t = 1)]
+    default_object_cost: u64,
+
+    /// Fail if any operation nests fields more deeply than this
+    #[arg(long, value_name = "DEPTH")]
+    max_depth: Option<usize>,
+
+    /// Fail if any operation's estimated cost is higher than this
+    #[arg(long, value_name = "COST")]
+    max_cost: Option<u64>,
+
+    /// Fail if any operation has more aliased fields than this
+    #[arg(long, value_name = "COUNT")]
+    max_aliases: Option<usize>,
+}
+
+impl Analyze {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        let sdl = self.schema.read_file_descriptor("SDL", &mut stdin())?;
+        let schema = Schema::parse_and_validate(sdl, self.schema.to_string())
+            .map_err(|WithErrors { errors, .. }| anyhow!("The schema is not valid:\n{errors}"))?;
+
+        let mut errors = DiagnosticList::new(Default::default());
+        let mut builder = ExecutableDocument::builder(Some(&schema), &mut errors);
+        let schema_path = match &self.schema {
+            FileDescriptorType::File(path) => dunce::canonicalize(path).ok(),
+            FileDescriptorType::Stdin => None,
+        };
+        for path in self.file_discovery.find(&["graphql"])? {
+            if schema_path.as_deref() == Some(path.as_std_path()) {
+                continue;
+            }
+            let text = Fs::read_file(&path)?;
+            // Schema files are often found alongside operations
+            if ast::Document::parse(&text, &path).is_ok_and(|document| {
+                document
+                    .definitions
+                    .iter()
+                    .any(|definition| !definition.is_executable_definition())
+            }) {
+                warnln!("Skipping {path}, as it contains schema definitions");
+                continue;
+            }
+            builder = builder.parse(text, path);
+        }
+        let document = builder.build();
+        if !errors.is_empty() {
+            return Err(anyhow!("Failed to parse the operations:\n{errors}").into());
+        }
+        let document = document
+            .validate(&schema)
+            .map_err(|WithErrors { errors, .. }| {
+                anyhow!("The operations are not valid against the schema:\n{errors}")
+            })?;
+        if document.operations.is_empty() {
+            return Err(anyhow!("No operations found under the provided includes").into());
+        }
+
+        let operations = analyze(
+            &schema,
+            &document,
+            CostDefaults {
+                list_size: self.default_list_size,
+                object_cost: self.default_object_cost,
+            },
+        );
+        let violations = operations
+            .iter()
+            .flat_map(|operation| self.violations(operation))
+            .collect::<Vec<_>>();
+        if !violations.is_empty() {
+            return Err(RoverError::new(anyhow!(
+                "{} budget violation(s):\n  {}",
+                violations.len(),
+                violations.iter().join("\n  ")
+            ))
+            .with_suggestion(RoverErrorSuggestion::Adhoc(
+                "Run `rover operation analyze` without budgets to see the full report for each operation."
+                    .to_string(),
+            )));
+        }
+        Ok(RoverOutput::CliOutput(Box::new(OperationAnalyzeOutput {
+            operations,
+        })))
+    }
+
+    fn violations(&self, operation: &OperationAnalysis) -> Vec<String> {
+        let name = &operation.name;
+        let mut violations = Vec::new();
+        if let Some(max) = self.max_depth
+            && operation.depth > max
+        {
+            violations.push(format!(
+                "{name} has a depth of {}, more than --max-depth {max}",
+                operation.depth
+            ));
+        }
+        if let Some(max) = self.max_cost
+            && operation.estimated_cost > max
+        {
+            violations.push(format!(
+                "{name} has an estimated cost of {}, more than --max-cost {max}",
+                operation.estimated_cost
+            ));
+        }
+        if let Some(max) = self.max_aliases
+            && operation.aliased_fields > max
+        {
+            violations.push(format!(
+                "{name} has {} aliased field(s), more than --max-aliases {max}",
+                operation.aliased_fields
+            ));
+        }
+        violations
+    }
+}
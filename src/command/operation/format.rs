@@ -0,0 +1,114 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+use itertools::Itertools;
+use rover_std::{Fs, warnln};
+use serde::Serialize;
+
+use super::{
+    formatter::{FormatOptions, OperationFile, ParseFailure, used_fragments},
+    output::{OperationFormatOutput, SkippedFile},
+};
+use crate::{
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult, options::FileDiscoveryOpt,
+};
+
+#[derive(Debug, Serialize, Parser)]
+/// Rewrite .graphql operation files in Rover's canonical format
+///
+/// Operations and fragments are printed the same way `rover persisted-queries generate` prints
+/// them before hashing, so formatting a file never changes the persisted query IDs of its
+/// operations, unless `--sort` or `--remove-unused-fragments` is also given.
+///
+/// Comments between definitions are kept with the definition that follows them. Files with
+/// comments inside an operation or fragment, and files with schema definitions, are skipped.
+#[command(after_help = "EXAMPLES:\n    \
+    rover operation format --include 'src/**/*.graphql'\n    \
+    rover operation format --check\n    \
+    rover operation format --sort --remove-unused-fragments")]
+pub struct Format {
+    #[clap(flatten)]
+    #[serde(flatten)]
+    file_discovery: FileDiscoveryOpt,
+
+    /// Report the files that aren't formatted without changing them, failing if there are any
+    #[arg(long)]
+    check: bool,
+
+    /// Sort the fields and fragment spreads in every selection set by name, and the definitions
+    /// in each file by name with operations first. This changes persisted query IDs, and the
+    /// order of fields in responses.
+    #[arg(long)]
+    sort: bool,
+
+    /// Remove fragments that no operation in any of the files uses, directly or through other
+    /// fragments
+    #[arg(long)]
+    remove_unused_fragments: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum OperationFormatError {
+    #[error("Failed to parse {} .graphql file(s):\n{}", .parse_failures.len(), .parse_failures.iter().join("\n"))]
+    ParseFailures { parse_failures: Vec<ParseFailure> },
+    #[error("No .graphql files found under the provided includes")]
+    NoFiles,
+    #[error("{} file(s) are not formatted:\n  {}", .files.len(), .files.iter().join("\n  "))]
+    Unformatted { files: Vec<Utf8PathBuf> },
+}
+
+impl Format {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        let paths = self.file_discovery.find(&["graphql"])?;
+        if paths.is_empty() {
+            return Err(OperationFormatError::NoFiles.into());
+        }
+        let (files, parse_failures): (Vec<_>, Vec<_>) = paths
+            .into_iter()
+            .map(|path| {
+                let text = Fs::read_file(&path).map_err(|err| ParseFailure {
+                    file: path.clone(),
+                    message: err.to_string(),
+                })?;
+                OperationFile::parse(path, text)
+            })
+            .partition_result();
+        if !parse_failures.is_empty() {
+            return Err(OperationFormatError::ParseFailures { parse_failures }.into());
+        }
+
+        let used = self.remove_unused_fragments.then(|| used_fragments(&files));
+        let options = FormatOptions { sort: self.sort };
+        let mut output = OperationFormatOutput {
+            check: self.check,
+            ..Default::default()
+        };
+        for file in &files {
+            match file.format(options, used.as_ref()) {
+                Ok(formatted) if formatted == file.text => output.unchanged += 1,
+                Ok(formatted) => {
+                    if !self.check {
+                        Fs::write_file(&file.path, formatted)?;
+                    }
+                    output.changed.push(file.path.clone());
+                }
+                Err(reason) => {
+                    warnln!("Skipping {}, as {reason}", file.path);
+                    output.skipped.push(SkippedFile {
+                        file: file.path.clone(),
+                        reason: reason.to_string(),
+                    });
+                }
+            }
+        }
+
+        if self.check && !output.changed.is_empty() {
+            return Err(RoverError::new(OperationFormatError::Unformatted {
+                files: output.changed,
+            })
+            .with_suggestion(RoverErrorSuggestion::Adhoc(
+                "Run `rover operation format` without `--check` to format them.".to_string(),
+            )));
+        }
+        Ok(RoverOutput::CliOutput(Box::new(output)))
+    }
+}
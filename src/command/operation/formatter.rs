@@ -0,0 +1,355 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+use apollo_compiler::{ast, parser::Parser as ApolloParser};
+use apollo_parser::{Lexer, TokenKind};
+use camino::Utf8PathBuf;
+
+use crate::command::persisted_queries::PrintableDefinition;
+
+/// An operation document read from disk, ready to be formatted.
+pub(super) struct OperationFile {
+    pub(super) path: Utf8PathBuf,
+    pub(super) text: String,
+    document: ast::Document,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("{file}: {message}")]
+pub(super) struct ParseFailure {
+    pub(super) file: Utf8PathBuf,
+    pub(super) message: String,
+}
+
+/// Why a file was left as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub(super) enum SkipReason {
+    #[error("it contains schema definitions, not just operations and fragments")]
+    NotAnOperationDocument,
+    #[error("it has comments inside an operation or fragment, which formatting would remove")]
+    CommentsInsideDefinitions,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct FormatOptions {
+    /// Sort selections by name, and definitions by name with operations first
+    pub(super) sort: bool,
+}
+
+impl OperationFile {
+    pub(super) fn parse(path: Utf8PathBuf, text: String) -> Result<Self, ParseFailure> {
+        let document = ApolloParser::new()
+            .parse_ast(&text, path.as_std_path())
+            .map_err(|err| ParseFailure {
+                file: path.clone(),
+                message: err.to_string(),
+            })?;
+        Ok(Self {
+            path,
+            text,
+            document,
+        })
+    }
+
+    /// Prints the document the way persisted query manifests do. Comments between definitions
+    /// move along with the definition that follows them. Fragments not in `used_fragments` are
+    /// removed, when it's given.
+    pub(super) fn format(
+        &self,
+        options: FormatOptions,
+        used_fragments: Option<&BTreeSet<String>>,
+    ) -> Result<String, SkipReason> {
+        let mut blocks = Vec::new();
+        for definition in &self.document.definitions {
+            let (key, printable) = match definition {
+                ast::Definition::OperationDefinition(operation) => {
+                    let mut operation = operation.clone();
+                    if options.sort {
+                        sort_selections(&mut operation.make_mut().selection_set);
+                    }
+                    let name = operation
+                        .name
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default();
+                    ((0, name), PrintableDefinition::Operation(operation))
+                }
+                ast::Definition::FragmentDefinition(fragment) => {
+                    let mut fragment = fragment.clone();
+                    if options.sort {
+                        sort_selections(&mut fragment.make_mut().selection_set);
+                    }
+                    let name = fragment.name.to_string();
+                    ((1, name), PrintableDefinition::Fragment(fragment))
+                }
+                _ => return Err(SkipReason::NotAnOperationDocument),
+            };
+            let span = definition
+                .location()
+                .map(|location| location.offset()..location.end_offset())
+                .unwrap_or_default();
+            blocks.push(Block {
+                key,
+                span,
+                comments: Vec::new(),
+                printable,
+            });
+        }
+
+        let mut trailing_comments = Vec::new();
+        for (offset, comment) in comments(&self.text) {
+            if blocks.iter().any(|block| block.span.contains(&offset)) {
+                return Err(SkipReason::CommentsInsideDefinitions);
+            }
+            match blocks.iter_mut().find(|block| block.span.start > offset) {
+                Some(block) => block.comments.push(comment),
+                None => trailing_comments.push(comment),
+            }
+        }
+
+        // Comments before an unused fragment describe it, so they go along with it
+        blocks.retain(|block| match (&block.printable, used_fragments) {
+            (PrintableDefinition::Fragment(fragment), Some(used)) => {
+                used.contains(fragment.name.as_str())
+            }
+            _ => true,
+        });
+        if options.sort {
+            blocks.sort_by(|a, b| a.key.cmp(&b.key));
+        }
+
+        let mut printed = blocks
+            .iter()
+            .map(|block| {
+                block
+                    .comments
+                    .iter()
+                    .map(|comment| format!("{comment}\n"))
+                    .chain(std::iter::once(block.printable.to_string()))
+                    .collect::<String>()
+            })
+            .chain((!trailing_comments.is_empty()).then(|| trailing_comments.join("\n")))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        if !printed.is_empty() {
+            printed.push('\n');
+        }
+        Ok(printed)
+    }
+}
+
+struct Block<'a> {
+    key: (u8, String),
+    span: Range<usize>,
+    comments: Vec<&'a str>,
+    printable: PrintableDefinition,
+}
+
+/// Every comment in `text`, with the offset it starts at.
+fn comments(text: &str) -> Vec<(usize, &str)> {
+    Lexer::new(text)
+        .filter_map(Result::ok)
+        .filter(|token| token.kind() == TokenKind::Comment)
+        .map(|token| (token.index(), token.data().trim_end()))
+        .collect()
+}
+
+/// Sorts fields by response name, then fragment spreads by fragment name, then inline fragments
+/// by type condition, all the way down.
+fn sort_selections(selections: &mut [ast::Selection]) {
+    for selection in selections.iter_mut() {
+        match selection {
+            ast::Selection::Field(field) => sort_selections(&mut field.make_mut().selection_set),
+            ast::Selection::InlineFragment(inline) => {
+                sort_selections(&mut inline.make_mut().selection_set)
+            }
+            ast::Selection::FragmentSpread(_) => {}
+        }
+    }
+    selections.sort_by_cached_key(|selection| match selection {
+        ast::Selection::Field(field) => (0, field.response_name().to_string()),
+        ast::Selection::FragmentSpread(spread) => (1, spread.fragment_name.to_string()),
+        ast::Selection::InlineFragment(inline) => (
+            2,
+            inline
+                .type_condition
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        ),
+    });
+}
+
+/// The fragments any operation in `files` uses, directly or through other fragments. Spreads
+/// marked `@client` count too, as the fragment is still needed by the client.
+pub(super) fn used_fragments(files: &[OperationFile]) -> BTreeSet<String> {
+    let mut fragment_spreads = BTreeMap::new();
+    let mut queue = Vec::new();
+    for definition in files.iter().flat_map(|file| &file.document.definitions) {
+        match definition {
+            ast::Definition::OperationDefinition(operation) => {
+                collect_spreads(&operation.selection_set, &mut queue);
+            }
+            ast::Definition::FragmentDefinition(fragment) => {
+                let mut spreads = Vec::new();
+                collect_spreads(&fragment.selection_set, &mut spreads);
+                fragment_spreads.insert(fragment.name.to_string(), spreads);
+            }
+            _ => {}
+        }
+    }
+
+    let mut used = BTreeSet::new();
+    while let Some(name) = queue.pop() {
+        if let Some(spreads) = fragment_spreads.get(&name)
+            && !used.contains(&name)
+        {
+            queue.extend(spreads.iter().cloned());
+        }
+        used.insert(name);
+    }
+    used
+}
+
+fn collect_spreads(selections: &[ast::Selection], spreads: &mut Vec<String>) {
+    for selection in selections {
+        match selection {
+            ast::Selection::Field(field) => collect_spreads(&field.selection_set, spreads),
+            ast::Selection::FragmentSpread(spread) => {
+                spreads.push(spread.fragment_name.to_string())
+            }
+            ast::Selection::InlineFragment(inline) => {
+                collect_spreads(&inline.selection_set, spreads)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn file(name: &str, text: &str) -> OperationFile {
+        OperationFile::parse(Utf8PathBuf::from(name), text.to_string()).unwrap()
+    }
+
+    fn format(text: &str, options: FormatOptions) -> Result<String, SkipReason> {
+        file("ops.graphql", text).format(options, None)
+    }
+
+    #[test]
+    fn prints_like_persisted_query_manifests_and_keeps_comments_between_definitions() {
+        let formatted = format(
+            indoc! {"
+                # Loads a product
+                query GetProduct($id: ID!) { product(id: $id) { name, ...ProductFields } }
+                fragment ProductFields on Product { sku }
+                # The end
+            "},
+            FormatOptions::default(),
+        )
+        .unwrap();
+        assert_that!(formatted.as_str()).is_equal_to(indoc! {"
+            # Loads a product
+            query GetProduct($id: ID!) {
+              product(id: $id) {
+                name
+                ...ProductFields
+              }
+            }
+
+            fragment ProductFields on Product {
+              sku
+            }
+
+            # The end
+        "});
+        assert_that!(format(&formatted, FormatOptions::default()))
+            .is_ok()
+            .is_equal_to(formatted);
+    }
+
+    #[test]
+    fn files_that_formatting_would_lose_information_from_are_skipped() {
+        assert_that!(format(
+            "query GetProduct {\n  # Why\n  product\n}\n",
+            FormatOptions::default()
+        ))
+        .is_err()
+        .is_equal_to(SkipReason::CommentsInsideDefinitions);
+        assert_that!(format(
+            "type Query { product: String }",
+            FormatOptions::default()
+        ))
+        .is_err()
+        .is_equal_to(SkipReason::NotAnOperationDocument);
+    }
+
+    #[test]
+    fn sorting_orders_selections_and_definitions_by_name() {
+        let formatted = format(
+            indoc! {"
+                fragment B on Product { sku }
+                query Zed { b: product { ... on Book { pages } name ...B id } a }
+                query Alpha { a }
+            "},
+            FormatOptions { sort: true },
+        )
+        .unwrap();
+        assert_that!(formatted.as_str()).is_equal_to(indoc! {"
+            query Alpha {
+              a
+            }
+
+            query Zed {
+              a
+              b: product {
+                id
+                name
+                ...B
+                ... on Book {
+                  pages
+                }
+              }
+            }
+
+            fragment B on Product {
+              sku
+            }
+        "});
+    }
+
+    #[test]
+    fn unused_fragments_are_found_across_files() {
+        let files = [
+            file(
+                "query.graphql",
+                "query GetProduct { product { ...Direct ...Local @client } }",
+            ),
+            file(
+                "fragments.graphql",
+                indoc! {"
+                    fragment Direct on Product { ...Nested }
+                    fragment Nested on Product { sku }
+                    fragment Local on Product { inCart }
+                    # Nothing uses this
+                    fragment Unused on Product { name }
+                "},
+            ),
+        ];
+        let used = used_fragments(&files);
+        assert_that!(used.iter().map(String::as_str).collect::<Vec<_>>())
+            .is_equal_to(vec!["Direct", "Local", "Nested"]);
+
+        let formatted = files[1]
+            .format(FormatOptions::default(), Some(&used))
+            .unwrap();
+        assert_that!(formatted.as_str()).does_not_contain("Unused");
+        assert_that!(formatted.as_str()).does_not_contain("Nothing uses this");
+    }
+}
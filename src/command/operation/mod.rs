@@ -0,0 +1,37 @@
+mod analysis;
+mod analyze;
+mod format;
+mod formatter;
+mod normalize;
+mod output;
+
+use clap::Parser;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Operation {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Report the depth, aliases and estimated cost of operations against a schema
+    Analyze(analyze::Analyze),
+    /// Rewrite .graphql operation files in Rover's canonical format
+    Format(format::Format),
+    /// Print the body and ID Rover would publish for each operation as a persisted query
+    Normalize(normalize::Normalize),
+}
+
+impl Operation {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Analyze(command) => command.run(),
+            Command::Format(command) => command.run(),
+            Command::Normalize(command) => command.run(),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use clap::Parser;
+use serde::Serialize;
+
+use super::output::OperationNormalizeOutput;
+use crate::{
+    RoverError, RoverOutput, RoverResult, command::persisted_queries::PersistedQueryManifest,
+    options::FileDiscoveryOpt,
+};
+
+#[derive(Debug, Serialize, Parser)]
+/// Print the body and ID Rover would publish for each operation as a persisted query
+///
+/// These are exactly what `rover persisted-queries generate` writes to a manifest. Each body
+/// holds the operation followed by every fragment it uses, sorted by name, with `@client` fields
+/// removed. The ID is the SHA-256 hash of the body.
+#[command(after_help = "EXAMPLES:\n    \
+    rover operation normalize --include 'src/**/*.graphql'\n    \
+    rover operation normalize --operation GetProduct")]
+pub struct Normalize {
+    #[clap(flatten)]
+    #[serde(flatten)]
+    file_discovery: FileDiscoveryOpt,
+
+    /// Only print the operation with this name
+    #[arg(long = "operation", value_name = "NAME")]
+    operation: Option<String>,
+}
+
+impl Normalize {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        let files = self.file_discovery.find(&["graphql"])?;
+        let manifest = PersistedQueryManifest::from_files(files)?;
+        let operations: Vec<_> = manifest
+            .into_operations()
+            .into_iter()
+            .filter(|operation| {
+                self.operation
+                    .as_ref()
+                    .is_none_or(|name| &operation.name == name)
+            })
+            .collect();
+
+        if let Some(name) = &self.operation
+            && operations.is_empty()
+        {
+            return Err(RoverError::new(anyhow::anyhow!(
+                "No operation named \"{name}\" was found"
+            )));
+        }
+        Ok(RoverOutput::CliOutput(Box::new(OperationNormalizeOutput {
+            operations,
+        })))
+    }
+}
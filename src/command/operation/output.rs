@@ -0,0 +1,183 @@
+use std::fmt::Write;
+
+use camino::Utf8PathBuf;
+use serde::Serialize;
+
+use super::analysis::{ListSizeSource, OperationAnalysis};
+use crate::command::{CliOutput, persisted_queries::PersistedQueryOperation};
+
+#[derive(Debug, Default, Serialize)]
+pub(super) struct OperationFormatOutput {
+    /// Whether files were only checked, rather than rewritten
+    pub check: bool,
+    /// The files that were rewritten, or that would be without `--check`
+    pub changed: Vec<Utf8PathBuf>,
+    pub unchanged: usize,
+    pub skipped: Vec<SkippedFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct SkippedFile {
+    pub file: Utf8PathBuf,
+    pub reason: String,
+}
+
+impl CliOutput for OperationFormatOutput {
+    fn text(&self) -> String {
+        let mut text = if self.changed.is_empty() {
+            String::from("All files are formatted.")
+        } else {
+            let mut text = format!("Formatted {} file(s):", self.changed.len());
+            for file in &self.changed {
+                let _ = write!(text, "\n  {file}");
+            }
+            text
+        };
+        if self.unchanged > 0 && !self.changed.is_empty() {
+            let _ = write!(text, "\n{} file(s) were already formatted.", self.unchanged);
+        }
+        if !self.skipped.is_empty() {
+            let _ = write!(text, "\n{} file(s) were skipped.", self.skipped.len());
+        }
+        text
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct OperationNormalizeOutput {
+    pub operations: Vec<PersistedQueryOperation>,
+}
+
+impl CliOutput for OperationNormalizeOutput {
+    fn text(&self) -> String {
+        if self.operations.is_empty() {
+            return String::from("No operations found.");
+        }
+        self.operations
+            .iter()
+            .map(|operation| {
+                format!(
+                    "# {} ({})\n# id: {}\n{}",
+                    operation.name, operation.operation_type, operation.id, operation.body
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct OperationAnalyzeOutput {
+    pub operations: Vec<OperationAnalysis>,
+}
+
+impl CliOutput for OperationAnalyzeOutput {
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for operation in &self.operations {
+            let _ = write!(text, "{} ({})", operation.name, operation.operation_type);
+            if let Some(file) = &operation.file {
+                let _ = write!(text, " in {file}");
+            }
+            let _ = write!(
+                text,
+                "\n  depth: {}, aliased fields: {}, estimated cost: {}",
+                operation.depth, operation.aliased_fields, operation.estimated_cost
+            );
+            if !operation.list_multipliers.is_empty() {
+                text.push_str("\n  list multipliers:");
+                for multiplier in &operation.list_multipliers {
+                    let source = match &multiplier.source {
+                        ListSizeSource::SlicingArgument(name) => format!("from argument `{name}`"),
+                        ListSizeSource::AssumedSize => "assumed size".to_string(),
+                        ListSizeSource::Default => "default list size".to_string(),
+                    };
+                    let _ = write!(
+                        text,
+                        "\n    {} x {} ({source})",
+                        multiplier.path, multiplier.size
+                    );
+                }
+            }
+            text.push_str("\n\n");
+        }
+        text.trim_end().to_string()
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::command::operation::analysis::ListMultiplier;
+
+    #[test]
+    fn format_text_lists_changed_files() {
+        let out = OperationFormatOutput {
+            check: false,
+            changed: vec!["a.graphql".into()],
+            unchanged: 2,
+            skipped: vec![SkippedFile {
+                file: "schema.graphql".into(),
+                reason: "it contains schema definitions".to_string(),
+            }],
+        };
+        assert_that!(out.text()).is_equal_to(
+            "Formatted 1 file(s):\n  a.graphql\n2 file(s) were already formatted.\n1 file(s) were skipped."
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn normalize_text_labels_each_body_with_its_id() {
+        let out = OperationNormalizeOutput {
+            operations: vec![PersistedQueryOperation {
+                id: "abc".to_string(),
+                name: "GetProduct".to_string(),
+                operation_type: "query",
+                body: "query GetProduct {\n  id\n}".to_string(),
+            }],
+        };
+        assert_that!(out.text()).is_equal_to(
+            "# GetProduct (query)\n# id: abc\nquery GetProduct {\n  id\n}".to_string(),
+        );
+        assert_that!(out.json().unwrap()["operations"][0]["type"])
+            .is_equal_to(serde_json::json!("query"));
+    }
+
+    #[test]
+    fn analyze_text_lists_metrics_and_multipliers() {
+        let out = OperationAnalyzeOutput {
+            operations: vec![OperationAnalysis {
+                name: "Products".to_string(),
+                operation_type: "query",
+                file: Some("ops.graphql".into()),
+                depth: 2,
+                aliased_fields: 1,
+                estimated_cost: 30,
+                list_multipliers: vec![ListMultiplier {
+                    path: "products".to_string(),
+                    size: 5,
+                    source: ListSizeSource::SlicingArgument("first".to_string()),
+                }],
+            }],
+        };
+        assert_that!(out.text()).is_equal_to(
+            "Products (query) in ops.graphql\n  depth: 2, aliased fields: 1, estimated cost: 30\n  list multipliers:\n    products x 5 (from argument `first`)"
+                .to_string(),
+        );
+    }
+}
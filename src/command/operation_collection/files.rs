@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use camino::{Utf8Path, Utf8PathBuf};
+use rover_client::operations::operation_collection::{
+    OperationCollectionEntry, OperationCollectionEntryInput, OperationHeader,
+};
+use rover_std::Fs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// The `.json` file written next to each pulled operation's `.graphql` file
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub(super) struct Sidecar {
+    /// The ID of the operation in the collection. Pushing updates this
+    /// operation rather than adding a new one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub variables: Value,
+    #[serde(default)]
+    pub headers: Vec<OperationHeader>,
+}
+
+impl Sidecar {
+    fn from_entry(entry: &OperationCollectionEntry) -> Self {
+        Self {
+            id: Some(entry.id.clone()),
+            name: Some(entry.name.clone()),
+            variables: entry.variables.as_deref().map_or(Value::Null, |variables| {
+                serde_json::from_str(variables)
+                    .unwrap_or_else(|_| Value::String(variables.to_string()))
+            }),
+            headers: entry.headers.clone(),
+        }
+    }
+
+    /// Studio stores variables as a string, so objects are written back out
+    /// as JSON and strings are sent as they are
+    fn variables(&self) -> RoverResult<Option<String>> {
+        Ok(match &self.variables {
+            Value::Null => None,
+            Value::String(variables) => Some(variables.clone()),
+            variables => Some(serde_json::to_string_pretty(variables)?),
+        })
+    }
+}
+
+/// An operation read from a local `.graphql` file and its optional `.json` sidecar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct LocalOperation {
+    pub path: Utf8PathBuf,
+    pub id: Option<String>,
+    pub entry: OperationCollectionEntryInput,
+}
+
+/// Turns an operation name into a file name: lowercase, with each run of
+/// other characters replaced by a single `-`
+pub(super) fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "operation".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Writes `<slug>.graphql` and `<slug>.json` for each operation, returning the
+/// `.graphql` paths. Operations whose names share a slug get a numeric suffix.
+pub(super) fn write_operations(
+    out_dir: &Utf8Path,
+    operations: &[OperationCollectionEntry],
+) -> RoverResult<Vec<Utf8PathBuf>> {
+    Fs::create_dir_all(out_dir)?;
+    let mut used = HashSet::new();
+    let mut written = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let base = slugify(&operation.name);
+        let mut slug = base.clone();
+        let mut suffix = 2;
+        while !used.insert(slug.clone()) {
+            slug = format!("{base}-{suffix}");
+            suffix += 1;
+        }
+
+        let document = out_dir.join(format!("{slug}.graphql"));
+        Fs::write_file(&document, format!("{}\n", operation.body.trim_end()))?;
+        let sidecar = serde_json::to_string_pretty(&Sidecar::from_entry(operation))?;
+        Fs::write_file(out_dir.join(format!("{slug}.json")), format!("{sidecar}\n"))?;
+        written.push(document);
+    }
+    Ok(written)
+}
+
+/// Reads each `.graphql` file with the `.json` sidecar next to it, if any.
+/// Operations without a sidecar name are named after their file.
+pub(super) fn read_operations(files: &[Utf8PathBuf]) -> RoverResult<Vec<LocalOperation>> {
+    files
+        .iter()
+        .map(|path| {
+            let body = Fs::read_file(path)?.trim_end().to_string();
+            let sidecar_path = path.with_extension("json");
+            let sidecar = if sidecar_path.exists() {
+                serde_json::from_str::<Sidecar>(&Fs::read_file(&sidecar_path)?).map_err(|e| {
+                    RoverError::new(anyhow!("Could not read {sidecar_path}: {e}"))
+                        .with_suggestion(RoverErrorSuggestion::Adhoc(
+                        "Sidecar files may set \"id\", \"name\", \"variables\" and \"headers\"."
+                            .to_string(),
+                    ))
+                })?
+            } else {
+                Sidecar::default()
+            };
+            let name = sidecar
+                .name
+                .clone()
+                .or_else(|| path.file_stem().map(str::to_string))
+                .unwrap_or_else(|| path.to_string());
+            Ok(LocalOperation {
+                path: path.clone(),
+                id: sidecar.id.clone(),
+                entry: OperationCollectionEntryInput {
+                    name,
+                    body,
+                    variables: sidecar.variables()?,
+                    headers: sidecar.headers,
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("GetMe", "getme")]
+    #[case("Get current user (v2)", "get-current-user-v2")]
+    #[case("  --", "operation")]
+    fn slugify_makes_file_names(#[case] name: &str, #[case] expected: &str) {
+        assert_that!(slugify(name)).is_equal_to(expected.to_string());
+    }
+
+    #[test]
+    fn pulled_operations_read_back_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let out_dir = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+        let operations = vec![
+            OperationCollectionEntry {
+                id: "o-1".to_string(),
+                name: "Me".to_string(),
+                body: "query Me { me { id } }".to_string(),
+                variables: Some("{\n  \"first\": 10\n}".to_string()),
+                headers: vec![OperationHeader {
+                    name: "x-client".to_string(),
+                    value: "web".to_string(),
+                }],
+            },
+            OperationCollectionEntry {
+                id: "o-2".to_string(),
+                name: "me".to_string(),
+                body: "query me { me { name } }\n".to_string(),
+                variables: None,
+                headers: Vec::new(),
+            },
+        ];
+
+        let written = write_operations(&out_dir, &operations).unwrap();
+        assert_that!(written).is_equal_to(vec![
+            out_dir.join("me.graphql"),
+            out_dir.join("me-2.graphql"),
+        ]);
+
+        let local = read_operations(&written).unwrap();
+        assert_that!(local[0].id).is_equal_to(Some("o-1".to_string()));
+        assert_that!(local[0].entry.variables).is_equal_to(operations[0].variables.clone());
+        assert_that!(local[0].entry.headers).is_equal_to(operations[0].headers.clone());
+        assert_that!(local[1].entry.name).is_equal_to("me".to_string());
+        assert_that!(local[1].entry.body).is_equal_to("query me { me { name } }".to_string());
+        assert_that!(local[1].entry.variables).is_none();
+    }
+
+    #[test]
+    fn operations_without_sidecars_are_named_after_their_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Utf8PathBuf::from_path_buf(dir.path().join("GetProducts.graphql")).unwrap();
+        std::fs::write(&path, "query GetProducts { products { id } }\n").unwrap();
+
+        let local = read_operations(&[path]).unwrap();
+        assert_that!(local[0].id).is_none();
+        assert_that!(local[0].entry.name).is_equal_to("GetProducts".to_string());
+    }
+}
@@ -0,0 +1,32 @@
+use clap::Parser;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult, utils::client::StudioClientConfig};
+
+mod files;
+mod output;
+mod pull;
+mod push;
+
+#[derive(Debug, Serialize, Parser)]
+pub struct OperationCollection {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Write each operation in a collection to local .graphql and .json files
+    Pull(pull::Pull),
+    /// Save local .graphql files to a collection, adding or updating its operations
+    Push(push::Push),
+}
+
+impl OperationCollection {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Pull(command) => command.run(client_config).await,
+            Command::Push(command) => command.run(client_config).await,
+        }
+    }
+}
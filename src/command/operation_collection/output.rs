@@ -0,0 +1,72 @@
+use std::fmt::Write;
+
+use camino::Utf8PathBuf;
+use serde::Serialize;
+
+use crate::command::CliOutput;
+
+#[derive(Debug, Serialize)]
+pub(super) struct OperationCollectionPullOutput {
+    pub collection_id: String,
+    pub name: String,
+    /// The `.graphql` file written for each operation
+    pub files: Vec<Utf8PathBuf>,
+}
+
+impl CliOutput for OperationCollectionPullOutput {
+    fn text(&self) -> String {
+        if self.files.is_empty() {
+            return format!("{} has no operations.", self.name);
+        }
+        let mut text = format!(
+            "Wrote {} operation(s) from {}:\n",
+            self.files.len(),
+            self.name
+        );
+        for file in &self.files {
+            let _ = writeln!(text, "  {file}");
+        }
+        text.trim_end().to_string()
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct OperationCollectionPushOutput {
+    pub collection_id: String,
+    pub name: String,
+    pub dry_run: bool,
+    /// The names of the operations added, or that would be added
+    pub added: Vec<String>,
+    /// The names of the operations updated, or that would be updated
+    pub updated: Vec<String>,
+    pub unchanged: usize,
+    /// Operations in the collection with no local file. Push never deletes them.
+    pub only_in_studio: Vec<String>,
+}
+
+impl CliOutput for OperationCollectionPushOutput {
+    fn text(&self) -> String {
+        let (added, updated) = (self.added.len(), self.updated.len());
+        if added == 0 && updated == 0 {
+            format!("{} is up to date.", self.name)
+        } else if self.dry_run {
+            format!(
+                "Dry run: would add {added} and update {updated} operation(s) in {}. Nothing was saved.",
+                self.name
+            )
+        } else {
+            format!(
+                "Added {added} and updated {updated} operation(s) in {}.",
+                self.name
+            )
+        }
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
@@ -0,0 +1,52 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+use rover_client::operations::operation_collection::fetch::{self, OperationCollectionFetchInput};
+use rover_std::Style;
+use serde::Serialize;
+
+use super::{files, output::OperationCollectionPullOutput};
+use crate::{RoverOutput, RoverResult, options::ProfileOpt, utils::client::StudioClientConfig};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Pull {
+    /// The ID of the operation collection
+    #[arg(value_name = "COLLECTION_ID")]
+    collection_id: String,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The directory to write the operations to. Existing files with the same
+    /// names are overwritten.
+    #[arg(long = "out-dir", value_name = "DIR", default_value = ".")]
+    out_dir: Utf8PathBuf,
+}
+
+impl Pull {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+
+        eprintln!(
+            "Pulling operation collection {} using credentials from the {} profile.",
+            Style::Link.paint(&self.collection_id),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let collection = fetch::run(
+            OperationCollectionFetchInput {
+                collection_id: self.collection_id.clone(),
+            },
+            &client,
+        )
+        .await?;
+        let files = files::write_operations(&self.out_dir, &collection.operations)?;
+
+        Ok(RoverOutput::CliOutput(Box::new(
+            OperationCollectionPullOutput {
+                collection_id: collection.id,
+                name: collection.name,
+                files,
+            },
+        )))
+    }
+}
@@ -0,0 +1,412 @@
+use std::{collections::HashSet, fmt::Write};
+
+use anyhow::anyhow;
+use clap::Parser;
+use rover_client::operations::operation_collection::{
+    OperationCollectionEntry,
+    fetch::{self, OperationCollectionFetchInput},
+    push::{self, OperationCollectionEntryUpdate, OperationCollectionPushInput},
+};
+use rover_std::{Style, prompt};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{
+    files::{self, LocalOperation},
+    output::OperationCollectionPushOutput,
+};
+use crate::{
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult,
+    command::client::check::parse_graphql_files,
+    options::{FileDiscoveryOpt, ProfileOpt},
+    utils::client::StudioClientConfig,
+};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Push {
+    /// The ID of the operation collection
+    #[arg(value_name = "COLLECTION_ID")]
+    collection_id: String,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    #[clap(flatten)]
+    #[serde(flatten)]
+    file_discovery: FileDiscoveryOpt,
+
+    /// Show what would change in the collection without saving anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Skips the step where the command asks for user confirmation before
+    /// saving the changes.
+    #[arg(long)]
+    confirm: bool,
+}
+
+impl Push {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let paths = self.file_discovery.find(&["graphql"])?;
+        if paths.is_empty() {
+            return Err(RoverError::new(anyhow!(
+                "No .graphql operations found under the provided includes"
+            )));
+        }
+        parse_graphql_files(paths.clone())?;
+        let local = files::read_operations(&paths)?;
+
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let collection = fetch::run(
+            OperationCollectionFetchInput {
+                collection_id: self.collection_id.clone(),
+            },
+            &client,
+        )
+        .await?;
+
+        let plan = PushPlan::new(&collection.operations, local)?;
+        eprint!("{}", plan.describe(&collection.name));
+
+        let output = OperationCollectionPushOutput {
+            collection_id: collection.id.clone(),
+            name: collection.name.clone(),
+            dry_run: self.dry_run,
+            added: plan.added.iter().map(|op| op.entry.name.clone()).collect(),
+            updated: plan
+                .updated
+                .iter()
+                .map(|update| update.local.entry.name.clone())
+                .collect(),
+            unchanged: plan.unchanged,
+            only_in_studio: plan.only_in_studio.clone(),
+        };
+        if self.dry_run || !plan.has_changes() {
+            return Ok(RoverOutput::CliOutput(Box::new(output)));
+        }
+
+        if !self.confirm
+            && !prompt::prompt_confirm_default_no(&format!(
+                "Save these changes to {}?",
+                collection.name
+            ))?
+        {
+            eprintln!("Push cancelled by user");
+            return Ok(RoverOutput::EmptySuccess);
+        }
+
+        push::run(
+            OperationCollectionPushInput {
+                collection_id: collection.id,
+                added: plan.added.into_iter().map(|op| op.entry).collect(),
+                updated: plan
+                    .updated
+                    .into_iter()
+                    .map(|update| OperationCollectionEntryUpdate {
+                        id: update.current.id,
+                        entry: update.local.entry,
+                    })
+                    .collect(),
+            },
+            &client,
+        )
+        .await?;
+
+        Ok(RoverOutput::CliOutput(Box::new(output)))
+    }
+}
+
+/// A local operation that differs from the collection entry it matched
+#[derive(Debug, PartialEq)]
+struct PlannedUpdate {
+    current: OperationCollectionEntry,
+    local: LocalOperation,
+    changed: Vec<&'static str>,
+}
+
+/// How local operations line up with a collection's operations.
+///
+/// Local operations match an entry by the ID in their sidecar, then by name.
+/// Entries that no local operation matches are reported but never deleted.
+#[derive(Debug, PartialEq)]
+struct PushPlan {
+    added: Vec<LocalOperation>,
+    updated: Vec<PlannedUpdate>,
+    unchanged: usize,
+    only_in_studio: Vec<String>,
+}
+
+impl PushPlan {
+    fn new(remote: &[OperationCollectionEntry], local: Vec<LocalOperation>) -> RoverResult<Self> {
+        let mut matched: HashSet<&str> = HashSet::new();
+        let mut plan = Self {
+            added: Vec::new(),
+            updated: Vec::new(),
+            unchanged: 0,
+            only_in_studio: Vec::new(),
+        };
+
+        for operation in local {
+            let by_id = operation
+                .id
+                .as_deref()
+                .and_then(|id| remote.iter().find(|entry| entry.id == id));
+            let current = by_id.or_else(|| {
+                remote.iter().find(|entry| {
+                    entry.name == operation.entry.name && !matched.contains(entry.id.as_str())
+                })
+            });
+            let Some(current) = current else {
+                plan.added.push(operation);
+                continue;
+            };
+            if !matched.insert(current.id.as_str()) {
+                return Err(RoverError::new(anyhow!(
+                    "More than one local file matches the operation '{}' ({}); {} is one of them.",
+                    current.name,
+                    current.id,
+                    operation.path
+                ))
+                .with_suggestion(RoverErrorSuggestion::Adhoc(
+                    "Give each file a different name or \"id\" in its .json file.".to_string(),
+                )));
+            }
+
+            let changed = changed_fields(current, &operation);
+            if changed.is_empty() {
+                plan.unchanged += 1;
+            } else {
+                plan.updated.push(PlannedUpdate {
+                    current: current.clone(),
+                    local: operation,
+                    changed,
+                });
+            }
+        }
+
+        plan.only_in_studio = remote
+            .iter()
+            .filter(|entry| !matched.contains(entry.id.as_str()))
+            .map(|entry| entry.name.clone())
+            .collect();
+        Ok(plan)
+    }
+
+    const fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.updated.is_empty()
+    }
+
+    fn describe(&self, collection_name: &str) -> String {
+        let mut text = format!("Changes to {}:\n", Style::Link.paint(collection_name));
+        for operation in &self.added {
+            let _ = writeln!(
+                text,
+                "  {} {} ({})",
+                Style::Success.paint("+ add"),
+                operation.entry.name,
+                Style::Path.paint(&operation.path)
+            );
+        }
+        for update in &self.updated {
+            let _ = writeln!(
+                text,
+                "  {} {} ({}): {}",
+                Style::Pending.paint("~ update"),
+                update.local.entry.name,
+                Style::Path.paint(&update.local.path),
+                update.changed.join(", ")
+            );
+            if update.changed.contains(&"body") {
+                for line in line_diff(&update.current.body, &update.local.entry.body) {
+                    let _ = writeln!(text, "      {line}");
+                }
+            }
+        }
+        if self.unchanged > 0 {
+            let _ = writeln!(text, "  {} unchanged", self.unchanged);
+        }
+        if !self.only_in_studio.is_empty() {
+            let _ = writeln!(
+                text,
+                "  Only in Studio, left as is: {}",
+                self.only_in_studio.join(", ")
+            );
+        }
+        if !self.has_changes() {
+            let _ = writeln!(text, "  Nothing to push.");
+        }
+        text
+    }
+}
+
+fn changed_fields(current: &OperationCollectionEntry, local: &LocalOperation) -> Vec<&'static str> {
+    let mut changed = Vec::new();
+    if current.name != local.entry.name {
+        changed.push("name");
+    }
+    if current.body.trim_end() != local.entry.body.trim_end() {
+        changed.push("body");
+    }
+    if normalize_variables(current.variables.as_deref())
+        != normalize_variables(local.entry.variables.as_deref())
+    {
+        changed.push("variables");
+    }
+    if current.headers != local.entry.headers {
+        changed.push("headers");
+    }
+    changed
+}
+
+/// Compares variables as JSON so formatting differences don't count as changes
+fn normalize_variables(variables: Option<&str>) -> Value {
+    match variables.map(str::trim) {
+        None | Some("") => Value::Null,
+        Some(variables) => {
+            serde_json::from_str(variables).unwrap_or_else(|_| Value::String(variables.to_string()))
+        }
+    }
+}
+
+/// A line-by-line diff of two documents, with `-` and `+` marking removed and
+/// added lines
+fn line_diff(before: &str, after: &str) -> Vec<String> {
+    let before: Vec<&str> = before.trim_end().lines().collect();
+    let after: Vec<&str> = after.trim_end().lines().collect();
+
+    // lengths of the longest common subsequence of each pair of suffixes
+    let mut lcs = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            lcs[i][j] = if before[i] == after[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() || j < after.len() {
+        if i < before.len() && j < after.len() && before[i] == after[j] {
+            lines.push(format!("  {}", before[i]));
+            i += 1;
+            j += 1;
+        } else if j < after.len() && (i == before.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            lines.push(Style::Success.paint(format!("+ {}", after[j])));
+            j += 1;
+        } else {
+            lines.push(Style::Failure.paint(format!("- {}", before[i])));
+            i += 1;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use rover_client::operations::operation_collection::{
+        OperationCollectionEntryInput, OperationHeader,
+    };
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn remote(id: &str, name: &str, body: &str) -> OperationCollectionEntry {
+        OperationCollectionEntry {
+            id: id.to_string(),
+            name: name.to_string(),
+            body: body.to_string(),
+            variables: Some("{\"first\":10}".to_string()),
+            headers: Vec::new(),
+        }
+    }
+
+    fn local(id: Option<&str>, name: &str, body: &str) -> LocalOperation {
+        LocalOperation {
+            path: Utf8PathBuf::from(format!("{name}.graphql")),
+            id: id.map(str::to_string),
+            entry: OperationCollectionEntryInput {
+                name: name.to_string(),
+                body: body.to_string(),
+                variables: Some("{\n  \"first\": 10\n}".to_string()),
+                headers: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn plan_matches_by_id_then_name() {
+        let remote = vec![
+            remote("o-1", "Me", "query Me { me { id } }"),
+            remote("o-2", "Products", "query Products { products { id } }"),
+            remote("o-3", "Reviews", "query Reviews { reviews { id } }"),
+        ];
+        let mut renamed = local(Some("o-1"), "CurrentUser", "query Me { me { id } }");
+        renamed.entry.headers.push(OperationHeader {
+            name: "x-client".to_string(),
+            value: "web".to_string(),
+        });
+        let plan = PushPlan::new(
+            &remote,
+            vec![
+                renamed,
+                local(None, "Products", "query Products { products { id } }"),
+                local(None, "Cart", "query Cart { cart { id } }"),
+            ],
+        )
+        .unwrap();
+
+        assert_that!(
+            plan.added
+                .iter()
+                .map(|op| op.entry.name.as_str())
+                .collect::<Vec<_>>()
+        )
+        .is_equal_to(vec!["Cart"]);
+        assert_that!(plan.updated).has_length(1);
+        assert_that!(plan.updated[0].current.id).is_equal_to("o-1".to_string());
+        assert_that!(plan.updated[0].changed).is_equal_to(vec!["name", "headers"]);
+        assert_that!(plan.unchanged).is_equal_to(1);
+        assert_that!(plan.only_in_studio).is_equal_to(vec!["Reviews".to_string()]);
+    }
+
+    #[test]
+    fn plan_errs_when_two_files_match_one_operation() {
+        let remote = vec![remote("o-1", "Me", "query Me { me { id } }")];
+        let plan = PushPlan::new(
+            &remote,
+            vec![
+                local(Some("o-1"), "Me", "query Me { me { id } }"),
+                local(Some("o-1"), "Me", "query Me { me { name } }"),
+            ],
+        );
+        assert_that!(plan).is_err();
+    }
+
+    #[test]
+    fn line_diff_marks_changed_lines() {
+        let diff = line_diff(
+            "query Me {\n  me {\n    id\n  }\n}",
+            "query Me {\n  me {\n    id\n    name\n  }\n}\n",
+        );
+        let diff: Vec<String> = diff
+            .into_iter()
+            .map(|line| console::strip_ansi_codes(&line).to_string())
+            .collect();
+        assert_that!(diff).is_equal_to(
+            [
+                "  query Me {",
+                "    me {",
+                "      id",
+                "+     name",
+                "    }",
+                "  }",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+        );
+    }
+}
@@ -431,6 +431,10 @@ impl RoverOutput {
 
                     table.add_row(vec![subgraph.name.clone(), url, formatted_updated_at]);
                 }
+                // a local registry has no Studio page to link to
+                if details.root_url.is_empty() {
+                    return Ok(Some(table.to_string()));
+                }
                 Some(format!(
                     "{}\n View full details at {}/graph/{}/service-list",
                     table,
@@ -1748,6 +1752,7 @@ View custom check details at: https://studio.apollographql.com/graph/my-graph/va
             supergraph_was_updated: true,
             subgraph_was_created: true,
             subgraph_was_updated: true,
+            launch_id: Some("l-1".to_string()),
             launch_url: Some("test.com/launchurl".to_string()),
             launch_cli_copy: Some(
                 "You can monitor this launch in Apollo Studio: test.com/launchurl".to_string(),
@@ -1798,6 +1803,7 @@ View custom check details at: https://studio.apollographql.com/graph/my-graph/va
             supergraph_was_updated: false,
             subgraph_was_created: false,
             subgraph_was_updated: true,
+            launch_id: None,
             launch_url: None,
             launch_cli_copy: None,
         };
@@ -1852,6 +1858,7 @@ View custom check details at: https://studio.apollographql.com/graph/my-graph/va
             supergraph_was_updated: false,
             subgraph_was_created: false,
             subgraph_was_updated: false,
+            launch_id: None,
             launch_url: None,
             launch_cli_copy: None,
         };
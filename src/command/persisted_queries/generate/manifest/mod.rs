@@ -4,7 +4,9 @@ mod operation;
 mod printer;
 
 use camino::Utf8PathBuf;
-use operation::{ParsedInputs, PersistedQueryOperation};
+use operation::ParsedInputs;
+pub(crate) use operation::PersistedQueryOperation;
+pub(crate) use printer::PrintableDefinition;
 use serde::Serialize;
 
 use crate::RoverResult;
@@ -13,14 +15,14 @@ const MANIFEST_FORMAT: &str = "apollo-persisted-query-manifest";
 const MANIFEST_VERSION: u8 = 1;
 
 #[derive(Debug, Serialize)]
-pub(super) struct PersistedQueryManifest {
+pub(crate) struct PersistedQueryManifest {
     format: &'static str,
     version: u8,
     operations: Vec<PersistedQueryOperation>,
 }
 
 impl PersistedQueryManifest {
-    pub(super) fn from_files(files: Vec<Utf8PathBuf>) -> RoverResult<Self> {
+    pub(crate) fn from_files(files: Vec<Utf8PathBuf>) -> RoverResult<Self> {
         let parsed_inputs = ParsedInputs::from_files(files)?;
         let operations = parsed_inputs.generate_operations()?;
         Ok(Self {
@@ -30,7 +32,11 @@ impl PersistedQueryManifest {
         })
     }
 
-    pub(super) const fn operation_count(&self) -> usize {
+    pub(crate) const fn operation_count(&self) -> usize {
         self.operations.len()
     }
+
+    pub(crate) fn into_operations(self) -> Vec<PersistedQueryOperation> {
+        self.operations
+    }
 }
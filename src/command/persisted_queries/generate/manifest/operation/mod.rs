@@ -4,7 +4,7 @@ mod parsed_operation;
 mod persisted_query_operation;
 
 pub(super) use parsed_inputs::ParsedInputs;
-pub(super) use persisted_query_operation::PersistedQueryOperation;
+pub(crate) use persisted_query_operation::PersistedQueryOperation;
 
 #[cfg(test)]
 mod tests {
@@ -2,9 +2,9 @@ use serde::Serialize;
 
 #[derive(Debug, Serialize)]
 pub(crate) struct PersistedQueryOperation {
-    pub(super) id: String,
-    pub(super) name: String,
+    pub(crate) id: String,
+    pub(crate) name: String,
     #[serde(rename = "type")]
-    pub(super) operation_type: &'static str,
-    pub(super) body: String,
+    pub(crate) operation_type: &'static str,
+    pub(crate) body: String,
 }
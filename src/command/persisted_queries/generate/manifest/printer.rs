@@ -2,7 +2,7 @@ use std::fmt;
 
 use apollo_compiler::{Node, ast};
 
-pub(super) enum PrintableDefinition {
+pub(crate) enum PrintableDefinition {
     Operation(Node<ast::OperationDefinition>),
     Fragment(Node<ast::FragmentDefinition>),
 }
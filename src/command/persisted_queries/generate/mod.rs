@@ -3,7 +3,7 @@ mod output;
 
 use camino::Utf8PathBuf;
 use clap::Parser;
-use manifest::PersistedQueryManifest;
+pub(crate) use manifest::{PersistedQueryManifest, PersistedQueryOperation, PrintableDefinition};
 use output::GenerateOutput;
 use rover_print::print::PrintExt;
 use rover_std::Fs;
@@ -3,6 +3,7 @@ mod publish;
 
 use clap::Parser;
 pub use generate::Generate;
+pub(crate) use generate::{PersistedQueryManifest, PersistedQueryOperation, PrintableDefinition};
 pub use publish::Publish;
 use rover_print::print::Print;
 use serde::Serialize;
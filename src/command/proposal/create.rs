@@ -0,0 +1,99 @@
+use clap::Parser;
+use rover_client::{
+    operations::proposal::{
+        create::{self, ProposalCreateInput},
+        revise::{self, ProposalReviseInput},
+    },
+    shared::GitContext,
+};
+use rover_std::Style;
+use serde::Serialize;
+
+use super::{output::ProposalRevisionOutput, revise::ProposalSubgraphOpts};
+use crate::{
+    RoverOutput, RoverResult,
+    options::{GraphRefOpt, ProfileOpt},
+    utils::client::StudioClientConfig,
+};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover proposal create my-graph@current --name products --schema ./products.graphql \
+    --display-name \"Add product reviews\"")]
+pub struct Create {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    subgraph: ProposalSubgraphOpts,
+
+    /// The title of the proposal
+    #[arg(long)]
+    display_name: String,
+
+    /// A longer description of the proposal
+    #[arg(long)]
+    description: Option<String>,
+
+    /// What the first revision changes. Defaults to the proposal's title.
+    #[arg(long)]
+    summary: Option<String>,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+impl Create {
+    pub async fn run(
+        &self,
+        client_config: StudioClientConfig,
+        git_context: GitContext,
+    ) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        // read the schema before creating anything, so a bad path doesn't leave
+        // an empty proposal behind
+        let subgraph = self.subgraph.read()?;
+        eprintln!(
+            "Creating a proposal from {} using credentials from the {} profile.",
+            self.graph.graph_ref, self.profile.profile_name
+        );
+
+        let created = create::run(
+            ProposalCreateInput {
+                graph_ref: self.graph.graph_ref.clone(),
+                display_name: self.display_name.clone(),
+                description: self.description.clone(),
+            },
+            &client,
+        )
+        .await?;
+
+        // a new proposal starts out with the variant's schema, so the change
+        // itself is saved as its first revision
+        let response = revise::run(
+            ProposalReviseInput {
+                proposal_id: created.proposal_id.clone(),
+                subgraphs: vec![subgraph],
+                summary: self
+                    .summary
+                    .clone()
+                    .unwrap_or_else(|| self.display_name.clone()),
+                git_context,
+            },
+            &client,
+        )
+        .await
+        .inspect_err(|_| {
+            eprintln!(
+                "{} Created proposal {}, but couldn't save the subgraph schema to it. Run `rover proposal revise {}` to try again.",
+                Style::WarningPrefix.paint("WARN:"),
+                created.proposal_id,
+                created.proposal_id
+            )
+        })?;
+        Ok(RoverOutput::CliOutput(Box::new(ProposalRevisionOutput {
+            created_from: Some(self.graph.graph_ref.clone()),
+            response,
+        })))
+    }
+}
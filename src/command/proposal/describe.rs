@@ -0,0 +1,33 @@
+use clap::Parser;
+use rover_client::operations::proposal::describe::{self, ProposalDescribeInput};
+use serde::Serialize;
+
+use super::output::ProposalDetailsOutput;
+use crate::{RoverOutput, RoverResult, options::ProfileOpt, utils::client::StudioClientConfig};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Describe {
+    /// The ID of the proposal
+    #[arg(value_name = "PROPOSAL_ID")]
+    proposal_id: String,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+impl Describe {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+
+        let details = describe::run(
+            ProposalDescribeInput {
+                proposal_id: self.proposal_id.clone(),
+            },
+            &client,
+        )
+        .await?;
+        Ok(RoverOutput::CliOutput(Box::new(ProposalDetailsOutput(
+            details,
+        ))))
+    }
+}
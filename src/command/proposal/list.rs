@@ -0,0 +1,58 @@
+use clap::Parser;
+use rover_client::operations::proposal::{
+    ProposalStatus,
+    list::{self, ProposalListInput},
+};
+use serde::Serialize;
+
+use super::output::ProposalListOutput;
+use crate::{
+    RoverOutput, RoverResult,
+    options::{GraphRefOpt, ProfileOpt},
+    utils::client::StudioClientConfig,
+};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct List {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// Only list proposals with this status. Can be passed more than once.
+    #[arg(long = "status", value_name = "STATUS")]
+    statuses: Vec<ProposalStatus>,
+
+    /// Only list proposals that change this subgraph. Can be passed more than once.
+    #[arg(long = "subgraph", value_name = "SUBGRAPH_NAME")]
+    subgraphs: Vec<String>,
+
+    /// The maximum number of proposals to list
+    #[arg(long, default_value_t = 25)]
+    limit: i64,
+}
+
+impl List {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        eprintln!(
+            "Listing proposals for {} using credentials from the {} profile.",
+            self.graph.graph_ref, self.profile.profile_name
+        );
+
+        let response = list::run(
+            ProposalListInput {
+                graph_ref: self.graph.graph_ref.clone(),
+                statuses: self.statuses.clone(),
+                subgraphs: self.subgraphs.clone(),
+                limit: Some(self.limit),
+            },
+            &client,
+        )
+        .await?;
+        Ok(RoverOutput::CliOutput(Box::new(ProposalListOutput(
+            response,
+        ))))
+    }
+}
@@ -0,0 +1,48 @@
+use clap::Parser;
+use rover_client::shared::GitContext;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult, utils::client::StudioClientConfig};
+
+mod create;
+mod describe;
+mod list;
+mod output;
+mod revise;
+mod status;
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Proposal {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Create a proposal from a variant with a changed subgraph schema
+    Create(create::Create),
+    /// Show a proposal, its latest revision and its reviews
+    Describe(describe::Describe),
+    /// List the proposals created from a variant
+    List(list::List),
+    /// Save a new revision of a proposal with a changed subgraph schema
+    Revise(revise::Revise),
+    /// Show or change the status of a proposal
+    Status(status::Status),
+}
+
+impl Proposal {
+    pub async fn run(
+        &self,
+        client_config: StudioClientConfig,
+        git_context: GitContext,
+    ) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Create(command) => command.run(client_config, git_context).await,
+            Command::Describe(command) => command.run(client_config).await,
+            Command::List(command) => command.run(client_config).await,
+            Command::Revise(command) => command.run(client_config, git_context).await,
+            Command::Status(command) => command.run(client_config).await,
+        }
+    }
+}
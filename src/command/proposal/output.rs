@@ -0,0 +1,328 @@
+use std::fmt::Write;
+
+use rover_client::operations::proposal::{
+    describe::{ProposalDetails, ProposalRevision},
+    list::ProposalListResponse,
+    revise::ProposalReviseResponse,
+    status::ProposalStatusResponse,
+};
+use rover_std::Style;
+use rover_studio::types::GraphRef;
+use serde_json::json;
+
+use crate::{command::CliOutput, utils::table};
+
+#[derive(Debug)]
+pub(super) struct ProposalListOutput(pub ProposalListResponse);
+
+impl CliOutput for ProposalListOutput {
+    fn text(&self) -> String {
+        if self.0.proposals.is_empty() {
+            return format!("No proposals found for {}.", self.0.graph_ref);
+        }
+        let mut table = table::get_table();
+        table.add_row(vec![
+            &Style::Success.paint("ID"),
+            &Style::Success.paint("Title"),
+            &Style::Success.paint("Status"),
+            &Style::Success.paint("Created By"),
+            &Style::Success.paint("Updated At"),
+        ]);
+        for proposal in &self.0.proposals {
+            table.add_row(vec![
+                proposal.id.clone(),
+                proposal.display_name.clone(),
+                proposal.status.to_string(),
+                proposal
+                    .created_by
+                    .clone()
+                    .unwrap_or_else(|| "N/A".to_string()),
+                proposal.updated_at.clone(),
+            ]);
+        }
+        let mut text = table.to_string();
+        let total = self.0.total_count;
+        if total > self.0.proposals.len() as i64 {
+            let _ = write!(
+                text,
+                "\nShowing {} of {total} proposals. Pass --limit to see more.",
+                self.0.proposals.len()
+            );
+        }
+        text
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(&self.0)
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ProposalDetailsOutput(pub ProposalDetails);
+
+impl CliOutput for ProposalDetailsOutput {
+    fn text(&self) -> String {
+        let details = &self.0;
+        let mut text = format!(
+            "{}\n{}\n\n",
+            Style::Heading.paint(&details.display_name),
+            details.id
+        );
+        let _ = writeln!(text, "Status: {}", details.status);
+        let _ = writeln!(text, "Created from: {}", details.source_variant);
+        if let Some(created_by) = &details.created_by {
+            let _ = writeln!(text, "Created by: {created_by}");
+        }
+        let _ = writeln!(text, "Created at: {}", details.created_at);
+        let _ = writeln!(text, "Updated at: {}", details.updated_at);
+        if !details.description.is_empty() {
+            let _ = write!(text, "\n{}\n", details.description);
+        }
+        let _ = write!(
+            text,
+            "\n{} ({} in total)\n{}",
+            Style::Heading.paint("Latest revision"),
+            details.revision_count,
+            revision_text(&details.latest_revision)
+        );
+        if !details.reviews.is_empty() {
+            let _ = writeln!(text, "\n{}", Style::Heading.paint("Reviews"));
+            for review in &details.reviews {
+                let reviewer = review.reviewer.as_deref().unwrap_or("Unknown reviewer");
+                let dismissed = if review.is_dismissed {
+                    " (dismissed)"
+                } else {
+                    ""
+                };
+                let _ = writeln!(text, "{reviewer}: {}{dismissed}", review.decision);
+            }
+        }
+        text.trim_end().to_string()
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(&self.0)
+    }
+}
+
+/// The output of `proposal create` and `proposal revise`, which both save a revision
+#[derive(Debug)]
+pub(super) struct ProposalRevisionOutput {
+    /// The variant a newly created proposal started from
+    pub created_from: Option<GraphRef>,
+    pub response: ProposalReviseResponse,
+}
+
+impl CliOutput for ProposalRevisionOutput {
+    fn text(&self) -> String {
+        let mut text = String::new();
+        if let Some(graph_ref) = &self.created_from {
+            let _ = writeln!(
+                text,
+                "Created proposal {} from {graph_ref}",
+                Style::Link.paint(&self.response.proposal_id)
+            );
+        }
+        let _ = write!(
+            text,
+            "Saved revision {} of proposal {}\n{}",
+            self.response.revision.id,
+            Style::Link.paint(&self.response.proposal_id),
+            revision_text(&self.response.revision)
+        );
+        text.trim_end().to_string()
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Ok(json!({
+            "proposal_id": self.response.proposal_id,
+            "created": self.created_from.is_some(),
+            "created_from": self.created_from.as_ref().map(ToString::to_string),
+            "revision": self.response.revision,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ProposalStatusOutput(pub ProposalDetails);
+
+impl CliOutput for ProposalStatusOutput {
+    fn text(&self) -> String {
+        let revision = &self.0.latest_revision;
+        format!(
+            "Proposal {} is {}\nLaunch: {}\nChecks: {}",
+            self.0.id,
+            self.0.status,
+            revision.launch_status.as_deref().unwrap_or("N/A"),
+            revision.check_status.as_deref().unwrap_or("N/A"),
+        )
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Ok(json!({
+            "proposal_id": self.0.id,
+            "status": self.0.status,
+            "launch_status": self.0.latest_revision.launch_status,
+            "check_status": self.0.latest_revision.check_status,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ProposalStatusChangeOutput(pub ProposalStatusResponse);
+
+impl CliOutput for ProposalStatusChangeOutput {
+    fn text(&self) -> String {
+        if self.0.previous_status == self.0.status {
+            format!(
+                "Proposal {} is already {}",
+                self.0.proposal_id, self.0.status
+            )
+        } else {
+            format!(
+                "Moved proposal {} from {} to {}",
+                self.0.proposal_id, self.0.previous_status, self.0.status
+            )
+        }
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(&self.0)
+    }
+}
+
+fn revision_text(revision: &ProposalRevision) -> String {
+    let mut text = String::new();
+    if !revision.summary.is_empty() {
+        let _ = writeln!(text, "Summary: {}", revision.summary);
+    }
+    let _ = writeln!(text, "Created at: {}", revision.created_at);
+    let _ = writeln!(
+        text,
+        "Launch: {}",
+        revision.launch_status.as_deref().unwrap_or("N/A")
+    );
+    if let Some(check_status) = &revision.check_status {
+        let _ = writeln!(text, "Checks: {check_status}");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rover_client::operations::proposal::{
+        ProposalStatus, ProposalSummary, describe::ProposalReview,
+    };
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn revision() -> ProposalRevision {
+        ProposalRevision {
+            id: "r-2".to_string(),
+            summary: "Add reviews".to_string(),
+            created_at: "2026-01-03T03:04:05Z".to_string(),
+            launch_id: Some("launch-2".to_string()),
+            launch_status: Some("LAUNCH_COMPLETED".to_string()),
+            check_workflow_id: None,
+            check_status: None,
+        }
+    }
+
+    fn details() -> ProposalDetails {
+        ProposalDetails {
+            id: "p-1".to_string(),
+            display_name: "Product reviews".to_string(),
+            description: String::new(),
+            status: ProposalStatus::Open,
+            source_variant: "mygraph@current".to_string(),
+            created_by: Some("Ada".to_string()),
+            created_at: "2026-01-02T03:04:05Z".to_string(),
+            updated_at: "2026-01-03T03:04:05Z".to_string(),
+            graph_id: "mygraph".to_string(),
+            backing_variant: "p-1-variant".to_string(),
+            latest_revision: revision(),
+            revision_count: 2,
+            reviews: vec![ProposalReview {
+                reviewer: Some("Grace".to_string()),
+                decision: "APPROVED".to_string(),
+                is_dismissed: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn list_text_mentions_proposals_past_the_limit() {
+        let output = ProposalListOutput(ProposalListResponse {
+            graph_ref: GraphRef::from_str("mygraph@current").unwrap(),
+            proposals: vec![ProposalSummary {
+                id: "p-1".to_string(),
+                display_name: "Product reviews".to_string(),
+                status: ProposalStatus::Draft,
+                source_variant: "mygraph@current".to_string(),
+                created_by: None,
+                created_at: "2026-01-02T03:04:05Z".to_string(),
+                updated_at: "2026-01-03T03:04:05Z".to_string(),
+            }],
+            total_count: 3,
+        });
+        let text = output.text();
+        assert_that!(text).contains("Product reviews");
+        assert_that!(text).contains("DRAFT");
+        assert_that!(text).contains("Showing 1 of 3 proposals");
+    }
+
+    #[test]
+    fn empty_list_says_so() {
+        let output = ProposalListOutput(ProposalListResponse {
+            graph_ref: GraphRef::from_str("mygraph@current").unwrap(),
+            proposals: Vec::new(),
+            total_count: 0,
+        });
+        assert_that!(output.text())
+            .is_equal_to("No proposals found for mygraph@current.".to_string());
+    }
+
+    #[test]
+    fn details_text_includes_the_latest_revision_and_reviews() {
+        let text = ProposalDetailsOutput(details()).text();
+        assert_that!(text).contains("Status: OPEN");
+        assert_that!(text).contains("Launch: LAUNCH_COMPLETED");
+        assert_that!(text).contains("Grace: APPROVED");
+    }
+
+    #[test]
+    fn details_json_leaves_out_the_backing_variant() {
+        let json = ProposalDetailsOutput(details()).json().unwrap();
+        assert_that!(json["status"]).is_equal_to(json!("OPEN"));
+        assert_that!(json.get("backing_variant")).is_none();
+    }
+
+    #[test]
+    fn created_proposals_say_where_they_came_from() {
+        let output = ProposalRevisionOutput {
+            created_from: Some(GraphRef::from_str("mygraph@current").unwrap()),
+            response: ProposalReviseResponse {
+                proposal_id: "p-1".to_string(),
+                revision: revision(),
+            },
+        };
+        assert_that!(output.text()).contains("from mygraph@current");
+        let json = output.json().unwrap();
+        assert_that!(json["created"]).is_equal_to(json!(true));
+        assert_that!(json["revision"]["launch_id"]).is_equal_to(json!("launch-2"));
+    }
+
+    #[test]
+    fn status_change_text() {
+        let output = ProposalStatusChangeOutput(ProposalStatusResponse {
+            proposal_id: "p-1".to_string(),
+            previous_status: ProposalStatus::Draft,
+            status: ProposalStatus::Open,
+        });
+        assert_that!(output.text())
+            .is_equal_to("Moved proposal p-1 from DRAFT to OPEN".to_string());
+    }
+}
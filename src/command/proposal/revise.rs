@@ -0,0 +1,90 @@
+use clap::Parser;
+use rover_client::{
+    operations::proposal::revise::{self, ProposalReviseInput, ProposalSubgraphInput},
+    shared::GitContext,
+};
+use serde::Serialize;
+
+use super::output::ProposalRevisionOutput;
+use crate::{
+    RoverOutput, RoverResult,
+    options::{ProfileOpt, SubgraphOpt},
+    utils::{client::StudioClientConfig, parsers::FileDescriptorType},
+};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Revise {
+    /// The ID of the proposal
+    #[arg(value_name = "PROPOSAL_ID")]
+    proposal_id: String,
+
+    #[clap(flatten)]
+    subgraph: ProposalSubgraphOpts,
+
+    /// What changed in this revision
+    #[arg(long)]
+    summary: String,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+/// The subgraph schema a proposal revision is saved with
+#[derive(Debug, Serialize, Parser)]
+pub(super) struct ProposalSubgraphOpts {
+    #[clap(flatten)]
+    subgraph: SubgraphOpt,
+
+    /// The changed subgraph schema. You can pass `-` to use stdin instead of a file.
+    #[arg(long, short = 's', value_name = "SCHEMA_PATH")]
+    #[serde(skip_serializing)]
+    schema: FileDescriptorType,
+
+    /// The routing URL of the subgraph. Only needed for subgraphs the variant
+    /// doesn't have yet.
+    #[arg(long = "routing-url")]
+    #[serde(skip_serializing)]
+    routing_url: Option<String>,
+}
+
+impl ProposalSubgraphOpts {
+    pub(super) fn read(&self) -> RoverResult<ProposalSubgraphInput> {
+        Ok(ProposalSubgraphInput {
+            name: self.subgraph.subgraph_name.clone(),
+            schema: self
+                .schema
+                .read_file_descriptor("SDL", &mut std::io::stdin())?,
+            routing_url: self.routing_url.clone(),
+        })
+    }
+}
+
+impl Revise {
+    pub async fn run(
+        &self,
+        client_config: StudioClientConfig,
+        git_context: GitContext,
+    ) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let subgraph = self.subgraph.read()?;
+        eprintln!(
+            "Revising proposal {} with subgraph {} using credentials from the {} profile.",
+            self.proposal_id, subgraph.name, self.profile.profile_name
+        );
+
+        let response = revise::run(
+            ProposalReviseInput {
+                proposal_id: self.proposal_id.clone(),
+                subgraphs: vec![subgraph],
+                summary: self.summary.clone(),
+                git_context,
+            },
+            &client,
+        )
+        .await?;
+        Ok(RoverOutput::CliOutput(Box::new(ProposalRevisionOutput {
+            created_from: None,
+            response,
+        })))
+    }
+}
@@ -0,0 +1,62 @@
+use clap::Parser;
+use rover_client::operations::proposal::{
+    ProposalStatus,
+    describe::{self, ProposalDescribeInput},
+    status::{self, ProposalStatusInput},
+};
+use serde::Serialize;
+
+use super::output::{ProposalStatusChangeOutput, ProposalStatusOutput};
+use crate::{RoverOutput, RoverResult, options::ProfileOpt, utils::client::StudioClientConfig};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover proposal status <PROPOSAL_ID>\n    \
+    rover proposal status <PROPOSAL_ID> --set open")]
+pub struct Status {
+    /// The ID of the proposal
+    #[arg(value_name = "PROPOSAL_ID")]
+    proposal_id: String,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// Move the proposal to this status: draft, open, approved, implemented or closed.
+    /// Without it, the current status is shown.
+    #[arg(long = "set", value_name = "STATUS")]
+    status: Option<ProposalStatus>,
+}
+
+impl Status {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+
+        match &self.status {
+            Some(status) => {
+                let response = status::run(
+                    ProposalStatusInput {
+                        proposal_id: self.proposal_id.clone(),
+                        status: status.clone(),
+                    },
+                    &client,
+                )
+                .await?;
+                Ok(RoverOutput::CliOutput(Box::new(
+                    ProposalStatusChangeOutput(response),
+                )))
+            }
+            None => {
+                let details = describe::run(
+                    ProposalDescribeInput {
+                        proposal_id: self.proposal_id.clone(),
+                    },
+                    &client,
+                )
+                .await?;
+                Ok(RoverOutput::CliOutput(Box::new(ProposalStatusOutput(
+                    details,
+                ))))
+            }
+        }
+    }
+}
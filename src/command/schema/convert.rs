@@ -0,0 +1,131 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use clap::{Parser, ValueEnum};
+use rover_client::operations::graph::introspect::{introspection_to_sdl, sdl_to_introspection};
+use serde::Serialize;
+
+use super::read_sdl;
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaFormat {
+    /// GraphQL SDL
+    Sdl,
+    /// Introspection JSON, as a `__schema` object or a full response wrapped in `data`
+    Introspection,
+}
+
+#[derive(Debug, Serialize, Parser)]
+/// Convert a schema between SDL and introspection JSON
+///
+/// Conversion happens offline, without a running server. Descriptions,
+/// deprecations, `@specifiedBy` URLs, repeatable directives and `@oneOf` input
+/// types are kept in both directions.
+///
+/// Reads from a file or from stdin when no file is given (or when - is passed).
+#[command(after_help = "EXAMPLES:\n    \
+    rover schema convert --from sdl --to introspection schema.graphql --output schema.json\n    \
+    rover schema convert --from introspection --to sdl schema.json\n    \
+    cat schema.graphql | rover schema convert --from sdl --to introspection")]
+pub struct Convert {
+    /// File to read. Pass - or omit to read from stdin.
+    #[arg(value_name = "FILE")]
+    file: Option<PathBuf>,
+
+    /// The format of the input
+    #[arg(long, value_name = "FORMAT")]
+    from: SchemaFormat,
+
+    /// The format to convert to
+    #[arg(long, value_name = "FORMAT")]
+    to: SchemaFormat,
+}
+
+impl Convert {
+    pub async fn run(&self) -> RoverResult<RoverOutput> {
+        let (contents, label) = read_sdl(self.file.as_deref().unwrap_or_else(|| Path::new("-")))?;
+        let introspection = match self.from {
+            SchemaFormat::Sdl => sdl_to_introspection(&contents)?,
+            SchemaFormat::Introspection => serde_json::from_str(&contents).map_err(|e| {
+                RoverError::new(anyhow!("{label} is not valid JSON: {e}")).with_suggestion(
+                    RoverErrorSuggestion::Adhoc(
+                        "Pass `--from sdl` to convert a schema written in SDL.".to_string(),
+                    ),
+                )
+            })?,
+        };
+
+        Ok(match self.to {
+            SchemaFormat::Sdl => RoverOutput::Introspection(introspection_to_sdl(&introspection)?),
+            // Introspection JSON is always written as a bare `__schema` object,
+            // checked by converting it to SDL first.
+            SchemaFormat::Introspection => RoverOutput::IntrospectionJson(sdl_to_introspection(
+                &introspection_to_sdl(&introspection)?,
+            )?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const SDL: &str = "scalar DateTime @specifiedBy(url: \"https://tools.ietf.org/html/rfc3339\")\n\ntype Query {\n  now: DateTime\n}\n";
+
+    async fn convert(contents: &str, from: SchemaFormat, to: SchemaFormat) -> RoverOutput {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), contents).unwrap();
+        Convert {
+            file: Some(file.path().to_path_buf()),
+            from,
+            to,
+        }
+        .run()
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sdl_round_trips_through_introspection() {
+        let RoverOutput::IntrospectionJson(introspection) =
+            convert(SDL, SchemaFormat::Sdl, SchemaFormat::Introspection).await
+        else {
+            panic!("expected introspection JSON");
+        };
+        assert_that!(introspection["__schema"]["queryType"]["name"])
+            .is_equal_to(serde_json::json!("Query"));
+
+        let RoverOutput::Introspection(sdl) = convert(
+            &introspection.to_string(),
+            SchemaFormat::Introspection,
+            SchemaFormat::Sdl,
+        )
+        .await
+        else {
+            panic!("expected SDL");
+        };
+        assert_that!(sdl).is_equal_to(SDL.to_string());
+    }
+
+    #[rstest]
+    #[case::sdl(SchemaFormat::Sdl)]
+    #[case::introspection(SchemaFormat::Introspection)]
+    #[tokio::test]
+    async fn invalid_input_is_an_error(#[case] from: SchemaFormat) {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "type Query {").unwrap();
+        let result = Convert {
+            file: Some(file.path().to_path_buf()),
+            from,
+            to: SchemaFormat::Sdl,
+        }
+        .run()
+        .await;
+        assert_that!(result).is_err();
+    }
+}
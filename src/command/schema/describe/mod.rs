@@ -1,14 +1,11 @@
-use std::{
-    io::{self, Read},
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
-use rover_schema::{ParsedSchema, SchemaCoordinate};
-use rover_std::Fs;
+use rover_schema::{ParsedSchema, SchemaCoordinate, SchemaError};
 use serde::Serialize;
 
-use crate::{RoverOutput, RoverResult};
+use super::read_sdl;
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
 
 mod output;
 pub use output::DescribeOutput;
@@ -34,12 +31,17 @@ pub enum ViewMode {
 /// Displays a structured description of a GraphQL schema. Start with an
 /// overview, then zoom into individual types and fields.
 ///
+/// With --ownership, describes a supergraph's subgraphs instead: which subgraphs
+/// resolve each type and field, entity keys, `@requires`/`@provides`, `@override`
+/// labels, and `@inaccessible`/`@tag` usage.
+///
 /// Reads from a file or from stdin when no file is given (or when - is passed).
 #[command(after_help = "EXAMPLES:\n    \
     rover schema describe schema.graphql\n    \
     rover schema describe schema.graphql --coord Post\n    \
     rover schema describe schema.graphql --coord User.posts\n    \
     rover schema describe schema.graphql --coord Post --view sdl\n    \
+    rover schema describe supergraph.graphql --ownership --coord Product.price\n    \
     cat schema.graphql | rover schema describe\n    \
     rover schema describe -")]
 pub struct Describe {
@@ -72,14 +74,36 @@ pub struct Describe {
     /// Use the top-level --format json flag for machine-readable output.
     #[arg(long = "view", short = 'v', value_name = "VIEW")]
     view: Option<ViewMode>,
+
+    /// Show which subgraphs own each type and field of a supergraph, from its
+    /// `@join__*` directives. Supports type and field coordinates.
+    #[arg(long = "ownership", conflicts_with = "view")]
+    ownership: bool,
 }
 
 impl Describe {
     pub async fn run(&self) -> RoverResult<RoverOutput> {
-        let (sdl_string, source_label) = self.read_sdl()?;
+        let (sdl_string, source_label) =
+            read_sdl(self.file.as_deref().unwrap_or_else(|| Path::new("-")))?;
         let output_format = self.output_format();
         let schema = ParsedSchema::parse(&sdl_string, &source_label);
 
+        if self.ownership {
+            let output = schema
+                .ownership(self.schema_coordinate.as_ref())
+                .map_err(|e| match e {
+                    SchemaError::NotASupergraph => RoverError::new(anyhow::anyhow!("{}", e))
+                        .with_suggestion(RoverErrorSuggestion::Adhoc(
+                            "Pass a supergraph schema, such as the output of `rover supergraph compose` or `rover supergraph fetch`."
+                                .to_string(),
+                        )),
+                    e => RoverError::new(anyhow::anyhow!("{}", e)),
+                })?;
+            return Ok(RoverOutput::CliOutput(Box::new(DescribeOutput::Ownership(
+                output,
+            ))));
+        }
+
         if matches!(output_format, OutputFormat::Sdl) {
             let sdl = schema
                 .filtered_sdl(self.schema_coordinate.as_ref())
@@ -113,29 +137,6 @@ impl Describe {
             Some(ViewMode::Description) | None => OutputFormat::Description,
         }
     }
-
-    /// Returns `(sdl_contents, display_label)`.
-    fn read_sdl(&self) -> RoverResult<(String, String)> {
-        let use_stdin = match &self.file {
-            None => true,
-            Some(p) => p == Path::new("-"),
-        };
-
-        if use_stdin {
-            let mut sdl = String::new();
-            io::stdin()
-                .read_to_string(&mut sdl)
-                .map_err(|e| anyhow::anyhow!("failed to read from stdin: {}", e))?;
-            return Ok((sdl, "<stdin>".to_string()));
-        }
-
-        let path = self.file.as_ref().unwrap();
-        let utf8_path = camino::Utf8PathBuf::try_from(path.clone()).map_err(|p| {
-            anyhow::anyhow!("path '{}' contains invalid UTF-8", p.as_path().display())
-        })?;
-        let label = utf8_path.to_string();
-        Ok((Fs::read_file(utf8_path)?, label))
-    }
 }
 
 fn serialize_coord<S>(coord: &Option<SchemaCoordinate>, s: S) -> Result<S::Ok, S::Error>
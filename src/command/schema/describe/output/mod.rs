@@ -2,6 +2,7 @@ mod directive_arg_detail;
 mod directive_detail;
 mod field_arg_detail;
 mod field_detail;
+mod ownership;
 mod schema_overview;
 mod type_detail;
 
@@ -9,8 +10,10 @@ use directive_arg_detail::DirectiveArgDetailDisplay;
 use directive_detail::DirectiveDetailDisplay;
 use field_arg_detail::FieldArgDetailDisplay;
 use field_detail::FieldDetailDisplay;
+use ownership::OwnershipDisplay;
 use rover_schema::{
-    DirectiveArgDetail, DirectiveDetail, FieldArgDetail, FieldDetail, SchemaOverview, TypeDetail,
+    DirectiveArgDetail, DirectiveDetail, FieldArgDetail, FieldDetail, OwnershipOutput,
+    SchemaOverview, TypeDetail,
 };
 use schema_overview::SchemaOverviewDisplay;
 use serde::Serialize;
@@ -27,6 +30,7 @@ pub enum DescribeOutput {
     Directive(DirectiveDetail),
     DirectiveArg(DirectiveArgDetail),
     FieldArg(FieldArgDetail),
+    Ownership(OwnershipOutput),
 }
 
 impl CliOutput for DescribeOutput {
@@ -39,6 +43,7 @@ impl CliOutput for DescribeOutput {
             Self::Directive(d) => DirectiveDetailDisplay::from(d).display(),
             Self::DirectiveArg(d) => DirectiveArgDetailDisplay::from(d).display(),
             Self::FieldArg(d) => FieldArgDetailDisplay::from(d).display(),
+            Self::Ownership(o) => OwnershipDisplay::from(o).display(),
         }
     }
 
@@ -0,0 +1,255 @@
+use comfy_table::{Table, presets};
+use itertools::Itertools;
+use rover_schema::{
+    EntityKey, FieldOwnership, OwnershipOutput, SchemaOwnership, TypeInSubgraph, TypeOwnership,
+};
+
+pub struct OwnershipDisplay<'a> {
+    output: &'a OwnershipOutput,
+}
+
+impl<'a> OwnershipDisplay<'a> {
+    pub fn display(&self) -> String {
+        match self.output {
+            OwnershipOutput::Schema(ownership) => schema_display(ownership),
+            OwnershipOutput::Type(ownership) => type_display(ownership),
+            OwnershipOutput::Field(ownership) => field_display(ownership),
+        }
+    }
+}
+
+impl<'a> From<&'a OwnershipOutput> for OwnershipDisplay<'a> {
+    fn from(output: &'a OwnershipOutput) -> Self {
+        OwnershipDisplay { output }
+    }
+}
+
+fn schema_display(ownership: &SchemaOwnership) -> String {
+    let mut subgraphs = Table::new();
+    subgraphs.load_style(presets::ASCII_FULL);
+    subgraphs.set_header(["Subgraph", "URL"]);
+    for subgraph in &ownership.subgraphs {
+        subgraphs.add_row([
+            subgraph.name.as_str(),
+            subgraph.url.as_deref().unwrap_or_default(),
+        ]);
+    }
+
+    let mut types = Table::new();
+    types.load_style(presets::ASCII_FULL);
+    types.set_header(["Type", "Subgraphs", "Keys"]);
+    for ty in &ownership.types {
+        let keys = ty
+            .subgraphs
+            .iter()
+            .filter(|s| !s.keys.is_empty())
+            .map(|s| format!("{}: {}", s.subgraph, keys(&s.keys)))
+            .join("\n");
+        types.add_row([
+            ty.name.as_str(),
+            &ty.subgraphs.iter().map(|s| s.subgraph.as_str()).join(", "),
+            &keys,
+        ]);
+    }
+
+    [
+        format!("OWNERSHIP {}", ownership.schema_source),
+        format!("Subgraphs\n{subgraphs}"),
+        format!("Types\n{types}"),
+        "Pass --coord <TYPE> to see which subgraphs resolve each of its fields.".to_string(),
+    ]
+    .join("\n\n")
+}
+
+fn type_display(ownership: &TypeOwnership) -> String {
+    let mut subgraphs = Table::new();
+    subgraphs.load_style(presets::ASCII_FULL);
+    subgraphs.set_header(["Subgraph", "Keys", "Notes"]);
+    for subgraph in &ownership.subgraphs {
+        subgraphs.add_row([
+            subgraph.subgraph.as_str(),
+            &keys(&subgraph.keys),
+            &type_notes(subgraph),
+        ]);
+    }
+
+    let fields = (!ownership.fields.is_empty()).then(|| {
+        let mut table = Table::new();
+        table.load_style(presets::ASCII_FULL);
+        table.set_header(["Field", "Resolved by", "Notes"]);
+        for field in &ownership.fields {
+            table.add_row([
+                field.field_name.as_str(),
+                &field
+                    .subgraphs
+                    .iter()
+                    .map(|s| s.subgraph.as_str())
+                    .join(", "),
+                &field_notes(field),
+            ]);
+        }
+        format!("Fields\n{table}")
+    });
+
+    [
+        Some(format!("OWNERSHIP {}", ownership.name)),
+        usage(ownership.inaccessible, &ownership.tags),
+        Some(format!("Subgraphs\n{subgraphs}")),
+        fields,
+    ]
+    .into_iter()
+    .flatten()
+    .join("\n\n")
+}
+
+fn field_display(ownership: &FieldOwnership) -> String {
+    let mut table = Table::new();
+    table.load_style(presets::ASCII_FULL);
+    table.set_header(["Subgraph", "Requires", "Provides", "Override"]);
+    for subgraph in &ownership.subgraphs {
+        let overrides = [
+            subgraph
+                .overrides
+                .as_ref()
+                .map(|from| format!("from {from}")),
+            subgraph
+                .override_label
+                .as_ref()
+                .map(|label| format!("label {label}")),
+        ]
+        .into_iter()
+        .flatten()
+        .join(", ");
+        table.add_row([
+            subgraph.subgraph.as_str(),
+            subgraph.requires.as_deref().unwrap_or_default(),
+            subgraph.provides.as_deref().unwrap_or_default(),
+            &overrides,
+        ]);
+    }
+
+    [
+        Some(format!(
+            "OWNERSHIP {}.{}",
+            ownership.type_name, ownership.field_name
+        )),
+        usage(ownership.inaccessible, &ownership.tags),
+        Some(format!("Resolved by\n{table}")),
+        (!ownership.external_in.is_empty())
+            .then(|| format!("External in: {}", ownership.external_in.join(", "))),
+    ]
+    .into_iter()
+    .flatten()
+    .join("\n\n")
+}
+
+fn usage(inaccessible: bool, tags: &[String]) -> Option<String> {
+    let lines = [
+        inaccessible.then(|| "INACCESSIBLE".to_string()),
+        (!tags.is_empty()).then(|| format!("Tags: {}", tags.join(", "))),
+    ]
+    .into_iter()
+    .flatten()
+    .join("\n");
+    (!lines.is_empty()).then_some(lines)
+}
+
+fn keys(keys: &[EntityKey]) -> String {
+    keys.iter()
+        .map(|key| {
+            if key.resolvable {
+                key.fields.clone()
+            } else {
+                format!("{} (not resolvable)", key.fields)
+            }
+        })
+        .join(", ")
+}
+
+fn type_notes(subgraph: &TypeInSubgraph) -> String {
+    [
+        subgraph.extension.then_some("extension"),
+        subgraph.interface_object.then_some("interface object"),
+    ]
+    .into_iter()
+    .flatten()
+    .join(", ")
+}
+
+fn field_notes(field: &FieldOwnership) -> String {
+    let mut notes = Vec::new();
+    for subgraph in &field.subgraphs {
+        let name = &subgraph.subgraph;
+        if let Some(requires) = &subgraph.requires {
+            notes.push(format!("{name} requires {requires}"));
+        }
+        if let Some(provides) = &subgraph.provides {
+            notes.push(format!("{name} provides {provides}"));
+        }
+        if let Some(from) = &subgraph.overrides {
+            notes.push(match &subgraph.override_label {
+                Some(label) => format!("{name} overrides {from} at {label}"),
+                None => format!("{name} overrides {from}"),
+            });
+        }
+    }
+    if !field.external_in.is_empty() {
+        notes.push(format!("external in {}", field.external_in.join(", ")));
+    }
+    if field.inaccessible {
+        notes.push("inaccessible".to_string());
+    }
+    if !field.tags.is_empty() {
+        notes.push(format!("tags: {}", field.tags.join(", ")));
+    }
+    notes.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_compiler::coordinate::SchemaCoordinate;
+    use rover_schema::ParsedSchema;
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+
+    use super::OwnershipDisplay;
+
+    #[fixture]
+    fn supergraph() -> ParsedSchema {
+        let sdl =
+            include_str!("../../../../../crates/rover-schema/src/test_fixtures/supergraph.graphql");
+        ParsedSchema::parse(sdl, "supergraph.graphql")
+    }
+
+    fn display(schema: &ParsedSchema, coord: &str) -> String {
+        let coord: SchemaCoordinate = coord.parse().unwrap();
+        let output = schema.ownership(Some(&coord)).unwrap();
+        OwnershipDisplay::from(&output).display()
+    }
+
+    #[rstest]
+    fn field_output(supergraph: ParsedSchema) {
+        assert_that!(display(&supergraph, "Product.inStock")).is_equal_to(
+            "OWNERSHIP Product.inStock\n\n\
+             Resolved by\n\
+             +----------+----------+----------+----------------------------------+\n\
+             | Subgraph | Requires | Provides | Override                         |\n\
+             +===================================================================+\n\
+             | products |          |          | label percent(25)                |\n\
+             |----------+----------+----------+----------------------------------|\n\
+             | reviews  |          |          | from products, label percent(25) |\n\
+             +----------+----------+----------+----------------------------------+"
+                .to_string(),
+        );
+    }
+
+    #[rstest]
+    fn type_output_notes_keys_and_field_dependencies(supergraph: ParsedSchema) {
+        let out = display(&supergraph, "Product");
+        assert_that!(out).starts_with("OWNERSHIP Product\n\nTags: public\n\nSubgraphs\n");
+        assert_that!(out).contains("| reviews  | id, sku (not resolvable) |");
+        assert_that!(out).contains("reviews requires name");
+        assert_that!(out).contains("reviews overrides products at percent(25)");
+        assert_that!(out).contains("external in reviews");
+    }
+}
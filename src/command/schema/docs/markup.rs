@@ -0,0 +1,195 @@
+use itertools::Itertools;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum PageFormat {
+    /// Markdown pages, linked with relative `.md` links
+    Markdown,
+    /// Standalone HTML pages, linked with relative `.html` links
+    Html,
+}
+
+const HTML_STYLE: &str = "body { font-family: system-ui, sans-serif; line-height: 1.5; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; }\n\
+    code { font-family: ui-monospace, monospace; }\n\
+    table { border-collapse: collapse; }\n\
+    th, td { border: 1px solid #ccc; padding: 0.25rem 0.5rem; text-align: left; vertical-align: top; }\n\
+    .deprecated { border-left: 4px solid #d97706; padding-left: 0.75rem; }";
+
+/// Builds the pieces of a page in one of the [`PageFormat`]s.
+///
+/// Functions taking `text` escape it, while those taking `markup` expect output of other
+/// functions here.
+impl PageFormat {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            PageFormat::Markdown => "md",
+            PageFormat::Html => "html",
+        }
+    }
+
+    pub fn text(self, text: &str) -> String {
+        match self {
+            PageFormat::Markdown => text.to_string(),
+            PageFormat::Html => escape_html(text),
+        }
+    }
+
+    pub fn code(self, text: &str) -> String {
+        match self {
+            PageFormat::Markdown => format!("`{text}`"),
+            PageFormat::Html => format!("<code>{}</code>", escape_html(text)),
+        }
+    }
+
+    pub fn strong(self, text: &str) -> String {
+        match self {
+            PageFormat::Markdown => format!("**{text}**"),
+            PageFormat::Html => format!("<strong>{}</strong>", escape_html(text)),
+        }
+    }
+
+    pub fn link(self, markup: &str, href: &str) -> String {
+        match self {
+            PageFormat::Markdown => format!("[{markup}]({href})"),
+            PageFormat::Html => format!("<a href=\"{}\">{markup}</a>", escape_html(href)),
+        }
+    }
+
+    /// A heading, with an `id` to link to in HTML. Markdown renderers derive their own.
+    pub fn heading(self, level: usize, markup: &str, id: Option<&str>) -> String {
+        match self {
+            PageFormat::Markdown => format!("{} {markup}", "#".repeat(level)),
+            PageFormat::Html => match id {
+                Some(id) => format!("<h{level} id=\"{}\">{markup}</h{level}>", escape_html(id)),
+                None => format!("<h{level}>{markup}</h{level}>"),
+            },
+        }
+    }
+
+    pub fn paragraph(self, markup: &str) -> String {
+        match self {
+            PageFormat::Markdown => markup.to_string(),
+            PageFormat::Html => format!("<p>{markup}</p>"),
+        }
+    }
+
+    /// A description from the schema. Descriptions are commonly written in Markdown, so they're
+    /// kept as they are in Markdown pages, and split into paragraphs in HTML pages.
+    pub fn description(self, text: &str) -> String {
+        match self {
+            PageFormat::Markdown => text.trim().to_string(),
+            PageFormat::Html => text
+                .split("\n\n")
+                .map(str::trim)
+                .filter(|paragraph| !paragraph.is_empty())
+                .map(|paragraph| self.paragraph(&escape_html(paragraph)))
+                .join("\n"),
+        }
+    }
+
+    pub fn deprecated(self, reason: Option<&str>) -> String {
+        let note = match reason {
+            Some(reason) => format!("{} {}", self.strong("Deprecated:"), self.text(reason)),
+            None => self.strong("Deprecated."),
+        };
+        match self {
+            PageFormat::Markdown => format!("> {}", note.replace('\n', "\n> ")),
+            PageFormat::Html => format!("<p class=\"deprecated\">{note}</p>"),
+        }
+    }
+
+    pub fn list(self, items: &[String]) -> String {
+        match self {
+            PageFormat::Markdown => items.iter().map(|item| format!("- {item}")).join("\n"),
+            PageFormat::Html => format!(
+                "<ul>\n{}\n</ul>",
+                items
+                    .iter()
+                    .map(|item| format!("<li>{item}</li>"))
+                    .join("\n")
+            ),
+        }
+    }
+
+    pub fn table(self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        match self {
+            PageFormat::Markdown => {
+                let cell = |markup: &String| markup.replace('\n', " ").replace('|', "\\|");
+                [
+                    format!("| {} |", headers.join(" | ")),
+                    format!("|{}", "---|".repeat(headers.len())),
+                ]
+                .into_iter()
+                .chain(
+                    rows.iter()
+                        .map(|row| format!("| {} |", row.iter().map(cell).join(" | "))),
+                )
+                .join("\n")
+            }
+            PageFormat::Html => {
+                let header = headers
+                    .iter()
+                    .map(|header| format!("<th>{}</th>", escape_html(header)))
+                    .join("");
+                let rows = rows
+                    .iter()
+                    .map(|row| {
+                        format!(
+                            "<tr>{}</tr>",
+                            row.iter().map(|cell| format!("<td>{cell}</td>")).join("")
+                        )
+                    })
+                    .join("\n");
+                format!("<table>\n<tr>{header}</tr>\n{rows}\n</table>")
+            }
+        }
+    }
+
+    /// A complete page made of `blocks` of markup.
+    pub fn page(self, title: &str, blocks: &[String]) -> String {
+        match self {
+            PageFormat::Markdown => format!("{}\n", blocks.join("\n\n")),
+            PageFormat::Html => format!(
+                "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{HTML_STYLE}\n</style>\n</head>\n<body>\n<main>\n{}\n</main>\n</body>\n</html>\n",
+                escape_html(title),
+                blocks.join("\n")
+            ),
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn html_escapes_text_but_not_markup() {
+        let format = PageFormat::Html;
+        let link = format.link(&format.code("[User!]"), "User.html");
+        assert_that!(link)
+            .is_equal_to("<a href=\"User.html\"><code>[User!]</code></a>".to_string());
+        assert_that!(format.description("Uses <b> tags\n\nand & more"))
+            .is_equal_to("<p>Uses &lt;b&gt; tags</p>\n<p>and &amp; more</p>".to_string());
+    }
+
+    #[test]
+    fn markdown_tables_keep_cells_on_one_line() {
+        let table = PageFormat::Markdown.table(
+            &["Value", "Description"],
+            &[vec!["`A`".to_string(), "first | line\nsecond".to_string()]],
+        );
+        assert_that!(table).is_equal_to(
+            "| Value | Description |\n|---|---|\n| `A` | first \\| line second |".to_string(),
+        );
+    }
+}
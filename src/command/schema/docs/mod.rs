@@ -0,0 +1,144 @@
+mod markup;
+mod output;
+mod pages;
+
+use std::path::PathBuf;
+
+use camino::Utf8PathBuf;
+use clap::Parser;
+use rover_schema::ParsedSchema;
+use rover_std::Fs;
+use serde::Serialize;
+
+use self::{markup::PageFormat, output::DocsOutput, pages::Pages};
+use super::read_piped_sdl;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Write a static, cross-linked reference for a GraphQL schema
+///
+/// Writes an index page, and a page for each type with its description, fields,
+/// arguments, deprecations, and the paths from the root types that reach it.
+/// Every mention of a type links to its page.
+///
+/// For a supergraph, pages also list the subgraphs that resolve each type and
+/// field, and the types federation adds are left out.
+///
+/// Pass `-` as FILE to read an SDL as a file from stdin.
+#[command(after_help = "EXAMPLES:\n    \
+    rover schema docs schema.graphql --out-dir docs\n    \
+    rover schema docs supergraph.graphql --out-dir public --page-format html --title \"Acme API\"\n    \
+    cat schema.graphql | rover schema docs - --out-dir docs")]
+pub struct Docs {
+    /// SDL file to read. Pass - to read from stdin.
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+
+    /// The directory to write pages to, which is created if it doesn't exist.
+    /// Existing pages with the same names are overwritten.
+    #[arg(long, value_name = "DIR")]
+    out_dir: Utf8PathBuf,
+
+    /// The format of the pages
+    #[arg(long, value_name = "FORMAT", default_value = "markdown")]
+    page_format: PageFormat,
+
+    /// The title of the index page
+    #[arg(long, default_value = "Schema reference")]
+    title: String,
+}
+
+impl Docs {
+    pub async fn run(&self) -> RoverResult<RoverOutput> {
+        let (sdl, label) = read_piped_sdl(&self.file)?;
+        let schema = ParsedSchema::parse(&sdl, &label);
+        let reference = schema.reference();
+        if reference.types.is_empty() {
+            return Err(anyhow::anyhow!("{label} doesn't define any types to document").into());
+        }
+
+        let pages = Pages::new(&reference, self.page_format);
+        Fs::create_dir_all(&self.out_dir)?;
+        let index = self.out_dir.join(pages.index_file_name());
+        Fs::write_file(&index, pages.index(&self.title))?;
+        let mut written = vec![index.clone()];
+        for ty in &reference.types {
+            let path = self.out_dir.join(pages.file_name(ty.name()));
+            Fs::write_file(&path, pages.type_page(ty))?;
+            written.push(path);
+        }
+
+        Ok(RoverOutput::CliOutput(Box::new(DocsOutput {
+            out_dir: self.out_dir.clone(),
+            page_format: self.page_format,
+            index,
+            pages: written,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const SUPERGRAPH: &str =
+        include_str!("../../../../crates/rover-schema/src/test_fixtures/supergraph.graphql");
+
+    async fn write_docs(page_format: PageFormat) -> (tempfile::TempDir, Utf8PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let schema = dir.path().join("supergraph.graphql");
+        std::fs::write(&schema, SUPERGRAPH).unwrap();
+        let out_dir = Utf8PathBuf::try_from(dir.path().join("docs")).unwrap();
+        Docs {
+            file: schema,
+            out_dir: out_dir.clone(),
+            page_format,
+            title: "Schema reference".to_string(),
+        }
+        .run()
+        .await
+        .unwrap();
+        (dir, out_dir)
+    }
+
+    #[tokio::test]
+    async fn writes_a_markdown_page_per_type() {
+        let (_dir, out_dir) = write_docs(PageFormat::Markdown).await;
+        let mut files: Vec<_> = std::fs::read_dir(&out_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        assert_that!(files).is_equal_to(
+            ["Product.md", "Query.md", "Review.md", "index.md"]
+                .map(String::from)
+                .to_vec(),
+        );
+
+        let index = std::fs::read_to_string(out_dir.join("index.md")).unwrap();
+        assert_that!(index).contains("**Subgraphs:** `products`, `reviews`");
+        assert_that!(index).contains("## Root types\n\n- [`Query`](Query.md)");
+        assert_that!(index).contains("- [`Product`](Product.md): A product in the catalog");
+
+        let product = std::fs::read_to_string(out_dir.join("Product.md")).unwrap();
+        assert_that!(product).contains("### `reviews`\n\n**Returns:** [`[Review!]!`](Review.md)");
+        assert_that!(product)
+            .contains("| `first` | `Int` | `5` |  |\n\n**Resolved by:** `reviews`");
+        assert_that!(product).contains("> **Deprecated:** Use cost instead");
+        assert_that!(product).contains("- [`Query.product`](Query.md#product)");
+    }
+
+    #[tokio::test]
+    async fn writes_standalone_html_pages() {
+        let (_dir, out_dir) = write_docs(PageFormat::Html).await;
+        let review = std::fs::read_to_string(out_dir.join("Review.html")).unwrap();
+        assert_that!(review).starts_with("<!DOCTYPE html>");
+        assert_that!(review).contains(
+            "<p><strong>Returns:</strong> <a href=\"Product.html\"><code>Product!</code></a></p>",
+        );
+        assert_that!(review).contains("<h3 id=\"body\"><code>body</code></h3>");
+    }
+}
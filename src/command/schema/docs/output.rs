@@ -0,0 +1,30 @@
+use camino::Utf8PathBuf;
+use serde::Serialize;
+
+use super::markup::PageFormat;
+use crate::command::CliOutput;
+
+#[derive(Debug, Serialize)]
+pub struct DocsOutput {
+    pub out_dir: Utf8PathBuf,
+    pub page_format: PageFormat,
+    /// The index page, which links to every other page
+    pub index: Utf8PathBuf,
+    /// Every page written, including the index
+    pub pages: Vec<Utf8PathBuf>,
+}
+
+impl CliOutput for DocsOutput {
+    fn text(&self) -> String {
+        format!(
+            "Wrote {} page(s) to {}. Start from {}",
+            self.pages.len(),
+            self.out_dir,
+            self.index
+        )
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
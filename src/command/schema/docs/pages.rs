@@ -0,0 +1,319 @@
+use std::collections::HashSet;
+
+use apollo_compiler::{Name, ast::Type};
+use itertools::Itertools;
+use rover_schema::{
+    FieldReference, SchemaReference, TypeDetail, TypeReference, root_paths::RootPath,
+};
+
+use super::markup::PageFormat;
+
+/// Writes the pages of a [`SchemaReference`], linking every mention of a documented type to its
+/// page.
+pub(super) struct Pages<'a> {
+    reference: &'a SchemaReference,
+    format: PageFormat,
+    documented: HashSet<&'a str>,
+}
+
+impl<'a> Pages<'a> {
+    pub fn new(reference: &'a SchemaReference, format: PageFormat) -> Self {
+        let documented = reference
+            .types
+            .iter()
+            .map(|ty| ty.name().as_str())
+            .collect();
+        Self {
+            reference,
+            format,
+            documented,
+        }
+    }
+
+    pub fn file_name(&self, type_name: &str) -> String {
+        format!("{type_name}.{}", self.format.extension())
+    }
+
+    pub fn index_file_name(&self) -> String {
+        format!("index.{}", self.format.extension())
+    }
+
+    pub fn index(&self, title: &str) -> String {
+        let f = self.format;
+        let mut blocks = vec![f.heading(1, &f.text(title), None)];
+        if !self.reference.subgraphs.is_empty() {
+            blocks.push(f.paragraph(&format!(
+                "{} {}",
+                f.strong("Subgraphs:"),
+                self.reference.subgraphs.iter().map(|s| f.code(s)).join(", ")
+            )));
+        }
+
+        let is_root = |ty: &&TypeReference| self.reference.root_types.contains(ty.name());
+        let roots = self
+            .reference
+            .root_types
+            .iter()
+            .filter_map(|name| self.reference.types.iter().find(|ty| ty.name() == name))
+            .collect::<Vec<_>>();
+        let sections: [(&str, Vec<&TypeReference>); 7] = [
+            ("Root types", roots),
+            (
+                "Objects",
+                self.of_kind(|d| matches!(d, TypeDetail::Object(_)), is_root),
+            ),
+            (
+                "Interfaces",
+                self.of_kind(|d| matches!(d, TypeDetail::Interface(_)), is_root),
+            ),
+            (
+                "Unions",
+                self.of_kind(|d| matches!(d, TypeDetail::Union(_)), is_root),
+            ),
+            (
+                "Enums",
+                self.of_kind(|d| matches!(d, TypeDetail::Enum(_)), is_root),
+            ),
+            (
+                "Input types",
+                self.of_kind(|d| matches!(d, TypeDetail::Input(_)), is_root),
+            ),
+            (
+                "Scalars",
+                self.of_kind(|d| matches!(d, TypeDetail::Scalar(_)), is_root),
+            ),
+        ];
+        for (heading, types) in sections {
+            if types.is_empty() {
+                continue;
+            }
+            blocks.push(f.heading(2, &f.text(heading), None));
+            let items = types
+                .iter()
+                .map(|ty| {
+                    let link = self.type_link(ty.name());
+                    match description(&ty.detail).and_then(|d| d.lines().next()) {
+                        Some(summary) => format!("{link}: {}", f.text(summary)),
+                        None => link,
+                    }
+                })
+                .collect::<Vec<_>>();
+            blocks.push(f.list(&items));
+        }
+        f.page(title, &blocks)
+    }
+
+    fn of_kind(
+        &self,
+        kind: impl Fn(&TypeDetail) -> bool,
+        is_root: impl Fn(&&TypeReference) -> bool,
+    ) -> Vec<&'a TypeReference> {
+        self.reference
+            .types
+            .iter()
+            .filter(|ty| kind(&ty.detail) && !is_root(ty))
+            .collect()
+    }
+
+    pub fn type_page(&self, ty: &TypeReference) -> String {
+        let f = self.format;
+        let name = ty.name();
+        let mut blocks = vec![
+            f.heading(1, &f.code(name), None),
+            f.paragraph(&format!(
+                "{} · {}",
+                f.text(kind(&ty.detail)),
+                f.link(&f.text("Schema reference"), &self.index_file_name())
+            )),
+        ];
+        if let Some(description) = description(&ty.detail) {
+            blocks.push(f.description(description));
+        }
+        let (implements, related) = match &ty.detail {
+            TypeDetail::Object(d) => (d.implements.as_slice(), None),
+            TypeDetail::Interface(d) => (
+                d.implements.as_slice(),
+                Some(("Implemented by", d.implementors.as_slice())),
+            ),
+            TypeDetail::Union(d) => (&[][..], Some(("Members", d.members.as_slice()))),
+            _ => (&[][..], None),
+        };
+        if !implements.is_empty() {
+            blocks.push(f.paragraph(&format!(
+                "{} {}",
+                f.strong("Implements:"),
+                implements.iter().map(|i| self.type_link(i)).join(", ")
+            )));
+        }
+        if !ty.owners.is_empty() {
+            blocks.push(f.paragraph(&format!(
+                "{} {}",
+                f.strong("Subgraphs:"),
+                ty.owners.iter().map(|s| f.code(s)).join(", ")
+            )));
+        }
+
+        if !ty.fields.is_empty() {
+            blocks.push(f.heading(2, &f.text("Fields"), None));
+            for field in &ty.fields {
+                blocks.extend(self.field(ty, field));
+            }
+        }
+        if let TypeDetail::Enum(d) = &ty.detail {
+            blocks.push(f.heading(2, &f.text("Values"), None));
+            let rows = d
+                .values
+                .iter()
+                .map(|value| {
+                    let deprecation = value.is_deprecated.then(|| {
+                        format!(
+                            "{} {}",
+                            f.strong("Deprecated:"),
+                            f.text(value.deprecation_reason.as_deref().unwrap_or_default())
+                        )
+                    });
+                    let notes = deprecation
+                        .into_iter()
+                        .chain(value.description.as_deref().map(|d| f.text(d)))
+                        .join(" ");
+                    vec![f.code(&value.name), notes]
+                })
+                .collect::<Vec<_>>();
+            blocks.push(f.table(&["Value", "Description"], &rows));
+        }
+        if let Some((heading, names)) = related
+            && !names.is_empty()
+        {
+            blocks.push(f.heading(2, &f.text(heading), None));
+            blocks.push(f.list(&names.iter().map(|n| self.type_link(n)).collect::<Vec<_>>()));
+        }
+
+        let via = via(&ty.detail);
+        if !via.is_empty() {
+            blocks.push(f.heading(2, &f.text("Reachable from"), None));
+            blocks.push(f.list(&via.iter().map(|p| self.root_path(p)).collect::<Vec<_>>()));
+        }
+        f.page(name, &blocks)
+    }
+
+    fn field(&self, ty: &TypeReference, field: &FieldReference) -> Vec<String> {
+        let f = self.format;
+        let mut blocks = vec![f.heading(3, &f.code(&field.name), Some(&field.name.to_lowercase()))];
+        let label = if matches!(ty.detail, TypeDetail::Input(_)) {
+            "Type:"
+        } else {
+            "Returns:"
+        };
+        let mut signature = format!("{} {}", f.strong(label), self.type_ref(&field.field_type));
+        if let Some(default) = &field.default_value {
+            signature.push_str(&format!(" = {}", f.code(default)));
+        }
+        blocks.push(f.paragraph(&signature));
+        if let Some(description) = &field.description {
+            blocks.push(f.description(description));
+        }
+        if field.is_deprecated {
+            blocks.push(f.deprecated(field.deprecation_reason.as_deref()));
+        }
+        if !field.arguments.is_empty() {
+            let rows = field
+                .arguments
+                .iter()
+                .map(|arg| {
+                    vec![
+                        f.code(&arg.arg_name),
+                        self.type_ref(&arg.arg_type),
+                        arg.default_value
+                            .as_deref()
+                            .map(|d| f.code(d))
+                            .unwrap_or_default(),
+                        arg.description
+                            .as_deref()
+                            .map(|d| f.text(d))
+                            .unwrap_or_default(),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            blocks.push(f.table(&["Argument", "Type", "Default", "Description"], &rows));
+        }
+        if !field.owners.is_empty() {
+            blocks.push(f.paragraph(&format!(
+                "{} {}",
+                f.strong("Resolved by:"),
+                field.owners.iter().map(|s| f.code(s)).join(", ")
+            )));
+        }
+        blocks
+    }
+
+    /// A full type such as `[Review!]!`, linked to the page of its named type.
+    fn type_ref(&self, ty: &Type) -> String {
+        let code = self.format.code(&ty.to_string());
+        let named = ty.inner_named_type();
+        if self.documented.contains(named.as_str()) {
+            self.format.link(&code, &self.file_name(named))
+        } else {
+            code
+        }
+    }
+
+    fn type_link(&self, name: &Name) -> String {
+        self.type_ref(&Type::Named(name.clone()))
+    }
+
+    /// A root path such as `Query.viewer -> Viewer.preferences`, linking each field to its
+    /// heading on its type's page.
+    fn root_path(&self, path: &RootPath) -> String {
+        path.segments
+            .iter()
+            .map(|segment| {
+                let code = self
+                    .format
+                    .code(&format!("{}.{}", segment.type_name, segment.field_name));
+                if self.documented.contains(segment.type_name.as_str()) {
+                    let href = format!(
+                        "{}#{}",
+                        self.file_name(&segment.type_name),
+                        segment.field_name.to_lowercase()
+                    );
+                    self.format.link(&code, &href)
+                } else {
+                    code
+                }
+            })
+            .join(" -> ")
+    }
+}
+
+const fn kind(detail: &TypeDetail) -> &'static str {
+    match detail {
+        TypeDetail::Object(_) => "object",
+        TypeDetail::Interface(_) => "interface",
+        TypeDetail::Input(_) => "input",
+        TypeDetail::Enum(_) => "enum",
+        TypeDetail::Union(_) => "union",
+        TypeDetail::Scalar(_) => "scalar",
+    }
+}
+
+fn description(detail: &TypeDetail) -> Option<&str> {
+    match detail {
+        TypeDetail::Object(d) => d.description.as_deref(),
+        TypeDetail::Interface(d) => d.description.as_deref(),
+        TypeDetail::Input(d) => d.description.as_deref(),
+        TypeDetail::Enum(d) => d.description.as_deref(),
+        TypeDetail::Union(d) => d.description.as_deref(),
+        TypeDetail::Scalar(d) => d.description.as_deref(),
+    }
+}
+
+fn via(detail: &TypeDetail) -> &[RootPath] {
+    match detail {
+        TypeDetail::Object(d) => &d.via,
+        TypeDetail::Interface(d) => &d.via,
+        TypeDetail::Input(d) => &d.via,
+        TypeDetail::Enum(d) => &d.via,
+        TypeDetail::Union(d) => &d.via,
+        TypeDetail::Scalar(d) => &d.via,
+    }
+}
@@ -1,7 +1,16 @@
+mod convert;
 mod describe;
+mod docs;
 mod search;
+mod stats;
+
+use std::{
+    io::{self, Read},
+    path::Path,
+};
 
 use clap::Parser;
+use rover_std::Fs;
 use serde::Serialize;
 
 use crate::{RoverOutput, RoverResult, utils::client::StudioClientConfig};
@@ -15,17 +24,54 @@ pub struct Schema {
 
 #[derive(Debug, Serialize, Parser)]
 pub enum Command {
+    /// Convert a schema between SDL and introspection JSON
+    Convert(convert::Convert),
     /// Describe a graph's schema by type or field
     Describe(describe::Describe),
-    /// Search a schema for types and fields by keyword
+    /// Write a static, cross-linked Markdown or HTML reference for a schema
+    Docs(docs::Docs),
+    /// Search a schema, or every subgraph in a supergraph config, for types and fields
     Search(search::Search),
+    /// Report size and quality statistics for a schema
+    Stats(stats::Stats),
 }
 
 impl Schema {
-    pub async fn run(&self, _client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         match &self.command {
+            Command::Convert(command) => command.run().await,
             Command::Describe(command) => command.run().await,
-            Command::Search(command) => command.run().await,
+            Command::Docs(command) => command.run().await,
+            Command::Search(command) => command.run(client_config).await,
+            Command::Stats(command) => command.run().await,
         }
     }
 }
+
+/// Reads a schema from `file`, or from stdin when `file` is `-`, along with a label for it to
+/// use in output.
+fn read_sdl(file: &Path) -> RoverResult<(String, String)> {
+    if file == Path::new("-") {
+        let mut sdl = String::new();
+        io::stdin()
+            .read_to_string(&mut sdl)
+            .map_err(|e| anyhow::anyhow!("failed to read from stdin: {}", e))?;
+        return Ok((sdl, "<stdin>".to_string()));
+    }
+
+    let utf8_path = camino::Utf8PathBuf::try_from(file.to_path_buf())
+        .map_err(|p| anyhow::anyhow!("path '{}' contains invalid UTF-8", p.as_path().display()))?;
+    let label = utf8_path.to_string();
+    Ok((Fs::read_file(utf8_path)?, label))
+}
+
+/// Like [`read_sdl`], but errors rather than waiting on stdin when it's a terminal.
+fn read_piped_sdl(file: &Path) -> RoverResult<(String, String)> {
+    if file == Path::new("-") && io::IsTerminal::is_terminal(&io::stdin()) {
+        return Err(anyhow::anyhow!(
+            "stdin is a terminal — pipe a schema file or pass a file path instead of -"
+        )
+        .into());
+    }
+    read_sdl(file)
+}
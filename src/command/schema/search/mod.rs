@@ -1,17 +1,22 @@
 mod output;
 
-use std::{
-    io::{self, Read},
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
+use anyhow::anyhow;
+use apollo_compiler::ast;
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::Parser;
-use rover_schema::ParsedSchema;
-use rover_std::Fs;
+use rover_schema::{OperationUsage, ParsedSchema, SearchQuery, search_subgraphs};
+use rover_std::{Fs, warnln};
 use serde::Serialize;
 
 use self::output::SearchOutput;
-use crate::{RoverOutput, RoverResult};
+use super::read_piped_sdl;
+use crate::{
+    RoverOutput, RoverResult,
+    options::{FileDiscoveryOpt, ProfileOpt},
+    utils::client::StudioClientConfig,
+};
 
 #[derive(Debug, Serialize, Parser)]
 /// Search a GraphQL schema for types and fields by keyword
@@ -27,6 +32,17 @@ use crate::{RoverOutput, RoverResult};
 ///                shorter terms must match a token exactly)
 ///   Description  appears in the SDL description (with no name match)
 ///
+/// Clauses can also filter results with `key:value` terms, which every result of
+/// the clause must satisfy. A clause of only filters matches everything they accept:
+///
+///   kind:        type, field, input_field or enum_value
+///   type:        the type a field returns (list and non-null wrappers ignored)
+///   deprecated:  true or false (true searches deprecated elements too)
+///   directive:   a directive applied to the element, such as @key
+///
+/// Pass a supergraph config (`.yaml` or `.yml`) as FILE to search the schema of
+/// every subgraph in it, labelling each result with its subgraph.
+///
 /// Pass `-` as FILE to read an SDL as a file from stdin.
 #[command(after_help = "EXAMPLES:\n    \
     rover schema search schema.graphql email\n    \
@@ -34,13 +50,18 @@ use crate::{RoverOutput, RoverResult};
     rover schema search schema.graphql \"email, displayName\"\n    \
     cat schema.graphql | rover schema search - user\n    \
     rover schema search schema.graphql author --limit 20\n    \
-    rover schema search schema.graphql id --include-deprecated")]
+    rover schema search schema.graphql id --include-deprecated\n    \
+    rover schema search schema.graphql kind:field type:ID deprecated:true\n    \
+    rover schema search supergraph.yaml email --limit 50\n    \
+    rover schema search supergraph.yaml directive:@key\n    \
+    rover schema search schema.graphql email --operations src/graphql")]
 pub struct Search {
-    /// SDL file to read. Pass - to read from stdin.
+    /// SDL file to read, or a supergraph config to search every subgraph of. Pass - to read
+    /// an SDL from stdin.
     #[arg(value_name = "FILE")]
     file: PathBuf,
 
-    /// Search terms. All terms must match (space-separated or quoted).
+    /// Search terms and filters. All terms must match (space-separated or quoted).
     #[arg(value_name = "TERMS", required = true)]
     terms: Vec<String>,
 
@@ -51,39 +72,134 @@ pub struct Search {
     /// Include deprecated fields and enum values in results.
     #[arg(long)]
     include_deprecated: bool,
+
+    /// A directory of operations (`.graphql` and `.gql` files). Each type and field in the
+    /// results lists the operations that select it.
+    #[arg(long, value_name = "DIR")]
+    operations: Option<Utf8PathBuf>,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
 }
 
 impl Search {
-    pub async fn run(&self) -> RoverResult<RoverOutput> {
-        let (sdl, label) = self.read_sdl()?;
-        let query = self.terms.join(" ");
-        let schema = ParsedSchema::parse(&sdl, &label);
-        let results = schema.search(&query, self.limit, self.include_deprecated);
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let query_text = self.terms.join(" ");
+        let query: SearchQuery = query_text.parse()?;
+        let schemas = match self.supergraph_config() {
+            Some(config) => self.resolve_subgraphs(client_config, config).await?,
+            None => {
+                let (sdl, label) = read_piped_sdl(&self.file)?;
+                vec![(None, ParsedSchema::parse(&sdl, &label))]
+            }
+        };
+
+        let mut results = match schemas.as_slice() {
+            [(None, schema)] => schema.search_query(&query, self.limit, self.include_deprecated),
+            _ => search_subgraphs(
+                schemas
+                    .iter()
+                    .map(|(name, schema)| (name.as_deref().unwrap_or_default(), schema)),
+                &query,
+                self.limit,
+                self.include_deprecated,
+            ),
+        };
+        if let Some(dir) = &self.operations {
+            let documents = read_operations(dir)?;
+            OperationUsage::collect(schemas.iter().map(|(_, schema)| schema), &documents)
+                .annotate(&mut results);
+        }
         Ok(RoverOutput::CliOutput(Box::new(SearchOutput {
-            query,
+            query: query_text,
             results,
         })))
     }
 
-    fn read_sdl(&self) -> RoverResult<(String, String)> {
-        if self.file == Path::new("-") {
-            if io::IsTerminal::is_terminal(&io::stdin()) {
-                return Err(anyhow::anyhow!(
-                    "stdin is a terminal — pipe a schema file or pass a file path instead of -"
-                )
-                .into());
+    fn supergraph_config(&self) -> Option<&Path> {
+        self.file
+            .extension()
+            .is_some_and(|extension| extension == "yaml" || extension == "yml")
+            .then_some(self.file.as_path())
+    }
+
+    /// Resolves every subgraph in the supergraph config the way `supergraph compose` does.
+    /// Subgraphs that can't be resolved are left out of the search with a warning.
+    async fn resolve_subgraphs(
+        &self,
+        client_config: StudioClientConfig,
+        config: &Path,
+    ) -> RoverResult<Vec<(Option<String>, ParsedSchema)>> {
+        let config = Utf8PathBuf::try_from(config.to_path_buf())
+            .map_err(|p| anyhow!("path '{}' contains invalid UTF-8", p.as_path().display()))?;
+        #[cfg(feature = "composition-js")]
+        {
+            use crate::{
+                composition::supergraph::config::sdl::resolve_subgraph_sdls,
+                utils::parsers::FileDescriptorType,
+            };
+
+            let (subgraphs, errors) = resolve_subgraph_sdls(
+                &client_config,
+                &self.profile,
+                &FileDescriptorType::File(config.clone()),
+            )
+            .await?;
+            for (name, error) in errors {
+                warnln!("Skipping subgraph {name}, as it couldn't be resolved: {error}");
             }
-            let mut sdl = String::new();
-            io::stdin()
-                .read_to_string(&mut sdl)
-                .map_err(|e| anyhow::anyhow!("failed to read from stdin: {}", e))?;
-            return Ok((sdl, "<stdin>".to_string()));
+            if subgraphs.is_empty() {
+                return Err(anyhow!("None of the subgraphs in {config} could be resolved").into());
+            }
+            Ok(subgraphs
+                .into_iter()
+                .map(|subgraph| {
+                    let schema = ParsedSchema::parse(subgraph.schema(), subgraph.name());
+                    (Some(subgraph.name().clone()), schema)
+                })
+                .collect())
+        }
+        #[cfg(not(feature = "composition-js"))]
+        {
+            let _ = (client_config, config);
+            Err(anyhow!(
+                "Searching a supergraph config resolves subgraphs with the supergraph config resolver, which this build of Rover doesn't include"
+            )
+            .into())
         }
+    }
+}
 
-        let utf8_path = camino::Utf8PathBuf::try_from(self.file.clone()).map_err(|p| {
-            anyhow::anyhow!("path '{}' contains invalid UTF-8", p.as_path().display())
-        })?;
-        let label = utf8_path.to_string();
-        Ok((Fs::read_file(utf8_path)?, label))
+/// Parses every operation document under `dir`. Files that fail to parse, or that hold schema
+/// definitions rather than operations, are skipped with a warning.
+fn read_operations(dir: &Utf8Path) -> RoverResult<Vec<ast::Document>> {
+    if !dir.is_dir() {
+        return Err(anyhow!("{dir} is not a directory").into());
+    }
+    let discovery = FileDiscoveryOpt {
+        include: Vec::new(),
+        exclude: Vec::new(),
+        root_dir: Some(dir.to_path_buf()),
+    };
+    let mut documents = Vec::new();
+    for path in discovery.find(&["graphql", "gql"])? {
+        let text = Fs::read_file(&path)?;
+        let document = match ast::Document::parse(text, &path) {
+            Ok(document) => document,
+            Err(_) => {
+                warnln!("Skipping {path}, as it couldn't be parsed");
+                continue;
+            }
+        };
+        if document
+            .definitions
+            .iter()
+            .any(|definition| !definition.is_executable_definition())
+        {
+            warnln!("Skipping {path}, as it contains schema definitions");
+            continue;
+        }
+        documents.push(document);
     }
+    Ok(documents)
 }
@@ -1,5 +1,5 @@
 use itertools::Itertools;
-use rover_schema::{SearchResult, root_paths::RootPath};
+use rover_schema::{OperationRef, SearchResult, root_paths::RootPath};
 use serde::Serialize;
 
 use crate::command::CliOutput;
@@ -39,14 +39,33 @@ fn format_result(r: &SearchResult) -> String {
         None => r.coordinate.to_string(),
     };
 
-    let kind_line = if r.via.is_empty() {
-        format!("  {}", r.kind)
-    } else {
+    let mut kind_line = format!("  {}", r.kind);
+    if let Some(subgraph) = &r.subgraph {
+        kind_line.push_str(&format!("  ·  in {subgraph}"));
+    }
+    if !r.via.is_empty() {
         let paths = r.via.iter().map(format_root_path).join(", ");
-        format!("  {}  ·  via {}", r.kind, paths)
-    };
+        kind_line.push_str(&format!("  ·  via {paths}"));
+    }
 
-    format!("{first_line}\n{kind_line}")
+    match &r.operations {
+        Some(operations) if operations.is_empty() => {
+            format!("{first_line}\n{kind_line}\n  not selected by any operation")
+        }
+        Some(operations) => format!(
+            "{first_line}\n{kind_line}\n  selected by {}",
+            operations.iter().map(format_operation).join(", ")
+        ),
+        None => format!("{first_line}\n{kind_line}"),
+    }
+}
+
+fn format_operation(operation: &OperationRef) -> String {
+    format!(
+        "{} ({})",
+        operation.name.as_deref().unwrap_or("<anonymous>"),
+        operation.file
+    )
 }
 
 fn format_root_path(p: &RootPath) -> String {
@@ -55,3 +74,44 @@ fn format_root_path(p: &RootPath) -> String {
         .map(|s| format!("{}.{}", s.type_name, s.field_name))
         .join(" -> ")
 }
+
+#[cfg(test)]
+mod tests {
+    use apollo_compiler::ast;
+    use rover_schema::{OperationUsage, ParsedSchema, search_subgraphs};
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn text_labels_subgraphs_and_selecting_operations() {
+        let accounts = ParsedSchema::parse(
+            "type Query { me: User } type User { id: ID! email: String! }",
+            "accounts.graphql",
+        );
+        let products = ParsedSchema::parse(
+            "type Query { top: Product } type Product { id: ID! email: String }",
+            "products.graphql",
+        );
+        let mut results = search_subgraphs(
+            [("accounts", &accounts), ("products", &products)],
+            &"email".parse().unwrap(),
+            10,
+            false,
+        );
+        let documents =
+            [ast::Document::parse("query Me { me { email } }", "ops/me.graphql").unwrap()];
+        OperationUsage::collect([&accounts, &products], &documents).annotate(&mut results);
+
+        let output = SearchOutput {
+            query: "email".to_string(),
+            results,
+        };
+        assert_that!(output.text()).is_equal_to(
+            "2 results for \"email\"\n\n\
+             Product.email\n  field  ·  in products  ·  via Query.top\n  not selected by any operation\n\n\
+             User.email\n  field  ·  in accounts  ·  via Query.me\n  selected by Me (ops/me.graphql)"
+                .to_string(),
+        );
+    }
+}
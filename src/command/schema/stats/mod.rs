@@ -0,0 +1,138 @@
+mod output;
+
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use clap::Parser;
+use rover_schema::ParsedSchema;
+use serde::Serialize;
+
+use self::output::StatsOutput;
+use super::read_piped_sdl;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// Tables for the terminal
+    Text,
+    /// Markdown tables, for release notes and pull requests
+    Markdown,
+}
+
+#[derive(Debug, Serialize, Parser)]
+/// Report size and quality statistics for a GraphQL schema
+///
+/// Counts types by kind, fields, arguments, enum values and deprecated elements,
+/// and the share of them with a description. Also lists custom scalars, the
+/// types furthest from a root type, the types with the most fields, and the
+/// most referenced types.
+///
+/// For a supergraph, the types federation adds are left out, and the report
+/// shows how many types and fields each subgraph contributes.
+///
+/// Pass `--compare` with an earlier version of the schema to show how each
+/// count has changed. Pass `--format json` for the statistics as JSON.
+///
+/// Pass `-` as FILE to read an SDL as a file from stdin.
+#[command(after_help = "EXAMPLES:\n    \
+    rover schema stats schema.graphql\n    \
+    rover schema stats supergraph.graphql --top 10\n    \
+    rover schema stats schema.graphql --compare schema.previous.graphql --report-format markdown\n    \
+    rover schema stats schema.graphql --format json > stats.json")]
+pub struct Stats {
+    /// SDL file to read. Pass - to read from stdin.
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+
+    /// An earlier version of the schema to compare against
+    #[arg(long, value_name = "OLD")]
+    compare: Option<PathBuf>,
+
+    /// The number of types to list in each ranking
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    top: usize,
+
+    /// The format of the report
+    #[arg(long, value_name = "FORMAT", default_value = "text")]
+    report_format: ReportFormat,
+}
+
+impl Stats {
+    pub async fn run(&self) -> RoverResult<RoverOutput> {
+        if self.compare.as_deref() == Some(Path::new("-")) {
+            return Err(anyhow!("--compare must be a file path, not stdin").into());
+        }
+        let (sdl, label) = read_piped_sdl(&self.file)?;
+        let stats = ParsedSchema::parse(&sdl, &label).stats(self.top);
+        let previous = match &self.compare {
+            Some(path) => {
+                let (sdl, label) = read_piped_sdl(path)?;
+                Some(ParsedSchema::parse(&sdl, &label).stats(self.top))
+            }
+            None => None,
+        };
+
+        Ok(RoverOutput::CliOutput(Box::new(StatsOutput {
+            stats,
+            previous,
+            report_format: self.report_format,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const SUPERGRAPH: &str =
+        include_str!("../../../../tests/fixtures/schema-stats/supergraph.graphql");
+
+    #[tokio::test]
+    async fn compares_against_an_earlier_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let current = dir.path().join("supergraph.graphql");
+        let previous = dir.path().join("previous.graphql");
+        std::fs::write(&current, SUPERGRAPH).unwrap();
+        std::fs::write(
+            &previous,
+            "type Query { product(id: ID!): Product } type Product { id: ID! }",
+        )
+        .unwrap();
+
+        let output = Stats {
+            file: current,
+            compare: Some(previous),
+            top: 3,
+            report_format: ReportFormat::Markdown,
+        }
+        .run()
+        .await
+        .unwrap();
+        let RoverOutput::CliOutput(output) = output else {
+            panic!("expected a CliOutput");
+        };
+        let report = output.text();
+        assert_that!(report).contains("| Metric | Current | Previous | Change |");
+        assert_that!(report).contains("| Types | 3 | 2 | +1 |");
+        assert_that!(report).contains("## Subgraphs");
+        assert_that!(output.json().unwrap()["previous"]["types"]["total"].as_u64())
+            .is_equal_to(Some(2));
+    }
+
+    #[tokio::test]
+    async fn rejects_stdin_for_the_earlier_schema() {
+        let err = Stats {
+            file: PathBuf::from("schema.graphql"),
+            compare: Some(PathBuf::from("-")),
+            top: 5,
+            report_format: ReportFormat::Text,
+        }
+        .run()
+        .await
+        .unwrap_err();
+        assert_that!(err.to_string()).contains("--compare must be a file path");
+    }
+}
@@ -0,0 +1,300 @@
+use comfy_table::{Table, presets};
+use itertools::Itertools;
+use rover_schema::{Coverage, SchemaStats};
+use serde::Serialize;
+
+use super::ReportFormat;
+use crate::command::CliOutput;
+
+#[derive(Debug, Serialize)]
+pub struct StatsOutput {
+    pub stats: SchemaStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<SchemaStats>,
+    #[serde(skip_serializing)]
+    pub report_format: ReportFormat,
+}
+
+/// A titled table of the report.
+struct Section {
+    title: &'static str,
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+/// Reads a summary metric from a schema's statistics.
+type MetricOf = fn(&SchemaStats) -> Metric;
+
+/// A value in the summary, which can be compared with the previous schema's.
+enum Metric {
+    Count(usize),
+    Share(Option<f64>),
+}
+
+impl CliOutput for StatsOutput {
+    fn text(&self) -> String {
+        let sections = self.sections();
+        match self.report_format {
+            ReportFormat::Text => {
+                let tables = sections.iter().map(|section| {
+                    let mut table = Table::new();
+                    table.load_style(presets::ASCII_FULL);
+                    table.set_header(&section.headers);
+                    for row in &section.rows {
+                        table.add_row(row);
+                    }
+                    format!("{}\n{table}", section.title)
+                });
+                [format!("SCHEMA {}", self.stats.schema_source)]
+                    .into_iter()
+                    .chain(tables)
+                    .join("\n\n")
+            }
+            ReportFormat::Markdown => {
+                let tables = sections.iter().map(|section| {
+                    let cell = |cell: &String| cell.replace('|', "\\|");
+                    [
+                        format!("## {}", section.title),
+                        String::new(),
+                        format!("| {} |", section.headers.join(" | ")),
+                        format!("|{}", "---|".repeat(section.headers.len())),
+                    ]
+                    .into_iter()
+                    .chain(
+                        section
+                            .rows
+                            .iter()
+                            .map(|row| format!("| {} |", row.iter().map(cell).join(" | "))),
+                    )
+                    .join("\n")
+                });
+                [format!(
+                    "# Schema statistics for `{}`",
+                    self.stats.schema_source
+                )]
+                .into_iter()
+                .chain(tables)
+                .join("\n\n")
+            }
+        }
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+impl StatsOutput {
+    fn sections(&self) -> Vec<Section> {
+        let stats = &self.stats;
+        let sections = [
+            self.summary(),
+            Section {
+                title: "Description coverage",
+                headers: vec!["Element", "Described", "Total", "Share"],
+                rows: [
+                    ("Types", stats.descriptions.types),
+                    ("Fields", stats.descriptions.fields),
+                    ("Input fields", stats.descriptions.input_fields),
+                    ("Arguments", stats.descriptions.arguments),
+                    ("Enum values", stats.descriptions.enum_values),
+                ]
+                .into_iter()
+                .filter(|(_, coverage)| coverage.total > 0)
+                .map(|(element, coverage)| {
+                    vec![
+                        element.to_string(),
+                        coverage.described.to_string(),
+                        coverage.total.to_string(),
+                        format_share(coverage.share()),
+                    ]
+                })
+                .collect(),
+            },
+            Section {
+                title: "Custom scalars",
+                headers: vec!["Scalar", "References", "Specified by"],
+                rows: stats
+                    .custom_scalars
+                    .iter()
+                    .map(|scalar| {
+                        vec![
+                            scalar.name.to_string(),
+                            scalar.references.to_string(),
+                            scalar.specified_by.clone().unwrap_or_default(),
+                        ]
+                    })
+                    .collect(),
+            },
+            Section {
+                title: "Deepest types",
+                headers: vec!["Type", "Depth"],
+                rows: stats
+                    .deepest_types
+                    .iter()
+                    .map(|ty| vec![ty.name.to_string(), ty.depth.to_string()])
+                    .collect(),
+            },
+            Section {
+                title: "Widest types",
+                headers: vec!["Type", "Fields"],
+                rows: stats
+                    .widest_types
+                    .iter()
+                    .map(|ty| vec![ty.name.to_string(), ty.fields.to_string()])
+                    .collect(),
+            },
+            Section {
+                title: "Most referenced types",
+                headers: vec!["Type", "References"],
+                rows: stats
+                    .most_referenced_types
+                    .iter()
+                    .map(|ty| vec![ty.name.to_string(), ty.references.to_string()])
+                    .collect(),
+            },
+            Section {
+                title: "Subgraphs",
+                headers: vec!["Subgraph", "Types", "Fields", "Exclusive fields"],
+                rows: stats
+                    .subgraphs
+                    .iter()
+                    .map(|subgraph| {
+                        vec![
+                            subgraph.name.clone(),
+                            subgraph.types.to_string(),
+                            subgraph.fields.to_string(),
+                            subgraph.exclusive_fields.to_string(),
+                        ]
+                    })
+                    .collect(),
+            },
+        ];
+        sections
+            .into_iter()
+            .filter(|section| !section.rows.is_empty())
+            .collect()
+    }
+
+    fn summary(&self) -> Section {
+        let metrics: [(&str, MetricOf); 14] = [
+            ("Types", |s| Metric::Count(s.types.total)),
+            ("Objects", |s| Metric::Count(s.types.objects)),
+            ("Interfaces", |s| Metric::Count(s.types.interfaces)),
+            ("Unions", |s| Metric::Count(s.types.unions)),
+            ("Enums", |s| Metric::Count(s.types.enums)),
+            ("Input types", |s| Metric::Count(s.types.inputs)),
+            ("Custom scalars", |s| Metric::Count(s.types.scalars)),
+            ("Fields", |s| Metric::Count(s.fields)),
+            ("Input fields", |s| Metric::Count(s.input_fields)),
+            ("Arguments", |s| Metric::Count(s.arguments)),
+            ("Enum values", |s| Metric::Count(s.enum_values)),
+            ("Deprecated", |s| Metric::Count(s.deprecated.total)),
+            ("Deprecated fields", |s| Metric::Count(s.deprecated.fields)),
+            ("Described", |s| {
+                Metric::Share(Coverage::share(&s.descriptions.overall))
+            }),
+        ];
+        let rows = metrics
+            .into_iter()
+            .map(|(label, metric)| {
+                let current = metric(&self.stats);
+                let mut row = vec![label.to_string(), current.to_string()];
+                if let Some(previous) = &self.previous {
+                    let previous = metric(previous);
+                    row.push(previous.to_string());
+                    row.push(current.change(&previous));
+                }
+                row
+            })
+            .collect();
+        Section {
+            title: "Summary",
+            headers: if self.previous.is_some() {
+                vec!["Metric", "Current", "Previous", "Change"]
+            } else {
+                vec!["Metric", "Count"]
+            },
+            rows,
+        }
+    }
+}
+
+impl Metric {
+    fn change(&self, previous: &Metric) -> String {
+        match (self, previous) {
+            (Metric::Count(current), Metric::Count(previous)) => {
+                match (*current as i64) - (*previous as i64) {
+                    0 => "0".to_string(),
+                    change => format!("{change:+}"),
+                }
+            }
+            (Metric::Share(Some(current)), Metric::Share(Some(previous))) => {
+                format!("{:+.1} pts", (current - previous) * 100.0)
+            }
+            _ => "-".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Metric::Count(count) => write!(f, "{count}"),
+            Metric::Share(share) => f.write_str(&format_share(*share)),
+        }
+    }
+}
+
+fn format_share(share: Option<f64>) -> String {
+    match share {
+        Some(share) => format!("{:.1}%", share * 100.0),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rover_schema::ParsedSchema;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn output(report_format: ReportFormat) -> StatsOutput {
+        let schema = ParsedSchema::parse(
+            r#"type Query { "A user" user(id: ID!): User } type User { id: ID! name: String @deprecated }"#,
+            "schema.graphql",
+        );
+        StatsOutput {
+            stats: schema.stats(5),
+            previous: None,
+            report_format,
+        }
+    }
+
+    #[test]
+    fn text_reports_are_tables() {
+        let text = output(ReportFormat::Text).text();
+        assert_that!(text).starts_with("SCHEMA schema.graphql\n\nSummary\n+");
+        assert_that!(text).contains("| Described         | 16.7% |");
+        assert_that!(text).does_not_contain("Custom scalars\n");
+    }
+
+    #[test]
+    fn markdown_reports_have_a_heading_per_section() {
+        let markdown = output(ReportFormat::Markdown).text();
+        assert_that!(markdown).starts_with(
+            "# Schema statistics for `schema.graphql`\n\n## Summary\n\n| Metric | Count |\n|---|---|\n| Types | 2 |",
+        );
+        assert_that!(markdown)
+            .contains("## Widest types\n\n| Type | Fields |\n|---|---|\n| User | 2 |");
+    }
+
+    #[test]
+    fn changes_are_signed() {
+        assert_that!(Metric::Count(3).change(&Metric::Count(5))).is_equal_to("-2".to_string());
+        assert_that!(Metric::Count(5).change(&Metric::Count(5))).is_equal_to("0".to_string());
+        assert_that!(Metric::Share(Some(0.5)).change(&Metric::Share(Some(0.25))))
+            .is_equal_to("+25.0 pts".to_string());
+    }
+}
@@ -0,0 +1,453 @@
+//! Shared pieces of `subgraph check` and `subgraph publish` when they run against
+//! every subgraph in a supergraph config (`--supergraph-config`).
+
+use std::{fmt::Display, future::Future, num::NonZeroUsize};
+
+use futures::{StreamExt, stream};
+use rover_client::{
+    RoverClientError, operations::subgraph::publish::SubgraphPublishResponse,
+    shared::CheckWorkflowResponse,
+};
+use rover_std::Style;
+use rover_studio::types::GraphRef;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+#[cfg(feature = "composition-js")]
+use crate::composition::supergraph::config::full::FullyResolvedSubgraph;
+use crate::{
+    RoverResult,
+    command::CliOutput,
+    options::ProfileOpt,
+    utils::{client::StudioClientConfig, parsers::FileDescriptorType},
+};
+
+/// A subgraph from the supergraph config, resolved down to its SDL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BatchSubgraph {
+    pub(crate) name: String,
+    pub(crate) routing_url: Option<String>,
+    pub(crate) schema: String,
+}
+
+#[cfg(feature = "composition-js")]
+impl From<FullyResolvedSubgraph> for BatchSubgraph {
+    fn from(subgraph: FullyResolvedSubgraph) -> Self {
+        BatchSubgraph {
+            name: subgraph.name().clone(),
+            routing_url: subgraph.routing_url().clone(),
+            schema: subgraph.schema().clone(),
+        }
+    }
+}
+
+/// Resolves every subgraph in a supergraph config with the same resolver
+/// `supergraph compose` uses. Subgraphs that can't be resolved come back as
+/// errored results rather than failing the whole run.
+#[cfg(feature = "composition-js")]
+pub(crate) async fn resolve_subgraphs(
+    client_config: &StudioClientConfig,
+    profile: &ProfileOpt,
+    supergraph_config: &FileDescriptorType,
+) -> RoverResult<(Vec<BatchSubgraph>, Vec<SubgraphBatchResult>)> {
+    use crate::composition::supergraph::config::sdl::resolve_subgraph_sdls;
+
+    let (subgraphs, errors) =
+        resolve_subgraph_sdls(client_config, profile, supergraph_config).await?;
+    let subgraphs = subgraphs.into_iter().map(BatchSubgraph::from).collect();
+    let errors = errors
+        .into_iter()
+        .map(|(name, err)| SubgraphBatchResult::errored(name, err))
+        .collect();
+    Ok((subgraphs, errors))
+}
+
+#[cfg(not(feature = "composition-js"))]
+pub(crate) async fn resolve_subgraphs(
+    _client_config: &StudioClientConfig,
+    _profile: &ProfileOpt,
+    _supergraph_config: &FileDescriptorType,
+) -> RoverResult<(Vec<BatchSubgraph>, Vec<SubgraphBatchResult>)> {
+    Err(anyhow::anyhow!(
+        "--supergraph-config resolves subgraphs with the supergraph config resolver, which this build of Rover doesn't include"
+    )
+    .into())
+}
+
+/// Runs `run` for every subgraph, with at most `max_concurrency` of them in flight at once
+pub(crate) async fn run_concurrently<F, Fut>(
+    subgraphs: Vec<BatchSubgraph>,
+    max_concurrency: NonZeroUsize,
+    run: F,
+) -> Vec<SubgraphBatchResult>
+where
+    F: FnMut(BatchSubgraph) -> Fut,
+    Fut: Future<Output = SubgraphBatchResult>,
+{
+    stream::iter(subgraphs)
+        .map(run)
+        .buffer_unordered(max_concurrency.get())
+        .collect()
+        .await
+}
+
+/// How a single subgraph fared, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SubgraphBatchStatus {
+    Passed,
+    Published,
+    Skipped,
+    Failed,
+    Errored,
+}
+
+impl SubgraphBatchStatus {
+    /// Checks that fail exit with 1, the same as a single `subgraph check`.
+    /// Subgraphs that couldn't be checked or published at all exit with 2.
+    const fn exit_code(self) -> i32 {
+        match self {
+            SubgraphBatchStatus::Passed
+            | SubgraphBatchStatus::Published
+            | SubgraphBatchStatus::Skipped => 0,
+            SubgraphBatchStatus::Failed => 1,
+            SubgraphBatchStatus::Errored => 2,
+        }
+    }
+
+    fn paint(self) -> String {
+        let style = match self {
+            SubgraphBatchStatus::Passed | SubgraphBatchStatus::Published => Style::Success,
+            SubgraphBatchStatus::Skipped => Style::Pending,
+            SubgraphBatchStatus::Failed | SubgraphBatchStatus::Errored => Style::Failure,
+        };
+        style.paint(self.to_string())
+    }
+}
+
+impl Display for SubgraphBatchStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            SubgraphBatchStatus::Passed => "passed",
+            SubgraphBatchStatus::Published => "published",
+            SubgraphBatchStatus::Skipped => "skipped",
+            SubgraphBatchStatus::Failed => "failed",
+            SubgraphBatchStatus::Errored => "errored",
+        };
+        write!(f, "{status}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SubgraphBatchResult {
+    pub(crate) subgraph: String,
+    pub(crate) status: SubgraphBatchStatus,
+    text: String,
+    json: Value,
+}
+
+impl SubgraphBatchResult {
+    /// Turns the outcome of a check workflow into a result. A failed workflow is a
+    /// failed check, and anything else that went wrong means the check didn't run.
+    pub(crate) fn check(
+        subgraph: String,
+        result: Result<CheckWorkflowResponse, RoverClientError>,
+    ) -> SubgraphBatchResult {
+        match result {
+            Ok(check_response) => SubgraphBatchResult {
+                subgraph,
+                status: SubgraphBatchStatus::Passed,
+                text: check_response.get_output(),
+                json: check_response.get_json(),
+            },
+            Err(RoverClientError::CheckWorkflowFailure { check_response, .. }) => {
+                SubgraphBatchResult {
+                    subgraph,
+                    status: SubgraphBatchStatus::Failed,
+                    text: check_response.get_output(),
+                    json: check_response.get_json(),
+                }
+            }
+            Err(err) => SubgraphBatchResult::errored(subgraph, err),
+        }
+    }
+
+    pub(crate) fn published(
+        subgraph: String,
+        graph_ref: &GraphRef,
+        publish_response: &SubgraphPublishResponse,
+    ) -> SubgraphBatchResult {
+        let mut lines = Vec::new();
+        if publish_response.subgraph_was_created {
+            lines.push(format!("A new subgraph was created in '{graph_ref}'"));
+        } else if publish_response.subgraph_was_updated {
+            lines.push(format!("The subgraph in '{graph_ref}' was updated"));
+        } else {
+            lines.push("The subgraph was NOT updated because no changes were detected".to_string());
+        }
+        if publish_response.supergraph_was_updated {
+            lines.push(format!(
+                "The supergraph schema for '{graph_ref}' was updated"
+            ));
+        }
+        if !publish_response.build_errors.is_empty() {
+            lines.push(format!(
+                "{} The following build errors occurred:\n{}",
+                Style::WarningPrefix.paint("WARN:"),
+                publish_response.build_errors
+            ));
+        }
+        SubgraphBatchResult {
+            subgraph,
+            status: SubgraphBatchStatus::Published,
+            text: lines.join("\n"),
+            json: json!(publish_response),
+        }
+    }
+
+    pub(crate) fn skipped(subgraph: String, reason: &str) -> SubgraphBatchResult {
+        SubgraphBatchResult {
+            subgraph,
+            status: SubgraphBatchStatus::Skipped,
+            text: reason.to_string(),
+            json: json!({ "reason": reason }),
+        }
+    }
+
+    pub(crate) fn errored(subgraph: String, err: impl Display) -> SubgraphBatchResult {
+        let message = err.to_string();
+        SubgraphBatchResult {
+            subgraph,
+            status: SubgraphBatchStatus::Errored,
+            json: json!({ "message": message }),
+            text: message,
+        }
+    }
+}
+
+/// [`CliOutput`] for `subgraph check` and `subgraph publish` with `--supergraph-config`
+#[derive(Debug)]
+pub(crate) struct SubgraphBatchOutput {
+    graph_ref: GraphRef,
+    results: Vec<SubgraphBatchResult>,
+}
+
+impl SubgraphBatchOutput {
+    pub(crate) fn new(graph_ref: GraphRef, mut results: Vec<SubgraphBatchResult>) -> Self {
+        results.sort_by(|a, b| a.subgraph.cmp(&b.subgraph));
+        SubgraphBatchOutput { graph_ref, results }
+    }
+
+    fn worst_status(&self) -> Option<SubgraphBatchStatus> {
+        self.results.iter().map(|result| result.status).max()
+    }
+}
+
+impl CliOutput for SubgraphBatchOutput {
+    fn exit_code(&self) -> i32 {
+        self.worst_status()
+            .map(SubgraphBatchStatus::exit_code)
+            .unwrap_or_default()
+    }
+
+    fn text(&self) -> String {
+        let mut sections = Vec::new();
+        for result in &self.results {
+            let heading = format!(
+                "{} {}",
+                Style::Heading.paint(&result.subgraph),
+                result.status.paint()
+            );
+            if result.text.is_empty() {
+                sections.push(heading);
+            } else {
+                sections.push(format!("{heading}\n{}", result.text.trim_end()));
+            }
+        }
+
+        let mut counts: Vec<(SubgraphBatchStatus, usize)> = Vec::new();
+        for result in &self.results {
+            match counts
+                .iter_mut()
+                .find(|(status, _)| *status == result.status)
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((result.status, 1)),
+            }
+        }
+        counts.sort();
+        let summary = counts
+            .iter()
+            .map(|(status, count)| format!("{count} {status}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let plural = if self.results.len() == 1 { "" } else { "s" };
+        sections.push(format!(
+            "{} subgraph{plural} against {}: {}",
+            self.results.len(),
+            Style::Link.paint(self.graph_ref.to_string()),
+            if summary.is_empty() {
+                "nothing to do".to_string()
+            } else {
+                summary
+            }
+        ));
+        sections.join("\n\n")
+    }
+
+    fn json(&self) -> Result<Value, serde_json::Error> {
+        Ok(json!({
+            "graph_ref": self.graph_ref.to_string(),
+            "subgraphs": self.results
+                .iter()
+                .map(|result| json!({
+                    "name": result.subgraph,
+                    "status": result.status,
+                    "result": result.json,
+                }))
+                .collect::<Vec<_>>(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use apollo_federation_types::rover::BuildErrors;
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[fixture]
+    fn graph_ref() -> GraphRef {
+        GraphRef::from_str("mygraph@current").unwrap()
+    }
+
+    fn passed(subgraph: &str) -> SubgraphBatchResult {
+        SubgraphBatchResult {
+            subgraph: subgraph.to_string(),
+            status: SubgraphBatchStatus::Passed,
+            text: String::new(),
+            json: json!({}),
+        }
+    }
+
+    #[rstest]
+    fn exit_code_is_zero_when_everything_passes(graph_ref: GraphRef) {
+        let output = SubgraphBatchOutput::new(graph_ref, vec![passed("a"), passed("b")]);
+        assert_that!(output.exit_code()).is_equal_to(0);
+    }
+
+    #[rstest]
+    fn exit_code_reflects_the_worst_result(graph_ref: GraphRef) {
+        let output = SubgraphBatchOutput::new(
+            graph_ref.clone(),
+            vec![
+                passed("a"),
+                SubgraphBatchResult::errored("b".to_string(), "boom"),
+                SubgraphBatchResult::skipped("c".to_string(), "unchanged"),
+            ],
+        );
+        assert_that!(output.exit_code()).is_equal_to(2);
+
+        let mut failed = passed("d");
+        failed.status = SubgraphBatchStatus::Failed;
+        let output = SubgraphBatchOutput::new(graph_ref, vec![passed("a"), failed]);
+        assert_that!(output.exit_code()).is_equal_to(1);
+    }
+
+    #[rstest]
+    fn text_lists_subgraphs_by_name_and_summarizes(graph_ref: GraphRef) {
+        let output = SubgraphBatchOutput::new(
+            graph_ref,
+            vec![
+                SubgraphBatchResult::errored("reviews".to_string(), "boom"),
+                passed("products"),
+                passed("accounts"),
+            ],
+        );
+        let text = temp_env::with_var("NO_COLOR", Some("1"), || output.text());
+        assert_that!(text).is_equal_to(
+            "accounts passed\n\nproducts passed\n\nreviews errored\nboom\n\n\
+             3 subgraphs against mygraph@current: 2 passed, 1 errored"
+                .to_string(),
+        );
+    }
+
+    #[rstest]
+    fn json_includes_each_subgraph_result(graph_ref: GraphRef) {
+        let publish_response = SubgraphPublishResponse {
+            api_schema_hash: Some("abc".to_string()),
+            supergraph_was_updated: true,
+            subgraph_was_created: false,
+            subgraph_was_updated: true,
+            build_errors: BuildErrors::new(),
+            launch_id: None,
+            launch_url: None,
+            launch_cli_copy: None,
+        };
+        let output = SubgraphBatchOutput::new(
+            graph_ref.clone(),
+            vec![
+                SubgraphBatchResult::published(
+                    "products".to_string(),
+                    &graph_ref,
+                    &publish_response,
+                ),
+                SubgraphBatchResult::skipped("accounts".to_string(), "unchanged"),
+            ],
+        );
+        assert_that!(output.json().unwrap()).is_equal_to(json!({
+            "graph_ref": "mygraph@current",
+            "subgraphs": [
+                {
+                    "name": "accounts",
+                    "status": "skipped",
+                    "result": { "reason": "unchanged" },
+                },
+                {
+                    "name": "products",
+                    "status": "published",
+                    "result": {
+                        "api_schema_hash": "abc",
+                        "supergraph_was_updated": true,
+                        "subgraph_was_created": false,
+                        "subgraph_was_updated": true,
+                        "launch_url": null,
+                        "launch_cli_copy": null,
+                    },
+                },
+            ],
+        }));
+    }
+
+    #[tokio::test]
+    async fn run_concurrently_never_exceeds_the_limit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = AtomicUsize::new(0);
+        let most_in_flight = AtomicUsize::new(0);
+        let subgraphs = (0..10)
+            .map(|i| BatchSubgraph {
+                name: format!("subgraph-{i}"),
+                routing_url: None,
+                schema: String::new(),
+            })
+            .collect();
+        let results = run_concurrently(subgraphs, NonZeroUsize::new(3).unwrap(), |subgraph| {
+            let (in_flight, most_in_flight) = (&in_flight, &most_in_flight);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                most_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                passed(&subgraph.name)
+            }
+        })
+        .await;
+        assert_that!(results).has_length(10);
+        assert_that!(most_in_flight.load(Ordering::SeqCst)).is_equal_to(3);
+    }
+}
@@ -7,31 +7,52 @@ use rover_client::{
     shared::{CheckConfig, GitContext},
 };
 use rover_std::Style;
+use rover_studio::types::GraphRef;
 use serde::Serialize;
 
+use super::batch::{self, SubgraphBatchOutput, SubgraphBatchResult};
 use crate::{
     RoverOutput, RoverResult,
-    options::{CheckConfigOpts, GraphRefOpt, ProfileOpt, SchemaOpt, SubgraphOpt},
-    utils::client::StudioClientConfig,
+    options::{CheckConfigOpts, OptionalGraphRefOpt, ProfileOpt, SubgraphBatchOpts},
+    utils::{client::StudioClientConfig, parsers::FileDescriptorType},
 };
 
 #[derive(Debug, Serialize, Parser)]
+#[command(
+    mut_arg("graph_ref", |arg| arg.required_unless_present("batch_graph_ref")),
+    mut_arg("background", |arg| arg.conflicts_with("supergraph_config"))
+)]
 pub struct Check {
     #[clap(flatten)]
-    graph: GraphRefOpt,
+    graph: OptionalGraphRefOpt,
 
-    #[clap(flatten)]
-    subgraph: SubgraphOpt,
+    /// The name of the subgraph.
+    #[arg(
+        long = "name",
+        required_unless_present = "supergraph_config",
+        conflicts_with = "supergraph_config"
+    )]
+    #[serde(skip_serializing)]
+    subgraph_name: Option<String>,
 
     #[clap(flatten)]
     profile: ProfileOpt,
 
-    #[clap(flatten)]
+    /// The schema file to check. You can pass `-` to use stdin instead of a file.
+    #[arg(
+        long,
+        short = 's',
+        required_unless_present = "supergraph_config",
+        conflicts_with = "supergraph_config"
+    )]
     #[serde(skip_serializing)]
-    schema: SchemaOpt,
+    schema: Option<FileDescriptorType>,
 
     #[clap(flatten)]
     config: CheckConfigOpts,
+
+    #[clap(flatten)]
+    batch: SubgraphBatchOpts,
 }
 
 impl Check {
@@ -41,29 +62,47 @@ impl Check {
         git_context: GitContext,
         checks_timeout_seconds: u64,
     ) -> RoverResult<RoverOutput> {
+        if let Some(supergraph_config) = &self.batch.supergraph_config {
+            return self
+                .run_batch(
+                    supergraph_config,
+                    client_config,
+                    git_context,
+                    checks_timeout_seconds,
+                )
+                .await;
+        }
+
         let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self
+            .graph
+            .graph_ref
+            .clone()
+            .expect("GRAPH_REF is required unless --supergraph-config is set");
+        let subgraph_name = self
+            .subgraph_name
+            .clone()
+            .expect("--name is required unless --supergraph-config is set");
 
         let proposed_schema = self
             .schema
+            .as_ref()
+            .expect("--schema is required unless --supergraph-config is set")
             .read_file_descriptor("SDL", &mut std::io::stdin())?;
 
         eprintln!(
             "Checking the proposed schema for subgraph {} against {}",
-            self.subgraph.subgraph_name,
-            Style::Link.paint(self.graph.graph_ref.to_string())
+            subgraph_name,
+            Style::Link.paint(graph_ref.to_string())
         );
 
         let workflow_res = check::run(
             SubgraphCheckAsyncInput {
-                graph_ref: self.graph.graph_ref.clone(),
-                subgraph: self.subgraph.subgraph_name.clone(),
+                graph_ref: graph_ref.clone(),
+                subgraph: subgraph_name.clone(),
                 git_context,
                 proposed_schema,
-                config: CheckConfig {
-                    query_count_threshold: self.config.query_count_threshold,
-                    query_count_threshold_percentage: self.config.query_percentage_threshold,
-                    validation_period: self.config.validation_period.clone(),
-                },
+                config: self.check_config(),
             },
             &client,
         )
@@ -73,11 +112,11 @@ impl Check {
         } else {
             let check_res = check_workflow::run(
                 CheckWorkflowInput {
-                    graph_ref: self.graph.graph_ref.clone(),
+                    graph_ref,
                     workflow_id: workflow_res.workflow_id,
                     checks_timeout_seconds,
                 },
-                self.subgraph.subgraph_name.clone(),
+                subgraph_name,
                 &client,
             )
             .await?;
@@ -85,4 +124,78 @@ impl Check {
             Ok(RoverOutput::CheckWorkflowResponse(check_res))
         }
     }
+
+    /// Checks every subgraph in the supergraph config, a few at a time, and
+    /// reports them together
+    async fn run_batch(
+        &self,
+        supergraph_config: &FileDescriptorType,
+        client_config: StudioClientConfig,
+        git_context: GitContext,
+        checks_timeout_seconds: u64,
+    ) -> RoverResult<RoverOutput> {
+        let graph_ref = self.batch_graph_ref();
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let (subgraphs, mut results) =
+            batch::resolve_subgraphs(&client_config, &self.profile, supergraph_config).await?;
+
+        eprintln!(
+            "Checking the proposed schemas for {} subgraphs against {}",
+            subgraphs.len(),
+            Style::Link.paint(graph_ref.to_string())
+        );
+
+        let checked = batch::run_concurrently(subgraphs, self.batch.max_concurrency, |subgraph| {
+            let (client, graph_ref, git_context) = (&client, &graph_ref, &git_context);
+            async move {
+                let result = async {
+                    let workflow_res = check::run(
+                        SubgraphCheckAsyncInput {
+                            graph_ref: graph_ref.clone(),
+                            subgraph: subgraph.name.clone(),
+                            git_context: git_context.clone(),
+                            proposed_schema: subgraph.schema,
+                            config: self.check_config(),
+                        },
+                        client,
+                    )
+                    .await?;
+                    check_workflow::run(
+                        CheckWorkflowInput {
+                            graph_ref: graph_ref.clone(),
+                            workflow_id: workflow_res.workflow_id,
+                            checks_timeout_seconds,
+                        },
+                        subgraph.name.clone(),
+                        client,
+                    )
+                    .await
+                }
+                .await;
+                SubgraphBatchResult::check(subgraph.name, result)
+            }
+        })
+        .await;
+        results.extend(checked);
+
+        Ok(RoverOutput::CliOutput(Box::new(SubgraphBatchOutput::new(
+            graph_ref, results,
+        ))))
+    }
+
+    fn batch_graph_ref(&self) -> GraphRef {
+        self.batch
+            .graph_ref
+            .clone()
+            .or_else(|| self.graph.graph_ref.clone())
+            .expect("GRAPH_REF or --graph-ref is required with --supergraph-config")
+    }
+
+    fn check_config(&self) -> CheckConfig {
+        CheckConfig {
+            query_count_threshold: self.config.query_count_threshold,
+            query_count_threshold_percentage: self.config.query_percentage_threshold,
+            validation_period: self.config.validation_period.clone(),
+        }
+    }
 }
@@ -1,5 +1,11 @@
 use clap::Parser;
-use rover_client::operations::subgraph::fetch::{self, SubgraphFetchInput};
+use rover_client::operations::{
+    graph::history::HistoryPoint,
+    subgraph::{
+        fetch::{self, SubgraphFetchInput},
+        fetch_at::{self, SubgraphFetchAtInput},
+    },
+};
 use rover_std::Style;
 use serde::Serialize;
 
@@ -19,27 +25,54 @@ pub struct Fetch {
 
     #[clap(flatten)]
     profile: ProfileOpt,
+
+    /// Fetch the schema the subgraph had in a past launch instead of its latest
+    /// one. Takes a launch ID from `rover subgraph history`, or an RFC 3339
+    /// timestamp to fetch the schema that was live then.
+    #[arg(long, value_name = "LAUNCH_ID|TIMESTAMP")]
+    #[serde(skip_serializing)]
+    at: Option<HistoryPoint>,
 }
 
 impl Fetch {
     pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
         let graph_ref = self.graph.graph_ref.to_string();
+        let at = self
+            .at
+            .as_ref()
+            .map(|at| format!(" as of {at}"))
+            .unwrap_or_default();
         eprintln!(
-            "Fetching SDL from {} (subgraph: {}) using credentials from the {} profile.",
+            "Fetching SDL from {}{at} (subgraph: {}) using credentials from the {} profile.",
             Style::Link.paint(graph_ref),
             Style::Link.paint(&self.subgraph.subgraph_name),
             Style::Command.paint(&self.profile.profile_name)
         );
 
-        let fetch_response = fetch::run(
-            SubgraphFetchInput {
-                graph_ref: self.graph.graph_ref.clone(),
-                subgraph_name: self.subgraph.subgraph_name.clone(),
-            },
-            &client,
-        )
-        .await?;
+        let fetch_response = match &self.at {
+            Some(at) => {
+                fetch_at::run(
+                    SubgraphFetchAtInput {
+                        graph_ref: self.graph.graph_ref.clone(),
+                        subgraph_name: self.subgraph.subgraph_name.clone(),
+                        at: at.clone(),
+                    },
+                    &client,
+                )
+                .await?
+            }
+            None => {
+                fetch::run(
+                    SubgraphFetchInput {
+                        graph_ref: self.graph.graph_ref.clone(),
+                        subgraph_name: self.subgraph.subgraph_name.clone(),
+                    },
+                    &client,
+                )
+                .await?
+            }
+        };
 
         Ok(RoverOutput::FetchResponse(fetch_response))
     }
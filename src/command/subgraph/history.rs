@@ -0,0 +1,107 @@
+use clap::Parser;
+use rover_client::operations::subgraph::history::{
+    self, SubgraphHistoryInput, SubgraphHistoryResponse,
+};
+use rover_std::Style;
+use serde::Serialize;
+
+use crate::{
+    RoverOutput, RoverResult,
+    command::CliOutput,
+    options::{GraphRefOpt, ProfileOpt, SubgraphOpt},
+    utils::{client::StudioClientConfig, table},
+};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover subgraph history my-graph@current --name products\n    \
+    rover subgraph fetch my-graph@current --name products --at 2026-01-02T00:00:00Z")]
+pub struct History {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    subgraph: SubgraphOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The most publishes to list, newest first
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(i64).range(1..=100))]
+    limit: i64,
+}
+
+impl History {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        eprintln!(
+            "Fetching publish history for {} (subgraph: {}) using credentials from the {} profile.",
+            Style::Link.paint(self.graph.graph_ref.to_string()),
+            Style::Link.paint(&self.subgraph.subgraph_name),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let response = history::run(
+            SubgraphHistoryInput {
+                graph_ref: self.graph.graph_ref.clone(),
+                subgraph_name: self.subgraph.subgraph_name.clone(),
+                limit: self.limit,
+            },
+            &client,
+        )
+        .await?;
+        Ok(RoverOutput::CliOutput(Box::new(SubgraphHistoryOutput(
+            response,
+        ))))
+    }
+}
+
+#[derive(Debug)]
+struct SubgraphHistoryOutput(SubgraphHistoryResponse);
+
+impl CliOutput for SubgraphHistoryOutput {
+    fn text(&self) -> String {
+        if self.0.publishes.is_empty() {
+            return format!(
+                "No publishes of subgraph {} found for {}.",
+                self.0.subgraph_name, self.0.graph_ref
+            );
+        }
+        let mut table = table::get_table();
+        table.add_row(vec![
+            &Style::Success.paint("Launch ID"),
+            &Style::Success.paint("Created At"),
+            &Style::Success.paint("Status"),
+            &Style::Success.paint("Change"),
+            &Style::Success.paint("Changes"),
+            &Style::Success.paint("Schema Hash"),
+        ]);
+        for publish in &self.0.publishes {
+            table.add_row(vec![
+                publish.launch_id.clone(),
+                publish.created_at.clone(),
+                publish.status.clone(),
+                publish.change.clone(),
+                publish
+                    .changes
+                    .map(|changes| changes.to_string())
+                    .unwrap_or_else(|| "N/A".to_string()),
+                publish
+                    .schema_hash
+                    .as_deref()
+                    .map(short_hash)
+                    .unwrap_or("N/A")
+                    .to_string(),
+            ]);
+        }
+        table.to_string()
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(&self.0)
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    hash.get(..12).unwrap_or(hash)
+}
@@ -1,6 +1,8 @@
+mod batch;
 mod check;
 mod delete;
 mod fetch;
+mod history;
 pub mod introspect;
 mod lint;
 mod list;
@@ -30,6 +32,9 @@ pub enum Command {
     /// Fetch a subgraph schema from the Apollo graph registry
     Fetch(fetch::Fetch),
 
+    /// List the past publishes of a subgraph
+    History(history::History),
+
     /// Introspect a running subgraph endpoint to retrieve its schema definition (SDL)
     Introspect(introspect::Introspect),
 
@@ -63,11 +68,12 @@ impl Subgraph {
                     .run(
                         client_config.get_reqwest_client()?,
                         output_opts,
-                        client_config.client_timeout().get_duration(),
+                        client_config.introspection_retry(),
                     )
                     .await
             }
             Command::Fetch(command) => command.run(client_config).await,
+            Command::History(command) => command.run(client_config).await,
             Command::Lint(command) => command.run(client_config).await,
             Command::List(command) => command.run(client_config).await,
             Command::Publish(command) => {
@@ -1,4 +1,9 @@
-use std::io::{self, IsTerminal};
+use std::{
+    collections::BTreeMap,
+    io::{self, IsTerminal},
+    num::NonZeroUsize,
+    time::Duration,
+};
 
 use anyhow::anyhow;
 use clap::Parser;
@@ -6,30 +11,49 @@ use futures::Future;
 use reqwest::Url;
 use rover_client::{
     RoverClientError,
+    blocking::StudioClient,
     operations::subgraph::{
         check::{self, SubgraphCheckAsyncInput},
         check_workflow::{self, CheckWorkflowInput},
+        fetch_all::{self, Subgraph, SubgraphFetchAllInput},
         publish::{self, SubgraphPublishInput},
         routing_url::{self, SubgraphRoutingUrlInput},
     },
-    shared::{CheckConfig, GitContext},
+    shared::{CheckConfig, CheckWorkflowResponse, GitContext},
 };
-use rover_std::Style;
+use rover_std::{Style, warnln};
+use rover_studio::types::GraphRef;
 use serde::Serialize;
 
+use super::batch::{
+    self, BatchSubgraph, SubgraphBatchOutput, SubgraphBatchResult, SubgraphBatchStatus,
+};
 use crate::{
     RoverError, RoverErrorSuggestion, RoverOutput, RoverResult,
-    options::{CheckConfigOpts, GraphRefOpt, OptionalSchemaOpt, ProfileOpt, SubgraphOpt},
-    utils::client::StudioClientConfig,
+    command::launch::wait_for_launch,
+    options::{
+        CheckConfigOpts, OptionalGraphRefOpt, OptionalSchemaOpt, ProfileOpt, SubgraphBatchOpts,
+    },
+    utils::{client::StudioClientConfig, parsers::FileDescriptorType},
 };
 
 #[derive(Debug, Serialize, Parser)]
+#[command(
+    mut_arg("graph_ref", |arg| arg.required_unless_present("batch_graph_ref")),
+    mut_group("OptionalSchemaOpt", |group| group.arg("supergraph_config"))
+)]
 pub struct Publish {
     #[clap(flatten)]
-    graph: GraphRefOpt,
-
-    #[clap(flatten)]
-    subgraph: SubgraphOpt,
+    graph: OptionalGraphRefOpt,
+
+    /// The name of the subgraph.
+    #[arg(
+        long = "name",
+        required_unless_present = "supergraph_config",
+        conflicts_with = "supergraph_config"
+    )]
+    #[serde(skip_serializing)]
+    subgraph_name: Option<String>,
 
     #[clap(flatten)]
     profile: ProfileOpt,
@@ -46,7 +70,7 @@ pub struct Publish {
     /// (often a deployed subgraph). May be left empty ("") or a placeholder url
     /// if not running a gateway or router in managed federation mode.
     /// Not required if `--use-example-schema` is provided.
-    #[arg(long)]
+    #[arg(long, conflicts_with = "supergraph_config")]
     #[serde(skip_serializing)]
     routing_url: Option<String>,
 
@@ -58,7 +82,7 @@ pub struct Publish {
     allow_invalid_routing_url: bool,
 
     /// This is shorthand for `--routing-url "" --allow-invalid-routing-url`.
-    #[arg(long)]
+    #[arg(long, conflicts_with = "supergraph_config")]
     no_url: bool,
 
     /// Run schema checks before publishing and abort if they fail
@@ -71,6 +95,28 @@ pub struct Publish {
     /// A message to associate with this publish in the Studio changelog
     #[arg(long, value_name = "MESSAGE")]
     changelog_message: Option<String>,
+
+    #[clap(flatten)]
+    batch: SubgraphBatchOpts,
+
+    /// Skip subgraphs from the supergraph config whose schema and routing URL
+    /// match what's currently published
+    #[arg(long, requires = "supergraph_config")]
+    skip_unchanged: bool,
+
+    /// Wait for the launch started by this publish, and any contract variant
+    /// launches it starts, to finish. Exits nonzero if any of them fail.
+    #[arg(long, conflicts_with = "supergraph_config")]
+    wait_for_launch: bool,
+
+    /// How many seconds to wait with `--wait-for-launch` before giving up
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 600,
+        requires = "wait_for_launch"
+    )]
+    launch_timeout: u64,
 }
 
 impl Publish {
@@ -80,7 +126,27 @@ impl Publish {
         git_context: GitContext,
         checks_timeout_seconds: u64,
     ) -> RoverResult<RoverOutput> {
+        if let Some(supergraph_config) = &self.batch.supergraph_config {
+            return self
+                .run_batch(
+                    supergraph_config,
+                    client_config,
+                    git_context,
+                    checks_timeout_seconds,
+                )
+                .await;
+        }
+
         let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self
+            .graph
+            .graph_ref
+            .clone()
+            .expect("GRAPH_REF is required unless --supergraph-config is set");
+        let subgraph_name = self
+            .subgraph_name
+            .clone()
+            .expect("--name is required unless --supergraph-config is set");
 
         let (url, schema) = if self.schema.is_using_example_schema() {
             (
@@ -95,8 +161,8 @@ impl Publish {
                 || async {
                     Ok(routing_url::run(
                         SubgraphRoutingUrlInput {
-                            graph_ref: self.graph.graph_ref.clone(),
-                            subgraph_name: self.subgraph.subgraph_name.clone(),
+                            graph_ref: graph_ref.clone(),
+                            subgraph_name: subgraph_name.clone(),
                         },
                         &client,
                     )
@@ -118,38 +184,20 @@ impl Publish {
         if self.check {
             eprintln!(
                 "Checking the proposed schema for subgraph {} against {}",
-                Style::Link.paint(&self.subgraph.subgraph_name),
-                Style::Link.paint(self.graph.graph_ref.to_string())
+                Style::Link.paint(&subgraph_name),
+                Style::Link.paint(graph_ref.to_string())
             );
 
-            let workflow_res = check::run(
-                SubgraphCheckAsyncInput {
-                    graph_ref: self.graph.graph_ref.clone(),
-                    subgraph: self.subgraph.subgraph_name.clone(),
-                    git_context: git_context.clone(),
-                    proposed_schema: schema.clone(),
-                    config: CheckConfig {
-                        validation_period: self.check_config.validation_period.clone(),
-                        query_count_threshold: self.check_config.query_count_threshold,
-                        query_count_threshold_percentage: self
-                            .check_config
-                            .query_percentage_threshold,
-                    },
-                },
-                &client,
-            )
-            .await?;
-
-            match check_workflow::run(
-                CheckWorkflowInput {
-                    graph_ref: self.graph.graph_ref.clone(),
-                    workflow_id: workflow_res.workflow_id,
+            match self
+                .run_checks(
+                    &client,
+                    &graph_ref,
+                    &subgraph_name,
+                    schema.clone(),
+                    git_context.clone(),
                     checks_timeout_seconds,
-                },
-                self.subgraph.subgraph_name.clone(),
-                &client,
-            )
-            .await
+                )
+                .await
             {
                 Ok(check_res) => {
                     eprintln!("{}", check_res.get_output());
@@ -181,8 +229,8 @@ impl Publish {
 
         eprintln!(
             "Publishing SDL to {} (subgraph: {}) using credentials from the {} profile.",
-            Style::Link.paint(self.graph.graph_ref.to_string()),
-            Style::Link.paint(&self.subgraph.subgraph_name),
+            Style::Link.paint(graph_ref.to_string()),
+            Style::Link.paint(&subgraph_name),
             Style::Command.paint(&self.profile.profile_name)
         );
 
@@ -190,8 +238,8 @@ impl Publish {
 
         let publish_response = publish::run(
             SubgraphPublishInput {
-                graph_ref: self.graph.graph_ref.clone(),
-                subgraph: self.subgraph.subgraph_name.clone(),
+                graph_ref: graph_ref.clone(),
+                subgraph: subgraph_name.clone(),
                 url,
                 schema,
                 git_context,
@@ -202,13 +250,258 @@ impl Publish {
         )
         .await?;
 
+        if self.wait_for_launch {
+            self.wait_for_published_launch(
+                &client,
+                &graph_ref,
+                publish_response.launch_id.as_deref(),
+            )
+            .await?;
+        }
+
         Ok(RoverOutput::SubgraphPublishResponse {
-            graph_ref: self.graph.graph_ref.clone(),
-            subgraph: self.subgraph.subgraph_name.clone(),
+            graph_ref,
+            subgraph: subgraph_name,
             publish_response,
         })
     }
 
+    /// Waits for the launch a publish started, failing if it or any contract
+    /// variant launch it started fails
+    async fn wait_for_published_launch(
+        &self,
+        client: &StudioClient,
+        graph_ref: &GraphRef,
+        launch_id: Option<&str>,
+    ) -> RoverResult<()> {
+        let Some(launch_id) = launch_id else {
+            warnln!("This publish didn't start a launch, so there's nothing to wait for.");
+            return Ok(());
+        };
+        let launch = wait_for_launch(
+            client,
+            graph_ref,
+            launch_id,
+            Duration::from_secs(self.launch_timeout),
+        )
+        .await?;
+        if launch.has_failed() {
+            let mut err = RoverError::new(anyhow!(
+                "Launch {launch_id} of {graph_ref} failed.{}",
+                launch
+                    .build_errors
+                    .iter()
+                    .map(|error| format!("\n  - {error}"))
+                    .collect::<String>()
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "The subgraph was published, but its launch didn't complete. Run `rover launch status {graph_ref} {launch_id}` for details."
+            )));
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Publishes every subgraph in the supergraph config, a few at a time, and
+    /// reports them together
+    async fn run_batch(
+        &self,
+        supergraph_config: &FileDescriptorType,
+        client_config: StudioClientConfig,
+        git_context: GitContext,
+        checks_timeout_seconds: u64,
+    ) -> RoverResult<RoverOutput> {
+        let graph_ref = self
+            .batch
+            .graph_ref
+            .clone()
+            .or_else(|| self.graph.graph_ref.clone())
+            .expect("GRAPH_REF or --graph-ref is required with --supergraph-config");
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let (subgraphs, mut results) =
+            batch::resolve_subgraphs(&client_config, &self.profile, supergraph_config).await?;
+
+        let published = if self.skip_unchanged {
+            Self::fetch_published_subgraphs(&client, &graph_ref).await
+        } else {
+            BTreeMap::new()
+        };
+        // every publish to a local registry recomposes the whole variant, so
+        // running them side by side could lose subgraphs from the supergraph
+        let max_concurrency = if client.local_registry().is_some() {
+            NonZeroUsize::MIN
+        } else {
+            self.batch.max_concurrency
+        };
+
+        eprintln!(
+            "Publishing {} subgraphs to {} using credentials from the {} profile.",
+            subgraphs.len(),
+            Style::Link.paint(graph_ref.to_string()),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let publishes = batch::run_concurrently(subgraphs, max_concurrency, |subgraph| {
+            let published = published.get(&subgraph.name);
+            self.publish_from_batch(
+                &client,
+                &graph_ref,
+                subgraph,
+                published,
+                git_context.clone(),
+                checks_timeout_seconds,
+            )
+        })
+        .await;
+        results.extend(publishes);
+
+        Ok(RoverOutput::CliOutput(Box::new(SubgraphBatchOutput::new(
+            graph_ref, results,
+        ))))
+    }
+
+    async fn publish_from_batch(
+        &self,
+        client: &StudioClient,
+        graph_ref: &GraphRef,
+        subgraph: BatchSubgraph,
+        published: Option<&Subgraph>,
+        git_context: GitContext,
+        checks_timeout_seconds: u64,
+    ) -> SubgraphBatchResult {
+        if let Some(published) = published
+            && Self::is_unchanged(&subgraph, published)
+        {
+            return SubgraphBatchResult::skipped(
+                subgraph.name,
+                "The schema and routing URL match what's currently published",
+            );
+        }
+
+        if !self.allow_invalid_routing_url
+            && let Err(err) = Self::handle_maybe_invalid_routing_url(
+                &subgraph.routing_url,
+                &mut io::stderr(),
+                &mut io::empty(),
+                false,
+            )
+        {
+            return SubgraphBatchResult::errored(subgraph.name, err);
+        }
+
+        if self.check {
+            let check = SubgraphBatchResult::check(
+                subgraph.name.clone(),
+                self.run_checks(
+                    client,
+                    graph_ref,
+                    &subgraph.name,
+                    subgraph.schema.clone(),
+                    git_context.clone(),
+                    checks_timeout_seconds,
+                )
+                .await,
+            );
+            if check.status != SubgraphBatchStatus::Passed {
+                return check;
+            }
+        }
+
+        let publish_response = publish::run(
+            SubgraphPublishInput {
+                graph_ref: graph_ref.clone(),
+                subgraph: subgraph.name.clone(),
+                url: subgraph.routing_url,
+                schema: subgraph.schema,
+                git_context,
+                convert_to_federated_graph: self.convert,
+                changelog_message: self.changelog_message.clone(),
+            },
+            client,
+        )
+        .await;
+        match publish_response {
+            Ok(publish_response) => {
+                SubgraphBatchResult::published(subgraph.name, graph_ref, &publish_response)
+            }
+            Err(err) => SubgraphBatchResult::errored(subgraph.name, err),
+        }
+    }
+
+    /// The subgraphs currently published to the variant, for `--skip-unchanged`.
+    /// A variant that can't be fetched (say, because it doesn't exist yet) has
+    /// nothing to skip.
+    async fn fetch_published_subgraphs(
+        client: &StudioClient,
+        graph_ref: &GraphRef,
+    ) -> BTreeMap<String, Subgraph> {
+        match fetch_all::run(
+            SubgraphFetchAllInput {
+                graph_ref: graph_ref.clone(),
+            },
+            client,
+        )
+        .await
+        {
+            Ok(response) => response
+                .subgraphs
+                .into_iter()
+                .map(|subgraph| (subgraph.name().clone(), subgraph))
+                .collect(),
+            Err(err) => {
+                warnln!("Could not fetch the published subgraphs, so none will be skipped: {err}");
+                BTreeMap::new()
+            }
+        }
+    }
+
+    /// A subgraph is unchanged when its SDL matches the published one and it either
+    /// keeps the published routing URL or doesn't set one
+    fn is_unchanged(subgraph: &BatchSubgraph, published: &Subgraph) -> bool {
+        subgraph.schema == *published.sdl()
+            && subgraph
+                .routing_url
+                .as_ref()
+                .is_none_or(|url| published.url().as_ref() == Some(url))
+    }
+
+    async fn run_checks(
+        &self,
+        client: &StudioClient,
+        graph_ref: &GraphRef,
+        subgraph_name: &str,
+        proposed_schema: String,
+        git_context: GitContext,
+        checks_timeout_seconds: u64,
+    ) -> Result<CheckWorkflowResponse, RoverClientError> {
+        let workflow_res = check::run(
+            SubgraphCheckAsyncInput {
+                graph_ref: graph_ref.clone(),
+                subgraph: subgraph_name.to_string(),
+                git_context,
+                proposed_schema,
+                config: CheckConfig {
+                    validation_period: self.check_config.validation_period.clone(),
+                    query_count_threshold: self.check_config.query_count_threshold,
+                    query_count_threshold_percentage: self.check_config.query_percentage_threshold,
+                },
+            },
+            client,
+        )
+        .await?;
+
+        check_workflow::run(
+            CheckWorkflowInput {
+                graph_ref: graph_ref.clone(),
+                workflow_id: workflow_res.workflow_id,
+                checks_timeout_seconds,
+            },
+            subgraph_name.to_string(),
+            client,
+        )
+        .await
+    }
+
     pub(crate) async fn determine_routing_url<F, G>(
         no_url: bool,
         routing_url: &Option<String>,
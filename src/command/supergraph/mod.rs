@@ -7,6 +7,7 @@ use crate::{RoverOutput, RoverResult, utils::client::StudioClientConfig};
 pub(crate) mod compose;
 mod config;
 mod fetch;
+mod plan;
 
 #[derive(Debug, Serialize, Parser)]
 pub struct Supergraph {
@@ -24,6 +25,9 @@ pub enum Command {
 
     /// Fetch supergraph SDL from the graph registry
     Fetch(fetch::Fetch),
+
+    /// Print the query plan for an operation against a supergraph schema
+    Plan(plan::Plan),
 }
 
 impl Supergraph {
@@ -41,6 +45,7 @@ impl Supergraph {
                     .await
             }
             Command::Config(command) => command.run(),
+            Command::Plan(command) => command.run(),
         }
     }
 }
@@ -0,0 +1,279 @@
+//! Renders a query plan as a Graphviz DOT graph.
+//!
+//! Every fetch is a node, and edges point from a fetch to those that wait for it: each step of
+//! a sequence to the next, and a condition or `@defer` to the fetches that depend on it. Fetches
+//! that run in parallel have no edges between them.
+
+use std::fmt::Write;
+
+use apollo_federation::query_plan::{
+    ConditionNode, DeferNode, FetchNode, PlanNode, QueryPlan, TopLevelPlanNode,
+};
+use itertools::Itertools;
+
+pub(super) fn render(plan: &QueryPlan) -> String {
+    let mut graph = Graph::default();
+    if let Some(node) = &plan.node {
+        graph.top_level_node(node);
+    }
+    let mut dot = String::from(
+        "digraph QueryPlan {\n  node [shape=box, fontname=\"monospace\"];\n  edge [fontname=\"monospace\"];\n",
+    );
+    for statement in graph.statements {
+        let _ = writeln!(dot, "  {statement};");
+    }
+    dot.push('}');
+    dot
+}
+
+/// The nodes that run first and last in part of a plan, for joining it to the parts around it.
+#[derive(Default)]
+struct Ends {
+    entries: Vec<String>,
+    exits: Vec<String>,
+}
+
+impl Ends {
+    fn extend(&mut self, other: Ends) {
+        self.entries.extend(other.entries);
+        self.exits.extend(other.exits);
+    }
+}
+
+#[derive(Default)]
+struct Graph {
+    nodes: usize,
+    statements: Vec<String>,
+}
+
+impl Graph {
+    fn node(&mut self, label: &str, attributes: &str) -> String {
+        let id = format!("n{}", self.nodes);
+        self.nodes += 1;
+        self.statements
+            .push(format!("{id} [label=\"{}\"{attributes}]", escape(label)));
+        id
+    }
+
+    fn edges(&mut self, from: &[String], to: &[String], attributes: &str) {
+        for (from, to) in from.iter().cartesian_product(to) {
+            let attributes = if attributes.is_empty() {
+                String::new()
+            } else {
+                format!(" [{attributes}]")
+            };
+            self.statements.push(format!("{from} -> {to}{attributes}"));
+        }
+    }
+
+    fn top_level_node(&mut self, node: &TopLevelPlanNode) -> Ends {
+        match node {
+            TopLevelPlanNode::Subscription(subscription) => {
+                let mut ends = Ends::from(self.fetch(&subscription.primary, None));
+                if let Some(rest) = &subscription.rest {
+                    let rest = self.plan_node(rest, None);
+                    self.edges(
+                        &ends.exits,
+                        &rest.entries,
+                        "style=dashed, label=\"each event\"",
+                    );
+                    ends.exits = rest.exits;
+                }
+                ends
+            }
+            TopLevelPlanNode::Fetch(fetch) => self.fetch(fetch, None).into(),
+            TopLevelPlanNode::Sequence(sequence) => self.sequence(&sequence.nodes, None),
+            TopLevelPlanNode::Parallel(parallel) => self.parallel(&parallel.nodes, None),
+            TopLevelPlanNode::Flatten(flatten) => {
+                self.plan_node(&flatten.node, Some(&flatten.path.iter().join(".")))
+            }
+            TopLevelPlanNode::Defer(defer) => self.defer(defer, None),
+            TopLevelPlanNode::Condition(condition) => self.condition(condition, None),
+        }
+    }
+
+    /// Renders `node`, where `path` is the path to the entities it fetches, if any.
+    fn plan_node(&mut self, node: &PlanNode, path: Option<&str>) -> Ends {
+        match node {
+            PlanNode::Fetch(fetch) => self.fetch(fetch, path).into(),
+            PlanNode::Sequence(sequence) => self.sequence(&sequence.nodes, path),
+            PlanNode::Parallel(parallel) => self.parallel(&parallel.nodes, path),
+            PlanNode::Flatten(flatten) => {
+                self.plan_node(&flatten.node, Some(&flatten.path.iter().join(".")))
+            }
+            PlanNode::Defer(defer) => self.defer(defer, path),
+            PlanNode::Condition(condition) => self.condition(condition, path),
+        }
+    }
+
+    fn fetch(&mut self, fetch: &FetchNode, path: Option<&str>) -> String {
+        let operation = fetch.operation_document.as_serialized().trim();
+        match path {
+            Some(path) => self.node(
+                &format!(
+                    "Entity fetch from {}\nat {path}\n\n{operation}",
+                    fetch.subgraph_name
+                ),
+                ", style=rounded",
+            ),
+            None => self.node(
+                &format!("Fetch from {}\n\n{operation}", fetch.subgraph_name),
+                "",
+            ),
+        }
+    }
+
+    fn sequence(&mut self, nodes: &[PlanNode], path: Option<&str>) -> Ends {
+        let mut ends = Ends::default();
+        for node in nodes {
+            let step = self.plan_node(node, path);
+            if step.entries.is_empty() {
+                continue;
+            }
+            if ends.entries.is_empty() {
+                ends.entries = step.entries;
+            } else {
+                self.edges(&ends.exits, &step.entries, "");
+            }
+            ends.exits = step.exits;
+        }
+        ends
+    }
+
+    fn parallel(&mut self, nodes: &[PlanNode], path: Option<&str>) -> Ends {
+        let mut ends = Ends::default();
+        for node in nodes {
+            let branch = self.plan_node(node, path);
+            ends.extend(branch);
+        }
+        ends
+    }
+
+    fn defer(&mut self, defer: &DeferNode, path: Option<&str>) -> Ends {
+        let mut ends = match &defer.primary.node {
+            Some(node) => self.plan_node(node, path),
+            None => Ends::default(),
+        };
+        let primary_exits = ends.exits.clone();
+        for deferred in &defer.deferred {
+            let Some(node) = &deferred.node else {
+                continue;
+            };
+            let block = self.plan_node(node, path);
+            let label = match &deferred.label {
+                Some(label) => format!("@defer(label: {label})"),
+                None => "@defer".to_string(),
+            };
+            self.edges(
+                &primary_exits,
+                &block.entries,
+                &format!("style=dashed, label=\"{}\"", escape(&label)),
+            );
+            if primary_exits.is_empty() {
+                ends.entries.extend(block.entries);
+            }
+            ends.exits.extend(block.exits);
+        }
+        ends
+    }
+
+    fn condition(&mut self, condition: &ConditionNode, path: Option<&str>) -> Ends {
+        let id = self.node(
+            &format!("if ${}", condition.condition_variable),
+            ", shape=diamond",
+        );
+        let mut ends = Ends {
+            entries: vec![id.clone()],
+            exits: Vec::new(),
+        };
+        for (clause, label) in [
+            (&condition.if_clause, "true"),
+            (&condition.else_clause, "false"),
+        ] {
+            match clause {
+                Some(node) => {
+                    let branch = self.plan_node(node, path);
+                    self.edges(
+                        std::slice::from_ref(&id),
+                        &branch.entries,
+                        &format!("label=\"{label}\""),
+                    );
+                    ends.exits.extend(branch.exits);
+                }
+                None => ends.exits.push(id.clone()),
+            }
+        }
+        ends.exits.dedup();
+        ends
+    }
+}
+
+impl From<String> for Ends {
+    fn from(id: String) -> Self {
+        Ends {
+            entries: vec![id.clone()],
+            exits: vec![id],
+        }
+    }
+}
+
+/// Escapes `text` for a quoted DOT string, with each line left-aligned.
+fn escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+    if escaped.contains('\n') {
+        format!("{}\\l", escaped.replace('\n', "\\l"))
+    } else {
+        escaped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_compiler::ExecutableDocument;
+    use apollo_federation::{
+        Supergraph,
+        query_plan::query_planner::{QueryPlanner, QueryPlannerConfig},
+    };
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn plan(operation: &str) -> QueryPlan {
+        let sdl =
+            include_str!("../../../../crates/rover-schema/src/test_fixtures/supergraph.graphql");
+        let supergraph = Supergraph::new(sdl).unwrap();
+        let planner = QueryPlanner::new(&supergraph, QueryPlannerConfig::default()).unwrap();
+        let document = ExecutableDocument::parse_and_validate(
+            planner.api_schema().schema(),
+            operation,
+            "query.graphql",
+        )
+        .unwrap();
+        planner
+            .build_query_plan(&document, None, Default::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn sequences_link_root_fetches_to_entity_fetches() {
+        let dot = render(&plan("{ product(id: \"1\") { name reviews { body } } }"));
+        assert_that!(dot).starts_with("digraph QueryPlan {\n");
+        assert_that!(dot).contains(
+            "n0 [label=\"Fetch from products\\l\\l{ product(id: \\\"1\\\") { __typename id name } }\\l\"];",
+        );
+        assert_that!(dot).contains("n1 [label=\"Entity fetch from reviews\\lat product\\l");
+        assert_that!(dot).contains(", style=rounded];\n  n0 -> n1;\n}");
+    }
+
+    #[test]
+    fn plans_without_fetches_have_no_nodes() {
+        let dot = render(&plan("{ __typename }"));
+        assert_that!(dot).does_not_contain("->");
+        assert_that!(dot).does_not_contain("label=");
+    }
+
+    #[test]
+    fn escape_quotes_and_left_aligns_lines() {
+        assert_that!(escape("a \"b\"\nc")).is_equal_to("a \\\"b\\\"\\lc\\l".to_string());
+    }
+}
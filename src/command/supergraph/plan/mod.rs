@@ -0,0 +1,231 @@
+mod dot;
+mod output;
+
+use std::io::stdin;
+
+use anyhow::anyhow;
+use apollo_compiler::{ExecutableDocument, Name, validation::WithErrors};
+use apollo_federation::{
+    Supergraph,
+    query_plan::query_planner::{QueryPlanOptions, QueryPlanner, QueryPlannerConfig},
+};
+use clap::Parser;
+use itertools::Itertools;
+use serde::Serialize;
+
+use self::output::PlanOutput;
+use crate::{
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult, utils::parsers::FileDescriptorType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanFormat {
+    /// The plan as the router prints it
+    Text,
+    /// A Graphviz DOT graph of the plan's fetches
+    Dot,
+}
+
+#[derive(Debug, Serialize, Parser)]
+/// Print the query plan the router would follow for an operation
+///
+/// The plan shows the fetch sent to each subgraph, which fetches run in sequence or in
+/// parallel, and the entity fetches that join the results together. Plans are built locally
+/// with the query planner bundled with Rover, so they may differ from those of a router using
+/// another version of the planner.
+///
+/// Pass `--format json` for the plan as JSON.
+#[command(after_help = "EXAMPLES:\n    \
+    rover supergraph plan --supergraph supergraph.graphql query.graphql\n    \
+    rover supergraph plan --supergraph supergraph.graphql query.graphql --plan-format dot | dot -Tsvg > plan.svg\n    \
+    rover supergraph compose --config supergraph.yaml | rover supergraph plan --supergraph - query.graphql")]
+pub struct Plan {
+    /// The supergraph schema to plan against. You can pass `-` to use stdin instead of a file.
+    #[arg(long, value_name = "SUPERGRAPH_SDL")]
+    #[serde(skip_serializing)]
+    supergraph: FileDescriptorType,
+
+    /// The file containing the operation to plan. You can pass `-` to use stdin instead of a
+    /// file.
+    #[arg(value_name = "OPERATION_FILE")]
+    #[serde(skip_serializing)]
+    operation: FileDescriptorType,
+
+    /// The operation to plan, when the file contains more than one
+    #[arg(long, value_name = "NAME")]
+    operation_name: Option<String>,
+
+    /// A progressive `@override` label to treat as enabled. Can be passed more than once.
+    #[arg(long = "override-label", value_name = "LABEL")]
+    override_labels: Vec<String>,
+
+    /// The format of the plan
+    #[arg(long, value_name = "FORMAT", default_value = "text")]
+    plan_format: PlanFormat,
+}
+
+impl Plan {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        if matches!(self.supergraph, FileDescriptorType::Stdin)
+            && matches!(self.operation, FileDescriptorType::Stdin)
+        {
+            return Err(anyhow!(
+                "Only one of --supergraph and OPERATION_FILE can be read from stdin"
+            )
+            .into());
+        }
+        let sdl = self
+            .supergraph
+            .read_file_descriptor("supergraph SDL", &mut stdin())?;
+        let operation = self
+            .operation
+            .read_file_descriptor("operation", &mut stdin())?;
+
+        let supergraph = Supergraph::new(&sdl).map_err(|err| {
+            RoverError::new(anyhow!("{} is not a valid supergraph:\n{err}", self.supergraph))
+                .with_suggestion(RoverErrorSuggestion::Adhoc(
+                    "Plans are built from a composed supergraph schema. Run `rover supergraph compose` or `rover supergraph fetch` to get one."
+                        .to_string(),
+                ))
+        })?;
+        let planner =
+            QueryPlanner::new(&supergraph, QueryPlannerConfig::default()).map_err(|err| {
+                anyhow!("Failed to create a query planner for the supergraph:\n{err}")
+            })?;
+
+        let unknown_labels = self
+            .override_labels
+            .iter()
+            .filter(|label| !planner.override_condition_labels().contains(label.as_str()))
+            .collect::<Vec<_>>();
+        if !unknown_labels.is_empty() {
+            let known = planner.override_condition_labels();
+            return Err(RoverError::new(anyhow!(
+                "The supergraph has no progressive `@override` labelled {}",
+                unknown_labels
+                    .iter()
+                    .map(|label| format!("`{label}`"))
+                    .join(", ")
+            ))
+            .with_suggestion(RoverErrorSuggestion::Adhoc(if known.is_empty() {
+                "The supergraph doesn't use progressive `@override`.".to_string()
+            } else {
+                format!(
+                    "Use one of the supergraph's labels: {}",
+                    known.iter().map(|label| format!("`{label}`")).join(", ")
+                )
+            })));
+        }
+
+        let document = ExecutableDocument::parse_and_validate(
+            planner.api_schema().schema(),
+            operation,
+            self.operation.to_string(),
+        )
+        .map_err(|WithErrors { errors, .. }| {
+            anyhow!("The operation is not valid against the supergraph's API schema:\n{errors}")
+        })?;
+        let operation_name = self
+            .operation_name
+            .as_deref()
+            .map(Name::new)
+            .transpose()
+            .map_err(|err| anyhow!("Invalid operation name: {err}"))?;
+        if operation_name.is_none() && document.operations.len() > 1 {
+            return Err(RoverError::new(anyhow!(
+                "{} contains more than one operation",
+                self.operation
+            ))
+            .with_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "Pass --operation-name with one of: {}",
+                document
+                    .operations
+                    .iter()
+                    .filter_map(|operation| operation.name.as_ref())
+                    .join(", ")
+            ))));
+        }
+
+        let plan = planner
+            .build_query_plan(
+                &document,
+                operation_name,
+                QueryPlanOptions {
+                    override_conditions: self.override_labels.clone(),
+                    ..Default::default()
+                },
+            )
+            .map_err(|err| anyhow!("Failed to plan the operation:\n{err}"))?;
+
+        Ok(RoverOutput::CliOutput(Box::new(PlanOutput {
+            plan,
+            plan_format: self.plan_format,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const SUPERGRAPH: &str =
+        include_str!("../../../../tests/fixtures/supergraph-plan/supergraph.graphql");
+
+    fn plan(operation: &str, override_labels: &[&str]) -> RoverResult<String> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| Utf8PathBuf::try_from(dir.path().join(name)).unwrap();
+        std::fs::write(path("supergraph.graphql"), SUPERGRAPH).unwrap();
+        std::fs::write(path("query.graphql"), operation).unwrap();
+        let output = Plan {
+            supergraph: FileDescriptorType::File(path("supergraph.graphql")),
+            operation: FileDescriptorType::File(path("query.graphql")),
+            operation_name: None,
+            override_labels: override_labels
+                .iter()
+                .map(|label| label.to_string())
+                .collect(),
+            plan_format: PlanFormat::Text,
+        }
+        .run()?;
+        match output {
+            RoverOutput::CliOutput(output) => Ok(output.text()),
+            _ => panic!("expected a CliOutput"),
+        }
+    }
+
+    #[test]
+    fn plans_entity_fetches_after_the_root_fetch() {
+        let text = plan("{ product(id: \"1\") { name reviews { body } } }", &[]).unwrap();
+        assert_that!(text)
+            .starts_with("QueryPlan {\n  Sequence {\n    Fetch(service: \"products\")");
+        assert_that!(text)
+            .contains("Flatten(path: \"product\") {\n      Fetch(service: \"reviews\")");
+    }
+
+    #[test]
+    fn override_labels_move_fields_between_subgraphs() {
+        let operation = "{ product(id: \"1\") { inStock } }";
+        let text = plan(operation, &[]).unwrap();
+        assert_that!(text).does_not_contain("service: \"reviews\"");
+        let text = plan(operation, &["percent(25)"]).unwrap();
+        assert_that!(text).contains("Fetch(service: \"reviews\")");
+    }
+
+    #[test]
+    fn unknown_override_labels_are_rejected() {
+        let err = plan("{ product(id: \"1\") { inStock } }", &["percent(50)"]).unwrap_err();
+        assert_that!(err.to_string())
+            .contains("The supergraph has no progressive `@override` labelled `percent(50)`");
+    }
+
+    #[test]
+    fn operations_are_validated_against_the_api_schema() {
+        let err = plan("{ product(id: \"1\") { internalCode } }", &[]).unwrap_err();
+        assert_that!(err.to_string())
+            .contains("The operation is not valid against the supergraph's API schema");
+    }
+}
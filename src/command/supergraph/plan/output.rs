@@ -0,0 +1,25 @@
+use apollo_federation::query_plan::QueryPlan;
+use serde_json::json;
+
+use super::{PlanFormat, dot};
+use crate::command::CliOutput;
+
+/// Output for `rover supergraph plan`: the query plan for a single operation.
+#[derive(Debug)]
+pub(super) struct PlanOutput {
+    pub plan: QueryPlan,
+    pub plan_format: PlanFormat,
+}
+
+impl CliOutput for PlanOutput {
+    fn text(&self) -> String {
+        match self.plan_format {
+            PlanFormat::Text => self.plan.to_string(),
+            PlanFormat::Dot => dot::render(&self.plan),
+        }
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Ok(json!({ "query_plan": serde_json::to_value(&self.plan)? }))
+    }
+}
@@ -0,0 +1,27 @@
+mod show;
+
+use clap::Parser;
+use houston::Config;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Telemetry {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Print exactly what was reported for the most recent commands
+    Show(show::Show),
+}
+
+impl Telemetry {
+    pub fn run(&self, config: Config) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Show(command) => command.run(config),
+        }
+    }
+}
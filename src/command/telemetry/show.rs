@@ -0,0 +1,152 @@
+use clap::Parser;
+use houston::Config;
+use rover_std::Style;
+use serde::Serialize;
+use serde_json::json;
+use sputnik::{History, HistoryEntry, NotReported};
+
+use crate::{RoverOutput, RoverResult, command::CliOutput, utils::telemetry::history_path};
+
+#[derive(Debug, Serialize, Parser)]
+/// Print exactly what was reported for the most recent commands
+///
+/// Every command's anonymous usage data is kept locally, whether or not it
+/// was reported, so you can review what leaves this machine. Where it was
+/// reported to is printed alongside it: Apollo by default, or the file or
+/// OTLP collector set with `APOLLO_TELEMETRY_URL` or `APOLLO_TELEMETRY_OTLP_URL`.
+#[command(after_help = "EXAMPLES:\n    \
+    rover telemetry show\n    \
+    rover telemetry show --limit 50 --format json")]
+pub struct Show {
+    /// Number of most recent commands to show.
+    #[arg(long, short = 'n', default_value_t = 10)]
+    limit: usize,
+}
+
+impl Show {
+    pub fn run(&self, config: Config) -> RoverResult<RoverOutput> {
+        let entries = History::new(history_path(&config)).last(self.limit)?;
+        Ok(RoverOutput::CliOutput(Box::new(TelemetryHistoryOutput {
+            entries,
+        })))
+    }
+}
+
+#[derive(Debug)]
+struct TelemetryHistoryOutput {
+    entries: Vec<HistoryEntry>,
+}
+
+impl CliOutput for TelemetryHistoryOutput {
+    fn text(&self) -> String {
+        if self.entries.is_empty() {
+            return "No commands have been recorded yet.".to_string();
+        }
+
+        self.entries
+            .iter()
+            .map(|entry| {
+                let heading = match (&entry.reported_to, entry.not_reported) {
+                    (Some(destination), _) => format!("Reported to {destination}:"),
+                    (None, Some(NotReported::TelemetryDisabled)) => {
+                        "Not reported (telemetry is disabled):".to_string()
+                    }
+                    (None, Some(NotReported::DebugBuild)) => {
+                        "Not reported (debug builds don't send telemetry to Apollo):".to_string()
+                    }
+                    (None, None) => "Not reported:".to_string(),
+                };
+                let payload = serde_json::to_string_pretty(&entry.payload)
+                    .unwrap_or_else(|_| entry.payload.to_string());
+                format!("{}\n{payload}", Style::Heading.paint(heading))
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Ok(json!({ "sessions": self.entries }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use camino::Utf8Path;
+    use serde_json::json;
+    use speculoos::prelude::*;
+    use sputnik::{History, HistoryEntry};
+
+    use super::*;
+
+    fn entry(command: &str, reported_to: Option<&str>) -> HistoryEntry {
+        HistoryEntry {
+            reported_to: reported_to.map(str::to_string),
+            not_reported: reported_to
+                .is_none()
+                .then_some(NotReported::TelemetryDisabled),
+            payload: json!({ "command": { "name": command, "arguments": {} } }),
+        }
+    }
+
+    #[test]
+    fn show_returns_the_most_recent_commands() {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_home_path = Utf8Path::from_path(tmp_home.path()).unwrap().to_owned();
+        let config = Config::new(Some(&tmp_home_path), None).unwrap();
+        let history = History::new(history_path(&config));
+        for command in ["config list", "subgraph fetch", "telemetry show"] {
+            history
+                .record(&entry(command, Some("https://example.com/telemetry")))
+                .unwrap();
+        }
+
+        let output = Show { limit: 2 }.run(config).unwrap();
+
+        let RoverOutput::CliOutput(output) = output else {
+            panic!("expected a CliOutput");
+        };
+        let json = output.json().unwrap();
+        assert_that!(json["sessions"].as_array().unwrap()).has_length(2);
+        assert_that!(json["sessions"][0]["payload"]["command"]["name"])
+            .is_equal_to(json!("subgraph fetch"));
+    }
+
+    #[test]
+    fn text_prints_each_payload_under_where_it_was_reported() {
+        let output = TelemetryHistoryOutput {
+            entries: vec![
+                entry("config list", Some("https://example.com/telemetry")),
+                entry("subgraph fetch", None),
+            ],
+        };
+
+        let text = output.text();
+
+        assert_that!(text).contains("Reported to https://example.com/telemetry:");
+        assert_that!(text).contains("Not reported (telemetry is disabled):");
+        assert_that!(text).contains("\"name\": \"subgraph fetch\"");
+    }
+
+    #[test]
+    fn text_says_a_debug_build_skipped_reporting() {
+        let output = TelemetryHistoryOutput {
+            entries: vec![HistoryEntry {
+                not_reported: Some(NotReported::DebugBuild),
+                ..entry("subgraph fetch", None)
+            }],
+        };
+
+        assert_that!(output.text()).contains("Not reported (debug builds don't send telemetry");
+        assert_that!(output.text()).does_not_contain("telemetry is disabled");
+    }
+
+    #[test]
+    fn text_explains_an_empty_history() {
+        let output = TelemetryHistoryOutput {
+            entries: Vec::new(),
+        };
+
+        assert_that!(output.text()).is_equal_to("No commands have been recorded yet.".to_string());
+    }
+}
@@ -0,0 +1,441 @@
+use std::{fmt::Write, io, time::Duration};
+
+use anyhow::anyhow;
+use clap::Parser;
+use rover_client::{
+    operations::{
+        contract::publish::{self, ContractPublishInput, ContractPublishResponse},
+        variant::{
+            configure::{self, VariantCheckConfiguration, VariantConfigureInput},
+            list::{self, VariantListInput},
+        },
+    },
+    shared::{Period, ValidationPeriod},
+};
+use rover_std::Style;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult,
+    command::CliOutput,
+    options::{GraphRefOpt, ProfileOpt, parse_query_count_threshold},
+    utils::client::StudioClientConfig,
+};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover variant configure my-graph@current\n    \
+    rover variant configure my-graph@current --validation-period 2w --included-variant staging\n    \
+    rover variant configure my-graph@public --include-tag public --no-hide-unreachable-types\n\n\
+Without any settings, the variant's current check configuration is shown.")]
+pub struct Configure {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// Turn operation checks on or off
+    #[arg(long, value_name = "ENABLED")]
+    operations_checks: Option<bool>,
+
+    /// How far back operation checks look for operations (i.e "24h" or "1w 2d 5h")
+    #[arg(long)]
+    #[serde(skip_serializing)]
+    validation_period: Option<ValidationPeriod>,
+
+    /// The minimum number of times an operation must have been executed in the
+    /// validation period to be checked
+    #[arg(long, value_parser = parse_query_count_threshold)]
+    query_count_threshold: Option<i64>,
+
+    /// The minimum percentage of requests in the validation period an operation
+    /// must make up to be checked
+    #[arg(long, value_name = "PERCENTAGE", value_parser = parse_percentage)]
+    query_percentage_threshold: Option<f64>,
+
+    /// Check operations from this variant too (e.g. '--included-variant current --included-variant staging')
+    #[arg(long, value_name = "VARIANT")]
+    #[serde(skip_serializing)]
+    included_variant: Vec<String>,
+
+    /// Fail this variant's checks when this contract variant's checks fail
+    /// (e.g. '--blocking-downstream-variant public')
+    #[arg(long, value_name = "VARIANT")]
+    #[serde(skip_serializing)]
+    blocking_downstream_variant: Vec<String>,
+
+    /// Stop any downstream contract variant's checks from failing this variant's checks
+    #[arg(long, conflicts_with = "blocking_downstream_variant")]
+    no_blocking_downstream_variants: bool,
+
+    /// Tags of schema elements to include in the contract schema. Only for contract variants.
+    #[arg(long, value_name = "TAG")]
+    #[serde(skip_serializing)]
+    include_tag: Vec<String>,
+
+    /// Use an empty include list for the contract schema
+    #[arg(long, conflicts_with = "include_tag")]
+    no_include_tags: bool,
+
+    /// Tags of schema elements to exclude from the contract schema. Only for contract variants.
+    #[arg(long, value_name = "TAG")]
+    #[serde(skip_serializing)]
+    exclude_tag: Vec<String>,
+
+    /// Use an empty exclude list for the contract schema
+    #[arg(long, conflicts_with = "exclude_tag")]
+    no_exclude_tags: bool,
+
+    /// Automatically hide types that can never be reached in operations on the contract schema
+    #[arg(long)]
+    hide_unreachable_types: bool,
+
+    /// Do not automatically hide types that can never be reached in operations on the contract schema
+    #[arg(long, conflicts_with = "hide_unreachable_types")]
+    no_hide_unreachable_types: bool,
+
+    /// Do not trigger a launch in Studio after changing the contract filters
+    #[arg(long)]
+    no_launch: bool,
+}
+
+fn parse_percentage(percentage: &str) -> Result<f64, io::Error> {
+    let percentage = percentage
+        .parse::<f64>()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    if (0.0..=100.0).contains(&percentage) {
+        Ok(percentage)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            anyhow!("Valid numbers are in the range 0 <= x <= 100"),
+        ))
+    }
+}
+
+impl Configure {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        self.validate()?;
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.graph_ref.clone();
+
+        // looked up before anything is changed, so a variant that can't take
+        // contract filters is rejected without a half-applied update
+        let current_filters = if self.changes_contract_filters() {
+            let current = list::run(
+                VariantListInput {
+                    graph_id: graph_ref.graph_id().to_string(),
+                    include_proposals: true,
+                },
+                &client,
+            )
+            .await?
+            .variants
+            .into_iter()
+            .find(|variant| variant.name == *graph_ref.variant())
+            .and_then(|variant| variant.contract_filters)
+            .ok_or_else(|| {
+                RoverError::new(anyhow!("{graph_ref} isn't a contract variant.")).with_suggestion(
+                    RoverErrorSuggestion::Adhoc(
+                        "Create a contract variant with `rover variant create --source-variant`."
+                            .to_string(),
+                    ),
+                )
+            })?;
+            Some(current)
+        } else {
+            None
+        };
+
+        let input = self.check_configuration();
+        if !input.is_empty() {
+            eprintln!(
+                "Updating the check configuration of {} using credentials from the {} profile.",
+                Style::Link.paint(graph_ref.to_string()),
+                Style::Command.paint(&self.profile.profile_name)
+            );
+        }
+        let config = configure::run(graph_ref.clone(), input, &client).await?;
+
+        let contract = match current_filters {
+            Some(current) => {
+                eprintln!(
+                    "Updating the contract filters of {}.",
+                    Style::Link.paint(graph_ref.to_string())
+                );
+                Some(
+                    publish::run(
+                        ContractPublishInput {
+                            graph_ref,
+                            source_variant: None,
+                            include_tags: Self::tags(
+                                &self.include_tag,
+                                self.no_include_tags,
+                                current.include,
+                            ),
+                            exclude_tags: Self::tags(
+                                &self.exclude_tag,
+                                self.no_exclude_tags,
+                                current.exclude,
+                            ),
+                            hide_unreachable_types: self.hide_unreachable_types,
+                            no_launch: self.no_launch,
+                        },
+                        &client,
+                    )
+                    .await?,
+                )
+            }
+            None => None,
+        };
+
+        Ok(RoverOutput::CliOutput(Box::new(VariantConfigureOutput {
+            config,
+            contract,
+        })))
+    }
+
+    /// Rejects combinations of arguments clap can't, before anything is changed.
+    fn validate(&self) -> RoverResult<()> {
+        // Studio doesn't report whether a contract hides unreachable types,
+        // so there's no current value to keep
+        if self.changes_contract_filters()
+            && !self.hide_unreachable_types
+            && !self.no_hide_unreachable_types
+        {
+            return Err(RoverError::new(anyhow!(
+                "Changing contract filters needs --hide-unreachable-types or --no-hide-unreachable-types."
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_configuration(&self) -> VariantConfigureInput {
+        VariantConfigureInput {
+            operations_checks: self.operations_checks,
+            time_range_seconds: self
+                .validation_period
+                .as_ref()
+                .map(|period| match period.from {
+                    Period::Past(seconds) => seconds,
+                    Period::Now => 0,
+                }),
+            operation_count_threshold: self.query_count_threshold,
+            operation_count_threshold_percentage: self.query_percentage_threshold,
+            included_variants: (!self.included_variant.is_empty())
+                .then(|| self.included_variant.clone()),
+            blocking_downstream_variants: if self.no_blocking_downstream_variants {
+                Some(Vec::new())
+            } else {
+                (!self.blocking_downstream_variant.is_empty())
+                    .then(|| self.blocking_downstream_variant.clone())
+            },
+        }
+    }
+
+    const fn changes_contract_filters(&self) -> bool {
+        !self.include_tag.is_empty()
+            || self.no_include_tags
+            || !self.exclude_tag.is_empty()
+            || self.no_exclude_tags
+            || self.hide_unreachable_types
+            || self.no_hide_unreachable_types
+    }
+
+    /// The tags given, or the contract's current ones when none were
+    fn tags(given: &[String], none: bool, current: Vec<String>) -> Vec<String> {
+        if none {
+            Vec::new()
+        } else if given.is_empty() {
+            current
+        } else {
+            given.to_vec()
+        }
+    }
+}
+
+#[derive(Debug)]
+struct VariantConfigureOutput {
+    config: VariantCheckConfiguration,
+    contract: Option<ContractPublishResponse>,
+}
+
+impl CliOutput for VariantConfigureOutput {
+    fn text(&self) -> String {
+        let config = &self.config;
+        let from_graph = |uses_graph_settings: bool| {
+            if uses_graph_settings {
+                " (from the graph's settings)"
+            } else {
+                ""
+            }
+        };
+        let mut text = format!(
+            "{}\n",
+            Style::Heading.paint(format!("Check configuration for {}", config.graph_ref))
+        );
+        let _ = writeln!(
+            text,
+            "Operation checks: {}",
+            if config.operations_checks_enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        if let Some(seconds) = config.time_range_seconds {
+            let _ = writeln!(
+                text,
+                "Validation period: {}{}",
+                humantime::format_duration(Duration::from_secs(seconds.max(0) as u64)),
+                from_graph(config.time_range_uses_graph_settings)
+            );
+        }
+        if let Some(threshold) = config.operation_count_threshold {
+            let _ = writeln!(text, "Query count threshold: {threshold}");
+        }
+        if let Some(percentage) = config.operation_count_threshold_percentage {
+            let _ = writeln!(text, "Query percentage threshold: {percentage}%");
+        }
+        if let Some(variants) = &config.included_variants {
+            let _ = writeln!(
+                text,
+                "Included variants: {}{}",
+                variants.join(", "),
+                from_graph(config.included_variants_use_graph_settings)
+            );
+        }
+        if !config.blocking_downstream_variants.is_empty() {
+            let _ = writeln!(
+                text,
+                "Blocking downstream variants: {}",
+                config.blocking_downstream_variants.join(", ")
+            );
+        }
+        if let Some(contract) = &self.contract {
+            let _ = write!(text, "\n{}\n", contract.config_description);
+            if let Some(launch_cli_copy) = &contract.launch_cli_copy {
+                let _ = writeln!(text, "{launch_cli_copy}");
+            }
+        }
+        text
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        let mut json = serde_json::to_value(&self.config)?;
+        json["contract"] = match &self.contract {
+            Some(contract) => json!({
+                "config_description": contract.config_description,
+                "launch_url": contract.launch_url,
+            }),
+            None => serde_json::Value::Null,
+        };
+        Ok(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rover_studio::types::GraphRef;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn config() -> VariantCheckConfiguration {
+        VariantCheckConfiguration {
+            graph_ref: GraphRef::new("mygraph", Some("current")).unwrap(),
+            operations_checks_enabled: true,
+            time_range_seconds: Some(1_209_600),
+            operation_count_threshold: Some(1),
+            operation_count_threshold_percentage: Some(0.5),
+            time_range_uses_graph_settings: false,
+            included_variants: Some(vec!["current".to_string(), "staging".to_string()]),
+            included_variants_use_graph_settings: true,
+            blocking_downstream_variants: vec!["public".to_string()],
+        }
+    }
+
+    #[test]
+    fn only_given_settings_are_sent() {
+        let configure = Configure::parse_from([
+            "configure",
+            "mygraph@current",
+            "--validation-period",
+            "1d",
+            "--no-blocking-downstream-variants",
+        ]);
+        assert_that!(configure.check_configuration()).is_equal_to(VariantConfigureInput {
+            time_range_seconds: Some(86_400),
+            blocking_downstream_variants: Some(Vec::new()),
+            ..Default::default()
+        });
+        assert_that!(configure.changes_contract_filters()).is_false();
+    }
+
+    #[test]
+    fn no_settings_only_shows_the_configuration() {
+        let configure = Configure::parse_from(["configure", "mygraph@current"]);
+        assert_that!(configure.check_configuration().is_empty()).is_true();
+    }
+
+    #[test]
+    fn contract_filters_need_a_hide_unreachable_types_choice() {
+        let configure = Configure::parse_from([
+            "configure",
+            "mygraph@public",
+            "--validation-period",
+            "1d",
+            "--include-tag",
+            "public",
+        ]);
+        assert_that!(configure.validate()).is_err();
+
+        let configure = Configure::parse_from([
+            "configure",
+            "mygraph@public",
+            "--include-tag",
+            "public",
+            "--no-hide-unreachable-types",
+        ]);
+        assert_that!(configure.validate()).is_ok();
+    }
+
+    #[test]
+    fn filters_left_off_keep_their_current_tags() {
+        let current = vec!["internal".to_string()];
+        assert_that!(Configure::tags(&[], false, current.clone())).is_equal_to(current.clone());
+        assert_that!(Configure::tags(&[], true, current.clone())).is_equal_to(Vec::new());
+        assert_that!(Configure::tags(&["beta".to_string()], false, current))
+            .is_equal_to(vec!["beta".to_string()]);
+    }
+
+    #[test]
+    fn text_shows_where_settings_come_from() {
+        let output = VariantConfigureOutput {
+            config: config(),
+            contract: None,
+        };
+        let text = output.text();
+        assert_that!(text).contains("Validation period: 14days\n");
+        assert_that!(text)
+            .contains("Included variants: current, staging (from the graph's settings)");
+        assert_that!(text).contains("Blocking downstream variants: public");
+    }
+
+    #[test]
+    fn json_includes_the_contract_change() {
+        let output = VariantConfigureOutput {
+            config: config(),
+            contract: Some(ContractPublishResponse {
+                config_description: "Includes @public".to_string(),
+                launch_url: None,
+                launch_cli_copy: None,
+            }),
+        };
+        let json = output.json().unwrap();
+        assert_that!(json["operations_checks_enabled"]).is_equal_to(json!(true));
+        assert_that!(json["contract"]["config_description"]).is_equal_to(json!("Includes @public"));
+    }
+}
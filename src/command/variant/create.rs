@@ -0,0 +1,142 @@
+use anyhow::anyhow;
+use clap::Parser;
+use rover_client::operations::{
+    contract::publish::{self, ContractPublishInput, ContractPublishResponse},
+    variant::list::{self, VariantListInput},
+};
+use rover_std::Style;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult,
+    command::CliOutput,
+    options::{GraphRefOpt, ProfileOpt},
+    utils::client::StudioClientConfig,
+};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover variant create my-graph@public --source-variant current --include-tag public\n\n\
+Variants that aren't contracts are created by publishing to them, \
+e.g. with `rover subgraph publish my-graph@staging`.")]
+pub struct Create {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The variant the contract variant's schema is filtered from. This can't
+    /// be changed once the variant is created.
+    #[arg(long, value_name = "VARIANT")]
+    #[serde(skip_serializing)]
+    source_variant: String,
+
+    /// Tags of schema elements to include in the contract schema
+    /// (e.g. '--include-tag foo --include-tag bar')
+    #[arg(long, value_name = "TAG")]
+    #[serde(skip_serializing)]
+    include_tag: Vec<String>,
+
+    /// Tags of schema elements to exclude from the contract schema
+    /// (e.g. '--exclude-tag foo --exclude-tag bar')
+    #[arg(long, value_name = "TAG")]
+    #[serde(skip_serializing)]
+    exclude_tag: Vec<String>,
+
+    /// Automatically hide types that can never be reached in operations on the contract schema
+    #[arg(long)]
+    hide_unreachable_types: bool,
+
+    /// Do not trigger a launch in Studio after creating the variant
+    #[arg(long)]
+    no_launch: bool,
+}
+
+impl Create {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.graph_ref.clone();
+
+        // upserting a contract would quietly change an existing variant's filters
+        let existing = list::run(
+            VariantListInput {
+                graph_id: graph_ref.graph_id().to_string(),
+                include_proposals: true,
+            },
+            &client,
+        )
+        .await?;
+        if existing
+            .variants
+            .iter()
+            .any(|variant| variant.name == *graph_ref.variant())
+        {
+            return Err(
+                RoverError::new(anyhow!("{graph_ref} already exists.")).with_suggestion(
+                    RoverErrorSuggestion::Adhoc(format!(
+                        "Run `rover variant configure {graph_ref}` to change its settings."
+                    )),
+                ),
+            );
+        }
+
+        eprintln!(
+            "Creating contract variant {} from {} using credentials from the {} profile.",
+            Style::Link.paint(graph_ref.to_string()),
+            Style::Link.paint(&self.source_variant),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+        let response = publish::run(
+            ContractPublishInput {
+                graph_ref: graph_ref.clone(),
+                source_variant: Some(self.source_variant.clone()),
+                include_tags: self.include_tag.clone(),
+                exclude_tags: self.exclude_tag.clone(),
+                hide_unreachable_types: self.hide_unreachable_types,
+                no_launch: self.no_launch,
+            },
+            &client,
+        )
+        .await?;
+
+        Ok(RoverOutput::CliOutput(Box::new(VariantCreateOutput {
+            graph_ref: graph_ref.to_string(),
+            source_variant: self.source_variant.clone(),
+            response,
+        })))
+    }
+}
+
+#[derive(Debug)]
+struct VariantCreateOutput {
+    graph_ref: String,
+    source_variant: String,
+    response: ContractPublishResponse,
+}
+
+impl CliOutput for VariantCreateOutput {
+    fn text(&self) -> String {
+        let mut text = format!(
+            "Created contract variant {} from {}.\n{}",
+            Style::Link.paint(&self.graph_ref),
+            self.source_variant,
+            self.response.config_description
+        );
+        if let Some(launch_cli_copy) = &self.response.launch_cli_copy {
+            text.push('\n');
+            text.push_str(launch_cli_copy);
+        }
+        text
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Ok(json!({
+            "graph_ref": self.graph_ref,
+            "source_variant": self.source_variant,
+            "config_description": self.response.config_description,
+            "launch_url": self.response.launch_url,
+        }))
+    }
+}
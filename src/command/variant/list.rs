@@ -0,0 +1,145 @@
+use clap::Parser;
+use rover_client::operations::variant::list::{self, VariantListInput, VariantListResponse};
+use rover_std::Style;
+use serde::Serialize;
+
+use crate::{
+    RoverOutput, RoverResult,
+    command::CliOutput,
+    options::ProfileOpt,
+    utils::{client::StudioClientConfig, table},
+};
+
+#[derive(Debug, Serialize, Parser)]
+#[command(after_help = "EXAMPLES:\n    \
+    rover variant list my-graph\n    \
+    rover variant list my-graph --include-proposals")]
+pub struct List {
+    /// The ID of the graph to list variants of
+    #[arg(value_name = "GRAPH_ID")]
+    graph_id: String,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// Also list the variants that back schema proposals
+    #[arg(long)]
+    include_proposals: bool,
+}
+
+impl List {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        eprintln!(
+            "Listing variants of {} using credentials from the {} profile.",
+            Style::Link.paint(&self.graph_id),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let response = list::run(
+            VariantListInput {
+                graph_id: self.graph_id.clone(),
+                include_proposals: self.include_proposals,
+            },
+            &client,
+        )
+        .await?;
+        Ok(RoverOutput::CliOutput(Box::new(VariantListOutput(
+            response,
+        ))))
+    }
+}
+
+#[derive(Debug)]
+struct VariantListOutput(VariantListResponse);
+
+impl CliOutput for VariantListOutput {
+    fn text(&self) -> String {
+        if self.0.variants.is_empty() {
+            return format!("No variants found for {}.", self.0.graph_id);
+        }
+        let mut table = table::get_table();
+        table.add_row(vec![
+            &Style::Success.paint("Variant"),
+            &Style::Success.paint("Federation"),
+            &Style::Success.paint("Source Variant"),
+            &Style::Success.paint("Contract Filters"),
+            &Style::Success.paint("Last Launched"),
+        ]);
+        for variant in &self.0.variants {
+            table.add_row(vec![
+                variant.name.clone(),
+                variant
+                    .federation_version
+                    .clone()
+                    .unwrap_or_else(|| "N/A".to_string()),
+                variant.source_variant.clone().unwrap_or_default(),
+                variant
+                    .contract_filters
+                    .as_ref()
+                    .map(|filters| {
+                        format!(
+                            "include: [{}] exclude: [{}]",
+                            filters.include.join(", "),
+                            filters.exclude.join(", ")
+                        )
+                    })
+                    .unwrap_or_default(),
+                variant
+                    .last_launched_at
+                    .clone()
+                    .unwrap_or_else(|| "never".to_string()),
+            ]);
+        }
+        table.to_string()
+    }
+
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rover_client::operations::variant::list::{ContractFilters, VariantDetails};
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn variant(name: &str) -> VariantDetails {
+        VariantDetails {
+            name: name.to_string(),
+            created_at: "2026-01-02T03:04:05Z".to_string(),
+            federation_version: Some("2.9".to_string()),
+            url: None,
+            source_variant: None,
+            contract_filters: None,
+            is_proposal: false,
+            last_launched_at: None,
+        }
+    }
+
+    #[test]
+    fn text_shows_contract_filters() {
+        let mut public = variant("public");
+        public.source_variant = Some("current".to_string());
+        public.contract_filters = Some(ContractFilters {
+            include: vec!["public".to_string()],
+            exclude: vec!["internal".to_string()],
+        });
+        let output = VariantListOutput(VariantListResponse {
+            graph_id: "mygraph".to_string(),
+            variants: vec![variant("current"), public],
+        });
+        assert_that!(output.text()).contains("include: [public] exclude: [internal]");
+    }
+
+    #[test]
+    fn text_says_when_there_are_no_variants() {
+        let output = VariantListOutput(VariantListResponse {
+            graph_id: "mygraph".to_string(),
+            variants: Vec::new(),
+        });
+        assert_that!(output.text()).is_equal_to("No variants found for mygraph.".to_string());
+    }
+}
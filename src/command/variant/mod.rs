@@ -0,0 +1,34 @@
+use clap::Parser;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult, utils::client::StudioClientConfig};
+
+mod configure;
+mod create;
+mod list;
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Variant {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Change a variant's check configuration or contract filters
+    Configure(configure::Configure),
+    /// Create a contract variant from a source variant
+    Create(create::Create),
+    /// List a graph's variants
+    List(list::List),
+}
+
+impl Variant {
+    pub async fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Configure(command) => command.run(client_config).await,
+            Command::Create(command) => command.run(client_config).await,
+            Command::List(command) => command.run(client_config).await,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Composition for the file-backed graph registry
+
+use apollo_federation_types::{
+    config::{FederationVersion, SupergraphConfig},
+    rover::BuildErrors,
+};
+use camino::Utf8PathBuf;
+use futures::future::BoxFuture;
+use rover_client::{
+    RoverClientError, local_registry::Compose, operations::subgraph::fetch_all::Subgraph,
+};
+use rover_std::Fs;
+
+use crate::{
+    composition::{CompositionError, supergraph::install::InstallSupergraph},
+    options::LicenseAccepter,
+    utils::{
+        client::StudioClientConfig,
+        effect::{exec::TokioCommand, install::InstallBinary},
+    },
+};
+
+/// Composes a local registry's variants with the supergraph plugin, the same way
+/// `rover supergraph compose` does. An already-installed plugin is used as-is, so
+/// publishing works offline once the plugin has been installed.
+#[derive(Debug, Clone)]
+pub struct PluginComposer {
+    client_config: StudioClientConfig,
+}
+
+impl PluginComposer {
+    pub const fn new(client_config: StudioClientConfig) -> PluginComposer {
+        PluginComposer { client_config }
+    }
+}
+
+impl Compose for PluginComposer {
+    fn compose(
+        &self,
+        subgraphs: Vec<Subgraph>,
+    ) -> BoxFuture<'_, Result<Result<String, BuildErrors>, RoverClientError>> {
+        Box::pin(async move {
+            let federation_version = FederationVersion::LatestFedTwo;
+            // prefer an installed plugin, so publishing works offline, and only
+            // download one if there isn't any yet
+            let installer =
+                InstallSupergraph::new(federation_version.clone(), self.client_config.clone());
+            let binary = match installer
+                .install(None, LicenseAccepter::from_env(), true)
+                .await
+            {
+                Ok(binary) => binary,
+                Err(_) => installer
+                    .install(None, LicenseAccepter::from_env(), false)
+                    .await
+                    .map_err(|err| RoverClientError::AdhocError {
+                        msg: format!(
+                            "Could not install the supergraph plugin to compose with: {err}"
+                        ),
+                    })?,
+            };
+
+            let subgraphs = subgraphs
+                .into_iter()
+                .map(|subgraph| (subgraph.name().clone(), subgraph.into()))
+                .collect();
+            let supergraph_config = SupergraphConfig::new(subgraphs, Some(federation_version));
+
+            let dir = tempfile::tempdir().map_err(|err| RoverClientError::AdhocError {
+                msg: err.to_string(),
+            })?;
+            let supergraph_config_path =
+                Utf8PathBuf::from_path_buf(dir.path().join("supergraph.yaml"))
+                    .expect("Unable to parse path");
+            let yaml = serde_yaml::to_string(&supergraph_config).map_err(|err| {
+                RoverClientError::AdhocError {
+                    msg: err.to_string(),
+                }
+            })?;
+            Fs::write_file(&supergraph_config_path, yaml).map_err(|err| {
+                RoverClientError::AdhocError {
+                    msg: err.to_string(),
+                }
+            })?;
+
+            match binary
+                .compose(&TokioCommand::default(), supergraph_config_path)
+                .await
+            {
+                Ok(success) => Ok(Ok(success.supergraph_sdl)),
+                Err(CompositionError::Build { source, .. }) => Ok(Err(source)),
+                Err(err) => Err(RoverClientError::AdhocError {
+                    msg: err.to_string(),
+                }),
+            }
+        })
+    }
+}
@@ -32,6 +32,7 @@ use crate::{
 };
 
 pub mod events;
+pub mod local_registry;
 pub mod pipeline;
 pub mod runner;
 pub mod supergraph;
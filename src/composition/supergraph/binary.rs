@@ -36,6 +36,7 @@ pub struct SupergraphBinary {
 }
 
 impl SupergraphBinary {
+    #[tracing::instrument(name = "composition", skip_all, fields(federation_version = %self.version))]
     pub async fn compose(
         &self,
         exec_impl: &impl ExecCommand,
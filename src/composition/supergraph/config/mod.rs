@@ -9,4 +9,5 @@ pub mod lazy;
 pub mod resolver;
 #[cfg(test)]
 pub(crate) mod scenario;
+pub mod sdl;
 pub mod unresolved;
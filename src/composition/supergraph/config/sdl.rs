@@ -0,0 +1,71 @@
+//! Resolves every subgraph in a supergraph config to its SDL, for commands that work with the
+//! subgraphs one at a time rather than composing them
+
+use std::{collections::BTreeMap, env::current_dir};
+
+use camino::Utf8PathBuf;
+use tower::ServiceExt;
+
+use super::{
+    error::ResolveSubgraphError,
+    full::{FullyResolvedSubgraph, introspect::MakeResolveIntrospectSubgraph},
+    resolver::{
+        SupergraphConfigResolver, fetch_remote_subgraph::MakeFetchRemoteSubgraph,
+        fetch_remote_subgraphs::MakeFetchRemoteSubgraphs,
+    },
+};
+use crate::{
+    RoverResult,
+    options::ProfileOpt,
+    utils::{client::StudioClientConfig, parsers::FileDescriptorType},
+};
+
+/// Resolves every subgraph in `supergraph_config` with the same resolver `supergraph compose`
+/// uses. Subgraphs that can't be resolved are returned by name alongside the ones that could,
+/// rather than failing the whole run.
+pub(crate) async fn resolve_subgraph_sdls(
+    client_config: &StudioClientConfig,
+    profile: &ProfileOpt,
+    supergraph_config: &FileDescriptorType,
+) -> RoverResult<(
+    Vec<FullyResolvedSubgraph>,
+    BTreeMap<String, ResolveSubgraphError>,
+)> {
+    let fetch_remote_subgraphs_factory = MakeFetchRemoteSubgraphs::builder()
+        .studio_client_config(client_config.clone())
+        .profile(profile.clone())
+        .build();
+    let fetch_remote_subgraph_factory = MakeFetchRemoteSubgraph::builder()
+        .studio_client_config(client_config.clone())
+        .profile(profile.clone())
+        .build()
+        .boxed_clone();
+    let resolve_introspect_subgraph_factory =
+        MakeResolveIntrospectSubgraph::new(client_config.service()?).boxed_clone();
+
+    let current_dir = Utf8PathBuf::try_from(current_dir()?)?;
+    let supergraph_root = match supergraph_config {
+        FileDescriptorType::File(file) => current_dir
+            .join(file)
+            .parent()
+            .map(|parent| parent.to_path_buf())
+            .unwrap_or(current_dir),
+        FileDescriptorType::Stdin => current_dir,
+    };
+
+    let resolver =
+        SupergraphConfigResolver::load_remote_subgraphs(fetch_remote_subgraphs_factory, None)
+            .await?
+            .load_from_file_descriptor(&mut std::io::stdin(), Some(supergraph_config))?
+            .skip_default_subgraph();
+    let (supergraph_config, errors) = resolver
+        .fully_resolve_subgraphs(
+            resolve_introspect_subgraph_factory,
+            fetch_remote_subgraph_factory,
+            &supergraph_root,
+        )
+        .await?;
+
+    let subgraphs = supergraph_config.subgraphs().values().cloned().collect();
+    Ok((subgraphs, errors))
+}
@@ -287,6 +287,24 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                     )),
                     None,
                 ),
+                RoverClientError::LaunchTimeoutError { graph_ref, launch_id } => (
+                    Some(RoverErrorSuggestion::Adhoc(format!(
+                        "The launch is still running. Run `rover launch status {graph_ref} {launch_id} --wait` to keep following it, or pass a larger `--launch-timeout`."
+                    ))),
+                    None,
+                ),
+                RoverClientError::ProposalNotFound { .. } => (
+                    Some(RoverErrorSuggestion::Adhoc(
+                        "Check the proposal ID, and run `rover proposal list <GRAPH_REF>` to see the proposals created from a variant.".to_string(),
+                    )),
+                    None,
+                ),
+                RoverClientError::OperationCollectionNotFound { .. } => (
+                    Some(RoverErrorSuggestion::Adhoc(
+                        "Check the collection ID. You can copy it from the collection's menu in Studio's Explorer, and make sure your API key can see the collection.".to_string(),
+                    )),
+                    None,
+                ),
                 RoverClientError::UnknownCheckWorkflowStatus => {
                     (Some(RoverErrorSuggestion::SubmitIssue), None)
                 }
@@ -332,6 +350,12 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                 RoverClientError::OrganizationIDNotFound { .. } => {
                     (Some(RoverErrorSuggestion::CheckGraphNameAndAuth), None)
                 }
+                RoverClientError::UnsupportedByLocalRegistry { .. } => (
+                    Some(RoverErrorSuggestion::Adhoc(
+                        "Unset `APOLLO_REGISTRY_URL`, or run `rover config registry --studio` for this profile, to run this command against GraphOS.".to_string(),
+                    )),
+                    None,
+                ),
                 RoverClientError::ServiceReady(_) => (None, None),
                 RoverClientError::Service { .. } => (None, None),
                 RoverClientError::InvalidTimestamp(_) => (None, None),
@@ -27,7 +27,7 @@ pub struct CheckConfigOpts {
     pub background: bool,
 }
 
-fn parse_query_count_threshold(threshold: &str) -> Result<i64, io::Error> {
+pub(crate) fn parse_query_count_threshold(threshold: &str) -> Result<i64, io::Error> {
     let threshold = threshold
         .parse::<i64>()
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
@@ -1,5 +1,7 @@
 use camino::{Utf8Path, Utf8PathBuf};
 use clap::Parser;
+#[cfg(feature = "composition-js")]
+use rover_std::FileMatcher;
 use rover_std::FileSearch;
 use serde::Serialize;
 
@@ -48,17 +50,26 @@ impl FileDiscoveryOpt {
     /// Finds files under the resolved root matching `include`/`exclude`, restricted to
     /// `extensions` when no explicit include patterns are given.
     pub fn find(&self, extensions: &[&str]) -> RoverResult<Vec<Utf8PathBuf>> {
+        self.search()?.find(extensions).map_err(RoverError::from)
+    }
+
+    /// Compiles the patterns [`FileDiscoveryOpt::find`] uses, to check individual paths
+    /// against without walking the root directory.
+    #[cfg(feature = "composition-js")]
+    pub fn matcher(&self, extensions: &[&str]) -> RoverResult<FileMatcher> {
+        self.search()?.matcher(extensions).map_err(RoverError::from)
+    }
+
+    fn search(&self) -> RoverResult<FileSearch> {
         let canonical_root = self.canonical_root()?;
         let includes = normalize_patterns(&self.include, &canonical_root);
         let excludes = normalize_patterns(&self.exclude, &canonical_root);
 
-        FileSearch::builder()
+        Ok(FileSearch::builder()
             .root(canonical_root)
             .includes(includes)
             .excludes(excludes)
-            .build()
-            .find(extensions)
-            .map_err(RoverError::from)
+            .build())
     }
 }
 
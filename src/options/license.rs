@@ -17,6 +17,17 @@ pub struct LicenseAccepter {
 }
 
 impl LicenseAccepter {
+    /// Reads acceptance from `APOLLO_ELV2_LICENSE`, for commands that install
+    /// plugins without taking an `--elv2-license` argument.
+    #[cfg(feature = "composition-js")]
+    pub(crate) fn from_env() -> LicenseAccepter {
+        LicenseAccepter {
+            elv2_license_accepted: std::env::var("APOLLO_ELV2_LICENSE")
+                .ok()
+                .and_then(|value| license_accept(&value).ok()),
+        }
+    }
+
     pub fn require_elv2_license(&self, client_config: &StudioClientConfig) -> RoverResult<()> {
         let did_accept = self.previously_accepted(client_config)?;
         if did_accept || self.prompt_accept(client_config)? {
@@ -11,6 +11,8 @@ pub(crate) static DEFAULT_AUTHORIZATION_URL: LazyLock<Url> =
     LazyLock::new(|| url!("https://auth.apollographql.com/oauth2/authorize"));
 pub(crate) static DEFAULT_TOKEN_URL: LazyLock<Url> =
     LazyLock::new(|| url!("https://auth.apollographql.com/oauth2/token"));
+pub(crate) static DEFAULT_DEVICE_AUTHORIZATION_URL: LazyLock<Url> =
+    LazyLock::new(|| url!("https://auth.apollographql.com/oauth2/device_authorization"));
 pub(crate) static DEFAULT_REVOCATION_URL: LazyLock<Url> =
     LazyLock::new(|| url!("https://auth.apollographql.com/oauth2/revoke"));
 pub(crate) static DEFAULT_WHOAMI_URL: LazyLock<Url> =
@@ -48,6 +50,15 @@ pub struct OauthOpts {
     )]
     pub(crate) token_url: Url,
 
+    /// Override the OAuth device authorization endpoint `rover auth login
+    /// --device` uses.
+    #[arg(
+        long = "oauth-device-authorization-url",
+        global = true,
+        default_value = DEFAULT_DEVICE_AUTHORIZATION_URL.as_str()
+    )]
+    pub(crate) device_authorization_url: Url,
+
     /// Override the OAuth whoami/userinfo endpoint `rover auth whoami` uses.
     #[arg(
         long = "oauth-whoami-url",
@@ -1,8 +1,13 @@
+use std::num::NonZeroUsize;
+
 use camino::Utf8PathBuf;
 use clap::{self, Parser};
+use rover_studio::types::GraphRef;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::utils::parsers::FileDescriptorType;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Parser)]
 pub struct SubgraphOpt {
     /// The name of the subgraph.
@@ -53,3 +58,29 @@ pub struct OptionalSubgraphOpts {
     #[serde(skip_serializing)]
     pub subgraph_retries: u64,
 }
+
+#[derive(Debug, Serialize, Parser)]
+pub struct SubgraphBatchOpts {
+    /// A supergraph config file to take the subgraphs from instead of `--name`
+    /// and `--schema`. Every subgraph in it is run against the graph ref.
+    /// You can pass `-` to use stdin instead of a file.
+    #[arg(long = "supergraph-config", value_name = "SUPERGRAPH_CONFIG_PATH")]
+    #[serde(skip_serializing)]
+    pub supergraph_config: Option<FileDescriptorType>,
+
+    /// <NAME>@<VARIANT> of graph in Apollo Studio, as an alternative to the
+    /// positional GRAPH_REF when `--supergraph-config` is used.
+    #[arg(
+        long = "graph-ref",
+        id = "batch_graph_ref",
+        value_name = "GRAPH_REF",
+        requires = "supergraph_config",
+        conflicts_with = "graph_ref"
+    )]
+    #[serde(skip_serializing)]
+    pub graph_ref: Option<GraphRef>,
+
+    /// The maximum number of subgraphs from the supergraph config to run at once
+    #[arg(long, value_name = "COUNT", default_value = "4")]
+    pub max_concurrency: NonZeroUsize,
+}
@@ -1,17 +1,21 @@
 use core::fmt;
-use std::{io, str::FromStr, time::Duration};
+use std::{io, path::PathBuf, str::FromStr, time::Duration};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use derive_getters::Getters;
 use houston as config;
 use reqwest::Client;
 use rover_client::blocking::StudioClient;
-use rover_http::{HttpService, ReqwestService};
+#[cfg(feature = "composition-js")]
+use rover_client::local_registry::LocalRegistry;
+use rover_http::{HttpService, NetworkConfig, ReqwestService, retry::RetryConfig};
 use rover_studio::service::HttpStudioServiceLayer;
 use serde::Serialize;
 use tower::{ServiceBuilder, ServiceExt};
 use url::Url;
 
+#[cfg(feature = "composition-js")]
+use crate::composition::local_registry::PluginComposer;
 use crate::{PKG_NAME, PKG_VERSION, options::ProfileOpt};
 
 /// the Apollo graph registry's production API endpoint
@@ -22,13 +26,16 @@ const STUDIO_PROD_API_ENDPOINT: &str = "https://api.apollographql.com/graphql";
 /// period for the actual request.
 const DOWNLOAD_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
 const DOWNLOAD_REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+/// How long to keep retrying a plugin download that keeps failing.
+pub(crate) const DOWNLOAD_RETRY_PERIOD: Duration = Duration::from_secs(600);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ClientBuilder {
     accept_invalid_certs: bool,
     accept_invalid_hostnames: bool,
     timeout: Option<std::time::Duration>,
     connect_timeout: Option<std::time::Duration>,
+    network: NetworkConfig,
 }
 
 impl Default for ClientBuilder {
@@ -38,56 +45,68 @@ impl Default for ClientBuilder {
 }
 
 impl ClientBuilder {
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             accept_invalid_certs: false,
             accept_invalid_hostnames: false,
             timeout: None,
             connect_timeout: None,
+            network: NetworkConfig::default(),
         }
     }
 
-    pub const fn accept_invalid_certs(self, value: bool) -> Self {
+    pub fn accept_invalid_certs(self, value: bool) -> Self {
         Self {
             accept_invalid_certs: value,
             ..self
         }
     }
 
-    pub const fn accept_invalid_hostnames(self, value: bool) -> Self {
+    pub fn accept_invalid_hostnames(self, value: bool) -> Self {
         Self {
             accept_invalid_hostnames: value,
             ..self
         }
     }
 
-    pub const fn with_timeout(self, timeout: std::time::Duration) -> Self {
+    pub fn with_timeout(self, timeout: std::time::Duration) -> Self {
         Self {
             timeout: Some(timeout),
             ..self
         }
     }
 
-    const fn clear_timeout(self) -> Self {
+    /// Trust, client identity and proxy settings to build the client with.
+    pub fn with_network(self, network: NetworkConfig) -> Self {
+        Self { network, ..self }
+    }
+
+    /// The trust, client identity and proxy settings the client is built with.
+    pub const fn network(&self) -> &NetworkConfig {
+        &self.network
+    }
+
+    fn clear_timeout(self) -> Self {
         Self {
             timeout: None,
             ..self
         }
     }
 
-    const fn with_connect_timeout(self, connect_timeout: std::time::Duration) -> Self {
+    fn with_connect_timeout(self, connect_timeout: std::time::Duration) -> Self {
         Self {
             connect_timeout: Some(connect_timeout),
             ..self
         }
     }
 
-    pub(crate) fn build(self) -> Result<Client> {
+    pub(crate) fn build(&self) -> Result<Client> {
         let mut builder = Client::builder()
             .gzip(true)
             .brotli(true)
             .danger_accept_invalid_certs(self.accept_invalid_certs)
             .danger_accept_invalid_hostnames(self.accept_invalid_hostnames);
+        builder = self.network.apply(builder)?;
 
         if let Some(timeout) = self.timeout {
             builder = builder.timeout(timeout);
@@ -160,6 +179,9 @@ pub struct StudioClientConfig {
     client: Option<Client>,
     client_timeout: ClientTimeout,
     download_timeout: Duration,
+    studio_retry: RetryConfig,
+    introspection_retry: RetryConfig,
+    download_retry: RetryConfig,
 }
 
 impl StudioClientConfig {
@@ -185,9 +207,27 @@ impl StudioClientConfig {
             client: None,
             client_timeout,
             download_timeout: DOWNLOAD_REQUEST_TIMEOUT,
+            studio_retry: RetryConfig::new(client_timeout.get_duration()),
+            introspection_retry: RetryConfig::new(client_timeout.get_duration()),
+            download_retry: RetryConfig::new(DOWNLOAD_RETRY_PERIOD),
         }
     }
 
+    /// How requests to Studio, introspection requests and plugin downloads are
+    /// each retried, in place of retrying each for up to the client timeout
+    /// (or [`DOWNLOAD_RETRY_PERIOD`]) with the default delays.
+    pub const fn with_retry_configs(
+        mut self,
+        studio: RetryConfig,
+        introspection: RetryConfig,
+        downloads: RetryConfig,
+    ) -> Self {
+        self.studio_retry = studio;
+        self.introspection_retry = introspection;
+        self.download_retry = downloads;
+        self
+    }
+
     pub const fn with_download_timeout(mut self, download_timeout: Duration) -> Self {
         self.download_timeout = download_timeout;
         self
@@ -216,6 +256,7 @@ impl StudioClientConfig {
     pub fn download_service(&self) -> Result<HttpService> {
         let client = self
             .client_builder
+            .clone()
             .clear_timeout()
             .with_timeout(self.download_timeout)
             .with_connect_timeout(DOWNLOAD_CONNECT_TIMEOUT)
@@ -227,7 +268,24 @@ impl StudioClientConfig {
     }
 
     pub fn get_authenticated_client(&self, profile_opt: &ProfileOpt) -> Result<StudioClient> {
-        let credential = config::Profile::get_credential(&profile_opt.profile_name, &self.config)?;
+        if let Some(root) = self.local_registry_root(profile_opt)? {
+            #[cfg(feature = "composition-js")]
+            {
+                let registry = LocalRegistry::new(root, PluginComposer::new(self.clone()));
+                return Ok(StudioClient::local(
+                    registry,
+                    &self.version,
+                    self.get_reqwest_client()?,
+                ));
+            }
+            #[cfg(not(feature = "composition-js"))]
+            return Err(anyhow!(
+                "The local registry at {} composes with the supergraph plugin, which this build of Rover doesn't include",
+                root.display()
+            ));
+        }
+        let credential =
+            config::CredentialSource::profile(&profile_opt.profile_name, &self.config)?;
         Ok(StudioClient::new(
             credential,
             &self.uri,
@@ -235,12 +293,35 @@ impl StudioClientConfig {
             self.is_sudo,
             self.get_reqwest_client()?,
             self.client_timeout.get_duration(),
-        ))
+        )
+        .with_retry_config(self.studio_retry.clone()))
+    }
+
+    /// The directory of the local graph registry to use instead of Studio, if
+    /// `APOLLO_REGISTRY_URL` or the profile's registry (`rover config registry`)
+    /// is a `file://` URL.
+    pub(crate) fn local_registry_root(&self, profile_opt: &ProfileOpt) -> Result<Option<PathBuf>> {
+        let registry_url = if self.uri == STUDIO_PROD_API_ENDPOINT {
+            match config::Profile::get_registry_url(&profile_opt.profile_name, &self.config)? {
+                Some(registry_url) => registry_url,
+                None => return Ok(None),
+            }
+        } else {
+            self.uri.clone()
+        };
+        match Url::parse(&registry_url) {
+            Ok(url) if url.scheme() == "file" => url
+                .to_file_path()
+                .map(Some)
+                .map_err(|_| anyhow!("{registry_url} is not a valid file path")),
+            _ => Ok(None),
+        }
     }
 
     pub fn authenticated_service(&self, profile_opt: &ProfileOpt) -> Result<HttpService> {
         let client = self.get_reqwest_client()?;
-        let credential = config::Profile::get_credential(&profile_opt.profile_name, &self.config)?;
+        let credential =
+            config::CredentialSource::profile(&profile_opt.profile_name, &self.config)?;
         let service = ServiceBuilder::new()
             .layer(HttpStudioServiceLayer::new(
                 Url::from_str(&self.uri)?,
@@ -256,10 +337,6 @@ impl StudioClientConfig {
     pub const fn accept_invalid_certs(&self) -> bool {
         self.client_builder.accept_invalid_certs
     }
-
-    pub const fn retry_period(&self) -> Duration {
-        self.client_timeout.get_duration()
-    }
 }
 
 #[cfg(test)]
@@ -284,6 +361,31 @@ mod tests {
         assert_eq!(builder.connect_timeout, Some(Duration::from_secs(5)));
     }
 
+    /// Plugin downloads rebuild the client with their own timeouts, but must
+    /// keep the trust and proxy settings every other request is made with.
+    #[test]
+    fn download_builder_keeps_the_network_settings() {
+        let network = rover_http::NetworkConfig::builder()
+            .proxy("http://proxy.example.com:3128")
+            .build();
+        let builder = ClientBuilder::new()
+            .with_network(network.clone())
+            .clear_timeout()
+            .with_connect_timeout(Duration::from_secs(5));
+        assert_eq!(builder.network, network);
+    }
+
+    #[test]
+    fn build_fails_on_an_unreadable_ca_certificate() {
+        let builder = ClientBuilder::new().with_network(
+            rover_http::NetworkConfig::builder()
+                .ca_certificate("/definitely/not/a/ca.pem")
+                .build(),
+        );
+        let error = builder.build().unwrap_err();
+        assert!(error.to_string().contains("/definitely/not/a/ca.pem"));
+    }
+
     fn test_client_config() -> super::StudioClientConfig {
         super::StudioClientConfig::new(
             None,
@@ -316,4 +418,21 @@ mod tests {
             assert_eq!(*config.download_timeout(), Duration::from_secs(secs));
         }
     }
+
+    /// Studio requests and introspection are retried for as long as the
+    /// client timeout, but a plugin download gets its own, longer budget.
+    #[test]
+    fn each_kind_of_request_has_its_own_retry_budget() {
+        let config = test_client_config();
+        let client_timeout = super::ClientTimeout::default().get_duration();
+        assert_eq!(*config.studio_retry().max_elapsed_time(), client_timeout);
+        assert_eq!(
+            *config.introspection_retry().max_elapsed_time(),
+            client_timeout
+        );
+        assert_eq!(
+            *config.download_retry().max_elapsed_time(),
+            super::DOWNLOAD_RETRY_PERIOD
+        );
+    }
 }
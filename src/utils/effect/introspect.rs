@@ -47,7 +47,7 @@ impl IntrospectSubgraph for StudioClientConfig {
                 headers,
                 endpoint: endpoint.clone(),
                 should_retry: false,
-                retry_period: self.retry_period(),
+                retry: self.introspection_retry().clone(),
             },
             &client,
         )
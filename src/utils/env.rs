@@ -99,8 +99,12 @@ pub enum RoverEnvKey {
     Home,
     Key,
     RegistryUrl,
+    RoverHttpRecord,
+    RoverHttpReplay,
     TelemetryUrl,
+    TelemetryOtlpUrl,
     TelemetryDisabled,
+    TracingOtlpUrl,
     VcsRemoteUrl,
     VcsBranch,
     VcsCommit,
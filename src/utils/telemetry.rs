@@ -1,14 +1,21 @@
 use std::collections::HashMap;
 
+use anyhow::anyhow;
 use camino::Utf8PathBuf;
+use houston::Config;
 use reqwest::Client;
-use sputnik::{Command, Report, SputnikError};
+use sputnik::{Command, Report, Sink, SputnikError};
 use url::Url;
 
 use crate::{PKG_NAME, PKG_VERSION, cli::Rover, utils::env::RoverEnvKey};
 
 const TELEMETRY_URL: &str = "https://rover.apollo.dev/telemetry";
 
+/// Where the most recent sessions are kept, for `rover telemetry show`.
+pub fn history_path(config: &Config) -> Utf8PathBuf {
+    config.home.join("telemetry_history.jsonl")
+}
+
 fn get_command_from_args(raw_arguments: &mut serde_json::Value) -> Command {
     let mut commands = Vec::new();
     let mut arguments = HashMap::new();
@@ -100,6 +107,20 @@ impl Report for Rover {
         Ok(Url::parse(&url)?)
     }
 
+    fn sink(&self) -> Result<Sink, SputnikError> {
+        if let Some(otlp_url) = self.get_env_var(RoverEnvKey::TelemetryOtlpUrl)? {
+            return Ok(Sink::Otlp(Url::parse(&otlp_url)?));
+        }
+        let endpoint = self.endpoint()?;
+        if endpoint.scheme() == "file" {
+            let path = endpoint
+                .to_file_path()
+                .map_err(|_| anyhow!("{endpoint} is not a valid file path"))?;
+            return Ok(Sink::JsonLines(Utf8PathBuf::try_from(path)?));
+        }
+        Ok(Sink::Http(endpoint))
+    }
+
     fn tool_name(&self) -> String {
         PKG_NAME.to_string()
     }
@@ -115,6 +136,13 @@ impl Report for Rover {
         Ok(config.home.join("machine.txt"))
     }
 
+    fn history_path(&self) -> Result<Utf8PathBuf, SputnikError> {
+        let config = self
+            .get_rover_config()
+            .map_err(|_| SputnikError::ConfigError)?;
+        Ok(history_path(&config))
+    }
+
     fn client(&self) -> anyhow::Result<Client, SputnikError> {
         self.get_reqwest_client().map_err(SputnikError::from)
     }
@@ -124,10 +152,12 @@ impl Report for Rover {
 mod tests {
     use std::collections::HashMap;
 
+    use camino::Utf8PathBuf;
     use clap::Parser;
     use serde_json::json;
-    use sputnik::Command;
+    use sputnik::{Command, Sink};
 
+    use super::TELEMETRY_URL;
     use crate::{
         PKG_NAME,
         cli::Rover,
@@ -209,4 +239,42 @@ mod tests {
         let is_telemetry_enabled = rover.is_telemetry_enabled().unwrap();
         assert_eq!(is_telemetry_enabled, expect_enabled);
     }
+
+    #[test]
+    fn it_reports_to_the_telemetry_url_by_default() {
+        let args = vec![PKG_NAME, "config", "list"];
+        let rover = Rover::parse_from(args);
+        let sink = rover.sink().expect("could not determine telemetry sink");
+
+        assert_eq!(sink, Sink::Http(TELEMETRY_URL.parse().unwrap()));
+    }
+
+    #[test]
+    fn it_appends_to_a_file_telemetry_url() {
+        let args = vec![PKG_NAME, "config", "list"];
+        let mut rover = Rover::parse_from(args);
+        let path = std::env::temp_dir().join("rover-usage.jsonl");
+        let url = url::Url::from_file_path(&path).unwrap();
+        rover
+            .insert_env_var(RoverEnvKey::TelemetryUrl, url.as_str())
+            .unwrap();
+        let sink = rover.sink().expect("could not determine telemetry sink");
+
+        assert_eq!(sink, Sink::JsonLines(Utf8PathBuf::try_from(path).unwrap()));
+    }
+
+    #[test]
+    fn it_prefers_an_otlp_collector() {
+        let args = vec![PKG_NAME, "config", "list"];
+        let mut rover = Rover::parse_from(args);
+        rover
+            .insert_env_var(RoverEnvKey::TelemetryUrl, "https://example.com/telemetry")
+            .unwrap();
+        rover
+            .insert_env_var(RoverEnvKey::TelemetryOtlpUrl, "http://localhost:4318")
+            .unwrap();
+        let sink = rover.sink().expect("could not determine telemetry sink");
+
+        assert_eq!(sink, Sink::Otlp("http://localhost:4318".parse().unwrap()));
+    }
 }
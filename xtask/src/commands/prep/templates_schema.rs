@@ -4,6 +4,7 @@ use anyhow::{anyhow, Result};
 use camino::Utf8PathBuf;
 use reqwest::Client;
 use rover_client::operations::graph::introspect::{self, GraphIntrospectInput};
+use rover_http::retry::RetryConfig;
 use rover_std::Fs;
 use url::Url;
 
@@ -36,7 +37,7 @@ async fn introspect() -> Result<String> {
             headers: HashMap::new(),
             endpoint,
             should_retry: false,
-            retry_period: Duration::from_secs(10),
+            retry: RetryConfig::new(Duration::from_secs(10)),
             use_legacy_introspection_query: false,
         },
         &Client::new(),